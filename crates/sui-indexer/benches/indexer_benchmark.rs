@@ -12,18 +12,26 @@ use criterion::Criterion;
 use prometheus::Registry;
 use tokio::runtime::Runtime;
 
+use sui_indexer::handlers::checkpoint_handler::index_transactions;
+use sui_indexer::handlers::pipelines::Pipelines;
 use sui_indexer::metrics::IndexerMetrics;
 use sui_indexer::models::checkpoints::Checkpoint;
 use sui_indexer::models::transactions::Transaction;
+use sui_indexer::models::validator_participation::CheckpointSigners;
 use sui_indexer::new_pg_connection_pool;
 use sui_indexer::store::{IndexerStore, PgIndexerStore, TemporaryCheckpointStore};
 use sui_indexer::utils::reset_database;
 use sui_json_rpc_types::CheckpointId;
-use sui_types::base_types::{ObjectDigest, ObjectID, SequenceNumber, SuiAddress};
-use sui_types::crypto::AggregateAuthoritySignature;
+use sui_types::base_types::{
+    random_object_ref, ObjectDigest, ObjectID, SequenceNumber, SuiAddress,
+};
+use sui_types::crypto::{get_key_pair, AccountKeyPair, AggregateAuthoritySignature};
 use sui_types::digests::TransactionDigest;
-use sui_types::messages_checkpoint::CheckpointDigest;
+use sui_types::effects::TransactionEffects;
+use sui_types::gas::GasCostSummary;
+use sui_types::messages_checkpoint::{CheckpointContents, CheckpointDigest, CheckpointSummary};
 use sui_types::transaction::{TransactionData, TEST_ONLY_GAS_UNIT_FOR_TRANSFER};
+use sui_types::utils::to_sender_signed_transaction;
 
 fn indexer_benchmark(c: &mut Criterion) {
     let pg_host = env::var("POSTGRES_HOST").unwrap_or_else(|_| "localhost".into());
@@ -52,6 +60,42 @@ fn indexer_benchmark(c: &mut Criterion) {
     });
 }
 
+// Rows indexed from a checkpoint of 1000 transfers, run it on two revisions to compare
+// the cost of indexing the transactions of a checkpoint.
+fn index_transactions_benchmark(c: &mut Criterion) {
+    let gas_price = 1000;
+    let (sender, keypair): (_, AccountKeyPair) = get_key_pair();
+    let transactions = (0..1000)
+        .map(|_| {
+            let data = TransactionData::new_transfer(
+                SuiAddress::random_for_testing_only(),
+                random_object_ref(),
+                sender,
+                random_object_ref(),
+                gas_price * TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+                gas_price,
+            );
+            let tx = to_sender_signed_transaction(data, &keypair);
+            (tx, TransactionEffects::default(), None)
+        })
+        .collect::<Vec<_>>();
+    let contents = CheckpointContents::new_with_causally_ordered_transactions(std::iter::empty());
+    let summary = CheckpointSummary::new(
+        0,
+        1,
+        transactions.len() as u64,
+        &contents,
+        None,
+        GasCostSummary::default(),
+        None,
+        Utc::now().timestamp_millis() as u64,
+    );
+
+    c.bench_function("index_transactions", |b| {
+        b.iter(|| index_transactions(&summary, &transactions, &Pipelines::default()))
+    });
+}
+
 fn create_checkpoint(sequence_number: i64) -> TemporaryCheckpointStore {
     TemporaryCheckpointStore {
         checkpoint: Checkpoint {
@@ -72,15 +116,25 @@ fn create_checkpoint(sequence_number: i64) -> TemporaryCheckpointStore {
             total_successful_transactions: 1000,
             network_total_transactions: 0,
             timestamp_ms: Utc::now().timestamp_millis(),
+            checkpoint_commitments: vec![],
         },
         transactions: (1..1000)
             .map(|_| create_transaction(sequence_number))
             .collect(),
+        transactions_json: vec![],
+        checkpoint_tx_digests: vec![],
         events: vec![],
         input_objects: vec![],
         changed_objects: vec![],
         move_calls: vec![],
+        tx_commands: vec![],
+        tx_calls: vec![],
         recipients: vec![],
+        tx_dependencies: vec![],
+        transfers: vec![],
+        ownership_transitions: vec![],
+        signers: CheckpointSigners::default(),
+        proof: None,
     }
 }
 
@@ -123,12 +177,23 @@ fn create_transaction(sequence_number: i64) -> Transaction {
         raw_transaction: bcs::to_bytes(&tx).unwrap(),
         transaction_effects_content: "".to_string(),
         confirmed_local_execution: None,
+        gas_owner: None,
+        gas_payment_object_ids: vec![],
+        gas_payment_object_versions: vec![],
+        gas_payment_object_digests: vec![],
+        expiration_epoch: None,
+        sponsored: false,
+        failure_kind: None,
+        failure_command: None,
+        abort_package: None,
+        abort_module: None,
+        abort_code: None,
     }
 }
 
 criterion_group! {
     name = benches;
     config = Criterion::default().sample_size(50).measurement_time(Duration::from_secs(10));
-    targets = indexer_benchmark
+    targets = indexer_benchmark, index_transactions_benchmark
 }
 criterion_main!(benches);
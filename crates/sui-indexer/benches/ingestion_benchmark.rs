@@ -0,0 +1,130 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Replays checkpoints through the checkpoint and objects handlers against a Postgres store
+// with DB commits skipped, so that only indexing itself is measured. Reads made while
+// indexing, e.g. of the modules of earlier packages, are served by the store.
+// Checkpoints are read from `CHECKPOINT_FIXTURES_DIR`, `benches/fixtures/checkpoints` by
+// default, as BCS serialized `CheckpointData` files named `<sequence number>.chk`, e.g.
+// written by the `dump_checkpoints` binary from a mainnet full node.
+
+#[macro_use]
+extern crate criterion;
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use criterion::{Criterion, Throughput};
+use prometheus::Registry;
+use tokio::runtime::Runtime;
+
+use sui_indexer::framework::interface::Handler;
+use sui_indexer::handlers::checkpoint_handler::new_handlers;
+use sui_indexer::handlers::pipelines::Pipelines;
+use sui_indexer::metrics::IndexerMetrics;
+use sui_indexer::store::PgIndexerStore;
+use sui_indexer::utils::reset_database;
+use sui_indexer::{new_pg_connection_pool, IndexerConfig};
+use sui_rest_api::CheckpointData;
+
+fn ingestion_benchmark(c: &mut Criterion) {
+    let fixtures_dir = env::var("CHECKPOINT_FIXTURES_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| {
+            PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("benches/fixtures/checkpoints")
+        });
+    let checkpoints = load_checkpoints(&fixtures_dir);
+    // an empty run would report nothing while looking like a pass
+    assert!(
+        !checkpoints.is_empty(),
+        "No checkpoint fixtures found in {:?}, write some with the dump_checkpoints binary \
+         or point CHECKPOINT_FIXTURES_DIR at a directory of them",
+        fixtures_dir
+    );
+
+    let pg_host = env::var("POSTGRES_HOST").unwrap_or_else(|_| "localhost".into());
+    let pg_port = env::var("POSTGRES_PORT").unwrap_or_else(|_| "32770".into());
+    let pw = env::var("POSTGRES_PASSWORD").unwrap_or_else(|_| "postgrespw".into());
+    let db_url = format!("postgres://postgres:{pw}@{pg_host}:{pg_port}");
+
+    let rt = Runtime::new().unwrap();
+    let registry = Registry::default();
+    mysten_metrics::init_metrics(&registry);
+    let metrics = IndexerMetrics::new(&registry);
+    let config = IndexerConfig {
+        skip_db_commit: true,
+        ..Default::default()
+    };
+    // commit tasks are spawned on the runtime and drain the handlers' channels
+    let (mut checkpoint_processor, mut objects_processor) = rt.block_on(async {
+        let blocking_cp = new_pg_connection_pool(&db_url).unwrap();
+        reset_database(&mut blocking_cp.get().unwrap(), true).unwrap();
+        new_handlers(
+            PgIndexerStore::new(blocking_cp, metrics.clone()),
+            metrics.clone(),
+            &config,
+            Pipelines::default(),
+        )
+        .unwrap()
+    });
+
+    let mut group = c.benchmark_group("ingestion");
+    group.throughput(Throughput::Elements(checkpoints.len() as u64));
+    group.bench_function("index_checkpoints", |b| {
+        b.iter(|| {
+            rt.block_on(async {
+                for checkpoint in &checkpoints {
+                    checkpoint_processor
+                        .process_checkpoint(checkpoint)
+                        .await
+                        .unwrap();
+                    objects_processor
+                        .process_checkpoint(checkpoint)
+                        .await
+                        .unwrap();
+                }
+            })
+        })
+    });
+    group.finish();
+
+    for (stage, latency) in [
+        ("checkpoint indexing", &metrics.checkpoint_index_latency),
+        (
+            "objects indexing",
+            &metrics.checkpoint_objects_index_latency,
+        ),
+    ] {
+        let count = latency.get_sample_count();
+        println!(
+            "{stage}: {:.3} ms on average over {count} samples",
+            latency.get_sample_sum() * 1000.0 / count.max(1) as f64
+        );
+    }
+}
+
+fn load_checkpoints(dir: &Path) -> Vec<Arc<CheckpointData>> {
+    let entries = fs::read_dir(dir)
+        .unwrap_or_else(|e| panic!("Failed reading checkpoint fixtures dir {:?}: {e}", dir));
+    let mut checkpoints = entries
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().map_or(false, |ext| ext == "chk"))
+        .map(|path| {
+            let checkpoint = bcs::from_bytes::<CheckpointData>(&fs::read(&path).unwrap())
+                .unwrap_or_else(|e| panic!("Failed reading checkpoint fixture {:?}: {e}", path));
+            Arc::new(checkpoint)
+        })
+        .collect::<Vec<_>>();
+    checkpoints.sort_by_key(|checkpoint| *checkpoint.checkpoint_summary.sequence_number());
+    checkpoints
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10).measurement_time(Duration::from_secs(30));
+    targets = ingestion_benchmark
+}
+criterion_main!(benches);
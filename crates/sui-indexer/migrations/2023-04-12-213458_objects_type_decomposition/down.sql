@@ -0,0 +1,5 @@
+DROP INDEX IF EXISTS objects_type_decomposition;
+ALTER TABLE objects
+    DROP COLUMN IF EXISTS object_type_package,
+    DROP COLUMN IF EXISTS object_type_module,
+    DROP COLUMN IF EXISTS object_type_name;
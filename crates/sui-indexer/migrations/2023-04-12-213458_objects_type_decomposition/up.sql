@@ -0,0 +1,20 @@
+-- Decompose object_type into package, module and struct name so that type queries can be
+-- served by an index instead of LIKE scans over the full type string, for example
+-- `0x2::coin::Coin<0x2::sui::SUI>` becomes (0x00..02, coin, Coin).
+-- Package addresses are normalized to the 64 hex chars form, non-struct types
+-- like packages are left as NULL.
+ALTER TABLE objects
+    ADD COLUMN object_type_package VARCHAR GENERATED ALWAYS AS (
+        CASE WHEN object_type LIKE '0x%::%::%'
+            THEN '0x' || lpad(substr(split_part(object_type, '::', 1), 3), 64, '0')
+        END) STORED,
+    ADD COLUMN object_type_module  VARCHAR GENERATED ALWAYS AS (
+        CASE WHEN object_type LIKE '0x%::%::%'
+            THEN split_part(object_type, '::', 2)
+        END) STORED,
+    ADD COLUMN object_type_name    VARCHAR GENERATED ALWAYS AS (
+        CASE WHEN object_type LIKE '0x%::%::%'
+            THEN split_part(split_part(object_type, '::', 3), '<', 1)
+        END) STORED;
+
+CREATE INDEX objects_type_decomposition ON objects (object_type_package, object_type_module, object_type_name, object_id);
@@ -0,0 +1,6 @@
+DROP INDEX IF EXISTS events_event_type_decomposition;
+ALTER TABLE events
+    DROP COLUMN IF EXISTS event_type_package,
+    DROP COLUMN IF EXISTS event_type_module,
+    DROP COLUMN IF EXISTS event_type_name,
+    DROP COLUMN IF EXISTS event_type_params;
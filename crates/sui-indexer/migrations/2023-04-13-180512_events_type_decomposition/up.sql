@@ -0,0 +1,27 @@
+-- Decomposed event type, populated by the indexer at index time. Package is the
+-- 64 hex chars form of the type's address, type params is the comma separated list
+-- of type parameters without the enclosing angle brackets, empty for non-generic types.
+ALTER TABLE events
+    ADD COLUMN event_type_package address,
+    ADD COLUMN event_type_module  TEXT,
+    ADD COLUMN event_type_name    TEXT,
+    ADD COLUMN event_type_params  TEXT;
+
+UPDATE events
+SET event_type_package = '0x' || lpad(substr(split_part(event_type, '::', 1), 3), 64, '0'),
+    event_type_module  = split_part(event_type, '::', 2),
+    event_type_name    = split_part(split_part(event_type, '::', 3), '<', 1),
+    event_type_params  = CASE
+                             WHEN position('<' IN event_type) > 0
+                                 THEN substr(event_type, position('<' IN event_type) + 1,
+                                             length(event_type) - position('<' IN event_type) - 1)
+                             ELSE ''
+        END;
+
+ALTER TABLE events
+    ALTER COLUMN event_type_package SET NOT NULL,
+    ALTER COLUMN event_type_module SET NOT NULL,
+    ALTER COLUMN event_type_name SET NOT NULL,
+    ALTER COLUMN event_type_params SET NOT NULL;
+
+CREATE INDEX events_event_type_decomposition ON events (event_type_package, event_type_module, event_type_name, id);
@@ -0,0 +1,9 @@
+CREATE TABLE protocol_configs
+(
+    epoch            BIGINT PRIMARY KEY,
+    protocol_version BIGINT NOT NULL,
+    -- JSON serialized ProtocolConfigResponse with feature flags and attributes,
+    -- NULL when the protocol version is not supported by this indexer binary.
+    protocol_config  TEXT
+);
+CREATE INDEX protocol_configs_protocol_version ON protocol_configs (protocol_version);
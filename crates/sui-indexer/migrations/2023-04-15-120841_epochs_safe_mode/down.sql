@@ -0,0 +1 @@
+ALTER TABLE epochs DROP COLUMN IF EXISTS safe_mode;
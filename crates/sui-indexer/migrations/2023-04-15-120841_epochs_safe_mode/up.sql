@@ -0,0 +1,4 @@
+-- true if the epoch ended without a SystemEpochInfoEvent, i.e. the epoch change ran in safe mode,
+-- in which case the end of epoch economics of the epoch are left NULL.
+ALTER TABLE epochs
+    ADD COLUMN safe_mode BOOLEAN NOT NULL DEFAULT false;
@@ -0,0 +1,15 @@
+-- Reporting records and stake of each active validator at the start of an epoch,
+-- read from the system state written by the epoch change.
+CREATE TABLE validator_epoch_events
+(
+    epoch             BIGINT NOT NULL,
+    validator_address TEXT   NOT NULL,
+    -- addresses of the validators reporting this validator
+    reported_by       TEXT[] NOT NULL,
+    report_count      BIGINT NOT NULL,
+    stake             BIGINT NOT NULL,
+    -- change of stake since the previous epoch, equals stake when the validator joined this epoch
+    stake_delta       BIGINT NOT NULL,
+    CONSTRAINT validator_epoch_events_pk PRIMARY KEY (epoch, validator_address)
+);
+CREATE INDEX validator_epoch_events_validator_address ON validator_epoch_events (validator_address, epoch);
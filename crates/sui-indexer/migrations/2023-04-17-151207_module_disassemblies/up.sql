@@ -0,0 +1,9 @@
+-- Cache of disassembled modules, filled lazily on read.
+CREATE TABLE module_disassemblies
+(
+    package_id  VARCHAR(66) NOT NULL,
+    version     BIGINT      NOT NULL,
+    module_name TEXT        NOT NULL,
+    disassembly TEXT        NOT NULL,
+    CONSTRAINT module_disassemblies_pk PRIMARY KEY (package_id, version, module_name)
+);
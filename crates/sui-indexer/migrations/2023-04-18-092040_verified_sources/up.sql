@@ -0,0 +1,11 @@
+CREATE TABLE verified_sources
+(
+    package_id     VARCHAR(66) PRIMARY KEY,
+    -- JSON object of source file path to file content
+    sources        TEXT        NOT NULL,
+    -- source verification service or uploader the sources came from
+    verifier       TEXT        NOT NULL,
+    verified_at_ms BIGINT      NOT NULL,
+    -- JSON metadata reported by the verifier, e.g. compiler version and build flags
+    metadata       TEXT
+);
@@ -0,0 +1,8 @@
+-- Edges of the transaction dependency DAG, from effects.dependencies().
+CREATE TABLE tx_dependencies (
+    transaction_digest          base58digest    NOT NULL,
+    dependency                  base58digest    NOT NULL,
+    checkpoint_sequence_number  BIGINT          NOT NULL,
+    CONSTRAINT tx_dependencies_pk PRIMARY KEY (transaction_digest, dependency)
+);
+CREATE INDEX tx_dependencies_dependency ON tx_dependencies (dependency, transaction_digest);
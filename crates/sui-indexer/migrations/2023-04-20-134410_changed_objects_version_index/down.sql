@@ -0,0 +1 @@
+DROP INDEX IF EXISTS changed_objects_object_id_version;
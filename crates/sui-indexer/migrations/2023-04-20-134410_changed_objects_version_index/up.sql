@@ -0,0 +1,2 @@
+-- Reverse lookup of the transaction producing an object version.
+CREATE INDEX changed_objects_object_id_version ON changed_objects (object_id, object_version);
@@ -0,0 +1 @@
+DROP TABLE IF EXISTS checkpoint_tx_digests;
@@ -0,0 +1,12 @@
+-- Transactions of each checkpoint in contents order, normalized from checkpoints.transactions,
+-- to locate a transaction in the checkpoint contents for inclusion proofs.
+CREATE TABLE checkpoint_tx_digests
+(
+    checkpoint_sequence_number BIGINT       NOT NULL,
+    position                   BIGINT       NOT NULL,
+    transaction_digest         base58digest NOT NULL,
+    effects_digest             base58digest NOT NULL,
+    content_digest             base58digest NOT NULL,
+    CONSTRAINT checkpoint_tx_digests_pk PRIMARY KEY (checkpoint_sequence_number, position)
+);
+CREATE UNIQUE INDEX checkpoint_tx_digests_transaction_digest ON checkpoint_tx_digests (transaction_digest);
@@ -0,0 +1,11 @@
+CREATE OR REPLACE VIEW network_metrics AS
+SELECT (SELECT recent_tps from real_time_tps)                                                       AS current_tps,
+       (SELECT COALESCE(tps_30_days, 0) FROM epoch_network_metrics)                                 AS tps_30_days,
+       (SELECT COUNT(1) FROM addresses)                                                             AS total_addresses,
+       -- row estimation
+       (SELECT reltuples AS estimate FROM pg_class WHERE relname = 'objects')::BIGINT               AS total_objects,
+       (SELECT COUNT(1) FROM packages)                                                              AS total_packages,
+       (SELECT MAX(epoch) FROM epochs)                                                              AS current_epoch,
+       (SELECT MAX(sequence_number) FROM checkpoints)                                               AS current_checkpoint;
+
+DROP TABLE IF EXISTS network_totals;
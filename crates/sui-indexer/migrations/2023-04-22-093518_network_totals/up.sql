@@ -0,0 +1,28 @@
+-- Running totals of the network, updated in the same DB transaction as the rows they count,
+-- so that reading them does not scan the counted tables. Each total has its own row b/c
+-- they are updated by concurrent commit tasks.
+CREATE TABLE network_totals
+(
+    name       VARCHAR(64) PRIMARY KEY,
+    total      BIGINT      NOT NULL,
+    -- last checkpoint counted in the total, for totals that are not idempotent to recount
+    checkpoint BIGINT      NOT NULL
+);
+
+INSERT INTO network_totals
+VALUES ('transaction_blocks', (SELECT COUNT(1) FROM transactions), -1),
+       ('events', (SELECT COUNT(1) FROM events), -1),
+       ('packages', (SELECT COUNT(1) FROM packages), -1),
+       -- objects neither deleted nor wrapped
+       ('live_objects',
+        (SELECT COUNT(1) FROM objects WHERE object_status NOT IN ('deleted', 'wrapped', 'unwrapped_then_deleted')),
+        (SELECT COALESCE(MAX(checkpoint), -1) FROM objects));
+
+CREATE OR REPLACE VIEW network_metrics AS
+SELECT (SELECT recent_tps from real_time_tps)                                                       AS current_tps,
+       (SELECT COALESCE(tps_30_days, 0) FROM epoch_network_metrics)                                 AS tps_30_days,
+       (SELECT COUNT(1) FROM addresses)                                                             AS total_addresses,
+       (SELECT total FROM network_totals WHERE name = 'live_objects')                               AS total_objects,
+       (SELECT total FROM network_totals WHERE name = 'packages')                                   AS total_packages,
+       (SELECT MAX(epoch) FROM epochs)                                                              AS current_epoch,
+       (SELECT MAX(sequence_number) FROM checkpoints)                                               AS current_checkpoint;
@@ -0,0 +1 @@
+DROP INDEX IF EXISTS transactions_transaction_digest_prefix;
@@ -0,0 +1,3 @@
+-- Byte-wise ordered index of digests, usable for prefix matches unlike the default
+-- collation of transactions_transaction_digest.
+CREATE INDEX transactions_transaction_digest_prefix ON transactions (transaction_digest COLLATE "C");
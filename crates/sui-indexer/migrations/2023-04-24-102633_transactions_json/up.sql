@@ -0,0 +1,11 @@
+-- SuiTransactionBlockResponse of transactions as JSONB, only populated with
+-- --index-transactions-json, so that nested fields can be queried with JSON operators.
+CREATE TABLE transactions_json
+(
+    transaction_digest         VARCHAR(44) PRIMARY KEY,
+    checkpoint_sequence_number BIGINT NOT NULL,
+    response                   JSONB  NOT NULL
+);
+CREATE INDEX transactions_json_checkpoint_sequence_number ON transactions_json (checkpoint_sequence_number);
+-- serves containment queries, e.g. response @> '{"effects": {"status": {"status": "failure"}}}'
+CREATE INDEX transactions_json_response ON transactions_json USING GIN (response jsonb_path_ops);
@@ -0,0 +1,3 @@
+DROP MATERIALIZED VIEW IF EXISTS top_packages;
+DROP MATERIALIZED VIEW IF EXISTS daily_active_addresses;
+DROP MATERIALIZED VIEW IF EXISTS daily_transaction_counts;
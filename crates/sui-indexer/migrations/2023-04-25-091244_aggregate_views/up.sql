@@ -0,0 +1,34 @@
+-- Aggregates commonly queried over SQL, refreshed periodically by the indexer for the
+-- views enabled with --materialized-views. Views are created empty so that the migration
+-- does not scan the aggregated tables, the first refresh populates them. The unique
+-- indexes are required to refresh them concurrently with reads.
+CREATE MATERIALIZED VIEW daily_transaction_counts AS
+SELECT date_trunc('day', to_timestamp(timestamp_ms / 1000.0)) AS day,
+       SUM(total_transaction_blocks)::BIGINT                 AS transaction_blocks,
+       SUM(total_successful_transaction_blocks)::BIGINT      AS successful_transaction_blocks,
+       SUM(total_transactions)::BIGINT                       AS transactions
+FROM checkpoints
+GROUP BY 1
+WITH NO DATA;
+CREATE UNIQUE INDEX daily_transaction_counts_day ON daily_transaction_counts (day);
+
+CREATE MATERIALIZED VIEW daily_active_addresses AS
+SELECT date_trunc('day', to_timestamp(timestamp_ms / 1000.0)) AS day,
+       COUNT(DISTINCT sender)                                 AS active_addresses
+FROM transactions
+WHERE timestamp_ms IS NOT NULL
+GROUP BY 1
+WITH NO DATA;
+CREATE UNIQUE INDEX daily_active_addresses_day ON daily_active_addresses (day);
+
+CREATE MATERIALIZED VIEW top_packages AS
+SELECT move_package,
+       COUNT(*)                           AS move_calls,
+       COUNT(DISTINCT transaction_digest) AS transaction_blocks,
+       COUNT(DISTINCT sender)             AS senders
+FROM move_calls
+GROUP BY move_package
+ORDER BY move_calls DESC
+LIMIT 1000
+WITH NO DATA;
+CREATE UNIQUE INDEX top_packages_move_package ON top_packages (move_package);
@@ -0,0 +1,9 @@
+-- latest released version of the Display of each object type, from display::VersionUpdated events
+CREATE TABLE display
+(
+    object_type TEXT PRIMARY KEY,
+    display_id  VARCHAR(66) NOT NULL,
+    version     INT         NOT NULL,
+    -- BCS of the VersionUpdated event, holding the templates of the fields
+    bcs         BYTEA       NOT NULL
+);
@@ -0,0 +1,4 @@
+DROP INDEX IF EXISTS events_event_type_decomposition;
+CREATE INDEX events_event_type_decomposition ON events (event_type_package, event_type_module, event_type_name, id);
+DROP INDEX IF EXISTS events_sequence;
+ALTER TABLE events DROP COLUMN IF EXISTS tx_sequence_number;
@@ -0,0 +1,61 @@
+-- Events are ordered by (tx_sequence_number, event_sequence): the position of their transaction
+-- in the contents of all checkpoints, then their position in the events of the transaction.
+-- This is the order of execution, and does not depend on the order rows are inserted in.
+ALTER TABLE events ADD COLUMN tx_sequence_number BIGINT;
+
+-- event_sequence of events indexed from checkpoints used to be 0, events of a transaction are
+-- inserted in order
+UPDATE events e
+SET event_sequence = s.event_sequence
+FROM (SELECT id, ROW_NUMBER() OVER (PARTITION BY transaction_digest ORDER BY id) - 1 AS event_sequence
+      FROM events) s
+WHERE e.id = s.id;
+
+UPDATE events e
+SET tx_sequence_number = t.tx_sequence_number
+FROM (SELECT tx.digest,
+             c.network_total_transactions - c.total_transaction_blocks + tx.position - 1 AS tx_sequence_number
+      FROM checkpoints c,
+           unnest(c.transactions) WITH ORDINALITY AS tx(digest, position)) t
+WHERE e.transaction_digest = t.digest;
+
+-- events whose transaction is recorded with its checkpoint but missing from the transactions
+-- array of the checkpoint, placed by the position of the transaction in the checkpoint contents
+UPDATE events e
+SET tx_sequence_number = c.network_total_transactions - c.total_transaction_blocks + d.position
+FROM transactions t
+         JOIN checkpoint_tx_digests d
+              ON d.transaction_digest = t.transaction_digest
+                  AND d.checkpoint_sequence_number = t.checkpoint_sequence_number
+         JOIN checkpoints c ON c.sequence_number = d.checkpoint_sequence_number
+WHERE e.tx_sequence_number IS NULL
+  AND e.transaction_digest = t.transaction_digest;
+
+-- events of transactions not in a committed checkpoint have no position, they were written before
+-- their checkpoint and are indexed again along with it
+DELETE
+FROM events e
+WHERE e.tx_sequence_number IS NULL
+  AND NOT EXISTS (SELECT 1
+                  FROM transactions t
+                           JOIN checkpoints c ON c.sequence_number = t.checkpoint_sequence_number
+                  WHERE t.transaction_digest = e.transaction_digest);
+
+-- any other event belongs to a committed checkpoint and would not be indexed again, fail rather
+-- than lose it
+DO $$
+DECLARE
+    unplaced BIGINT;
+BEGIN
+    SELECT COUNT(1) INTO unplaced FROM events WHERE tx_sequence_number IS NULL;
+    IF unplaced > 0 THEN
+        RAISE EXCEPTION '% events of committed checkpoints could not be placed by transaction sequence number', unplaced;
+    END IF;
+END $$;
+
+ALTER TABLE events ALTER COLUMN tx_sequence_number SET NOT NULL;
+CREATE UNIQUE INDEX events_sequence ON events (tx_sequence_number, event_sequence);
+
+DROP INDEX events_event_type_decomposition;
+CREATE INDEX events_event_type_decomposition
+    ON events (event_type_package, event_type_module, event_type_name, tx_sequence_number, event_sequence);
@@ -0,0 +1,10 @@
+-- addresses and objects watched for transactions touching them, see models::watchlists
+CREATE TABLE watchlists
+(
+    -- either address or object
+    entity_kind VARCHAR(16) NOT NULL,
+    entity_id   VARCHAR(66) NOT NULL,
+    -- name of the rule, reported in notifications of matching transactions
+    rule        TEXT        NOT NULL,
+    PRIMARY KEY (entity_kind, entity_id, rule)
+);
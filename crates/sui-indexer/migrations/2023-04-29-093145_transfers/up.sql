@@ -0,0 +1,17 @@
+-- balance changes of addresses receiving coins from the sender of a transaction, see
+-- models::transfers
+CREATE TABLE transfers
+(
+    id                          BIGSERIAL       PRIMARY KEY,
+    transaction_digest          base58digest    NOT NULL,
+    checkpoint_sequence_number  BIGINT          NOT NULL,
+    timestamp_ms                BIGINT          NOT NULL,
+    sender                      address         NOT NULL,
+    recipient                   address         NOT NULL,
+    coin_type                   TEXT            NOT NULL,
+    amount                      BIGINT          NOT NULL
+);
+CREATE INDEX transfers_transaction_digest ON transfers (transaction_digest);
+CREATE INDEX transfers_coin_type_timestamp ON transfers (coin_type, timestamp_ms);
+CREATE INDEX transfers_sender_timestamp ON transfers (sender, timestamp_ms);
+CREATE INDEX transfers_recipient_timestamp ON transfers (recipient, timestamp_ms);
@@ -0,0 +1,15 @@
+-- addresses that non-coin objects were passed to, see models::ownership_history
+CREATE TABLE object_ownership_history
+(
+    id                          BIGSERIAL       PRIMARY KEY,
+    object_id                   address         NOT NULL,
+    object_version              BIGINT          NOT NULL,
+    -- null if the previous owner is not an address, e.g. when the object is created,
+    -- unwrapped or taken out of its parent
+    from_owner                  address,
+    to_owner                    address         NOT NULL,
+    transaction_digest          base58digest    NOT NULL,
+    checkpoint_sequence_number  BIGINT          NOT NULL
+);
+CREATE UNIQUE INDEX object_ownership_history_object_version ON object_ownership_history (object_id, object_version);
+CREATE INDEX object_ownership_history_to_owner ON object_ownership_history (to_owner);
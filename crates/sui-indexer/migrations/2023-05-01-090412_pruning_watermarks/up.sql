@@ -0,0 +1,8 @@
+-- progress of the pruner, see models::retention
+CREATE TABLE pruning_watermarks
+(
+    -- name of the pruned table family, e.g. events or tx_indices
+    table_family             VARCHAR(32) PRIMARY KEY,
+    -- checkpoints before this one are pruned
+    pruned_before_checkpoint BIGINT      NOT NULL
+);
@@ -0,0 +1,14 @@
+-- names of the name service, see models::name_service
+CREATE TABLE name_records
+(
+    -- dynamic field of the registry table holding the record
+    field_id                    address         PRIMARY KEY,
+    name                        VARCHAR(256)    NOT NULL,
+    nft_id                      address         NOT NULL,
+    target_address              address,
+    expiration_timestamp_ms     BIGINT          NOT NULL,
+    version                     BIGINT          NOT NULL,
+    checkpoint_sequence_number  BIGINT          NOT NULL
+);
+CREATE UNIQUE INDEX name_records_name ON name_records (name);
+CREATE INDEX name_records_target_address ON name_records (target_address, field_id);
@@ -0,0 +1,69 @@
+-- Serialized size of the contents of objects, the Move struct or the modules of a
+-- package, so that the state of a package or type can be summed up. Objects written
+-- before this migration have a size of 0 until they are written again, deleted and
+-- wrapped objects have a size of 0.
+ALTER TABLE objects
+    ADD COLUMN object_size BIGINT NOT NULL DEFAULT 0;
+ALTER TABLE objects_history
+    ADD COLUMN object_size BIGINT NOT NULL DEFAULT 0;
+
+CREATE OR REPLACE FUNCTION objects_modified_func() RETURNS TRIGGER AS
+$body$
+BEGIN
+    IF (TG_OP = 'INSERT') THEN
+        INSERT INTO objects_history
+        VALUES (NEW.epoch, NEW.checkpoint, NEW.object_id, NEW.version, NEW.object_digest, NEW.owner_type,
+                NEW.owner_address, NULL, NULL,
+                NEW.initial_shared_version,
+                NEW.previous_transaction, NEW.object_type, NEW.object_status, NEW.has_public_transfer,
+                NEW.storage_rebate, NEW.bcs, NEW.object_size);
+        RETURN NEW;
+    ELSEIF (TG_OP = 'UPDATE') THEN
+        INSERT INTO objects_history
+        VALUES (NEW.epoch, NEW.checkpoint, NEW.object_id, NEW.version, NEW.object_digest, NEW.owner_type,
+                NEW.owner_address, OLD.owner_type, OLD.owner_address,
+                NEW.initial_shared_version,
+                NEW.previous_transaction, NEW.object_type, NEW.object_status, NEW.has_public_transfer,
+                NEW.storage_rebate, NEW.bcs, NEW.object_size);
+        -- MUSTFIX(gegaowp): we cannot update checkpoint in-place, b/c checkpoint is a partition key,
+        -- we need to prune old data in this partition periodically, like pruning old epochs upon new epoch.
+        RETURN NEW;
+    ELSIF (TG_OP = 'DELETE') THEN
+        -- object deleted from the main table, archive the history for that object
+        DELETE FROM objects_history WHERE object_id = old.object_id;
+        RETURN OLD;
+    ELSE
+        RAISE WARNING '[OBJECTS_MODIFIED_FUNC] - Other action occurred: %, at %',TG_OP,NOW();
+        RETURN NULL;
+    END IF;
+
+EXCEPTION
+    WHEN data_exception THEN
+        RAISE WARNING '[OBJECTS_MODIFIED_FUNC] - UDF ERROR [DATA EXCEPTION] - SQLSTATE: %, SQLERRM: %',SQLSTATE,SQLERRM;
+        RETURN NULL;
+    WHEN unique_violation THEN
+        RAISE WARNING '[OBJECTS_MODIFIED_FUNC] - UDF ERROR [UNIQUE] - SQLSTATE: %, SQLERRM: %',SQLSTATE,SQLERRM;
+        RETURN NULL;
+    WHEN OTHERS THEN
+        RAISE WARNING '[OBJECTS_MODIFIED_FUNC] - UDF ERROR [OTHER] - SQLSTATE: %, SQLERRM: %',SQLSTATE,SQLERRM;
+        RETURN NULL;
+END;
+$body$
+    LANGUAGE plpgsql;
+
+-- Growth of the object state by epoch and object type, updated by the indexer when the
+-- epoch_storage_growth view is enabled, like the views of the aggregate_views migration
+-- are refreshed. Each version in objects_history adds the difference with the size of the
+-- previous version, so the growth only covers the epochs in objects_history. Types are
+-- decomposed as in objects, with '' for packages. Each update recomputes the last epoch
+-- of the table, which may have grown since, and adds the epochs after it.
+CREATE TABLE epoch_storage_growth
+(
+    epoch               BIGINT  NOT NULL,
+    object_type_package VARCHAR NOT NULL,
+    object_type_module  VARCHAR NOT NULL,
+    object_type_name    VARCHAR NOT NULL,
+    size_delta          BIGINT  NOT NULL,
+    object_changes      BIGINT  NOT NULL,
+    PRIMARY KEY (epoch, object_type_package, object_type_module, object_type_name)
+);
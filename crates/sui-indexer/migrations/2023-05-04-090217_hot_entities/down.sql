@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS hot_packages;
+DROP TABLE IF EXISTS hot_objects;
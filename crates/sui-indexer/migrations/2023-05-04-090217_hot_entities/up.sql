@@ -0,0 +1,18 @@
+-- Transactions touching each object and Move calls to each package per time bucket of
+-- checkpoint timestamps, see models::hot_entities. Leaderboards sum up the buckets of a
+-- window instead of scanning the transaction index tables.
+CREATE TABLE hot_objects
+(
+    bucket_start_ms BIGINT  NOT NULL,
+    object_id       address NOT NULL,
+    touches         BIGINT  NOT NULL,
+    PRIMARY KEY (bucket_start_ms, object_id)
+);
+
+CREATE TABLE hot_packages
+(
+    bucket_start_ms BIGINT  NOT NULL,
+    package         address NOT NULL,
+    calls           BIGINT  NOT NULL,
+    PRIMARY KEY (bucket_start_ms, package)
+);
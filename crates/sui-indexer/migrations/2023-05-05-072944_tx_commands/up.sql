@@ -0,0 +1,20 @@
+-- Commands of programmable transactions, with their arguments summarized, see
+-- models::transaction_index::TxCommand.
+CREATE TABLE tx_commands
+(
+    transaction_digest          base58digest    NOT NULL,
+    command_index               BIGINT          NOT NULL,
+    checkpoint_sequence_number  BIGINT          NOT NULL,
+    epoch                       BIGINT          NOT NULL,
+    -- move_call, transfer_objects, split_coins, merge_coins, publish, upgrade or make_move_vec
+    command_kind                VARCHAR(32)     NOT NULL,
+    -- package called, or upgraded
+    package                     address,
+    module                      TEXT,
+    function                    TEXT,
+    type_arguments              TEXT,
+    arguments                   TEXT            NOT NULL,
+    CONSTRAINT tx_commands_pk PRIMARY KEY (transaction_digest, command_index)
+);
+CREATE INDEX tx_commands_command_kind ON tx_commands (command_kind, epoch);
+CREATE INDEX tx_commands_package ON tx_commands (package, module, function) WHERE package IS NOT NULL;
@@ -0,0 +1,15 @@
+-- Move calls of programmable transactions with their arguments as a JSON array, pure
+-- arguments decoded by the parameter types of the called function, see models::tx_calls.
+CREATE TABLE tx_calls
+(
+    transaction_digest          base58digest    NOT NULL,
+    command_index               BIGINT          NOT NULL,
+    checkpoint_sequence_number  BIGINT          NOT NULL,
+    epoch                       BIGINT          NOT NULL,
+    package                     address         NOT NULL,
+    module                      TEXT            NOT NULL,
+    function                    TEXT            NOT NULL,
+    arguments                   JSONB           NOT NULL,
+    CONSTRAINT tx_calls_pk PRIMARY KEY (transaction_digest, command_index)
+);
+CREATE INDEX tx_calls_function ON tx_calls (package, module, function, checkpoint_sequence_number);
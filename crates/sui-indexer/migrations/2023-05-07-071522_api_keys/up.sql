@@ -0,0 +1,11 @@
+-- keys of the clients of the RPC server when it requires API keys, see models::api_keys.
+-- Keys are stored as their hex SHA-256 digest, e.g. encode(sha256('<key>'), 'hex').
+CREATE TABLE api_keys
+(
+    key_hash            VARCHAR(64) PRIMARY KEY,
+    -- name the usage metrics of the key are labeled with
+    name                TEXT        NOT NULL,
+    -- quota of the key, unlimited if NULL
+    requests_per_second INT,
+    revoked             BOOLEAN     NOT NULL DEFAULT FALSE
+);
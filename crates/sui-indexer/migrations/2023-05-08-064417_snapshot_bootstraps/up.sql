@@ -0,0 +1,10 @@
+-- formal snapshots the objects table was bootstrapped from, see the bootstrap_from_snapshot
+-- binary. Indexing of an empty checkpoints table starts at the checkpoint of the snapshot.
+CREATE TABLE snapshot_bootstraps
+(
+    epoch                      BIGINT PRIMARY KEY,
+    -- last checkpoint of the epoch, the state of the snapshot is the state after it
+    checkpoint_sequence_number BIGINT NOT NULL,
+    object_count               BIGINT NOT NULL,
+    package_count              BIGINT NOT NULL
+);
@@ -0,0 +1,6 @@
+-- digest of the genesis checkpoint of the chain indexed into the database, recorded on the
+-- first start of the indexer and checked against the full node on every start after.
+CREATE TABLE chain_identifier
+(
+    checkpoint_digest VARCHAR(255) PRIMARY KEY
+);
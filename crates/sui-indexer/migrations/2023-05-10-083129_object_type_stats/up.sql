@@ -0,0 +1,11 @@
+-- number of live objects of each struct type at the end of an epoch, counted by the
+-- object commit task right after committing the last checkpoint of the epoch.
+CREATE TABLE object_type_stats
+(
+    epoch        BIGINT  NOT NULL,
+    package      VARCHAR NOT NULL,
+    module       VARCHAR NOT NULL,
+    name         VARCHAR NOT NULL,
+    object_count BIGINT  NOT NULL,
+    PRIMARY KEY (epoch, package, module, name)
+);
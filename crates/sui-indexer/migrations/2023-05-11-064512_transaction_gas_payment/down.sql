@@ -0,0 +1,8 @@
+DROP INDEX IF EXISTS transactions_gas_payment_object_ids;
+DROP INDEX IF EXISTS transactions_gas_owner;
+ALTER TABLE transactions
+    DROP COLUMN IF EXISTS gas_owner,
+    DROP COLUMN IF EXISTS gas_payment_object_ids,
+    DROP COLUMN IF EXISTS gas_payment_object_versions,
+    DROP COLUMN IF EXISTS gas_payment_object_digests,
+    DROP COLUMN IF EXISTS expiration_epoch;
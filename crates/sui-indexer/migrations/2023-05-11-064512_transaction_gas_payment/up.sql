@@ -0,0 +1,15 @@
+-- Gas payment and expiration of the transaction data, for the accounting of sponsored
+-- transactions and gas stations. The gas_object_* columns are of the gas object in the effects,
+-- the coin the payment objects are merged into. Transactions indexed before this migration
+-- have no gas owner and payment objects.
+ALTER TABLE transactions
+    ADD COLUMN gas_owner                   address,
+    -- arrays of VARCHAR rather than of the domains, which have no array operators
+    ADD COLUMN gas_payment_object_ids      VARCHAR(66)[] NOT NULL DEFAULT '{}',
+    ADD COLUMN gas_payment_object_versions BIGINT[]      NOT NULL DEFAULT '{}',
+    ADD COLUMN gas_payment_object_digests  VARCHAR(44)[] NOT NULL DEFAULT '{}',
+    -- null for transactions without an expiration
+    ADD COLUMN expiration_epoch            BIGINT;
+
+CREATE INDEX transactions_gas_owner ON transactions (gas_owner);
+CREATE INDEX transactions_gas_payment_object_ids ON transactions USING GIN (gas_payment_object_ids);
@@ -0,0 +1,5 @@
+DROP TABLE IF EXISTS sponsor_gas_spend;
+DROP INDEX IF EXISTS transactions_sponsored_timestamp_ms;
+DROP INDEX IF EXISTS transactions_sponsored;
+ALTER TABLE transactions
+    DROP COLUMN IF EXISTS sponsored;
@@ -0,0 +1,22 @@
+-- Transactions whose gas is paid by another address than the sender, e.g. by a gas station.
+-- Transactions indexed before gas owners were recorded are not flagged.
+ALTER TABLE transactions
+    ADD COLUMN sponsored BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE INDEX transactions_sponsored ON transactions (gas_owner, id) WHERE sponsored;
+CREATE INDEX transactions_sponsored_timestamp_ms ON transactions (gas_owner, timestamp_ms) WHERE sponsored;
+
+-- Gas spent by each sponsor on the transactions of other senders per epoch, added to as
+-- checkpoints are committed, see models::sponsors.
+CREATE TABLE sponsor_gas_spend
+(
+    sponsor                address NOT NULL,
+    epoch                  BIGINT  NOT NULL,
+    transaction_count      BIGINT  NOT NULL,
+    -- net of the storage rebates, can be negative
+    total_gas_cost         BIGINT  NOT NULL,
+    computation_cost       BIGINT  NOT NULL,
+    storage_cost           BIGINT  NOT NULL,
+    storage_rebate         BIGINT  NOT NULL,
+    PRIMARY KEY (sponsor, epoch)
+);
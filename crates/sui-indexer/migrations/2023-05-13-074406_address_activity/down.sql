@@ -0,0 +1,5 @@
+ALTER TABLE addresses
+    DROP COLUMN IF EXISTS transactions_sent,
+    DROP COLUMN IF EXISTS transactions_received,
+    DROP COLUMN IF EXISTS first_activity_checkpoint,
+    DROP COLUMN IF EXISTS last_activity_checkpoint;
@@ -0,0 +1,9 @@
+-- Transactions sent and received by each address and the checkpoints of its first and last
+-- activity, added to as checkpoints are committed, see models::addresses::AddressActivity.
+-- Received transactions are derived from the recipients of the tx_indices pipeline. Counts
+-- of addresses only include the transactions indexed after this migration.
+ALTER TABLE addresses
+    ADD COLUMN transactions_sent         BIGINT NOT NULL DEFAULT 0,
+    ADD COLUMN transactions_received     BIGINT NOT NULL DEFAULT 0,
+    ADD COLUMN first_activity_checkpoint BIGINT,
+    ADD COLUMN last_activity_checkpoint  BIGINT;
@@ -0,0 +1,8 @@
+DROP INDEX IF EXISTS transactions_abort;
+DROP INDEX IF EXISTS transactions_failure_kind;
+ALTER TABLE transactions
+    DROP COLUMN IF EXISTS failure_kind,
+    DROP COLUMN IF EXISTS failure_command,
+    DROP COLUMN IF EXISTS abort_package,
+    DROP COLUMN IF EXISTS abort_module,
+    DROP COLUMN IF EXISTS abort_code;
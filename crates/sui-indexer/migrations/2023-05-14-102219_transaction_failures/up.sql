@@ -0,0 +1,14 @@
+-- Execution error of failed transactions, decoded from their effects at index time so that
+-- failures can be grouped without decoding the effects. failure_kind is the variant of
+-- ExecutionFailureStatus, e.g. MoveAbort or InsufficientGas, abort columns are set for
+-- Move aborts only. Transactions indexed before this migration have no failure columns.
+ALTER TABLE transactions
+    ADD COLUMN failure_kind    TEXT,
+    ADD COLUMN failure_command BIGINT,
+    ADD COLUMN abort_package   address,
+    ADD COLUMN abort_module    TEXT,
+    ADD COLUMN abort_code      BIGINT;
+
+CREATE INDEX transactions_failure_kind ON transactions (failure_kind) WHERE failure_kind IS NOT NULL;
+CREATE INDEX transactions_abort ON transactions (abort_package, abort_module, abort_code, id)
+    WHERE abort_package IS NOT NULL;
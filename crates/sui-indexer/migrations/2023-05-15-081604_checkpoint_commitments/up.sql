@@ -0,0 +1,4 @@
+-- Commitments of checkpoint summaries, e.g. ECMH live object set digests, as their digest
+-- bytes like epochs.epoch_commitments, so that state commitments can be cross-checked
+-- against snapshots. Checkpoints indexed before this migration have no commitments.
+ALTER TABLE checkpoints ADD COLUMN checkpoint_commitments BYTEA[] NOT NULL DEFAULT '{}';
@@ -0,0 +1 @@
+DROP TABLE IF EXISTS reexecution_mismatches;
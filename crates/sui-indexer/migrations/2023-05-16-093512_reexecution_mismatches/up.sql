@@ -0,0 +1,12 @@
+-- Sampled transactions whose effects, re-executed locally against the object versions of
+-- objects_history, differ from the effects of their checkpoint. local_error is the
+-- execution error of the local execution if any.
+CREATE TABLE reexecution_mismatches (
+    transaction_digest          base58digest PRIMARY KEY,
+    checkpoint_sequence_number  BIGINT       NOT NULL,
+    expected_effects_digest     base58digest NOT NULL,
+    local_effects_digest        base58digest NOT NULL,
+    local_error                 TEXT,
+    reexecuted_at_ms            BIGINT       NOT NULL
+);
+CREATE INDEX reexecution_mismatches_checkpoint ON reexecution_mismatches (checkpoint_sequence_number);
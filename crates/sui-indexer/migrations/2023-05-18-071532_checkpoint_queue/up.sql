@@ -0,0 +1,10 @@
+-- Checkpoints indexed by indexer processes of a split pipeline, waiting to be committed in
+-- order by the committer. Queued checkpoints are keyed by the sequence number of their
+-- checkpoint, queued epochs by their epoch. payload is the zstd compressed JSON of the
+-- indexed data.
+CREATE TABLE checkpoint_queue (
+    kind             VARCHAR(16) NOT NULL,
+    sequence_number  BIGINT      NOT NULL,
+    payload          BYTEA       NOT NULL,
+    PRIMARY KEY (kind, sequence_number)
+);
@@ -0,0 +1 @@
+ALTER TABLE system_states DROP COLUMN IF EXISTS summary;
@@ -0,0 +1,5 @@
+-- Full SuiSystemStateSummary at the start of each epoch as JSON, including the active
+-- validators with their stake and the system parameters, so that the system state of past
+-- epochs can be served without an archival fullnode. Epochs indexed before this migration
+-- have no summary.
+ALTER TABLE system_states ADD COLUMN summary JSONB;
@@ -0,0 +1,12 @@
+-- Checkpoints of each epoch signed by each validator of its committee, from the signer
+-- bitmaps of the checkpoint certificates, added to as checkpoints are committed, see
+-- models::validator_participation. Validators are identified by their index in the
+-- committee, i.e. in the order of their protocol public keys; validators that signed no
+-- checkpoint have no row. Checkpoints indexed before this migration are not counted.
+CREATE TABLE validator_participation
+(
+    epoch              BIGINT NOT NULL,
+    committee_index    BIGINT NOT NULL,
+    checkpoints_signed BIGINT NOT NULL,
+    PRIMARY KEY (epoch, committee_index)
+);
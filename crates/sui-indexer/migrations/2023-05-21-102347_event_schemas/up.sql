@@ -0,0 +1,15 @@
+-- Layouts of the structs that can be emitted as events, i.e. with the copy and drop
+-- abilities, of each indexed package, see models::event_schemas. Structs are recorded
+-- under the id and version of every package version declaring them, the id an event type
+-- refers to being the package that introduced the struct. Packages upgraded in place, i.e.
+-- system packages, have several versions under the same id. Packages indexed before this
+-- migration are filled in on first read where read-path writes are allowed.
+CREATE TABLE event_schemas
+(
+    package_id  VARCHAR(66) NOT NULL,
+    version     BIGINT      NOT NULL,
+    module      TEXT        NOT NULL,
+    name        TEXT        NOT NULL,
+    layout      JSONB       NOT NULL,
+    PRIMARY KEY (package_id, version, module, name)
+);
@@ -0,0 +1,11 @@
+-- BCS of the certified summary, the contents and the effects of the transactions of each
+-- checkpoint, in the order of the contents, from which light clients verify transactions
+-- against the committee of the epoch, see models::checkpoint_proofs. Filled by the
+-- checkpoint_proofs pipeline and pruned with the transactions.
+CREATE TABLE checkpoint_proofs
+(
+    sequence_number   BIGINT  PRIMARY KEY,
+    certified_summary BYTEA   NOT NULL,
+    contents          BYTEA   NOT NULL,
+    effects           BYTEA[] NOT NULL
+);
@@ -0,0 +1,18 @@
+-- number of live objects of each struct type as of the objects committed so far, kept up to
+-- date by the indexer in the transactions that commit objects, so that the object type stats
+-- of an epoch are a copy of it rather than a scan of the objects table.
+CREATE TABLE object_type_counts
+(
+    package      VARCHAR NOT NULL,
+    module       VARCHAR NOT NULL,
+    name         VARCHAR NOT NULL,
+    object_count BIGINT  NOT NULL,
+    PRIMARY KEY (package, module, name)
+);
+
+INSERT INTO object_type_counts (package, module, name, object_count)
+SELECT object_type_package, object_type_module, object_type_name, COUNT(*)
+FROM objects
+WHERE object_status NOT IN ('deleted', 'wrapped', 'unwrapped_then_deleted')
+  AND object_type_package IS NOT NULL
+GROUP BY object_type_package, object_type_module, object_type_name;
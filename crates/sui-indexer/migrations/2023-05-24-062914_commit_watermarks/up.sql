@@ -0,0 +1,10 @@
+-- progress of the commit tasks writing the tables of checkpoints in several transactions,
+-- read by wait_for_checkpoint
+CREATE TABLE commit_watermarks
+(
+    -- name of the committed pipeline, e.g. checkpoints for the checkpoints with their
+    -- transactions, events and transaction index tables
+    pipeline             VARCHAR(32) PRIMARY KEY,
+    -- checkpoints up to this one are committed
+    committed_checkpoint BIGINT      NOT NULL
+);
@@ -0,0 +1,159 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::{Arc, RwLock};
+
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::routing::{delete, get, post};
+use axum::{Json, Router};
+use serde::Deserialize;
+use telemetry_subscribers::FilterHandle;
+use tracing::{info, warn};
+
+use sui_json_rpc_types::WatchlistEntry;
+
+use crate::store::{IndexerStore, PgIndexerStore};
+
+// Example commands:
+//
+// View the current log filter:
+//
+//   $ curl 'http://127.0.0.1:<admin-port>/logging'
+//
+// Trace the checkpoint handler and keep info logs elsewhere, the filter is replaced as a
+// whole with the same syntax as RUST_LOG:
+//
+//   $ curl -X POST 'http://127.0.0.1:<admin-port>/logging' \
+//       -d 'info,sui_indexer::handlers::checkpoint_handler=trace'
+//
+// Watch an address for transactions touching it, by the rule `deposits`, and stop watching
+// it. The network is only given to processes indexing several networks:
+//
+//   $ curl -X POST 'http://127.0.0.1:<admin-port>/watchlist?network=testnet' \
+//       -H 'Content-Type: application/json' \
+//       -d '{"entity": {"kind": "address", "id": "0x2"}, "rule": "deposits"}'
+//   $ curl -X DELETE 'http://127.0.0.1:<admin-port>/watchlist?network=testnet' \
+//       -H 'Content-Type: application/json' \
+//       -d '{"entity": {"kind": "address", "id": "0x2"}, "rule": "deposits"}'
+
+const LOGGING_ROUTE: &str = "/logging";
+const WATCHLIST_ROUTE: &str = "/watchlist";
+
+/// Stores of the indexers writing networks in this process, by network name, an indexer of a
+/// single network having the empty name. Registered as the indexers start, since the admin
+/// server is up before.
+#[derive(Clone, Default)]
+pub struct AdminStores(Arc<RwLock<HashMap<String, PgIndexerStore>>>);
+
+impl AdminStores {
+    pub fn register(&self, network: Option<&str>, store: PgIndexerStore) {
+        self.0
+            .write()
+            .unwrap()
+            .insert(network.unwrap_or_default().to_string(), store);
+    }
+
+    fn get(&self, network: Option<&str>) -> Result<PgIndexerStore, (StatusCode, String)> {
+        let network = network.unwrap_or_default();
+        self.0.read().unwrap().get(network).cloned().ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                format!("No indexer writing network '{network}' in this process"),
+            )
+        })
+    }
+}
+
+#[derive(Clone)]
+struct AdminState {
+    filter_handle: Arc<FilterHandle>,
+    stores: AdminStores,
+}
+
+#[derive(Deserialize)]
+struct NetworkParam {
+    network: Option<String>,
+}
+
+/// Serves admin routes of a running indexer on localhost only.
+pub async fn run_admin_server(port: u16, filter_handle: FilterHandle, stores: AdminStores) {
+    let filter = filter_handle.get().unwrap_or_default();
+    let app = Router::new()
+        .route(LOGGING_ROUTE, get(get_filter))
+        .route(LOGGING_ROUTE, post(set_filter))
+        .route(WATCHLIST_ROUTE, post(add_watchlist_entry))
+        .route(WATCHLIST_ROUTE, delete(remove_watchlist_entry))
+        .with_state(AdminState {
+            filter_handle: Arc::new(filter_handle),
+            stores,
+        });
+
+    let socket_address = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), port);
+    info!(
+        filter =% filter,
+        address =% socket_address,
+        "Starting indexer admin server"
+    );
+    if let Err(e) = axum::Server::bind(&socket_address)
+        .serve(app.into_make_service())
+        .await
+    {
+        warn!("Indexer admin server stopped with error: {:?}", e);
+    }
+}
+
+async fn get_filter(State(state): State<AdminState>) -> (StatusCode, String) {
+    match state.filter_handle.get() {
+        Ok(filter) => (StatusCode::OK, filter),
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+    }
+}
+
+async fn set_filter(State(state): State<AdminState>, new_filter: String) -> (StatusCode, String) {
+    match state.filter_handle.update(&new_filter) {
+        Ok(()) => {
+            info!(filter =% new_filter, "Log filter updated");
+            (StatusCode::OK, "".into())
+        }
+        Err(err) => (StatusCode::BAD_REQUEST, err.to_string()),
+    }
+}
+
+async fn add_watchlist_entry(
+    State(state): State<AdminState>,
+    Query(params): Query<NetworkParam>,
+    Json(entry): Json<WatchlistEntry>,
+) -> (StatusCode, String) {
+    let store = match state.stores.get(params.network.as_deref()) {
+        Ok(store) => store,
+        Err(err) => return err,
+    };
+    match store.persist_watchlist_entry(entry.clone()).await {
+        Ok(()) => {
+            info!(rule =% entry.rule, "Watchlist entry added");
+            (StatusCode::OK, "".into())
+        }
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+    }
+}
+
+async fn remove_watchlist_entry(
+    State(state): State<AdminState>,
+    Query(params): Query<NetworkParam>,
+    Json(entry): Json<WatchlistEntry>,
+) -> (StatusCode, String) {
+    let store = match state.stores.get(params.network.as_deref()) {
+        Ok(store) => store,
+        Err(err) => return err,
+    };
+    match store.delete_watchlist_entry(entry.clone()).await {
+        Ok(()) => {
+            info!(rule =% entry.rule, "Watchlist entry removed");
+            (StatusCode::OK, "".into())
+        }
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+    }
+}
@@ -0,0 +1,198 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+use axum::extract::State;
+use axum::http::{Request, StatusCode};
+use axum::middleware::Next;
+use axum::response::Response;
+use tracing::warn;
+
+use crate::apis::query_limits::{json_rpc_error, rate_limited, ClientRateLimiter};
+use crate::metrics::IndexerMetrics;
+use crate::models::api_keys::{hash_api_key, ApiKey};
+use crate::store::IndexerStore;
+
+pub const API_KEY_HEADER: &str = "x-api-key";
+// server error code of the JSON RPC error sent with 401 responses
+const UNAUTHORIZED_ERROR_CODE: i32 = -32030;
+// keys added or revoked in the api_keys table take effect within this interval
+const API_KEY_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// API keys the RPC server requires, reloaded periodically from the api_keys table, with the
+/// quotas of the keys counted by key hash, as names are only labels and need not be unique.
+pub struct ApiKeyAuth {
+    keys: RwLock<HashMap<String, ApiKey>>,
+    quotas: ClientRateLimiter,
+    metrics: IndexerMetrics,
+}
+
+enum Rejection {
+    MissingKey,
+    InvalidKey,
+    RevokedKey(String),
+    QuotaExceeded(String, u32, Duration),
+}
+
+impl ApiKeyAuth {
+    pub fn new(keys: Vec<ApiKey>, metrics: IndexerMetrics) -> Self {
+        let auth = Self {
+            keys: RwLock::new(HashMap::new()),
+            quotas: ClientRateLimiter::new(0, vec![]),
+            metrics,
+        };
+        auth.set_keys(keys);
+        auth
+    }
+
+    fn set_keys(&self, keys: Vec<ApiKey>) {
+        *self.keys.write().unwrap() = keys
+            .into_iter()
+            .map(|key| (key.key_hash.clone(), key))
+            .collect();
+    }
+
+    pub async fn refresh_forever<S: IndexerStore>(self: Arc<Self>, store: S) {
+        loop {
+            tokio::time::sleep(API_KEY_REFRESH_INTERVAL).await;
+            match store.get_api_keys().await {
+                Ok(keys) => self.set_keys(keys),
+                Err(e) => warn!("Failed to refresh API keys with error: {:?}", e),
+            }
+        }
+    }
+
+    fn check(&self, key: Option<&str>, now: Instant) -> Result<String, Rejection> {
+        let key_hash = hash_api_key(key.ok_or(Rejection::MissingKey)?);
+        let keys = self.keys.read().unwrap();
+        let key = keys.get(&key_hash).ok_or(Rejection::InvalidKey)?;
+        if key.revoked {
+            return Err(Rejection::RevokedKey(key.name.clone()));
+        }
+        if let Some(requests_per_second) = key.requests_per_second {
+            let requests_per_second = requests_per_second.max(0) as u32;
+            self.quotas
+                .acquire_at_rate(&key.key_hash, requests_per_second, now)
+                .map_err(|retry_after| {
+                    Rejection::QuotaExceeded(key.name.clone(), requests_per_second, retry_after)
+                })?;
+        }
+        Ok(key.name.clone())
+    }
+}
+
+/// Middleware of the RPC server rejecting requests without a valid API key in the
+/// `x-api-key` header, or over the quota of their key.
+pub async fn api_key_auth<B>(
+    State(auth): State<Arc<ApiKeyAuth>>,
+    request: Request<B>,
+    next: Next<B>,
+) -> Response {
+    let key = request
+        .headers()
+        .get(API_KEY_HEADER)
+        .and_then(|value| value.to_str().ok());
+    let rejection = match auth.check(key, Instant::now()) {
+        Ok(name) => {
+            auth.metrics
+                .total_api_key_requests
+                .with_label_values(&[&name])
+                .inc();
+            return next.run(request).await;
+        }
+        Err(rejection) => rejection,
+    };
+    let (name, reason, response) = match rejection {
+        Rejection::MissingKey => (
+            String::new(),
+            "missing_key",
+            unauthorized(format!("Missing API key in the {API_KEY_HEADER} header")),
+        ),
+        Rejection::InvalidKey => (
+            String::new(),
+            "invalid_key",
+            unauthorized("Invalid API key".to_string()),
+        ),
+        Rejection::RevokedKey(name) => (
+            name,
+            "revoked_key",
+            unauthorized("API key has been revoked".to_string()),
+        ),
+        Rejection::QuotaExceeded(name, requests_per_second, retry_after) => (
+            name,
+            "quota_exceeded",
+            rate_limited(requests_per_second, retry_after),
+        ),
+    };
+    auth.metrics
+        .total_api_key_rejected_requests
+        .with_label_values(&[&name, reason])
+        .inc();
+    response
+}
+
+fn unauthorized(message: String) -> Response {
+    json_rpc_error(StatusCode::UNAUTHORIZED, UNAUTHORIZED_ERROR_CODE, message)
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Instant;
+
+    use prometheus::Registry;
+
+    use crate::metrics::IndexerMetrics;
+    use crate::models::api_keys::{hash_api_key, ApiKey};
+
+    use super::{ApiKeyAuth, Rejection};
+
+    #[test]
+    fn test_check_api_key() {
+        let key = |key: &str, name: &str, requests_per_second, revoked| ApiKey {
+            key_hash: hash_api_key(key),
+            name: name.to_string(),
+            requests_per_second,
+            revoked,
+        };
+        let auth = ApiKeyAuth::new(
+            vec![
+                key("unlimited", "a", None, false),
+                key("limited", "b", Some(1), false),
+                // another key of the same name has its own quota
+                key("limited too", "b", Some(1), false),
+                key("revoked", "c", None, true),
+            ],
+            IndexerMetrics::new(&Registry::new()),
+        );
+        let now = Instant::now();
+        assert!(matches!(auth.check(None, now), Err(Rejection::MissingKey)));
+        assert!(matches!(
+            auth.check(Some("unknown"), now),
+            Err(Rejection::InvalidKey)
+        ));
+        assert!(matches!(
+            auth.check(Some("revoked"), now),
+            Err(Rejection::RevokedKey(_))
+        ));
+        assert_eq!(
+            auth.check(Some("unlimited"), now).ok().as_deref(),
+            Some("a")
+        );
+        assert_eq!(
+            auth.check(Some("unlimited"), now).ok().as_deref(),
+            Some("a")
+        );
+        assert_eq!(auth.check(Some("limited"), now).ok().as_deref(), Some("b"));
+        assert!(matches!(
+            auth.check(Some("limited"), now),
+            Err(Rejection::QuotaExceeded(_, 1, _))
+        ));
+        assert_eq!(
+            auth.check(Some("limited too"), now).ok().as_deref(),
+            Some("b")
+        );
+    }
+}
@@ -13,15 +13,16 @@ use sui_open_rpc::Module;
 use sui_types::balance::Supply;
 use sui_types::base_types::{ObjectID, SuiAddress};
 
+use crate::apis::is_transport_error;
+use crate::framework::endpoints::FullNodeEndpoints;
+
 pub(crate) struct CoinReadApi {
-    fullnode: HttpClient,
+    fullnode: FullNodeEndpoints<HttpClient>,
 }
 
 impl CoinReadApi {
-    pub fn new(fullnode_client: HttpClient) -> Self {
-        Self {
-            fullnode: fullnode_client,
-        }
+    pub fn new(fullnode: FullNodeEndpoints<HttpClient>) -> Self {
+        Self { fullnode }
     }
 }
 
@@ -35,7 +36,13 @@ impl CoinReadApiServer for CoinReadApi {
         limit: Option<usize>,
     ) -> RpcResult<CoinPage> {
         self.fullnode
-            .get_coins(owner, coin_type, cursor, limit)
+            .call(
+                |client| {
+                    let coin_type = coin_type.clone();
+                    async move { client.get_coins(owner, coin_type, cursor, limit).await }
+                },
+                is_transport_error,
+            )
             .await
     }
 
@@ -45,7 +52,12 @@ impl CoinReadApiServer for CoinReadApi {
         cursor: Option<ObjectID>,
         limit: Option<usize>,
     ) -> RpcResult<CoinPage> {
-        self.fullnode.get_all_coins(owner, cursor, limit).await
+        self.fullnode
+            .call(
+                |client| async move { client.get_all_coins(owner, cursor, limit).await },
+                is_transport_error,
+            )
+            .await
     }
 
     async fn get_balance(
@@ -53,19 +65,48 @@ impl CoinReadApiServer for CoinReadApi {
         owner: SuiAddress,
         coin_type: Option<String>,
     ) -> RpcResult<Balance> {
-        self.fullnode.get_balance(owner, coin_type).await
+        self.fullnode
+            .call(
+                |client| {
+                    let coin_type = coin_type.clone();
+                    async move { client.get_balance(owner, coin_type).await }
+                },
+                is_transport_error,
+            )
+            .await
     }
 
     async fn get_all_balances(&self, owner: SuiAddress) -> RpcResult<Vec<Balance>> {
-        self.fullnode.get_all_balances(owner).await
+        self.fullnode
+            .call(
+                |client| async move { client.get_all_balances(owner).await },
+                is_transport_error,
+            )
+            .await
     }
 
     async fn get_coin_metadata(&self, coin_type: String) -> RpcResult<Option<SuiCoinMetadata>> {
-        self.fullnode.get_coin_metadata(coin_type).await
+        self.fullnode
+            .call(
+                |client| {
+                    let coin_type = coin_type.clone();
+                    async move { client.get_coin_metadata(coin_type).await }
+                },
+                is_transport_error,
+            )
+            .await
     }
 
     async fn get_total_supply(&self, coin_type: String) -> RpcResult<Supply> {
-        self.fullnode.get_total_supply(coin_type).await
+        self.fullnode
+            .call(
+                |client| {
+                    let coin_type = coin_type.clone();
+                    async move { client.get_total_supply(coin_type).await }
+                },
+                is_transport_error,
+            )
+            .await
     }
 }
 
@@ -0,0 +1,574 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::net::SocketAddr;
+
+use axum::body::StreamBody;
+use axum::extract::{Extension, Query};
+use axum::http::header::{CONTENT_DISPOSITION, CONTENT_TYPE};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use futures::{stream, Stream, StreamExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use fastcrypto::encoding::{Base64, Encoding};
+use sui_json_rpc_types::{EventFilter, SuiEvent};
+use sui_types::base_types::SuiAddress;
+use sui_types::digests::TransactionDigest;
+use sui_types::event::EventID;
+
+use crate::apis::ClientLayers;
+use crate::errors::IndexerError;
+use crate::models::checkpoint_proofs::TransactionProof;
+use crate::models::transactions::Transaction;
+use crate::store::IndexerStore;
+
+const EXPORT_PAGE_SIZE: usize = 1000;
+const MAX_EXPORT_ROWS: usize = 1_000_000;
+
+/// Serves query results as CSV or JSONL downloads, streamed page by page with chunked
+/// transfer encoding so that exports of up to `MAX_EXPORT_ROWS` rows are not buffered:
+///
+/// - `GET /export/transactions?format=csv&sender=0x..` with at most one of `sender` and
+///   `checkpoint`, all transactions otherwise;
+/// - `GET /export/events?format=jsonl&filter=<EventFilter as JSON>`.
+///
+/// Both take an optional `limit` on the number of rows. Rows are in ascending order.
+///
+/// `POST /sql` runs the SELECT in the body over the views allowlisted by
+/// `readonly_sql_views` and answers with its rows as JSON, see `ReadonlySql`, or with
+/// 429 Too Many Requests if `readonly_sql_max_concurrent_queries` are already running.
+///
+/// `GET /proof?transaction=<digest>` answers with the Base64 BCS of the certified summary
+/// and contents of the checkpoint of the transaction and of its effects, from which light
+/// clients verify the transaction against the committee, see `TransactionProof::verify`.
+///
+/// Requests go through the same API key authentication and rate limits as the JSON-RPC
+/// server, see `ClientLayers`.
+pub async fn run_export_server<S>(addr: SocketAddr, store: S, layers: ClientLayers)
+where
+    S: IndexerStore + Clone + Sync + Send + 'static,
+{
+    info!("Indexer export server listening on {addr}...");
+    let app = Router::new()
+        .route("/export/transactions", get(export_transactions::<S>))
+        .route("/export/events", get(export_events::<S>))
+        .route("/sql", post(execute_readonly_sql::<S>))
+        .route("/proof", get(get_transaction_proof::<S>))
+        .layer(Extension(store));
+    if let Err(e) = axum::Server::bind(&addr)
+        .serve(
+            layers
+                .apply(app)
+                .into_make_service_with_connect_info::<SocketAddr>(),
+        )
+        .await
+    {
+        warn!("Indexer export server stopped with error: {:?}", e);
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Csv,
+    #[default]
+    Jsonl,
+}
+
+impl ExportFormat {
+    fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "text/csv",
+            ExportFormat::Jsonl => "application/x-ndjson",
+        }
+    }
+
+    fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Jsonl => "jsonl",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TransactionExportQuery {
+    #[serde(default)]
+    format: ExportFormat,
+    sender: Option<SuiAddress>,
+    checkpoint: Option<i64>,
+    limit: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EventExportQuery {
+    #[serde(default)]
+    format: ExportFormat,
+    filter: String,
+    limit: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TransactionProofQuery {
+    transaction: TransactionDigest,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedTransactionProof {
+    pub checkpoint: u64,
+    pub position: u64,
+    pub transaction_digest: String,
+    pub certified_summary: String,
+    pub contents: String,
+    pub effects: String,
+}
+
+impl From<TransactionProof> for ExportedTransactionProof {
+    fn from(proof: TransactionProof) -> Self {
+        Self {
+            checkpoint: proof.checkpoint,
+            position: proof.position,
+            transaction_digest: proof.transaction_digest.base58_encode(),
+            certified_summary: Base64::encode(proof.certified_summary),
+            contents: Base64::encode(proof.contents),
+            effects: Base64::encode(proof.effects),
+        }
+    }
+}
+
+/// A row of an export, serialized as is to JSONL and as `csv_fields` to CSV.
+pub trait ExportRow: Serialize {
+    const CSV_HEADER: &'static [&'static str];
+
+    fn csv_fields(&self) -> Vec<String>;
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedTransaction {
+    pub digest: String,
+    pub sender: String,
+    pub checkpoint: Option<i64>,
+    pub timestamp_ms: Option<i64>,
+    pub kind: String,
+    pub execution_success: bool,
+    pub gas_price: i64,
+    pub gas_budget: i64,
+    pub total_gas_cost: i64,
+    pub computation_cost: i64,
+    pub storage_cost: i64,
+    pub storage_rebate: i64,
+    pub non_refundable_storage_fee: i64,
+}
+
+impl From<&Transaction> for ExportedTransaction {
+    fn from(tx: &Transaction) -> Self {
+        Self {
+            digest: tx.transaction_digest.clone(),
+            sender: tx.sender.clone(),
+            checkpoint: tx.checkpoint_sequence_number,
+            timestamp_ms: tx.timestamp_ms,
+            kind: tx.transaction_kind.clone(),
+            execution_success: tx.execution_success,
+            gas_price: tx.gas_price,
+            gas_budget: tx.gas_budget,
+            total_gas_cost: tx.total_gas_cost,
+            computation_cost: tx.computation_cost,
+            storage_cost: tx.storage_cost,
+            storage_rebate: tx.storage_rebate,
+            non_refundable_storage_fee: tx.non_refundable_storage_fee,
+        }
+    }
+}
+
+impl ExportRow for ExportedTransaction {
+    const CSV_HEADER: &'static [&'static str] = &[
+        "digest",
+        "sender",
+        "checkpoint",
+        "timestamp_ms",
+        "kind",
+        "execution_success",
+        "gas_price",
+        "gas_budget",
+        "total_gas_cost",
+        "computation_cost",
+        "storage_cost",
+        "storage_rebate",
+        "non_refundable_storage_fee",
+    ];
+
+    fn csv_fields(&self) -> Vec<String> {
+        vec![
+            self.digest.clone(),
+            self.sender.clone(),
+            optional_field(self.checkpoint),
+            optional_field(self.timestamp_ms),
+            self.kind.clone(),
+            self.execution_success.to_string(),
+            self.gas_price.to_string(),
+            self.gas_budget.to_string(),
+            self.total_gas_cost.to_string(),
+            self.computation_cost.to_string(),
+            self.storage_cost.to_string(),
+            self.storage_rebate.to_string(),
+            self.non_refundable_storage_fee.to_string(),
+        ]
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedEvent {
+    pub transaction_digest: String,
+    pub event_sequence: u64,
+    pub timestamp_ms: Option<u64>,
+    pub package_id: String,
+    pub module: String,
+    pub sender: String,
+    pub event_type: String,
+    pub parsed_json: serde_json::Value,
+}
+
+impl From<&SuiEvent> for ExportedEvent {
+    fn from(event: &SuiEvent) -> Self {
+        Self {
+            transaction_digest: event.id.tx_digest.to_string(),
+            event_sequence: event.id.event_seq,
+            timestamp_ms: event.timestamp_ms,
+            package_id: event.package_id.to_string(),
+            module: event.transaction_module.to_string(),
+            sender: event.sender.to_string(),
+            event_type: event.type_.to_string(),
+            parsed_json: event.parsed_json.clone(),
+        }
+    }
+}
+
+impl ExportRow for ExportedEvent {
+    const CSV_HEADER: &'static [&'static str] = &[
+        "transaction_digest",
+        "event_sequence",
+        "timestamp_ms",
+        "package_id",
+        "module",
+        "sender",
+        "event_type",
+        "parsed_json",
+    ];
+
+    fn csv_fields(&self) -> Vec<String> {
+        vec![
+            self.transaction_digest.clone(),
+            self.event_sequence.to_string(),
+            optional_field(self.timestamp_ms),
+            self.package_id.clone(),
+            self.module.clone(),
+            self.sender.clone(),
+            self.event_type.clone(),
+            self.parsed_json.to_string(),
+        ]
+    }
+}
+
+fn optional_field<T: ToString>(value: Option<T>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_default()
+}
+
+// RFC 4180 quoting, only for fields that need it
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn csv_line<'a>(fields: impl IntoIterator<Item = &'a str>) -> String {
+    let mut line = fields
+        .into_iter()
+        .map(csv_field)
+        .collect::<Vec<_>>()
+        .join(",");
+    line.push_str("\r\n");
+    line
+}
+
+/// Encodes a page of rows, the CSV header is sent separately before the first page.
+pub fn encode_rows<T: ExportRow>(rows: &[T], format: ExportFormat) -> Vec<u8> {
+    let mut chunk = vec![];
+    for row in rows {
+        match format {
+            ExportFormat::Csv => {
+                let fields = row.csv_fields();
+                chunk.extend(csv_line(fields.iter().map(String::as_str)).into_bytes());
+            }
+            ExportFormat::Jsonl => {
+                // unwrap is safe, export rows serialize to JSON without failing
+                serde_json::to_writer(&mut chunk, row).unwrap();
+                chunk.push(b'\n');
+            }
+        }
+    }
+    chunk
+}
+
+fn header<T: ExportRow>(format: ExportFormat) -> Vec<u8> {
+    match format {
+        ExportFormat::Csv => csv_line(T::CSV_HEADER.iter().copied()).into_bytes(),
+        ExportFormat::Jsonl => vec![],
+    }
+}
+
+fn export_response<T, St>(name: &str, format: ExportFormat, pages: St) -> Response
+where
+    T: ExportRow,
+    St: Stream<Item = Result<Vec<u8>, IndexerError>> + Send + 'static,
+{
+    let name = name.to_string();
+    let body = stream::once(async move { Ok(header::<T>(format)) })
+        .chain(pages)
+        .map_err(move |e| {
+            // the response is already under way, the client sees a truncated body
+            warn!("Failed exporting {name} with error: {:?}", e);
+            std::io::Error::new(std::io::ErrorKind::Other, e.to_string())
+        });
+    (
+        [
+            (CONTENT_TYPE, format.content_type().to_string()),
+            (
+                CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{name}.{}\"", format.extension()),
+            ),
+        ],
+        StreamBody::new(body),
+    )
+        .into_response()
+}
+
+fn export_limit(limit: Option<usize>) -> usize {
+    limit.unwrap_or(MAX_EXPORT_ROWS).min(MAX_EXPORT_ROWS)
+}
+
+async fn export_transactions<S>(
+    Extension(store): Extension<S>,
+    Query(query): Query<TransactionExportQuery>,
+) -> Response
+where
+    S: IndexerStore + Clone + Sync + Send + 'static,
+{
+    if query.sender.is_some() && query.checkpoint.is_some() {
+        return (
+            StatusCode::BAD_REQUEST,
+            "At most one of sender and checkpoint can be given",
+        )
+            .into_response();
+    }
+    let format = query.format;
+    // cursor is the id of the last exported transaction
+    let pages = stream::try_unfold(
+        (None::<i64>, export_limit(query.limit), false),
+        move |(cursor, remaining, done)| {
+            let store = store.clone();
+            async move {
+                if done || remaining == 0 {
+                    return Ok(None);
+                }
+                let page_size = remaining.min(EXPORT_PAGE_SIZE);
+                let page = match (query.sender, query.checkpoint) {
+                    (Some(sender), _) => {
+                        store
+                            .get_transaction_page_by_sender_address(
+                                sender.to_string(),
+                                cursor,
+                                page_size,
+                                false,
+                            )
+                            .await?
+                    }
+                    (None, Some(checkpoint)) => {
+                        store
+                            .get_transaction_page_by_checkpoint(
+                                checkpoint, cursor, page_size, false,
+                            )
+                            .await?
+                    }
+                    (None, None) => {
+                        store
+                            .get_all_transaction_page(cursor, page_size, false)
+                            .await?
+                    }
+                };
+                let rows = page
+                    .iter()
+                    .map(ExportedTransaction::from)
+                    .collect::<Vec<_>>();
+                let next = (
+                    page.last().and_then(|tx| tx.id).or(cursor),
+                    remaining - page.len(),
+                    page.len() < page_size,
+                );
+                Ok::<_, IndexerError>(Some((encode_rows(&rows, format), next)))
+            }
+        },
+    );
+    export_response::<ExportedTransaction, _>("transactions", format, pages)
+}
+
+async fn export_events<S>(
+    Extension(store): Extension<S>,
+    Query(query): Query<EventExportQuery>,
+) -> Response
+where
+    S: IndexerStore + Clone + Sync + Send + 'static,
+{
+    let filter = match serde_json::from_str::<EventFilter>(&query.filter) {
+        Ok(filter) => filter,
+        Err(e) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                format!("Invalid event filter: {e}"),
+            )
+                .into_response()
+        }
+    };
+    let format = query.format;
+    let pages = stream::try_unfold(
+        (None::<EventID>, export_limit(query.limit), false),
+        move |(cursor, remaining, done)| {
+            let store = store.clone();
+            let filter = filter.clone();
+            async move {
+                if done || remaining == 0 {
+                    return Ok(None);
+                }
+                let page_size = remaining.min(EXPORT_PAGE_SIZE);
+                let page = store
+                    .get_events(filter, cursor, Some(page_size), false)
+                    .await?;
+                let rows = page
+                    .data
+                    .iter()
+                    .map(ExportedEvent::from)
+                    .collect::<Vec<_>>();
+                let next = (
+                    page.next_cursor,
+                    remaining - page.data.len(),
+                    !page.has_next_page,
+                );
+                Ok::<_, IndexerError>(Some((encode_rows(&rows, format), next)))
+            }
+        },
+    );
+    export_response::<ExportedEvent, _>("events", format, pages)
+}
+
+async fn execute_readonly_sql<S>(Extension(store): Extension<S>, sql: String) -> Response
+where
+    S: IndexerStore + Clone + Sync + Send + 'static,
+{
+    match store.execute_readonly_sql(sql).await {
+        Ok(result) => Json(result).into_response(),
+        // errors of the query itself, e.g. an unknown column
+        Err(
+            e @ (IndexerError::InvalidArgumentError(_)
+            | IndexerError::PostgresReadError(_)
+            | IndexerError::QueryTimeout(_)),
+        ) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+        Err(e @ IndexerError::NotSupportedError(_)) => {
+            (StatusCode::NOT_FOUND, e.to_string()).into_response()
+        }
+        Err(e @ IndexerError::TooManyRequests(_)) => {
+            (StatusCode::TOO_MANY_REQUESTS, e.to_string()).into_response()
+        }
+        Err(e) => {
+            warn!("Failed running read only SQL query with error: {:?}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response()
+        }
+    }
+}
+
+async fn get_transaction_proof<S>(
+    Extension(store): Extension<S>,
+    Query(query): Query<TransactionProofQuery>,
+) -> Response
+where
+    S: IndexerStore + Clone + Sync + Send + 'static,
+{
+    match store.get_transaction_proof(query.transaction).await {
+        Ok(Some(proof)) => Json(ExportedTransactionProof::from(proof)).into_response(),
+        // unknown transactions and checkpoints indexed without the checkpoint_proofs pipeline
+        Ok(None) => (
+            StatusCode::NOT_FOUND,
+            format!("No proof of transaction {}", query.transaction),
+        )
+            .into_response(),
+        Err(e) => {
+            warn!(
+                "Failed reading proof of transaction {} with error: {:?}",
+                query.transaction, e
+            );
+            (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{encode_rows, header, ExportFormat, ExportedTransaction};
+
+    fn transaction(digest: &str, kind: &str) -> ExportedTransaction {
+        ExportedTransaction {
+            digest: digest.to_string(),
+            sender: "0x1".to_string(),
+            checkpoint: Some(7),
+            timestamp_ms: None,
+            kind: kind.to_string(),
+            execution_success: true,
+            gas_price: 1000,
+            gas_budget: 5000,
+            total_gas_cost: 10,
+            computation_cost: 8,
+            storage_cost: 4,
+            storage_rebate: 2,
+            non_refundable_storage_fee: 0,
+        }
+    }
+
+    #[test]
+    fn test_encode_rows() {
+        let rows = vec![
+            transaction("a", "ProgrammableTransaction"),
+            transaction("b", "kind, with \"quotes\""),
+        ];
+        let csv = String::from_utf8(
+            [
+                header::<ExportedTransaction>(ExportFormat::Csv),
+                encode_rows(&rows, ExportFormat::Csv),
+            ]
+            .concat(),
+        )
+        .unwrap();
+        assert_eq!(
+            csv,
+            "digest,sender,checkpoint,timestamp_ms,kind,execution_success,gas_price,gas_budget,\
+            total_gas_cost,computation_cost,storage_cost,storage_rebate,non_refundable_storage_fee\r\n\
+            a,0x1,7,,ProgrammableTransaction,true,1000,5000,10,8,4,2,0\r\n\
+            b,0x1,7,,\"kind, with \"\"quotes\"\"\",true,1000,5000,10,8,4,2,0\r\n"
+        );
+
+        assert!(header::<ExportedTransaction>(ExportFormat::Jsonl).is_empty());
+        let jsonl = String::from_utf8(encode_rows(&rows, ExportFormat::Jsonl)).unwrap();
+        let lines = jsonl.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+        let first: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(first["digest"], "a");
+        assert_eq!(first["checkpoint"], 7);
+        assert!(first["timestampMs"].is_null());
+    }
+}
@@ -1,31 +1,51 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::str::FromStr;
+use std::time::Duration;
+
 use async_trait::async_trait;
+use futures::future::join_all;
 use jsonrpsee::core::RpcResult;
 use jsonrpsee::RpcModule;
 
-use sui_json_rpc::api::{
-    validate_limit, ExtendedApiServer, QUERY_MAX_RESULT_LIMIT, QUERY_MAX_RESULT_LIMIT_CHECKPOINTS,
-};
+use sui_json_rpc::api::{validate_limit, ExtendedApiServer, QUERY_MAX_RESULT_LIMIT_CHECKPOINTS};
 use sui_json_rpc::SuiRpcModule;
 use sui_json_rpc_types::{
-    AddressMetrics, CheckpointedObjectID, EpochInfo, EpochPage, MoveCallMetrics, NetworkMetrics,
-    Page, QueryObjectsPage, SuiObjectDataFilter, SuiObjectResponse, SuiObjectResponseQuery,
+    AbortedTransactionBlock, AbortedTransactionBlockPage, AddressMetrics, CheckpointedObjectID,
+    EpochGasSpend, EpochGasSpendPage, EpochInfo, EpochPage, EventFilter, EventSequence, HotEntity,
+    MoveCallMetrics, NetworkMetrics, OwnershipHistoryPage, Page, QueryObjectsPage,
+    SequencedEventPage, SuiMoveNormalizedStruct, SuiObjectDataFilter, SuiObjectResponse,
+    SuiObjectResponseQuery, SuiTransactionBlockResponseOptions, TransactionBlocksPage,
+    ValidatorParticipationPage, WatchlistEntry, WatchlistPage,
 };
 use sui_open_rpc::Module;
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::digests::TransactionDigest;
+use sui_types::parse_sui_struct_tag;
 use sui_types::sui_serde::BigInt;
+use sui_types::sui_system_state::sui_system_state_summary::SuiSystemStateSummary;
 
+use crate::apis::QueryLimits;
 use crate::errors::IndexerError;
+use crate::models::hot_entities::HotWindow;
+use crate::models::transactions::Transaction;
+use crate::models::watchlists::DBWatchlistEntry;
+use crate::pagination::Cursor;
 use crate::store::IndexerStore;
 
+const DEFAULT_CHECKPOINT_WAIT_TIMEOUT: Duration = Duration::from_secs(10);
+// bounds how long a request holds a connection of the server
+const MAX_CHECKPOINT_WAIT_TIMEOUT: Duration = Duration::from_secs(30);
+
 pub(crate) struct ExtendedApi<S> {
     state: S,
+    limits: QueryLimits,
 }
 
 impl<S: IndexerStore> ExtendedApi<S> {
-    pub fn new(state: S) -> Self {
-        Self { state }
+    pub fn new(state: S, limits: QueryLimits) -> Self {
+        Self { state, limits }
     }
 
     async fn query_objects_internal(
@@ -34,7 +54,7 @@ impl<S: IndexerStore> ExtendedApi<S> {
         cursor: Option<CheckpointedObjectID>,
         limit: Option<usize>,
     ) -> Result<QueryObjectsPage, IndexerError> {
-        let limit = validate_limit(limit, *QUERY_MAX_RESULT_LIMIT)?;
+        let limit = self.limits.page_size(limit)?;
 
         let at_checkpoint = if let Some(CheckpointedObjectID {
             at_checkpoint: Some(cp),
@@ -83,6 +103,56 @@ impl<S: IndexerStore> ExtendedApi<S> {
             has_next_page,
         })
     }
+
+    // Sequence number of the transaction of `cursor` to page transactions from, by their
+    // sequence number like the transaction queries of the indexer API.
+    async fn transaction_cursor_sequence(
+        &self,
+        cursor: Option<TransactionDigest>,
+        is_descending: bool,
+    ) -> Result<Option<i64>, IndexerError> {
+        self.state
+            .get_transaction_sequence_by_digest(
+                cursor.map(|digest| digest.to_string()),
+                is_descending,
+            )
+            .await
+    }
+
+    // Page of at most `limit` of `transactions`, read with a limit of `limit + 1` to tell
+    // whether there is a next page.
+    async fn transaction_page(
+        &self,
+        mut transactions: Vec<Transaction>,
+        limit: usize,
+        options: Option<SuiTransactionBlockResponseOptions>,
+    ) -> Result<TransactionBlocksPage, IndexerError> {
+        let has_next_page = transactions.len() > limit;
+        transactions.truncate(limit);
+        let next_cursor = transactions
+            .last()
+            .map(|tx| {
+                tx.transaction_digest.parse().map_err(|e| {
+                    IndexerError::SerdeError(format!(
+                        "Failed to deserialize transaction digest: {:?} with error {:?}",
+                        tx.transaction_digest, e
+                    ))
+                })
+            })
+            .transpose()?;
+        let data = join_all(transactions.into_iter().map(|tx| {
+            self.state
+                .compose_sui_transaction_block_response(tx, options.as_ref())
+        }))
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()?;
+        Ok(Page {
+            data,
+            next_cursor,
+            has_next_page,
+        })
+    }
 }
 
 #[async_trait]
@@ -113,6 +183,13 @@ impl<S: IndexerStore + Sync + Send + 'static> ExtendedApiServer for ExtendedApi<
         Ok(self.state.get_current_epoch().await?)
     }
 
+    async fn get_system_state_at_epoch(
+        &self,
+        epoch: BigInt<u64>,
+    ) -> RpcResult<SuiSystemStateSummary> {
+        Ok(self.state.get_system_state_at_epoch(*epoch).await?)
+    }
+
     async fn query_objects(
         &self,
         query: SuiObjectResponseQuery,
@@ -122,6 +199,350 @@ impl<S: IndexerStore + Sync + Send + 'static> ExtendedApiServer for ExtendedApi<
         Ok(self.query_objects_internal(query, cursor, limit).await?)
     }
 
+    async fn query_events_by_sequence(
+        &self,
+        query: EventFilter,
+        cursor: Option<EventSequence>,
+        limit: Option<usize>,
+        descending_order: Option<bool>,
+    ) -> RpcResult<SequencedEventPage> {
+        let limit = self.limits.page_size(limit)?;
+        Ok(self
+            .state
+            .get_events_by_sequence(
+                query,
+                cursor,
+                Some(limit),
+                descending_order.unwrap_or_default(),
+            )
+            .await?)
+    }
+
+    async fn get_event_schema(&self, event_type: String) -> RpcResult<SuiMoveNormalizedStruct> {
+        let event_type = parse_sui_struct_tag(&event_type).map_err(|e| {
+            IndexerError::InvalidArgumentError(format!(
+                "Invalid event type {event_type} with error: {e}"
+            ))
+        })?;
+        Ok(self.state.get_event_schema(event_type).await?)
+    }
+
+    async fn get_type_layout(&self, struct_type: String) -> RpcResult<serde_json::Value> {
+        let struct_tag = parse_sui_struct_tag(&struct_type).map_err(|e| {
+            IndexerError::InvalidArgumentError(format!(
+                "Invalid struct type {struct_type} with error: {e}"
+            ))
+        })?;
+        let layout = self.state.get_type_layout(struct_tag).await?;
+        Ok(serde_json::to_value(layout).map_err(|e| {
+            IndexerError::SerdeError(format!(
+                "Failed serializing layout of {struct_type} with error: {e}"
+            ))
+        })?)
+    }
+
+    async fn get_aborted_transactions(
+        &self,
+        package: ObjectID,
+        module: String,
+        abort_code: Option<BigInt<u64>>,
+        cursor: Option<String>,
+        limit: Option<usize>,
+        descending_order: Option<bool>,
+    ) -> RpcResult<AbortedTransactionBlockPage> {
+        let limit = self.limits.page_size(limit)?;
+        let cursor = cursor
+            .map(|cursor| {
+                Cursor::decode_with(&cursor, |cursor| match cursor {
+                    Cursor::Transaction { tx_sequence_number } => Some(tx_sequence_number),
+                    _ => None,
+                })
+            })
+            .transpose()?;
+        let mut transactions = self
+            .state
+            .get_failed_transactions_by_abort(
+                package,
+                module,
+                abort_code.map(|code| *code),
+                cursor,
+                limit + 1,
+                descending_order.unwrap_or(false),
+            )
+            .await?;
+
+        let has_next_page = transactions.len() > limit;
+        transactions.truncate(limit);
+        let next_cursor = transactions
+            .last()
+            .and_then(|aborted| aborted.transaction.id)
+            .map(|tx_sequence_number| Cursor::Transaction { tx_sequence_number }.encode());
+        let data = transactions
+            .into_iter()
+            .map(AbortedTransactionBlock::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Page {
+            data,
+            next_cursor,
+            has_next_page,
+        })
+    }
+
+    async fn get_watchlist(
+        &self,
+        cursor: Option<String>,
+        limit: Option<usize>,
+    ) -> RpcResult<WatchlistPage> {
+        let limit = self.limits.page_size(limit)?;
+        let cursor = cursor
+            .map(|cursor| {
+                let entry = Cursor::decode_with(&cursor, |cursor| match cursor {
+                    Cursor::Watchlist {
+                        entity_kind,
+                        entity_id,
+                        rule,
+                    } => Some(DBWatchlistEntry {
+                        entity_kind,
+                        entity_id,
+                        rule,
+                    }),
+                    _ => None,
+                })?;
+                WatchlistEntry::try_from(entry).map_err(|e| {
+                    IndexerError::InvalidArgumentError(format!("Invalid cursor {cursor}: {e}"))
+                })
+            })
+            .transpose()?;
+        let mut data = self.state.get_watchlist(cursor, limit + 1).await?;
+        let has_next_page = data.len() > limit;
+        data.truncate(limit);
+        let next_cursor = data.last().map(|entry| {
+            let entry = DBWatchlistEntry::from(entry.clone());
+            Cursor::Watchlist {
+                entity_kind: entry.entity_kind,
+                entity_id: entry.entity_id,
+                rule: entry.rule,
+            }
+            .encode()
+        });
+        Ok(Page {
+            data,
+            next_cursor,
+            has_next_page,
+        })
+    }
+
+    async fn get_ownership_history(
+        &self,
+        object_id: ObjectID,
+        cursor: Option<String>,
+        limit: Option<usize>,
+        descending_order: Option<bool>,
+    ) -> RpcResult<OwnershipHistoryPage> {
+        let limit = self.limits.page_size(limit)?;
+        let cursor = cursor
+            .map(|cursor| {
+                Cursor::decode_with(&cursor, |cursor| match cursor {
+                    Cursor::ObjectVersion { version } => Some(version),
+                    _ => None,
+                })
+            })
+            .transpose()?;
+        let mut data = self
+            .state
+            .get_ownership_history(
+                object_id,
+                cursor,
+                limit + 1,
+                descending_order.unwrap_or(false),
+            )
+            .await?;
+        let has_next_page = data.len() > limit;
+        data.truncate(limit);
+        let next_cursor = data.last().map(|transition| {
+            Cursor::ObjectVersion {
+                version: transition.object_version,
+            }
+            .encode()
+        });
+        Ok(Page {
+            data,
+            next_cursor,
+            has_next_page,
+        })
+    }
+
+    async fn get_hot_objects(
+        &self,
+        window: String,
+        limit: Option<usize>,
+    ) -> RpcResult<Vec<HotEntity>> {
+        let window = HotWindow::from_str(&window)?;
+        let limit = self.limits.page_size(limit)?;
+        Ok(self.state.get_hot_objects(window, limit).await?)
+    }
+
+    async fn get_hot_packages(
+        &self,
+        window: String,
+        limit: Option<usize>,
+    ) -> RpcResult<Vec<HotEntity>> {
+        let window = HotWindow::from_str(&window)?;
+        let limit = self.limits.page_size(limit)?;
+        Ok(self.state.get_hot_packages(window, limit).await?)
+    }
+
+    async fn get_transaction_blocks_by_gas_object(
+        &self,
+        object_id: ObjectID,
+        options: Option<SuiTransactionBlockResponseOptions>,
+        cursor: Option<TransactionDigest>,
+        limit: Option<usize>,
+        descending_order: Option<bool>,
+    ) -> RpcResult<TransactionBlocksPage> {
+        let limit = self.limits.page_size(limit)?;
+        let is_descending = descending_order.unwrap_or(false);
+        let start_sequence = self
+            .transaction_cursor_sequence(cursor, is_descending)
+            .await?;
+        let transactions = self
+            .state
+            .get_transactions_by_gas_object(object_id, start_sequence, limit + 1, is_descending)
+            .await?;
+        Ok(self.transaction_page(transactions, limit, options).await?)
+    }
+
+    async fn get_transaction_blocks_by_gas_owner(
+        &self,
+        gas_owner: SuiAddress,
+        sponsored_only: Option<bool>,
+        options: Option<SuiTransactionBlockResponseOptions>,
+        cursor: Option<TransactionDigest>,
+        limit: Option<usize>,
+        descending_order: Option<bool>,
+    ) -> RpcResult<TransactionBlocksPage> {
+        let limit = self.limits.page_size(limit)?;
+        let is_descending = descending_order.unwrap_or(false);
+        let start_sequence = self
+            .transaction_cursor_sequence(cursor, is_descending)
+            .await?;
+        let transactions = self
+            .state
+            .get_transactions_by_gas_owner(
+                gas_owner,
+                sponsored_only.unwrap_or(false),
+                start_sequence,
+                limit + 1,
+                is_descending,
+            )
+            .await?;
+        Ok(self.transaction_page(transactions, limit, options).await?)
+    }
+
+    async fn get_sponsored_transaction_blocks(
+        &self,
+        sponsor: SuiAddress,
+        start_ms: BigInt<u64>,
+        end_ms: BigInt<u64>,
+        options: Option<SuiTransactionBlockResponseOptions>,
+        cursor: Option<TransactionDigest>,
+        limit: Option<usize>,
+        descending_order: Option<bool>,
+    ) -> RpcResult<TransactionBlocksPage> {
+        let limit = self.limits.page_size(limit)?;
+        let is_descending = descending_order.unwrap_or(false);
+        let start_sequence = self
+            .transaction_cursor_sequence(cursor, is_descending)
+            .await?;
+        let transactions = self
+            .state
+            .get_sponsored_transactions(
+                sponsor,
+                *start_ms,
+                *end_ms,
+                start_sequence,
+                limit + 1,
+                is_descending,
+            )
+            .await?;
+        Ok(self.transaction_page(transactions, limit, options).await?)
+    }
+
+    async fn get_sponsor_gas_spend(
+        &self,
+        sponsor: SuiAddress,
+        cursor: Option<String>,
+        limit: Option<usize>,
+        descending_order: Option<bool>,
+    ) -> RpcResult<EpochGasSpendPage> {
+        let limit = self.limits.page_size(limit)?;
+        let cursor = cursor
+            .map(|cursor| {
+                Cursor::decode_with(&cursor, |cursor| match cursor {
+                    Cursor::Epoch { epoch } => Some(epoch),
+                    _ => None,
+                })
+            })
+            .transpose()?;
+        let mut spend = self
+            .state
+            .get_sponsor_gas_spend(
+                sponsor,
+                cursor,
+                limit + 1,
+                descending_order.unwrap_or(false),
+            )
+            .await?;
+        let has_next_page = spend.len() > limit;
+        spend.truncate(limit);
+        let data = spend
+            .into_iter()
+            .map(EpochGasSpend::from)
+            .collect::<Vec<_>>();
+        let next_cursor = data
+            .last()
+            .map(|spend| Cursor::Epoch { epoch: spend.epoch }.encode());
+        Ok(Page {
+            data,
+            next_cursor,
+            has_next_page,
+        })
+    }
+
+    async fn get_validator_participation(
+        &self,
+        epoch: BigInt<u64>,
+        cursor: Option<String>,
+        limit: Option<usize>,
+    ) -> RpcResult<ValidatorParticipationPage> {
+        let limit = self.limits.page_size(limit)?;
+        let cursor = cursor
+            .map(|cursor| {
+                Cursor::decode_with(&cursor, |cursor| match cursor {
+                    Cursor::CommitteeIndex { committee_index } => Some(committee_index),
+                    _ => None,
+                })
+            })
+            .transpose()?;
+        let mut data = self
+            .state
+            .get_validator_participation(*epoch, cursor, limit + 1)
+            .await?;
+        let has_next_page = data.len() > limit;
+        data.truncate(limit);
+        let next_cursor = data.last().map(|participation| {
+            Cursor::CommitteeIndex {
+                committee_index: participation.committee_index,
+            }
+            .encode()
+        });
+        Ok(Page {
+            data,
+            next_cursor,
+            has_next_page,
+        })
+    }
+
     async fn get_network_metrics(&self) -> RpcResult<NetworkMetrics> {
         Ok(self.state.get_network_metrics().await?)
     }
@@ -169,6 +590,20 @@ impl<S: IndexerStore + Sync + Send + 'static> ExtendedApiServer for ExtendedApi<
         // rolling_total_successful_transaction_blocks.
         Ok((total_txes as u64).into())
     }
+
+    async fn wait_for_checkpoint(
+        &self,
+        sequence_number: BigInt<u64>,
+        timeout_ms: Option<BigInt<u64>>,
+    ) -> RpcResult<()> {
+        let timeout = timeout_ms.map_or(DEFAULT_CHECKPOINT_WAIT_TIMEOUT, |timeout_ms| {
+            Duration::from_millis(*timeout_ms).min(MAX_CHECKPOINT_WAIT_TIMEOUT)
+        });
+        Ok(self
+            .state
+            .wait_for_checkpoint(*sequence_number as i64, timeout)
+            .await?)
+    }
 }
 
 impl<S> SuiRpcModule for ExtendedApi<S>
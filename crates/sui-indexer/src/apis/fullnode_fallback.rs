@@ -0,0 +1,295 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::hash::Hash;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use backoff::ExponentialBackoff;
+use futures::future::{BoxFuture, WeakShared};
+use futures::FutureExt;
+use jsonrpsee::core::{Error as RpcError, RpcResult};
+use jsonrpsee::http_client::HttpClient;
+use jsonrpsee::types::error::CallError;
+use jsonrpsee::types::ErrorObjectOwned;
+use tokio::sync::Semaphore;
+
+use sui_json_rpc::api::ReadApiClient;
+use sui_json_rpc_types::{
+    SuiGetPastObjectRequest, SuiObjectDataOptions, SuiObjectResponse, SuiPastObjectResponse,
+};
+use sui_types::base_types::{ObjectID, SequenceNumber};
+
+use crate::errors::IndexerError;
+use crate::framework::endpoints::FullNodeEndpoints;
+use crate::metrics::IndexerMetrics;
+
+const FALLBACK_MAX_RETRY_ELAPSED: Duration = Duration::from_secs(10);
+
+type ResponseFuture<T> = BoxFuture<'static, Result<T, ErrorObjectOwned>>;
+// weak so that requests whose callers all went away are dropped rather than kept in flight
+type InFlight<K, T> = Arc<Mutex<HashMap<K, WeakShared<ResponseFuture<T>>>>>;
+// object id, version (None for latest) and JSON serialized options
+type ObjectRequestKey = (ObjectID, Option<SequenceNumber>, String);
+
+/// Full node client for reads not served by the indexer yet. Requests in flight are
+/// capped, identical object reads in flight are sent once, and transport errors fail
+/// over to the other endpoints before being retried with exponential backoff. Errors
+/// returned by the full node keep their code.
+#[derive(Clone)]
+pub(crate) struct FullNodeFallback {
+    endpoints: FullNodeEndpoints<HttpClient>,
+    limiter: Arc<Semaphore>,
+    objects: InFlight<ObjectRequestKey, SuiObjectResponse>,
+    past_objects: InFlight<ObjectRequestKey, SuiPastObjectResponse>,
+    metrics: IndexerMetrics,
+}
+
+impl FullNodeFallback {
+    pub fn new(
+        endpoints: FullNodeEndpoints<HttpClient>,
+        max_concurrent_requests: usize,
+        metrics: IndexerMetrics,
+    ) -> Self {
+        Self {
+            endpoints,
+            limiter: Arc::new(Semaphore::new(max_concurrent_requests)),
+            objects: Arc::new(Mutex::new(HashMap::new())),
+            past_objects: Arc::new(Mutex::new(HashMap::new())),
+            metrics,
+        }
+    }
+
+    pub async fn get_object(
+        &self,
+        object_id: ObjectID,
+        options: Option<SuiObjectDataOptions>,
+    ) -> RpcResult<SuiObjectResponse> {
+        let key = (object_id, None, options_key(&options));
+        let this = self.clone();
+        let request = async move {
+            this.call(|client| {
+                let options = options.clone();
+                async move { client.get_object(object_id, options).await }
+            })
+            .await
+        };
+        self.deduplicated(&self.objects, key, request).await
+    }
+
+    pub async fn try_get_past_object(
+        &self,
+        object_id: ObjectID,
+        version: SequenceNumber,
+        options: Option<SuiObjectDataOptions>,
+    ) -> RpcResult<SuiPastObjectResponse> {
+        let key = (object_id, Some(version), options_key(&options));
+        let this = self.clone();
+        let request = async move {
+            this.call(|client| {
+                let options = options.clone();
+                async move {
+                    client
+                        .try_get_past_object(object_id, version, options)
+                        .await
+                }
+            })
+            .await
+        };
+        self.deduplicated(&self.past_objects, key, request).await
+    }
+
+    pub async fn multi_get_objects(
+        &self,
+        object_ids: Vec<ObjectID>,
+        options: Option<SuiObjectDataOptions>,
+    ) -> RpcResult<Vec<SuiObjectResponse>> {
+        self.call(|client| {
+            let object_ids = object_ids.clone();
+            let options = options.clone();
+            async move { client.multi_get_objects(object_ids, options).await }
+        })
+        .await
+    }
+
+    pub async fn try_multi_get_past_objects(
+        &self,
+        past_objects: Vec<SuiGetPastObjectRequest>,
+        options: Option<SuiObjectDataOptions>,
+    ) -> RpcResult<Vec<SuiPastObjectResponse>> {
+        self.call(|client| {
+            let past_objects = past_objects.clone();
+            let options = options.clone();
+            async move {
+                client
+                    .try_multi_get_past_objects(past_objects, options)
+                    .await
+            }
+        })
+        .await
+    }
+
+    async fn call<T, F, Fut>(&self, request: F) -> RpcResult<T>
+    where
+        F: Fn(HttpClient) -> Fut,
+        Fut: Future<Output = RpcResult<T>>,
+    {
+        self.metrics.total_fullnode_fallback_request.inc();
+        let backoff = ExponentialBackoff {
+            max_elapsed_time: Some(FALLBACK_MAX_RETRY_ELAPSED),
+            ..Default::default()
+        };
+        backoff::future::retry(backoff, || async {
+            // the permit is only held by attempts, not across the backoff between them
+            let _permit = self.limiter.acquire().await.map_err(|e| {
+                backoff::Error::permanent(RpcError::from(IndexerError::FullNodeReadingError(
+                    e.to_string(),
+                )))
+            })?;
+            self.metrics.inflight_fullnode_fallback_request.inc();
+            let response = self.endpoints.call(&request, is_transport_error).await;
+            self.metrics.inflight_fullnode_fallback_request.dec();
+            response.map_err(|e| match e {
+                e if is_transport_error(&e) => {
+                    self.metrics.total_fullnode_fallback_retry.inc();
+                    backoff::Error::transient(e)
+                }
+                e => backoff::Error::permanent(e),
+            })
+        })
+        .await
+    }
+
+    // Joins an identical request in flight if any, otherwise sends `request` and lets
+    // others join it until it completes. The request is dropped, cancelling it, once no
+    // caller awaits it anymore.
+    async fn deduplicated<K, T, Fut>(
+        &self,
+        in_flight: &InFlight<K, T>,
+        key: K,
+        request: Fut,
+    ) -> RpcResult<T>
+    where
+        K: Eq + Hash + Clone + Send + 'static,
+        T: Clone + Send + Sync + 'static,
+        Fut: Future<Output = RpcResult<T>> + Send + 'static,
+    {
+        let response = {
+            let mut requests = in_flight.lock().unwrap();
+            if let Some(response) = requests.get(&key).and_then(WeakShared::upgrade) {
+                self.metrics
+                    .total_fullnode_fallback_deduplicated_request
+                    .inc();
+                response
+            } else {
+                let guard = InFlightGuard {
+                    in_flight: in_flight.clone(),
+                    key: key.clone(),
+                };
+                let response = async move {
+                    let result = request.await.map_err(error_object);
+                    guard.in_flight.lock().unwrap().remove(&guard.key);
+                    result
+                }
+                .boxed()
+                .shared();
+                // unwrap is safe b/c the response was not polled yet
+                requests.insert(key, response.downgrade().unwrap());
+                response
+            }
+        };
+        response
+            .await
+            .map_err(|e| RpcError::Call(CallError::Custom(e)))
+    }
+}
+
+// Removes the entry of a request dropped before completing, unless it was already
+// replaced by a new request.
+struct InFlightGuard<K: Eq + Hash, T> {
+    in_flight: InFlight<K, T>,
+    key: K,
+}
+
+impl<K: Eq + Hash, T> Drop for InFlightGuard<K, T> {
+    fn drop(&mut self) {
+        let mut requests = self.in_flight.lock().unwrap();
+        if requests
+            .get(&self.key)
+            .map_or(false, |response| response.upgrade().is_none())
+        {
+            requests.remove(&self.key);
+        }
+    }
+}
+
+// errors returned by the full node keep their code, others fail like indexer reads
+fn error_object(e: RpcError) -> ErrorObjectOwned {
+    match e {
+        RpcError::Call(e) => e.into(),
+        e => CallError::Failed(IndexerError::FullNodeReadingError(e.to_string()).into()).into(),
+    }
+}
+
+/// Errors returned by the full node itself are neither retried nor failed over.
+pub(crate) fn is_transport_error(e: &RpcError) -> bool {
+    !matches!(e, RpcError::Call(_))
+}
+
+fn options_key(options: &Option<SuiObjectDataOptions>) -> String {
+    serde_json::to_string(options).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod test {
+    use futures::FutureExt;
+    use jsonrpsee::core::{Error as RpcError, RpcResult};
+    use jsonrpsee::http_client::HttpClientBuilder;
+    use jsonrpsee::types::error::{CallError, INVALID_PARAMS_CODE};
+    use jsonrpsee::types::ErrorObject;
+    use prometheus::Registry;
+
+    use super::{FullNodeFallback, InFlight};
+    use crate::framework::endpoints::FullNodeEndpoints;
+    use crate::metrics::IndexerMetrics;
+
+    #[tokio::test]
+    async fn test_deduplicated_request() {
+        let metrics = IndexerMetrics::new(&Registry::new());
+        let client = HttpClientBuilder::default()
+            .build("http://localhost:9000")
+            .unwrap();
+        let endpoints = FullNodeEndpoints::new(vec![client], metrics.clone());
+        let fallback = FullNodeFallback::new(endpoints, 1, metrics);
+        let in_flight: InFlight<u64, ()> = Default::default();
+
+        // errors returned by the full node keep their code
+        let error = fallback
+            .deduplicated(&in_flight, 0, async {
+                Err(RpcError::Call(CallError::Custom(ErrorObject::owned(
+                    INVALID_PARAMS_CODE,
+                    "invalid object id",
+                    None::<()>,
+                ))))
+            })
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(error, RpcError::Call(CallError::Custom(e)) if e.code() == INVALID_PARAMS_CODE)
+        );
+        assert!(in_flight.lock().unwrap().is_empty());
+
+        // a request no caller awaits anymore is dropped with its entry
+        let mut request = Box::pin(fallback.deduplicated(
+            &in_flight,
+            1,
+            futures::future::pending::<RpcResult<()>>(),
+        ));
+        assert!((&mut request).now_or_never().is_none());
+        assert_eq!(in_flight.lock().unwrap().len(), 1);
+        drop(request);
+        assert!(in_flight.lock().unwrap().is_empty());
+    }
+}
@@ -15,48 +15,106 @@ use sui_types::base_types::{ObjectID, SuiAddress};
 use sui_types::sui_serde::BigInt;
 use sui_types::sui_system_state::sui_system_state_summary::SuiSystemStateSummary;
 
-pub(crate) struct GovernanceReadApi {
-    fullnode: HttpClient,
+use crate::apis::is_transport_error;
+use crate::framework::endpoints::FullNodeEndpoints;
+use crate::store::IndexerStore;
+
+pub(crate) struct GovernanceReadApi<S> {
+    state: S,
+    fullnode: FullNodeEndpoints<HttpClient>,
+    migrated_methods: Vec<String>,
 }
 
-impl GovernanceReadApi {
-    pub fn new(fullnode_client: HttpClient) -> Self {
+impl<S: IndexerStore> GovernanceReadApi<S> {
+    pub fn new(
+        state: S,
+        fullnode: FullNodeEndpoints<HttpClient>,
+        migrated_methods: Vec<String>,
+    ) -> Self {
         Self {
-            fullnode: fullnode_client,
+            state,
+            fullnode,
+            migrated_methods,
         }
     }
 }
 
 #[async_trait]
-impl GovernanceReadApiServer for GovernanceReadApi {
+impl<S> GovernanceReadApiServer for GovernanceReadApi<S>
+where
+    S: IndexerStore + Sync + Send + 'static,
+{
     async fn get_stakes_by_ids(
         &self,
         staked_sui_ids: Vec<ObjectID>,
     ) -> RpcResult<Vec<DelegatedStake>> {
-        self.fullnode.get_stakes_by_ids(staked_sui_ids).await
+        self.fullnode
+            .call(
+                |client| {
+                    let staked_sui_ids = staked_sui_ids.clone();
+                    async move { client.get_stakes_by_ids(staked_sui_ids).await }
+                },
+                is_transport_error,
+            )
+            .await
     }
     async fn get_stakes(&self, owner: SuiAddress) -> RpcResult<Vec<DelegatedStake>> {
-        self.fullnode.get_stakes(owner).await
+        self.fullnode
+            .call(
+                |client| async move { client.get_stakes(owner).await },
+                is_transport_error,
+            )
+            .await
     }
 
     async fn get_committee_info(&self, epoch: Option<BigInt<u64>>) -> RpcResult<SuiCommittee> {
-        self.fullnode.get_committee_info(epoch).await
+        self.fullnode
+            .call(
+                |client| async move { client.get_committee_info(epoch).await },
+                is_transport_error,
+            )
+            .await
     }
 
     async fn get_latest_sui_system_state(&self) -> RpcResult<SuiSystemStateSummary> {
-        self.fullnode.get_latest_sui_system_state().await
+        self.fullnode
+            .call(
+                |client| async move { client.get_latest_sui_system_state().await },
+                is_transport_error,
+            )
+            .await
     }
 
     async fn get_reference_gas_price(&self) -> RpcResult<BigInt<u64>> {
-        self.fullnode.get_reference_gas_price().await
+        if !self
+            .migrated_methods
+            .contains(&"get_reference_gas_price".to_string())
+        {
+            return self
+                .fullnode
+                .call(
+                    |client| async move { client.get_reference_gas_price().await },
+                    is_transport_error,
+                )
+                .await;
+        }
+        Ok(self.state.get_reference_gas_price().await?.into())
     }
 
     async fn get_validators_apy(&self) -> RpcResult<ValidatorApys> {
-        self.fullnode.get_validators_apy().await
+        self.fullnode
+            .call(
+                |client| async move { client.get_validators_apy().await },
+                is_transport_error,
+            )
+            .await
     }
 }
 
-impl SuiRpcModule for GovernanceReadApi {
+impl<S> SuiRpcModule for GovernanceReadApi<S>
+where
+    S: IndexerStore + Sync + Send + 'static,
+{
     fn rpc(self) -> RpcModule<Self> {
         self.into_rpc()
     }
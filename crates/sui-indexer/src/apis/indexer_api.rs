@@ -10,7 +10,7 @@ use jsonrpsee::types::SubscriptionResult;
 use jsonrpsee::{RpcModule, SubscriptionSink};
 
 use move_core_types::identifier::Identifier;
-use sui_json_rpc::api::{cap_page_limit, IndexerApiClient, IndexerApiServer};
+use sui_json_rpc::api::{IndexerApiClient, IndexerApiServer};
 use sui_json_rpc::SuiRpcModule;
 use sui_json_rpc_types::{
     DynamicFieldPage, EventFilter, EventPage, ObjectsPage, Page, SuiObjectDataFilter,
@@ -23,6 +23,7 @@ use sui_types::digests::TransactionDigest;
 use sui_types::dynamic_field::DynamicFieldName;
 use sui_types::event::EventID;
 
+use crate::apis::QueryLimits;
 use crate::errors::IndexerError;
 use crate::store::IndexerStore;
 
@@ -30,14 +31,21 @@ pub(crate) struct IndexerApi<S> {
     state: S,
     fullnode: HttpClient,
     migrated_methods: Vec<String>,
+    limits: QueryLimits,
 }
 
 impl<S: IndexerStore> IndexerApi<S> {
-    pub fn new(state: S, fullnode_client: HttpClient, migrated_methods: Vec<String>) -> Self {
+    pub fn new(
+        state: S,
+        fullnode_client: HttpClient,
+        migrated_methods: Vec<String>,
+        limits: QueryLimits,
+    ) -> Self {
         Self {
             state,
             fullnode: fullnode_client,
             migrated_methods,
+            limits,
         }
     }
 
@@ -48,8 +56,14 @@ impl<S: IndexerStore> IndexerApi<S> {
         limit: Option<usize>,
         descending_order: Option<bool>,
     ) -> Result<EventPage, IndexerError> {
+        let limit = self.limits.page_size(limit)?;
         self.state
-            .get_events(query, cursor, limit, descending_order.unwrap_or_default())
+            .get_events(
+                query,
+                cursor,
+                Some(limit),
+                descending_order.unwrap_or_default(),
+            )
             .await
     }
 
@@ -60,7 +74,7 @@ impl<S: IndexerStore> IndexerApi<S> {
         limit: Option<usize>,
         descending_order: Option<bool>,
     ) -> Result<TransactionBlocksPage, IndexerError> {
-        let limit = cap_page_limit(limit);
+        let limit = self.limits.page_size(limit)?;
         let is_descending = descending_order.unwrap_or_default();
         let cursor_str = cursor.map(|digest| digest.to_string());
         let mut tx_vec_from_db = match query.filter {
@@ -294,7 +308,7 @@ impl<S: IndexerStore> IndexerApi<S> {
             None => Ok((address, None)),
         }?;
         let options = options.unwrap_or_default();
-        let limit = cap_page_limit(limit);
+        let limit = self.limits.page_size(limit)?;
 
         // NOTE: fetch one more object to check if there is next page
         let mut objects = self
@@ -456,7 +470,13 @@ where
     }
 
     async fn resolve_name_service_address(&self, name: String) -> RpcResult<Option<SuiAddress>> {
-        self.fullnode.resolve_name_service_address(name).await
+        if !self
+            .migrated_methods
+            .contains(&"resolve_name_service_address".to_string())
+        {
+            return self.fullnode.resolve_name_service_address(name).await;
+        }
+        Ok(self.state.resolve_name(name).await?)
     }
 
     async fn resolve_name_service_names(
@@ -465,9 +485,19 @@ where
         cursor: Option<ObjectID>,
         limit: Option<usize>,
     ) -> RpcResult<Page<String, ObjectID>> {
-        self.fullnode
-            .resolve_name_service_names(address, cursor, limit)
-            .await
+        if !self
+            .migrated_methods
+            .contains(&"resolve_name_service_names".to_string())
+        {
+            return self
+                .fullnode
+                .resolve_name_service_names(address, cursor, limit)
+                .await;
+        }
+        Ok(self
+            .state
+            .lookup_names(address, cursor, self.limits.page_size(limit)?)
+            .await?)
     }
 }
 
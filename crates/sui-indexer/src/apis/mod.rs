@@ -1,20 +1,28 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+pub(crate) use api_key_auth::{ApiKeyAuth, API_KEY_HEADER};
 pub(crate) use coin_api::CoinReadApi;
+pub(crate) use export_api::run_export_server;
 pub(crate) use extended_api::ExtendedApi;
+pub(crate) use fullnode_fallback::{is_transport_error, FullNodeFallback};
 pub(crate) use governance_api::GovernanceReadApi;
 pub(crate) use indexer_api::IndexerApi;
 pub(crate) use move_utils::MoveUtilsApi;
+pub(crate) use query_limits::{ClientLayers, ClientRateLimiter, QueryLimits};
 pub(crate) use read_api::ReadApi;
 pub(crate) use transaction_builder_api::TransactionBuilderApi;
 pub(crate) use write_api::WriteApi;
 
+mod api_key_auth;
 mod coin_api;
+mod export_api;
 mod extended_api;
+mod fullnode_fallback;
 mod governance_api;
 mod indexer_api;
 mod move_utils;
+mod query_limits;
 mod read_api;
 mod transaction_builder_api;
 mod write_api;
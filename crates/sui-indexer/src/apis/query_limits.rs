@@ -0,0 +1,297 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use axum::extract::{ConnectInfo, State};
+use axum::http::header::RETRY_AFTER;
+use axum::http::{HeaderMap, Request, StatusCode};
+use axum::middleware::{from_fn_with_state, Next};
+use axum::response::{IntoResponse, Response};
+use axum::{Json, Router};
+use serde_json::json;
+use sui_json_rpc::api::QUERY_MAX_RESULT_LIMIT;
+
+use crate::apis::api_key_auth::{api_key_auth, ApiKeyAuth};
+use crate::errors::IndexerError;
+
+const FORWARDED_FOR_HEADER: &str = "x-forwarded-for";
+// server error code of the JSON RPC error sent with 429 responses
+const RATE_LIMITED_ERROR_CODE: i32 = -32029;
+// buckets are refilled within a second, so the buckets of clients idle for longer are full
+// and can be forgotten, they are dropped at most once per interval
+const IDLE_BUCKET_TTL: Duration = Duration::from_secs(1);
+const IDLE_BUCKET_EVICTION_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Limits on the reads of a single request served from Postgres by the RPC APIs, on top of
+/// the statement timeout of their queries.
+#[derive(Clone, Copy, Debug)]
+pub struct QueryLimits {
+    pub max_page_size: usize,
+    // rows read by a request for a list of keys, e.g. digests of a multi-get
+    pub max_scanned_rows: usize,
+}
+
+impl Default for QueryLimits {
+    fn default() -> Self {
+        Self {
+            max_page_size: *QUERY_MAX_RESULT_LIMIT,
+            max_scanned_rows: *QUERY_MAX_RESULT_LIMIT,
+        }
+    }
+}
+
+impl QueryLimits {
+    /// Page size of a paginated read, the default page size if `limit` is not set. Limits
+    /// larger than `max_page_size` are clamped to it, pages tell clients whether there are
+    /// more results with `has_next_page` either way.
+    pub fn page_size(&self, limit: Option<usize>) -> Result<usize, IndexerError> {
+        match limit {
+            None => Ok(self.max_page_size.min(*QUERY_MAX_RESULT_LIMIT)),
+            Some(0) => Err(IndexerError::InvalidArgumentError(
+                "Page size limit cannot be smaller than 1".to_string(),
+            )),
+            Some(limit) => Ok(limit.min(self.max_page_size)),
+        }
+    }
+
+    pub fn check_scanned_rows(&self, rows: usize) -> Result<(), IndexerError> {
+        if rows > self.max_scanned_rows {
+            return Err(IndexerError::InvalidArgumentError(format!(
+                "Request reads {rows} rows, which exceeds max scanned rows {}, \
+                split it into smaller requests",
+                self.max_scanned_rows
+            )));
+        }
+        Ok(())
+    }
+}
+
+struct Bucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+struct Buckets {
+    buckets: HashMap<String, Bucket>,
+    evicted_at: Instant,
+}
+
+/// Token buckets of the clients of the RPC server, refilled at `requests_per_second` and
+/// holding up to a second worth of requests. Clients are told apart by peer address, or
+/// by X-Forwarded-For for peers among `trusted_proxies`.
+pub struct ClientRateLimiter {
+    requests_per_second: u32,
+    trusted_proxies: Vec<IpAddr>,
+    buckets: Mutex<Buckets>,
+}
+
+impl ClientRateLimiter {
+    pub fn new(requests_per_second: u32, trusted_proxies: Vec<IpAddr>) -> Self {
+        Self {
+            requests_per_second,
+            trusted_proxies,
+            buckets: Mutex::new(Buckets {
+                buckets: HashMap::new(),
+                evicted_at: Instant::now(),
+            }),
+        }
+    }
+
+    /// Takes a token of `client`, or returns how long until one is available.
+    fn acquire(&self, client: &str, now: Instant) -> Result<(), Duration> {
+        self.acquire_at_rate(client, self.requests_per_second, now)
+    }
+
+    /// Like `acquire`, for clients with their own rate, e.g. the quotas of API keys.
+    pub(crate) fn acquire_at_rate(
+        &self,
+        client: &str,
+        requests_per_second: u32,
+        now: Instant,
+    ) -> Result<(), Duration> {
+        let rate = requests_per_second.max(1) as f64;
+        let mut buckets = self.buckets.lock().unwrap();
+        if now.duration_since(buckets.evicted_at) >= IDLE_BUCKET_EVICTION_INTERVAL {
+            buckets
+                .buckets
+                .retain(|_, bucket| now.duration_since(bucket.updated_at) < IDLE_BUCKET_TTL);
+            buckets.evicted_at = now;
+        }
+        let bucket = buckets.buckets.entry(client.to_string()).or_insert(Bucket {
+            tokens: rate,
+            updated_at: now,
+        });
+        let elapsed = now.duration_since(bucket.updated_at).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * rate).min(rate);
+        bucket.updated_at = now;
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) / rate))
+        }
+    }
+
+    #[cfg(test)]
+    fn tracked_clients(&self) -> usize {
+        self.buckets.lock().unwrap().buckets.len()
+    }
+
+    // The address of the client, the peer unless it is a trusted proxy. Each proxy appends the
+    // address it got the request from to X-Forwarded-For, so the client is the last address
+    // not of a trusted proxy, addresses before it may have been sent by the client itself.
+    fn client_id(&self, headers: &HeaderMap, peer: SocketAddr) -> String {
+        let mut client = peer.ip();
+        if self.trusted_proxies.contains(&client) {
+            let forwarded_for = headers
+                .get_all(FORWARDED_FOR_HEADER)
+                .iter()
+                .filter_map(|value| value.to_str().ok())
+                .flat_map(|value| value.split(','))
+                .collect::<Vec<_>>();
+            for address in forwarded_for.into_iter().rev() {
+                match address.trim().parse::<IpAddr>() {
+                    Ok(address) => {
+                        client = address;
+                        if !self.trusted_proxies.contains(&client) {
+                            break;
+                        }
+                    }
+                    Err(_) => break,
+                }
+            }
+        }
+        client.to_string()
+    }
+}
+
+/// Middleware of the RPC server rejecting requests of clients over their rate limit with
+/// a 429 carrying a JSON RPC error.
+pub async fn rate_limit<B>(
+    State(limiter): State<Arc<ClientRateLimiter>>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    request: Request<B>,
+    next: Next<B>,
+) -> Response {
+    let client = limiter.client_id(request.headers(), peer);
+    match limiter.acquire(&client, Instant::now()) {
+        Ok(()) => next.run(request).await,
+        Err(retry_after) => rate_limited(limiter.requests_per_second, retry_after),
+    }
+}
+
+/// Authentication by API key and per-client rate limits, applied alike to every server of
+/// the RPC server worker, so that clients have a single budget across them.
+#[derive(Clone, Default)]
+pub struct ClientLayers {
+    pub auth: Option<Arc<ApiKeyAuth>>,
+    pub rate_limiter: Option<Arc<ClientRateLimiter>>,
+}
+
+impl ClientLayers {
+    /// `router` behind the layers, to be served with the connect info of the clients.
+    pub fn apply(&self, mut router: Router) -> Router {
+        if let Some(auth) = &self.auth {
+            router = router.layer(from_fn_with_state(auth.clone(), api_key_auth));
+        }
+        // per-client limits apply before authentication, so that guessing keys is rate limited
+        if let Some(rate_limiter) = &self.rate_limiter {
+            router = router.layer(from_fn_with_state(rate_limiter.clone(), rate_limit));
+        }
+        router
+    }
+}
+
+pub(crate) fn rate_limited(requests_per_second: u32, retry_after: Duration) -> Response {
+    let retry_after_secs = retry_after.as_secs().max(1);
+    let mut response = json_rpc_error(
+        StatusCode::TOO_MANY_REQUESTS,
+        RATE_LIMITED_ERROR_CODE,
+        format!(
+            "Rate limit of {requests_per_second} requests per second exceeded, \
+            retry after {retry_after_secs} seconds"
+        ),
+    );
+    response
+        .headers_mut()
+        .insert(RETRY_AFTER, retry_after_secs.into());
+    response
+}
+
+/// A response carrying a JSON RPC error, for requests rejected before reaching the server.
+pub(crate) fn json_rpc_error(status: StatusCode, code: i32, message: String) -> Response {
+    let body = json!({
+        "jsonrpc": "2.0",
+        "error": {
+            "code": code,
+            "message": message,
+        },
+        "id": null,
+    });
+    (status, Json(body)).into_response()
+}
+
+#[cfg(test)]
+mod test {
+    use std::net::SocketAddr;
+    use std::time::{Duration, Instant};
+
+    use axum::http::HeaderMap;
+
+    use super::{ClientRateLimiter, QueryLimits};
+
+    #[test]
+    fn test_page_size() {
+        let limits = QueryLimits {
+            max_page_size: 100,
+            max_scanned_rows: 10,
+        };
+        assert_eq!(limits.page_size(Some(100)).unwrap(), 100);
+        assert!(limits.page_size(None).unwrap() <= 100);
+        assert!(limits.page_size(Some(0)).is_err());
+        assert_eq!(limits.page_size(Some(101)).unwrap(), 100);
+        assert!(limits.check_scanned_rows(10).is_ok());
+        assert!(limits.check_scanned_rows(11).is_err());
+    }
+
+    #[test]
+    fn test_rate_limiter() {
+        let limiter = ClientRateLimiter::new(2, vec![]);
+        let now = Instant::now();
+        assert!(limiter.acquire("a", now).is_ok());
+        assert!(limiter.acquire("a", now).is_ok());
+        assert!(limiter.acquire("a", now).is_err());
+        // other clients have their own bucket
+        assert!(limiter.acquire("b", now).is_ok());
+        assert!(limiter
+            .acquire("a", now + Duration::from_millis(500))
+            .is_ok());
+        assert!(limiter
+            .acquire("a", now + Duration::from_millis(500))
+            .is_err());
+        // buckets of idle clients are dropped
+        assert_eq!(limiter.tracked_clients(), 2);
+        assert!(limiter.acquire("c", now + Duration::from_secs(10)).is_ok());
+        assert_eq!(limiter.tracked_clients(), 1);
+    }
+
+    #[test]
+    fn test_client_id() {
+        let limiter = ClientRateLimiter::new(1, vec!["10.0.0.1".parse().unwrap()]);
+        let proxy: SocketAddr = "10.0.0.1:4000".parse().unwrap();
+        let peer: SocketAddr = "5.6.7.8:4000".parse().unwrap();
+        let mut headers = HeaderMap::new();
+        assert_eq!(limiter.client_id(&headers, proxy), "10.0.0.1");
+        headers.insert("x-forwarded-for", "9.9.9.9, 1.2.3.4".parse().unwrap());
+        // only the address appended by the trusted proxy is used
+        assert_eq!(limiter.client_id(&headers, proxy), "1.2.3.4");
+        // the header of other peers is ignored
+        assert_eq!(limiter.client_id(&headers, peer), "5.6.7.8");
+        headers.insert("x-forwarded-for", "1.2.3.4, 10.0.0.1".parse().unwrap());
+        assert_eq!(limiter.client_id(&headers, proxy), "1.2.3.4");
+    }
+}
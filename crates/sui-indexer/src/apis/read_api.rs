@@ -1,6 +1,8 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::str::FromStr;
+
 use async_trait::async_trait;
 use futures::future::join_all;
 use jsonrpsee::core::RpcResult;
@@ -16,9 +18,10 @@ use sui_json_rpc_types::{
 };
 use sui_open_rpc::Module;
 use sui_types::base_types::{ObjectID, SequenceNumber};
-use sui_types::digests::{ChainIdentifier, TransactionDigest};
+use sui_types::digests::{ChainIdentifier, CheckpointDigest, TransactionDigest};
 use sui_types::sui_serde::BigInt;
 
+use crate::apis::{FullNodeFallback, QueryLimits};
 use crate::errors::IndexerError;
 use crate::store::IndexerStore;
 use crate::types::SuiTransactionBlockResponseWithOptions;
@@ -26,16 +29,26 @@ use sui_json_rpc_types::SuiLoadedChildObjectsResponse;
 
 pub(crate) struct ReadApi<S> {
     fullnode: HttpClient,
+    fallback: FullNodeFallback,
     state: S,
     migrated_methods: Vec<String>,
+    limits: QueryLimits,
 }
 
 impl<S: IndexerStore> ReadApi<S> {
-    pub fn new(state: S, fullnode_client: HttpClient, migrated_methods: Vec<String>) -> Self {
+    pub fn new(
+        state: S,
+        fullnode_client: HttpClient,
+        fallback: FullNodeFallback,
+        migrated_methods: Vec<String>,
+        limits: QueryLimits,
+    ) -> Self {
         Self {
             state,
             fullnode: fullnode_client,
+            fallback,
             migrated_methods,
+            limits,
         }
     }
 
@@ -72,6 +85,7 @@ impl<S: IndexerStore> ReadApi<S> {
         digests: &[TransactionDigest],
         options: Option<SuiTransactionBlockResponseOptions>,
     ) -> Result<Vec<SuiTransactionBlockResponse>, IndexerError> {
+        self.limits.check_scanned_rows(digests.len())?;
         let digest_strs = digests
             .iter()
             .map(|digest| digest.base58_encode())
@@ -138,7 +152,7 @@ where
                 .indexer_metrics()
                 .get_object_latency
                 .start_timer();
-            let obj_resp = self.fullnode.get_object(object_id, options).await;
+            let obj_resp = self.fallback.get_object(object_id, options).await;
             obj_guard.stop_and_record();
             return obj_resp;
         }
@@ -156,7 +170,7 @@ where
             .indexer_metrics()
             .multi_get_objects_latency
             .start_timer();
-        let objs_resp = self.fullnode.multi_get_objects(object_ids, options).await;
+        let objs_resp = self.fallback.multi_get_objects(object_ids, options).await;
         objs_guard.stop_and_record();
         objs_resp
     }
@@ -239,7 +253,7 @@ where
             .try_get_past_object_latency
             .start_timer();
         let past_obj_resp = self
-            .fullnode
+            .fallback
             .try_get_past_object(object_id, version, options)
             .await;
         past_obj_guard.stop_and_record();
@@ -257,7 +271,7 @@ where
             .try_multi_get_past_objects_latency
             .start_timer();
         let multi_past_obj_resp = self
-            .fullnode
+            .fallback
             .try_multi_get_past_objects(past_objects, options)
             .await;
         multi_past_obj_guard.stop_and_record();
@@ -369,11 +383,17 @@ where
     }
 
     async fn get_chain_identifier(&self) -> RpcResult<String> {
-        let ci = self
-            .state
-            .get_checkpoint(CheckpointId::SequenceNumber(0))
-            .await?
-            .digest;
+        // recorded on the first start, also when the genesis checkpoint is not indexed
+        let ci = match self.state.get_chain_identifier().await? {
+            Some(recorded) => CheckpointDigest::from_str(&recorded.checkpoint_digest)
+                .map_err(IndexerError::from)?,
+            None => {
+                self.state
+                    .get_checkpoint(CheckpointId::SequenceNumber(0))
+                    .await?
+                    .digest
+            }
+        };
         Ok(ChainIdentifier::from(ci).to_string())
     }
 }
@@ -0,0 +1,283 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+
+use anyhow::{anyhow, bail, Context, Result};
+use clap::Parser;
+use prometheus::Registry;
+use tracing::{info, warn};
+
+use sui_core::authority::authority_store_tables::LiveObject;
+use sui_indexer::metrics::IndexerMetrics;
+use sui_indexer::models::name_service::NameRecord;
+use sui_indexer::models::objects::Object;
+use sui_indexer::models::packages::Package;
+use sui_indexer::models::snapshot_bootstraps::SnapshotBootstrap;
+use sui_indexer::store::{IndexerStore, PgIndexerStore, TransactionObjectChanges};
+use sui_indexer::utils::reset_database;
+use sui_indexer::{get_pg_pool_connection, new_pg_connection_pool, new_rpc_client};
+use sui_json_rpc::api::QUERY_MAX_RESULT_LIMIT;
+use sui_json_rpc_types::{
+    CheckpointId, SuiTransactionBlockDataAPI, SuiTransactionBlockResponseOptions,
+};
+use sui_sdk::SuiClient;
+use sui_snapshot::reader::StateSnapshotReaderV1;
+use sui_storage::object_store::{ObjectStoreConfig, ObjectStoreType};
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::digests::TransactionDigest;
+use sui_types::move_package::MovePackage;
+use sui_types::storage::WriteKind;
+
+// Fills the objects and packages tables of an empty database from the formal snapshot of
+// an epoch, the live object set after the last checkpoint of the epoch. The indexer then
+// starts indexing from that checkpoint instead of genesis, checkpoints, transactions and
+// events before it are not indexed.
+#[tokio::main]
+async fn main() -> Result<()> {
+    let _guard = telemetry_subscribers::TelemetryConfig::new()
+        .with_env()
+        .init();
+    let config = BootstrapConfig::parse();
+    let metrics = IndexerMetrics::new(&Registry::default());
+    let blocking_cp = new_pg_connection_pool(&config.db_url)?;
+    if config.reset_db {
+        reset_database(
+            &mut get_pg_pool_connection(&blocking_cp)?,
+            /* drop_all */ true,
+        )?;
+    }
+    let store = PgIndexerStore::new(blocking_cp, metrics.clone());
+    if store.get_latest_tx_checkpoint_sequence_number().await? >= 0 {
+        bail!("Checkpoints are already indexed, bootstrap from a snapshot needs an empty database");
+    }
+    if let Some(bootstrap) = store.get_snapshot_bootstrap().await? {
+        bail!(
+            "Database is already bootstrapped from the snapshot of epoch {}",
+            bootstrap.epoch
+        );
+    }
+    let client = new_rpc_client(&config.rpc_client_url).await?;
+    let checkpoint = find_last_checkpoint_of_epoch(&client, config.epoch).await?;
+    info!(
+        "Bootstrapping objects from the snapshot of epoch {} at checkpoint {}",
+        config.epoch, checkpoint
+    );
+
+    let remote_store_config: ObjectStoreConfig = serde_yaml::from_reader(
+        std::fs::File::open(&config.snapshot_store_config).with_context(|| {
+            format!(
+                "Failed opening snapshot store config {:?}",
+                config.snapshot_store_config
+            )
+        })?,
+    )?;
+    let local_store_config = ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(config.staging_dir.clone()),
+        ..Default::default()
+    };
+    let reader = StateSnapshotReaderV1::new(
+        config.epoch,
+        &remote_store_config,
+        &local_store_config,
+        usize::MAX,
+        NonZeroUsize::new(config.download_concurrency)
+            .ok_or_else(|| anyhow!("Download concurrency must be positive"))?,
+    )
+    .await?;
+
+    let (mut object_count, mut package_count, mut wrapped_count) = (0, 0, 0);
+    reader
+        .read_live_objects(|live_objects| {
+            let indexed = index_live_objects(
+                live_objects,
+                config.epoch,
+                checkpoint,
+                config.name_service_registry_id,
+            )
+            .map(|(changes, packages, wrapped)| {
+                object_count += changes.changed_objects.len();
+                package_count += packages.len();
+                wrapped_count += wrapped;
+                (changes, packages)
+            });
+            let committed = object_count;
+            let (store, client, metrics) = (&store, &client, &metrics);
+            async move {
+                let (changes, packages) = indexed?;
+                let publishers =
+                    get_publishers(client, packages.iter().map(|(digest, _)| *digest)).await?;
+                let packages = packages
+                    .iter()
+                    .map(|(digest, package)| {
+                        let publisher = publishers.get(digest).copied().unwrap_or(SuiAddress::ZERO);
+                        Package::new(publisher, package)
+                    })
+                    .collect::<Vec<_>>();
+                store.persist_packages(&packages).await?;
+                store
+                    .persist_object_changes(
+                        &[changes],
+                        metrics.object_mutation_db_commit_latency.clone(),
+                        metrics.object_deletion_db_commit_latency.clone(),
+                        metrics.total_object_change_chunk_committed.clone(),
+                    )
+                    .await?;
+                info!("Committed {} objects of the snapshot", committed);
+                Ok(())
+            }
+        })
+        .await?;
+
+    store
+        .persist_snapshot_bootstrap(SnapshotBootstrap {
+            epoch: config.epoch as i64,
+            checkpoint_sequence_number: checkpoint as i64,
+            object_count: object_count as i64,
+            package_count: package_count as i64,
+        })
+        .await?;
+    info!(
+        "Bootstrapped {} objects and {} packages from the snapshot of epoch {}, skipped {} wrapped objects, \
+        the indexer will start from checkpoint {}",
+        object_count, package_count, config.epoch, wrapped_count, checkpoint
+    );
+    Ok(())
+}
+
+// Objects of a snapshot file as created at the checkpoint of the snapshot, with the
+// packages among them and the number of wrapped objects skipped.
+fn index_live_objects(
+    live_objects: Vec<LiveObject>,
+    epoch: u64,
+    checkpoint: u64,
+    name_service_registry_id: Option<ObjectID>,
+) -> Result<(
+    TransactionObjectChanges,
+    Vec<(TransactionDigest, MovePackage)>,
+    usize,
+)> {
+    let mut changes = TransactionObjectChanges {
+        changed_objects: vec![],
+        deleted_objects: vec![],
+        name_records: vec![],
+    };
+    let mut packages = vec![];
+    let mut wrapped = 0;
+    for live_object in live_objects {
+        // wrapped objects are only keys in the snapshot, they are indexed once unwrapped
+        // or deleted
+        let object = match live_object {
+            LiveObject::Normal(object) => object,
+            LiveObject::Wrapped(_) => {
+                wrapped += 1;
+                continue;
+            }
+        };
+        if let Some(registry_id) = name_service_registry_id {
+            changes.name_records.extend(NameRecord::try_from_object(
+                &object,
+                registry_id,
+                checkpoint,
+            )?);
+        }
+        if let Some(package) = object.data.try_as_package() {
+            packages.push((object.previous_transaction, package.clone()));
+        }
+        changes
+            .changed_objects
+            .push(Object::new(epoch, checkpoint, WriteKind::Create, &object));
+    }
+    Ok((changes, packages, wrapped))
+}
+
+// Binary search for the last checkpoint of `epoch` on the full node.
+async fn find_last_checkpoint_of_epoch(client: &SuiClient, epoch: u64) -> Result<u64> {
+    let epoch_of = |sequence_number: u64| async move {
+        client
+            .read_api()
+            .get_checkpoint(CheckpointId::SequenceNumber(sequence_number))
+            .await
+            .map(|checkpoint| (checkpoint.epoch, checkpoint.end_of_epoch_data.is_some()))
+    };
+    let mut low = 0;
+    let mut high = client
+        .read_api()
+        .get_latest_checkpoint_sequence_number()
+        .await?;
+    if epoch_of(high).await?.0 <= epoch {
+        bail!("Epoch {epoch} has not ended yet");
+    }
+    // the last checkpoint of the epoch is in [low, high)
+    while high - low > 1 {
+        let mid = low + (high - low) / 2;
+        if epoch_of(mid).await?.0 <= epoch {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    let (low_epoch, end_of_epoch) = epoch_of(low).await?;
+    if low_epoch != epoch || !end_of_epoch {
+        bail!("Failed finding the last checkpoint of epoch {epoch}, found checkpoint {low}");
+    }
+    Ok(low)
+}
+
+// Senders of the transactions that published packages, read from the full node.
+async fn get_publishers(
+    client: &SuiClient,
+    digests: impl Iterator<Item = TransactionDigest>,
+) -> Result<HashMap<TransactionDigest, SuiAddress>> {
+    let digests = digests.collect::<Vec<_>>();
+    let mut publishers = HashMap::new();
+    for chunk in digests.chunks(*QUERY_MAX_RESULT_LIMIT) {
+        let responses = client
+            .read_api()
+            .multi_get_transactions_with_options(
+                chunk.to_vec(),
+                SuiTransactionBlockResponseOptions::new().with_input(),
+            )
+            .await?;
+        for response in responses {
+            match response.transaction {
+                Some(transaction) => {
+                    publishers.insert(response.digest, *transaction.data.sender());
+                }
+                None => warn!(
+                    "Full node returned no transaction {}, package author is unknown",
+                    response.digest
+                ),
+            }
+        }
+    }
+    Ok(publishers)
+}
+
+#[derive(Parser)]
+#[clap(name = "Bootstrap From Snapshot")]
+pub struct BootstrapConfig {
+    #[clap(long)]
+    pub db_url: String,
+    // Drop all tables and run the migrations first.
+    #[clap(long)]
+    pub reset_db: bool,
+    #[clap(long)]
+    pub rpc_client_url: String,
+    // Epoch of the snapshot, the snapshot holds the state at the end of the epoch.
+    #[clap(long)]
+    pub epoch: u64,
+    // YAML object store config of the bucket with the formal snapshots.
+    #[clap(long)]
+    pub snapshot_store_config: PathBuf,
+    // Local directory the MANIFEST and REFERENCE files of the snapshot are downloaded to.
+    #[clap(long)]
+    pub staging_dir: PathBuf,
+    #[clap(long, default_value = "16")]
+    pub download_concurrency: usize,
+    #[clap(long)]
+    pub name_service_registry_id: Option<ObjectID>,
+}
@@ -0,0 +1,44 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::Parser;
+use tracing::info;
+
+// Writes checkpoints of a full node as BCS serialized `CheckpointData` files, used as
+// fixtures by the ingestion benchmark.
+#[tokio::main]
+async fn main() -> Result<()> {
+    let _guard = telemetry_subscribers::TelemetryConfig::new()
+        .with_env()
+        .init();
+    let config = DumpConfig::parse();
+    // experimental rest api route is found at `/rest` on the same interface as the jsonrpc
+    // service
+    let client = sui_rest_api::Client::new(format!("{}/rest", config.rpc_client_url));
+    fs::create_dir_all(&config.output_dir)?;
+
+    for sequence_number in config.start_checkpoint..=config.end_checkpoint {
+        let checkpoint = client.get_full_checkpoint(sequence_number).await?;
+        let path = config.output_dir.join(format!("{sequence_number}.chk"));
+        fs::write(&path, bcs::to_bytes(&checkpoint)?)?;
+        info!("Wrote checkpoint {} to {:?}", sequence_number, path);
+    }
+    Ok(())
+}
+
+#[derive(Parser)]
+#[clap(name = "Dump Checkpoints")]
+pub struct DumpConfig {
+    #[clap(long)]
+    pub rpc_client_url: String,
+    #[clap(long)]
+    pub start_checkpoint: u64,
+    #[clap(long)]
+    pub end_checkpoint: u64,
+    #[clap(long, default_value = "benches/fixtures/checkpoints")]
+    pub output_dir: PathBuf,
+}
@@ -0,0 +1,78 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use anyhow::{bail, Result};
+use clap::Parser;
+use prometheus::Registry;
+use tracing::{info, warn};
+
+use sui_indexer::metrics::IndexerMetrics;
+use sui_indexer::new_pg_connection_pool;
+use sui_indexer::store::query_plans::{find_plan_issues, query_shapes};
+use sui_indexer::store::{IndexerStore, PgIndexerStore};
+
+// Explains the canonical query shapes of the store against the schema of a live database,
+// without running them, and reports the ones reading whole tables or sorting many rows,
+// e.g. of deployments with custom or missing indexes. Exits with an error on issues.
+#[tokio::main]
+async fn main() -> Result<()> {
+    let _guard = telemetry_subscribers::TelemetryConfig::new()
+        .with_env()
+        .init();
+    let config = IndexAdvisorConfig::parse();
+    let store = PgIndexerStore::new(
+        new_pg_connection_pool(&config.db_url)?,
+        IndexerMetrics::new(&Registry::default()),
+    );
+
+    let table_rows = store
+        .get_table_sizes()
+        .await?
+        .into_iter()
+        .map(|size| (size.table_name, size.estimated_rows))
+        .collect::<HashMap<_, _>>();
+    let mut shapes_with_issues = 0;
+    for shape in query_shapes() {
+        let (name, sql) = (shape.name, shape.sql());
+        let plan = match store.explain_query(shape).await {
+            Ok(plan) => plan,
+            Err(e) => {
+                warn!("Failed explaining query shape {}: {:?}", name, e);
+                shapes_with_issues += 1;
+                continue;
+            }
+        };
+        let issues = find_plan_issues(&plan, &table_rows, config.min_rows);
+        if issues.is_empty() {
+            info!("{}: ok", name);
+            continue;
+        }
+        shapes_with_issues += 1;
+        for issue in issues {
+            warn!("{}: {}", name, issue);
+        }
+        if config.verbose {
+            info!("{}: {}\n{:#}", name, sql, plan);
+        }
+    }
+    if shapes_with_issues > 0 {
+        bail!("{shapes_with_issues} query shapes read more rows than their pages");
+    }
+    info!("All query shapes are served by indexes");
+    Ok(())
+}
+
+#[derive(Parser)]
+#[clap(name = "Index Advisor")]
+pub struct IndexAdvisorConfig {
+    #[clap(long)]
+    pub db_url: String,
+    // Tables with fewer rows, as estimated by the planner statistics, are fine to scan.
+    #[clap(long, default_value = "10000")]
+    pub min_rows: i64,
+    // Also print the query and plan of the shapes with issues.
+    #[clap(long)]
+    pub verbose: bool,
+}
@@ -0,0 +1,153 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Result};
+use clap::Parser;
+use prometheus::Registry;
+use tracing::{error, info, warn};
+
+use sui_indexer::metrics::IndexerMetrics;
+use sui_indexer::models::objects::{owner_to_owner_info, Object, ObjectStatus};
+use sui_indexer::store::{IndexerStore, PgIndexerStore};
+use sui_indexer::{new_pg_connection_pool, new_rpc_client};
+use sui_json_rpc_types::{SuiObjectDataOptions, SuiObjectResponse};
+use sui_types::base_types::ObjectID;
+use sui_types::error::SuiObjectResponseError;
+
+// Samples objects of the `objects` table and cross-checks their version, digest and
+// owner against the current state of a full node. Exits with an error on drift.
+#[tokio::main]
+async fn main() -> Result<()> {
+    let _guard = telemetry_subscribers::TelemetryConfig::new()
+        .with_env()
+        .init();
+    let config = VerifyObjectsConfig::parse();
+    let store = PgIndexerStore::new(
+        new_pg_connection_pool(&config.db_url)?,
+        IndexerMetrics::new(&Registry::default()),
+    );
+    let client = new_rpc_client(&config.rpc_client_url).await?;
+
+    let objects = store.sample_objects(config.sample_size).await?;
+    info!("Verifying {} sampled objects...", objects.len());
+    let (mut matched, mut behind, mut drifted) = (0, 0, 0);
+    for object in objects {
+        let object_id = ObjectID::from_str(&object.object_id)?;
+        let response = client
+            .read_api()
+            .get_object_with_options(object_id, SuiObjectDataOptions::new().with_owner())
+            .await?;
+        match verify_object(&object, response)? {
+            Verification::Match => matched += 1,
+            Verification::Behind { fullnode_version } => {
+                warn!(
+                    "Object {} is at version {} on the full node, {} in the indexer",
+                    object.object_id, fullnode_version, object.version
+                );
+                behind += 1;
+            }
+            Verification::Drift(drift) => {
+                error!("Object {} drifted: {}", object.object_id, drift);
+                drifted += 1;
+            }
+        }
+    }
+    info!(
+        "Verified objects, matched: {}, behind the full node: {}, drifted: {}",
+        matched, behind, drifted
+    );
+    if drifted > 0 {
+        bail!("{drifted} sampled objects drifted from the full node");
+    }
+    Ok(())
+}
+
+enum Verification {
+    Match,
+    // the object changed on the full node after the version indexed, which is expected
+    // of objects changed in checkpoints the indexer has yet to commit
+    Behind { fullnode_version: u64 },
+    Drift(String),
+}
+
+fn verify_object(object: &Object, response: SuiObjectResponse) -> Result<Verification> {
+    let indexed_deleted = matches!(
+        object.object_status,
+        ObjectStatus::Deleted | ObjectStatus::Wrapped | ObjectStatus::UnwrappedThenDeleted
+    );
+    let indexed_version = object.version as u64;
+    let (fullnode_version, fullnode_deleted) = match (&response.data, &response.error) {
+        (Some(data), _) => (data.version.value(), false),
+        (None, Some(SuiObjectResponseError::Deleted { version, .. })) => (version.value(), true),
+        (None, Some(SuiObjectResponseError::NotExists { .. })) => {
+            return Ok(Verification::Drift(
+                "object does not exist on the full node".to_string(),
+            ))
+        }
+        (None, error) => {
+            return Err(anyhow!(
+                "Failed reading object {} from the full node: {:?}",
+                object.object_id,
+                error
+            ))
+        }
+    };
+
+    if fullnode_version > indexed_version {
+        return Ok(Verification::Behind { fullnode_version });
+    }
+    if fullnode_version < indexed_version {
+        return Ok(Verification::Drift(format!(
+            "version {} is ahead of the full node's {}",
+            indexed_version, fullnode_version
+        )));
+    }
+    if indexed_deleted != fullnode_deleted {
+        return Ok(Verification::Drift(format!(
+            "indexed as {:?} at version {}, deleted on the full node: {}",
+            object.object_status, indexed_version, fullnode_deleted
+        )));
+    }
+    // deleted objects have no owner and their digest only marks the deletion
+    let data = match response.data {
+        Some(data) => data,
+        None => return Ok(Verification::Match),
+    };
+    let (_, _, indexed_digest) = object.get_object_ref()?;
+    if indexed_digest != data.digest {
+        return Ok(Verification::Drift(format!(
+            "digest {} differs from the full node's {}",
+            indexed_digest, data.digest
+        )));
+    }
+    let fullnode_owner =
+        owner_to_owner_info(&data.owner.ok_or_else(|| {
+            anyhow!("Full node returned object {} without owner", data.object_id)
+        })?);
+    let indexed_owner = (
+        object.owner_type.clone(),
+        object.owner_address.clone(),
+        object.initial_shared_version,
+    );
+    if indexed_owner != fullnode_owner {
+        return Ok(Verification::Drift(format!(
+            "owner {:?} differs from the full node's {:?}",
+            indexed_owner, fullnode_owner
+        )));
+    }
+    Ok(Verification::Match)
+}
+
+#[derive(Parser)]
+#[clap(name = "Verify Objects")]
+pub struct VerifyObjectsConfig {
+    #[clap(long)]
+    pub db_url: String,
+    #[clap(long)]
+    pub rpc_client_url: String,
+    // number of objects sampled from the objects table
+    #[clap(long, default_value = "100")]
+    pub sample_size: usize,
+}
@@ -0,0 +1,131 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use jsonrpsee::http_client::{HeaderMap, HeaderValue, HttpClient, HttpClientBuilder};
+use sui_json_rpc::api::IndexerApiClient;
+use sui_json_rpc::{CLIENT_SDK_TYPE_HEADER, CLIENT_SDK_VERSION_HEADER};
+use sui_json_rpc_types::{
+    EventFilter, EventPage, ObjectsPage, SuiObjectResponseQuery, SuiTransactionBlockResponseQuery,
+    TransactionBlocksPage,
+};
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::digests::TransactionDigest;
+use sui_types::event::EventID;
+
+use crate::apis::API_KEY_HEADER;
+use crate::errors::IndexerError;
+
+/// Typed client of the query API served by an indexer, so that Rust services do not have
+/// to build the JSON RPC requests themselves. Pages are returned as served, follow
+/// `next_cursor` to read the next one.
+#[derive(Clone)]
+pub struct IndexerClient {
+    http: HttpClient,
+}
+
+impl IndexerClient {
+    pub fn new(rpc_url: &str) -> Result<Self, IndexerError> {
+        Self::build(rpc_url, None)
+    }
+
+    /// Client of an indexer started with `--rpc-require-api-key`, sending `api_key` with
+    /// every request.
+    pub fn with_api_key(rpc_url: &str, api_key: &str) -> Result<Self, IndexerError> {
+        Self::build(rpc_url, Some(api_key))
+    }
+
+    fn build(rpc_url: &str, api_key: Option<&str>) -> Result<Self, IndexerError> {
+        let http = HttpClientBuilder::default()
+            .max_request_body_size(2 << 30)
+            .set_headers(client_headers(api_key)?)
+            .build(rpc_url)
+            .map_err(|e| {
+                IndexerError::HttpClientInitError(format!(
+                    "Failed to initialize indexer RPC client with error: {:?}",
+                    e
+                ))
+            })?;
+        Ok(Self { http })
+    }
+
+    pub async fn query_transactions(
+        &self,
+        query: SuiTransactionBlockResponseQuery,
+        cursor: Option<TransactionDigest>,
+        limit: Option<usize>,
+        descending_order: bool,
+    ) -> Result<TransactionBlocksPage, IndexerError> {
+        self.http
+            .query_transaction_blocks(query, cursor, limit, Some(descending_order))
+            .await
+            .map_err(|e| IndexerError::RpcClientError(e.to_string()))
+    }
+
+    pub async fn get_owned_objects(
+        &self,
+        address: SuiAddress,
+        query: Option<SuiObjectResponseQuery>,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> Result<ObjectsPage, IndexerError> {
+        self.http
+            .get_owned_objects(address, query, cursor, limit)
+            .await
+            .map_err(|e| IndexerError::RpcClientError(e.to_string()))
+    }
+
+    pub async fn get_events(
+        &self,
+        filter: EventFilter,
+        cursor: Option<EventID>,
+        limit: Option<usize>,
+        descending_order: bool,
+    ) -> Result<EventPage, IndexerError> {
+        self.http
+            .query_events(filter, cursor, limit, Some(descending_order))
+            .await
+            .map_err(|e| IndexerError::RpcClientError(e.to_string()))
+    }
+}
+
+fn client_headers(api_key: Option<&str>) -> Result<HeaderMap, IndexerError> {
+    let mut headers = HeaderMap::new();
+    headers.insert(CLIENT_SDK_TYPE_HEADER, HeaderValue::from_static("rust"));
+    headers.insert(
+        CLIENT_SDK_VERSION_HEADER,
+        HeaderValue::from_static(env!("CARGO_PKG_VERSION")),
+    );
+    if let Some(api_key) = api_key {
+        let mut value = HeaderValue::from_str(api_key).map_err(|_| {
+            IndexerError::InvalidArgumentError("API key is not a valid header value".to_string())
+        })?;
+        value.set_sensitive(true);
+        headers.insert(API_KEY_HEADER, value);
+    }
+    Ok(headers)
+}
+
+#[cfg(test)]
+mod test {
+    use sui_json_rpc::{CLIENT_SDK_TYPE_HEADER, CLIENT_SDK_VERSION_HEADER};
+
+    use crate::apis::API_KEY_HEADER;
+
+    use super::client_headers;
+
+    #[test]
+    fn test_client_headers() {
+        let headers = client_headers(None).unwrap();
+        assert_eq!(headers[CLIENT_SDK_TYPE_HEADER], "rust");
+        assert_eq!(
+            headers[CLIENT_SDK_VERSION_HEADER],
+            env!("CARGO_PKG_VERSION")
+        );
+        assert!(!headers.contains_key(API_KEY_HEADER));
+
+        let headers = client_headers(Some("secret")).unwrap();
+        assert_eq!(headers[API_KEY_HEADER], "secret");
+        assert!(headers[API_KEY_HEADER].is_sensitive());
+        assert!(client_headers(Some("bad\nkey")).is_err());
+    }
+}
@@ -57,6 +57,9 @@ pub enum IndexerError {
     #[error("Indexer failed to read PostgresDB with error: `{0}`")]
     PostgresReadError(String),
 
+    #[error("Indexer query exceeded its statement timeout with error: `{0}`")]
+    QueryTimeout(String),
+
     #[error("Indexer failed to reset PostgresDB with error: `{0}`")]
     PostgresResetError(String),
 
@@ -69,6 +72,9 @@ pub enum IndexerError {
     #[error("Indexer failed to initialize fullnode Http client with error: `{0}`")]
     HttpClientInitError(String),
 
+    #[error("Indexer RPC request failed with error: `{0}`")]
+    RpcClientError(String),
+
     #[error("Indexer failed to serialize/deserialize with error: `{0}`")]
     SerdeError(String),
 
@@ -104,6 +110,21 @@ pub enum IndexerError {
 
     #[error("Indexer failed to send item to channel with error: `{0}`")]
     MpscChannelError(String),
+
+    #[error("Indexer found inconsistent checkpoint data: `{0}`")]
+    DataInconsistency(String),
+
+    #[error("Indexer failed to deserialize Move module with error: `{0}`")]
+    ModuleDeserializationError(String),
+
+    #[error("Indexer did not commit checkpoint {0} within {1:?}")]
+    CheckpointWaitTimeout(i64, std::time::Duration),
+
+    #[error("Indexer lost the leader lock `{0}`")]
+    LeaderLockLost(String),
+
+    #[error("Indexer is serving too many requests: `{0}`")]
+    TooManyRequests(String),
 }
 
 pub trait Context<T> {
@@ -127,3 +148,9 @@ impl From<tokio::task::JoinError> for IndexerError {
         IndexerError::UncategorizedError(anyhow::Error::from(value))
     }
 }
+
+impl From<typed_store::rocks::TypedStoreError> for IndexerError {
+    fn from(value: typed_store::rocks::TypedStoreError) -> Self {
+        IndexerError::UncategorizedError(anyhow::Error::from(value))
+    }
+}
@@ -0,0 +1,246 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use futures::stream::FuturesOrdered;
+use futures::StreamExt;
+use sui_rest_api::{CheckpointData, Client};
+use sui_types::messages_checkpoint::{CertifiedCheckpointSummary, CheckpointSequenceNumber};
+
+use super::endpoints::FullNodeEndpoints;
+use super::memory_budget::BudgetedSender;
+use crate::metrics::IndexerMetrics;
+
+/// Downloads checkpoints from the full node and sends them in order to the processing
+/// pipeline. The number of concurrent downloads starts at the configured concurrency and
+/// follows the observed download latency and errors, see [`AdaptiveConcurrency`]. Memory
+/// held by downloaded checkpoints is bounded by the budget of the sender instead.
+pub struct CheckpointDownloader {
+    client: FullNodeEndpoints<Client>,
+    sender: BudgetedSender<CheckpointData>,
+    concurrency: AdaptiveConcurrency,
+    metrics: IndexerMetrics,
+}
+
+impl CheckpointDownloader {
+    // concurrency is kept between these fractions and multiples of the configured one
+    const MIN_CONCURRENCY_DIVISOR: usize = 4;
+    const MAX_CONCURRENCY_MULTIPLIER: usize = 2;
+    // a request taking longer than this fails over to the next endpoint
+    const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+    pub fn new(
+        client: FullNodeEndpoints<Client>,
+        sender: BudgetedSender<CheckpointData>,
+        concurrency: usize,
+        metrics: IndexerMetrics,
+    ) -> Self {
+        Self {
+            client,
+            sender,
+            concurrency: AdaptiveConcurrency::new(
+                concurrency,
+                (concurrency / Self::MIN_CONCURRENCY_DIVISOR).max(1),
+                concurrency * Self::MAX_CONCURRENCY_MULTIPLIER,
+            ),
+            metrics,
+        }
+    }
+
+    pub async fn get_latest_checkpoint(&self) -> Result<CertifiedCheckpointSummary> {
+        self.client
+            .call(
+                |client| async move {
+                    tokio::time::timeout(Self::REQUEST_TIMEOUT, client.get_latest_checkpoint())
+                        .await?
+                },
+                |_| true,
+            )
+            .await
+    }
+
+    /// Downloads `checkpoints` and sends them in order, calling `on_sent` with the
+    /// sequence number of each checkpoint sent. Stops at the first failed download,
+    /// checkpoints before it are still sent.
+    pub async fn download(
+        &mut self,
+        checkpoints: impl IntoIterator<Item = CheckpointSequenceNumber>,
+        mut on_sent: impl FnMut(CheckpointSequenceNumber),
+    ) -> Result<()> {
+        let mut checkpoints = checkpoints.into_iter();
+        let mut in_flight = FuturesOrdered::new();
+        loop {
+            while in_flight.len() < self.concurrency.limit() {
+                match checkpoints.next() {
+                    Some(sequence_number) => {
+                        in_flight.push_back(self.download_checkpoint(sequence_number))
+                    }
+                    None => break,
+                }
+            }
+            let (latency, checkpoint) = match in_flight.next().await {
+                Some(downloaded) => downloaded,
+                None => break,
+            };
+            let checkpoint = match checkpoint {
+                Ok(checkpoint) => checkpoint,
+                Err(e) => {
+                    self.concurrency.record_error();
+                    self.metrics
+                        .checkpoint_download_concurrency
+                        .set(self.concurrency.limit() as i64);
+                    return Err(e);
+                }
+            };
+            self.metrics
+                .fullnode_checkpoint_data_download_latency
+                .observe(latency.as_secs_f64());
+
+            let sequence_number = *checkpoint.checkpoint_summary.sequence_number();
+            self.send(checkpoint).await?;
+            on_sent(sequence_number);
+
+            self.concurrency.record(latency);
+            self.metrics
+                .checkpoint_download_concurrency
+                .set(self.concurrency.limit() as i64);
+        }
+        Ok(())
+    }
+
+    /// Sends a checkpoint obtained elsewhere than the full node, e.g. from a genesis blob,
+    /// to the processing pipeline.
+    pub async fn send(&self, checkpoint: CheckpointData) -> Result<()> {
+        // BCS size as an estimate of the memory held by the checkpoint
+        let size = bcs::serialized_size(&checkpoint)?;
+        self.sender
+            .send(checkpoint, size)
+            .await
+            .expect("channel shouldn't be closed");
+        Ok(())
+    }
+
+    fn download_checkpoint(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+    ) -> impl std::future::Future<Output = (Duration, Result<CheckpointData>)> {
+        let client = self.client.clone();
+        async move {
+            let start = Instant::now();
+            let checkpoint = client
+                .call(
+                    |client| async move {
+                        tokio::time::timeout(
+                            Self::REQUEST_TIMEOUT,
+                            client.get_full_checkpoint(sequence_number),
+                        )
+                        .await?
+                    },
+                    |_| true,
+                )
+                .await;
+            (start.elapsed(), checkpoint)
+        }
+    }
+}
+
+/// Concurrency limit adjusted once per window of `limit` completed downloads. It grows
+/// while latency stays close to the lowest seen, and halves down to `min` when latency
+/// rises, as the full node is saturated, or right away when a download fails. A backed
+/// up pipeline leaves it alone, senders wait on the memory budget of the pipeline.
+pub struct AdaptiveConcurrency {
+    limit: usize,
+    min: usize,
+    max: usize,
+    min_latency: Option<Duration>,
+    avg_latency: Option<Duration>,
+    completed_in_window: usize,
+}
+
+impl AdaptiveConcurrency {
+    // average latency above this multiple of the lowest latency is considered saturation
+    const LATENCY_TOLERANCE: u32 = 2;
+    const LATENCY_EWMA_WEIGHT: f64 = 0.2;
+
+    pub fn new(initial: usize, min: usize, max: usize) -> Self {
+        assert!(min > 0 && min <= initial && initial <= max);
+        Self {
+            limit: initial,
+            min,
+            max,
+            min_latency: None,
+            avg_latency: None,
+            completed_in_window: 0,
+        }
+    }
+
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Records a completed download.
+    pub fn record(&mut self, latency: Duration) {
+        self.min_latency = Some(self.min_latency.map_or(latency, |min| min.min(latency)));
+        let avg_latency = self.avg_latency.map_or(latency, |avg| {
+            avg.mul_f64(1.0 - Self::LATENCY_EWMA_WEIGHT)
+                + latency.mul_f64(Self::LATENCY_EWMA_WEIGHT)
+        });
+        self.avg_latency = Some(avg_latency);
+
+        self.completed_in_window += 1;
+        if self.completed_in_window < self.limit {
+            return;
+        }
+        self.completed_in_window = 0;
+
+        // unwrap is safe b/c min_latency was set above.
+        let saturated = avg_latency > self.min_latency.unwrap() * Self::LATENCY_TOLERANCE;
+        if saturated {
+            self.decrease();
+        } else {
+            self.limit = (self.limit + (self.limit / 10).max(1)).min(self.max);
+        }
+    }
+
+    /// Records a failed download, the downloads of the window are started over.
+    pub fn record_error(&mut self) {
+        self.completed_in_window = 0;
+        self.decrease();
+    }
+
+    fn decrease(&mut self) {
+        self.limit = (self.limit / 2).max(self.min);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::AdaptiveConcurrency;
+
+    #[test]
+    fn test_adaptive_concurrency() {
+        let mut concurrency = AdaptiveConcurrency::new(10, 2, 20);
+        assert_eq!(concurrency.limit(), 10);
+        let latency = Duration::from_millis(100);
+        for _ in 0..200 {
+            concurrency.record(latency);
+        }
+        assert_eq!(concurrency.limit(), 20);
+
+        // a failed download halves concurrency right away
+        concurrency.record_error();
+        assert_eq!(concurrency.limit(), 10);
+
+        // the full node slows down, down to the floor
+        for _ in 0..40 {
+            concurrency.record(latency * 10);
+        }
+        assert_eq!(concurrency.limit(), 2);
+        concurrency.record_error();
+        assert_eq!(concurrency.limit(), 2);
+    }
+}
@@ -0,0 +1,160 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fmt::Display;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use tracing::warn;
+
+use crate::metrics::IndexerMetrics;
+
+/// Clients of one or more full node endpoints. Requests are spread over the endpoints
+/// round-robin and fail over to the next endpoint on error, trying endpoints that
+/// failed their last request only after the healthy ones. Endpoints are logged and
+/// labeled by their index, never their URL, which may carry credentials.
+#[derive(Clone)]
+pub struct FullNodeEndpoints<C> {
+    endpoints: Arc<Vec<Endpoint<C>>>,
+    next: Arc<AtomicUsize>,
+    metrics: IndexerMetrics,
+}
+
+struct Endpoint<C> {
+    // index of the endpoint in the configured URLs
+    label: String,
+    client: C,
+    healthy: AtomicBool,
+}
+
+impl<C: Clone> FullNodeEndpoints<C> {
+    pub fn new(clients: Vec<C>, metrics: IndexerMetrics) -> Self {
+        assert!(
+            !clients.is_empty(),
+            "At least one full node endpoint is required"
+        );
+        let endpoints = clients
+            .into_iter()
+            .enumerate()
+            .map(|(index, client)| {
+                let label = index.to_string();
+                metrics
+                    .fullnode_endpoint_healthy
+                    .with_label_values(&[&label])
+                    .set(1);
+                Endpoint {
+                    label,
+                    client,
+                    healthy: AtomicBool::new(true),
+                }
+            })
+            .collect();
+        Self {
+            endpoints: Arc::new(endpoints),
+            next: Arc::new(AtomicUsize::new(0)),
+            metrics,
+        }
+    }
+
+    /// Sends `request` to the next endpoint in turn. Errors for which `fail_over` returns
+    /// true are retried on the other endpoints, the last error is returned if all of
+    /// them fail.
+    pub async fn call<T, E, F, Fut>(&self, request: F, fail_over: fn(&E) -> bool) -> Result<T, E>
+    where
+        F: Fn(C) -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+        E: Display,
+    {
+        let len = self.endpoints.len();
+        let start = self.next.fetch_add(1, Ordering::Relaxed) % len;
+        let (healthy, unhealthy): (Vec<_>, Vec<_>) = (0..len)
+            .map(|i| &self.endpoints[(start + i) % len])
+            .partition(|endpoint| endpoint.healthy.load(Ordering::Relaxed));
+
+        let mut last_err = None;
+        for endpoint in healthy.into_iter().chain(unhealthy) {
+            self.metrics
+                .fullnode_endpoint_request
+                .with_label_values(&[&endpoint.label])
+                .inc();
+            match request(endpoint.client.clone()).await {
+                Ok(response) => {
+                    self.set_health(endpoint, true);
+                    return Ok(response);
+                }
+                Err(e) if fail_over(&e) => {
+                    warn!(
+                        "Request to full node endpoint {} failed, failing over: {}",
+                        endpoint.label, e
+                    );
+                    self.metrics
+                        .fullnode_endpoint_error
+                        .with_label_values(&[&endpoint.label])
+                        .inc();
+                    self.set_health(endpoint, false);
+                    last_err = Some(e);
+                }
+                // the endpoint responded, the error is not its fault
+                Err(e) => {
+                    self.set_health(endpoint, true);
+                    return Err(e);
+                }
+            }
+        }
+        // unwrap is safe b/c there is at least one endpoint and each failed.
+        Err(last_err.unwrap())
+    }
+
+    fn set_health(&self, endpoint: &Endpoint<C>, healthy: bool) {
+        endpoint.healthy.store(healthy, Ordering::Relaxed);
+        self.metrics
+            .fullnode_endpoint_healthy
+            .with_label_values(&[&endpoint.label])
+            .set(healthy as i64);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use prometheus::Registry;
+
+    use super::FullNodeEndpoints;
+    use crate::metrics::IndexerMetrics;
+
+    #[tokio::test]
+    async fn test_fail_over_to_healthy_endpoint() {
+        let metrics = IndexerMetrics::new(&Registry::new());
+        let endpoints = FullNodeEndpoints::new(vec![false, true], metrics.clone());
+        let request = |up: bool| async move {
+            if up {
+                Ok(())
+            } else {
+                Err("unavailable")
+            }
+        };
+
+        for _ in 0..4 {
+            endpoints.call(request, |_| true).await.unwrap();
+        }
+        // the down endpoint is only tried first once, after that it is tried last
+        assert_eq!(
+            metrics
+                .fullnode_endpoint_request
+                .with_label_values(&["0"])
+                .get(),
+            1
+        );
+        assert_eq!(
+            metrics
+                .fullnode_endpoint_healthy
+                .with_label_values(&["0"])
+                .get(),
+            0
+        );
+        assert!(endpoints
+            .call(|_| async { Err::<(), _>("bad request") }, |_| false)
+            .await
+            .is_err());
+    }
+}
@@ -1,41 +1,71 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::Result;
-use sui_rest_api::{CheckpointData, Client};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use sui_config::genesis::Genesis;
+use sui_rest_api::CheckpointData;
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
-use tracing::{info, warn};
+use tracing::{info, trace, warn};
+
+use super::downloader::CheckpointDownloader;
+use crate::handlers::checkpoint_queue::IndexerShard;
 
 pub struct CheckpointFetcher {
-    client: Client,
+    downloader: CheckpointDownloader,
     last_downloaded_checkpoint: Option<CheckpointSequenceNumber>,
     highest_known_checkpoint: CheckpointSequenceNumber,
-    sender: mysten_metrics::metered_channel::Sender<CheckpointData>,
+    // sent instead of downloading checkpoint 0, for full nodes without its data
+    genesis: Option<CheckpointData>,
+    shard: IndexerShard,
 }
 
 impl CheckpointFetcher {
     const INTERVAL_PERIOD: std::time::Duration = std::time::Duration::from_secs(5);
-    const CHECKPOINT_DOWNLOAD_CONCURRENCY: usize = 100;
 
     pub fn new(
-        client: Client,
+        downloader: CheckpointDownloader,
         last_downloaded_checkpoint: Option<CheckpointSequenceNumber>,
-        sender: mysten_metrics::metered_channel::Sender<CheckpointData>,
     ) -> Self {
         Self {
-            client,
+            downloader,
             last_downloaded_checkpoint,
             highest_known_checkpoint: 0,
-            sender,
+            genesis: None,
+            shard: IndexerShard::default(),
         }
     }
 
+    /// Downloads only the checkpoints of `shard`.
+    pub fn with_shard(mut self, shard: IndexerShard) -> Self {
+        self.shard = shard;
+        self
+    }
+
+    /// Starts from `genesis` if no checkpoint was downloaded yet.
+    pub fn with_genesis(mut self, genesis: CheckpointData) -> Self {
+        self.genesis = Some(genesis);
+        self
+    }
+
     pub async fn run(mut self) {
         let mut interval = tokio::time::interval(Self::INTERVAL_PERIOD);
         interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
 
         info!("CheckpointFetcher started");
 
+        if let Some(genesis) = self.genesis.take() {
+            if self.last_downloaded_checkpoint.is_none() && self.shard.contains(0) {
+                info!("Sending genesis checkpoint of the genesis blob");
+                self.downloader
+                    .send(genesis)
+                    .await
+                    .expect("genesis checkpoint should be sent");
+                self.last_downloaded_checkpoint = Some(0);
+            }
+        }
+
         loop {
             interval.tick().await;
 
@@ -52,46 +82,48 @@ impl CheckpointFetcher {
     }
 
     async fn update_highest_known_checkpoint(&mut self) -> Result<()> {
-        let checkpoint = self.client.get_latest_checkpoint().await?;
+        let checkpoint = self.downloader.get_latest_checkpoint().await?;
         self.highest_known_checkpoint =
             std::cmp::max(self.highest_known_checkpoint, *checkpoint.sequence_number());
         Ok(())
     }
 
     async fn download_checkpoints(&mut self) -> Result<()> {
-        use futures::StreamExt;
-        use tap::Pipe;
-
         let checkpoint_range = self
             .last_downloaded_checkpoint
             .map(|i| i.saturating_add(1))
             .unwrap_or(0)..=self.highest_known_checkpoint;
 
-        if !checkpoint_range.is_empty() {
-            info!("Starting download of checkpoints {checkpoint_range:?}");
-        }
-
-        let mut checkpoint_stream = checkpoint_range
-            .map(|next| self.client.get_full_checkpoint(next))
-            .pipe(futures::stream::iter)
-            .buffered(Self::CHECKPOINT_DOWNLOAD_CONCURRENCY);
-
-        while let Some(maybe_checkpoint) = checkpoint_stream.next().await {
-            let checkpoint = maybe_checkpoint?;
-            self.last_downloaded_checkpoint =
-                Some(*checkpoint.checkpoint_summary.sequence_number());
-
-            info!(
-                checkpoint = checkpoint.checkpoint_summary.sequence_number(),
-                "successfully downloaded checkpoint"
-            );
-
-            self.sender
-                .send(checkpoint)
-                .await
-                .expect("channel shouldn't be closed");
+        if checkpoint_range.is_empty() {
+            return Ok(());
         }
-
-        Ok(())
+        info!("Starting download of checkpoints {checkpoint_range:?}");
+
+        let last_downloaded_checkpoint = &mut self.last_downloaded_checkpoint;
+        self.downloader
+            .download(
+                self.shard.sequence_numbers(checkpoint_range),
+                |checkpoint| {
+                    *last_downloaded_checkpoint = Some(checkpoint);
+                    trace!(checkpoint, "successfully downloaded checkpoint");
+                },
+            )
+            .await
     }
 }
+
+/// Checkpoint data of the genesis checkpoint of the genesis blob at `path`.
+pub fn genesis_checkpoint_data(path: &Path) -> Result<CheckpointData> {
+    let genesis =
+        Genesis::load(path).with_context(|| format!("Failed loading genesis blob {path:?}"))?;
+    Ok(CheckpointData {
+        checkpoint_summary: genesis.checkpoint().into_inner(),
+        checkpoint_contents: genesis.checkpoint_contents().clone(),
+        transactions: vec![(
+            genesis.transaction().clone(),
+            genesis.effects().clone(),
+            Some(genesis.events().clone()),
+        )],
+        objects: genesis.objects().to_vec(),
+    })
+}
@@ -1,6 +1,8 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::sync::Arc;
+
 use anyhow::Result;
 use sui_rest_api::CheckpointData;
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
@@ -8,7 +10,7 @@ use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 #[async_trait::async_trait]
 pub trait Handler: Send {
     fn name(&self) -> &str;
-    async fn process_checkpoint(&mut self, checkpoint_data: &CheckpointData) -> Result<()>;
+    async fn process_checkpoint(&mut self, checkpoint_data: &Arc<CheckpointData>) -> Result<()>;
 }
 
 pub trait BackfillHandler: Handler {
@@ -18,7 +20,7 @@ pub trait BackfillHandler: Handler {
 #[async_trait::async_trait]
 pub trait OutOfOrderHandler: Send + Sync {
     fn name(&self) -> &str;
-    async fn process_checkpoint(&self, checkpoint_data: &CheckpointData) -> Result<()>;
+    async fn process_checkpoint(&self, checkpoint_data: &Arc<CheckpointData>) -> Result<()>;
 }
 
 #[async_trait::async_trait]
@@ -27,7 +29,7 @@ impl<T: OutOfOrderHandler> Handler for T {
         OutOfOrderHandler::name(self)
     }
 
-    async fn process_checkpoint(&mut self, checkpoint_data: &CheckpointData) -> Result<()> {
+    async fn process_checkpoint(&mut self, checkpoint_data: &Arc<CheckpointData>) -> Result<()> {
         OutOfOrderHandler::process_checkpoint(self, checkpoint_data).await
     }
 }
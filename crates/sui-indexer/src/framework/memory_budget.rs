@@ -0,0 +1,124 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use futures::{Stream, StreamExt};
+use mysten_metrics::metered_channel;
+use prometheus::IntGauge;
+use tokio::sync::mpsc::error::SendError;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+// the budget is accounted in KiB so that budgets over 4GiB fit in semaphore permits
+const BUDGET_UNIT_BYTES: usize = 1024;
+
+/// Creates a channel bounded both by the number of items and by the total estimated
+/// size of the items buffered in it. The size of an item is released when the item
+/// is taken from the returned stream.
+pub fn budgeted_channel<T>(
+    size: usize,
+    memory_budget_bytes: usize,
+    gauge: &IntGauge,
+    buffered_bytes: IntGauge,
+) -> (BudgetedSender<T>, impl Stream<Item = T> + Unpin) {
+    let (sender, receiver) = metered_channel::channel(size, gauge);
+    let budget_units = (memory_budget_bytes / BUDGET_UNIT_BYTES).clamp(1, u32::MAX as usize);
+    let sender = BudgetedSender {
+        sender,
+        size,
+        budget: Arc::new(Semaphore::new(budget_units)),
+        budget_units: budget_units as u32,
+        buffered_bytes,
+    };
+    let stream = metered_channel::ReceiverStream::new(receiver).map(|(item, _bytes)| item);
+    (sender, stream)
+}
+
+pub struct BudgetedSender<T> {
+    sender: metered_channel::Sender<(T, BufferedBytes)>,
+    size: usize,
+    budget: Arc<Semaphore>,
+    budget_units: u32,
+    buffered_bytes: IntGauge,
+}
+
+impl<T> BudgetedSender<T> {
+    /// Sends `item` of estimated size `bytes`, waiting until both a slot and enough of
+    /// the memory budget are free. An item larger than the whole budget waits for the
+    /// channel to be drained and is then sent alone.
+    pub async fn send(&self, item: T, bytes: usize) -> Result<(), SendError<T>> {
+        let units = (((bytes + BUDGET_UNIT_BYTES - 1) / BUDGET_UNIT_BYTES) as u32)
+            .clamp(1, self.budget_units);
+        let permit = match self.budget.clone().acquire_many_owned(units).await {
+            Ok(permit) => permit,
+            Err(_) => return Err(SendError(item)),
+        };
+        self.buffered_bytes.add(bytes as i64);
+        let buffered = BufferedBytes {
+            _permit: permit,
+            bytes,
+            gauge: self.buffered_bytes.clone(),
+        };
+        self.sender
+            .send((item, buffered))
+            .await
+            .map_err(|SendError((item, _))| SendError(item))
+    }
+
+    /// Fill ratio of the channel in [0, 1], the higher of its slots and memory budget.
+    pub fn backlog(&self) -> f64 {
+        let slots = 1.0 - self.sender.capacity() as f64 / self.size as f64;
+        let memory = 1.0 - self.budget.available_permits() as f64 / self.budget_units as f64;
+        slots.max(memory)
+    }
+}
+
+// Share of the memory budget held by an item in the channel.
+struct BufferedBytes {
+    _permit: OwnedSemaphorePermit,
+    bytes: usize,
+    gauge: IntGauge,
+}
+
+impl Drop for BufferedBytes {
+    fn drop(&mut self) {
+        self.gauge.sub(self.bytes as i64);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use futures::StreamExt;
+    use prometheus::IntGauge;
+
+    use super::budgeted_channel;
+
+    #[tokio::test]
+    async fn test_budgeted_channel() {
+        let buffered_bytes = IntGauge::new("buffered_bytes", "test").unwrap();
+        let (sender, mut receiver) = budgeted_channel(
+            10,
+            4096,
+            &IntGauge::new("channel", "test").unwrap(),
+            buffered_bytes.clone(),
+        );
+
+        sender.send(1, 3000).await.unwrap();
+        assert_eq!(buffered_bytes.get(), 3000);
+        assert_eq!(sender.backlog(), 0.75);
+        // does not fit in the remaining budget until the first item is taken
+        let send = sender.send(2, 2000);
+        tokio::pin!(send);
+        assert!(futures::poll!(&mut send).is_pending());
+
+        assert_eq!(receiver.next().await, Some(1));
+        send.await.unwrap();
+        assert_eq!(buffered_bytes.get(), 2000);
+        assert_eq!(receiver.next().await, Some(2));
+        assert_eq!(buffered_bytes.get(), 0);
+
+        // larger than the whole budget, sent once the channel is empty
+        sender.send(3, 10_000).await.unwrap();
+        assert_eq!(receiver.next().await, Some(3));
+    }
+}
@@ -1,6 +1,10 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod downloader;
+pub mod endpoints;
 pub mod fetcher;
 pub mod interface;
+pub mod memory_budget;
+pub mod row_batch;
 pub mod runner;
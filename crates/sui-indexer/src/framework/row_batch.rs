@@ -0,0 +1,108 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use futures::stream::{self, Stream, StreamExt};
+use futures::FutureExt;
+use tokio::time::Instant;
+
+/// Groups the items of `stream` into batches of about `max_rows` rows, as counted by
+/// `rows`. A batch takes the items that are ready right away and is emitted as soon as
+/// `stream` has nothing ready, so that items are not held back at the tip of the stream.
+/// While items keep being ready, e.g. catching up, a batch is emitted once it reaches
+/// `max_rows`, or `max_latency` after its first item was received, whichever comes first.
+/// Items are never split, so a batch exceeds `max_rows` by at most the rows of its last
+/// item.
+pub fn batch_by_rows<S, F>(
+    stream: S,
+    max_rows: usize,
+    max_latency: Duration,
+    rows: F,
+) -> impl Stream<Item = Vec<S::Item>>
+where
+    S: Stream + Unpin,
+    F: Fn(&S::Item) -> usize,
+{
+    stream::unfold(
+        (stream, rows, false),
+        move |(mut stream, rows, ended)| async move {
+            if ended {
+                return None;
+            }
+            let first = stream.next().await?;
+            let deadline = Instant::now() + max_latency;
+            let mut batch_rows = rows(&first);
+            let mut batch = vec![first];
+            let mut ended = false;
+            while batch_rows < max_rows && Instant::now() < deadline {
+                match stream.next().now_or_never() {
+                    Some(Some(item)) => {
+                        batch_rows += rows(&item);
+                        batch.push(item);
+                    }
+                    Some(None) => {
+                        ended = true;
+                        break;
+                    }
+                    // nothing ready, flush rather than wait for more items
+                    None => break,
+                }
+            }
+            Some((batch, (stream, rows, ended)))
+        },
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use futures::StreamExt;
+    use tokio::sync::mpsc;
+    use tokio_stream::wrappers::ReceiverStream;
+
+    use super::batch_by_rows;
+
+    #[tokio::test]
+    async fn test_batch_by_rows() {
+        // items are their own row counts
+        let items = futures::stream::iter(vec![3, 4, 1, 10, 2]);
+        let batches = batch_by_rows(items, 5, Duration::from_secs(60), |rows| *rows)
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(batches, vec![vec![3, 4], vec![1, 10], vec![2]]);
+    }
+
+    #[tokio::test]
+    async fn test_batch_flushed_when_nothing_ready() {
+        let (sender, receiver) = mpsc::channel(10);
+        let mut batches = Box::pin(batch_by_rows(
+            ReceiverStream::new(receiver),
+            100,
+            Duration::from_secs(60),
+            |rows: &usize| *rows,
+        ));
+
+        sender.send(1).await.unwrap();
+        sender.send(2).await.unwrap();
+        // well below max_rows, flushed without waiting for max_latency
+        let batch = tokio::time::timeout(Duration::from_secs(1), batches.next()).await;
+        assert_eq!(batch.unwrap(), Some(vec![1, 2]));
+
+        sender.send(3).await.unwrap();
+        drop(sender);
+        assert_eq!(batches.next().await, Some(vec![3]));
+        assert_eq!(batches.next().await, None);
+    }
+
+    #[tokio::test]
+    async fn test_batch_flushed_after_max_latency() {
+        // items keep being ready, batches are cut by max_latency
+        let items = futures::stream::iter(vec![1, 2, 3]);
+        let batches = batch_by_rows(items, 100, Duration::ZERO, |rows| *rows)
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(batches, vec![vec![1], vec![2], vec![3]]);
+    }
+}
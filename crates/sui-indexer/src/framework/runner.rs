@@ -1,23 +1,33 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::sync::Arc;
+
+use anyhow::Result;
 use sui_rest_api::CheckpointData;
 
 use super::interface::Handler;
 
-pub async fn run<S>(mut stream: S, mut handlers: Vec<Box<dyn Handler>>)
+/// Feeds every checkpoint of `stream` to all handlers, stopping with the error of the first
+/// handler failing a checkpoint.
+pub async fn run<S>(mut stream: S, mut handlers: Vec<Box<dyn Handler>>) -> Result<()>
 where
     S: futures::Stream<Item = CheckpointData> + std::marker::Unpin,
 {
     use futures::StreamExt;
 
     while let Some(checkpoint) = stream.next().await {
+        // shared by the handlers, which may hand it on to their own tasks
+        let checkpoint = Arc::new(checkpoint);
         //TODO create tracing spans for processing
         futures::future::join_all(
             handlers
                 .iter_mut()
-                .map(|handler| async { handler.process_checkpoint(&checkpoint).await.unwrap() }),
+                .map(|handler| handler.process_checkpoint(&checkpoint)),
         )
-        .await;
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>>>()?;
     }
+    Ok(())
 }
@@ -0,0 +1,129 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::{anyhow, Context, Result};
+use futures::{StreamExt, TryStreamExt};
+use mysten_metrics::{get_metrics, metered_channel, spawn_monitored_task};
+use object_store::path::Path as ObjectPath;
+use object_store::DynObjectStore;
+use tokio::task::JoinHandle;
+use tracing::{error, info};
+
+use sui_rest_api::CheckpointData;
+use sui_storage::object_store::util::put;
+use sui_storage::object_store::ObjectStoreConfig;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+
+use crate::framework::interface::Handler;
+use crate::metrics::IndexerMetrics;
+
+const ARCHIVE_QUEUE_SIZE: usize = 100;
+const ARCHIVE_UPLOAD_CONCURRENCY: usize = 10;
+// 0 selects the default zstd level
+const ZSTD_LEVEL: i32 = 0;
+
+/// Writes every checkpoint as zstd compressed BCS `CheckpointData` to an object store,
+/// keyed by sequence number. Uploads run in the background, concurrently and in any
+/// order, ingestion only waits on them when the upload queue is full. An upload that still
+/// fails after its retries stops archiving, and ingestion of the next checkpoint fails with
+/// its error rather than going on with a gap in the archive.
+pub struct CheckpointArchiver {
+    sender: metered_channel::Sender<Arc<CheckpointData>>,
+    task: Option<JoinHandle<Result<()>>>,
+}
+
+impl CheckpointArchiver {
+    pub fn new(store: Arc<DynObjectStore>, metrics: IndexerMetrics) -> Self {
+        let (sender, receiver) = metered_channel::channel(
+            ARCHIVE_QUEUE_SIZE,
+            &get_metrics()
+                .unwrap()
+                .channels
+                .with_label_values(&["checkpoint_archiving"]),
+        );
+        let task = spawn_monitored_task!(archive_checkpoints(store, metrics, receiver));
+        Self {
+            sender,
+            task: Some(task),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Handler for CheckpointArchiver {
+    fn name(&self) -> &str {
+        "checkpoint-archiver"
+    }
+
+    async fn process_checkpoint(&mut self, checkpoint_data: &Arc<CheckpointData>) -> Result<()> {
+        if self.sender.send(checkpoint_data.clone()).await.is_ok() {
+            return Ok(());
+        }
+        // the task only stops when an upload failed, report why
+        Err(match self.task.take() {
+            Some(task) => match task.await {
+                Ok(Err(e)) => e,
+                Ok(Ok(())) => anyhow!("Checkpoint archiving task has stopped"),
+                Err(e) => anyhow!("Checkpoint archiving task panicked: {e}"),
+            },
+            None => anyhow!("Checkpoint archiving task has stopped"),
+        })
+    }
+}
+
+async fn archive_checkpoints(
+    store: Arc<DynObjectStore>,
+    metrics: IndexerMetrics,
+    receiver: metered_channel::Receiver<Arc<CheckpointData>>,
+) -> Result<()> {
+    info!("Indexer checkpoint archiving task started...");
+    let metrics = &metrics;
+    metered_channel::ReceiverStream::new(receiver)
+        .map(|checkpoint| {
+            let store = store.clone();
+            async move {
+                let sequence_number = *checkpoint.checkpoint_summary.sequence_number();
+                let bytes =
+                    tokio::task::spawn_blocking(move || encode_checkpoint(&checkpoint)).await??;
+                put(&checkpoint_path(sequence_number), bytes.into(), store)
+                    .await
+                    .with_context(|| format!("Failed uploading checkpoint {sequence_number}"))?;
+                Ok::<_, anyhow::Error>(sequence_number)
+            }
+        })
+        .buffer_unordered(ARCHIVE_UPLOAD_CONCURRENCY)
+        // uploads are retried with backoff before failing
+        .inspect_err(|e| {
+            error!("Failed archiving checkpoint with error: {e:?}");
+            metrics.total_checkpoint_archive_failure.inc();
+        })
+        .try_for_each(|_| async move {
+            metrics.total_checkpoint_archived.inc();
+            Ok::<_, anyhow::Error>(())
+        })
+        .await
+}
+
+/// Object store described by the YAML `ObjectStoreConfig` at `path`.
+pub fn make_object_store(path: &Path) -> Result<Arc<DynObjectStore>> {
+    let config: ObjectStoreConfig = serde_yaml::from_reader(
+        File::open(path).with_context(|| format!("Failed opening object store config {path:?}"))?,
+    )
+    .with_context(|| format!("Failed parsing object store config {path:?}"))?;
+    config.make()
+}
+
+pub fn checkpoint_path(sequence_number: CheckpointSequenceNumber) -> ObjectPath {
+    ObjectPath::from(format!("{sequence_number}.chk.zst"))
+}
+
+pub fn encode_checkpoint(checkpoint: &CheckpointData) -> Result<Vec<u8>> {
+    Ok(zstd::encode_all(
+        bcs::to_bytes(checkpoint)?.as_slice(),
+        ZSTD_LEVEL,
+    )?)
+}
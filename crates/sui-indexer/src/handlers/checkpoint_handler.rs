@@ -1,58 +1,124 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use cached::{Cached, SizedCache, TimedSizedCache};
 use fastcrypto::traits::ToFromBytes;
+use futures::future::join_all;
 use itertools::Itertools;
+use move_binary_format::CompiledModule;
+use move_bytecode_utils::module_cache::GetModule;
 use move_core_types::ident_str;
+use move_core_types::language_storage::ModuleId;
 use mysten_metrics::{get_metrics, spawn_monitored_task};
-use std::collections::HashMap;
+use rayon::prelude::*;
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use sui_rest_api::CheckpointData;
 use sui_types::committee::EpochId;
-use sui_types::effects::{TransactionEffects, TransactionEffectsAPI};
+use sui_types::effects::{TransactionEffects, TransactionEffectsAPI, TransactionEvents};
+use sui_types::move_package::MovePackage;
 use sui_types::object::Owner;
-use sui_types::transaction::TransactionDataAPI;
+use sui_types::transaction::{
+    CallArg, Command, InputObjectKind, ProgrammableMoveCall, TransactionDataAPI, TransactionKind,
+};
 use tap::tap::TapFallible;
-use tracing::{error, info, warn};
+use tokio::task::JoinHandle;
+use tracing::{error, info, trace, warn};
 
-use sui_types::base_types::ObjectID;
-use sui_types::messages_checkpoint::{CheckpointCommitment, CheckpointSequenceNumber};
+use sui_json_rpc_types::{
+    SuiTransactionBlock, SuiTransactionBlockEffects, SuiTransactionBlockResponse,
+};
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::digests::ChainIdentifier;
+use sui_types::messages_checkpoint::{CheckpointSequenceNumber, CheckpointSummary};
 use sui_types::sui_system_state::sui_system_state_summary::SuiSystemStateSummary;
 use sui_types::sui_system_state::{get_sui_system_state, SuiSystemStateTrait};
 use sui_types::SUI_SYSTEM_ADDRESS;
 
 use crate::errors::IndexerError;
 use crate::framework::interface::Handler;
+use crate::framework::row_batch::batch_by_rows;
+use crate::handlers::checkpoint_notifier::CheckpointNotifier;
+use crate::handlers::checkpoint_queue::{
+    start_checkpoint_dequeue_task, start_checkpoint_enqueue_task,
+};
+use crate::handlers::pipelines::{Pipeline, Pipelines};
+use crate::handlers::source_verification::SourceVerificationClient;
 use crate::metrics::IndexerMetrics;
-use crate::models::checkpoints::Checkpoint;
+use crate::models::addresses::CheckpointAddressActivity;
+use crate::models::checkpoint_proofs::CheckpointProof;
+use crate::models::checkpoint_queue::QueueKind;
+use crate::models::checkpoints::{commitments_to_column, Checkpoint, CheckpointTxDigest};
 use crate::models::epoch::{DBEpochInfo, SystemEpochInfoEvent};
 use crate::models::events::Event;
+use crate::models::hot_entities::CheckpointHotEntities;
+use crate::models::name_service::NameRecord;
 use crate::models::objects::{DeletedObject, ObjectStatus};
+use crate::models::ownership_history::OwnershipTransition;
 use crate::models::packages::Package;
+use crate::models::protocol_configs::DBProtocolConfig;
+use crate::models::system_state::DBValidatorEpochEvent;
 use crate::models::transaction_index::ChangedObject;
 use crate::models::transaction_index::InputObject;
 use crate::models::transaction_index::MoveCall;
 use crate::models::transaction_index::Recipient;
-use crate::models::transactions::Transaction;
+use crate::models::transaction_index::TxCommand;
+use crate::models::transaction_index::TxDependency;
+use crate::models::transactions::{
+    expiration_epoch, gas_payment_columns, ExecutionFailure, Transaction, TransactionJson,
+};
+use crate::models::transfers::Transfer;
+use crate::models::tx_calls::{PureType, TxCall};
+use crate::models::validator_participation::CheckpointSigners;
+use crate::models::watchlists::{Watchlist, WatchlistMatch};
 use crate::store::{
     IndexerStore, TemporaryCheckpointStore, TemporaryEpochStore, TransactionObjectChanges,
 };
 use crate::IndexerConfig;
 
-const CHECKPOINT_QUEUE_SIZE: usize = 1000;
 const EPOCH_QUEUE_LIMIT: usize = 20;
+// packages published by recently indexed checkpoints, called before the objects committer
+// persists them
+const RECENT_PACKAGE_CACHE_SIZE: usize = 1_000;
+// called modules missing from the store are waited for, as indexers of other shards or the
+// objects committer may not have committed their packages yet
+const CALLED_MODULE_LOOKUP_ATTEMPTS: usize = 10;
+const CALLED_MODULE_RETRY_INTERVAL: Duration = Duration::from_millis(500);
+// called modules still missing from the store after the retries are not looked up again for
+// a while, so that calls to them do not stall every checkpoint
+const UNRESOLVED_MODULE_CACHE_SIZE: usize = 1_000;
+const UNRESOLVED_MODULE_CACHE_TTL_SECS: u64 = 600;
+// reads of the previous checkpoint, for the continuity check of network total transactions,
+// failing this many times in a row halt the checkpoint commit
+const PREVIOUS_CHECKPOINT_READ_ATTEMPTS: usize = 10;
+
+// object changes of a checkpoint, with the epoch the checkpoint ends if any
+pub type CheckpointObjectChanges = (
+    CheckpointSequenceNumber,
+    Option<EpochId>,
+    Vec<TransactionObjectChanges>,
+);
+
+// Task committing the indexed checkpoints, which only stops on its own when they cannot be
+// committed. Its error is reported by the handler next failing to send it a checkpoint.
+type CommitTask = Arc<Mutex<Option<JoinHandle<Result<(), IndexerError>>>>>;
+
+struct IndexingSenders {
+    checkpoint: mysten_metrics::metered_channel::Sender<TemporaryCheckpointStore>,
+    objects: mysten_metrics::metered_channel::Sender<CheckpointObjectChanges>,
+    epoch: mysten_metrics::metered_channel::Sender<TemporaryEpochStore>,
+}
 
-pub fn new_handlers<S>(
-    state: S,
-    metrics: IndexerMetrics,
-    config: &IndexerConfig,
-) -> (CheckpointProcessor<S>, ObjectsProcessor<S>)
-where
-    S: IndexerStore + Clone + Sync + Send + 'static,
-{
-    let checkpoint_queue_size = std::env::var("CHECKPOINT_QUEUE_SIZE")
-        .unwrap_or(CHECKPOINT_QUEUE_SIZE.to_string())
-        .parse::<usize>()
-        .unwrap();
+struct IndexingReceivers {
+    checkpoint: mysten_metrics::metered_channel::Receiver<TemporaryCheckpointStore>,
+    objects: mysten_metrics::metered_channel::Receiver<CheckpointObjectChanges>,
+    epoch: mysten_metrics::metered_channel::Receiver<TemporaryEpochStore>,
+}
+
+fn indexing_channels(config: &IndexerConfig) -> (IndexingSenders, IndexingReceivers) {
+    let checkpoint_queue_size = config.checkpoint_queue_size();
     let global_metrics = get_metrics().unwrap();
     let (tx_indexing_sender, tx_indexing_receiver) = mysten_metrics::metered_channel::channel(
         checkpoint_queue_size,
@@ -76,55 +142,237 @@ where
             .with_label_values(&["checkpoint_epoch_indexing"]),
     );
 
-    let state_clone = state.clone();
-    let metrics_clone = metrics.clone();
-    let config_clone = config.clone();
-    spawn_monitored_task!(start_tx_checkpoint_commit_task(
-        state_clone,
-        metrics_clone,
-        config_clone,
-        tx_indexing_receiver,
+    (
+        IndexingSenders {
+            checkpoint: tx_indexing_sender,
+            objects: object_indexing_sender,
+            epoch: epoch_indexing_sender,
+        },
+        IndexingReceivers {
+            checkpoint: tx_indexing_receiver,
+            objects: object_indexing_receiver,
+            epoch: epoch_indexing_receiver,
+        },
+    )
+}
+
+pub fn new_handlers<S>(
+    state: S,
+    metrics: IndexerMetrics,
+    config: &IndexerConfig,
+    pipelines: Pipelines,
+) -> Result<(CheckpointProcessor<S>, ObjectsProcessor<S>), IndexerError>
+where
+    S: IndexerStore + Clone + Sync + Send + 'static,
+{
+    let source_verifier = new_source_verifier(config)?;
+    let (senders, receivers) = indexing_channels(config);
+    let commit_task = spawn_commit_tasks(&state, &metrics, config, &pipelines, receivers);
+    Ok(new_processors(
+        state,
+        metrics,
+        config,
+        pipelines,
+        senders,
+        source_verifier,
+        Some(commit_task),
+    ))
+}
+
+/// Handlers of an indexer of a split pipeline, which queue the indexed checkpoints in the
+/// checkpoint_queue table for the committer instead of committing them.
+pub fn new_queueing_handlers<S>(
+    state: S,
+    metrics: IndexerMetrics,
+    config: &IndexerConfig,
+    pipelines: Pipelines,
+) -> Result<(CheckpointProcessor<S>, ObjectsProcessor<S>), IndexerError>
+where
+    S: IndexerStore + Clone + Sync + Send + 'static,
+{
+    let source_verifier = new_source_verifier(config)?;
+    let (senders, receivers) = indexing_channels(config);
+    let max_queued = config.max_queued_checkpoints as i64;
+    spawn_monitored_task!(start_checkpoint_enqueue_task(
+        state.clone(),
+        metrics.clone(),
+        QueueKind::Transactions,
+        receivers.checkpoint,
+        |checkpoint: &TemporaryCheckpointStore| checkpoint.checkpoint.sequence_number,
+        Some(max_queued),
+    ));
+    spawn_monitored_task!(start_checkpoint_enqueue_task(
+        state.clone(),
+        metrics.clone(),
+        QueueKind::Objects,
+        receivers.objects,
+        |(checkpoint_seq, _, _): &CheckpointObjectChanges| *checkpoint_seq as i64,
+        Some(max_queued),
+    ));
+    spawn_monitored_task!(start_checkpoint_enqueue_task(
+        state.clone(),
+        metrics.clone(),
+        QueueKind::Epochs,
+        receivers.epoch,
+        |epoch: &TemporaryEpochStore| epoch.new_epoch.epoch,
+        None,
+    ));
+    Ok(new_processors(
+        state,
+        metrics,
+        config,
+        pipelines,
+        senders,
+        source_verifier,
+        None,
+    ))
+}
+
+/// Starts the committer of a split pipeline, committing the checkpoints queued by its
+/// indexers in order. The returned task only ends when the checkpoints cannot be committed.
+pub fn start_queue_committer<S>(
+    state: S,
+    metrics: IndexerMetrics,
+    config: &IndexerConfig,
+    pipelines: Pipelines,
+) -> JoinHandle<Result<(), IndexerError>>
+where
+    S: IndexerStore + Clone + Sync + Send + 'static,
+{
+    let (senders, receivers) = indexing_channels(config);
+    let commit_task = spawn_commit_tasks(&state, &metrics, config, &pipelines, receivers);
+    spawn_monitored_task!(start_checkpoint_dequeue_task(
+        state.clone(),
+        metrics.clone(),
+        QueueKind::Transactions,
+        senders.checkpoint,
+    ));
+    spawn_monitored_task!(start_checkpoint_dequeue_task(
+        state.clone(),
+        metrics.clone(),
+        QueueKind::Objects,
+        senders.objects,
+    ));
+    spawn_monitored_task!(start_checkpoint_dequeue_task(
+        state,
+        metrics,
+        QueueKind::Epochs,
+        senders.epoch,
+    ));
+    commit_task
+}
+
+fn spawn_commit_tasks<S>(
+    state: &S,
+    metrics: &IndexerMetrics,
+    config: &IndexerConfig,
+    pipelines: &Pipelines,
+    receivers: IndexingReceivers,
+) -> JoinHandle<Result<(), IndexerError>>
+where
+    S: IndexerStore + Clone + Sync + Send + 'static,
+{
+    let commit_task = spawn_monitored_task!(start_tx_checkpoint_commit_task(
+        state.clone(),
+        metrics.clone(),
+        config.clone(),
+        pipelines.clone(),
+        receivers.checkpoint,
     ));
 
-    let state_clone = state.clone();
-    let metrics_clone = metrics.clone();
     spawn_monitored_task!(start_epoch_commit_task(
-        state_clone,
-        metrics_clone,
-        epoch_indexing_receiver,
+        state.clone(),
+        metrics.clone(),
+        receivers.epoch,
     ));
 
-    let state_clone = state.clone();
-    let metrics_clone = metrics.clone();
-    let config_clone = config.clone();
     spawn_monitored_task!(start_object_checkpoint_commit_task(
-        state_clone,
-        metrics_clone,
-        config_clone,
-        object_indexing_receiver,
+        state.clone(),
+        metrics.clone(),
+        config.clone(),
+        receivers.objects,
     ));
+    commit_task
+}
+
+// Error of the commit task once it stopped, which it did with an error unless it panicked.
+async fn commit_task_error(commit_task: &CommitTask, what: &str) -> IndexerError {
+    let task = commit_task.lock().unwrap().take();
+    let stopped = || IndexerError::MpscChannelError(format!("{what} commit task has stopped"));
+    match task {
+        Some(task) => match task.await {
+            Ok(Err(e)) => e,
+            Ok(Ok(())) => stopped(),
+            Err(e) => e.into(),
+        },
+        // reported by another handler sharing the task
+        None => stopped(),
+    }
+}
+
+/// Client of the configured source verification service, an invalid url is a config error
+/// reported before any handler task is started.
+fn new_source_verifier(
+    config: &IndexerConfig,
+) -> Result<Option<SourceVerificationClient>, IndexerError> {
+    config
+        .source_verification_url
+        .as_deref()
+        .map(SourceVerificationClient::new)
+        .transpose()
+}
 
+fn new_processors<S>(
+    state: S,
+    metrics: IndexerMetrics,
+    config: &IndexerConfig,
+    pipelines: Pipelines,
+    senders: IndexingSenders,
+    source_verifier: Option<SourceVerificationClient>,
+    commit_task: Option<JoinHandle<Result<(), IndexerError>>>,
+) -> (CheckpointProcessor<S>, ObjectsProcessor<S>)
+where
+    S: IndexerStore + Clone + Sync + Send + 'static,
+{
     let checkpoint_processor = CheckpointProcessor {
         state: state.clone(),
         metrics: metrics.clone(),
-        epoch_indexing_sender,
-        checkpoint_sender: tx_indexing_sender,
+        epoch_indexing_sender: senders.epoch,
+        checkpoint_sender: senders.checkpoint,
+        commit_task: Arc::new(Mutex::new(commit_task)),
+        index_transactions_json: config.index_transactions_json,
+        pipelines,
+        recent_packages: Arc::new(Mutex::new(SizedCache::with_size(RECENT_PACKAGE_CACHE_SIZE))),
+        unresolved_modules: Arc::new(Mutex::new(TimedSizedCache::with_size_and_lifespan(
+            UNRESOLVED_MODULE_CACHE_SIZE,
+            UNRESOLVED_MODULE_CACHE_TTL_SECS,
+        ))),
     };
 
     let object_processor = ObjectsProcessor {
         metrics,
-        object_indexing_sender,
+        object_indexing_sender: senders.objects,
         state,
+        source_verifier,
+        skip_inconsistent_checkpoints: config.skip_inconsistent_checkpoints,
+        halt_on_unparseable_packages: config.halt_on_unparseable_packages,
+        name_service_registry_id: config.name_service_registry_id,
     };
 
     (checkpoint_processor, object_processor)
 }
 
+#[derive(Clone)]
 pub struct CheckpointProcessor<S> {
     state: S,
     metrics: IndexerMetrics,
     epoch_indexing_sender: mysten_metrics::metered_channel::Sender<TemporaryEpochStore>,
     checkpoint_sender: mysten_metrics::metered_channel::Sender<TemporaryCheckpointStore>,
+    commit_task: CommitTask,
+    index_transactions_json: bool,
+    pipelines: Pipelines,
+    recent_packages: Arc<Mutex<SizedCache<ObjectID, MovePackage>>>,
+    unresolved_modules: Arc<Mutex<TimedSizedCache<ModuleId, ()>>>,
 }
 
 #[async_trait::async_trait]
@@ -136,23 +384,34 @@ where
         "checkpoint-transaction-and-epoch-indexer"
     }
 
-    async fn process_checkpoint(&mut self, checkpoint_data: &CheckpointData) -> anyhow::Result<()> {
-        info!(
+    async fn process_checkpoint(
+        &mut self,
+        checkpoint_data: &Arc<CheckpointData>,
+    ) -> anyhow::Result<()> {
+        trace!(
             checkpoint_seq = checkpoint_data.checkpoint_summary.sequence_number(),
             "Checkpoint received by indexing processor"
         );
         // Index checkpoint data
         let index_timer = self.metrics.checkpoint_index_latency.start_timer();
 
-        let (checkpoint, epoch) = Self::index_checkpoint_and_epoch(&self.state, checkpoint_data)
-            .await
-            .tap_err(|e| {
-                error!(
-                    "Failed to index checkpoints {:?} with error: {}",
-                    checkpoint_data,
-                    e.to_string()
-                );
-            })?;
+        let (checkpoint, epoch) = Self::index_checkpoint_and_epoch(
+            &self.state,
+            &self.metrics,
+            &self.recent_packages,
+            &self.unresolved_modules,
+            checkpoint_data,
+            self.index_transactions_json,
+            &self.pipelines,
+        )
+        .await
+        .tap_err(|e| {
+            error!(
+                "Failed to index checkpoints {:?} with error: {}",
+                checkpoint_data,
+                e.to_string()
+            );
+        })?;
         let elapsed = index_timer.stop_and_record();
 
         // commit first epoch immediately, send other epochs to channel to be committed later.
@@ -180,37 +439,42 @@ where
             }
         }
         let seq = checkpoint.checkpoint.sequence_number;
-        info!(
+        trace!(
             checkpoint_seq = seq,
-            elapsed, "Checkpoint indexing finished, about to sending to commit handler"
+            elapsed,
+            "Checkpoint indexing finished, about to sending to commit handler"
         );
         // NOTE: when the channel is full, checkpoint_sender_guard will wait until the channel has space.
         // Checkpoints are sent sequentially to stick to the order of checkpoint sequence numbers.
-        self.checkpoint_sender
-            .send(checkpoint)
-            .await
-            .tap_ok(|_| info!(checkpoint_seq = seq, "Checkpoint sent to commit handler"))
-            .unwrap_or_else(|e| {
-                panic!(
-                    "checkpoint channel send should not fail, but got error: {:?}",
-                    e
-                )
-            });
+        if self.checkpoint_sender.send(checkpoint).await.is_err() {
+            return Err(commit_task_error(&self.commit_task, "Checkpoint")
+                .await
+                .into());
+        }
+        trace!(checkpoint_seq = seq, "Checkpoint sent to commit handler");
 
         Ok(())
     }
 }
 
+// Object store over the changed objects of a checkpoint, an object mutated by several
+// transactions of the checkpoint appears once per version.
 struct CheckpointDataObjectStore<'a> {
     objects: &'a [sui_types::object::Object],
 }
 
 impl<'a> sui_types::storage::ObjectStore for CheckpointDataObjectStore<'a> {
+    // Returns the object as of the end of the checkpoint, i.e. its highest version.
     fn get_object(
         &self,
         object_id: &ObjectID,
     ) -> Result<Option<sui_types::object::Object>, sui_types::error::SuiError> {
-        Ok(self.objects.iter().find(|o| o.id() == *object_id).cloned())
+        Ok(self
+            .objects
+            .iter()
+            .filter(|o| o.id() == *object_id)
+            .max_by_key(|o| o.version())
+            .cloned())
     }
 
     fn get_object_by_key(
@@ -230,7 +494,7 @@ impl<S> CheckpointProcessor<S>
 where
     S: IndexerStore + Clone + Sync + Send + 'static,
 {
-    async fn index_epoch(
+    pub(crate) async fn index_epoch(
         state: &S,
         data: &CheckpointData,
     ) -> Result<Option<TemporaryEpochStore>, IndexerError> {
@@ -243,8 +507,9 @@ where
 
         let checkpoint_object_store = CheckpointDataObjectStore { objects };
 
-        // NOTE: Index epoch when object checkpoint index has reached the same checkpoint,
-        // because epoch info is based on the latest system state object by the current checkpoint.
+        // NOTE: epoch info is derived from the system state object written by the checkpoint
+        // itself rather than the latest system state, so replays and backfills of historical
+        // epochs record the validator set and gas price of that epoch.
         let epoch_index = if checkpoint_summary.epoch() == 0
             && *checkpoint_summary.sequence_number() == 0
         {
@@ -256,6 +521,10 @@ where
                 .iter()
                 .map(|v| (system_state.epoch, v.clone()).into())
                 .collect();
+            let validator_epoch_events = DBValidatorEpochEvent::from_system_state(&system_state);
+            let chain = ChainIdentifier::from(*checkpoint_summary.digest()).chain();
+            let protocol_config =
+                DBProtocolConfig::new(system_state.epoch, system_state.protocol_version, chain)?;
 
             Some(TemporaryEpochStore {
                 last_epoch: None,
@@ -267,6 +536,8 @@ where
                 },
                 system_state: system_state.into(),
                 validators,
+                validator_epoch_events,
+                protocol_config,
             })
         } else if let Some(end_of_epoch_data) = &checkpoint_summary.end_of_epoch_data {
             let system_state = get_sui_system_state(&checkpoint_object_store)?;
@@ -285,22 +556,27 @@ where
             let event = epoch_event
                 .map(|e| bcs::from_bytes::<SystemEpochInfoEvent>(&e.contents))
                 .transpose()?;
+            // Epoch change in safe mode does not emit SystemEpochInfoEvent, record such
+            // epochs without end of epoch economics and flag them.
+            let safe_mode = event.is_none();
+            if safe_mode {
+                warn!(
+                    "Epoch {} ended in safe mode without SystemEpochInfoEvent at checkpoint {}.",
+                    checkpoint_summary.epoch(),
+                    checkpoint_summary.sequence_number()
+                );
+                state.indexer_metrics().total_safe_mode_epoch.inc();
+            }
+            let event = event.as_ref();
 
             let validators = system_state
                 .active_validators
                 .iter()
                 .map(|v| (system_state.epoch, v.clone()).into())
                 .collect();
+            let validator_epoch_events = DBValidatorEpochEvent::from_system_state(&system_state);
 
-            let epoch_commitments = end_of_epoch_data
-                .epoch_commitments
-                .iter()
-                .map(|c| match c {
-                    CheckpointCommitment::ECMHLiveObjectSetDigest(d) => {
-                        Some(d.digest.into_inner().to_vec())
-                    }
-                })
-                .collect();
+            let epoch_commitments = commitments_to_column(&end_of_epoch_data.epoch_commitments);
 
             let (next_epoch_committee, next_epoch_committee_stake) =
                 end_of_epoch_data.next_epoch_committee.iter().fold(
@@ -312,7 +588,20 @@ where
                     },
                 );
 
-            let event = event.as_ref();
+            // recorded at startup, also for databases without the genesis checkpoint, e.g.
+            // bootstrapped from a snapshot
+            let chain = state
+                .get_chain_identifier()
+                .await?
+                .ok_or_else(|| {
+                    IndexerError::DataInconsistency(
+                        "No chain identifier recorded for the protocol config of the new epoch"
+                            .to_string(),
+                    )
+                })?
+                .chain()?;
+            let protocol_config =
+                DBProtocolConfig::new(system_state.epoch, system_state.protocol_version, chain)?;
 
             let last_epoch = system_state.epoch as i64 - 1;
             let network_tx_count_prev_epoch = state
@@ -345,6 +634,7 @@ where
                     storage_rebate: event.map(|e| e.storage_rebate),
                     leftover_storage_fund_inflow: event.map(|e| e.leftover_storage_fund_inflow),
                     epoch_commitments,
+                    safe_mode,
                 }),
                 new_epoch: DBEpochInfo {
                     epoch: system_state.epoch as i64,
@@ -354,6 +644,8 @@ where
                 },
                 system_state: system_state.into(),
                 validators,
+                validator_epoch_events,
+                protocol_config,
             })
         } else {
             None
@@ -362,129 +654,185 @@ where
         Ok(epoch_index)
     }
 
+    // Modules of the functions called with pure arguments. Packages published in the
+    // checkpoint or in recently indexed ones may not be committed yet, their modules are read
+    // from the checkpoint objects and the recent packages first. The remaining modules are
+    // read from the store, retried for a while as their packages may be committed by the
+    // indexer of another shard. Modules that stay unresolved are remembered in
+    // `unresolved_modules` and not looked up again until they expire from it. Pure arguments
+    // of calls to unresolved modules are counted in `total_unresolved_call_arguments`.
+    async fn resolve_called_modules(
+        state: &S,
+        metrics: &IndexerMetrics,
+        recent_packages: &Mutex<SizedCache<ObjectID, MovePackage>>,
+        unresolved_modules: &Mutex<TimedSizedCache<ModuleId, ()>>,
+        transactions: &[(
+            sui_types::transaction::Transaction,
+            TransactionEffects,
+            Option<TransactionEvents>,
+        )],
+        objects: &[sui_types::object::Object],
+    ) -> Result<HashMap<ModuleId, Arc<CompiledModule>>, IndexerError> {
+        let calls = transactions
+            .iter()
+            .filter_map(|(tx, _, _)| match tx.transaction_data().kind() {
+                TransactionKind::ProgrammableTransaction(pt) => Some(pt),
+                _ => None,
+            })
+            .flat_map(|pt| {
+                pt.commands.iter().filter_map(|command| match command {
+                    Command::MoveCall(call) if has_pure_arguments(call, &pt.inputs) => Some((
+                        ModuleId::new(call.package.into(), call.module.clone()),
+                        count_pure_arguments(call, &pt.inputs),
+                    )),
+                    _ => None,
+                })
+            })
+            .collect::<Vec<_>>();
+        let packages = {
+            let mut recent_packages = recent_packages.lock().unwrap();
+            for package in objects.iter().filter_map(|o| o.data.try_as_package()) {
+                recent_packages.cache_set(package.id(), package.clone());
+            }
+            calls
+                .iter()
+                .map(|(id, _)| ObjectID::from(*id.address()))
+                .collect::<HashSet<_>>()
+                .into_iter()
+                .filter_map(|id| recent_packages.cache_get(&id).map(|p| (id, p.clone())))
+                .collect::<HashMap<_, _>>()
+        };
+        let called = calls.iter().map(|(id, _)| id).collect::<HashSet<_>>();
+        let known_unresolved = {
+            let mut unresolved_modules = unresolved_modules.lock().unwrap();
+            called
+                .iter()
+                .filter(|id| unresolved_modules.cache_get(**id).is_some())
+                .map(|id| (*id).clone())
+                .collect::<Vec<_>>()
+        };
+        let mut modules = HashMap::new();
+        let mut unresolved = vec![];
+        for id in called {
+            let bytes = packages
+                .get(&ObjectID::from(*id.address()))
+                .and_then(|p| p.serialized_module_map().get(id.name().as_str()));
+            match bytes.map(|bytes| CompiledModule::deserialize_with_defaults(bytes)) {
+                Some(Ok(module)) => {
+                    modules.insert(id.clone(), Arc::new(module));
+                }
+                Some(Err(e)) => warn!("Failed to deserialize module {id}: {e}"),
+                None if known_unresolved.contains(id) => {}
+                None => unresolved.push(id.clone()),
+            }
+        }
+        for attempt in 1..=CALLED_MODULE_LOOKUP_ATTEMPTS {
+            if unresolved.is_empty() {
+                break;
+            }
+            if attempt > 1 {
+                tokio::time::sleep(CALLED_MODULE_RETRY_INTERVAL).await;
+            }
+            let resolved = state.get_modules(unresolved.clone()).await?;
+            unresolved.retain(|id| !resolved.contains_key(id));
+            modules.extend(resolved);
+        }
+        {
+            // known ones are not set again, so that they expire
+            let mut unresolved_modules = unresolved_modules.lock().unwrap();
+            for id in &unresolved {
+                unresolved_modules.cache_set(id.clone(), ());
+            }
+        }
+        // known ones may since have been published in a recent checkpoint
+        unresolved.extend(
+            known_unresolved
+                .into_iter()
+                .filter(|id| !modules.contains_key(id)),
+        );
+        if !unresolved.is_empty() {
+            let unresolved_arguments = calls
+                .iter()
+                .filter(|(id, _)| !modules.contains_key(id))
+                .map(|(_, arguments)| *arguments as u64)
+                .sum::<u64>();
+            warn!(
+                "Failed to resolve modules {unresolved:?}, {unresolved_arguments} pure arguments of their calls are not decoded"
+            );
+            metrics
+                .total_unresolved_call_arguments
+                .inc_by(unresolved_arguments);
+        }
+        Ok(modules)
+    }
+
     async fn index_checkpoint_and_epoch(
         state: &S,
+        metrics: &IndexerMetrics,
+        recent_packages: &Mutex<SizedCache<ObjectID, MovePackage>>,
+        unresolved_modules: &Mutex<TimedSizedCache<ModuleId, ()>>,
         data: &CheckpointData,
+        index_transactions_json: bool,
+        pipelines: &Pipelines,
     ) -> Result<(TemporaryCheckpointStore, Option<TemporaryEpochStore>), IndexerError> {
         let CheckpointData {
             transactions,
             checkpoint_summary,
             checkpoint_contents,
-            objects: _,
+            objects,
         } = data;
 
-        let mut db_transactions = Vec::new();
-        let mut db_events = Vec::new();
-        let mut db_input_objects = Vec::new();
-        let mut db_changed_objects = Vec::new();
-        let mut db_move_calls = Vec::new();
-        let mut db_recipients = Vec::new();
-
-        for (tx, fx, events) in transactions {
-            let transaction_digest = tx.digest();
-            let tx = tx.transaction_data();
-
-            let db_txn = Transaction {
-                id: None,
-                transaction_digest: transaction_digest.base58_encode(),
-                sender: tx.sender().to_string(),
-                checkpoint_sequence_number: Some(*checkpoint_summary.sequence_number() as i64),
-                timestamp_ms: Some(checkpoint_summary.timestamp_ms as i64),
-                transaction_kind: tx.kind().name().to_owned(),
-                transaction_count: tx.kind().num_commands() as i64,
-                execution_success: fx.status().is_ok(),
-                gas_object_id: fx.gas_object().0 .0.to_string(),
-                gas_object_sequence: fx.gas_object().0 .1.value() as i64,
-                gas_object_digest: fx.gas_object().0 .2.to_string(),
-                gas_budget: tx.gas_budget() as i64,
-                total_gas_cost: fx.gas_cost_summary().net_gas_usage(),
-                computation_cost: fx.gas_cost_summary().computation_cost as i64,
-                storage_cost: fx.gas_cost_summary().storage_cost as i64,
-                storage_rebate: fx.gas_cost_summary().storage_rebate as i64,
-                non_refundable_storage_fee: fx.gas_cost_summary().non_refundable_storage_fee as i64,
-                gas_price: tx.gas_price() as i64,
-                raw_transaction: bcs::to_bytes(&tx).unwrap(),
-                transaction_effects_content: serde_json::to_string(&fx).unwrap(),
-                confirmed_local_execution: None,
-            };
-
-            db_transactions.push(db_txn);
-
-            db_events.extend(events.iter().flat_map(|events| &events.data).map(|event| {
-                Event::from_sui_event(event, transaction_digest, checkpoint_summary.timestamp_ms)
-            }));
-
-            // Input Objects
-            db_input_objects.extend(
-                tx.input_objects()
-                    .expect("committed txns have been validated")
-                    .into_iter()
-                    .map(|obj_kind| InputObject {
-                        id: None,
-                        transaction_digest: transaction_digest.to_string(),
-                        checkpoint_sequence_number: *checkpoint_summary.sequence_number() as i64,
-                        epoch: checkpoint_summary.epoch() as i64,
-                        object_id: obj_kind.object_id().to_string(),
-                        object_version: obj_kind.version().map(|v| v.value() as i64),
-                    }),
-            );
-
-            // Changed Objects
-            db_changed_objects.extend(fx.all_changed_objects().into_iter().map(
-                |(object_ref, _owner, write_kind)| ChangedObject {
-                    id: None,
-                    transaction_digest: transaction_digest.to_string(),
-                    checkpoint_sequence_number: *checkpoint_summary.sequence_number() as i64,
-                    epoch: checkpoint_summary.epoch() as i64,
-                    object_id: object_ref.0.to_string(),
-                    object_change_type: crate::types::write_kind_to_str(write_kind).to_string(),
-                    object_version: object_ref.1.value() as i64,
-                },
-            ));
-
-            // Move Calls
-            if let sui_types::transaction::TransactionKind::ProgrammableTransaction(pt) = tx.kind()
-            {
-                db_move_calls.extend(pt.commands.clone().into_iter().filter_map(move |command| {
-                    match command {
-                        sui_types::transaction::Command::MoveCall(m) => Some(MoveCall {
-                            id: None,
-                            transaction_digest: transaction_digest.to_string(),
-                            checkpoint_sequence_number: *checkpoint_summary.sequence_number()
-                                as i64,
-                            epoch: checkpoint_summary.epoch() as i64,
-                            sender: tx.sender().to_string(),
-                            move_package: m.package.to_string(),
-                            move_module: m.module.to_string(),
-                            move_function: m.function.to_string(),
-                        }),
-                        _ => None,
-                    }
-                }));
-            }
-
-            // Recipients
-            db_recipients.extend(
-                fx.all_changed_objects()
-                    .into_iter()
-                    .filter_map(|(_object_ref, owner, _write_kind)| match owner {
-                        Owner::AddressOwner(address) => Some(address.to_string()),
-                        _ => None,
-                    })
-                    .unique()
-                    .map(|recipient| Recipient {
-                        id: None,
-                        transaction_digest: transaction_digest.to_string(),
-                        checkpoint_sequence_number: *checkpoint_summary.sequence_number() as i64,
-                        epoch: checkpoint_summary.epoch() as i64,
-                        sender: tx.sender().to_string(),
-                        recipient,
-                    }),
-            );
-        }
-
+        let indexed = index_transactions(checkpoint_summary, transactions, pipelines);
+        let tx_calls = if pipelines.is_enabled(Pipeline::TxIndices) {
+            let modules = Self::resolve_called_modules(
+                state,
+                metrics,
+                recent_packages,
+                unresolved_modules,
+                transactions,
+                objects,
+            )
+            .await?;
+            index_tx_calls(checkpoint_summary, transactions, &modules)
+        } else {
+            vec![]
+        };
+        let transfers = if pipelines.is_enabled(Pipeline::Transfers) {
+            index_transfers(checkpoint_summary, transactions, objects)
+        } else {
+            vec![]
+        };
+        let proof = if pipelines.is_enabled(Pipeline::CheckpointProofs) {
+            Some(CheckpointProof::new(
+                checkpoint_summary,
+                checkpoint_contents,
+                transactions.iter().map(|(_, effects, _)| effects),
+            )?)
+        } else {
+            None
+        };
+        let ownership_transitions = if pipelines.is_enabled(Pipeline::OwnershipHistory) {
+            index_ownership_transitions(checkpoint_summary, transactions, objects)
+        } else {
+            vec![]
+        };
+        let transactions_json = if index_transactions_json {
+            index_transactions_json_responses(checkpoint_summary, transactions)?
+        } else {
+            vec![]
+        };
         let epoch_index = Self::index_epoch(state, data).await?;
 
+        let IndexedTransactions {
+            transactions: db_transactions,
+            events,
+            input_objects,
+            changed_objects,
+            move_calls,
+            tx_commands,
+            recipients,
+            tx_dependencies,
+        } = indexed;
         let total_transactions = db_transactions.iter().map(|t| t.transaction_count).sum();
         let total_successful_transaction_blocks = db_transactions
             .iter()
@@ -506,121 +854,801 @@ where
                     total_successful_transaction_blocks as i64,
                 ),
                 transactions: db_transactions,
-                events: db_events,
-                input_objects: db_input_objects,
-                changed_objects: db_changed_objects,
-                move_calls: db_move_calls,
-                recipients: db_recipients,
+                transactions_json,
+                checkpoint_tx_digests: CheckpointTxDigest::from_checkpoint_contents(
+                    checkpoint_summary,
+                    checkpoint_contents,
+                ),
+                events,
+                input_objects,
+                changed_objects,
+                move_calls,
+                tx_commands,
+                tx_calls,
+                recipients,
+                tx_dependencies,
+                transfers,
+                ownership_transitions,
+                signers: CheckpointSigners::new(checkpoint_summary),
+                proof,
             },
             epoch_index,
         ))
     }
 }
 
-const DB_COMMIT_RETRY_INTERVAL_IN_MILLIS: u64 = 100;
-
-pub async fn start_tx_checkpoint_commit_task<S>(
-    state: S,
-    metrics: IndexerMetrics,
-    config: IndexerConfig,
-    tx_indexing_receiver: mysten_metrics::metered_channel::Receiver<TemporaryCheckpointStore>,
-) where
-    S: IndexerStore + Clone + Sync + Send + 'static,
-{
-    use futures::StreamExt;
-
-    info!("Indexer checkpoint commit task started...");
-    let checkpoint_commit_batch_size = std::env::var("CHECKPOINT_COMMIT_BATCH_SIZE")
-        .unwrap_or(5.to_string())
-        .parse::<usize>()
-        .unwrap();
-    info!("Using checkpoint commit batch size {checkpoint_commit_batch_size}");
-
-    let mut stream = mysten_metrics::metered_channel::ReceiverStream::new(tx_indexing_receiver)
-        .ready_chunks(checkpoint_commit_batch_size);
+/// Rows indexed from the transactions of a checkpoint.
+#[derive(Default)]
+pub struct IndexedTransactions {
+    pub transactions: Vec<Transaction>,
+    pub events: Vec<Event>,
+    pub input_objects: Vec<InputObject>,
+    pub changed_objects: Vec<ChangedObject>,
+    pub move_calls: Vec<MoveCall>,
+    pub tx_commands: Vec<TxCommand>,
+    pub recipients: Vec<Recipient>,
+    pub tx_dependencies: Vec<TxDependency>,
+}
 
-    while let Some(indexed_checkpoint_batch) = stream.next().await {
-        let mut checkpoint_batch = vec![];
-        let mut tx_batch = vec![];
+// Transactions, effects and events are borrowed from the checkpoint data, which is shared
+// by all handlers; only the rows written to the DB are allocated.
+pub fn index_transactions(
+    checkpoint_summary: &CheckpointSummary,
+    transactions: &[(
+        sui_types::transaction::Transaction,
+        TransactionEffects,
+        Option<TransactionEvents>,
+    )],
+    pipelines: &Pipelines,
+) -> IndexedTransactions {
+    let checkpoint_sequence_number = *checkpoint_summary.sequence_number() as i64;
+    let epoch = checkpoint_summary.epoch as i64;
+    let mut indexed = IndexedTransactions {
+        transactions: Vec::with_capacity(transactions.len()),
+        ..Default::default()
+    };
+    // transactions are in the order of the checkpoint contents
+    let first_tx_sequence_number =
+        checkpoint_summary.network_total_transactions - transactions.len() as u64;
+
+    for (tx_sequence_number, (tx, fx, events)) in
+        (first_tx_sequence_number..).zip(transactions.iter())
+    {
+        let transaction_digest = tx.digest();
+        let digest = transaction_digest.base58_encode();
+        let tx = tx.transaction_data();
+        let sender = tx.sender().to_string();
+        let changed_objects = fx.all_changed_objects();
+        let (gas_payment_object_ids, gas_payment_object_versions, gas_payment_object_digests) =
+            gas_payment_columns(tx.gas());
+        let failure = ExecutionFailure::from_status(fx.status());
+
+        indexed.transactions.push(Transaction {
+            id: None,
+            transaction_digest: digest.clone(),
+            sender: sender.clone(),
+            checkpoint_sequence_number: Some(checkpoint_sequence_number),
+            timestamp_ms: Some(checkpoint_summary.timestamp_ms as i64),
+            transaction_kind: tx.kind().name().to_owned(),
+            transaction_count: tx.kind().num_commands() as i64,
+            execution_success: fx.status().is_ok(),
+            gas_object_id: fx.gas_object().0 .0.to_string(),
+            gas_object_sequence: fx.gas_object().0 .1.value() as i64,
+            gas_object_digest: fx.gas_object().0 .2.to_string(),
+            gas_budget: tx.gas_budget() as i64,
+            total_gas_cost: fx.gas_cost_summary().net_gas_usage(),
+            computation_cost: fx.gas_cost_summary().computation_cost as i64,
+            storage_cost: fx.gas_cost_summary().storage_cost as i64,
+            storage_rebate: fx.gas_cost_summary().storage_rebate as i64,
+            non_refundable_storage_fee: fx.gas_cost_summary().non_refundable_storage_fee as i64,
+            gas_price: tx.gas_price() as i64,
+            raw_transaction: bcs::to_bytes(&tx).unwrap(),
+            transaction_effects_content: serde_json::to_string(&fx).unwrap(),
+            confirmed_local_execution: None,
+            gas_owner: Some(tx.gas_owner().to_string()),
+            sponsored: tx.is_sponsored_tx(),
+            failure_kind: failure.as_ref().map(|failure| failure.kind.clone()),
+            failure_command: failure.as_ref().and_then(|failure| failure.command),
+            abort_package: failure
+                .as_ref()
+                .and_then(|failure| failure.abort_package.clone()),
+            abort_module: failure
+                .as_ref()
+                .and_then(|failure| failure.abort_module.clone()),
+            abort_code: failure.as_ref().and_then(|failure| failure.abort_code),
+            gas_payment_object_ids,
+            gas_payment_object_versions,
+            gas_payment_object_digests,
+            expiration_epoch: expiration_epoch(tx.expiration()),
+        });
 
-        if config.skip_db_commit {
-            info!(
-                "[Checkpoint/Tx] Downloaded and indexed checkpoint {:?} - {:?} successfully, skipping DB commit...",
-                indexed_checkpoint_batch.first().map(|c| c.checkpoint.sequence_number),
-                indexed_checkpoint_batch.last().map(|c| c.checkpoint.sequence_number),
+        if pipelines.is_enabled(Pipeline::Events) {
+            indexed.events.extend(
+                events
+                    .iter()
+                    .flat_map(|events| &events.data)
+                    .enumerate()
+                    .map(|(event_sequence, event)| {
+                        Event::from_sui_event(
+                            event,
+                            transaction_digest,
+                            tx_sequence_number,
+                            event_sequence as u64,
+                            checkpoint_summary.timestamp_ms,
+                        )
+                    }),
             );
-            continue;
         }
 
-        for indexed_checkpoint in indexed_checkpoint_batch {
-            // Write checkpoint to DB
-            let TemporaryCheckpointStore {
-                checkpoint,
-                transactions,
-                events,
-                input_objects,
-                changed_objects,
-                move_calls,
-                recipients,
-            } = indexed_checkpoint;
-            checkpoint_batch.push(checkpoint);
-            tx_batch.push(transactions);
+        if !pipelines.is_enabled(Pipeline::TxIndices) {
+            continue;
+        }
 
-            // NOTE: retrials are necessary here, otherwise results can be popped and discarded.
-            let events_handler = state.clone();
-            spawn_monitored_task!(async move {
-                let mut event_commit_res = events_handler.persist_events(&events).await;
-                while let Err(e) = event_commit_res {
-                    warn!(
-                        "Indexer event commit failed with error: {:?}, retrying after {:?} milli-secs...",
-                        e, DB_COMMIT_RETRY_INTERVAL_IN_MILLIS
-                    );
-                    tokio::time::sleep(std::time::Duration::from_millis(
-                        DB_COMMIT_RETRY_INTERVAL_IN_MILLIS,
-                    ))
-                    .await;
-                    event_commit_res = events_handler.persist_events(&events).await;
-                }
-            });
+        // Input Objects
+        indexed.input_objects.extend(
+            tx.input_objects()
+                .expect("committed txns have been validated")
+                .into_iter()
+                .map(|obj_kind| InputObject {
+                    id: None,
+                    transaction_digest: digest.clone(),
+                    checkpoint_sequence_number,
+                    epoch,
+                    object_id: obj_kind.object_id().to_string(),
+                    object_version: obj_kind.version().map(|v| v.value() as i64),
+                }),
+        );
 
-            let tx_index_table_handler = state.clone();
-            spawn_monitored_task!(async move {
-                let mut transaction_index_tables_commit_res = tx_index_table_handler
-                    .persist_transaction_index_tables(
-                        &input_objects,
-                        &changed_objects,
-                        &move_calls,
-                        &recipients,
-                    )
-                    .await;
-                while let Err(e) = transaction_index_tables_commit_res {
-                    warn!(
-                        "Indexer transaction index tables commit failed with error: {:?}, retrying after {:?} milli-secs...",
-                        e, DB_COMMIT_RETRY_INTERVAL_IN_MILLIS
-                    );
-                    tokio::time::sleep(std::time::Duration::from_millis(
-                        DB_COMMIT_RETRY_INTERVAL_IN_MILLIS,
-                    ))
-                    .await;
-                    transaction_index_tables_commit_res = tx_index_table_handler
-                        .persist_transaction_index_tables(
-                            &input_objects,
-                            &changed_objects,
-                            &move_calls,
-                            &recipients,
-                        )
-                        .await;
-                }
-            });
+        // Changed Objects
+        indexed.changed_objects.extend(changed_objects.iter().map(
+            |(object_ref, _owner, write_kind)| ChangedObject {
+                id: None,
+                transaction_digest: digest.clone(),
+                checkpoint_sequence_number,
+                epoch,
+                object_id: object_ref.0.to_string(),
+                object_change_type: crate::types::write_kind_to_str(*write_kind).to_string(),
+                object_version: object_ref.1.value() as i64,
+            },
+        ));
+
+        // Move Calls and commands
+        if let sui_types::transaction::TransactionKind::ProgrammableTransaction(pt) = tx.kind() {
+            indexed
+                .tx_commands
+                .extend(
+                    pt.commands
+                        .iter()
+                        .enumerate()
+                        .map(|(command_index, command)| {
+                            TxCommand::new(
+                                digest.clone(),
+                                command_index,
+                                checkpoint_sequence_number,
+                                epoch,
+                                command,
+                            )
+                        }),
+                );
+            indexed
+                .move_calls
+                .extend(pt.commands.iter().filter_map(|command| match command {
+                    sui_types::transaction::Command::MoveCall(m) => Some(MoveCall {
+                        id: None,
+                        transaction_digest: digest.clone(),
+                        checkpoint_sequence_number,
+                        epoch,
+                        sender: sender.clone(),
+                        move_package: m.package.to_string(),
+                        move_module: m.module.to_string(),
+                        move_function: m.function.to_string(),
+                    }),
+                    _ => None,
+                }));
         }
 
-        // now commit batched data
-        let tx_batch = tx_batch.into_iter().flatten().collect::<Vec<_>>();
-        let checkpoint_tx_db_guard = metrics.checkpoint_db_commit_latency.start_timer();
-        let mut checkpoint_tx_commit_res = state
-            .persist_checkpoint_transactions(
-                &checkpoint_batch,
-                &tx_batch,
+        // Recipients
+        indexed.recipients.extend(
+            changed_objects
+                .iter()
+                .filter_map(|(_object_ref, owner, _write_kind)| match owner {
+                    Owner::AddressOwner(address) => Some(address),
+                    _ => None,
+                })
+                .unique()
+                .map(|recipient| Recipient {
+                    id: None,
+                    transaction_digest: digest.clone(),
+                    checkpoint_sequence_number,
+                    epoch,
+                    sender: sender.clone(),
+                    recipient: recipient.to_string(),
+                }),
+        );
+
+        // Transaction dependencies
+        indexed
+            .tx_dependencies
+            .extend(fx.dependencies().iter().map(|dependency| TxDependency {
+                transaction_digest: digest.clone(),
+                dependency: dependency.to_string(),
+                checkpoint_sequence_number,
+            }));
+    }
+    indexed
+}
+
+fn has_pure_arguments(call: &ProgrammableMoveCall, inputs: &[CallArg]) -> bool {
+    count_pure_arguments(call, inputs) > 0
+}
+
+fn count_pure_arguments(call: &ProgrammableMoveCall, inputs: &[CallArg]) -> usize {
+    call.arguments
+        .iter()
+        .filter(|argument| match argument {
+            sui_types::transaction::Argument::Input(input) => {
+                matches!(inputs.get(*input as usize), Some(CallArg::Pure(_)))
+            }
+            _ => false,
+        })
+        .count()
+}
+
+// Move calls of programmable transactions, with their pure arguments decoded by the
+// parameters of the called functions found in `modules`.
+pub fn index_tx_calls(
+    checkpoint_summary: &CheckpointSummary,
+    transactions: &[(
+        sui_types::transaction::Transaction,
+        TransactionEffects,
+        Option<TransactionEvents>,
+    )],
+    modules: &HashMap<ModuleId, Arc<CompiledModule>>,
+) -> Vec<TxCall> {
+    let mut tx_calls = vec![];
+    for (tx, _, _) in transactions {
+        let pt = match tx.transaction_data().kind() {
+            TransactionKind::ProgrammableTransaction(pt) => pt,
+            _ => continue,
+        };
+        let digest = tx.digest().base58_encode();
+        for (command_index, command) in pt.commands.iter().enumerate() {
+            let call = match command {
+                Command::MoveCall(call) => call,
+                _ => continue,
+            };
+            let parameters = modules
+                .get(&ModuleId::new(call.package.into(), call.module.clone()))
+                .and_then(|module| {
+                    PureType::function_parameters(module, &call.function, &call.type_arguments)
+                });
+            tx_calls.push(TxCall::new(
+                digest.clone(),
+                command_index,
+                *checkpoint_summary.sequence_number() as i64,
+                checkpoint_summary.epoch as i64,
+                call,
+                &pt.inputs,
+                parameters.as_deref(),
+            ));
+        }
+    }
+    tx_calls
+}
+
+// Balance changes of the addresses other than its sender that a successful transaction wrote
+// coins to, by coin type. Coins of other owners than the sender cannot be inputs of the
+// transaction, except the gas of a sponsor, which is skipped, so the balance change of a
+// recipient is the sum of the coins written to it.
+pub fn index_transfers(
+    checkpoint_summary: &CheckpointSummary,
+    transactions: &[(
+        sui_types::transaction::Transaction,
+        TransactionEffects,
+        Option<TransactionEvents>,
+    )],
+    objects: &[sui_types::object::Object],
+) -> Vec<Transfer> {
+    let objects = objects
+        .iter()
+        .map(|o| ((o.id(), o.version()), o))
+        .collect::<HashMap<_, _>>();
+    let mut transfers = vec![];
+    for (tx, fx, _) in transactions {
+        if !fx.status().is_ok() {
+            continue;
+        }
+        let digest = tx.digest().base58_encode();
+        let tx = tx.transaction_data();
+        let sender = tx.sender();
+        // balance changes in order of the first coin written to each recipient
+        let mut balance_changes: Vec<((SuiAddress, String), u128)> = vec![];
+        for ((object_id, version, _), owner, _) in fx.all_changed_objects() {
+            let recipient = match owner {
+                Owner::AddressOwner(recipient) if recipient != sender => recipient,
+                _ => continue,
+            };
+            if recipient == tx.gas_owner() && tx.gas().iter().any(|gas| gas.0 == object_id) {
+                continue;
+            }
+            let (coin_type, coin) = match objects.get(&(object_id, version)) {
+                Some(object) => match (object.coin_type_maybe(), object.as_coin_maybe()) {
+                    (Some(coin_type), Some(coin)) => (coin_type, coin),
+                    _ => continue,
+                },
+                None => continue,
+            };
+            let key = (recipient, coin_type.to_string());
+            match balance_changes.iter_mut().find(|(k, _)| *k == key) {
+                Some((_, amount)) => *amount += coin.value() as u128,
+                None => balance_changes.push((key, coin.value() as u128)),
+            }
+        }
+        for ((recipient, coin_type), amount) in balance_changes {
+            let Ok(amount) = i64::try_from(amount) else {
+                warn!(
+                    "Skipping transfer of {amount} {coin_type} to {recipient} in transaction \
+                    {digest}, the amount exceeds the BIGINT range of the transfers table"
+                );
+                continue;
+            };
+            transfers.push(Transfer {
+                id: None,
+                transaction_digest: digest.clone(),
+                checkpoint_sequence_number: *checkpoint_summary.sequence_number() as i64,
+                timestamp_ms: checkpoint_summary.timestamp_ms as i64,
+                sender: sender.to_string(),
+                recipient: recipient.to_string(),
+                coin_type,
+                amount,
+            });
+        }
+    }
+    transfers
+}
+
+// Non-coin Move objects written by a successful transaction to an address they were not
+// owned by. The previous owner is the sender if the object was one of its owned inputs,
+// and unknown otherwise, e.g. for objects created, unwrapped or taken out of a parent.
+pub fn index_ownership_transitions(
+    checkpoint_summary: &CheckpointSummary,
+    transactions: &[(
+        sui_types::transaction::Transaction,
+        TransactionEffects,
+        Option<TransactionEvents>,
+    )],
+    objects: &[sui_types::object::Object],
+) -> Vec<OwnershipTransition> {
+    let objects = objects
+        .iter()
+        .map(|o| ((o.id(), o.version()), o))
+        .collect::<HashMap<_, _>>();
+    let mut transitions = vec![];
+    for (tx, fx, _) in transactions {
+        if !fx.status().is_ok() {
+            continue;
+        }
+        let digest = tx.digest().base58_encode();
+        let tx = tx.transaction_data();
+        let sender = tx.sender();
+        let owned_inputs = tx
+            .input_objects()
+            .expect("committed txns have been validated")
+            .into_iter()
+            .filter_map(|input| match input {
+                InputObjectKind::ImmOrOwnedMoveObject(object_ref) => Some(object_ref.0),
+                _ => None,
+            })
+            .collect::<HashSet<_>>();
+        for ((object_id, version, _), owner, _) in fx.all_changed_objects() {
+            let to_owner = match owner {
+                Owner::AddressOwner(to_owner) => to_owner,
+                _ => continue,
+            };
+            let from_owner = owned_inputs.contains(&object_id).then_some(sender);
+            if from_owner == Some(to_owner) {
+                continue;
+            }
+            match objects.get(&(object_id, version)) {
+                Some(object) if !object.is_coin() => {}
+                _ => continue,
+            }
+            transitions.push(OwnershipTransition {
+                id: None,
+                object_id: object_id.to_string(),
+                object_version: version.value() as i64,
+                from_owner: from_owner.map(|owner| owner.to_string()),
+                to_owner: to_owner.to_string(),
+                transaction_digest: digest.clone(),
+                checkpoint_sequence_number: *checkpoint_summary.sequence_number() as i64,
+            });
+        }
+    }
+    transitions
+}
+
+pub fn index_transactions_json_responses(
+    checkpoint_summary: &CheckpointSummary,
+    transactions: &[(
+        sui_types::transaction::Transaction,
+        TransactionEffects,
+        Option<TransactionEvents>,
+    )],
+) -> Result<Vec<TransactionJson>, IndexerError> {
+    transactions
+        .iter()
+        .map(|(tx, fx, _)| {
+            let digest = *tx.digest();
+            let mut response = SuiTransactionBlockResponse::new(digest);
+            response.transaction = Some(
+                SuiTransactionBlock::try_from(tx.data().clone(), &NoModuleResolver).map_err(
+                    |e| {
+                        IndexerError::SerdeError(format!(
+                            "Failed converting transaction {digest} to JSON with error: {e:?}"
+                        ))
+                    },
+                )?,
+            );
+            response.effects = Some(SuiTransactionBlockEffects::try_from(fx.clone())?);
+            response.timestamp_ms = Some(checkpoint_summary.timestamp_ms);
+            response.checkpoint = Some(*checkpoint_summary.sequence_number());
+            Ok(TransactionJson {
+                transaction_digest: digest.base58_encode(),
+                checkpoint_sequence_number: *checkpoint_summary.sequence_number() as i64,
+                response: serde_json::to_value(&response).map_err(|e| {
+                    IndexerError::SerdeError(format!(
+                        "Failed serializing transaction {digest} to JSON with error: {e:?}"
+                    ))
+                })?,
+            })
+        })
+        .collect()
+}
+
+// Packages called by a transaction may not be committed yet when it is indexed, so
+// modules are not resolved for its JSON. Pure inputs of Move calls are left untyped,
+// as BCS bytes.
+struct NoModuleResolver;
+
+impl GetModule for NoModuleResolver {
+    type Error = std::convert::Infallible;
+    type Item = CompiledModule;
+
+    fn get_module_by_id(&self, _id: &ModuleId) -> Result<Option<CompiledModule>, Self::Error> {
+        Ok(None)
+    }
+}
+
+const DB_COMMIT_RETRY_INTERVAL_IN_MILLIS: u64 = 100;
+const WATCHLIST_RELOAD_INTERVAL: Duration = Duration::from_secs(10);
+// batches committed but waiting for the commit tasks of their events and transaction index
+// tables, beyond which the checkpoint commit task waits
+const COMMIT_WATERMARK_QUEUE_SIZE: usize = 100;
+
+/// Batches committed by a commit task since its last summary log line, logged at most once
+/// per `commit_log_interval_secs` so that logs stay readable at many checkpoints per second.
+struct CommitSummary {
+    task: &'static str,
+    interval: Duration,
+    logged_at: Instant,
+    first_checkpoint: Option<i64>,
+    last_checkpoint: i64,
+    batches: usize,
+    // transactions or object changes, depending on the task
+    rows: usize,
+    commit_secs: f64,
+}
+
+impl CommitSummary {
+    fn new(task: &'static str, interval: Duration) -> Self {
+        Self {
+            task,
+            interval,
+            logged_at: Instant::now(),
+            first_checkpoint: None,
+            last_checkpoint: 0,
+            batches: 0,
+            rows: 0,
+            commit_secs: 0.0,
+        }
+    }
+
+    fn record(&mut self, first_checkpoint: i64, last_checkpoint: i64, rows: usize, elapsed: f64) {
+        trace!(
+            task = self.task,
+            first_checkpoint,
+            last_checkpoint,
+            rows,
+            elapsed,
+            "Committed checkpoint batch"
+        );
+        self.first_checkpoint.get_or_insert(first_checkpoint);
+        self.last_checkpoint = last_checkpoint;
+        self.batches += 1;
+        self.rows += rows;
+        self.commit_secs += elapsed;
+        if self.logged_at.elapsed() < self.interval {
+            return;
+        }
+        let first_checkpoint = self.first_checkpoint.take().unwrap_or(last_checkpoint);
+        info!(
+            task = self.task,
+            first_checkpoint,
+            last_checkpoint,
+            checkpoints = last_checkpoint - first_checkpoint + 1,
+            batches = self.batches,
+            rows = self.rows,
+            commit_secs = self.commit_secs,
+            "Committed checkpoints"
+        );
+        self.logged_at = Instant::now();
+        self.batches = 0;
+        self.rows = 0;
+        self.commit_secs = 0.0;
+    }
+}
+
+pub async fn start_tx_checkpoint_commit_task<S>(
+    state: S,
+    metrics: IndexerMetrics,
+    config: IndexerConfig,
+    pipelines: Pipelines,
+    tx_indexing_receiver: mysten_metrics::metered_channel::Receiver<TemporaryCheckpointStore>,
+) -> Result<(), IndexerError>
+where
+    S: IndexerStore + Clone + Sync + Send + 'static,
+{
+    use futures::StreamExt;
+
+    info!(
+        "Indexer checkpoint commit task started, committing batches of {} rows or after {} ms...",
+        config.checkpoint_commit_batch_rows, config.checkpoint_commit_max_latency_ms
+    );
+
+    let notifier = if config.checkpoint_webhook_urls.is_empty() {
+        None
+    } else {
+        Some(
+            CheckpointNotifier::new(
+                config.checkpoint_webhook_urls.clone(),
+                config.checkpoint_webhook_secret.clone(),
+                metrics.clone(),
+            )
+            .tap_err(|e| error!("Failed creating checkpoint notifier with error: {:?}", e))?,
+        )
+    };
+    let notify = notifier.is_some();
+    let (watermark_sender, watermark_receiver) = mysten_metrics::metered_channel::channel(
+        COMMIT_WATERMARK_QUEUE_SIZE,
+        &get_metrics()
+            .unwrap()
+            .channels
+            .with_label_values(&["checkpoint_commit_watermark"]),
+    );
+    spawn_monitored_task!(start_commit_watermark_task(
+        state.clone(),
+        notifier,
+        watermark_receiver
+    ));
+
+    let mut commit_summary = CommitSummary::new(
+        "checkpoints",
+        Duration::from_secs(config.commit_log_interval_secs),
+    );
+    let mut stream = Box::pin(batch_by_rows(
+        mysten_metrics::metered_channel::ReceiverStream::new(tx_indexing_receiver),
+        config.checkpoint_commit_batch_rows,
+        Duration::from_millis(config.checkpoint_commit_max_latency_ms),
+        TemporaryCheckpointStore::row_count,
+    ));
+    let persist_events = pipelines.is_enabled(Pipeline::Events);
+    let persist_tx_index_tables = [
+        Pipeline::TxIndices,
+        Pipeline::Transfers,
+        Pipeline::OwnershipHistory,
+    ]
+    .into_iter()
+    .any(|pipeline| pipelines.is_enabled(pipeline));
+    let mut watchlist = Watchlist::default();
+    let mut watchlist_loaded_at: Option<Instant> = None;
+    // sequence number and network total transactions of the last checkpoint checked
+    let mut previous_checkpoint: Option<(i64, i64)> = None;
+
+    while let Some(indexed_checkpoint_batch) = stream.next().await {
+        let mut checkpoint_batch = vec![];
+        let mut tx_batch = vec![];
+        let mut tx_json_batch = vec![];
+        let mut checkpoint_tx_digests_batch = vec![];
+        let mut hot_entities_batch = vec![];
+        let mut address_activity_batch = vec![];
+        let mut signers_batch = vec![];
+        let mut proofs_batch = vec![];
+
+        if config.skip_db_commit {
+            info!(
+                "[Checkpoint/Tx] Downloaded and indexed checkpoint {:?} - {:?} successfully, skipping DB commit...",
+                indexed_checkpoint_batch.first().map(|c| c.checkpoint.sequence_number),
+                indexed_checkpoint_batch.last().map(|c| c.checkpoint.sequence_number),
+            );
+            continue;
+        }
+
+        // entries added or removed take effect within WATCHLIST_RELOAD_INTERVAL
+        if notify
+            && watchlist_loaded_at.map_or(true, |loaded_at| {
+                loaded_at.elapsed() > WATCHLIST_RELOAD_INTERVAL
+            })
+        {
+            match state.get_watchlist_entries().await {
+                Ok(entries) => {
+                    watchlist = Watchlist::new(entries);
+                    watchlist_loaded_at = Some(Instant::now());
+                }
+                Err(e) => warn!("Failed reloading watchlist with error: {:?}", e),
+            }
+        }
+        let mut watchlist_matches = vec![];
+        // tasks committing the events and transaction index tables of the batch
+        let mut commits = vec![];
+
+        // Nothing of the batch is committed if a checkpoint breaks the continuity of
+        // network total transactions. Returning drops the receiver, and the error halts
+        // indexing once reported by the handler failing to send the next checkpoint.
+        for indexed_checkpoint in &indexed_checkpoint_batch {
+            let checkpoint = &indexed_checkpoint.checkpoint;
+            let previous_network_total_transactions = match previous_checkpoint {
+                Some((sequence_number, network_total_transactions))
+                    if sequence_number + 1 == checkpoint.sequence_number =>
+                {
+                    Some(network_total_transactions)
+                }
+                // re-ingested checkpoints arrive out of order
+                _ => {
+                    get_previous_network_total_transactions(&state, checkpoint.sequence_number)
+                        .await?
+                }
+            };
+            if let Some(previous_network_total_transactions) = previous_network_total_transactions {
+                if let Err(e) =
+                    check_tx_count_continuity(previous_network_total_transactions, checkpoint)
+                {
+                    metrics.total_inconsistent_checkpoint.inc();
+                    error!("Halting checkpoint commit: {}", e);
+                    return Err(e);
+                }
+            }
+            previous_checkpoint = Some((
+                checkpoint.sequence_number,
+                checkpoint.network_total_transactions,
+            ));
+        }
+
+        for indexed_checkpoint in indexed_checkpoint_batch {
+            // Write checkpoint to DB
+            let TemporaryCheckpointStore {
+                checkpoint,
+                transactions,
+                transactions_json,
+                checkpoint_tx_digests,
+                events,
+                input_objects,
+                changed_objects,
+                move_calls,
+                tx_commands,
+                tx_calls,
+                recipients,
+                tx_dependencies,
+                transfers,
+                ownership_transitions,
+                signers,
+                proof,
+            } = indexed_checkpoint;
+            watchlist_matches.extend(watchlist.matches(
+                &transactions,
+                &recipients,
+                &input_objects,
+                &changed_objects,
+            ));
+            hot_entities_batch.push(CheckpointHotEntities::new(
+                checkpoint.sequence_number,
+                checkpoint.timestamp_ms,
+                &input_objects,
+                &changed_objects,
+                &move_calls,
+            ));
+            address_activity_batch.push(CheckpointAddressActivity::new(
+                checkpoint.sequence_number,
+                checkpoint.timestamp_ms,
+                &transactions,
+                &recipients,
+            ));
+            signers_batch.push(signers);
+            proofs_batch.extend(proof);
+            checkpoint_batch.push(checkpoint);
+            tx_batch.push(transactions);
+            tx_json_batch.push(transactions_json);
+            checkpoint_tx_digests_batch.push(checkpoint_tx_digests);
+
+            // NOTE: retrials are necessary here, otherwise results can be popped and discarded.
+            if persist_events {
+                let events_handler = state.clone();
+                commits.push(spawn_monitored_task!(async move {
+                    let mut event_commit_res = events_handler.persist_events(&events).await;
+                    while let Err(e) = event_commit_res {
+                        warn!(
+                            "Indexer event commit failed with error: {:?}, retrying after {:?} milli-secs...",
+                            e, DB_COMMIT_RETRY_INTERVAL_IN_MILLIS
+                        );
+                        tokio::time::sleep(std::time::Duration::from_millis(
+                            DB_COMMIT_RETRY_INTERVAL_IN_MILLIS,
+                        ))
+                        .await;
+                        event_commit_res = events_handler.persist_events(&events).await;
+                    }
+                }));
+            }
+
+            if persist_tx_index_tables {
+                let tx_index_table_handler = state.clone();
+                commits.push(spawn_monitored_task!(async move {
+                    let mut transaction_index_tables_commit_res = tx_index_table_handler
+                        .persist_transaction_index_tables(
+                            &input_objects,
+                            &changed_objects,
+                            &move_calls,
+                            &tx_commands,
+                            &tx_calls,
+                            &recipients,
+                            &tx_dependencies,
+                            &transfers,
+                            &ownership_transitions,
+                        )
+                        .await;
+                    while let Err(e) = transaction_index_tables_commit_res {
+                        warn!(
+                            "Indexer transaction index tables commit failed with error: {:?}, retrying after {:?} milli-secs...",
+                            e, DB_COMMIT_RETRY_INTERVAL_IN_MILLIS
+                        );
+                        tokio::time::sleep(std::time::Duration::from_millis(
+                            DB_COMMIT_RETRY_INTERVAL_IN_MILLIS,
+                        ))
+                        .await;
+                        transaction_index_tables_commit_res = tx_index_table_handler
+                            .persist_transaction_index_tables(
+                                &input_objects,
+                                &changed_objects,
+                                &move_calls,
+                                &tx_commands,
+                                &tx_calls,
+                                &recipients,
+                                &tx_dependencies,
+                                &transfers,
+                                &ownership_transitions,
+                            )
+                            .await;
+                    }
+                }));
+            }
+        }
+
+        // now commit batched data
+        let tx_batch = tx_batch.into_iter().flatten().collect::<Vec<_>>();
+        let tx_json_batch = tx_json_batch.into_iter().flatten().collect::<Vec<_>>();
+        let checkpoint_tx_digests_batch = checkpoint_tx_digests_batch
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+        let checkpoint_tx_db_guard = metrics.checkpoint_db_commit_latency.start_timer();
+        let mut checkpoint_tx_commit_res = state
+            .persist_checkpoint_transactions(
+                &checkpoint_batch,
+                &tx_batch,
+                &tx_json_batch,
+                &checkpoint_tx_digests_batch,
+                &hot_entities_batch,
+                &address_activity_batch,
+                &signers_batch,
+                &proofs_batch,
                 metrics.total_transaction_chunk_committed.clone(),
             )
             .await;
@@ -637,6 +1665,12 @@ pub async fn start_tx_checkpoint_commit_task<S>(
                 .persist_checkpoint_transactions(
                     &checkpoint_batch,
                     &tx_batch,
+                    &tx_json_batch,
+                    &checkpoint_tx_digests_batch,
+                    &hot_entities_batch,
+                    &address_activity_batch,
+                    &signers_batch,
+                    &proofs_batch,
                     metrics.total_transaction_chunk_committed.clone(),
                 )
                 .await;
@@ -648,19 +1682,18 @@ pub async fn start_tx_checkpoint_commit_task<S>(
         metrics
             .latest_tx_checkpoint_sequence_number
             .set(last_checkpoint_seq);
-
+        metrics
+            .latest_tx_checkpoint_timestamp_ms
+            .set(checkpoint_batch.last().as_ref().unwrap().timestamp_ms);
+        metrics
+            .total_watchlist_matches
+            .inc_by(watchlist_matches.len() as u64);
         metrics
             .total_tx_checkpoint_committed
             .inc_by(checkpoint_batch.len() as u64);
         let tx_count = tx_batch.len();
         metrics.total_transaction_committed.inc_by(tx_count as u64);
-        info!(
-            elapsed,
-            "Tx Checkpoint {}-{} committed with {} transactions.",
-            first_checkpoint_seq,
-            last_checkpoint_seq,
-            tx_count,
-        );
+        commit_summary.record(first_checkpoint_seq, last_checkpoint_seq, tx_count, elapsed);
         metrics
             .transaction_per_checkpoint
             .observe(tx_count as f64 / (last_checkpoint_seq - first_checkpoint_seq + 1) as f64);
@@ -669,9 +1702,149 @@ pub async fn start_tx_checkpoint_commit_task<S>(
         metrics
             .thousand_transaction_avg_db_commit_latency
             .observe(elapsed * 1000.0 / tx_count as f64);
+
+        // NOTE: when the channel is full, this waits for the commit tasks of earlier batches.
+        if watermark_sender
+            .send(CommittedBatch {
+                checkpoints: checkpoint_batch,
+                watchlist_matches,
+                commits,
+            })
+            .await
+            .is_err()
+        {
+            error!("Halting checkpoint commit: the commit watermark task stopped");
+            return Err(IndexerError::MpscChannelError(
+                "Commit watermark task has stopped".to_string(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+// A batch whose checkpoints rows are committed, along with the tasks committing the rest of
+// their tables.
+struct CommittedBatch {
+    checkpoints: Vec<Checkpoint>,
+    watchlist_matches: Vec<WatchlistMatch>,
+    commits: Vec<JoinHandle<()>>,
+}
+
+// Advances the commit watermark over each batch once the tasks committing its events and
+// transaction index tables are done, in commit order, then notifies webhooks of the batch.
+async fn start_commit_watermark_task<S>(
+    state: S,
+    notifier: Option<CheckpointNotifier>,
+    mut receiver: mysten_metrics::metered_channel::Receiver<CommittedBatch>,
+) where
+    S: IndexerStore + Clone + Sync + Send + 'static,
+{
+    info!("Indexer commit watermark task started...");
+    while let Some(CommittedBatch {
+        checkpoints,
+        watchlist_matches,
+        commits,
+    }) = receiver.recv().await
+    {
+        // commit tasks retry until they succeed, they only fail when they panic, leaving
+        // their checkpoints incomplete
+        for result in join_all(commits).await {
+            if let Err(e) = result {
+                error!("Halting commit watermark: a commit task failed with error: {e}");
+                return;
+            }
+        }
+        let Some(last_checkpoint) = checkpoints.last() else {
+            continue;
+        };
+        while let Err(e) = state
+            .persist_commit_watermark(last_checkpoint.sequence_number)
+            .await
+        {
+            warn!(
+                "Indexer commit watermark commit failed with error: {:?}, retrying after {:?} milli-secs...",
+                e, DB_COMMIT_RETRY_INTERVAL_IN_MILLIS
+            );
+            tokio::time::sleep(std::time::Duration::from_millis(
+                DB_COMMIT_RETRY_INTERVAL_IN_MILLIS,
+            ))
+            .await;
+        }
+        if let Some(notifier) = &notifier {
+            checkpoints
+                .iter()
+                .for_each(|checkpoint| notifier.notify(checkpoint));
+            watchlist_matches
+                .into_iter()
+                .for_each(|watchlist_match| notifier.notify_watchlist_match(watchlist_match));
+        }
+    }
+}
+
+// None if the previous checkpoint is not committed, e.g. it is missing and yet to be
+// re-ingested.
+async fn get_previous_network_total_transactions<S>(
+    state: &S,
+    sequence_number: i64,
+) -> Result<Option<i64>, IndexerError>
+where
+    S: IndexerStore,
+{
+    if sequence_number == 0 {
+        return Ok(Some(0));
+    }
+    let mut attempt = 1;
+    loop {
+        match state.get_indexer_checkpoints(sequence_number - 2, 1).await {
+            Ok(checkpoints) => {
+                return Ok(checkpoints
+                    .into_iter()
+                    .find(|c| c.sequence_number == sequence_number - 1)
+                    .map(|c| c.network_total_transactions))
+            }
+            Err(e) if attempt >= PREVIOUS_CHECKPOINT_READ_ATTEMPTS => {
+                error!(
+                    "Halting checkpoint commit: failed reading checkpoint {} {} times, last with error: {:?}",
+                    sequence_number - 1,
+                    attempt,
+                    e
+                );
+                return Err(e);
+            }
+            Err(e) => {
+                attempt += 1;
+                warn!(
+                    "Failed reading checkpoint {} with error: {:?}, retrying after {:?} milli-secs...",
+                    sequence_number - 1,
+                    e,
+                    DB_COMMIT_RETRY_INTERVAL_IN_MILLIS
+                );
+                tokio::time::sleep(std::time::Duration::from_millis(
+                    DB_COMMIT_RETRY_INTERVAL_IN_MILLIS,
+                ))
+                .await;
+            }
+        }
     }
 }
 
+fn check_tx_count_continuity(
+    previous_network_total_transactions: i64,
+    checkpoint: &Checkpoint,
+) -> Result<(), IndexerError> {
+    let expected = previous_network_total_transactions + checkpoint.total_transaction_blocks;
+    if checkpoint.network_total_transactions != expected {
+        return Err(IndexerError::DataInconsistency(format!(
+            "Checkpoint {} has network total transactions {}, expected {} from the previous checkpoint and its {} transaction blocks",
+            checkpoint.sequence_number,
+            checkpoint.network_total_transactions,
+            expected,
+            checkpoint.total_transaction_blocks,
+        )));
+    }
+    Ok(())
+}
+
 pub async fn start_epoch_commit_task<S>(
     state: S,
     metrics: IndexerMetrics,
@@ -711,23 +1884,29 @@ pub async fn start_object_checkpoint_commit_task<S>(
     state: S,
     metrics: IndexerMetrics,
     config: IndexerConfig,
-    object_indexing_receiver: mysten_metrics::metered_channel::Receiver<(
-        sui_types::messages_checkpoint::CheckpointSequenceNumber,
-        Vec<crate::store::TransactionObjectChanges>,
-    )>,
+    object_indexing_receiver: mysten_metrics::metered_channel::Receiver<CheckpointObjectChanges>,
 ) where
     S: IndexerStore + Clone + Sync + Send + 'static,
 {
     use futures::StreamExt;
 
     info!("Indexer object checkpoint commit task started...");
-    let checkpoint_commit_batch_size = std::env::var("CHECKPOINT_COMMIT_BATCH_SIZE")
-        .unwrap_or(5.to_string())
-        .parse::<usize>()
-        .unwrap();
-
-    let mut stream = mysten_metrics::metered_channel::ReceiverStream::new(object_indexing_receiver)
-        .ready_chunks(checkpoint_commit_batch_size);
+    let mut commit_summary = CommitSummary::new(
+        "objects",
+        Duration::from_secs(config.commit_log_interval_secs),
+    );
+    let mut stream = Box::pin(batch_by_rows(
+        mysten_metrics::metered_channel::ReceiverStream::new(object_indexing_receiver),
+        config.checkpoint_commit_batch_rows,
+        Duration::from_millis(config.checkpoint_commit_max_latency_ms),
+        |(_, _, object_changes)| {
+            object_changes
+                .iter()
+                .map(|changes| changes.changed_objects.len() + changes.deleted_objects.len())
+                .sum::<usize>()
+                .max(1)
+        },
+    ));
 
     while let Some(object_change_batch) = stream.next().await {
         let last_checkpoint_seq = object_change_batch.last().map(|b| b.0).unwrap();
@@ -744,62 +1923,121 @@ pub async fn start_object_checkpoint_commit_task<S>(
 
         // NOTE: commit object changes in the current task to stick to the original order,
         // spawned tasks are possible to be executed in a different order.
-        let object_changes = object_change_batch
-            .into_iter()
-            .flat_map(|(_, o)| o)
-            .collect::<Vec<_>>();
         let object_commit_timer = metrics.object_db_commit_latency.start_timer();
-        let mut object_changes_commit_res = state
-            .persist_object_changes(
-                &object_changes,
-                metrics.object_mutation_db_commit_latency.clone(),
-                metrics.object_deletion_db_commit_latency.clone(),
-                metrics.total_object_change_chunk_committed.clone(),
-            )
-            .await;
-        while let Err(e) = object_changes_commit_res {
-            warn!(
-                "Indexer object changes commit failed (checkpoints [{:?}, {:?}]) with error: {:?}, retrying after {:?} milli-secs...",
-                first_checkpoint_seq, last_checkpoint_seq, e, DB_COMMIT_RETRY_INTERVAL_IN_MILLIS
-            );
-            tokio::time::sleep(std::time::Duration::from_millis(
-                DB_COMMIT_RETRY_INTERVAL_IN_MILLIS,
-            ))
-            .await;
-            object_changes_commit_res = state
-                .persist_object_changes(
+        let mut object_change_count = 0;
+        let mut object_changes = vec![];
+        for (checkpoint_seq, ended_epoch, changes) in object_change_batch {
+            object_changes.extend(changes);
+            // objects are committed up to the last checkpoint of an epoch before counting
+            // them, so that the object type stats are of the objects live at its end
+            if let Some(epoch) = ended_epoch {
+                commit_object_changes(
+                    &state,
+                    &metrics,
                     &object_changes,
-                    metrics.object_mutation_db_commit_latency.clone(),
-                    metrics.object_deletion_db_commit_latency.clone(),
-                    metrics.total_object_change_chunk_committed.clone(),
+                    first_checkpoint_seq,
+                    checkpoint_seq,
                 )
                 .await;
+                object_change_count += object_changes.len();
+                object_changes.clear();
+                persist_object_type_stats(&state, epoch).await;
+            }
         }
+        commit_object_changes(
+            &state,
+            &metrics,
+            &object_changes,
+            first_checkpoint_seq,
+            last_checkpoint_seq,
+        )
+        .await;
+        object_change_count += object_changes.len();
         let elapsed = object_commit_timer.stop_and_record();
         metrics.total_object_checkpoint_committed.inc();
         metrics
             .total_object_change_committed
-            .inc_by(object_changes.len() as u64);
+            .inc_by(object_change_count as u64);
         metrics
             .latest_indexer_object_checkpoint_sequence_number
             .set(last_checkpoint_seq as i64);
-        info!(
+        commit_summary.record(
+            first_checkpoint_seq as i64,
+            last_checkpoint_seq as i64,
+            object_change_count,
             elapsed,
-            "Object Checkpoint {}-{} committed with {} object changes",
-            first_checkpoint_seq,
-            last_checkpoint_seq,
-            object_changes.len(),
         );
     }
 }
 
+async fn commit_object_changes<S>(
+    state: &S,
+    metrics: &IndexerMetrics,
+    object_changes: &[TransactionObjectChanges],
+    first_checkpoint_seq: CheckpointSequenceNumber,
+    last_checkpoint_seq: CheckpointSequenceNumber,
+) where
+    S: IndexerStore + Clone + Sync + Send + 'static,
+{
+    let mut object_changes_commit_res = state
+        .persist_object_changes(
+            object_changes,
+            metrics.object_mutation_db_commit_latency.clone(),
+            metrics.object_deletion_db_commit_latency.clone(),
+            metrics.total_object_change_chunk_committed.clone(),
+        )
+        .await;
+    while let Err(e) = object_changes_commit_res {
+        warn!(
+            "Indexer object changes commit failed (checkpoints [{:?}, {:?}]) with error: {:?}, retrying after {:?} milli-secs...",
+            first_checkpoint_seq, last_checkpoint_seq, e, DB_COMMIT_RETRY_INTERVAL_IN_MILLIS
+        );
+        tokio::time::sleep(std::time::Duration::from_millis(
+            DB_COMMIT_RETRY_INTERVAL_IN_MILLIS,
+        ))
+        .await;
+        object_changes_commit_res = state
+            .persist_object_changes(
+                object_changes,
+                metrics.object_mutation_db_commit_latency.clone(),
+                metrics.object_deletion_db_commit_latency.clone(),
+                metrics.total_object_change_chunk_committed.clone(),
+            )
+            .await;
+    }
+}
+
+// A copy of the object type counts maintained as objects are committed, one row per type,
+// retried so that no epoch is left without stats, as they cannot be computed once objects
+// of the next epoch are committed.
+async fn persist_object_type_stats<S>(state: &S, epoch: EpochId)
+where
+    S: IndexerStore + Clone + Sync + Send + 'static,
+{
+    while let Err(e) = state.persist_object_type_stats(epoch).await {
+        warn!(
+            "Indexer object type stats commit of epoch {} failed with error: {:?}, retrying after {:?} milli-secs...",
+            epoch, e, DB_COMMIT_RETRY_INTERVAL_IN_MILLIS
+        );
+        tokio::time::sleep(std::time::Duration::from_millis(
+            DB_COMMIT_RETRY_INTERVAL_IN_MILLIS,
+        ))
+        .await;
+    }
+    info!(epoch, "Committed object type stats");
+}
+
 pub struct ObjectsProcessor<S> {
     metrics: IndexerMetrics,
-    object_indexing_sender: mysten_metrics::metered_channel::Sender<(
-        sui_types::messages_checkpoint::CheckpointSequenceNumber,
-        Vec<TransactionObjectChanges>,
-    )>,
+    object_indexing_sender: mysten_metrics::metered_channel::Sender<CheckpointObjectChanges>,
     state: S,
+    source_verifier: Option<SourceVerificationClient>,
+    // skip objects of checkpoints with inconsistent data instead of halting.
+    skip_inconsistent_checkpoints: bool,
+    // halt on packages with modules this binary cannot deserialize instead of storing them.
+    halt_on_unparseable_packages: bool,
+    // table of the name service registry whose records are indexed, if any.
+    name_service_registry_id: Option<ObjectID>,
 }
 
 #[async_trait::async_trait]
@@ -811,26 +2049,42 @@ where
         "objects-indexer"
     }
 
-    async fn process_checkpoint(&mut self, checkpoint_data: &CheckpointData) -> anyhow::Result<()> {
+    async fn process_checkpoint(
+        &mut self,
+        checkpoint_data: &Arc<CheckpointData>,
+    ) -> anyhow::Result<()> {
         let checkpoint_seq = *checkpoint_data.checkpoint_summary.sequence_number();
-        info!(checkpoint_seq, "Objects received by indexing processor");
+        trace!(checkpoint_seq, "Objects received by indexing processor");
         // Index checkpoint data
-        let index_timer = self.metrics.checkpoint_index_latency.start_timer();
-
-        let object_changes =
-            Self::index_checkpoint_objects(self.state.clone(), checkpoint_data).await;
+        let index_timer = self.metrics.checkpoint_objects_index_latency.start_timer();
+
+        let object_changes = match self.index_checkpoint_objects(checkpoint_data).await {
+            Ok(object_changes) => object_changes,
+            Err(e @ IndexerError::DataInconsistency(_)) => {
+                self.metrics.total_inconsistent_checkpoint.inc();
+                if !self.skip_inconsistent_checkpoints {
+                    error!("Halting objects indexing: {}", e);
+                    return Err(e.into());
+                }
+                error!("Skipping objects of checkpoint {}: {}", checkpoint_seq, e);
+                vec![]
+            }
+            Err(e) => return Err(e.into()),
+        };
         index_timer.stop_and_record();
 
+        let ended_epoch = checkpoint_data
+            .checkpoint_summary
+            .end_of_epoch_data
+            .as_ref()
+            .map(|_| checkpoint_data.checkpoint_summary.epoch());
         self.object_indexing_sender
-            .send((checkpoint_seq, object_changes))
+            .send((checkpoint_seq, ended_epoch, object_changes))
             .await
-            .tap_ok(|_| info!(checkpoint_seq, "Objects sent to commit handler"))
-            .unwrap_or_else(|e| {
-                panic!(
-                    "checkpoint channel send should not fail, but got error: {:?}",
-                    e
-                )
-            });
+            .map_err(|_| {
+                IndexerError::MpscChannelError("Object commit task has stopped".to_string())
+            })?;
+        trace!(checkpoint_seq, "Objects sent to commit handler");
 
         Ok(())
     }
@@ -841,11 +2095,93 @@ where
     S: IndexerStore + Clone + Sync + Send + 'static,
 {
     async fn index_checkpoint_objects(
-        packages_handler: S,
-        data: &CheckpointData,
-    ) -> Vec<TransactionObjectChanges> {
+        &self,
+        data: &Arc<CheckpointData>,
+    ) -> Result<Vec<TransactionObjectChanges>, IndexerError> {
+        let epoch = data.checkpoint_summary.epoch();
+        let checkpoint = *data.checkpoint_summary.sequence_number();
+        let name_service_registry_id = self.name_service_registry_id;
+        let metrics = self.metrics.clone();
+
+        // Index objects
+        // object digests and BCS are computed per changed object, spread the transactions
+        // over the rayon pool, collect keeps the transaction order. The pool is waited on
+        // from a blocking thread so that the async workers are not held meanwhile, the
+        // checkpoint is shared with it rather than copied.
+        let checkpoint_data = data.clone();
+        let object_changes = tokio::task::spawn_blocking(move || {
+            let objects: HashMap<_, _> = checkpoint_data
+                .objects
+                .iter()
+                .map(|o| ((o.id(), o.version()), o))
+                .collect();
+            checkpoint_data
+                .transactions
+                .par_iter()
+                .map(|(_, fx, _)| {
+                    let mut name_records = vec![];
+                    let changed_objects = fx
+                        .all_changed_objects()
+                        .into_iter()
+                        .map(|(oref, _owner, kind)| {
+                            let object = objects.get(&(oref.0, oref.1)).ok_or_else(|| {
+                                IndexerError::DataInconsistency(format!(
+                                    "Object {:?} changed by transaction {} not found in checkpoint {}",
+                                    oref,
+                                    fx.transaction_digest(),
+                                    checkpoint
+                                ))
+                            })?;
+                            if let Some(registry_id) = name_service_registry_id {
+                                // a malformed record is skipped rather than failing the
+                                // indexing of all objects of the checkpoint
+                                match NameRecord::try_from_object(object, registry_id, checkpoint) {
+                                    Ok(record) => name_records.extend(record),
+                                    Err(e) => {
+                                        warn!(
+                                            "Skipping name record {} of checkpoint {checkpoint}: {e}",
+                                            object.id()
+                                        );
+                                        metrics.total_skipped_name_records.inc();
+                                    }
+                                }
+                            }
+                            Ok(crate::models::objects::Object::new(
+                                epoch, checkpoint, kind, object,
+                            ))
+                        })
+                        .collect::<Result<Vec<_>, IndexerError>>()?;
+
+                    let deleted_objects = get_deleted_db_objects(fx, epoch, checkpoint);
+
+                    Ok(TransactionObjectChanges {
+                        changed_objects,
+                        deleted_objects,
+                        name_records,
+                    })
+                })
+                .collect::<Result<Vec<_>, IndexerError>>()
+        })
+        .await??;
+
         // Index packages
-        let packages = Self::index_packages(data);
+        let packages = Self::index_packages(data)?;
+        if self.halt_on_unparseable_packages {
+            Self::check_package_modules(&packages)?;
+        }
+        let packages_handler = self.state.clone();
+        if let Some(source_verifier) = self.source_verifier.clone() {
+            let package_ids = packages
+                .iter()
+                .filter_map(|p| ObjectID::from_str(&p.package_id).ok())
+                .collect::<Vec<_>>();
+            let sources_handler = packages_handler.clone();
+            spawn_monitored_task!(Self::index_verified_sources(
+                sources_handler,
+                source_verifier,
+                package_ids
+            ));
+        }
         spawn_monitored_task!(async move {
             let mut package_commit_res = packages_handler.persist_packages(&packages).await;
             while let Err(e) = package_commit_res {
@@ -861,38 +2197,37 @@ where
             }
         });
 
-        // Index objects
-        let epoch = data.checkpoint_summary.epoch();
-        let checkpoint = *data.checkpoint_summary.sequence_number();
-        let objects: HashMap<_, _> = data
-            .objects
-            .iter()
-            .map(|o| ((o.id(), o.version()), o))
-            .collect();
-
-        data.transactions
-            .iter()
-            .map(|(_, fx, _)| {
-                let changed_objects = fx
-                    .all_changed_objects()
-                    .into_iter()
-                    .map(|(oref, _owner, kind)| {
-                        let object = objects.get(&(oref.0, oref.1)).unwrap();
-                        crate::models::objects::Object::new(epoch, checkpoint, kind, object)
-                    })
-                    .collect();
-
-                let deleted_objects = get_deleted_db_objects(fx, epoch, checkpoint);
+        Ok(object_changes)
+    }
 
-                TransactionObjectChanges {
-                    changed_objects,
-                    deleted_objects,
+    // Best effort, packages are not always verified by the time they are indexed.
+    async fn index_verified_sources(
+        state: S,
+        source_verifier: SourceVerificationClient,
+        package_ids: Vec<ObjectID>,
+    ) {
+        for package_id in package_ids {
+            let source = match source_verifier.get_verified_source(package_id).await {
+                Ok(Some(source)) => source,
+                Ok(None) => continue,
+                Err(e) => {
+                    warn!(
+                        "Failed fetching verified source of package {} with error: {:?}",
+                        package_id, e
+                    );
+                    continue;
                 }
-            })
-            .collect()
+            };
+            if let Err(e) = state.persist_verified_source(source).await {
+                warn!(
+                    "Failed persisting verified source of package {} with error: {:?}",
+                    package_id, e
+                );
+            }
+        }
     }
 
-    fn index_packages(checkpoint_data: &CheckpointData) -> Vec<Package> {
+    fn index_packages(checkpoint_data: &CheckpointData) -> Result<Vec<Package>, IndexerError> {
         let senders: HashMap<_, _> = checkpoint_data
             .transactions
             .iter()
@@ -904,16 +2239,47 @@ where
             .iter()
             .filter_map(|o| {
                 if let sui_types::object::Data::Package(p) = &o.data {
-                    let sender = senders
-                        .get(&o.previous_transaction)
-                        .expect("transaction for this object should be present");
-                    Some(Package::new(*sender, p))
+                    let sender = senders.get(&o.previous_transaction).ok_or_else(|| {
+                        IndexerError::DataInconsistency(format!(
+                            "Transaction {} publishing package {} not found in checkpoint {}",
+                            o.previous_transaction,
+                            p.id(),
+                            checkpoint_data.checkpoint_summary.sequence_number()
+                        ))
+                    });
+                    Some(sender.map(|sender| Package::new(*sender, p)))
                 } else {
                     None
                 }
             })
             .collect()
     }
+
+    // Packages are otherwise stored as raw module bytes and their modules only deserialized
+    // when first resolved, modules that fail to deserialize (e.g. a bytecode version newer
+    // than this binary supports) are then resolvable once the indexer is upgraded.
+    fn check_package_modules(packages: &[Package]) -> Result<(), IndexerError> {
+        for package in packages {
+            let unparseable = package
+                .data
+                .iter()
+                .filter_map(|module| {
+                    CompiledModule::deserialize_with_defaults(&module.1)
+                        .err()
+                        .map(|e| format!("{}: {:?}", module.0, e))
+                })
+                .collect::<Vec<_>>();
+            if !unparseable.is_empty() {
+                return Err(IndexerError::ModuleDeserializationError(format!(
+                    "Package {} version {} has modules that cannot be deserialized: {}",
+                    package.package_id,
+                    package.version,
+                    unparseable.join(", ")
+                )));
+            }
+        }
+        Ok(())
+    }
 }
 
 pub fn get_deleted_db_objects(
@@ -947,3 +2313,211 @@ pub fn get_deleted_db_objects(
         })
         .collect::<Vec<_>>()
 }
+
+#[cfg(test)]
+mod test {
+    use move_core_types::language_storage::StructTag;
+    use sui_types::balance::Supply;
+    use sui_types::base_types::{SequenceNumber, SuiAddress};
+    use sui_types::coin::TreasuryCap;
+    use sui_types::crypto::{get_key_pair, AccountKeyPair};
+    use sui_types::effects::TransactionEffectsV1;
+    use sui_types::gas::GasCostSummary;
+    use sui_types::id::UID;
+    use sui_types::messages_checkpoint::CheckpointContents;
+    use sui_types::object::Object;
+    use sui_types::storage::ObjectStore;
+    use sui_types::transaction::TransactionData;
+    use sui_types::utils::to_sender_signed_transaction;
+
+    use super::*;
+
+    #[test]
+    fn test_checkpoint_object_store_returns_last_version() {
+        let id = ObjectID::random();
+        let owner = SuiAddress::ZERO;
+        let objects: Vec<Object> = [3u64, 7, 5]
+            .into_iter()
+            .map(|v| Object::with_id_owner_version_for_testing(id, SequenceNumber::from(v), owner))
+            .collect();
+        let store = CheckpointDataObjectStore { objects: &objects };
+
+        let latest = store.get_object(&id).unwrap().unwrap();
+        assert_eq!(latest.version(), SequenceNumber::from(7));
+        let earlier = store
+            .get_object_by_key(&id, SequenceNumber::from(3))
+            .unwrap()
+            .unwrap();
+        assert_eq!(earlier.version(), SequenceNumber::from(3));
+        assert!(store.get_object(&ObjectID::random()).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_index_transfers() {
+        let (sender, keypair): (_, AccountKeyPair) = get_key_pair();
+        let recipient = SuiAddress::random_for_testing_only();
+        let gas = Object::with_id_owner_gas_for_testing(ObjectID::random(), sender, 1000);
+        // a coin split off and sent, and a coin sent whole
+        let split = Object::with_id_owner_gas_for_testing(ObjectID::random(), recipient, 100);
+        let sent = Object::with_id_owner_gas_for_testing(ObjectID::random(), recipient, 200);
+        let data = TransactionData::new_transfer_sui(
+            recipient,
+            sender,
+            Some(100),
+            gas.compute_object_reference(),
+            1000,
+            1,
+        );
+        let tx = to_sender_signed_transaction(data, &keypair);
+        let gas_object = (gas.compute_object_reference(), Owner::AddressOwner(sender));
+        let fx = TransactionEffects::V1(TransactionEffectsV1 {
+            created: vec![(
+                split.compute_object_reference(),
+                Owner::AddressOwner(recipient),
+            )],
+            mutated: vec![
+                gas_object.clone(),
+                (
+                    sent.compute_object_reference(),
+                    Owner::AddressOwner(recipient),
+                ),
+            ],
+            gas_object,
+            ..Default::default()
+        });
+        let contents =
+            CheckpointContents::new_with_causally_ordered_transactions(std::iter::empty());
+        let summary = CheckpointSummary::new(
+            0,
+            1,
+            1,
+            &contents,
+            None,
+            GasCostSummary::default(),
+            None,
+            42,
+        );
+
+        let transfers = index_transfers(&summary, &[(tx, fx, None)], &[gas, split, sent]);
+        let transfers = transfers
+            .iter()
+            .map(|t| {
+                (
+                    t.sender.clone(),
+                    t.recipient.clone(),
+                    t.coin_type.as_str(),
+                    t.amount,
+                )
+            })
+            .collect::<Vec<_>>();
+        // both coins add up to the balance change of the recipient
+        assert_eq!(
+            transfers,
+            vec![(
+                sender.to_string(),
+                recipient.to_string(),
+                "0x2::sui::SUI",
+                300
+            )]
+        );
+    }
+
+    #[test]
+    fn test_index_ownership_transitions() {
+        let (sender, keypair): (_, AccountKeyPair) = get_key_pair();
+        let recipient = SuiAddress::random_for_testing_only();
+        let gas = Object::with_id_owner_gas_for_testing(ObjectID::random(), sender, 1000);
+        let treasury_cap = |id| {
+            Object::treasury_cap_for_testing(
+                StructTag::from_str("0x42::nft::NFT").unwrap(),
+                TreasuryCap {
+                    id: UID::new(id),
+                    total_supply: Supply { value: 0 },
+                },
+            )
+        };
+        // an object sent by the sender, and an object created for the sender
+        let sent = treasury_cap(ObjectID::random());
+        let created = treasury_cap(ObjectID::random());
+        let data = TransactionData::new_transfer(
+            recipient,
+            sent.compute_object_reference(),
+            sender,
+            gas.compute_object_reference(),
+            1000,
+            1,
+        );
+        let tx = to_sender_signed_transaction(data, &keypair);
+        let gas_object = (gas.compute_object_reference(), Owner::AddressOwner(sender));
+        let fx = TransactionEffects::V1(TransactionEffectsV1 {
+            created: vec![(
+                created.compute_object_reference(),
+                Owner::AddressOwner(sender),
+            )],
+            mutated: vec![
+                gas_object.clone(),
+                (
+                    sent.compute_object_reference(),
+                    Owner::AddressOwner(recipient),
+                ),
+            ],
+            gas_object,
+            ..Default::default()
+        });
+        let contents =
+            CheckpointContents::new_with_causally_ordered_transactions(std::iter::empty());
+        let summary = CheckpointSummary::new(
+            0,
+            1,
+            1,
+            &contents,
+            None,
+            GasCostSummary::default(),
+            None,
+            42,
+        );
+
+        let transitions = index_ownership_transitions(
+            &summary,
+            &[(tx, fx, None)],
+            &[gas, sent.clone(), created.clone()],
+        );
+        let transitions = transitions
+            .into_iter()
+            .map(|t| (t.object_id, t.from_owner, t.to_owner))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            transitions,
+            vec![
+                (
+                    sent.id().to_string(),
+                    Some(sender.to_string()),
+                    recipient.to_string()
+                ),
+                (created.id().to_string(), None, sender.to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tx_count_continuity() {
+        let checkpoint = Checkpoint {
+            sequence_number: 10,
+            total_transaction_blocks: 3,
+            network_total_transactions: 42,
+            ..Default::default()
+        };
+        assert!(check_tx_count_continuity(39, &checkpoint).is_ok());
+        assert!(matches!(
+            check_tx_count_continuity(40, &checkpoint),
+            Err(IndexerError::DataInconsistency(_))
+        ));
+
+        let genesis = Checkpoint {
+            total_transaction_blocks: 1,
+            network_total_transactions: 1,
+            ..Default::default()
+        };
+        assert!(check_tx_count_continuity(0, &genesis).is_ok());
+    }
+}
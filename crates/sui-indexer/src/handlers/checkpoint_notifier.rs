@@ -0,0 +1,217 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use backoff::future::retry;
+use backoff::ExponentialBackoff;
+use fastcrypto::encoding::{Encoding, Hex};
+use fastcrypto::hmac::{hmac_sha3_256, HmacKey};
+use fastcrypto::traits::ToFromBytes;
+use futures::future::join_all;
+use mysten_metrics::{get_metrics, metered_channel, spawn_monitored_task};
+use serde::Serialize;
+use tracing::{info, warn};
+
+use crate::errors::IndexerError;
+use crate::metrics::IndexerMetrics;
+use crate::models::checkpoints::Checkpoint;
+use crate::models::watchlists::WatchlistMatch;
+use crate::Secret;
+
+const NOTIFICATION_QUEUE_SIZE: usize = 1000;
+const WEBHOOK_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+// retries of a notification stop after this long, the notification is then dropped
+const WEBHOOK_MAX_RETRY_DURATION: Duration = Duration::from_secs(60);
+pub const SIGNATURE_HEADER: &str = "X-Sui-Indexer-Signature";
+
+/// Body of the POST requests sent to webhooks, tagged by `kind`.
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum Notification {
+    /// A checkpoint was committed.
+    Checkpoint(CheckpointNotification),
+    /// A committed transaction touched an entity of the watchlist.
+    WatchlistMatch(WatchlistMatch),
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckpointNotification {
+    pub sequence_number: u64,
+    pub digest: String,
+    pub transaction_blocks: u64,
+    pub timestamp_ms: u64,
+}
+
+impl From<&Checkpoint> for CheckpointNotification {
+    fn from(checkpoint: &Checkpoint) -> Self {
+        Self {
+            sequence_number: checkpoint.sequence_number as u64,
+            digest: checkpoint.checkpoint_digest.clone(),
+            transaction_blocks: checkpoint.total_transaction_blocks as u64,
+            timestamp_ms: checkpoint.timestamp_ms as u64,
+        }
+    }
+}
+
+/// POSTs a `Notification` to every webhook for each committed checkpoint and watchlist
+/// match, in commit order. Checkpoints are notified once their commit watermark covers them,
+/// i.e. once their events and transaction index tables are committed as well. Objects are
+/// committed by their own task and follow the object checkpoint watermark. Requests are
+/// signed with the secret if any: the hex encoded HMAC-SHA3-256 of the body is sent in the
+/// `SIGNATURE_HEADER` header. Notifications are sent in the background, they are dropped
+/// rather than holding back commits when webhooks fall behind.
+pub struct CheckpointNotifier {
+    sender: metered_channel::Sender<Notification>,
+    metrics: IndexerMetrics,
+}
+
+impl CheckpointNotifier {
+    pub fn new(
+        urls: Vec<String>,
+        secret: Option<Secret>,
+        metrics: IndexerMetrics,
+    ) -> Result<Self, IndexerError> {
+        let client = reqwest::Client::builder()
+            .timeout(WEBHOOK_REQUEST_TIMEOUT)
+            .build()
+            .map_err(|e| {
+                IndexerError::HttpClientInitError(format!(
+                    "Failed building webhook client with error: {e}"
+                ))
+            })?;
+        let key = secret
+            .map(|secret| {
+                HmacKey::from_bytes(secret.expose().as_bytes()).map_err(|e| {
+                    IndexerError::InvalidArgumentError(format!("Invalid webhook secret: {e}"))
+                })
+            })
+            .transpose()?;
+        let (sender, receiver) = metered_channel::channel(
+            NOTIFICATION_QUEUE_SIZE,
+            &get_metrics()
+                .unwrap()
+                .channels
+                .with_label_values(&["checkpoint_notification"]),
+        );
+        spawn_monitored_task!(send_notifications(
+            client,
+            urls,
+            key,
+            metrics.clone(),
+            receiver
+        ));
+        Ok(Self { sender, metrics })
+    }
+
+    pub fn notify(&self, checkpoint: &Checkpoint) {
+        self.send(Notification::Checkpoint(checkpoint.into()));
+    }
+
+    pub fn notify_watchlist_match(&self, watchlist_match: WatchlistMatch) {
+        self.send(Notification::WatchlistMatch(watchlist_match));
+    }
+
+    fn send(&self, notification: Notification) {
+        if let Err(e) = self.sender.try_send(notification) {
+            warn!(
+                "Dropped webhook notification {:?}, webhooks are falling behind",
+                e.into_inner()
+            );
+            self.metrics.total_checkpoint_notification_dropped.inc();
+        }
+    }
+}
+
+async fn send_notifications(
+    client: reqwest::Client,
+    urls: Vec<String>,
+    key: Option<HmacKey>,
+    metrics: IndexerMetrics,
+    mut receiver: metered_channel::Receiver<Notification>,
+) {
+    info!("Indexer checkpoint notification task started...");
+    while let Some(notification) = receiver.recv().await {
+        send_notification(&client, &urls, key.as_ref(), &metrics, notification).await;
+    }
+}
+
+async fn send_notification(
+    client: &reqwest::Client,
+    urls: &[String],
+    key: Option<&HmacKey>,
+    metrics: &IndexerMetrics,
+    notification: Notification,
+) {
+    // unwrap is safe, serializing the notification cannot fail
+    let body = serde_json::to_vec(&notification).unwrap();
+    let signature = key.map(|key| sign(key, &body));
+    let results = join_all(urls.iter().map(|url| {
+        let backoff = ExponentialBackoff {
+            max_elapsed_time: Some(WEBHOOK_MAX_RETRY_DURATION),
+            ..ExponentialBackoff::default()
+        };
+        retry(backoff, || async {
+            let mut request = client.post(url).header("Content-Type", "application/json");
+            if let Some(signature) = &signature {
+                request = request.header(SIGNATURE_HEADER, signature);
+            }
+            request
+                .body(body.clone())
+                .send()
+                .await
+                .and_then(|response| response.error_for_status())
+                .map_err(backoff::Error::transient)
+        })
+    }))
+    .await;
+    for (url, result) in urls.iter().zip(results) {
+        if let Err(e) = result {
+            warn!(
+                "Failed notifying {url} of {:?} with error: {e}",
+                notification
+            );
+            metrics.total_checkpoint_notification_failure.inc();
+        }
+    }
+}
+
+pub fn sign(key: &HmacKey, body: &[u8]) -> String {
+    Hex::encode(hmac_sha3_256(key, body).to_vec())
+}
+
+#[cfg(test)]
+mod test {
+    use fastcrypto::hmac::HmacKey;
+    use fastcrypto::traits::ToFromBytes;
+
+    use super::{sign, Notification};
+    use crate::models::checkpoints::Checkpoint;
+
+    #[test]
+    fn test_checkpoint_notification() {
+        let checkpoint = Checkpoint {
+            sequence_number: 42,
+            checkpoint_digest: "digest".to_string(),
+            total_transaction_blocks: 3,
+            timestamp_ms: 1000,
+            ..Default::default()
+        };
+        let body = serde_json::to_string(&Notification::Checkpoint((&checkpoint).into())).unwrap();
+        assert_eq!(
+            body,
+            r#"{"kind":"checkpoint","sequenceNumber":42,"digest":"digest","transactionBlocks":3,"timestampMs":1000}"#
+        );
+
+        let key = HmacKey::from_bytes(b"secret").unwrap();
+        let signature = sign(&key, body.as_bytes());
+        assert_eq!(signature.len(), 64);
+        assert_eq!(signature, sign(&key, body.as_bytes()));
+        assert_ne!(signature, sign(&key, b"other body"));
+        assert_ne!(
+            signature,
+            sign(&HmacKey::from_bytes(b"other").unwrap(), body.as_bytes())
+        );
+    }
+}
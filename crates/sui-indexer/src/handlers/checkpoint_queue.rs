@@ -0,0 +1,333 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use futures::StreamExt;
+use mysten_metrics::metered_channel::{Receiver, ReceiverStream, Sender};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use tracing::{info, warn};
+
+use crate::errors::IndexerError;
+use crate::metrics::IndexerMetrics;
+use crate::models::checkpoint_queue::{QueueKind, QueuedCheckpoint};
+use crate::store::IndexerStore;
+
+// checkpoints written to, or read from, the queue table at once
+const QUEUE_BATCH_SIZE: usize = 100;
+// wait of the indexers for the committer and of the committer for the indexers
+const QUEUE_POLL_INTERVAL: Duration = Duration::from_millis(500);
+const QUEUE_RETRY_INTERVAL: Duration = Duration::from_millis(100);
+// the committer warns about data queued past a missing sequence number this often
+const QUEUE_GAP_WARNING_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Role of a fullnode sync worker. The pipeline can be split across processes, scaling
+/// indexing out to several indexers while a single committer keeps the commit order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PipelineRole {
+    /// indexes and commits checkpoints
+    All,
+    /// indexes checkpoints into the checkpoint_queue table
+    Indexer,
+    /// commits the checkpoints of the checkpoint_queue table, in order
+    Committer,
+}
+
+impl PipelineRole {
+    pub const ALL: [PipelineRole; 3] = [
+        PipelineRole::All,
+        PipelineRole::Indexer,
+        PipelineRole::Committer,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            PipelineRole::All => "all",
+            PipelineRole::Indexer => "indexer",
+            PipelineRole::Committer => "committer",
+        }
+    }
+}
+
+impl FromStr for PipelineRole {
+    type Err = IndexerError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|role| role.name() == s)
+            .ok_or_else(|| {
+                IndexerError::InvalidArgumentError(format!(
+                    "Unknown pipeline role {s}, expected one of {:?}",
+                    Self::ALL.map(|role| role.name())
+                ))
+            })
+    }
+}
+
+/// Checkpoints indexed by one of `count` indexers, those with sequence numbers equal to
+/// `index` modulo `count`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IndexerShard {
+    pub index: u64,
+    pub count: u64,
+}
+
+impl Default for IndexerShard {
+    fn default() -> Self {
+        Self { index: 0, count: 1 }
+    }
+}
+
+impl IndexerShard {
+    pub fn contains(&self, sequence_number: u64) -> bool {
+        sequence_number % self.count == self.index
+    }
+
+    /// Sequence numbers of the shard in `range`, in order.
+    pub fn sequence_numbers(&self, range: RangeInclusive<u64>) -> impl Iterator<Item = u64> {
+        let (start, end) = range.into_inner();
+        let first = start + (self.index + self.count - start % self.count) % self.count;
+        (first..=end).step_by(self.count as usize)
+    }
+}
+
+impl fmt::Display for IndexerShard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.index, self.count)
+    }
+}
+
+impl FromStr for IndexerShard {
+    type Err = IndexerError;
+
+    /// Parses `<index>/<count>`, e.g. `0/4`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            IndexerError::InvalidArgumentError(format!(
+                "Invalid indexer shard {s}, expected <index>/<count> with index below count"
+            ))
+        };
+        let (index, count) = s.split_once('/').ok_or_else(invalid)?;
+        let index = index.parse::<u64>().map_err(|_| invalid())?;
+        let count = count.parse::<u64>().map_err(|_| invalid())?;
+        if index >= count {
+            return Err(invalid());
+        }
+        Ok(Self { index, count })
+    }
+}
+
+/// Writes the indexed data received on `receiver` to the queue of `kind`, keyed by
+/// `sequence_number`. With `max_queued` set, waits while the data is more than that many
+/// sequence numbers ahead of the last committed, so that the queue table stays bounded.
+pub async fn start_checkpoint_enqueue_task<S, T, F>(
+    state: S,
+    metrics: IndexerMetrics,
+    kind: QueueKind,
+    receiver: Receiver<T>,
+    sequence_number: F,
+    max_queued: Option<i64>,
+) where
+    S: IndexerStore + Clone + Sync + Send + 'static,
+    T: Serialize,
+    F: Fn(&T) -> i64,
+{
+    info!("Indexer {} enqueue task started...", kind.name());
+    let mut stream = ReceiverStream::new(receiver).ready_chunks(QUEUE_BATCH_SIZE);
+    let mut committed = -1;
+
+    while let Some(batch) = stream.next().await {
+        let last = batch.iter().map(&sequence_number).max().unwrap_or_default();
+        if let Some(max_queued) = max_queued {
+            while last > committed + max_queued {
+                match state.get_committed_queue_sequence_number(kind).await {
+                    Ok(sequence_number) if sequence_number > committed => {
+                        committed = sequence_number;
+                        continue;
+                    }
+                    Ok(_) => {}
+                    Err(e) => warn!(
+                        "Failed reading committed {} with error: {:?}",
+                        kind.name(),
+                        e
+                    ),
+                }
+                tokio::time::sleep(QUEUE_POLL_INTERVAL).await;
+            }
+        }
+
+        let checkpoints = batch
+            .iter()
+            .map(|data| QueuedCheckpoint::new(kind, sequence_number(data), data))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap_or_else(|e| panic!("indexed data should be serializable, but got: {:?}", e));
+        let queued = checkpoints.len();
+        let mut enqueue_res = state.enqueue_checkpoints(checkpoints.clone()).await;
+        while let Err(e) = enqueue_res {
+            warn!(
+                "Indexer {} enqueue failed with error: {:?}, retrying after {:?}...",
+                kind.name(),
+                e,
+                QUEUE_RETRY_INTERVAL
+            );
+            tokio::time::sleep(QUEUE_RETRY_INTERVAL).await;
+            enqueue_res = state.enqueue_checkpoints(checkpoints.clone()).await;
+        }
+        metrics
+            .total_checkpoints_enqueued
+            .with_label_values(&[kind.name()])
+            .inc_by(queued as u64);
+    }
+}
+
+/// Passes the data queued by the indexers of `kind` to its commit task through `sender`,
+/// in order of sequence number from the one after the last committed, and deletes it from
+/// the queue once committed. Only contiguous sequence numbers are passed on, missing ones,
+/// e.g. of the shard of an indexer lagging behind the others, are waited for.
+pub async fn start_checkpoint_dequeue_task<S, T>(
+    state: S,
+    metrics: IndexerMetrics,
+    kind: QueueKind,
+    sender: Sender<T>,
+) where
+    S: IndexerStore + Clone + Sync + Send + 'static,
+    T: DeserializeOwned,
+{
+    let mut committed_res = state.get_committed_queue_sequence_number(kind).await;
+    while let Err(e) = committed_res {
+        warn!(
+            "Failed reading committed {} with error: {:?}, retrying after {:?}...",
+            kind.name(),
+            e,
+            QUEUE_RETRY_INTERVAL
+        );
+        tokio::time::sleep(QUEUE_RETRY_INTERVAL).await;
+        committed_res = state.get_committed_queue_sequence_number(kind).await;
+    }
+    let mut next = committed_res.unwrap() + 1;
+    // committed data left in the queue by a previous run is deleted on the first pass
+    let mut deleted = -1;
+    let mut gap_warned_at: Option<Instant> = None;
+    info!(
+        "Indexer {} dequeue task started from {}...",
+        kind.name(),
+        next
+    );
+
+    loop {
+        match state.get_committed_queue_sequence_number(kind).await {
+            Ok(committed) if committed > deleted => {
+                match state.delete_queued_checkpoints(kind, committed).await {
+                    Ok(_) => deleted = committed,
+                    Err(e) => warn!(
+                        "Failed deleting committed {} from the queue with error: {:?}",
+                        kind.name(),
+                        e
+                    ),
+                }
+            }
+            Ok(_) => {}
+            Err(e) => warn!(
+                "Failed reading committed {} with error: {:?}",
+                kind.name(),
+                e
+            ),
+        }
+
+        let queued = match state
+            .get_queued_checkpoints(kind, next, QUEUE_BATCH_SIZE)
+            .await
+        {
+            Ok(queued) => queued,
+            Err(e) => {
+                warn!("Failed reading queued {} with error: {:?}", kind.name(), e);
+                tokio::time::sleep(QUEUE_RETRY_INTERVAL).await;
+                continue;
+            }
+        };
+        match queued.first() {
+            Some(checkpoint) if checkpoint.sequence_number != next => {
+                if gap_warned_at.map_or(true, |at| at.elapsed() >= QUEUE_GAP_WARNING_INTERVAL) {
+                    warn!(
+                        "Waiting for {} {} to be queued, {} is queued already",
+                        kind.name(),
+                        next,
+                        checkpoint.sequence_number
+                    );
+                    gap_warned_at = Some(Instant::now());
+                }
+            }
+            _ => gap_warned_at = None,
+        }
+        let mut dequeued = 0;
+        for checkpoint in queued {
+            if checkpoint.sequence_number != next {
+                break;
+            }
+            let data = checkpoint.data::<T>().unwrap_or_else(|e| {
+                panic!("queued data should be deserializable, but got: {:?}", e)
+            });
+            sender.send(data).await.unwrap_or_else(|e| {
+                panic!(
+                    "checkpoint channel send should not fail, but got error: {:?}",
+                    e
+                )
+            });
+            next += 1;
+            dequeued += 1;
+        }
+        metrics
+            .total_checkpoints_dequeued
+            .with_label_values(&[kind.name()])
+            .inc_by(dequeued);
+        metrics
+            .checkpoint_queue_next_sequence_number
+            .with_label_values(&[kind.name()])
+            .set(next);
+        if dequeued == 0 {
+            tokio::time::sleep(QUEUE_POLL_INTERVAL).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use super::{IndexerShard, PipelineRole};
+
+    #[test]
+    fn test_parse_indexer_shard() {
+        let shard = IndexerShard::from_str("1/4").unwrap();
+        assert_eq!(shard, IndexerShard { index: 1, count: 4 });
+        assert_eq!(shard.to_string(), "1/4");
+        assert!(shard.contains(5));
+        assert!(!shard.contains(4));
+        assert!(IndexerShard::default().contains(7));
+        assert_eq!(
+            shard.sequence_numbers(6..=17).collect::<Vec<_>>(),
+            vec![9, 13, 17]
+        );
+        assert_eq!(shard.sequence_numbers(1..=1).collect::<Vec<_>>(), vec![1]);
+        assert_eq!(shard.sequence_numbers(2..=4).count(), 0);
+        assert_eq!(
+            IndexerShard::default()
+                .sequence_numbers(0..=3)
+                .collect::<Vec<_>>(),
+            vec![0, 1, 2, 3]
+        );
+        for invalid in ["4/4", "1", "a/4", "0/0", "-1/4"] {
+            assert!(IndexerShard::from_str(invalid).is_err(), "{invalid}");
+        }
+        assert_eq!(
+            PipelineRole::from_str("committer").unwrap(),
+            PipelineRole::Committer
+        );
+        assert!(PipelineRole::from_str("writer").is_err());
+    }
+}
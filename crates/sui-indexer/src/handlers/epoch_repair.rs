@@ -0,0 +1,94 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use sui_json_rpc_types::CheckpointId;
+use sui_rest_api::Client;
+use tracing::{info, warn};
+
+use crate::errors::IndexerError;
+use crate::framework::endpoints::FullNodeEndpoints;
+use crate::handlers::checkpoint_handler::CheckpointProcessor;
+use crate::metrics::IndexerMetrics;
+use crate::store::IndexerStore;
+
+// a request taking longer than this fails over to the next endpoint
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Commits the epochs started by committed checkpoints but missing from the epochs table,
+/// in order. Epochs are committed apart from their checkpoints, so an indexer stopped in
+/// between leaves the epoch of its last checkpoints missing for good, as indexing resumes
+/// after that checkpoint. The checkpoint that starts a missing epoch, the last one of the
+/// epoch before, is downloaded again to index the epoch.
+pub async fn repair_missing_epochs<S>(
+    store: &S,
+    client: &FullNodeEndpoints<Client>,
+    metrics: &IndexerMetrics,
+) -> Result<(), IndexerError>
+where
+    S: IndexerStore + Clone + Sync + Send + 'static,
+{
+    let latest_checkpoint = store.get_latest_tx_checkpoint_sequence_number().await?;
+    if latest_checkpoint < 0 {
+        return Ok(());
+    }
+    let checkpoint_epoch = store
+        .get_checkpoint(CheckpointId::SequenceNumber(latest_checkpoint as u64))
+        .await?
+        .epoch as i64;
+    let latest_epoch = store.get_latest_epoch_id().await?;
+    if latest_epoch >= checkpoint_epoch {
+        return Ok(());
+    }
+    warn!(
+        "Epochs {}..={} of committed checkpoints are missing, committing them",
+        latest_epoch + 1,
+        checkpoint_epoch
+    );
+
+    for epoch in latest_epoch + 1..=checkpoint_epoch {
+        // the first epoch is started by the genesis checkpoint
+        let boundary = if epoch == 0 {
+            Some(0)
+        } else {
+            store.get_epoch_last_checkpoint(epoch - 1).await?
+        };
+        let boundary = boundary.ok_or_else(|| {
+            IndexerError::DataInconsistency(format!(
+                "Epoch {epoch} is missing, but no checkpoint of epoch {} is committed",
+                epoch - 1
+            ))
+        })?;
+        let checkpoint = client
+            .call(
+                |client| async move {
+                    tokio::time::timeout(
+                        REQUEST_TIMEOUT,
+                        client.get_full_checkpoint(boundary as u64),
+                    )
+                    .await?
+                },
+                |_| true,
+            )
+            .await
+            .map_err(|e| {
+                IndexerError::FullNodeReadingError(format!(
+                    "Failed downloading checkpoint {boundary} to commit epoch {epoch}: {e}"
+                ))
+            })?;
+        let indexed = CheckpointProcessor::index_epoch(store, &checkpoint)
+            .await?
+            .filter(|indexed| indexed.new_epoch.epoch == epoch)
+            .ok_or_else(|| {
+                IndexerError::DataInconsistency(format!(
+                    "Checkpoint {boundary} does not start missing epoch {epoch}"
+                ))
+            })?;
+        store.persist_epoch(&indexed).await?;
+        metrics.total_epoch_committed.inc();
+        metrics.total_epochs_repaired.inc();
+        info!("Committed missing epoch {epoch} from checkpoint {boundary}");
+    }
+    Ok(())
+}
@@ -0,0 +1,149 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashSet;
+use std::time::Duration;
+
+use mysten_metrics::{get_metrics, spawn_monitored_task};
+use sui_rest_api::Client;
+use tracing::{error, info, warn};
+
+use crate::errors::IndexerError;
+use crate::framework::downloader::CheckpointDownloader;
+use crate::framework::endpoints::FullNodeEndpoints;
+use crate::framework::interface::Handler;
+use crate::framework::memory_budget::budgeted_channel;
+use crate::handlers::checkpoint_handler::CheckpointProcessor;
+use crate::metrics::IndexerMetrics;
+use crate::store::IndexerStore;
+
+const GAP_DETECTION_INTERVAL: Duration = Duration::from_secs(600);
+// number of checkpoints read per query when scanning
+const GAP_SCAN_BATCH_SIZE: i64 = 10_000;
+const GAP_DOWNLOAD_QUEUE_SIZE: usize = 100;
+const GAP_DOWNLOAD_MEMORY_BUDGET_BYTES: usize = 256 * 1024 * 1024;
+const GAP_DOWNLOAD_CONCURRENCY: usize = 10;
+
+/// Scans committed checkpoints for missing sequence numbers and for breaks in the
+/// continuity of `network_total_transactions`, on startup and then periodically.
+/// Missing checkpoints are downloaded again and indexed by a checkpoint processor
+/// of their own, which shares the commit tasks of the main pipeline. Objects are
+/// left alone, as their later versions are committed already.
+/// Continuity breaks are only reported, committed checkpoints are never overwritten.
+pub struct GapDetector<S> {
+    store: S,
+    downloader: CheckpointDownloader,
+    metrics: IndexerMetrics,
+    // all checkpoints up to this one are committed
+    contiguous_up_to: i64,
+    // discontinuities already reported, by sequence number of the checkpoint before them
+    reported: HashSet<i64>,
+    // gaps whose re-ingestion failed, retried on the next scan
+    failed: HashSet<i64>,
+}
+
+impl<S> GapDetector<S>
+where
+    S: IndexerStore + Clone + Sync + Send + 'static,
+{
+    pub fn new(
+        store: S,
+        client: FullNodeEndpoints<Client>,
+        processor: CheckpointProcessor<S>,
+        metrics: IndexerMetrics,
+    ) -> Self {
+        let (sender, stream) = budgeted_channel(
+            GAP_DOWNLOAD_QUEUE_SIZE,
+            GAP_DOWNLOAD_MEMORY_BUDGET_BYTES,
+            &get_metrics()
+                .unwrap()
+                .channels
+                .with_label_values(&["checkpoint_gap_downloading"]),
+            metrics.buffered_checkpoint_bytes.clone(),
+        );
+        let handlers: Vec<Box<dyn Handler>> = vec![Box::new(processor)];
+        spawn_monitored_task!(async move {
+            if let Err(e) = crate::framework::runner::run(stream, handlers).await {
+                error!("Gap re-ingestion stopped with error: {:?}", e);
+            }
+        });
+        Self {
+            store,
+            downloader: CheckpointDownloader::new(
+                client,
+                sender,
+                GAP_DOWNLOAD_CONCURRENCY,
+                metrics.clone(),
+            ),
+            metrics,
+            contiguous_up_to: 0,
+            reported: HashSet::new(),
+            failed: HashSet::new(),
+        }
+    }
+
+    pub async fn run(mut self) {
+        info!("Indexer checkpoint gap detector started...");
+        let mut interval = tokio::time::interval(GAP_DETECTION_INTERVAL);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.detect_gaps().await {
+                warn!("Failed detecting checkpoint gaps with error: {:?}", e);
+            }
+        }
+    }
+
+    async fn detect_gaps(&mut self) -> Result<(), IndexerError> {
+        let latest_checkpoint = self
+            .store
+            .get_latest_tx_checkpoint_sequence_number()
+            .await?;
+        let mut first_gap = None;
+        let mut start = self.contiguous_up_to;
+        while start < latest_checkpoint {
+            let end = std::cmp::min(start + GAP_SCAN_BATCH_SIZE, latest_checkpoint);
+            // ranges overlap by one checkpoint so that pairs across them are compared
+            let discontinuities = self
+                .store
+                .get_checkpoint_discontinuities(start, end)
+                .await?;
+            for discontinuity in discontinuities {
+                let missing = discontinuity.missing_checkpoints();
+                if missing.is_some() {
+                    first_gap = first_gap.or(Some(discontinuity.sequence_number));
+                }
+                let first_seen = self.reported.insert(discontinuity.sequence_number);
+                if first_seen {
+                    self.metrics.gaps_detected.inc();
+                }
+                match missing {
+                    Some(missing)
+                        if first_seen || self.failed.remove(&discontinuity.sequence_number) =>
+                    {
+                        warn!("Checkpoints {missing:?} are missing, re-ingesting them");
+                        if let Err(e) = self.downloader.download(missing.clone(), |_| {}).await {
+                            warn!("Failed re-ingesting checkpoints {missing:?}: {e}");
+                            self.failed.insert(discontinuity.sequence_number);
+                        }
+                    }
+                    None if first_seen => error!(
+                        "Network total transactions of checkpoint {} is {}, expected {} from checkpoint {}",
+                        discontinuity.next_sequence_number,
+                        discontinuity.next_network_total_transactions,
+                        discontinuity.network_total_transactions
+                            + discontinuity.next_total_transaction_blocks,
+                        discontinuity.sequence_number,
+                    ),
+                    // already re-ingested or reported
+                    _ => {}
+                }
+            }
+            start = end;
+        }
+        // gaps are scanned again until filled, continuity breaks are permanent and
+        // don't hold back the scan
+        self.contiguous_up_to = first_gap.unwrap_or(start);
+        Ok(())
+    }
+}
@@ -1,4 +1,11 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod checkpoint_archiver;
 pub mod checkpoint_handler;
+pub mod checkpoint_notifier;
+pub mod checkpoint_queue;
+pub mod epoch_repair;
+pub mod gap_detector;
+pub mod pipelines;
+pub mod source_verification;
@@ -0,0 +1,102 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashSet;
+use std::str::FromStr;
+
+use crate::errors::IndexerError;
+
+/// Tables filled while indexing checkpoints that can be left empty, so that deployments
+/// only pay for the tables they query.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Pipeline {
+    /// events and display
+    Events,
+    /// input_objects, changed_objects, move_calls, tx_commands, tx_calls, recipients and
+    /// tx_dependencies, which also feed hot_objects and hot_packages
+    TxIndices,
+    Transfers,
+    /// object_ownership_history
+    OwnershipHistory,
+    /// objects_history, filled by a trigger on objects
+    ObjectsHistory,
+    /// checkpoint_proofs, served to light clients verifying transactions
+    CheckpointProofs,
+}
+
+impl Pipeline {
+    pub const ALL: [Pipeline; 6] = [
+        Pipeline::Events,
+        Pipeline::TxIndices,
+        Pipeline::Transfers,
+        Pipeline::OwnershipHistory,
+        Pipeline::ObjectsHistory,
+        Pipeline::CheckpointProofs,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Pipeline::Events => "events",
+            Pipeline::TxIndices => "tx_indices",
+            Pipeline::Transfers => "transfers",
+            Pipeline::OwnershipHistory => "ownership_history",
+            Pipeline::ObjectsHistory => "objects_history",
+            Pipeline::CheckpointProofs => "checkpoint_proofs",
+        }
+    }
+}
+
+impl FromStr for Pipeline {
+    type Err = IndexerError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|pipeline| pipeline.name() == s)
+            .ok_or_else(|| {
+                IndexerError::InvalidArgumentError(format!(
+                    "Unknown pipeline {s}, expected one of {:?}",
+                    Self::ALL.map(|pipeline| pipeline.name())
+                ))
+            })
+    }
+}
+
+/// Pipelines to run, all of them but the disabled ones.
+#[derive(Clone, Debug, Default)]
+pub struct Pipelines {
+    disabled: HashSet<Pipeline>,
+}
+
+impl Pipelines {
+    pub fn new(disabled: &[String]) -> Result<Self, IndexerError> {
+        Ok(Self {
+            disabled: disabled
+                .iter()
+                .map(|pipeline| Pipeline::from_str(pipeline))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    pub fn is_enabled(&self, pipeline: Pipeline) -> bool {
+        !self.disabled.contains(&pipeline)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Pipeline, Pipelines};
+
+    #[test]
+    fn test_disable_pipelines() {
+        let pipelines =
+            Pipelines::new(&["events".to_string(), "objects_history".to_string()]).unwrap();
+        assert!(!pipelines.is_enabled(Pipeline::Events));
+        assert!(!pipelines.is_enabled(Pipeline::ObjectsHistory));
+        assert!(pipelines.is_enabled(Pipeline::TxIndices));
+        assert!(Pipeline::ALL
+            .into_iter()
+            .all(|pipeline| Pipelines::default().is_enabled(pipeline)));
+        assert!(Pipelines::new(&["transactions".to_string()]).is_err());
+    }
+}
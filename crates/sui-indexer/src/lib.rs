@@ -3,11 +3,19 @@
 #![recursion_limit = "256"]
 
 use std::env;
-use std::net::SocketAddr;
+use std::net::{IpAddr, SocketAddr};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Arc;
 use std::{collections::HashMap, time::Duration};
 
 use anyhow::{anyhow, Result};
-use axum::{extract::Extension, http::StatusCode, routing::get, Router};
+use axum::{
+    extract::{Extension, Query},
+    http::StatusCode,
+    routing::get,
+    Json, Router,
+};
 use clap::Parser;
 use diesel::pg::PgConnection;
 use diesel::r2d2::ConnectionManager;
@@ -15,33 +23,65 @@ use jsonrpsee::http_client::{HeaderMap, HeaderValue, HttpClient, HttpClientBuild
 use metrics::IndexerMetrics;
 use prometheus::{Registry, TextEncoder};
 use regex::Regex;
+use serde::{Deserialize, Serialize};
 use tokio::runtime::Handle;
+use tokio::task::JoinHandle;
 use tracing::{info, warn};
 use url::Url;
 
 use apis::{
-    CoinReadApi, ExtendedApi, GovernanceReadApi, IndexerApi, ReadApi, TransactionBuilderApi,
-    WriteApi,
+    is_transport_error, run_export_server, ApiKeyAuth, ClientLayers, ClientRateLimiter,
+    CoinReadApi, ExtendedApi, FullNodeFallback, GovernanceReadApi, IndexerApi, QueryLimits,
+    ReadApi, TransactionBuilderApi, WriteApi,
 };
 use errors::IndexerError;
 use mysten_metrics::{spawn_monitored_task, RegistryService};
+use processors::leader_election::LeaderElection;
+use processors::materialized_view_processor::MaterializedViewProcessor;
 use processors::processor_orchestrator::ProcessorOrchestrator;
+use processors::pruner::Pruner;
+use processors::reexecution_processor::ReexecutionProcessor;
+use processors::table_size_processor::TableSizeProcessor;
 use store::IndexerStore;
-use sui_json_rpc::{JsonRpcServerBuilder, ServerHandle, ServerType, CLIENT_SDK_TYPE_HEADER};
+use sui_json_rpc::api::ReadApiClient;
+use sui_json_rpc::{JsonRpcServerBuilder, ServerType, CLIENT_SDK_TYPE_HEADER};
+use sui_json_rpc_types::CheckpointId;
 use sui_sdk::{SuiClient, SuiClientBuilder};
+use sui_types::base_types::ObjectID;
 
 use crate::apis::MoveUtilsApi;
-use crate::framework::fetcher::CheckpointFetcher;
-use crate::handlers::checkpoint_handler::new_handlers;
+use crate::framework::downloader::CheckpointDownloader;
+use crate::framework::endpoints::FullNodeEndpoints;
+use crate::framework::fetcher::{genesis_checkpoint_data, CheckpointFetcher};
+use crate::framework::interface::Handler;
+use crate::framework::memory_budget::budgeted_channel;
+use crate::handlers::checkpoint_archiver::{make_object_store, CheckpointArchiver};
+use crate::handlers::checkpoint_handler::{
+    new_handlers, new_queueing_handlers, start_queue_committer,
+};
+use crate::handlers::checkpoint_queue::PipelineRole;
+use crate::handlers::epoch_repair::repair_missing_epochs;
+use crate::handlers::gap_detector::GapDetector;
+use crate::handlers::pipelines::{Pipeline, Pipelines};
+use crate::models::chain_identifier::ChainIdentifier;
+use crate::models::materialized_views::MaterializedView;
+use crate::models::retention::parse_retention_policies;
 
+pub mod admin;
 pub mod apis;
+pub mod client;
+pub mod config;
 pub mod errors;
 pub mod framework;
-mod handlers;
+pub mod handlers;
 pub mod metrics;
 pub mod models;
+pub mod networks;
+pub mod pagination;
 pub mod processors;
+pub mod query;
 pub mod schema;
+pub mod search;
 pub mod store;
 pub mod test_utils;
 pub mod types;
@@ -51,6 +91,8 @@ pub type PgConnectionPool = diesel::r2d2::Pool<ConnectionManager<PgConnection>>;
 pub type PgPoolConnection = diesel::r2d2::PooledConnection<ConnectionManager<PgConnection>>;
 
 const METRICS_ROUTE: &str = "/metrics";
+const HEALTH_ROUTE: &str = "/health";
+const METRICS_PREFIX: &str = "indexer";
 /// Returns all endpoints for which we have implemented on the indexer,
 /// some of them are not validated yet.
 /// NOTE: we only use this for integration testing
@@ -87,16 +129,135 @@ pub struct IndexerConfig {
     pub db_port: Option<u16>,
     #[clap(long)]
     pub db_name: Option<String>,
+    // Connection url of the reader role serving queries of the RPC APIs, e.g. of a read
+    // replica. Queries are served with the writer role of db_url if not set.
+    #[clap(long, alias = "db-read-replica-url")]
+    pub db_reader_url: Option<String>,
+    // Max connections of each connection pool, DB_POOL_SIZE or 100 if not set.
+    #[clap(long)]
+    pub db_pool_size: Option<u32>,
+    // Seconds to wait for a connection of a pool, DB_CONNECTION_TIMEOUT or 30 if not set.
+    #[clap(long)]
+    pub db_connection_timeout_secs: Option<u64>,
+    // Statement timeout in seconds of the connections of the indexer, DB_STATEMENT_TIMEOUT
+    // or 30 if not set.
+    #[clap(long)]
+    pub db_statement_timeout_secs: Option<u64>,
+    // Start the RPC server even if its role is able to modify the database, in which case
+    // its reads may write back what they computed, e.g. event schemas.
+    #[clap(long)]
+    pub allow_db_reader_writes: bool,
+    // Statement timeout in milliseconds of queries of the RPC APIs, queries running longer
+    // are cancelled.
+    #[clap(long, default_value = "10000", global = true)]
+    pub db_query_timeout_ms: u64,
+    // Cache the latest checkpoint and current epoch for a short time in front of Postgres.
+    #[clap(long)]
+    pub enable_query_cache: bool,
+    // Log reads of the RPC APIs taking longer than this many milliseconds, along with
+    // their parameters, disabled if not set.
+    #[clap(long)]
+    pub slow_query_threshold_ms: Option<u64>,
+    // Compress the BCS of objects and raw transactions written with zstd at this level,
+    // 0 selecting the default level. Rows written uncompressed stay readable either way.
+    #[clap(long)]
+    pub payload_compression_level: Option<i32>,
+    // YAML object store config of a blob store that the BCS of objects larger than
+    // object_blob_threshold_bytes is offloaded to, with only a pointer kept in Postgres.
+    // Objects written with offloading can only be read with the same blob store. Payloads
+    // failing to upload are kept in Postgres, blobs are deleted along with the last
+    // objects_history row pointing to them when it is pruned.
+    #[clap(long)]
+    pub object_blob_store_config: Option<PathBuf>,
+    #[clap(long, default_value = "262144")]
+    pub object_blob_threshold_bytes: usize,
+    // Rows committed per statement, capped for tables with many columns so that a statement
+    // binds at most 65535 parameters.
+    #[clap(long, default_value = "1000")]
+    pub commit_chunk_size: usize,
+    // Rows committed per statement of tables, overriding commit_chunk_size, e.g.
+    // events=5000,objects=500.
+    #[clap(
+        long,
+        multiple_occurrences = false,
+        multiple_values = true,
+        value_delimiter = ','
+    )]
+    pub table_commit_chunk_sizes: Vec<String>,
     #[clap(long)]
     pub rpc_client_url: String,
+    // Extra full node endpoints. Requests are spread over these and rpc_client_url,
+    // failing over to another endpoint on error.
+    #[clap(long, multiple_occurrences = false, multiple_values = true)]
+    pub additional_rpc_client_urls: Vec<String>,
     #[clap(long, default_value = "0.0.0.0", global = true)]
     pub client_metric_host: String,
     #[clap(long, default_value = "9184", global = true)]
     pub client_metric_port: u16,
+    // Serve admin routes, e.g. to change log levels at runtime or manage the watchlist, on
+    // localhost at this port, disabled if not set.
+    #[clap(long)]
+    pub admin_port: Option<u16>,
+    // Log in newline-delimited JSON, as with RUST_LOG_JSON.
+    #[clap(long)]
+    pub log_json: bool,
     #[clap(long, default_value = "0.0.0.0", global = true)]
     pub rpc_server_url: String,
     #[clap(long, default_value = "9000", global = true)]
     pub rpc_server_port: u16,
+    // Max page size of paginated reads served from the database, larger limits are clamped.
+    // Defaults to the RPC_QUERY_MAX_RESULT_LIMIT of the full node.
+    #[clap(long)]
+    pub rpc_max_page_size: Option<usize>,
+    // Max rows a request for a list of keys may read, e.g. digests of a multi-get.
+    // Defaults to the RPC_QUERY_MAX_RESULT_LIMIT of the full node.
+    #[clap(long)]
+    pub rpc_max_scanned_rows: Option<usize>,
+    // Requests per second allowed to each client of the RPC server, by its peer address,
+    // unlimited if not set.
+    #[clap(long)]
+    pub rpc_client_requests_per_second: Option<u32>,
+    // Addresses of the proxies in front of the RPC server, e.g. 10.0.0.1,10.0.0.2. Clients
+    // connecting through them are told apart by X-Forwarded-For instead, which is ignored
+    // from any other peer.
+    #[clap(
+        long,
+        multiple_occurrences = false,
+        multiple_values = true,
+        value_delimiter = ','
+    )]
+    pub rpc_trusted_proxies: Vec<IpAddr>,
+    // Require clients of the RPC server to send a key of the api_keys table in the
+    // x-api-key header, each key being limited to its own quota.
+    #[clap(long)]
+    pub rpc_require_api_key: bool,
+    // Serve CSV/JSONL exports of transactions and events, read only SQL queries and light
+    // client proofs of transactions on rpc_server_url at this port along with the RPC
+    // server, disabled if not set.
+    #[clap(long)]
+    pub export_server_port: Option<u16>,
+    // Views that clients can run ad hoc SELECTs over with POST /sql on the export server,
+    // e.g. network_metrics,daily_transaction_counts, disabled if not set.
+    #[clap(
+        long,
+        multiple_occurrences = false,
+        multiple_values = true,
+        value_delimiter = ','
+    )]
+    pub readonly_sql_views: Vec<String>,
+    // Rows returned by a read only SQL query at most, longer results are truncated.
+    #[clap(long, default_value = "1000")]
+    pub readonly_sql_max_rows: usize,
+    // Statement timeout in milliseconds of read only SQL queries.
+    #[clap(long, default_value = "5000")]
+    pub readonly_sql_timeout_ms: u64,
+    // Role read only SQL queries run as, which must be able to SELECT from the views of
+    // readonly_sql_views only, see ReadonlySql for how to provision it.
+    #[clap(long, default_value = "readonly_sql")]
+    pub readonly_sql_role: String,
+    // Read only SQL queries running at once at most, further queries are rejected.
+    #[clap(long, default_value = "4")]
+    pub readonly_sql_max_concurrent_queries: usize,
     #[clap(long, multiple_occurrences = false, multiple_values = true)]
     pub migrated_methods: Vec<String>,
     #[clap(long)]
@@ -108,6 +269,133 @@ pub struct IndexerConfig {
     // NOTE: experimental only, do not use in production.
     #[clap(long)]
     pub skip_db_commit: bool,
+    // Skip objects of a checkpoint with inconsistent data instead of halting indexing.
+    #[clap(long)]
+    pub skip_inconsistent_checkpoints: bool,
+    // Index the records of the name service registry table with this id, which is the
+    // parent of their dynamic fields, and not the name service if not set.
+    #[clap(long)]
+    pub name_service_registry_id: Option<ObjectID>,
+    // Halt indexing on packages with modules that fail to deserialize, e.g. of a newer
+    // bytecode version, instead of storing them and resolving their modules lazily.
+    #[clap(long)]
+    pub halt_on_unparseable_packages: bool,
+    // Source verification service queried for verified sources of newly indexed packages.
+    #[clap(long)]
+    pub source_verification_url: Option<String>,
+    // Max number of requests in flight to the full node for methods served by fallback.
+    #[clap(long, default_value = "100", global = true)]
+    pub fullnode_max_concurrent_requests: usize,
+    // Checkpoints downloaded concurrently from the full node to begin with, adjusted between
+    // a quarter and twice this value by download latency and errors.
+    #[clap(long, default_value = "100")]
+    pub checkpoint_download_concurrency: usize,
+    // Memory budget in MiB of downloaded checkpoints waiting to be indexed.
+    #[clap(long, default_value = "2048", global = true)]
+    pub download_queue_memory_budget_mb: usize,
+    // Checkpoints queued for transaction and object indexing each, CHECKPOINT_QUEUE_SIZE or
+    // 1000 if not set.
+    #[clap(long)]
+    pub checkpoint_queue_size: Option<usize>,
+    // Role of the fullnode sync worker in a pipeline split across processes: `all` indexes
+    // and commits checkpoints, `indexer` only indexes them into the checkpoint_queue table
+    // and `committer` only commits the queued checkpoints, in order. Several indexers, each
+    // of its own --indexer-shard, can feed a single committer.
+    #[clap(long, default_value = "all")]
+    pub pipeline_role: String,
+    // Checkpoints indexed by an `indexer`, as <index>/<count> of the sequence numbers
+    // modulo count, e.g. 1/4. All checkpoints are indexed if not set.
+    #[clap(long)]
+    pub indexer_shard: Option<String>,
+    // Checkpoints an `indexer` queues ahead of the last committed before waiting for the
+    // committer.
+    #[clap(long, default_value = "10000")]
+    pub max_queued_checkpoints: usize,
+    // Replicas of a fullnode sync worker stand by until they take the leader lock of their
+    // role in the database, so that only one of them writes and a standby takes over when
+    // the leader stops. The leader stops once it loses the lock.
+    #[clap(long)]
+    pub leader_election: bool,
+    // Interval of standbys trying the leader lock, and of the leader checking it holds it.
+    #[clap(long, default_value = "5")]
+    pub leader_election_interval_secs: u64,
+    // Directory of an on-disk module cache kept across restarts, disabled if not set.
+    #[clap(long)]
+    pub module_cache_path: Option<PathBuf>,
+    // YAML object store config of an archive that every ingested checkpoint is written to,
+    // disabled if not set.
+    #[clap(long)]
+    pub checkpoint_archive_config: Option<PathBuf>,
+    // genesis.blob of the network, an empty database is indexed from its genesis checkpoint
+    // rather than the full node's, for networks whose full nodes prune history.
+    #[clap(long)]
+    pub genesis_blob_path: Option<PathBuf>,
+    // Also store transactions as JSONB in transactions_json, for SQL queries over their
+    // nested fields.
+    #[clap(long)]
+    pub index_transactions_json: bool,
+    // Aggregates refreshed by the indexer, any of the materialized views
+    // daily_transaction_counts, daily_active_addresses and top_packages, and the
+    // epoch_storage_growth table, updated incrementally from the last epoch it covers.
+    #[clap(long, multiple_occurrences = false, multiple_values = true)]
+    pub materialized_views: Vec<String>,
+    #[clap(long, default_value = "3600")]
+    pub materialized_view_refresh_interval_secs: u64,
+    // Number of epochs of data kept in table families, e.g. events=30,objects_history=7.
+    // Families are any of events, transactions, tx_indices, transfers and objects_history,
+    // and are kept forever without a policy.
+    #[clap(
+        long,
+        multiple_occurrences = false,
+        multiple_values = true,
+        value_delimiter = ','
+    )]
+    pub retention: Vec<String>,
+    // URLs that a JSON summary of every committed checkpoint is POSTed to, along with
+    // every committed transaction touching an entity of the watchlists table.
+    #[clap(long, multiple_occurrences = false, multiple_values = true)]
+    pub checkpoint_webhook_urls: Vec<String>,
+    // Secret that checkpoint webhook requests are signed with, unsigned if not set.
+    #[clap(long)]
+    pub checkpoint_webhook_secret: Option<Secret>,
+    // Pipelines whose tables are left empty, any of events, tx_indices, transfers,
+    // ownership_history, objects_history and checkpoint_proofs.
+    #[clap(
+        long = "disable",
+        multiple_occurrences = false,
+        multiple_values = true,
+        value_delimiter = ','
+    )]
+    pub disabled_pipelines: Vec<String>,
+    // Checkpoints are committed in batches of about this many rows, so that batches span
+    // many checkpoints while backfilling and few at the tip of the chain.
+    #[clap(long, default_value = "5000")]
+    pub checkpoint_commit_batch_rows: usize,
+    // A batch is committed as soon as no more indexed checkpoints are ready. While they keep
+    // being ready, e.g. catching up, it is committed at most this long after its first
+    // checkpoint was indexed, even if it has fewer rows than checkpoint_commit_batch_rows.
+    #[clap(long, default_value = "200")]
+    pub checkpoint_commit_max_latency_ms: u64,
+    // Committed batches are summarized in one info log line per commit task at most this
+    // often, 0 logs every batch. Per-checkpoint logs are at trace level.
+    #[clap(long, default_value = "10")]
+    pub commit_log_interval_secs: u64,
+    // Table sizes are sampled this often and exported as metrics, 0 disables sampling.
+    #[clap(long, default_value = "300")]
+    pub table_size_sample_interval_secs: u64,
+    // Tables growing this many times faster or slower than their average are warned about.
+    #[clap(long, default_value = "3.0")]
+    pub table_growth_alert_factor: f64,
+    // One in this many indexed transactions is re-executed against the object versions of
+    // objects_history and its effects digest compared to its checkpoint, mismatches are
+    // recorded in reexecution_mismatches. 0 disables re-execution.
+    #[clap(long, default_value = "0")]
+    pub reexecution_sample_interval: u64,
+    // YAML list of networks indexed by this process, each into the Postgres schema of its
+    // name with its own full node, sharing the runtime and the metrics server. The full
+    // node options of the command line are ignored when set.
+    #[clap(long)]
+    pub networks_config: Option<PathBuf>,
 }
 
 impl IndexerConfig {
@@ -125,6 +413,13 @@ impl IndexerConfig {
         ))
     }
 
+    /// rpc_client_url followed by additional_rpc_client_urls
+    pub fn rpc_client_urls(&self) -> Vec<String> {
+        std::iter::once(self.rpc_client_url.clone())
+            .chain(self.additional_rpc_client_urls.iter().cloned())
+            .collect()
+    }
+
     pub fn all_implemented_methods() -> Vec<String> {
         IMPLEMENTED_METHODS.iter().map(|&s| s.to_string()).collect()
     }
@@ -152,20 +447,102 @@ impl Default for IndexerConfig {
             db_host: None,
             db_port: None,
             db_name: None,
+            db_reader_url: None,
+            db_pool_size: None,
+            db_connection_timeout_secs: None,
+            db_statement_timeout_secs: None,
+            allow_db_reader_writes: false,
+            db_query_timeout_ms: 10_000,
+            enable_query_cache: false,
+            slow_query_threshold_ms: None,
+            payload_compression_level: None,
+            object_blob_store_config: None,
+            object_blob_threshold_bytes: 262144,
+            commit_chunk_size: 1000,
+            table_commit_chunk_sizes: vec![],
             rpc_client_url: "http://127.0.0.1:9000".to_string(),
+            additional_rpc_client_urls: vec![],
             client_metric_host: "0.0.0.0".to_string(),
             client_metric_port: 9184,
+            admin_port: None,
+            log_json: false,
             rpc_server_url: "0.0.0.0".to_string(),
             rpc_server_port: 9000,
+            rpc_max_page_size: None,
+            rpc_max_scanned_rows: None,
+            rpc_client_requests_per_second: None,
+            rpc_trusted_proxies: vec![],
+            rpc_require_api_key: false,
+            export_server_port: None,
+            readonly_sql_views: vec![],
+            readonly_sql_max_rows: 1000,
+            readonly_sql_timeout_ms: 5000,
+            readonly_sql_role: "readonly_sql".to_string(),
+            readonly_sql_max_concurrent_queries: 4,
             migrated_methods: vec![],
             reset_db: false,
             fullnode_sync_worker: true,
             rpc_server_worker: true,
             skip_db_commit: false,
+            skip_inconsistent_checkpoints: false,
+            name_service_registry_id: None,
+            halt_on_unparseable_packages: false,
+            source_verification_url: None,
+            fullnode_max_concurrent_requests: 100,
+            checkpoint_download_concurrency: 100,
+            download_queue_memory_budget_mb: 2048,
+            checkpoint_queue_size: None,
+            pipeline_role: "all".to_string(),
+            indexer_shard: None,
+            max_queued_checkpoints: 10_000,
+            leader_election: false,
+            leader_election_interval_secs: 5,
+            module_cache_path: None,
+            checkpoint_archive_config: None,
+            genesis_blob_path: None,
+            index_transactions_json: false,
+            materialized_views: vec![],
+            materialized_view_refresh_interval_secs: 3600,
+            retention: vec![],
+            checkpoint_webhook_urls: vec![],
+            checkpoint_webhook_secret: None,
+            disabled_pipelines: vec![],
+            checkpoint_commit_batch_rows: 5000,
+            checkpoint_commit_max_latency_ms: 200,
+            commit_log_interval_secs: 10,
+            table_size_sample_interval_secs: 300,
+            table_growth_alert_factor: 3.0,
+            reexecution_sample_interval: 0,
+            networks_config: None,
         }
     }
 }
 
+/// A secret of the config, redacted from its `Debug` output so that logging the config
+/// does not leak it.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Secret(String);
+
+impl Secret {
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl FromStr for Secret {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(s.to_string()))
+    }
+}
+
+impl std::fmt::Debug for Secret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Secret(<redacted>)")
+    }
+}
+
 pub struct Indexer;
 
 const DOWNLOAD_QUEUE_SIZE: usize = 1000;
@@ -184,63 +561,278 @@ impl Indexer {
         );
         mysten_metrics::init_metrics(registry);
 
+        // the chain is recorded by the fullnode sync worker, RPC servers only check it
+        let record_chain = !config.rpc_server_worker && config.fullnode_sync_worker;
+        check_chain_identifier(&store, config, record_chain).await?;
         if config.rpc_server_worker {
             info!("Starting indexer with only RPC server");
-            let handle = build_json_rpc_server(registry, store.clone(), config, custom_runtime)
-                .await
-                .expect("Json rpc server should not run into errors upon start.");
-            handle.stopped().await;
+            if store.reader_has_write_privileges().await? {
+                if !config.allow_db_reader_writes {
+                    return Err(IndexerError::InvalidArgumentError(
+                        "Database role of the RPC server has write privileges, use a read-only \
+                        role with --db-reader-url or pass --allow-db-reader-writes"
+                            .to_string(),
+                    ));
+                }
+                warn!("Database role of the RPC server has write privileges");
+            }
+            // fails to start rather than serve any server without the layers
+            let layers = client_layers(&store, config).await?;
+            if let Some(port) = config.export_server_port {
+                let addr = SocketAddr::new(
+                    // unwrap() here is safe b/c the address is a static config.
+                    config.rpc_server_url.as_str().parse().unwrap(),
+                    port,
+                );
+                spawn_monitored_task!(run_export_server(addr, store.clone(), layers.clone()));
+            }
+            let handle =
+                build_json_rpc_server(registry, store.clone(), config, &layers, custom_runtime)
+                    .await
+                    .expect("Json rpc server should not run into errors upon start.");
+            handle.await?;
             Ok(())
         } else if config.fullnode_sync_worker {
             info!("Starting indexer with only fullnode sync");
-            let mut processor_orchestrator = ProcessorOrchestrator::new(store.clone(), registry);
-            spawn_monitored_task!(processor_orchestrator.run_forever());
+            let pipeline_role = config.pipeline_role()?;
+            let leader_election = if config.leader_election {
+                let leader_election = LeaderElection::new(
+                    store.clone(),
+                    config.leader_lock_name()?,
+                    Duration::from_secs(config.leader_election_interval_secs),
+                    metrics.clone(),
+                );
+                leader_election.acquire().await;
+                Some(leader_election)
+            } else {
+                None
+            };
+            // the leader stops once it loses the lock, as a standby may have taken it
+            let leader_lock_lost = async {
+                match &leader_election {
+                    Some(leader_election) => leader_election.wait_until_lost().await,
+                    None => futures::future::pending().await,
+                }
+            };
+            let materialized_views = config
+                .materialized_views
+                .iter()
+                .map(|view| MaterializedView::from_str(view))
+                .collect::<Result<Vec<_>, _>>()?;
+            let retention_policies = parse_retention_policies(&config.retention)?;
+            let pipelines = Pipelines::new(&config.disabled_pipelines)?;
+            store
+                .set_objects_history_enabled(pipelines.is_enabled(Pipeline::ObjectsHistory))
+                .await?;
+            // processors of committed data are left to the committer of a split pipeline
+            if pipeline_role != PipelineRole::Indexer {
+                let mut processor_orchestrator =
+                    ProcessorOrchestrator::new(store.clone(), registry);
+                spawn_monitored_task!(processor_orchestrator.run_forever());
+                if !materialized_views.is_empty() {
+                    let materialized_view_processor = MaterializedViewProcessor::new(
+                        store.clone(),
+                        materialized_views,
+                        Duration::from_secs(config.materialized_view_refresh_interval_secs),
+                        metrics.clone(),
+                    );
+                    spawn_monitored_task!(materialized_view_processor.run());
+                }
+                if !retention_policies.is_empty() {
+                    let pruner = Pruner::new(store.clone(), retention_policies, metrics.clone());
+                    spawn_monitored_task!(pruner.run());
+                }
+                if config.table_size_sample_interval_secs > 0 {
+                    let table_size_processor = TableSizeProcessor::new(
+                        store.clone(),
+                        Duration::from_secs(config.table_size_sample_interval_secs),
+                        config.table_growth_alert_factor,
+                        metrics.clone(),
+                    );
+                    spawn_monitored_task!(table_size_processor.run());
+                }
+                if config.reexecution_sample_interval > 0 {
+                    let reexecution_processor = ReexecutionProcessor::new(
+                        store.clone(),
+                        config.reexecution_sample_interval,
+                        metrics.clone(),
+                    );
+                    spawn_monitored_task!(reexecution_processor.run());
+                }
+            }
 
             // -1 will be returned when checkpoints table is empty.
             let last_seq_from_db = store
                 .get_latest_tx_checkpoint_sequence_number()
                 .await
                 .expect("Failed to get latest tx checkpoint sequence number from DB");
-            let (downloaded_checkpoint_data_sender, downloaded_checkpoint_data_receiver) =
-                mysten_metrics::metered_channel::channel(
+            // freshness is reported from the last committed checkpoint until the next commit
+            if last_seq_from_db >= 0 && pipeline_role != PipelineRole::Indexer {
+                let checkpoint = store
+                    .get_checkpoint(CheckpointId::SequenceNumber(last_seq_from_db as u64))
+                    .await?;
+                metrics
+                    .latest_tx_checkpoint_sequence_number
+                    .set(last_seq_from_db);
+                metrics
+                    .latest_tx_checkpoint_timestamp_ms
+                    .set(checkpoint.timestamp_ms as i64);
+            }
+            if pipeline_role != PipelineRole::Indexer {
+                spawn_monitored_task!(metrics.clone().update_checkpoint_freshness());
+            }
+            if pipeline_role == PipelineRole::Committer {
+                info!("Committing the checkpoints queued by the indexers");
+                let commit_task = start_queue_committer(store, metrics, config, pipelines);
+                return tokio::select! {
+                    result = commit_task => result?,
+                    e = leader_lock_lost => Err(e),
+                };
+            }
+            let (downloaded_checkpoint_data_sender, downloaded_checkpoint_data_stream) =
+                budgeted_channel(
                     DOWNLOAD_QUEUE_SIZE,
+                    config.download_queue_memory_budget_mb * 1024 * 1024,
                     &mysten_metrics::get_metrics()
                         .unwrap()
                         .channels
                         .with_label_values(&["checkpoint_tx_downloading"]),
+                    metrics.buffered_checkpoint_bytes.clone(),
                 );
 
             // experimental rest api route is found at `/rest` on the same interface as the jsonrpc
             // service
-            let rest_api_url = format!("{}/rest", config.rpc_client_url);
-            let fetcher = CheckpointFetcher::new(
-                sui_rest_api::Client::new(&rest_api_url),
-                if last_seq_from_db < 0 {
-                    None
-                } else {
-                    Some(last_seq_from_db as u64)
-                },
+            let rest_clients = config
+                .rpc_client_urls()
+                .into_iter()
+                .map(|url| sui_rest_api::Client::new(format!("{}/rest", url)))
+                .collect();
+            let endpoints = FullNodeEndpoints::new(rest_clients, metrics.clone());
+            // epochs of a split pipeline are left in the queue until committed
+            if pipeline_role == PipelineRole::All {
+                repair_missing_epochs(&store, &endpoints, &metrics).await?;
+            }
+            let downloader = CheckpointDownloader::new(
+                endpoints.clone(),
                 downloaded_checkpoint_data_sender,
+                config.checkpoint_download_concurrency,
+                metrics.clone(),
             );
+            // an empty database bootstrapped from a formal snapshot is indexed from the
+            // checkpoint of the snapshot, which is indexed again to record its epoch change
+            let last_downloaded_checkpoint = if last_seq_from_db >= 0 {
+                Some(last_seq_from_db as u64)
+            } else if let Some(bootstrap) = store.get_snapshot_bootstrap().await? {
+                info!(
+                    "Indexing from checkpoint {} of the snapshot of epoch {}",
+                    bootstrap.checkpoint_sequence_number, bootstrap.epoch
+                );
+                Some(bootstrap.checkpoint_sequence_number as u64 - 1)
+            } else {
+                None
+            };
+            let mut fetcher = CheckpointFetcher::new(downloader, last_downloaded_checkpoint)
+                .with_shard(config.indexer_shard()?);
+            if let Some(genesis_blob_path) = &config.genesis_blob_path {
+                fetcher = fetcher.with_genesis(genesis_checkpoint_data(genesis_blob_path)?);
+            }
             spawn_monitored_task!(fetcher.run());
 
-            let (checkpoint_handler, object_handler) = new_handlers(store, metrics, config);
-
-            crate::framework::runner::run(
-                mysten_metrics::metered_channel::ReceiverStream::new(
-                    downloaded_checkpoint_data_receiver,
-                ),
-                vec![Box::new(checkpoint_handler), Box::new(object_handler)],
-            )
-            .await;
+            let mut handlers: Vec<Box<dyn Handler>> = vec![];
+            if let Some(archive_config) = &config.checkpoint_archive_config {
+                handlers.push(Box::new(CheckpointArchiver::new(
+                    make_object_store(archive_config)?,
+                    metrics.clone(),
+                )));
+            }
+            let (checkpoint_handler, object_handler) = if pipeline_role == PipelineRole::Indexer {
+                new_queueing_handlers(store.clone(), metrics.clone(), config, pipelines)?
+            } else {
+                new_handlers(store.clone(), metrics.clone(), config, pipelines)?
+            };
+            // missing checkpoints are re-indexed and committed in-process, which only a
+            // process of the whole pipeline can do
+            if pipeline_role == PipelineRole::All {
+                let gap_detector =
+                    GapDetector::new(store, endpoints, checkpoint_handler.clone(), metrics);
+                spawn_monitored_task!(gap_detector.run());
+            }
+            handlers.push(Box::new(checkpoint_handler));
+            handlers.push(Box::new(object_handler));
 
-            Ok(())
+            let runner = crate::framework::runner::run(downloaded_checkpoint_data_stream, handlers);
+            // a handler fails with the error of the commit task if that stopped first
+            tokio::select! {
+                result = runner => result.map_err(|e| {
+                    e.downcast::<IndexerError>()
+                        .unwrap_or_else(IndexerError::UncategorizedError)
+                }),
+                e = leader_lock_lost => Err(e),
+            }
         } else {
             Ok(())
         }
     }
 }
 
+/// Checks that the full node, or the genesis blob, is of the chain indexed into the database,
+/// and records the chain of an empty database if `record` is set. Databases are never
+/// indexed from more than one network.
+async fn check_chain_identifier<S: IndexerStore>(
+    store: &S,
+    config: &IndexerConfig,
+    record: bool,
+) -> Result<(), IndexerError> {
+    let recorded = store.get_chain_identifier().await?;
+    if recorded.is_none() && !record {
+        return Ok(());
+    }
+    let (source, digest) = match &config.genesis_blob_path {
+        Some(path) => (
+            format!("genesis blob {path:?}"),
+            *genesis_checkpoint_data(path)?.checkpoint_summary.digest(),
+        ),
+        None => {
+            let endpoints = get_fullnode_endpoints(config, store.indexer_metrics())?;
+            let request = |client: HttpClient| async move {
+                client.get_checkpoint(CheckpointId::SequenceNumber(0)).await
+            };
+            let genesis_checkpoint = endpoints
+                .call(request, is_transport_error)
+                .await
+                .map_err(|e| {
+                    IndexerError::FullNodeReadingError(format!(
+                        "Failed reading genesis checkpoint from the full nodes with error {e:?}, pass --genesis-blob-path for full nodes without it"
+                    ))
+                })?;
+            ("the full nodes".to_string(), genesis_checkpoint.digest)
+        }
+    };
+    let digest = digest.base58_encode();
+    match recorded {
+        Some(recorded) if recorded.checkpoint_digest != digest => {
+            Err(IndexerError::InvalidArgumentError(format!(
+                "Database is indexed from the chain with genesis checkpoint {}, but {} is of \
+                the chain with genesis checkpoint {}, refusing to index another network into \
+                the database",
+                recorded.checkpoint_digest, source, digest
+            )))
+        }
+        Some(_) => Ok(()),
+        None => {
+            info!(
+                "Recording the chain with genesis checkpoint {} of {}",
+                digest, source
+            );
+            store
+                .persist_chain_identifier(ChainIdentifier {
+                    checkpoint_digest: digest,
+                })
+                .await
+        }
+    }
+}
+
 // TODO(gegaowp): this is only used in validation now, will remove in a separate PR
 // together with the validation codes.
 pub async fn new_rpc_client(http_url: &str) -> Result<SuiClient, IndexerError> {
@@ -257,6 +849,19 @@ pub async fn new_rpc_client(http_url: &str) -> Result<SuiClient, IndexerError> {
         })
 }
 
+// Clients of rpc_client_url and additional_rpc_client_urls, failing over to each other
+fn get_fullnode_endpoints(
+    config: &IndexerConfig,
+    metrics: &IndexerMetrics,
+) -> Result<FullNodeEndpoints<HttpClient>, IndexerError> {
+    let clients = config
+        .rpc_client_urls()
+        .iter()
+        .map(|url| get_http_client(url))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(FullNodeEndpoints::new(clients, metrics.clone()))
+}
+
 fn get_http_client(rpc_client_url: &str) -> Result<HttpClient, IndexerError> {
     let mut headers = HeaderMap::new();
     headers.insert(CLIENT_SDK_TYPE_HEADER, HeaderValue::from_static("indexer"));
@@ -276,13 +881,22 @@ fn get_http_client(rpc_client_url: &str) -> Result<HttpClient, IndexerError> {
 }
 
 pub fn new_pg_connection_pool(db_url: &str) -> Result<PgConnectionPool, IndexerError> {
-    let pool_config = PgConectionPoolConfig::default();
+    new_pg_connection_pool_in_schema(db_url, None, &PgConnectionPoolConfig::default())
+}
+
+/// Pool of connections whose search path is `schema`, e.g. the schema of a network of a
+/// process indexing several networks, the default search path of the role if not set.
+pub fn new_pg_connection_pool_in_schema(
+    db_url: &str,
+    schema: Option<&str>,
+    pool_config: &PgConnectionPoolConfig,
+) -> Result<PgConnectionPool, IndexerError> {
     let manager = ConnectionManager::<PgConnection>::new(db_url);
 
     diesel::r2d2::Pool::builder()
         .max_size(pool_config.pool_size)
         .connection_timeout(pool_config.connection_timeout)
-        .connection_customizer(Box::new(pool_config.connection_config()))
+        .connection_customizer(Box::new(pool_config.connection_config(schema)))
         .build(manager)
         .map_err(|e| {
             IndexerError::PgConnectionPoolInitError(format!(
@@ -292,51 +906,69 @@ pub fn new_pg_connection_pool(db_url: &str) -> Result<PgConnectionPool, IndexerE
         })
 }
 
-#[derive(Debug, Clone, Copy)]
-struct PgConectionPoolConfig {
-    pool_size: u32,
-    connection_timeout: Duration,
-    statement_timeout: Duration,
+/// A connection outside of any pool, configured like the connections of the pools of
+/// `new_pg_connection_pool_in_schema`, e.g. to hold a session lock that must be released by
+/// closing the connection rather than returning it to a pool.
+pub fn new_pg_connection_in_schema(
+    db_url: &str,
+    schema: Option<&str>,
+    pool_config: &PgConnectionPoolConfig,
+) -> Result<PgConnection, IndexerError> {
+    use diesel::r2d2::CustomizeConnection;
+    use diesel::Connection;
+
+    let mut conn = PgConnection::establish(db_url).map_err(|e| {
+        IndexerError::PgPoolConnectionError(format!(
+            "Failed to connect to PostgresDB with error: {:?}",
+            e
+        ))
+    })?;
+    pool_config
+        .connection_config(schema)
+        .on_acquire(&mut conn)
+        .map_err(|e| {
+            IndexerError::PgPoolConnectionError(format!(
+                "Failed to configure PostgresDB connection with error: {:?}",
+                e
+            ))
+        })?;
+    Ok(conn)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PgConnectionPoolConfig {
+    pub pool_size: u32,
+    pub connection_timeout: Duration,
+    pub statement_timeout: Duration,
 }
 
-impl PgConectionPoolConfig {
-    const DEFAULT_POOL_SIZE: u32 = 100;
-    const DEFAULT_CONNECTION_TIMEOUT: u64 = 30;
-    const DEFAULT_STATEMENT_TIMEOUT: u64 = 30;
+impl PgConnectionPoolConfig {
+    pub const DEFAULT_POOL_SIZE: u32 = 100;
+    pub const DEFAULT_CONNECTION_TIMEOUT_SECS: u64 = 30;
+    pub const DEFAULT_STATEMENT_TIMEOUT_SECS: u64 = 30;
 
-    fn connection_config(&self) -> PgConnectionConfig {
+    fn connection_config(&self, schema: Option<&str>) -> PgConnectionConfig {
         PgConnectionConfig {
             statement_timeout: self.statement_timeout,
+            schema: schema.map(str::to_string),
         }
     }
 }
 
-impl Default for PgConectionPoolConfig {
+impl Default for PgConnectionPoolConfig {
     fn default() -> Self {
-        let db_pool_size = std::env::var("DB_POOL_SIZE")
-            .ok()
-            .and_then(|s| s.parse::<u32>().ok())
-            .unwrap_or(Self::DEFAULT_POOL_SIZE);
-        let conn_timeout_secs = std::env::var("DB_CONNECTION_TIMEOUT")
-            .ok()
-            .and_then(|s| s.parse::<u64>().ok())
-            .unwrap_or(Self::DEFAULT_CONNECTION_TIMEOUT);
-        let statement_timeout_secs = std::env::var("DB_STATEMENT_TIMEOUT")
-            .ok()
-            .and_then(|s| s.parse::<u64>().ok())
-            .unwrap_or(Self::DEFAULT_STATEMENT_TIMEOUT);
-
         Self {
-            pool_size: db_pool_size,
-            connection_timeout: Duration::from_secs(conn_timeout_secs),
-            statement_timeout: Duration::from_secs(statement_timeout_secs),
+            pool_size: Self::DEFAULT_POOL_SIZE,
+            connection_timeout: Duration::from_secs(Self::DEFAULT_CONNECTION_TIMEOUT_SECS),
+            statement_timeout: Duration::from_secs(Self::DEFAULT_STATEMENT_TIMEOUT_SECS),
         }
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 struct PgConnectionConfig {
     statement_timeout: Duration,
+    schema: Option<String>,
 }
 
 impl diesel::r2d2::CustomizeConnection<PgConnection, diesel::r2d2::Error> for PgConnectionConfig {
@@ -349,6 +981,11 @@ impl diesel::r2d2::CustomizeConnection<PgConnection, diesel::r2d2::Error> for Pg
         ))
         .execute(conn)
         .map_err(diesel::r2d2::Error::QueryError)?;
+        if let Some(schema) = &self.schema {
+            sql_query(format!("SET search_path TO \"{schema}\""))
+                .execute(conn)
+                .map_err(diesel::r2d2::Error::QueryError)?;
+        }
         Ok(())
     }
 }
@@ -362,39 +999,91 @@ pub fn get_pg_pool_connection(pool: &PgConnectionPool) -> Result<PgPoolConnectio
     })
 }
 
+// API key authentication and rate limits of the servers of the RPC server worker
+async fn client_layers<S: IndexerStore + Sync + Send + 'static + Clone>(
+    state: &S,
+    config: &IndexerConfig,
+) -> Result<ClientLayers, IndexerError> {
+    let mut layers = ClientLayers::default();
+    if config.rpc_require_api_key {
+        let auth = Arc::new(ApiKeyAuth::new(
+            state.get_api_keys().await?,
+            state.indexer_metrics().clone(),
+        ));
+        spawn_monitored_task!(auth.clone().refresh_forever(state.clone()));
+        layers.auth = Some(auth);
+    }
+    if let Some(requests_per_second) = config.rpc_client_requests_per_second {
+        layers.rate_limiter = Some(Arc::new(ClientRateLimiter::new(
+            requests_per_second,
+            config.rpc_trusted_proxies.clone(),
+        )));
+    }
+    Ok(layers)
+}
+
 pub async fn build_json_rpc_server<S: IndexerStore + Sync + Send + 'static + Clone>(
     prometheus_registry: &Registry,
     state: S,
     config: &IndexerConfig,
-    custom_runtime: Option<Handle>,
-) -> Result<ServerHandle, IndexerError> {
+    layers: &ClientLayers,
+    _custom_runtime: Option<Handle>,
+) -> Result<JoinHandle<()>, IndexerError> {
+    let defaults = QueryLimits::default();
+    let limits = QueryLimits {
+        max_page_size: config.rpc_max_page_size.unwrap_or(defaults.max_page_size),
+        max_scanned_rows: config
+            .rpc_max_scanned_rows
+            .unwrap_or(defaults.max_scanned_rows),
+    };
     let mut builder = JsonRpcServerBuilder::new(env!("CARGO_PKG_VERSION"), prometheus_registry);
     let http_client = get_http_client(config.rpc_client_url.as_str())?;
 
+    let fullnode_endpoints = get_fullnode_endpoints(config, state.indexer_metrics())?;
+    let fullnode_fallback = FullNodeFallback::new(
+        fullnode_endpoints.clone(),
+        config.fullnode_max_concurrent_requests,
+        state.indexer_metrics().clone(),
+    );
+
     builder.register_module(ReadApi::new(
         state.clone(),
         http_client.clone(),
+        fullnode_fallback,
         config.migrated_methods.clone(),
+        limits,
     ))?;
-    builder.register_module(CoinReadApi::new(http_client.clone()))?;
+    builder.register_module(CoinReadApi::new(fullnode_endpoints.clone()))?;
     builder.register_module(TransactionBuilderApi::new(http_client.clone()))?;
-    builder.register_module(GovernanceReadApi::new(http_client.clone()))?;
+    builder.register_module(GovernanceReadApi::new(
+        state.clone(),
+        fullnode_endpoints,
+        config.migrated_methods.clone(),
+    ))?;
     builder.register_module(IndexerApi::new(
         state.clone(),
         http_client.clone(),
         config.migrated_methods.clone(),
+        limits,
     ))?;
     builder.register_module(WriteApi::new(state.clone(), http_client.clone()))?;
-    builder.register_module(ExtendedApi::new(state.clone()))?;
+    builder.register_module(ExtendedApi::new(state.clone(), limits))?;
     builder.register_module(MoveUtilsApi::new(http_client))?;
     let default_socket_addr = SocketAddr::new(
         // unwrap() here is safe b/c the address is a static config.
         config.rpc_server_url.as_str().parse().unwrap(),
         config.rpc_server_port,
     );
-    Ok(builder
-        .start(default_socket_addr, custom_runtime, Some(ServerType::Http))
-        .await?)
+    // served here rather than by the builder, to rate limit clients by their address
+    let router = layers.apply(builder.to_router(Some(ServerType::Http))?);
+    let server = axum::Server::bind(&default_socket_addr)
+        .serve(router.into_make_service_with_connect_info::<SocketAddr>());
+    info!("Sui JSON-RPC server listening on {}", server.local_addr());
+    Ok(tokio::spawn(async move {
+        if let Err(e) = server.await {
+            warn!("Indexer JSON-RPC server stopped with error: {:?}", e);
+        }
+    }))
 }
 
 fn convert_url(url_str: &str) -> Option<String> {
@@ -409,22 +1098,14 @@ pub fn start_prometheus_server(
     addr: SocketAddr,
     fn_url: &str,
 ) -> Result<(RegistryService, Registry), anyhow::Error> {
-    let converted_fn_url = convert_url(fn_url);
-    if converted_fn_url.is_none() {
-        warn!(
-            "Failed to convert full node url {} to a shorter version",
-            fn_url
-        );
-    }
-    let fn_url_str = converted_fn_url.unwrap_or_else(|| "unknown_url".to_string());
-
-    let labels = HashMap::from([("indexer_fullnode".to_string(), fn_url_str)]);
+    let labels = metric_labels(fn_url);
     info!("Starting prometheus server with labels: {:?}", labels);
-    let registry = Registry::new_custom(Some("indexer".to_string()), Some(labels))?;
+    let registry = Registry::new_custom(Some(METRICS_PREFIX.to_string()), Some(labels))?;
     let registry_service = RegistryService::new(registry.clone());
 
     let app = Router::new()
         .route(METRICS_ROUTE, get(metrics))
+        .route(HEALTH_ROUTE, get(health))
         .layer(Extension(registry_service.clone()));
 
     tokio::spawn(async move {
@@ -436,6 +1117,29 @@ pub fn start_prometheus_server(
     Ok((registry_service, registry))
 }
 
+/// Registry of the metrics of a network of a process indexing several networks, labelled
+/// with the network, to be added to the registry service of the prometheus server.
+pub fn new_network_registry(network: &str, fn_url: &str) -> Result<Registry, anyhow::Error> {
+    let mut labels = metric_labels(fn_url);
+    labels.insert("network".to_string(), network.to_string());
+    Ok(Registry::new_custom(
+        Some(METRICS_PREFIX.to_string()),
+        Some(labels),
+    )?)
+}
+
+fn metric_labels(fn_url: &str) -> HashMap<String, String> {
+    let converted_fn_url = convert_url(fn_url);
+    if converted_fn_url.is_none() {
+        warn!(
+            "Failed to convert full node url {} to a shorter version",
+            fn_url
+        );
+    }
+    let fn_url_str = converted_fn_url.unwrap_or_else(|| "unknown_url".to_string());
+    HashMap::from([("indexer_fullnode".to_string(), fn_url_str)])
+}
+
 async fn metrics(Extension(registry_service): Extension<RegistryService>) -> (StatusCode, String) {
     let metrics_families = registry_service.gather_all();
     match TextEncoder.encode_to_string(&metrics_families) {
@@ -446,3 +1150,64 @@ async fn metrics(Extension(registry_service): Extension<RegistryService>) -> (St
         ),
     }
 }
+
+#[derive(Debug, Deserialize)]
+struct HealthQuery {
+    // respond with 503 when the latest committed checkpoint is older than this
+    max_freshness_ms: Option<i64>,
+    // network of a process indexing several networks, the first network if not set
+    network: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct IndexerHealth {
+    latest_checkpoint: Option<i64>,
+    latest_checkpoint_timestamp_ms: Option<i64>,
+    // wall clock minus the timestamp of the latest committed checkpoint
+    freshness_ms: Option<i64>,
+}
+
+/// Freshness of the indexer, e.g. for load balancers with `?max_freshness_ms=`, computed
+/// when requested from the gauges of the latest committed checkpoint.
+async fn health(
+    Extension(registry_service): Extension<RegistryService>,
+    Query(query): Query<HealthQuery>,
+) -> (StatusCode, Json<IndexerHealth>) {
+    let metric_families = registry_service.gather_all();
+    let gauge = |name: &str| {
+        let name = format!("{METRICS_PREFIX}_{name}");
+        metric_families
+            .iter()
+            .filter(|family| family.get_name() == name)
+            .flat_map(|family| family.get_metric())
+            .find(|metric| {
+                query.network.as_ref().map_or(true, |network| {
+                    metric
+                        .get_label()
+                        .iter()
+                        .any(|label| label.get_name() == "network" && label.get_value() == network)
+                })
+            })
+            .map(|metric| metric.get_gauge().get_value() as i64)
+            .filter(|value| *value > 0)
+    };
+    let latest_checkpoint_timestamp_ms = gauge(metrics::LATEST_CHECKPOINT_TIMESTAMP_MS);
+    let health = IndexerHealth {
+        latest_checkpoint: gauge(metrics::LATEST_CHECKPOINT_SEQUENCE_NUMBER),
+        latest_checkpoint_timestamp_ms,
+        freshness_ms: latest_checkpoint_timestamp_ms.map(metrics::checkpoint_freshness_ms),
+    };
+    let healthy = match query.max_freshness_ms {
+        Some(max_freshness_ms) => health
+            .freshness_ms
+            .map_or(false, |freshness_ms| freshness_ms <= max_freshness_ms),
+        None => true,
+    };
+    let status = if healthy {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(health))
+}
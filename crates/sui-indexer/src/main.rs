@@ -1,26 +1,48 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use clap::Parser;
+use std::time::Duration;
+
+use futures::future::select_all;
+use prometheus::Registry;
 use tracing::{error, info};
 
+use sui_indexer::admin::{run_admin_server, AdminStores};
 use sui_indexer::errors::IndexerError;
+use sui_indexer::handlers::checkpoint_archiver::make_object_store;
 use sui_indexer::metrics::IndexerMetrics;
-use sui_indexer::start_prometheus_server;
-use sui_indexer::store::PgIndexerStore;
-use sui_indexer::utils::reset_database;
-use sui_indexer::{get_pg_pool_connection, new_pg_connection_pool, Indexer, IndexerConfig};
+use sui_indexer::networks::load_network_configs;
+use sui_indexer::store::{BlobStore, CommitChunkSizes, PgIndexerStore, ReadonlySql};
+use sui_indexer::utils::{create_schema_if_not_exists, reset_database};
+use sui_indexer::{
+    get_pg_pool_connection, new_pg_connection_in_schema, new_pg_connection_pool_in_schema, Indexer,
+    IndexerConfig,
+};
+use sui_indexer::{new_network_registry, start_prometheus_server};
 
 #[tokio::main]
 async fn main() -> Result<(), IndexerError> {
+    let indexer_config = IndexerConfig::from_env()?;
     // NOTE: this is to print out tracing like info, warn & error.
-    let _guard = telemetry_subscribers::TelemetryConfig::new()
-        .with_env()
-        .init();
-
-    let indexer_config = IndexerConfig::parse();
+    let mut telemetry_config = telemetry_subscribers::TelemetryConfig::new().with_env();
+    if indexer_config.log_json {
+        telemetry_config = telemetry_config.with_json();
+    }
+    let (_guard, filter_handle) = telemetry_config.init();
     info!("Parsed indexer config: {:#?}", indexer_config);
-    let (_registry_service, registry) = start_prometheus_server(
+    let admin_stores = AdminStores::default();
+    if let Some(admin_port) = indexer_config.admin_port {
+        tokio::spawn(run_admin_server(
+            admin_port,
+            filter_handle,
+            admin_stores.clone(),
+        ));
+    }
+    let networks = match &indexer_config.networks_config {
+        Some(path) => load_network_configs(path)?,
+        None => vec![],
+    };
+    let (registry_service, registry) = start_prometheus_server(
         // NOTE: this parses the input host addr and port number for socket addr,
         // so unwrap() is safe here.
         format!(
@@ -31,6 +53,50 @@ async fn main() -> Result<(), IndexerError> {
         .unwrap(),
         indexer_config.rpc_client_url.as_str(),
     )?;
+    if networks.is_empty() {
+        return start_indexer(indexer_config, registry, None, admin_stores).await;
+    }
+
+    // every network is indexed into its own schema, with its metrics labelled with the
+    // network, the process stops with the first network that stops
+    let mut handles = vec![];
+    for network in networks {
+        let config = network.indexer_config(&indexer_config);
+        let registry = new_network_registry(&network.name, &config.rpc_client_url)?;
+        registry_service.add(registry.clone());
+        info!(
+            "Starting indexer of network {} with full node {}",
+            network.name, config.rpc_client_url
+        );
+        let name = network.name.clone();
+        let admin_stores = admin_stores.clone();
+        let handle = tokio::spawn(async move {
+            start_indexer(
+                config,
+                registry,
+                Some(network.schema().to_string()),
+                admin_stores,
+            )
+            .await
+            .map_err(|e| {
+                error!("Indexer of network {} stopped with error {:?}", name, e);
+                e
+            })
+        });
+        handles.push(handle);
+    }
+    let (result, _, _) = select_all(handles).await;
+    result.map_err(|e| IndexerError::UncategorizedError(e.into()))?
+}
+
+// Starts the indexer of `config`, with its tables in `schema` if set, the schema being named
+// after the network. Writers are registered with the admin server by that name.
+async fn start_indexer(
+    indexer_config: IndexerConfig,
+    registry: Registry,
+    schema: Option<String>,
+    admin_stores: AdminStores,
+) -> Result<(), IndexerError> {
     let indexer_metrics = IndexerMetrics::new(&registry);
     let db_url = indexer_config.get_db_url().map_err(|e| {
         IndexerError::PgPoolConnectionError(format!(
@@ -38,7 +104,16 @@ async fn main() -> Result<(), IndexerError> {
             e
         ))
     })?;
-    let blocking_cp = new_pg_connection_pool(&db_url).map_err(|e| {
+    let pool_config = indexer_config.pg_pool_config();
+    // RPC servers only read, through a single pool of the reader role if set, and leave
+    // creating and resetting the schema to the writer
+    let rpc_only = indexer_config.rpc_server_worker;
+    let pool_url = match (&indexer_config.db_reader_url, rpc_only) {
+        (Some(db_reader_url), true) => db_reader_url,
+        _ => &db_url,
+    };
+    let blocking_cp = new_pg_connection_pool_in_schema(pool_url, schema.as_deref(), &pool_config)
+        .map_err(|e| {
         error!(
             "Failed creating Postgres connection pool with error {:?}",
             e
@@ -65,7 +140,7 @@ async fn main() -> Result<(), IndexerError> {
         }
     });
 
-    if indexer_config.reset_db {
+    if !rpc_only {
         let mut conn = get_pg_pool_connection(&blocking_cp).map_err(|e| {
             error!(
                 "Failed getting Postgres connection from connection pool with error {:?}",
@@ -73,16 +148,79 @@ async fn main() -> Result<(), IndexerError> {
             );
             e
         })?;
-        reset_database(&mut conn, /* drop_all */ true).map_err(|e| {
-            let db_err_msg = format!(
-                "Failed resetting database with url: {:?} and error: {:?}",
-                db_url, e
-            );
-            error!("{}", db_err_msg);
-            IndexerError::PostgresResetError(db_err_msg)
-        })?;
+        // the tables of a network are created along with its schema
+        let schema_created = match &schema {
+            Some(schema) => create_schema_if_not_exists(&mut conn, schema)?,
+            None => false,
+        };
+        if indexer_config.reset_db || schema_created {
+            reset_database(&mut conn, /* drop_all */ true).map_err(|e| {
+                let db_err_msg = format!(
+                    "Failed resetting database with url: {:?} and error: {:?}",
+                    db_url, e
+                );
+                error!("{}", db_err_msg);
+                IndexerError::PostgresResetError(db_err_msg)
+            })?;
+        }
+    }
+    let mut store = PgIndexerStore::new(blocking_cp, indexer_metrics.clone())
+        .with_query_timeout(Duration::from_millis(indexer_config.db_query_timeout_ms));
+    if indexer_config.leader_election {
+        let (db_url, schema) = (db_url.clone(), schema.clone());
+        store = store.with_leader_lock_connector(move || {
+            new_pg_connection_in_schema(&db_url, schema.as_deref(), &pool_config)
+        });
+    }
+    // checked by the RPC server to be the case only of a role allowed to write
+    if rpc_only && indexer_config.allow_db_reader_writes {
+        store = store.with_read_path_writes();
+    }
+    if let (Some(db_reader_url), false) = (&indexer_config.db_reader_url, rpc_only) {
+        let read_cp =
+            new_pg_connection_pool_in_schema(db_reader_url, schema.as_deref(), &pool_config)
+                .map_err(|e| {
+                    error!("Failed creating reader connection pool with error {:?}", e);
+                    e
+                })?;
+        store = store.with_reader_pool(read_cp);
+    }
+    if indexer_config.enable_query_cache {
+        store = store.with_query_cache();
+    }
+    if let Some(threshold_ms) = indexer_config.slow_query_threshold_ms {
+        store = store.with_slow_query_log(Duration::from_millis(threshold_ms));
+    }
+    store = store.with_commit_chunk_sizes(CommitChunkSizes::new(
+        indexer_config.commit_chunk_size,
+        &indexer_config.table_commit_chunk_sizes,
+    )?)?;
+    if let Some(level) = indexer_config.payload_compression_level {
+        store = store.with_payload_compression(level);
+    }
+    if let Some(blob_store_config) = &indexer_config.object_blob_store_config {
+        store = store.with_blob_store(BlobStore::new(
+            make_object_store(blob_store_config)?,
+            indexer_config.object_blob_threshold_bytes,
+        ));
+    }
+    if !indexer_config.readonly_sql_views.is_empty() {
+        store = store.with_readonly_sql(ReadonlySql::new(
+            indexer_config.readonly_sql_views.clone(),
+            indexer_config.readonly_sql_role.clone(),
+            indexer_config.readonly_sql_max_rows,
+            Duration::from_millis(indexer_config.readonly_sql_timeout_ms),
+            indexer_config.readonly_sql_max_concurrent_queries,
+        ))?;
+    }
+    if let Some(module_cache_path) = &indexer_config.module_cache_path {
+        store = store.with_persistent_module_cache(module_cache_path)?;
+    }
+
+    // RPC servers hold a reader role, the watchlist is managed through the writer only
+    if !rpc_only {
+        admin_stores.register(schema.as_deref(), store.clone());
     }
-    let store = PgIndexerStore::new(blocking_cp, indexer_metrics.clone());
 
     Indexer::start(&indexer_config, &registry, store, indexer_metrics, None).await
 }
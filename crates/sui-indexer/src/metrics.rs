@@ -1,9 +1,13 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
 use prometheus::{
-    register_histogram_with_registry, register_int_counter_with_registry,
-    register_int_gauge_with_registry, Histogram, IntCounter, IntGauge, Registry,
+    register_histogram_vec_with_registry, register_histogram_with_registry,
+    register_int_counter_vec_with_registry, register_int_counter_with_registry,
+    register_int_gauge_vec_with_registry, register_int_gauge_with_registry, Histogram,
+    HistogramVec, IntCounter, IntCounterVec, IntGauge, IntGaugeVec, Registry,
 };
 
 /// Prometheus metrics for sui-indexer.
@@ -18,6 +22,10 @@ const DB_COMMIT_LATENCY_SEC_BUCKETS: &[f64] = &[
     5.0, 10.0, 20.0, 40.0, 60.0, 80.0, 100.0, 200.0,
 ];
 
+pub const LATEST_CHECKPOINT_SEQUENCE_NUMBER: &str = "latest_indexer_checkpoint_sequence_number";
+pub const LATEST_CHECKPOINT_TIMESTAMP_MS: &str = "latest_indexer_checkpoint_timestamp_ms";
+const CHECKPOINT_FRESHNESS_UPDATE_INTERVAL: Duration = Duration::from_secs(1);
+
 #[derive(Clone)]
 pub struct IndexerMetrics {
     pub total_checkpoint_received: IntCounter,
@@ -28,15 +36,30 @@ pub struct IndexerMetrics {
     pub total_transaction_chunk_committed: IntCounter,
     pub total_object_change_chunk_committed: IntCounter,
     pub total_epoch_committed: IntCounter,
+    pub total_safe_mode_epoch: IntCounter,
+    pub total_epochs_repaired: IntCounter,
+    pub total_inconsistent_checkpoint: IntCounter,
+    pub total_unparseable_module: IntCounter,
+    pub total_skipped_name_records: IntCounter,
+    pub total_unresolved_call_arguments: IntCounter,
+    pub total_checkpoint_archived: IntCounter,
+    pub total_checkpoint_archive_failure: IntCounter,
+    pub total_blob_upload_failure: IntCounter,
+    pub gaps_detected: IntCounter,
     pub latest_fullnode_checkpoint_sequence_number: IntGauge,
     pub latest_tx_checkpoint_sequence_number: IntGauge,
     pub latest_indexer_object_checkpoint_sequence_number: IntGauge,
+    pub latest_tx_checkpoint_timestamp_ms: IntGauge,
+    // wall clock of the indexer minus the timestamp of the latest committed checkpoint
+    pub checkpoint_freshness_ms: IntGauge,
     // checkpoint E2E latency is:
     // fullnode_download_latency + checkpoint_index_latency + db_commit_latency
     pub fullnode_checkpoint_data_download_latency: Histogram,
     pub fullnode_checkpoint_wait_and_download_latency: Histogram,
     pub fullnode_transaction_download_latency: Histogram,
     pub fullnode_object_download_latency: Histogram,
+    pub checkpoint_download_concurrency: IntGauge,
+    pub buffered_checkpoint_bytes: IntGauge,
     pub checkpoint_index_latency: Histogram,
     pub checkpoint_objects_index_latency: Histogram,
     pub checkpoint_db_commit_latency: Histogram,
@@ -75,6 +98,47 @@ pub struct IndexerMetrics {
     // indexer state metrics
     pub db_conn_pool_size: IntGauge,
     pub idle_db_conn: IntGauge,
+    // full node fallback metrics
+    pub total_fullnode_fallback_request: IntCounter,
+    pub total_fullnode_fallback_deduplicated_request: IntCounter,
+    pub total_fullnode_fallback_retry: IntCounter,
+    pub inflight_fullnode_fallback_request: IntGauge,
+    // per full node endpoint health, labeled by the index of the endpoint, 0 for
+    // rpc_client_url and 1 onwards for additional_rpc_client_urls
+    pub fullnode_endpoint_request: IntCounterVec,
+    pub fullnode_endpoint_error: IntCounterVec,
+    pub fullnode_endpoint_healthy: IntGaugeVec,
+    // materialized view refreshes, labeled by view
+    pub materialized_view_refresh_latency: HistogramVec,
+    pub total_materialized_view_refresh_failure: IntCounterVec,
+    // checkpoint webhooks
+    pub total_checkpoint_notification_failure: IntCounter,
+    pub total_checkpoint_notification_dropped: IntCounter,
+    pub total_watchlist_matches: IntCounter,
+    // reads of the RPC APIs over the slow query threshold, labeled by store method
+    pub total_slow_store_reads: IntCounterVec,
+    // rows deleted by the pruner, labeled by table family
+    pub total_pruned_rows: IntCounterVec,
+    // requests of the RPC server authenticated by API key, labeled by key name
+    pub total_api_key_requests: IntCounterVec,
+    // labeled by key name, empty for unknown keys, and reason
+    pub total_api_key_rejected_requests: IntCounterVec,
+    // sampled table sizes, labeled by table
+    pub table_size_bytes: IntGaugeVec,
+    pub table_estimated_rows: IntGaugeVec,
+    pub table_growth_bytes_per_sec: IntGaugeVec,
+    pub total_table_growth_anomalies: IntCounterVec,
+    // sampled transactions re-executed against the object history
+    pub total_transactions_reexecuted: IntCounter,
+    pub total_reexecution_mismatches: IntCounter,
+    pub total_reexecutions_skipped: IntCounter,
+    pub total_unchanged_object_mutations: IntCounter,
+    // checkpoints passed through the queue of a split pipeline, labeled by queue kind
+    pub total_checkpoints_enqueued: IntCounterVec,
+    pub total_checkpoints_dequeued: IntCounterVec,
+    pub checkpoint_queue_next_sequence_number: IntGaugeVec,
+    // 1 while this replica holds the leader lock, with --leader-election
+    pub is_leader: IntGauge,
 }
 
 impl IndexerMetrics {
@@ -128,6 +192,66 @@ impl IndexerMetrics {
                 registry,
             )
             .unwrap(),
+            total_safe_mode_epoch: register_int_counter_with_registry!(
+                "total_safe_mode_epoch",
+                "Total number of epochs indexed that ended in safe mode",
+                registry,
+            )
+            .unwrap(),
+            total_epochs_repaired: register_int_counter_with_registry!(
+                "total_epochs_repaired",
+                "Total number of epochs of committed checkpoints found missing on startup and committed",
+                registry,
+            )
+            .unwrap(),
+            total_inconsistent_checkpoint: register_int_counter_with_registry!(
+                "total_inconsistent_checkpoint",
+                "Total number of checkpoints whose data was found inconsistent during indexing",
+                registry,
+            )
+            .unwrap(),
+            total_unparseable_module: register_int_counter_with_registry!(
+                "total_unparseable_module",
+                "Total number of module resolutions failing to deserialize the module",
+                registry,
+            )
+            .unwrap(),
+            total_skipped_name_records: register_int_counter_with_registry!(
+                "total_skipped_name_records",
+                "Total number of objects of the name service registry not indexed as they failed to deserialize as name records",
+                registry,
+            )
+            .unwrap(),
+            total_unresolved_call_arguments: register_int_counter_with_registry!(
+                "total_unresolved_call_arguments",
+                "Total number of pure arguments of indexed Move calls not decoded, as the called module was not found",
+                registry,
+            )
+            .unwrap(),
+            total_checkpoint_archived: register_int_counter_with_registry!(
+                "total_checkpoint_archived",
+                "Total number of checkpoints written to the checkpoint archive",
+                registry,
+            )
+            .unwrap(),
+            total_checkpoint_archive_failure: register_int_counter_with_registry!(
+                "total_checkpoint_archive_failure",
+                "Total number of checkpoints that failed to be written to the checkpoint archive",
+                registry,
+            )
+            .unwrap(),
+            total_blob_upload_failure: register_int_counter_with_registry!(
+                "total_blob_upload_failure",
+                "Total number of object payloads kept in Postgres as they failed to be uploaded to the blob store",
+                registry,
+            )
+            .unwrap(),
+            gaps_detected: register_int_counter_with_registry!(
+                "gaps_detected",
+                "Total number of missing checkpoint ranges and transaction count mismatches found in committed checkpoints",
+                registry,
+            )
+            .unwrap(),
             latest_fullnode_checkpoint_sequence_number: register_int_gauge_with_registry!(
                 "latest_fullnode_checkpoint_sequence_number",
                 "Latest checkpoint sequence number from the Full Node",
@@ -135,7 +259,7 @@ impl IndexerMetrics {
             )
             .unwrap(),
             latest_tx_checkpoint_sequence_number: register_int_gauge_with_registry!(
-                "latest_indexer_checkpoint_sequence_number",
+                LATEST_CHECKPOINT_SEQUENCE_NUMBER,
                 "Latest checkpoint sequence number from the Indexer",
                 registry,
             )
@@ -146,6 +270,18 @@ impl IndexerMetrics {
                 registry,
             )
             .unwrap(),
+            latest_tx_checkpoint_timestamp_ms: register_int_gauge_with_registry!(
+                LATEST_CHECKPOINT_TIMESTAMP_MS,
+                "Timestamp in milliseconds of the latest checkpoint committed by the Indexer",
+                registry,
+            )
+            .unwrap(),
+            checkpoint_freshness_ms: register_int_gauge_with_registry!(
+                "checkpoint_freshness_ms",
+                "Milliseconds between the wall clock and the timestamp of the latest committed checkpoint",
+                registry,
+            )
+            .unwrap(),
             fullnode_checkpoint_data_download_latency: register_histogram_with_registry!(
                 "fullnode_checkpoint_data_download_latency",
                 "Time spent in downloading checkpoint and transation for a new checkpoint from the Full Node",
@@ -175,6 +311,18 @@ impl IndexerMetrics {
                 registry,
             )
             .unwrap(),
+            checkpoint_download_concurrency: register_int_gauge_with_registry!(
+                "checkpoint_download_concurrency",
+                "Number of checkpoints allowed to be downloaded concurrently from the Full Node",
+                registry,
+            )
+            .unwrap(),
+            buffered_checkpoint_bytes: register_int_gauge_with_registry!(
+                "buffered_checkpoint_bytes",
+                "Estimated size in bytes of downloaded checkpoints waiting to be indexed",
+                registry,
+            )
+            .unwrap(),
             checkpoint_index_latency: register_histogram_with_registry!(
                 "checkpoint_index_latency",
                 "Time spent in indexing a checkpoint",
@@ -381,6 +529,191 @@ impl IndexerMetrics {
                 "Number of idle database connections",
                 registry
             ).unwrap(),
+            total_fullnode_fallback_request: register_int_counter_with_registry!(
+                "total_fullnode_fallback_request",
+                "Total number of requests sent to the full node as fallback",
+                registry,
+            )
+            .unwrap(),
+            total_fullnode_fallback_deduplicated_request: register_int_counter_with_registry!(
+                "total_fullnode_fallback_deduplicated_request",
+                "Total number of fallback requests served by an identical request in flight",
+                registry,
+            )
+            .unwrap(),
+            total_fullnode_fallback_retry: register_int_counter_with_registry!(
+                "total_fullnode_fallback_retry",
+                "Total number of fallback requests retried after a transport error",
+                registry,
+            )
+            .unwrap(),
+            inflight_fullnode_fallback_request: register_int_gauge_with_registry!(
+                "inflight_fullnode_fallback_request",
+                "Number of fallback requests in flight to the full node",
+                registry,
+            )
+            .unwrap(),
+            fullnode_endpoint_request: register_int_counter_vec_with_registry!(
+                "fullnode_endpoint_request",
+                "Total number of requests sent to each full node endpoint",
+                &["endpoint"],
+                registry,
+            )
+            .unwrap(),
+            fullnode_endpoint_error: register_int_counter_vec_with_registry!(
+                "fullnode_endpoint_error",
+                "Total number of failed or timed out requests of each full node endpoint",
+                &["endpoint"],
+                registry,
+            )
+            .unwrap(),
+            fullnode_endpoint_healthy: register_int_gauge_vec_with_registry!(
+                "fullnode_endpoint_healthy",
+                "1 if the last request to the full node endpoint succeeded, 0 otherwise",
+                &["endpoint"],
+                registry,
+            )
+            .unwrap(),
+            materialized_view_refresh_latency: register_histogram_vec_with_registry!(
+                "materialized_view_refresh_latency",
+                "Time spent refreshing each materialized view",
+                &["view"],
+                LATENCY_SEC_BUCKETS.to_vec(),
+                registry,
+            )
+            .unwrap(),
+            total_materialized_view_refresh_failure: register_int_counter_vec_with_registry!(
+                "total_materialized_view_refresh_failure",
+                "Total number of failed refreshes of each materialized view",
+                &["view"],
+                registry,
+            )
+            .unwrap(),
+            total_checkpoint_notification_failure: register_int_counter_with_registry!(
+                "total_checkpoint_notification_failure",
+                "Total number of checkpoint webhook requests that failed after retries",
+                registry,
+            )
+            .unwrap(),
+            total_checkpoint_notification_dropped: register_int_counter_with_registry!(
+                "total_checkpoint_notification_dropped",
+                "Total number of checkpoint notifications dropped as webhooks fell behind",
+                registry,
+            )
+            .unwrap(),
+            total_watchlist_matches: register_int_counter_with_registry!(
+                "total_watchlist_matches",
+                "Total number of watchlist entries matched by committed transactions",
+                registry,
+            )
+            .unwrap(),
+            total_slow_store_reads: register_int_counter_vec_with_registry!(
+                "total_slow_store_reads",
+                "Total number of store reads slower than the slow query threshold",
+                &["method"],
+                registry,
+            )
+            .unwrap(),
+            total_pruned_rows: register_int_counter_vec_with_registry!(
+                "total_pruned_rows",
+                "Total number of rows deleted by the pruner from each table family",
+                &["table"],
+                registry,
+            )
+            .unwrap(),
+            total_api_key_requests: register_int_counter_vec_with_registry!(
+                "total_api_key_requests",
+                "Total number of requests of the RPC server accepted for each API key",
+                &["api_key"],
+                registry,
+            )
+            .unwrap(),
+            total_api_key_rejected_requests: register_int_counter_vec_with_registry!(
+                "total_api_key_rejected_requests",
+                "Total number of requests of the RPC server rejected by API key and reason",
+                &["api_key", "reason"],
+                registry,
+            )
+            .unwrap(),
+            table_size_bytes: register_int_gauge_vec_with_registry!(
+                "table_size_bytes",
+                "Size on disk of each table with its indexes and TOAST data",
+                &["table"],
+                registry,
+            )
+            .unwrap(),
+            table_estimated_rows: register_int_gauge_vec_with_registry!(
+                "table_estimated_rows",
+                "Row count of each table estimated by the planner statistics",
+                &["table"],
+                registry,
+            )
+            .unwrap(),
+            table_growth_bytes_per_sec: register_int_gauge_vec_with_registry!(
+                "table_growth_bytes_per_sec",
+                "Growth rate of each table between the last two samples of its size",
+                &["table"],
+                registry,
+            )
+            .unwrap(),
+            total_table_growth_anomalies: register_int_counter_vec_with_registry!(
+                "total_table_growth_anomalies",
+                "Total number of samples of each table growing much faster or slower than usual",
+                &["table"],
+                registry,
+            )
+            .unwrap(),
+            total_transactions_reexecuted: register_int_counter_with_registry!(
+                "total_transactions_reexecuted",
+                "Total number of sampled transactions re-executed against the object history",
+                registry,
+            )
+            .unwrap(),
+            total_reexecution_mismatches: register_int_counter_with_registry!(
+                "total_reexecution_mismatches",
+                "Total number of re-executed transactions with effects differing from their checkpoint",
+                registry,
+            )
+            .unwrap(),
+            total_reexecutions_skipped: register_int_counter_with_registry!(
+                "total_reexecutions_skipped",
+                "Total number of sampled transactions that could not be re-executed, e.g. of pruned objects",
+                registry,
+            )
+            .unwrap(),
+            total_unchanged_object_mutations: register_int_counter_with_registry!(
+                "total_unchanged_object_mutations",
+                "Total number of mutated objects written without their unchanged BCS",
+                registry,
+            )
+            .unwrap(),
+            total_checkpoints_enqueued: register_int_counter_vec_with_registry!(
+                "total_checkpoints_enqueued",
+                "Total number of indexed checkpoints queued for the committer",
+                &["kind"],
+                registry,
+            )
+            .unwrap(),
+            total_checkpoints_dequeued: register_int_counter_vec_with_registry!(
+                "total_checkpoints_dequeued",
+                "Total number of queued checkpoints passed to the commit tasks",
+                &["kind"],
+                registry,
+            )
+            .unwrap(),
+            checkpoint_queue_next_sequence_number: register_int_gauge_vec_with_registry!(
+                "checkpoint_queue_next_sequence_number",
+                "Sequence number the committer of a split pipeline takes from the queue next",
+                &["kind"],
+                registry,
+            )
+            .unwrap(),
+            is_leader: register_int_gauge_with_registry!(
+                "is_leader",
+                "1 while this replica of the fullnode sync worker is the leader, 0 while it stands by",
+                registry,
+            )
+            .unwrap(),
         }
     }
 }
@@ -408,4 +741,25 @@ impl IndexerObjectProcessorMetrics {
             .unwrap(),
         }
     }
+
+    /// Updates checkpoint_freshness_ms from the latest committed checkpoint, periodically
+    /// rather than on commit so that it keeps growing while no checkpoint is committed.
+    pub async fn update_checkpoint_freshness(self) {
+        loop {
+            let timestamp_ms = self.latest_tx_checkpoint_timestamp_ms.get();
+            if timestamp_ms > 0 {
+                self.checkpoint_freshness_ms
+                    .set(checkpoint_freshness_ms(timestamp_ms));
+            }
+            tokio::time::sleep(CHECKPOINT_FRESHNESS_UPDATE_INTERVAL).await;
+        }
+    }
+}
+
+pub fn checkpoint_freshness_ms(checkpoint_timestamp_ms: i64) -> i64 {
+    let now_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64;
+    now_ms - checkpoint_timestamp_ms
 }
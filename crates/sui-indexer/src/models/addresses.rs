@@ -1,7 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use diesel::prelude::*;
 use diesel::sql_types::BigInt;
@@ -9,6 +9,8 @@ use diesel::QueryableByName;
 
 use sui_json_rpc_types::AddressMetrics;
 
+use crate::models::transaction_index::Recipient;
+use crate::models::transactions::Transaction;
 use crate::schema::{active_addresses, address_stats, addresses};
 use crate::types::AddressData;
 
@@ -32,6 +34,121 @@ pub struct ActiveAddress {
     pub last_appearance_time: i64,
 }
 
+/// Counters of the transactions an address sent and received, kept up to date as checkpoints
+/// are committed instead of counting the transaction index tables. A transaction is received
+/// by the owners of the objects it creates or changes other than its sender.
+#[derive(Queryable, Insertable, Clone, Debug, PartialEq, Eq)]
+#[diesel(table_name = addresses, primary_key(account_address))]
+pub struct AddressActivity {
+    pub account_address: String,
+    pub first_appearance_tx: String,
+    pub first_appearance_time: i64,
+    pub last_appearance_tx: String,
+    pub last_appearance_time: i64,
+    pub transactions_sent: i64,
+    pub transactions_received: i64,
+    pub first_activity_checkpoint: Option<i64>,
+    pub last_activity_checkpoint: Option<i64>,
+}
+
+impl AddressActivity {
+    fn new(
+        account_address: &str,
+        transaction_digest: &str,
+        checkpoint: i64,
+        timestamp_ms: i64,
+    ) -> Self {
+        Self {
+            account_address: account_address.to_string(),
+            first_appearance_tx: transaction_digest.to_string(),
+            first_appearance_time: timestamp_ms,
+            last_appearance_tx: transaction_digest.to_string(),
+            last_appearance_time: timestamp_ms,
+            transactions_sent: 0,
+            transactions_received: 0,
+            first_activity_checkpoint: Some(checkpoint),
+            last_activity_checkpoint: Some(checkpoint),
+        }
+    }
+
+    fn record_sent(&mut self, transaction_digest: &str) {
+        self.transactions_sent += 1;
+        self.last_appearance_tx = transaction_digest.to_string();
+    }
+
+    fn record_received(&mut self, transaction_digest: &str) {
+        self.transactions_received += 1;
+        self.last_appearance_tx = transaction_digest.to_string();
+    }
+
+    /// Adds the activity of the same address in a later checkpoint.
+    pub fn merge(&mut self, later: &AddressActivity) {
+        self.transactions_sent += later.transactions_sent;
+        self.transactions_received += later.transactions_received;
+        self.first_activity_checkpoint = self
+            .first_activity_checkpoint
+            .into_iter()
+            .chain(later.first_activity_checkpoint)
+            .min();
+        self.last_activity_checkpoint = self
+            .last_activity_checkpoint
+            .into_iter()
+            .chain(later.last_activity_checkpoint)
+            .max();
+        if later.last_appearance_time >= self.last_appearance_time {
+            self.last_appearance_tx = later.last_appearance_tx.clone();
+            self.last_appearance_time = later.last_appearance_time;
+        }
+    }
+}
+
+/// Activity of the addresses of a checkpoint to add to their counters once the checkpoint
+/// is committed, derived from its transactions and recipients.
+#[derive(Debug, Clone, Default)]
+pub struct CheckpointAddressActivity {
+    pub checkpoint_sequence_number: i64,
+    pub addresses: Vec<AddressActivity>,
+}
+
+impl CheckpointAddressActivity {
+    pub fn new(
+        checkpoint_sequence_number: i64,
+        timestamp_ms: i64,
+        transactions: &[Transaction],
+        recipients: &[Recipient],
+    ) -> Self {
+        // the sender owns the gas object, sending is not receiving
+        let mut received: HashMap<&str, Vec<&str>> = HashMap::new();
+        for recipient in recipients.iter().filter(|r| r.recipient != r.sender) {
+            received
+                .entry(&recipient.transaction_digest)
+                .or_default()
+                .push(&recipient.recipient);
+        }
+        let mut activity: BTreeMap<&str, AddressActivity> = BTreeMap::new();
+        let new_activity = |address: &str, digest: &str| {
+            AddressActivity::new(address, digest, checkpoint_sequence_number, timestamp_ms)
+        };
+        for transaction in transactions {
+            let digest = transaction.transaction_digest.as_str();
+            activity
+                .entry(&transaction.sender)
+                .or_insert_with(|| new_activity(&transaction.sender, digest))
+                .record_sent(digest);
+            for recipient in received.get(digest).into_iter().flatten() {
+                activity
+                    .entry(recipient)
+                    .or_insert_with(|| new_activity(recipient, digest))
+                    .record_received(digest);
+            }
+        }
+        Self {
+            checkpoint_sequence_number,
+            addresses: activity.into_values().collect(),
+        }
+    }
+}
+
 pub fn dedup_from_and_to_addresses(addrs: Vec<AddressData>) -> Vec<Address> {
     let addr_map = addrs.into_iter().fold(HashMap::new(), |mut acc, addr| {
         let key = addr.account_address.clone();
@@ -140,3 +257,66 @@ impl From<DBAddressStats> for AddressStats {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::models::transaction_index::Recipient;
+    use crate::models::transactions::Transaction;
+
+    use super::CheckpointAddressActivity;
+
+    #[test]
+    fn test_checkpoint_address_activity() {
+        let transaction = |digest: &str, sender: &str| Transaction {
+            transaction_digest: digest.to_string(),
+            sender: sender.to_string(),
+            ..Default::default()
+        };
+        let recipient = |digest: &str, sender: &str, recipient: &str| Recipient {
+            transaction_digest: digest.to_string(),
+            sender: sender.to_string(),
+            recipient: recipient.to_string(),
+            ..Default::default()
+        };
+        let mut activity = CheckpointAddressActivity::new(
+            7,
+            1000,
+            &[transaction("a", "0x1"), transaction("b", "0x1")],
+            &[
+                recipient("a", "0x1", "0x1"),
+                recipient("a", "0x1", "0x2"),
+                recipient("b", "0x1", "0x2"),
+            ],
+        );
+        assert_eq!(
+            activity
+                .addresses
+                .iter()
+                .map(|a| (
+                    a.account_address.as_str(),
+                    a.transactions_sent,
+                    a.transactions_received,
+                    a.last_appearance_tx.as_str()
+                ))
+                .collect::<Vec<_>>(),
+            vec![("0x1", 2, 0, "b"), ("0x2", 0, 2, "b")]
+        );
+
+        let later = CheckpointAddressActivity::new(9, 2000, &[transaction("c", "0x2")], &[]);
+        activity.addresses[1].merge(&later.addresses[0]);
+        let merged = &activity.addresses[1];
+        assert_eq!(
+            (merged.transactions_sent, merged.transactions_received),
+            (1, 2)
+        );
+        assert_eq!(merged.first_activity_checkpoint, Some(7));
+        assert_eq!(merged.last_activity_checkpoint, Some(9));
+        assert_eq!(
+            (
+                merged.first_appearance_tx.as_str(),
+                merged.last_appearance_tx.as_str()
+            ),
+            ("a", "c")
+        );
+    }
+}
@@ -0,0 +1,37 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use diesel::prelude::*;
+use fastcrypto::encoding::{Encoding, Hex};
+use fastcrypto::hash::{HashFunction, Sha256};
+
+use crate::schema::api_keys;
+
+/// A key clients of the RPC server authenticate with, when it requires API keys. Only the
+/// digest of the key is stored, see `hash_api_key`.
+#[derive(Queryable, Insertable, Debug, Clone, PartialEq, Eq)]
+#[diesel(table_name = api_keys)]
+pub struct ApiKey {
+    pub key_hash: String,
+    pub name: String,
+    pub requests_per_second: Option<i32>,
+    pub revoked: bool,
+}
+
+/// Hex SHA-256 digest of a key, the same as `encode(sha256('<key>'), 'hex')` in Postgres.
+pub fn hash_api_key(key: &str) -> String {
+    Hex::encode(Sha256::digest(key.as_bytes()).digest)
+}
+
+#[cfg(test)]
+mod test {
+    use super::hash_api_key;
+
+    #[test]
+    fn test_hash_api_key() {
+        assert_eq!(
+            hash_api_key("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+}
@@ -0,0 +1,26 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::str::FromStr;
+
+use diesel::prelude::*;
+use sui_protocol_config::Chain;
+use sui_types::digests::CheckpointDigest;
+
+use crate::errors::IndexerError;
+use crate::schema::chain_identifier;
+
+/// Genesis checkpoint digest of the chain indexed into the database, so that a database is
+/// never indexed from full nodes of another network.
+#[derive(Queryable, Insertable, Debug, Clone, PartialEq, Eq)]
+#[diesel(table_name = chain_identifier)]
+pub struct ChainIdentifier {
+    pub checkpoint_digest: String,
+}
+
+impl ChainIdentifier {
+    pub fn chain(&self) -> Result<Chain, IndexerError> {
+        let digest = CheckpointDigest::from_str(&self.checkpoint_digest)?;
+        Ok(sui_types::digests::ChainIdentifier::from(digest).chain())
+    }
+}
@@ -0,0 +1,101 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use sui_types::committee::Committee;
+use sui_types::digests::TransactionDigest;
+use sui_types::effects::{TransactionEffects, TransactionEffectsAPI};
+use sui_types::message_envelope::Message;
+use sui_types::messages_checkpoint::{
+    CertifiedCheckpointSummary, CheckpointContents, CheckpointSequenceNumber,
+};
+
+use crate::errors::IndexerError;
+use crate::schema::checkpoint_proofs;
+
+/// What a light client needs to verify the transactions of a checkpoint against the
+/// committee of its epoch, as BCS: the certified summary, the contents hashing to the
+/// content digest of the summary and the effects of the transactions hashing to the effects
+/// digests of the contents, in the order of the contents.
+#[derive(Queryable, Insertable, Debug, Clone, Default, Serialize, Deserialize)]
+#[diesel(table_name = checkpoint_proofs)]
+pub struct CheckpointProof {
+    pub sequence_number: i64,
+    pub certified_summary: Vec<u8>,
+    pub contents: Vec<u8>,
+    pub effects: Vec<Option<Vec<u8>>>,
+}
+
+impl CheckpointProof {
+    pub fn new<'a>(
+        checkpoint: &CertifiedCheckpointSummary,
+        contents: &CheckpointContents,
+        effects: impl IntoIterator<Item = &'a TransactionEffects>,
+    ) -> Result<Self, IndexerError> {
+        let effects = effects
+            .into_iter()
+            .map(|effects| (*effects.transaction_digest(), effects))
+            .collect::<HashMap<_, _>>();
+        let effects = contents
+            .iter()
+            .map(|digests| {
+                effects
+                    .get(&digests.transaction)
+                    .map(bcs::to_bytes)
+                    .transpose()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self {
+            sequence_number: checkpoint.sequence_number as i64,
+            certified_summary: bcs::to_bytes(checkpoint)?,
+            contents: bcs::to_bytes(contents)?,
+            effects,
+        })
+    }
+}
+
+/// Proof that `transaction_digest` was executed with `effects` in `checkpoint`, at
+/// `position` of its contents.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionProof {
+    pub checkpoint: CheckpointSequenceNumber,
+    pub position: u64,
+    pub transaction_digest: TransactionDigest,
+    pub certified_summary: Vec<u8>,
+    pub contents: Vec<u8>,
+    pub effects: Vec<u8>,
+}
+
+impl TransactionProof {
+    /// Checks the summary is certified by `committee`, the committee of its epoch, and the
+    /// chain of digests from the summary down to the effects of the transaction.
+    pub fn verify(&self, committee: &Committee) -> Result<TransactionEffects, IndexerError> {
+        let summary: CertifiedCheckpointSummary = bcs::from_bytes(&self.certified_summary)?;
+        let contents: CheckpointContents = bcs::from_bytes(&self.contents)?;
+        let effects: TransactionEffects = bcs::from_bytes(&self.effects)?;
+        summary.verify_with_contents(committee, Some(&contents))?;
+        if summary.sequence_number != self.checkpoint {
+            return Err(IndexerError::InvalidArgumentError(format!(
+                "Proof of checkpoint {} carries the summary of checkpoint {}",
+                self.checkpoint, summary.sequence_number
+            )));
+        }
+        let digests = contents.iter().nth(self.position as usize).ok_or_else(|| {
+            IndexerError::InvalidArgumentError(format!(
+                "Checkpoint {} has no transaction at position {}",
+                self.checkpoint, self.position
+            ))
+        })?;
+        if digests.transaction != self.transaction_digest || digests.effects != effects.digest() {
+            return Err(IndexerError::InvalidArgumentError(format!(
+                "Transaction {} and its effects are not at position {} of checkpoint {}",
+                self.transaction_digest, self.position, self.checkpoint
+            )));
+        }
+        Ok(effects)
+    }
+}
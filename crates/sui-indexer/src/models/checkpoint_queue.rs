@@ -0,0 +1,119 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use diesel::prelude::*;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::errors::IndexerError;
+use crate::models::compression::{compress_payload, decompress_payload};
+use crate::schema::checkpoint_queue;
+
+// payloads are written once and read once, a fast level is enough
+const QUEUE_COMPRESSION_LEVEL: i32 = 3;
+
+/// Indexed data passed from the indexers of a split pipeline to its committer, one queue
+/// per commit task.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum QueueKind {
+    /// `TemporaryCheckpointStore`s by checkpoint
+    Transactions,
+    /// object changes by checkpoint
+    Objects,
+    /// `TemporaryEpochStore`s by epoch
+    Epochs,
+}
+
+impl QueueKind {
+    pub const ALL: [QueueKind; 3] = [
+        QueueKind::Transactions,
+        QueueKind::Objects,
+        QueueKind::Epochs,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            QueueKind::Transactions => "transactions",
+            QueueKind::Objects => "objects",
+            QueueKind::Epochs => "epochs",
+        }
+    }
+}
+
+#[derive(Queryable, Insertable, Debug, Clone, PartialEq, Eq)]
+#[diesel(table_name = checkpoint_queue)]
+pub struct QueuedCheckpoint {
+    pub kind: String,
+    pub sequence_number: i64,
+    pub payload: Vec<u8>,
+}
+
+impl QueuedCheckpoint {
+    pub fn new<T: Serialize>(
+        kind: QueueKind,
+        sequence_number: i64,
+        data: &T,
+    ) -> Result<Self, IndexerError> {
+        let json = serde_json::to_vec(data).map_err(|e| {
+            IndexerError::SerdeError(format!(
+                "Failed serializing queued {} {sequence_number} with error: {:?}",
+                kind.name(),
+                e
+            ))
+        })?;
+        Ok(Self {
+            kind: kind.name().to_string(),
+            sequence_number,
+            payload: compress_payload(json, QUEUE_COMPRESSION_LEVEL),
+        })
+    }
+
+    pub fn data<T: DeserializeOwned>(&self) -> Result<T, IndexerError> {
+        serde_json::from_slice(&decompress_payload(&self.payload)?).map_err(|e| {
+            IndexerError::SerdeError(format!(
+                "Failed deserializing queued {} {} with error: {:?}",
+                self.kind, self.sequence_number, e
+            ))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::models::objects::{DeletedObject, ObjectStatus};
+    use crate::models::owners::OwnerType;
+    use crate::store::TransactionObjectChanges;
+
+    use super::{QueueKind, QueuedCheckpoint};
+
+    #[test]
+    fn test_queued_checkpoint_payload() {
+        let changes = vec![TransactionObjectChanges {
+            changed_objects: vec![],
+            deleted_objects: vec![DeletedObject {
+                epoch: 1,
+                checkpoint: Some(7),
+                object_id: "0x5".to_string(),
+                version: 3,
+                object_digest: "digest".to_string(),
+                owner_type: OwnerType::AddressOwner,
+                previous_transaction: "tx".to_string(),
+                object_type: "0x2::coin::Coin<0x2::sui::SUI>".repeat(10),
+                object_status: ObjectStatus::Deleted,
+                has_public_transfer: true,
+            }],
+            name_records: vec![],
+        }];
+        let queued =
+            QueuedCheckpoint::new(QueueKind::Objects, 7, &(7u64, Some(1u64), &changes)).unwrap();
+        assert_eq!(queued.kind, "objects");
+        let (sequence_number, ended_epoch, dequeued): (
+            u64,
+            Option<u64>,
+            Vec<TransactionObjectChanges>,
+        ) = queued.data().unwrap();
+        assert_eq!((sequence_number, ended_epoch), (7, Some(1)));
+        assert_eq!(dequeued[0].deleted_objects[0].object_id, "0x5");
+        assert!(queued.data::<Vec<u64>>().is_err());
+    }
+}
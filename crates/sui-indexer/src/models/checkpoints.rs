@@ -1,20 +1,29 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::ops::RangeInclusive;
+
 use diesel::prelude::*;
+use diesel::sql_types::BigInt;
+use serde::{Deserialize, Serialize};
 
+use fastcrypto::encoding::{Base58, Encoding};
+use fastcrypto::hash::Digest;
 use fastcrypto::traits::EncodeDecodeBase64;
 use sui_json_rpc_types::Checkpoint as RpcCheckpoint;
 use sui_types::base_types::TransactionDigest;
 use sui_types::crypto::AggregateAuthoritySignature;
-use sui_types::digests::CheckpointDigest;
+use sui_types::digests::{CheckpointContentsDigest, CheckpointDigest, TransactionEffectsDigest};
 use sui_types::gas::GasCostSummary;
-use sui_types::messages_checkpoint::EndOfEpochData;
+use sui_types::messages_checkpoint::{
+    CheckpointCommitment, CheckpointSequenceNumber, ECMHLiveObjectSetDigest, EndOfEpochData,
+};
 
 use crate::errors::IndexerError;
+use crate::schema::checkpoint_tx_digests;
 use crate::schema::checkpoints::{self};
 
-#[derive(Queryable, Insertable, Debug, Clone, Default)]
+#[derive(Queryable, Insertable, Debug, Clone, Default, Serialize, Deserialize)]
 #[diesel(table_name = checkpoints)]
 pub struct Checkpoint {
     pub sequence_number: i64,
@@ -41,6 +50,8 @@ pub struct Checkpoint {
     pub network_total_transactions: i64,
     pub timestamp_ms: i64,
     pub validator_signature: String,
+    // see commitments_to_column
+    pub checkpoint_commitments: Vec<Option<Vec<u8>>>,
 }
 
 impl Checkpoint {
@@ -79,6 +90,7 @@ impl Checkpoint {
             network_total_transactions: checkpoint.network_total_transactions as i64,
             timestamp_ms: checkpoint.timestamp_ms as i64,
             validator_signature: checkpoint.auth_sig().signature.encode_base64(),
+            checkpoint_commitments: commitments_to_column(&checkpoint.checkpoint_commitments),
         }
     }
 
@@ -145,7 +157,144 @@ impl Checkpoint {
             network_total_transactions: self.network_total_transactions as u64,
             timestamp_ms: self.timestamp_ms as u64,
             transactions: parsed_tx_digests,
-            checkpoint_commitments: vec![],
+            checkpoint_commitments: commitments_from_column(&self.checkpoint_commitments),
+        })
+    }
+}
+
+/// Commitments as an array column of commitment bytes, the 32 bytes of the digest of
+/// ECMH live object set digests.
+pub fn commitments_to_column(commitments: &[CheckpointCommitment]) -> Vec<Option<Vec<u8>>> {
+    commitments
+        .iter()
+        .map(|commitment| match commitment {
+            CheckpointCommitment::ECMHLiveObjectSetDigest(d) => {
+                Some(d.digest.into_inner().to_vec())
+            }
         })
+        .collect()
+}
+
+/// Commitments of an array column of commitment bytes, skipping malformed ones.
+pub fn commitments_from_column(column: &[Option<Vec<u8>>]) -> Vec<CheckpointCommitment> {
+    column
+        .iter()
+        .flatten()
+        .filter_map(|bytes| {
+            let bytes: [u8; 32] = bytes.clone().try_into().ok()?;
+            Some(CheckpointCommitment::ECMHLiveObjectSetDigest(
+                ECMHLiveObjectSetDigest::from(Digest::new(bytes)),
+            ))
+        })
+        .collect()
+}
+
+#[derive(Queryable, Insertable, Debug, Clone, Default, Serialize, Deserialize)]
+#[diesel(table_name = checkpoint_tx_digests)]
+pub struct CheckpointTxDigest {
+    pub checkpoint_sequence_number: i64,
+    pub position: i64,
+    pub transaction_digest: String,
+    pub effects_digest: String,
+    pub content_digest: String,
+}
+
+impl CheckpointTxDigest {
+    pub fn from_checkpoint_contents(
+        checkpoint: &sui_types::messages_checkpoint::CertifiedCheckpointSummary,
+        contents: &sui_types::messages_checkpoint::CheckpointContents,
+    ) -> Vec<Self> {
+        let content_digest = checkpoint.content_digest.base58_encode();
+        contents
+            .iter()
+            .enumerate()
+            .map(|(position, digests)| Self {
+                checkpoint_sequence_number: checkpoint.sequence_number as i64,
+                position: position as i64,
+                transaction_digest: digests.transaction.base58_encode(),
+                effects_digest: digests.effects.base58_encode(),
+                content_digest: content_digest.clone(),
+            })
+            .collect()
+    }
+}
+
+/// Location of a transaction in the contents of its checkpoint, the contents hash to
+/// `content_digest` which is signed over in the checkpoint summary.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionInclusion {
+    pub checkpoint: CheckpointSequenceNumber,
+    pub position: u64,
+    pub transaction_digest: TransactionDigest,
+    pub effects_digest: TransactionEffectsDigest,
+    pub content_digest: CheckpointContentsDigest,
+}
+
+impl TryFrom<CheckpointTxDigest> for TransactionInclusion {
+    type Error = IndexerError;
+
+    fn try_from(digest: CheckpointTxDigest) -> Result<Self, Self::Error> {
+        Ok(TransactionInclusion {
+            checkpoint: digest.checkpoint_sequence_number as u64,
+            position: digest.position as u64,
+            transaction_digest: TransactionDigest::new(decode_digest(&digest.transaction_digest)?),
+            effects_digest: TransactionEffectsDigest::new(decode_digest(&digest.effects_digest)?),
+            content_digest: CheckpointContentsDigest::new(decode_digest(&digest.content_digest)?),
+        })
+    }
+}
+
+fn decode_digest(digest: &str) -> Result<[u8; 32], IndexerError> {
+    Base58::decode(digest)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| IndexerError::SerdeError(format!("Failed to decode digest: {:?}", digest)))
+}
+
+/// Consecutive committed checkpoints that do not follow each other, either because
+/// checkpoints are missing between them or because `network_total_transactions` of the
+/// next one does not add up with its transaction blocks.
+#[derive(QueryableByName, Debug, Clone, PartialEq, Eq)]
+pub struct CheckpointDiscontinuity {
+    #[diesel(sql_type = BigInt)]
+    pub sequence_number: i64,
+    #[diesel(sql_type = BigInt)]
+    pub network_total_transactions: i64,
+    #[diesel(sql_type = BigInt)]
+    pub next_sequence_number: i64,
+    #[diesel(sql_type = BigInt)]
+    pub next_network_total_transactions: i64,
+    #[diesel(sql_type = BigInt)]
+    pub next_total_transaction_blocks: i64,
+}
+
+impl CheckpointDiscontinuity {
+    pub fn missing_checkpoints(&self) -> Option<RangeInclusive<CheckpointSequenceNumber>> {
+        (self.next_sequence_number > self.sequence_number + 1).then(|| {
+            (self.sequence_number + 1) as CheckpointSequenceNumber
+                ..=(self.next_sequence_number - 1) as CheckpointSequenceNumber
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use sui_types::messages_checkpoint::{CheckpointCommitment, ECMHLiveObjectSetDigest};
+
+    use super::{commitments_from_column, commitments_to_column};
+
+    #[test]
+    fn test_commitments_column() {
+        let commitments = vec![CheckpointCommitment::from(
+            ECMHLiveObjectSetDigest::default(),
+        )];
+        let mut column = commitments_to_column(&commitments);
+        assert_eq!(column.len(), 1);
+        assert_eq!(commitments_from_column(&column), commitments);
+        // malformed digests are skipped
+        column.push(Some(vec![1, 2, 3]));
+        column.push(None);
+        assert_eq!(commitments_from_column(&column), commitments);
     }
 }
@@ -0,0 +1,65 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::borrow::Cow;
+
+use crate::errors::IndexerError;
+
+// Prefix of zstd compressed payloads, followed by the zstd frame. The BCS payloads of the
+// compressed columns never start with it followed by the zstd magic, so payloads written
+// uncompressed stay readable.
+const COMPRESSED_PAYLOAD_V1: u8 = 0xf1;
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+// smaller payloads are stored as they are, compressing them saves next to nothing
+const MIN_COMPRESSED_PAYLOAD_SIZE: usize = 128;
+
+/// Compresses `payload` with zstd at `level`, 0 selecting the default level. Payloads that
+/// are small or do not shrink are returned as they are.
+pub fn compress_payload(payload: Vec<u8>, level: i32) -> Vec<u8> {
+    if payload.len() < MIN_COMPRESSED_PAYLOAD_SIZE {
+        return payload;
+    }
+    match zstd::encode_all(payload.as_slice(), level) {
+        Ok(compressed) if compressed.len() + 1 < payload.len() => {
+            let mut prefixed = Vec::with_capacity(compressed.len() + 1);
+            prefixed.push(COMPRESSED_PAYLOAD_V1);
+            prefixed.extend(compressed);
+            prefixed
+        }
+        _ => payload,
+    }
+}
+
+/// The payload as written before `compress_payload`, of compressed and uncompressed payloads.
+pub fn decompress_payload(payload: &[u8]) -> Result<Cow<'_, [u8]>, IndexerError> {
+    match payload.split_first() {
+        Some((&COMPRESSED_PAYLOAD_V1, frame)) if frame.starts_with(&ZSTD_MAGIC) => {
+            zstd::decode_all(frame).map(Cow::Owned).map_err(|e| {
+                IndexerError::SerdeError(format!(
+                    "Failed decompressing payload with error: {:?}",
+                    e
+                ))
+            })
+        }
+        _ => Ok(Cow::Borrowed(payload)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{compress_payload, decompress_payload};
+
+    #[test]
+    fn test_payload_compression() {
+        let payload = [vec![0u8; 1024], (0..=255).collect()].concat();
+        let compressed = compress_payload(payload.clone(), 3);
+        assert!(compressed.len() < payload.len());
+        assert_eq!(decompress_payload(&compressed).unwrap(), payload);
+        // small payloads and payloads written uncompressed
+        let small = vec![1, 2, 3];
+        assert_eq!(compress_payload(small.clone(), 3), small);
+        assert_eq!(decompress_payload(&small).unwrap(), small);
+        assert_eq!(decompress_payload(&payload).unwrap(), payload);
+        assert_eq!(decompress_payload(&[]).unwrap(), Vec::<u8>::new());
+    }
+}
@@ -0,0 +1,141 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+
+use diesel::{Insertable, Queryable};
+
+use sui_types::base_types::ObjectID;
+use sui_types::collection_types::VecMap;
+use sui_types::display::{
+    DisplayVersionUpdatedEvent, DISPLAY_MODULE_NAME, DISPLAY_VERSION_UPDATED_EVENT_NAME,
+};
+use sui_types::SUI_FRAMEWORK_ADDRESS;
+
+use crate::errors::IndexerError;
+use crate::models::events::Event;
+use crate::schema::display;
+
+/// Latest released version of the Display of an object type.
+#[derive(Queryable, Insertable, Debug, Clone)]
+#[diesel(table_name = display)]
+pub struct DBDisplay {
+    // canonical type string of the objects displayed, the type param of the event
+    pub object_type: String,
+    pub display_id: String,
+    pub version: i32,
+    pub bcs: Vec<u8>,
+}
+
+impl DBDisplay {
+    /// Displays released by `display::VersionUpdated` events among `events`, the last
+    /// release of each object type only.
+    pub fn from_events(events: &[Event]) -> Result<Vec<Self>, IndexerError> {
+        let framework = ObjectID::from(SUI_FRAMEWORK_ADDRESS).to_string();
+        let mut displays = BTreeMap::new();
+        for event in events.iter().filter(|event| {
+            event.event_type_package == framework
+                && event.event_type_module == DISPLAY_MODULE_NAME.as_str()
+                && event.event_type_name == DISPLAY_VERSION_UPDATED_EVENT_NAME.as_str()
+        }) {
+            let updated: DisplayVersionUpdatedEvent =
+                bcs::from_bytes(&event.event_bcs).map_err(|e| {
+                    IndexerError::SerdeError(format!(
+                        "Failed deserializing display event of transaction {} with error: {e}",
+                        event.transaction_digest
+                    ))
+                })?;
+            displays.insert(
+                event.event_type_params.clone(),
+                Self {
+                    object_type: event.event_type_params.clone(),
+                    display_id: updated.id.id.bytes.to_string(),
+                    version: updated.version as i32,
+                    bcs: event.event_bcs.clone(),
+                },
+            );
+        }
+        Ok(displays.into_values().collect())
+    }
+
+    /// Templates of the displayed fields, by field name.
+    pub fn fields(&self) -> Result<VecMap<String, String>, IndexerError> {
+        bcs::from_bytes::<DisplayVersionUpdatedEvent>(&self.bcs)
+            .map(|updated| updated.fields)
+            .map_err(|e| {
+                IndexerError::SerdeError(format!(
+                    "Failed deserializing display of type {} with error: {e}",
+                    self.object_type
+                ))
+            })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use move_core_types::language_storage::StructTag;
+    use sui_types::base_types::{ObjectID, SuiAddress};
+    use sui_types::collection_types::{Entry, VecMap};
+    use sui_types::digests::TransactionDigest;
+    use sui_types::display::{DisplayVersionUpdatedEvent, DISPLAY_MODULE_NAME};
+    use sui_types::id::UID;
+    use sui_types::SUI_FRAMEWORK_ADDRESS;
+
+    use super::DBDisplay;
+    use crate::models::events::Event;
+
+    fn event(type_: StructTag, contents: Vec<u8>) -> Event {
+        Event::from_sui_event(
+            &sui_types::event::Event::new(
+                &SUI_FRAMEWORK_ADDRESS,
+                DISPLAY_MODULE_NAME,
+                SuiAddress::random_for_testing_only(),
+                type_,
+                contents,
+            ),
+            &TransactionDigest::random(),
+            0,
+            0,
+            0,
+        )
+    }
+
+    fn display_event(object_type: &StructTag, version: u16, name: &str) -> Event {
+        let fields = VecMap {
+            contents: vec![Entry {
+                key: "name".to_string(),
+                value: name.to_string(),
+            }],
+        };
+        event(
+            DisplayVersionUpdatedEvent::type_(object_type),
+            bcs::to_bytes(&(UID::new(ObjectID::random()), version, fields)).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_displays_from_events() {
+        let nft = StructTag::from_str("0x42::nft::Nft").unwrap();
+        let coin = StructTag::from_str("0x2::coin::Coin<0x2::sui::SUI>").unwrap();
+        let events = vec![
+            display_event(&nft, 1, "{name}"),
+            event(StructTag::from_str("0x42::nft::Minted").unwrap(), vec![]),
+            display_event(&coin, 1, "Coin"),
+            display_event(&nft, 2, "Nft {name}"),
+        ];
+
+        let displays = DBDisplay::from_events(&events).unwrap();
+        assert_eq!(displays.len(), 2);
+        let nft_display = displays
+            .iter()
+            .find(|display| display.object_type == events[0].event_type_params)
+            .unwrap();
+        assert_eq!(nft_display.version, 2);
+        assert_eq!(
+            nft_display.fields().unwrap().contents[0].value,
+            "Nft {name}"
+        );
+    }
+}
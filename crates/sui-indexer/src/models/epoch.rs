@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use diesel::{Insertable, Queryable};
-use jsonrpsee::core::__reexports::serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use sui_json_rpc_types::{EndOfEpochInfo, EpochInfo};
 
@@ -10,7 +10,7 @@ use crate::errors::IndexerError;
 use crate::models::system_state::DBValidatorSummary;
 use crate::schema::epochs;
 
-#[derive(Queryable, Insertable, Debug, Clone, Default)]
+#[derive(Queryable, Insertable, Debug, Clone, Default, Serialize, Deserialize)]
 #[diesel(table_name = epochs)]
 pub struct DBEpochInfo {
     pub epoch: i64,
@@ -36,6 +36,8 @@ pub struct DBEpochInfo {
     pub total_gas_fees: Option<i64>,
     pub total_stake_rewards_distributed: Option<i64>,
     pub leftover_storage_fund_inflow: Option<i64>,
+    // epoch ended in safe mode without emitting SystemEpochInfoEvent
+    pub safe_mode: bool,
 }
 
 impl DBEpochInfo {
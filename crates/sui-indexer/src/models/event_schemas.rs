@@ -0,0 +1,75 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use diesel::prelude::*;
+use move_binary_format::normalized;
+use move_binary_format::CompiledModule;
+
+use sui_json_rpc_types::SuiMoveNormalizedStruct;
+
+use crate::errors::IndexerError;
+use crate::models::packages::Package;
+use crate::schema::event_schemas;
+
+/// Layout of a struct declared by a package that can be emitted as an event, i.e. that has
+/// the copy and drop abilities. `layout` is the `SuiMoveNormalizedStruct` of the struct,
+/// describing the fields of the `parsed_json` of its events, as of `version` of the package.
+#[derive(Queryable, Insertable, Debug, Clone)]
+#[diesel(table_name = event_schemas)]
+pub struct EventSchema {
+    pub package_id: String,
+    pub version: i64,
+    pub module: String,
+    pub name: String,
+    pub layout: serde_json::Value,
+}
+
+impl EventSchema {
+    /// Schemas of the event structs of all modules of `package`, keyed by the id and version
+    /// of this version of the package.
+    pub fn from_package(package: &Package) -> Result<Vec<Self>, IndexerError> {
+        let mut schemas = vec![];
+        for module in &package.data {
+            let compiled = CompiledModule::deserialize_with_defaults(&module.1).map_err(|e| {
+                IndexerError::ModuleDeserializationError(format!(
+                    "Failed deserializing module {}::{} with error: {e:?}",
+                    package.package_id, module.0
+                ))
+            })?;
+            let normalized = normalized::Module::new(&compiled);
+            for (name, struct_) in normalized.structs {
+                if !(struct_.abilities.has_copy() && struct_.abilities.has_drop()) {
+                    continue;
+                }
+                let layout =
+                    serde_json::to_value(SuiMoveNormalizedStruct::from(struct_)).map_err(|e| {
+                        IndexerError::SerdeError(format!(
+                            "Failed serializing layout of {}::{}::{name} with error: {e}",
+                            package.package_id, module.0
+                        ))
+                    })?;
+                schemas.push(Self {
+                    package_id: package.package_id.clone(),
+                    version: package.version,
+                    module: normalized.name.to_string(),
+                    name: name.to_string(),
+                    layout,
+                });
+            }
+        }
+        Ok(schemas)
+    }
+}
+
+impl TryFrom<EventSchema> for SuiMoveNormalizedStruct {
+    type Error = IndexerError;
+
+    fn try_from(schema: EventSchema) -> Result<Self, Self::Error> {
+        serde_json::from_value(schema.layout).map_err(|e| {
+            IndexerError::SerdeError(format!(
+                "Failed deserializing layout of {}::{}::{} with error: {e}",
+                schema.package_id, schema.module, schema.name
+            ))
+        })
+    }
+}
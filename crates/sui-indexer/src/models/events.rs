@@ -6,10 +6,12 @@ use std::str::FromStr;
 use diesel::prelude::*;
 use move_bytecode_utils::module_cache::GetModule;
 use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::StructTag;
 use move_core_types::value::MoveStruct;
+use serde::{Deserialize, Serialize};
 
 use sui_json_rpc_types::{SuiEvent, SuiMoveStruct};
-use sui_types::base_types::TransactionDigest;
+use sui_types::base_types::{ObjectID, TransactionDigest};
 use sui_types::event::EventID;
 use sui_types::object::{MoveObject, ObjectFormatOptions};
 use sui_types::parse_sui_struct_tag;
@@ -17,7 +19,7 @@ use sui_types::parse_sui_struct_tag;
 use crate::errors::IndexerError;
 use crate::schema::events;
 
-#[derive(Queryable, Insertable, Debug, Clone)]
+#[derive(Queryable, Insertable, Debug, Clone, Serialize, Deserialize)]
 #[diesel(table_name = events)]
 pub struct Event {
     #[diesel(deserialize_as = i64)]
@@ -30,40 +32,59 @@ pub struct Event {
     pub event_type: String,
     pub event_time_ms: Option<i64>,
     pub event_bcs: Vec<u8>,
+    // event_type decomposed at index time, so that filtering by type does not
+    // need to match on the full canonical type string.
+    pub event_type_package: String,
+    pub event_type_module: String,
+    pub event_type_name: String,
+    pub event_type_params: String,
+    // events are ordered by (tx_sequence_number, event_sequence), the sequence number of
+    // their transaction in the network then their index in the events of the transaction
+    pub tx_sequence_number: i64,
 }
 
-impl From<SuiEvent> for Event {
-    fn from(se: SuiEvent) -> Self {
-        Self {
-            id: None,
-            transaction_digest: se.id.tx_digest.base58_encode(),
-            event_sequence: se.id.event_seq as i64,
-            sender: se.sender.to_string(),
-            package: se.package_id.to_string(),
-            module: se.transaction_module.to_string(),
-            event_type: se.type_.to_string(),
-            event_time_ms: se.timestamp_ms.map(|t| t as i64),
-            event_bcs: se.bcs,
-        }
-    }
+/// Decomposes a struct tag into package, module, name and the comma separated type params,
+/// in the same format as they are stored in the events table.
+pub fn decompose_struct_tag(struct_tag: &StructTag) -> (String, String, String, String) {
+    let type_params = struct_tag
+        .type_params
+        .iter()
+        .map(|t| t.to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    (
+        ObjectID::from(struct_tag.address).to_string(),
+        struct_tag.module.to_string(),
+        struct_tag.name.to_string(),
+        type_params,
+    )
 }
 
 impl Event {
     pub fn from_sui_event(
         event: &sui_types::event::Event,
         transaction_digest: &TransactionDigest,
+        tx_sequence_number: u64,
+        event_sequence: u64,
         event_timestamp_ms: u64,
     ) -> Self {
+        let (event_type_package, event_type_module, event_type_name, event_type_params) =
+            decompose_struct_tag(&event.type_);
         Self {
             id: None,
             transaction_digest: transaction_digest.base58_encode(),
-            event_sequence: 0,
+            event_sequence: event_sequence as i64,
             sender: event.sender.to_string(),
             package: event.package_id.to_string(),
             module: event.transaction_module.to_string(),
             event_type: event.type_.to_string(),
             event_time_ms: Some(event_timestamp_ms as i64),
             event_bcs: event.contents.clone(),
+            event_type_package,
+            event_type_module,
+            event_type_name,
+            event_type_params,
+            tx_sequence_number: tx_sequence_number as i64,
         }
     }
 
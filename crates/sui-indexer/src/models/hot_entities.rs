@@ -0,0 +1,232 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{BTreeMap, HashSet};
+use std::str::FromStr;
+
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Text};
+
+use sui_json_rpc_types::HotEntity;
+use sui_types::base_types::ObjectID;
+
+use crate::errors::IndexerError;
+use crate::models::transaction_index::{ChangedObject, InputObject, MoveCall};
+use crate::schema::{hot_objects, hot_packages};
+
+/// Width of the time buckets the leaderboards are counted in, so a window spans its
+/// duration rounded up to whole buckets.
+pub const HOT_BUCKET_MS: i64 = 5 * 60 * 1000;
+
+/// Rolling windows of the leaderboards, ending at the latest committed checkpoint.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum HotWindow {
+    OneHour,
+    OneDay,
+}
+
+impl HotWindow {
+    pub const ALL: [HotWindow; 2] = [HotWindow::OneHour, HotWindow::OneDay];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            HotWindow::OneHour => "1h",
+            HotWindow::OneDay => "24h",
+        }
+    }
+
+    pub fn duration_ms(&self) -> i64 {
+        match self {
+            HotWindow::OneHour => 60 * 60 * 1000,
+            HotWindow::OneDay => 24 * 60 * 60 * 1000,
+        }
+    }
+
+    /// Buckets older than the longest window are not read anymore.
+    pub fn max_duration_ms() -> i64 {
+        Self::ALL
+            .iter()
+            .map(HotWindow::duration_ms)
+            .max()
+            .unwrap_or_default()
+    }
+}
+
+impl FromStr for HotWindow {
+    type Err = IndexerError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|window| window.name() == s)
+            .ok_or_else(|| {
+                IndexerError::InvalidArgumentError(format!(
+                    "Unknown window {s}, expected one of {:?}",
+                    Self::ALL.map(|window| window.name())
+                ))
+            })
+    }
+}
+
+pub fn bucket_start_ms(timestamp_ms: i64) -> i64 {
+    timestamp_ms - timestamp_ms.rem_euclid(HOT_BUCKET_MS)
+}
+
+#[derive(Queryable, Insertable, Debug, Clone, PartialEq, Eq)]
+#[diesel(table_name = hot_objects)]
+pub struct HotObject {
+    pub bucket_start_ms: i64,
+    pub object_id: String,
+    // transactions taking the object as input or changing it
+    pub touches: i64,
+}
+
+#[derive(Queryable, Insertable, Debug, Clone, PartialEq, Eq)]
+#[diesel(table_name = hot_packages)]
+pub struct HotPackage {
+    pub bucket_start_ms: i64,
+    pub package: String,
+    pub calls: i64,
+}
+
+/// Counts of a checkpoint to add to the leaderboards once the checkpoint is committed,
+/// derived from its transaction index rows.
+#[derive(Debug, Clone, Default)]
+pub struct CheckpointHotEntities {
+    pub checkpoint_sequence_number: i64,
+    pub objects: Vec<HotObject>,
+    pub packages: Vec<HotPackage>,
+}
+
+impl CheckpointHotEntities {
+    pub fn new(
+        checkpoint_sequence_number: i64,
+        timestamp_ms: i64,
+        input_objects: &[InputObject],
+        changed_objects: &[ChangedObject],
+        move_calls: &[MoveCall],
+    ) -> Self {
+        let bucket_start_ms = bucket_start_ms(timestamp_ms);
+        // an object both read and changed by a transaction is touched once
+        let touched = input_objects
+            .iter()
+            .map(|o| (&o.transaction_digest, &o.object_id))
+            .chain(
+                changed_objects
+                    .iter()
+                    .map(|o| (&o.transaction_digest, &o.object_id)),
+            )
+            .collect::<HashSet<_>>();
+        let mut touches = BTreeMap::new();
+        for (_, object_id) in touched {
+            *touches.entry(object_id).or_insert(0) += 1;
+        }
+        let mut calls = BTreeMap::new();
+        for move_call in move_calls {
+            *calls.entry(&move_call.move_package).or_insert(0) += 1;
+        }
+        Self {
+            checkpoint_sequence_number,
+            objects: touches
+                .into_iter()
+                .map(|(object_id, touches)| HotObject {
+                    bucket_start_ms,
+                    object_id: object_id.clone(),
+                    touches,
+                })
+                .collect(),
+            packages: calls
+                .into_iter()
+                .map(|(package, calls)| HotPackage {
+                    bucket_start_ms,
+                    package: package.clone(),
+                    calls,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(QueryableByName, Debug)]
+pub struct DBHotEntity {
+    #[diesel(sql_type = Text)]
+    pub id: String,
+    #[diesel(sql_type = BigInt)]
+    pub count: i64,
+}
+
+impl TryFrom<DBHotEntity> for HotEntity {
+    type Error = IndexerError;
+
+    fn try_from(entity: DBHotEntity) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: ObjectID::from_str(&entity.id)?,
+            count: entity.count as u64,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use crate::models::transaction_index::{ChangedObject, InputObject, MoveCall};
+
+    use super::{bucket_start_ms, CheckpointHotEntities, HotWindow, HOT_BUCKET_MS};
+
+    #[test]
+    fn test_checkpoint_hot_entities() {
+        let input_object = |tx: &str, object_id: &str| InputObject {
+            transaction_digest: tx.to_string(),
+            object_id: object_id.to_string(),
+            ..Default::default()
+        };
+        let changed_object = |tx: &str, object_id: &str| ChangedObject {
+            transaction_digest: tx.to_string(),
+            object_id: object_id.to_string(),
+            ..Default::default()
+        };
+        let move_call = |tx: &str, package: &str| MoveCall {
+            transaction_digest: tx.to_string(),
+            move_package: package.to_string(),
+            ..Default::default()
+        };
+        let timestamp_ms = 3 * HOT_BUCKET_MS + 42;
+        let hot = CheckpointHotEntities::new(
+            7,
+            timestamp_ms,
+            &[input_object("a", "0x1"), input_object("b", "0x1")],
+            &[changed_object("a", "0x1"), changed_object("a", "0x2")],
+            &[
+                move_call("a", "0x2"),
+                move_call("a", "0x2"),
+                move_call("b", "0x3"),
+            ],
+        );
+        assert_eq!(hot.checkpoint_sequence_number, 7);
+        assert_eq!(
+            hot.objects
+                .iter()
+                .map(|o| (o.bucket_start_ms, o.object_id.as_str(), o.touches))
+                .collect::<Vec<_>>(),
+            vec![(3 * HOT_BUCKET_MS, "0x1", 2), (3 * HOT_BUCKET_MS, "0x2", 1)]
+        );
+        assert_eq!(
+            hot.packages
+                .iter()
+                .map(|p| (p.package.as_str(), p.calls))
+                .collect::<Vec<_>>(),
+            vec![("0x2", 2), ("0x3", 1)]
+        );
+        assert_eq!(bucket_start_ms(timestamp_ms), 3 * HOT_BUCKET_MS);
+    }
+
+    #[test]
+    fn test_parse_hot_window() {
+        for window in HotWindow::ALL {
+            assert_eq!(HotWindow::from_str(window.name()).unwrap(), window);
+        }
+        assert!(HotWindow::from_str("7d").is_err());
+        assert_eq!(HotWindow::max_duration_ms(), 24 * 60 * 60 * 1000);
+    }
+}
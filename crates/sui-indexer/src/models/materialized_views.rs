@@ -0,0 +1,76 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::str::FromStr;
+
+use diesel::prelude::*;
+use diesel::sql_types::Bool;
+
+use crate::errors::IndexerError;
+
+/// Materialized views of common aggregates, created by the `aggregate_views` and
+/// `object_size` migrations and refreshed periodically by the indexer when enabled.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MaterializedView {
+    DailyTransactionCounts,
+    DailyActiveAddresses,
+    TopPackages,
+    /// net bytes of object data added per epoch and object type, a table updated
+    /// incrementally rather than a materialized view, as it is computed from objects_history
+    EpochStorageGrowth,
+}
+
+impl MaterializedView {
+    pub const ALL: [MaterializedView; 4] = [
+        MaterializedView::DailyTransactionCounts,
+        MaterializedView::DailyActiveAddresses,
+        MaterializedView::TopPackages,
+        MaterializedView::EpochStorageGrowth,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            MaterializedView::DailyTransactionCounts => "daily_transaction_counts",
+            MaterializedView::DailyActiveAddresses => "daily_active_addresses",
+            MaterializedView::TopPackages => "top_packages",
+            MaterializedView::EpochStorageGrowth => "epoch_storage_growth",
+        }
+    }
+}
+
+impl FromStr for MaterializedView {
+    type Err = IndexerError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|view| view.name() == s)
+            .ok_or_else(|| {
+                IndexerError::InvalidArgumentError(format!(
+                    "Unknown materialized view {s}, expected one of {:?}",
+                    Self::ALL.map(|view| view.name())
+                ))
+            })
+    }
+}
+
+#[derive(QueryableByName, Debug)]
+pub struct MaterializedViewStatus {
+    #[diesel(sql_type = Bool)]
+    pub ispopulated: bool,
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use super::MaterializedView;
+
+    #[test]
+    fn test_parse_materialized_view() {
+        for view in MaterializedView::ALL {
+            assert_eq!(MaterializedView::from_str(view.name()).unwrap(), view);
+        }
+        assert!(MaterializedView::from_str("transactions").is_err());
+    }
+}
@@ -2,14 +2,38 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod addresses;
+pub mod api_keys;
+pub mod chain_identifier;
 pub mod checkpoint_metrics;
+pub mod checkpoint_proofs;
+pub mod checkpoint_queue;
 pub mod checkpoints;
+pub mod compression;
+pub mod display;
 pub mod epoch;
+pub mod event_schemas;
 pub mod events;
+pub mod hot_entities;
+pub mod materialized_views;
+pub mod name_service;
 pub mod network_metrics;
+pub mod object_type_stats;
 pub mod objects;
 pub mod owners;
+pub mod ownership_history;
 pub mod packages;
+pub mod portfolio;
+pub mod protocol_configs;
+pub mod reexecution;
+pub mod retention;
+pub mod snapshot_bootstraps;
+pub mod sponsors;
 pub mod system_state;
+pub mod table_sizes;
 pub mod transaction_index;
 pub mod transactions;
+pub mod transfers;
+pub mod tx_calls;
+pub mod validator_participation;
+pub mod verified_sources;
+pub mod watchlists;
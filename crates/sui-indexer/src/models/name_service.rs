@@ -0,0 +1,192 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fmt;
+
+use diesel::{Insertable, Queryable};
+use move_core_types::language_storage::{StructTag, TypeTag};
+use serde::{Deserialize, Serialize};
+
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::collection_types::VecMap;
+use sui_types::dynamic_field::{DynamicFieldInfo, Field};
+use sui_types::id::ID;
+use sui_types::object::{Object, Owner};
+
+use crate::errors::IndexerError;
+use crate::schema::name_records;
+
+const NAME_RECORD_MODULE: &str = "name_record";
+const NAME_RECORD_STRUCT: &str = "NameRecord";
+
+/// Rust version of the `domain::Domain` Move type of the name service, with the labels
+/// stored from the top level domain down.
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct Domain {
+    pub labels: Vec<String>,
+}
+
+impl fmt::Display for Domain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let labels = self.labels.iter().rev().cloned().collect::<Vec<_>>();
+        f.write_str(&labels.join("."))
+    }
+}
+
+/// Rust version of the `name_record::NameRecord` Move type of the name service.
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct MoveNameRecord {
+    pub nft_id: ID,
+    pub expiration_timestamp_ms: u64,
+    pub target_address: Option<SuiAddress>,
+    pub data: VecMap<String, String>,
+}
+
+/// A name of the name service, indexed from the dynamic fields of the registry table.
+#[derive(Queryable, Insertable, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[diesel(table_name = name_records)]
+pub struct NameRecord {
+    // id of the dynamic field holding the record, derived from the name so that it is the
+    // same for all registrations of the name
+    pub field_id: String,
+    pub name: String,
+    pub nft_id: String,
+    pub target_address: Option<String>,
+    pub expiration_timestamp_ms: i64,
+    pub version: i64,
+    pub checkpoint_sequence_number: i64,
+}
+
+impl NameRecord {
+    /// The record held by `object` if it is a dynamic field of the registry table
+    /// `registry_id`, None for any other object.
+    pub fn try_from_object(
+        object: &Object,
+        registry_id: ObjectID,
+        checkpoint: u64,
+    ) -> Result<Option<Self>, IndexerError> {
+        if object.owner != Owner::ObjectOwner(registry_id.into()) {
+            return Ok(None);
+        }
+        let move_object = match object.data.try_as_move() {
+            Some(move_object) => move_object,
+            None => return Ok(None),
+        };
+        if !is_name_record_field(&move_object.type_().clone().into()) {
+            return Ok(None);
+        }
+        let field: Field<Domain, MoveNameRecord> = bcs::from_bytes(move_object.contents())
+            .map_err(|e| {
+                IndexerError::SerdeError(format!(
+                    "Failed deserializing name record {} with error: {e}",
+                    object.id()
+                ))
+            })?;
+        Ok(Some(Self {
+            field_id: object.id().to_string(),
+            name: field.name.to_string(),
+            nft_id: field.value.nft_id.bytes.to_string(),
+            target_address: field
+                .value
+                .target_address
+                .map(|address| address.to_string()),
+            expiration_timestamp_ms: field.value.expiration_timestamp_ms as i64,
+            version: object.version().value() as i64,
+            checkpoint_sequence_number: checkpoint as i64,
+        }))
+    }
+}
+
+// `dynamic_field::Field<domain::Domain, name_record::NameRecord>` of any package, as the
+// registry table only holds name records
+fn is_name_record_field(tag: &StructTag) -> bool {
+    DynamicFieldInfo::is_dynamic_field(tag)
+        && matches!(
+            tag.type_params.get(1),
+            Some(TypeTag::Struct(value))
+                if value.module.as_str() == NAME_RECORD_MODULE
+                    && value.name.as_str() == NAME_RECORD_STRUCT
+        )
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use move_core_types::language_storage::{StructTag, TypeTag};
+    use sui_types::base_types::{ObjectID, SequenceNumber, SuiAddress};
+    use sui_types::collection_types::VecMap;
+    use sui_types::digests::TransactionDigest;
+    use sui_types::dynamic_field::{DynamicFieldInfo, Field};
+    use sui_types::id::{ID, UID};
+    use sui_types::object::{MoveObject, Object, Owner};
+
+    use super::{Domain, MoveNameRecord, NameRecord};
+
+    fn name_record_object(owner: Owner, target_address: SuiAddress) -> Object {
+        let field = Field {
+            id: UID::new(ObjectID::random()),
+            name: Domain {
+                labels: vec!["sui".to_string(), "example".to_string()],
+            },
+            value: MoveNameRecord {
+                nft_id: ID::new(ObjectID::random()),
+                expiration_timestamp_ms: 1_700_000_000_000,
+                target_address: Some(target_address),
+                data: VecMap { contents: vec![] },
+            },
+        };
+        let type_ = DynamicFieldInfo::dynamic_field_type(
+            TypeTag::Struct(Box::new(
+                StructTag::from_str("0x42::domain::Domain").unwrap(),
+            )),
+            TypeTag::Struct(Box::new(
+                StructTag::from_str("0x42::name_record::NameRecord").unwrap(),
+            )),
+        );
+        // the type of dynamic fields has no public transfer
+        let move_object = unsafe {
+            MoveObject::new_from_execution_with_limit(
+                type_.into(),
+                false,
+                SequenceNumber::from_u64(3),
+                bcs::to_bytes(&field).unwrap(),
+                u64::MAX,
+            )
+            .unwrap()
+        };
+        Object::new_move(move_object, owner, TransactionDigest::random())
+    }
+
+    #[test]
+    fn test_name_record_from_object() {
+        let registry_id = ObjectID::random();
+        let target_address = SuiAddress::random_for_testing_only();
+
+        let object = name_record_object(Owner::ObjectOwner(registry_id.into()), target_address);
+        let record = NameRecord::try_from_object(&object, registry_id, 7)
+            .unwrap()
+            .unwrap();
+        assert_eq!(record.field_id, object.id().to_string());
+        assert_eq!(record.name, "example.sui");
+        assert_eq!(record.target_address, Some(target_address.to_string()));
+        assert_eq!(record.expiration_timestamp_ms, 1_700_000_000_000);
+        assert_eq!(record.version, 3);
+        assert_eq!(record.checkpoint_sequence_number, 7);
+
+        // fields of other tables
+        let object = name_record_object(
+            Owner::ObjectOwner(ObjectID::random().into()),
+            target_address,
+        );
+        assert_eq!(
+            NameRecord::try_from_object(&object, registry_id, 7).unwrap(),
+            None
+        );
+        let object = Object::new_gas_for_testing();
+        assert_eq!(
+            NameRecord::try_from_object(&object, registry_id, 7).unwrap(),
+            None
+        );
+    }
+}
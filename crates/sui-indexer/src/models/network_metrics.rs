@@ -26,6 +26,37 @@ pub struct DBNetworkMetrics {
     pub current_checkpoint: i64,
 }
 
+// names of the rows of the network_totals table
+pub const TOTAL_TRANSACTION_BLOCKS: &str = "transaction_blocks";
+pub const TOTAL_EVENTS: &str = "events";
+pub const TOTAL_PACKAGES: &str = "packages";
+pub const TOTAL_LIVE_OBJECTS: &str = "live_objects";
+
+/// Running totals of the network, maintained while committing checkpoints.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NetworkTotals {
+    pub total_transaction_blocks: i64,
+    pub total_events: i64,
+    pub total_packages: i64,
+    pub total_live_objects: i64,
+}
+
+impl NetworkTotals {
+    pub fn from_rows(rows: Vec<(String, i64)>) -> Self {
+        let mut totals = Self::default();
+        for (name, total) in rows {
+            match name.as_str() {
+                TOTAL_TRANSACTION_BLOCKS => totals.total_transaction_blocks = total,
+                TOTAL_EVENTS => totals.total_events = total,
+                TOTAL_PACKAGES => totals.total_packages = total,
+                TOTAL_LIVE_OBJECTS => totals.total_live_objects = total,
+                _ => {}
+            }
+        }
+        totals
+    }
+}
+
 #[derive(QueryableByName, Debug, Clone, Default)]
 pub struct DBMoveCallMetrics {
     #[diesel(sql_type = BigInt)]
@@ -0,0 +1,18 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use diesel::prelude::*;
+
+use crate::schema::object_type_stats;
+
+/// Number of objects of a struct type live at the end of an epoch, generic types are
+/// counted under their struct regardless of their type parameters.
+#[derive(Queryable, Insertable, Debug, Clone, PartialEq, Eq)]
+#[diesel(table_name = object_type_stats)]
+pub struct ObjectTypeStat {
+    pub epoch: i64,
+    pub package: String,
+    pub module: String,
+    pub name: String,
+    pub object_count: i64,
+}
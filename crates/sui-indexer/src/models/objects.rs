@@ -14,6 +14,7 @@ use diesel::sql_types::{Bytea, Nullable, Record, VarChar};
 use diesel::SqlType;
 use diesel_derive_enum::DbEnum;
 use fastcrypto::encoding::{Base64, Encoding};
+use fastcrypto::hash::{HashFunction, Sha256};
 use serde::{Deserialize, Serialize};
 use serde_json;
 use std::collections::hash_map::Entry;
@@ -29,6 +30,7 @@ use sui_types::{
 };
 
 use crate::errors::IndexerError;
+use crate::models::compression::{compress_payload, decompress_payload};
 use crate::models::owners::OwnerType;
 use crate::schema::objects;
 use crate::schema::sql_types::BcsBytes;
@@ -38,7 +40,9 @@ const OBJECT: &str = "object";
 // NOTE: please add updating statement like below in pg_indexer_store.rs,
 // if new columns are added here:
 // objects::epoch.eq(excluded(objects::epoch))
-#[derive(Queryable, Insertable, Debug, Identifiable, Clone, QueryableByName)]
+#[derive(
+    Queryable, Insertable, Debug, Identifiable, Clone, QueryableByName, Serialize, Deserialize,
+)]
 #[diesel(table_name = objects, primary_key(object_id))]
 pub struct Object {
     // epoch id in which this object got update.
@@ -59,8 +63,10 @@ pub struct Object {
     pub has_public_transfer: bool,
     pub storage_rebate: i64,
     pub bcs: Vec<NamedBcsBytes>,
+    // serialized size of the Move struct or of the modules of the package, 0 if deleted
+    pub object_size: i64,
 }
-#[derive(SqlType, Debug, Clone)]
+#[derive(SqlType, Debug, Clone, Serialize, Deserialize)]
 #[diesel(sql_type = crate::schema::sql_types::BcsBytes)]
 pub struct NamedBcsBytes(pub String, pub Vec<u8>);
 
@@ -77,7 +83,7 @@ impl FromSql<Nullable<BcsBytes>, Pg> for NamedBcsBytes {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DeletedObject {
     // epoch id in which this object got deleted.
     pub epoch: i64,
@@ -111,6 +117,7 @@ impl From<DeletedObject> for Object {
             has_public_transfer: o.has_public_transfer,
             storage_rebate: 0,
             bcs: vec![],
+            object_size: 0,
         }
     }
 }
@@ -172,10 +179,8 @@ impl Object {
             object_status: kind.into(),
             has_public_transfer,
             storage_rebate: object.storage_rebate as i64,
-            bcs: vec![NamedBcsBytes(
-                OBJECT.to_string(),
-                bcs::to_bytes(object).unwrap(),
-            )],
+            bcs: object_bcs(&object.data),
+            object_size: data_size(&object.data) as i64,
         }
     }
 
@@ -225,6 +230,10 @@ impl Object {
             object_status: *status,
             has_public_transfer,
             storage_rebate: o.storage_rebate.unwrap_or_default() as i64,
+            object_size: bcs
+                .iter()
+                .map(|NamedBcsBytes(_, bytes)| bytes.len() as i64)
+                .sum(),
             bcs,
         }
     }
@@ -246,6 +255,41 @@ impl Object {
         })
     }
 
+    /// Digest of the content of the object, i.e. of every column but its version and the
+    /// columns derived from it, which stays the same across mutations leaving the object as it
+    /// was, e.g. of shared objects only taken by mutable reference. `None` for packages and
+    /// rows without BCS.
+    pub fn content_digest(&self) -> Option<[u8; 32]> {
+        if !matches!(
+            ObjectType::from_str(&self.object_type),
+            Ok(ObjectType::Struct(_))
+        ) {
+            return None;
+        }
+        let NamedBcsBytes(_, bytes) = self.bcs.first()?;
+        let content = bcs::to_bytes(&(
+            &self.object_type,
+            self.has_public_transfer,
+            decompress_payload(bytes).ok()?.as_ref(),
+            &self.owner_type,
+            &self.owner_address,
+            self.initial_shared_version,
+            self.storage_rebate,
+        ))
+        .ok()?;
+        Some(Sha256::digest(content).digest)
+    }
+
+    /// Compresses the BCS of the object, read back transparently when converted to an object.
+    pub fn with_compressed_payload(mut self, level: i32) -> Self {
+        self.bcs = self
+            .bcs
+            .into_iter()
+            .map(|NamedBcsBytes(name, bytes)| NamedBcsBytes(name, compress_payload(bytes, level)))
+            .collect();
+        self
+    }
+
     pub fn get_object_ref(&self) -> Result<ObjectRef, IndexerError> {
         let object_id = self.object_id.parse()?;
         let digest = self.object_digest.parse().map_err(|e| {
@@ -265,17 +309,25 @@ impl TryFrom<Object> for sui_types::object::Object {
         let object_type = ObjectType::from_str(&o.object_type)?;
         let object_id = ObjectID::from_str(&o.object_id)?;
         let version = SequenceNumber::from_u64(o.version as u64);
+        let owner_address = || {
+            o.owner_address.as_deref().ok_or_else(|| {
+                IndexerError::PostgresReadError(format!(
+                    "Owner address of object {} should not be empty",
+                    o.object_id
+                ))
+            })
+        };
         let owner = match o.owner_type {
-            OwnerType::AddressOwner => Owner::AddressOwner(SuiAddress::from_str(
-                &o.owner_address.expect("Owner address should not be empty."),
-            )?),
-            OwnerType::ObjectOwner => Owner::ObjectOwner(SuiAddress::from_str(
-                &o.owner_address.expect("Owner address should not be empty."),
-            )?),
+            OwnerType::AddressOwner => Owner::AddressOwner(SuiAddress::from_str(owner_address()?)?),
+            OwnerType::ObjectOwner => Owner::ObjectOwner(SuiAddress::from_str(owner_address()?)?),
             OwnerType::Shared => Owner::Shared {
                 initial_shared_version: SequenceNumber::from_u64(
-                    o.initial_shared_version
-                        .expect("Shared version should not be empty.") as u64,
+                    o.initial_shared_version.ok_or_else(|| {
+                        IndexerError::PostgresReadError(format!(
+                            "Shared version of object {} should not be empty",
+                            o.object_id
+                        ))
+                    })? as u64,
                 ),
             },
             OwnerType::Immutable => Owner::Immutable,
@@ -287,8 +339,10 @@ impl TryFrom<Object> for sui_types::object::Object {
                 let modules = o
                     .bcs
                     .into_iter()
-                    .map(|NamedBcsBytes(name, bytes)| (name, bytes))
-                    .collect();
+                    .map(|NamedBcsBytes(name, bytes)| {
+                        Ok((name, decompress_payload(&bytes)?.into_owned()))
+                    })
+                    .collect::<Result<_, IndexerError>>()?;
                 // Ok to unwrap, package size is safe guarded by the full node, we are not limiting size when reading back from DB.
                 let package = MovePackage::new(
                     object_id,
@@ -312,12 +366,13 @@ impl TryFrom<Object> for sui_types::object::Object {
             }
             // Reconstructing MoveObject form database table, move VM safety concern is irrelevant here.
             ObjectType::Struct(object_type) => unsafe {
-                let content = o
-                    .bcs
-                    .first()
-                    .expect("BCS content should not be empty")
-                    .1
-                    .clone();
+                let NamedBcsBytes(_, bytes) = o.bcs.first().ok_or_else(|| {
+                    IndexerError::PostgresReadError(format!(
+                        "BCS of object {} version {} should not be empty",
+                        o.object_id, o.version
+                    ))
+                })?;
+                let content = decompress_payload(bytes)?.into_owned();
                 // Ok to unwrap, object size is safe guarded by the full node, we are not limiting size when reading back from DB.
                 let object = MoveObject::new_from_execution_with_limit(
                     object_type,
@@ -364,6 +419,33 @@ impl DeletedObject {
     }
 }
 
+// Move objects are stored as their contents and packages as their modules, without the
+// version and previous transaction, which have their own columns.
+fn object_bcs(data: &Data) -> Vec<NamedBcsBytes> {
+    match data {
+        Data::Move(object) => vec![NamedBcsBytes(
+            OBJECT.to_string(),
+            object.contents().to_vec(),
+        )],
+        Data::Package(package) => package
+            .serialized_module_map()
+            .iter()
+            .map(|(name, module)| NamedBcsBytes(name.clone(), module.clone()))
+            .collect(),
+    }
+}
+
+fn data_size(data: &Data) -> usize {
+    match data {
+        Data::Move(object) => object.contents().len(),
+        Data::Package(package) => package
+            .serialized_module_map()
+            .values()
+            .map(|module| module.len())
+            .sum(),
+    }
+}
+
 // return owner_type, owner_address and initial_shared_version
 pub fn owner_to_owner_info(owner: &Owner) -> (OwnerType, Option<String>, Option<i64>) {
     match owner {
@@ -380,14 +462,10 @@ pub fn owner_to_owner_info(owner: &Owner) -> (OwnerType, Option<String>, Option<
     }
 }
 
-pub fn compose_object_bulk_insert_update_query(objects: &[Object]) -> String {
-    let insert_query = compose_object_bulk_insert_query(objects)
-        .as_str()
-        .trim_matches(';')
-        .to_string();
-    let insert_update_query = format!(
-        "{} ON CONFLICT (object_id) 
-        DO UPDATE SET 
+// Only rows of newer versions than those written replace them, so that committing object
+// changes again, e.g. replaying a checkpoint, neither rolls objects back nor rewrites them.
+const OBJECT_UPSERT_CLAUSE: &str = "ON CONFLICT (object_id)
+        DO UPDATE SET
             epoch = EXCLUDED.epoch,
             checkpoint = EXCLUDED.checkpoint,
             version = EXCLUDED.version,
@@ -400,10 +478,34 @@ pub fn compose_object_bulk_insert_update_query(objects: &[Object]) -> String {
             object_status = EXCLUDED.object_status,
             has_public_transfer = EXCLUDED.has_public_transfer,
             storage_rebate = EXCLUDED.storage_rebate,
-            bcs = EXCLUDED.bcs;",
-        insert_query
-    );
-    insert_update_query
+            bcs = EXCLUDED.bcs,
+            object_size = EXCLUDED.object_size
+        WHERE objects.version < EXCLUDED.version";
+
+const OBJECT_COLUMNS: &str = "epoch, checkpoint, object_id, version, object_digest, owner_type, owner_address, initial_shared_version, previous_transaction, object_type, object_status, has_public_transfer, storage_rebate, bcs, object_size";
+
+/// Updates the version and the columns derived from it of the rows of objects whose content
+/// is unchanged since they were last written, returning the ids of the updated rows. Never
+/// inserts, so that rows absent from `objects` are left to be written in full. Rows already
+/// at the version are not updated either, as when a checkpoint is replayed.
+pub const UNCHANGED_OBJECTS_UPDATE_QUERY: &str = "UPDATE objects SET
+        epoch = u.epoch,
+        checkpoint = u.checkpoint,
+        version = u.version,
+        object_digest = u.object_digest,
+        previous_transaction = u.previous_transaction,
+        object_status = u.object_status::object_status
+    FROM UNNEST($1::VARCHAR[], $2::BIGINT[], $3::BIGINT[], $4::BIGINT[], $5::VARCHAR[], $6::VARCHAR[], $7::VARCHAR[])
+        AS u(object_id, epoch, checkpoint, version, object_digest, previous_transaction, object_status)
+    WHERE objects.object_id = u.object_id AND objects.version < u.version
+    RETURNING objects.object_id";
+
+pub fn compose_object_bulk_insert_update_query(objects: &[Object]) -> String {
+    let insert_query = compose_object_bulk_insert_query(objects)
+        .as_str()
+        .trim_matches(';')
+        .to_string();
+    format!("{} {};", insert_query, OBJECT_UPSERT_CLAUSE)
 }
 
 pub fn compose_object_bulk_insert_query(objects: &[Object]) -> String {
@@ -439,6 +541,7 @@ pub fn compose_object_bulk_insert_query(objects: &[Object]) -> String {
                 obj.has_public_transfer,
                 obj.storage_rebate,
                 bcs_rows,
+                obj.object_size,
             )
         })
         .collect::<Vec<_>>();
@@ -446,7 +549,7 @@ pub fn compose_object_bulk_insert_query(objects: &[Object]) -> String {
     let rows_query = rows
         .iter()
         .map(|row| {
-            let (epoch, checkpoint, object_id, version, object_digest, owner_type, owner_address, initial_shared_version, previous_transaction, object_type, object_status, has_public_transfer, storage_rebate, bcs_rows) = row;
+            let (epoch, checkpoint, object_id, version, object_digest, owner_type, owner_address, initial_shared_version, previous_transaction, object_type, object_status, has_public_transfer, storage_rebate, bcs_rows, object_size) = row;
 
             let bcs_rows_query = bcs_rows
                 .iter()
@@ -464,7 +567,7 @@ pub fn compose_object_bulk_insert_query(objects: &[Object]) -> String {
             format!(
                 "ROW({}::BIGINT, {}::BIGINT, '{}'::address, {}::BIGINT, '{}'::base58digest, '{}'::owner_type, 
                      '{}'::address, {}::BIGINT, '{}'::base58digest, '{}'::VARCHAR, '{}'::object_status,
-                     {}::BOOLEAN, {}::BIGINT, ARRAY[{}]::bcs_bytes[])",
+                     {}::BOOLEAN, {}::BIGINT, ARRAY[{}]::bcs_bytes[], {}::BIGINT)",
                 epoch,
                 checkpoint,
                 object_id,
@@ -485,6 +588,7 @@ pub fn compose_object_bulk_insert_query(objects: &[Object]) -> String {
                 has_public_transfer,
                 storage_rebate,
                 bcs_rows_query,
+                object_size,
             )
         })
         .collect::<Vec<_>>()
@@ -493,11 +597,11 @@ pub fn compose_object_bulk_insert_query(objects: &[Object]) -> String {
     // Construct a prepared statement with placeholders for each row element
     let bulk_insert_query = format!(
         "INSERT INTO objects
-            (epoch, checkpoint, object_id, version, object_digest, owner_type, owner_address, initial_shared_version, previous_transaction, object_type, object_status, has_public_transfer, storage_rebate, bcs)
+            ({})
         SELECT (unnest_arr).*
         FROM unnest(ARRAY[{}]::record[]) 
-        AS unnest_arr(epoch BIGINT, checkpoint BIGINT, object_id address, version BIGINT, object_digest base58digest, owner_type owner_type, owner_address address, initial_shared_version BIGINT, previous_transaction base58digest, object_type VARCHAR, object_status object_status, has_public_transfer BOOLEAN, storage_rebate BIGINT, bcs bcs_bytes[]);",
-        rows_query
+        AS unnest_arr(epoch BIGINT, checkpoint BIGINT, object_id address, version BIGINT, object_digest base58digest, owner_type owner_type, owner_address address, initial_shared_version BIGINT, previous_transaction base58digest, object_type VARCHAR, object_status object_status, has_public_transfer BOOLEAN, storage_rebate BIGINT, bcs bcs_bytes[], object_size BIGINT);",
+        OBJECT_COLUMNS, rows_query
     );
     bulk_insert_query
 }
@@ -522,3 +626,52 @@ pub fn filter_latest_objects(objects: Vec<Object>) -> Vec<Object> {
     }
     latest_objects.into_values().collect()
 }
+
+#[cfg(test)]
+mod test {
+    use sui_types::base_types::{ObjectID, SequenceNumber, SuiAddress};
+    use sui_types::storage::WriteKind;
+
+    use super::Object;
+
+    #[test]
+    fn test_content_digest() {
+        let id = ObjectID::random();
+        let owner = SuiAddress::random_for_testing_only();
+        let object = |version, owner| {
+            let object = sui_types::object::Object::with_id_owner_version_for_testing(
+                id,
+                SequenceNumber::from_u64(version),
+                owner,
+            );
+            Object::new(0, 0, WriteKind::Mutate, &object)
+        };
+        let digest = object(1, owner).content_digest();
+        assert!(digest.is_some());
+        // the stored bytes do not depend on the version
+        let bytes = |object: Object| object.bcs.into_iter().map(|b| b.1).collect::<Vec<_>>();
+        assert_eq!(bytes(object(1, owner)), bytes(object(2, owner)));
+        // only the version changed
+        assert_eq!(object(2, owner).content_digest(), digest);
+        // transferred
+        let transferred = SuiAddress::random_for_testing_only();
+        assert_ne!(object(2, transferred).content_digest(), digest);
+        // read back as it was written
+        let written = sui_types::object::Object::with_id_owner_version_for_testing(
+            id,
+            SequenceNumber::from_u64(3),
+            owner,
+        );
+        let read: sui_types::object::Object = Object::new(0, 0, WriteKind::Mutate, &written)
+            .try_into()
+            .unwrap();
+        assert_eq!(written, read);
+        // rows without BCS are an error rather than a panic
+        let empty = Object {
+            bcs: vec![],
+            ..object(3, owner)
+        };
+        assert!(empty.content_digest().is_none());
+        assert!(sui_types::object::Object::try_from(empty).is_err());
+    }
+}
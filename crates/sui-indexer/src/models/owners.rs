@@ -19,7 +19,7 @@ pub struct ObjectOwner {
     pub object_status: ObjectStatus,
 }
 
-#[derive(DbEnum, Debug, Clone, Deserialize, Serialize)]
+#[derive(DbEnum, Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 #[ExistingTypePath = "crate::schema::sql_types::OwnerType"]
 #[serde(rename_all = "snake_case")]
 pub enum OwnerType {
@@ -0,0 +1,53 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::str::FromStr;
+
+use diesel::{Insertable, Queryable};
+use serde::{Deserialize, Serialize};
+
+use sui_json_rpc_types::ObjectOwnershipTransition;
+use sui_types::base_types::SuiAddress;
+
+use crate::errors::IndexerError;
+use crate::schema::object_ownership_history;
+
+/// A non-coin object passed to an address by a transaction, see
+/// `index_ownership_transitions`.
+#[derive(Queryable, Insertable, Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[diesel(table_name = object_ownership_history)]
+pub struct OwnershipTransition {
+    #[diesel(deserialize_as = i64)]
+    pub id: Option<i64>,
+    pub object_id: String,
+    pub object_version: i64,
+    // None if the object was not owned by an address before the transaction
+    pub from_owner: Option<String>,
+    pub to_owner: String,
+    pub transaction_digest: String,
+    pub checkpoint_sequence_number: i64,
+}
+
+impl TryFrom<OwnershipTransition> for ObjectOwnershipTransition {
+    type Error = IndexerError;
+
+    fn try_from(transition: OwnershipTransition) -> Result<Self, Self::Error> {
+        let transaction_digest = transition.transaction_digest.parse().map_err(|e| {
+            IndexerError::SerdeError(format!(
+                "Failed to parse transaction digest {} : {:?}",
+                transition.transaction_digest, e
+            ))
+        })?;
+        Ok(Self {
+            object_version: transition.object_version as u64,
+            from_owner: transition
+                .from_owner
+                .as_deref()
+                .map(SuiAddress::from_str)
+                .transpose()?,
+            to_owner: SuiAddress::from_str(&transition.to_owner)?,
+            transaction_digest,
+            checkpoint: transition.checkpoint_sequence_number as u64,
+        })
+    }
+}
@@ -2,10 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::errors::IndexerError;
-use crate::schema::packages;
+use crate::schema::{module_disassemblies, packages};
 
 use diesel::prelude::*;
-use sui_types::move_package::MovePackage;
+use sui_types::move_package::{disassemble_modules, MovePackage};
 
 use crate::models::objects::NamedBcsBytes;
 use sui_json_rpc_types::SuiRawMovePackage;
@@ -49,3 +49,42 @@ impl Package {
         })
     }
 }
+
+#[derive(Queryable, Insertable, Clone, Debug)]
+#[diesel(table_name = module_disassemblies)]
+pub struct ModuleDisassembly {
+    pub package_id: String,
+    pub version: i64,
+    pub module_name: String,
+    pub disassembly: String,
+}
+
+impl ModuleDisassembly {
+    pub fn try_from_package(package: &Package, module_name: &str) -> Result<Self, IndexerError> {
+        let module = package
+            .data
+            .iter()
+            .find(|m| m.0 == module_name)
+            .ok_or_else(|| {
+                IndexerError::InvalidArgumentError(format!(
+                    "Module {module_name} not found in package {}",
+                    package.package_id
+                ))
+            })?;
+        let disassembly = disassemble_modules(std::iter::once(&module.1))?
+            .remove(module_name)
+            .and_then(|v| v.as_str().map(|s| s.to_owned()))
+            .ok_or_else(|| {
+                IndexerError::SerdeError(format!(
+                    "Failed disassembling module {module_name} of package {}",
+                    package.package_id
+                ))
+            })?;
+        Ok(Self {
+            package_id: package.package_id.clone(),
+            version: package.version,
+            module_name: module_name.to_owned(),
+            disassembly,
+        })
+    }
+}
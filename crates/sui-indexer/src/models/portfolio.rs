@@ -0,0 +1,98 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::btree_map::Entry;
+use std::collections::BTreeMap;
+
+use diesel::sql_types::{Array, Nullable, Text};
+use diesel::QueryableByName;
+use serde::{Deserialize, Serialize};
+
+use sui_types::coin::{Coin, CoinMetadata};
+
+use crate::errors::IndexerError;
+use crate::models::compression::decompress_payload;
+use crate::models::objects::NamedBcsBytes;
+use crate::schema::sql_types::BcsBytes;
+
+// A coin object owned by the queried address, joined with the CoinMetadata of its coin type.
+#[derive(QueryableByName, Debug, Clone)]
+pub struct DBCoinWithMetadata {
+    #[diesel(sql_type = Text)]
+    pub coin_type: String,
+    #[diesel(sql_type = Array<Nullable<BcsBytes>>)]
+    pub bcs: Vec<NamedBcsBytes>,
+    #[diesel(sql_type = Nullable<Array<Nullable<BcsBytes>>>)]
+    pub metadata_bcs: Option<Vec<NamedBcsBytes>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CoinPortfolioEntry {
+    pub coin_type: String,
+    pub coin_object_count: usize,
+    pub total_balance: u128,
+    // None if the CoinMetadata of the coin type is not indexed.
+    pub decimals: Option<u8>,
+    pub symbol: Option<String>,
+    // total_balance with decimals applied, for example "1.500000000" for 1.5 SUI,
+    // same as total_balance if decimals is unknown.
+    pub balance: String,
+}
+
+pub fn compose_portfolio(
+    coins: Vec<DBCoinWithMetadata>,
+) -> Result<Vec<CoinPortfolioEntry>, IndexerError> {
+    let mut portfolio: BTreeMap<String, CoinPortfolioEntry> = BTreeMap::new();
+    for coin in coins {
+        let coin_bcs = coin.bcs.first().ok_or_else(|| {
+            IndexerError::SerdeError(format!("Coin of type {} has empty BCS", coin.coin_type))
+        })?;
+        let value = Coin::from_bcs_bytes(&decompress_payload(&coin_bcs.1)?)?.value();
+        let entry = match portfolio.entry(coin.coin_type.clone()) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let metadata = coin
+                    .metadata_bcs
+                    .as_ref()
+                    .and_then(|bcs| bcs.first())
+                    .map(|bcs| {
+                        Ok::<_, IndexerError>(CoinMetadata::from_bcs_bytes(&decompress_payload(
+                            &bcs.1,
+                        )?)?)
+                    })
+                    .transpose()?;
+                entry.insert(CoinPortfolioEntry {
+                    coin_type: coin.coin_type,
+                    coin_object_count: 0,
+                    total_balance: 0,
+                    decimals: metadata.as_ref().map(|m| m.decimals),
+                    symbol: metadata.map(|m| m.symbol),
+                    balance: String::new(),
+                })
+            }
+        };
+        entry.coin_object_count += 1;
+        entry.total_balance += value as u128;
+    }
+
+    Ok(portfolio
+        .into_values()
+        .map(|mut entry| {
+            entry.balance = apply_decimals(entry.total_balance, entry.decimals.unwrap_or(0));
+            entry
+        })
+        .collect())
+}
+
+fn apply_decimals(amount: u128, decimals: u8) -> String {
+    match 10u128.checked_pow(decimals as u32) {
+        Some(scale) if decimals > 0 => format!(
+            "{}.{:0width$}",
+            amount / scale,
+            amount % scale,
+            width = decimals as usize
+        ),
+        _ => amount.to_string(),
+    }
+}
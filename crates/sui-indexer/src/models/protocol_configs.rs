@@ -0,0 +1,68 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use diesel::{Insertable, Queryable};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use sui_json_rpc_types::ProtocolConfigResponse;
+use sui_protocol_config::{Chain, ProtocolConfig, ProtocolVersion};
+use sui_types::base_types::EpochId;
+
+use crate::errors::IndexerError;
+use crate::schema::protocol_configs;
+
+#[derive(Queryable, Insertable, Debug, Clone, Serialize, Deserialize)]
+#[diesel(table_name = protocol_configs)]
+pub struct DBProtocolConfig {
+    pub epoch: i64,
+    pub protocol_version: i64,
+    pub protocol_config: Option<String>,
+}
+
+impl DBProtocolConfig {
+    pub fn new(epoch: EpochId, protocol_version: u64, chain: Chain) -> Result<Self, IndexerError> {
+        let config = ProtocolConfig::get_for_version_if_supported(
+            ProtocolVersion::new(protocol_version),
+            chain,
+        );
+        if config.is_none() {
+            warn!(
+                "Protocol version {} of epoch {} is not supported by this indexer, only the version is recorded.",
+                protocol_version, epoch
+            );
+        }
+        let protocol_config = config
+            .map(|config| serde_json::to_string(&ProtocolConfigResponse::from(config)))
+            .transpose()
+            .map_err(|e| {
+                IndexerError::SerdeError(format!(
+                    "Failed serializing protocol config of version {protocol_version} with error: {e:?}"
+                ))
+            })?;
+        Ok(Self {
+            epoch: epoch as i64,
+            protocol_version: protocol_version as i64,
+            protocol_config,
+        })
+    }
+}
+
+impl TryFrom<DBProtocolConfig> for ProtocolConfigResponse {
+    type Error = IndexerError;
+
+    fn try_from(config: DBProtocolConfig) -> Result<Self, Self::Error> {
+        let protocol_config = config.protocol_config.ok_or_else(|| {
+            IndexerError::NotSupportedError(format!(
+                "Protocol config of version {} for epoch {} was not recorded by the indexer",
+                config.protocol_version, config.epoch
+            ))
+        })?;
+        serde_json::from_str(&protocol_config).map_err(|e| {
+            IndexerError::SerdeError(format!(
+                "Failed deserializing protocol config of epoch {} with error: {e:?}",
+                config.epoch
+            ))
+        })
+    }
+}
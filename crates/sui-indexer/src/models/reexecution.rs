@@ -0,0 +1,19 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use diesel::prelude::*;
+
+use crate::schema::reexecution_mismatches;
+
+/// Sampled transaction whose effects, re-executed locally against the object history,
+/// differ from the effects of its checkpoint.
+#[derive(Queryable, Insertable, Debug, Clone, PartialEq, Eq)]
+#[diesel(table_name = reexecution_mismatches)]
+pub struct ReexecutionMismatch {
+    pub transaction_digest: String,
+    pub checkpoint_sequence_number: i64,
+    pub expected_effects_digest: String,
+    pub local_effects_digest: String,
+    pub local_error: Option<String>,
+    pub reexecuted_at_ms: i64,
+}
@@ -0,0 +1,199 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use crate::errors::IndexerError;
+
+/// Retentions shorter than this would prune the previous epoch, which the RPC APIs keep
+/// serving right after an epoch change.
+pub const MIN_RETENTION_EPOCHS: u64 = 2;
+
+/// Families of tables pruned together by checkpoint.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PrunedTable {
+    Events,
+    /// transactions, transactions_json, checkpoint_tx_digests and checkpoint_proofs
+    Transactions,
+    /// input_objects, changed_objects, move_calls, tx_commands, tx_calls, recipients and
+    /// tx_dependencies
+    TxIndices,
+    Transfers,
+    ObjectsHistory,
+}
+
+impl PrunedTable {
+    pub const ALL: [PrunedTable; 5] = [
+        PrunedTable::Events,
+        PrunedTable::Transactions,
+        PrunedTable::TxIndices,
+        PrunedTable::Transfers,
+        PrunedTable::ObjectsHistory,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            PrunedTable::Events => "events",
+            PrunedTable::Transactions => "transactions",
+            PrunedTable::TxIndices => "tx_indices",
+            PrunedTable::Transfers => "transfers",
+            PrunedTable::ObjectsHistory => "objects_history",
+        }
+    }
+
+    /// Whether rows are found by the transactions of the pruned checkpoints, in which case
+    /// they must be pruned before the transactions themselves.
+    pub fn is_pruned_through_transactions(&self) -> bool {
+        matches!(self, PrunedTable::TxIndices | PrunedTable::Transfers)
+    }
+}
+
+impl FromStr for PrunedTable {
+    type Err = IndexerError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|table| table.name() == s)
+            .ok_or_else(|| {
+                IndexerError::InvalidArgumentError(format!(
+                    "Unknown pruned table {s}, expected one of {:?}",
+                    Self::ALL.map(|table| table.name())
+                ))
+            })
+    }
+}
+
+/// Data of the last `epochs` epochs, the current one included, is kept in `table`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    pub table: PrunedTable,
+    pub epochs: u64,
+}
+
+impl FromStr for RetentionPolicy {
+    type Err = IndexerError;
+
+    /// Parses `<table>=<epochs>`, e.g. `events=30`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (table, epochs) = s.split_once('=').ok_or_else(|| {
+            IndexerError::InvalidArgumentError(format!(
+                "Invalid retention policy {s}, expected <table>=<epochs>"
+            ))
+        })?;
+        let epochs = epochs.parse::<u64>().map_err(|e| {
+            IndexerError::InvalidArgumentError(format!(
+                "Invalid retention epochs in policy {s}: {e}"
+            ))
+        })?;
+        Ok(Self {
+            table: PrunedTable::from_str(table)?,
+            epochs,
+        })
+    }
+}
+
+/// Parses and checks the retention policies, tables without a policy are kept forever.
+/// Policies are returned in pruning order, tables pruned through transactions first.
+pub fn parse_retention_policies(policies: &[String]) -> Result<Vec<RetentionPolicy>, IndexerError> {
+    let mut retentions = HashMap::new();
+    for policy in policies {
+        let policy = RetentionPolicy::from_str(policy)?;
+        if policy.epochs < MIN_RETENTION_EPOCHS {
+            return Err(IndexerError::InvalidArgumentError(format!(
+                "Retention of {} is {} epochs, at least {MIN_RETENTION_EPOCHS} are required",
+                policy.table.name(),
+                policy.epochs
+            )));
+        }
+        if retentions.insert(policy.table, policy.epochs).is_some() {
+            return Err(IndexerError::InvalidArgumentError(format!(
+                "Multiple retention policies for {}",
+                policy.table.name()
+            )));
+        }
+    }
+    // rows left behind by the transactions they are found by could never be pruned
+    if let Some(transactions_epochs) = retentions.get(&PrunedTable::Transactions) {
+        for table in PrunedTable::ALL
+            .into_iter()
+            .filter(PrunedTable::is_pruned_through_transactions)
+        {
+            if retentions
+                .get(&table)
+                .map_or(true, |epochs| epochs > transactions_epochs)
+            {
+                return Err(IndexerError::InvalidArgumentError(format!(
+                    "{} must not be retained longer than transactions",
+                    table.name()
+                )));
+            }
+        }
+    }
+    let mut policies = retentions
+        .into_iter()
+        .map(|(table, epochs)| RetentionPolicy { table, epochs })
+        .collect::<Vec<_>>();
+    policies.sort_by_key(|policy| {
+        (
+            !policy.table.is_pruned_through_transactions(),
+            policy.table.name(),
+        )
+    });
+    Ok(policies)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_retention_policies, PrunedTable, RetentionPolicy};
+
+    fn parse(policies: &[&str]) -> Result<Vec<RetentionPolicy>, String> {
+        parse_retention_policies(&policies.iter().map(|p| p.to_string()).collect::<Vec<_>>())
+            .map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn test_parse_retention_policies() {
+        let err = parse(&["transactions=60", "objects_history=7", "events=30"]).unwrap_err();
+        // tx_indices and transfers would be kept forever
+        assert!(err.contains("must not be retained longer than transactions"));
+
+        let policies = parse(&[
+            "objects_history=7",
+            "transactions=60",
+            "events=30",
+            "tx_indices=60",
+            "transfers=60",
+        ])
+        .unwrap();
+        assert_eq!(
+            policies
+                .iter()
+                .map(|policy| (policy.table, policy.epochs))
+                .collect::<Vec<_>>(),
+            vec![
+                (PrunedTable::Transfers, 60),
+                (PrunedTable::TxIndices, 60),
+                (PrunedTable::Events, 30),
+                (PrunedTable::ObjectsHistory, 7),
+                (PrunedTable::Transactions, 60),
+            ]
+        );
+
+        // events are pruned by the transaction numbers of the checkpoints, which outlive them
+        assert!(parse(&[
+            "transactions=30",
+            "events=60",
+            "tx_indices=30",
+            "transfers=30"
+        ])
+        .is_ok());
+
+        assert!(parse(&["events=1"]).is_err());
+        assert!(parse(&["events=30", "events=7"]).is_err());
+        assert!(parse(&["events"]).is_err());
+        assert!(parse(&["objects=7"]).is_err());
+        assert!(parse(&[]).unwrap().is_empty());
+    }
+}
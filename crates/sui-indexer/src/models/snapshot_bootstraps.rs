@@ -0,0 +1,17 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use diesel::prelude::*;
+
+use crate::schema::snapshot_bootstraps;
+
+/// A formal snapshot the objects table was bootstrapped from, recorded once all its objects
+/// are committed.
+#[derive(Queryable, Insertable, Debug, Clone, PartialEq, Eq)]
+#[diesel(table_name = snapshot_bootstraps)]
+pub struct SnapshotBootstrap {
+    pub epoch: i64,
+    pub checkpoint_sequence_number: i64,
+    pub object_count: i64,
+    pub package_count: i64,
+}
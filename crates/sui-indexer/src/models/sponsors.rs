@@ -0,0 +1,125 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{BTreeMap, HashMap};
+
+use diesel::prelude::*;
+
+use sui_json_rpc_types::EpochGasSpend;
+
+use crate::models::transactions::Transaction;
+use crate::schema::sponsor_gas_spend;
+
+/// Gas spent by a sponsor on the transactions of other senders within an epoch.
+#[derive(Queryable, Insertable, Debug, Clone, PartialEq, Eq)]
+#[diesel(table_name = sponsor_gas_spend)]
+pub struct SponsorGasSpend {
+    pub sponsor: String,
+    pub epoch: i64,
+    pub transaction_count: i64,
+    pub total_gas_cost: i64,
+    pub computation_cost: i64,
+    pub storage_cost: i64,
+    pub storage_rebate: i64,
+}
+
+impl SponsorGasSpend {
+    /// Spend of the sponsored ones of `transactions` per sponsor and epoch, with the epochs
+    /// of their checkpoints. Transactions of unknown checkpoints are not counted.
+    pub fn from_transactions(
+        transactions: &[Transaction],
+        checkpoint_epochs: &HashMap<i64, i64>,
+    ) -> Vec<SponsorGasSpend> {
+        let mut spend = BTreeMap::new();
+        for transaction in transactions.iter().filter(|tx| tx.sponsored) {
+            let epoch = match transaction
+                .checkpoint_sequence_number
+                .and_then(|checkpoint| checkpoint_epochs.get(&checkpoint))
+            {
+                Some(epoch) => *epoch,
+                None => continue,
+            };
+            let sponsor = match &transaction.gas_owner {
+                Some(sponsor) => sponsor,
+                None => continue,
+            };
+            let entry = spend
+                .entry((sponsor.clone(), epoch))
+                .or_insert_with(|| SponsorGasSpend {
+                    sponsor: sponsor.clone(),
+                    epoch,
+                    transaction_count: 0,
+                    total_gas_cost: 0,
+                    computation_cost: 0,
+                    storage_cost: 0,
+                    storage_rebate: 0,
+                });
+            entry.transaction_count += 1;
+            entry.total_gas_cost += transaction.total_gas_cost;
+            entry.computation_cost += transaction.computation_cost;
+            entry.storage_cost += transaction.storage_cost;
+            entry.storage_rebate += transaction.storage_rebate;
+        }
+        spend.into_values().collect()
+    }
+}
+
+impl From<SponsorGasSpend> for EpochGasSpend {
+    fn from(spend: SponsorGasSpend) -> Self {
+        Self {
+            epoch: spend.epoch as u64,
+            transaction_count: spend.transaction_count as u64,
+            total_gas_cost: spend.total_gas_cost,
+            computation_cost: spend.computation_cost as u64,
+            storage_cost: spend.storage_cost as u64,
+            storage_rebate: spend.storage_rebate as u64,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use crate::models::transactions::Transaction;
+
+    use super::SponsorGasSpend;
+
+    #[test]
+    fn test_sponsor_gas_spend() {
+        let transaction = |sender: &str, gas_owner: &str, checkpoint: i64, cost: i64| Transaction {
+            sender: sender.to_string(),
+            gas_owner: Some(gas_owner.to_string()),
+            sponsored: sender != gas_owner,
+            checkpoint_sequence_number: Some(checkpoint),
+            total_gas_cost: cost,
+            computation_cost: cost,
+            ..Default::default()
+        };
+        let checkpoint_epochs = HashMap::from([(1, 0), (2, 0), (3, 1)]);
+        let spend = SponsorGasSpend::from_transactions(
+            &[
+                transaction("0xa", "0xs", 1, 10),
+                transaction("0xb", "0xs", 2, 20),
+                transaction("0xa", "0xs", 3, 5),
+                // paid by the sender
+                transaction("0xs", "0xs", 3, 100),
+                // unknown checkpoint
+                transaction("0xa", "0xs", 4, 100),
+            ],
+            &checkpoint_epochs,
+        );
+        assert_eq!(
+            spend
+                .iter()
+                .map(|s| (
+                    s.sponsor.as_str(),
+                    s.epoch,
+                    s.transaction_count,
+                    s.total_gas_cost
+                ))
+                .collect::<Vec<_>>(),
+            vec![("0xs", 0, 2, 30), ("0xs", 1, 1, 5)]
+        );
+    }
+}
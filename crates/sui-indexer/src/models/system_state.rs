@@ -1,9 +1,11 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::HashMap;
 use std::str::FromStr;
 
 use diesel::{Insertable, Queryable};
+use serde::{Deserialize, Serialize};
 
 use sui_types::base_types::{EpochId, ObjectID, SuiAddress};
 use sui_types::sui_system_state::sui_system_state_summary::{
@@ -11,9 +13,9 @@ use sui_types::sui_system_state::sui_system_state_summary::{
 };
 
 use crate::errors::IndexerError;
-use crate::schema::{at_risk_validators, system_states, validators};
+use crate::schema::{at_risk_validators, system_states, validator_epoch_events, validators};
 
-#[derive(Queryable, Insertable, Debug, Clone, Default)]
+#[derive(Queryable, Insertable, Debug, Clone, Default, Serialize, Deserialize)]
 #[diesel(table_name = system_states)]
 pub struct DBSystemStateSummary {
     pub epoch: i64,
@@ -38,6 +40,7 @@ pub struct DBSystemStateSummary {
     pub inactive_pools_size: i64,
     pub validator_candidates_id: String,
     pub validator_candidates_size: i64,
+    pub summary: Option<serde_json::Value>,
 }
 
 impl From<SuiSystemStateSummary> for DBSystemStateSummary {
@@ -66,11 +69,12 @@ impl From<SuiSystemStateSummary> for DBSystemStateSummary {
             inactive_pools_size: s.inactive_pools_size as i64,
             validator_candidates_id: s.validator_candidates_id.to_string(),
             validator_candidates_size: s.validator_candidates_size as i64,
+            summary: serde_json::to_value(&s).ok(),
         }
     }
 }
 
-#[derive(Queryable, Insertable, Debug, Clone, Default)]
+#[derive(Queryable, Insertable, Debug, Clone, Default, Serialize, Deserialize)]
 #[diesel(table_name = validators)]
 pub struct DBValidatorSummary {
     pub epoch: i64,
@@ -217,3 +221,73 @@ pub struct DBAtRiskValidator {
     pub epoch_count: i64,
     pub reported_by: Vec<String>,
 }
+
+#[derive(Queryable, Insertable, Debug, Clone, Default, Serialize, Deserialize)]
+#[diesel(table_name = validator_epoch_events)]
+pub struct DBValidatorEpochEvent {
+    pub epoch: i64,
+    pub validator_address: String,
+    pub reported_by: Vec<Option<String>>,
+    pub report_count: i64,
+    pub stake: i64,
+    pub stake_delta: i64,
+}
+
+impl DBValidatorEpochEvent {
+    // stake_delta is set to the full stake here and adjusted against the
+    // previous epoch when persisted.
+    pub fn from_system_state(s: &SuiSystemStateSummary) -> Vec<Self> {
+        let reports: HashMap<_, _> = s
+            .validator_report_records
+            .iter()
+            .map(|(reportee, reporters)| (*reportee, reporters))
+            .collect();
+        s.active_validators
+            .iter()
+            .map(|v| {
+                let reported_by: Vec<Option<String>> = reports
+                    .get(&v.sui_address)
+                    .map(|reporters| reporters.iter().map(|r| Some(r.to_string())).collect())
+                    .unwrap_or_default();
+                Self {
+                    epoch: s.epoch as i64,
+                    validator_address: v.sui_address.to_string(),
+                    report_count: reported_by.len() as i64,
+                    reported_by,
+                    stake: v.staking_pool_sui_balance as i64,
+                    stake_delta: v.staking_pool_sui_balance as i64,
+                }
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatorEpochEvent {
+    pub epoch: EpochId,
+    pub validator_address: SuiAddress,
+    pub reported_by: Vec<SuiAddress>,
+    pub report_count: u64,
+    pub stake: u64,
+    pub stake_delta: i64,
+}
+
+impl TryFrom<DBValidatorEpochEvent> for ValidatorEpochEvent {
+    type Error = IndexerError;
+    fn try_from(db: DBValidatorEpochEvent) -> Result<ValidatorEpochEvent, Self::Error> {
+        Ok(ValidatorEpochEvent {
+            epoch: db.epoch as u64,
+            validator_address: SuiAddress::from_str(&db.validator_address)?,
+            reported_by: db
+                .reported_by
+                .into_iter()
+                .flatten()
+                .map(|r| SuiAddress::from_str(&r))
+                .collect::<Result<_, _>>()?,
+            report_count: db.report_count as u64,
+            stake: db.stake as u64,
+            stake_delta: db.stake_delta,
+        })
+    }
+}
@@ -0,0 +1,17 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use diesel::sql_types::{BigInt, Text};
+use diesel::QueryableByName;
+
+/// Size on disk of a table of the indexer, with its indexes and TOAST data, and the row
+/// count estimated by the planner statistics. Partitions count towards their parent table.
+#[derive(QueryableByName, Debug, Clone, PartialEq, Eq)]
+pub struct TableSize {
+    #[diesel(sql_type = Text)]
+    pub table_name: String,
+    #[diesel(sql_type = BigInt)]
+    pub total_bytes: i64,
+    #[diesel(sql_type = BigInt)]
+    pub estimated_rows: i64,
+}
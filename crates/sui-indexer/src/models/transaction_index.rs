@@ -1,10 +1,17 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::schema::{changed_objects, input_objects, move_calls, recipients};
+use std::fmt::Display;
+
+use crate::schema::{
+    changed_objects, input_objects, move_calls, recipients, tx_commands, tx_dependencies,
+};
 use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Text};
+use serde::{Deserialize, Serialize};
+use sui_types::transaction::Command;
 
-#[derive(Queryable, Insertable, Debug, Clone, Default)]
+#[derive(Queryable, Insertable, Debug, Clone, Default, Serialize, Deserialize)]
 #[diesel(table_name = input_objects)]
 pub struct InputObject {
     pub id: Option<i64>,
@@ -15,7 +22,7 @@ pub struct InputObject {
     pub object_version: Option<i64>,
 }
 
-#[derive(Queryable, Insertable, Debug, Clone, Default)]
+#[derive(Queryable, Insertable, Debug, Clone, Default, Serialize, Deserialize)]
 #[diesel(table_name = move_calls)]
 pub struct MoveCall {
     pub id: Option<i64>,
@@ -28,7 +35,7 @@ pub struct MoveCall {
     pub move_function: String,
 }
 
-#[derive(Queryable, Insertable, Debug, Clone, Default)]
+#[derive(Queryable, Insertable, Debug, Clone, Default, Serialize, Deserialize)]
 #[diesel(table_name = recipients)]
 pub struct Recipient {
     pub id: Option<i64>,
@@ -39,7 +46,7 @@ pub struct Recipient {
     pub recipient: String,
 }
 
-#[derive(Queryable, Insertable, Debug, Clone, Default)]
+#[derive(Queryable, Insertable, Debug, Clone, Default, Serialize, Deserialize)]
 #[diesel(table_name = changed_objects)]
 pub struct ChangedObject {
     pub id: Option<i64>,
@@ -51,3 +58,207 @@ pub struct ChangedObject {
     pub object_change_type: String,
     pub object_version: i64,
 }
+
+// A command of a programmable transaction, with its arguments summarized as in
+// `Argument`'s Display, e.g. `[Input(0), Result(1)] to Input(2)`.
+#[derive(Queryable, Insertable, Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[diesel(table_name = tx_commands)]
+pub struct TxCommand {
+    pub transaction_digest: String,
+    pub command_index: i64,
+    pub checkpoint_sequence_number: i64,
+    pub epoch: i64,
+    pub command_kind: String,
+    pub package: Option<String>,
+    pub module: Option<String>,
+    pub function: Option<String>,
+    pub type_arguments: Option<String>,
+    pub arguments: String,
+}
+
+impl TxCommand {
+    pub fn new(
+        transaction_digest: String,
+        command_index: usize,
+        checkpoint_sequence_number: i64,
+        epoch: i64,
+        command: &Command,
+    ) -> Self {
+        let tx_command = Self {
+            transaction_digest,
+            command_index: command_index as i64,
+            checkpoint_sequence_number,
+            epoch,
+            ..Default::default()
+        };
+        match command {
+            Command::MoveCall(call) => Self {
+                command_kind: "move_call".to_string(),
+                package: Some(call.package.to_string()),
+                module: Some(call.module.to_string()),
+                function: Some(call.function.to_string()),
+                type_arguments: (!call.type_arguments.is_empty())
+                    .then(|| join(&call.type_arguments)),
+                arguments: join(&call.arguments),
+                ..tx_command
+            },
+            Command::TransferObjects(objects, address) => Self {
+                command_kind: "transfer_objects".to_string(),
+                arguments: format!("[{}] to {address}", join(objects)),
+                ..tx_command
+            },
+            Command::SplitCoins(coin, amounts) => Self {
+                command_kind: "split_coins".to_string(),
+                arguments: format!("{coin} into [{}]", join(amounts)),
+                ..tx_command
+            },
+            Command::MergeCoins(coin, coins) => Self {
+                command_kind: "merge_coins".to_string(),
+                arguments: format!("[{}] into {coin}", join(coins)),
+                ..tx_command
+            },
+            Command::Publish(modules, dependencies) => Self {
+                command_kind: "publish".to_string(),
+                arguments: format!(
+                    "{} modules, dependencies [{}]",
+                    modules.len(),
+                    join(dependencies)
+                ),
+                ..tx_command
+            },
+            Command::Upgrade(modules, dependencies, package, ticket) => Self {
+                command_kind: "upgrade".to_string(),
+                package: Some(package.to_string()),
+                arguments: format!(
+                    "{} modules, dependencies [{}], ticket {ticket}",
+                    modules.len(),
+                    join(dependencies)
+                ),
+                ..tx_command
+            },
+            Command::MakeMoveVec(type_, elements) => Self {
+                command_kind: "make_move_vec".to_string(),
+                type_arguments: type_.as_ref().map(|type_| type_.to_string()),
+                arguments: format!("[{}]", join(elements)),
+                ..tx_command
+            },
+        }
+    }
+}
+
+fn join<T: Display>(items: &[T]) -> String {
+    items
+        .iter()
+        .map(|item| item.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[derive(Queryable, Insertable, Debug, Clone, Default, Serialize, Deserialize)]
+#[diesel(table_name = tx_dependencies)]
+pub struct TxDependency {
+    pub transaction_digest: String,
+    pub dependency: String,
+    pub checkpoint_sequence_number: i64,
+}
+
+// A transaction reached when walking the dependency DAG, depth is the
+// shortest distance from the starting transaction.
+#[derive(QueryableByName, Debug, Clone)]
+pub struct DBTxDependencyNode {
+    #[diesel(sql_type = Text)]
+    pub transaction_digest: String,
+    #[diesel(sql_type = BigInt)]
+    pub depth: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionDependencyNode {
+    pub transaction_digest: String,
+    pub depth: u64,
+}
+
+impl From<DBTxDependencyNode> for TransactionDependencyNode {
+    fn from(node: DBTxDependencyNode) -> Self {
+        Self {
+            transaction_digest: node.transaction_digest,
+            depth: node.depth as u64,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use move_core_types::identifier::Identifier;
+    use move_core_types::language_storage::TypeTag;
+    use sui_types::base_types::ObjectID;
+    use sui_types::transaction::{Argument, Command, ProgrammableMoveCall};
+
+    use super::TxCommand;
+
+    fn tx_command(command: Command) -> TxCommand {
+        TxCommand::new("digest".to_string(), 2, 10, 1, &command)
+    }
+
+    #[test]
+    fn test_tx_command() {
+        let command = tx_command(Command::MoveCall(Box::new(ProgrammableMoveCall {
+            package: ObjectID::from_single_byte(2),
+            module: Identifier::new("coin").unwrap(),
+            function: Identifier::new("join").unwrap(),
+            type_arguments: vec![TypeTag::from_str("0x2::sui::SUI").unwrap()],
+            arguments: vec![Argument::Input(0), Argument::NestedResult(1, 0)],
+        })));
+        assert_eq!(command.command_index, 2);
+        assert_eq!(command.command_kind, "move_call");
+        assert_eq!(
+            command.package,
+            Some(ObjectID::from_single_byte(2).to_string())
+        );
+        assert_eq!(command.module.as_deref(), Some("coin"));
+        assert_eq!(command.function.as_deref(), Some("join"));
+        assert!(command.type_arguments.is_some());
+        assert_eq!(command.arguments, "Input(0), NestedResult(1,0)");
+
+        let command = tx_command(Command::TransferObjects(
+            vec![Argument::Result(0), Argument::GasCoin],
+            Argument::Input(1),
+        ));
+        assert_eq!(command.command_kind, "transfer_objects");
+        assert_eq!(command.package, None);
+        assert_eq!(command.arguments, "[Result(0), GasCoin] to Input(1)");
+
+        let command = tx_command(Command::SplitCoins(
+            Argument::GasCoin,
+            vec![Argument::Input(0), Argument::Input(1)],
+        ));
+        assert_eq!(command.arguments, "GasCoin into [Input(0), Input(1)]");
+
+        let command = tx_command(Command::Upgrade(
+            vec![vec![], vec![]],
+            vec![ObjectID::from_single_byte(1)],
+            ObjectID::from_single_byte(9),
+            Argument::Result(0),
+        ));
+        assert_eq!(command.command_kind, "upgrade");
+        assert_eq!(
+            command.package,
+            Some(ObjectID::from_single_byte(9).to_string())
+        );
+        assert_eq!(
+            command.arguments,
+            format!(
+                "2 modules, dependencies [{}], ticket Result(0)",
+                ObjectID::from_single_byte(1)
+            )
+        );
+
+        let command = tx_command(Command::MakeMoveVec(None, vec![]));
+        assert_eq!(command.command_kind, "make_move_vec");
+        assert_eq!(command.type_arguments, None);
+        assert_eq!(command.arguments, "[]");
+    }
+}
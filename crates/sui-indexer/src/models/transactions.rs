@@ -1,15 +1,24 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::str::FromStr;
+
 use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
 
-use sui_json_rpc_types::{SuiTransactionBlockDataAPI, SuiTransactionBlockEffectsAPI};
+use sui_json_rpc_types::{
+    AbortedTransactionBlock, SuiTransactionBlockDataAPI, SuiTransactionBlockEffectsAPI,
+};
+use sui_types::base_types::{ObjectID, ObjectRef, SuiAddress};
+use sui_types::execution_status::{ExecutionFailureStatus, ExecutionStatus};
+use sui_types::transaction::{SenderSignedData, TransactionDataAPI, TransactionExpiration};
 
 use crate::errors::IndexerError;
-use crate::schema::transactions;
+use crate::models::compression::compress_payload;
+use crate::schema::{transactions, transactions_json};
 use crate::types::TemporaryTransactionBlockResponseStore;
 
-#[derive(Clone, Debug, Queryable, Insertable, QueryableByName)]
+#[derive(Clone, Debug, Default, Queryable, Insertable, QueryableByName, Serialize, Deserialize)]
 #[diesel(table_name = transactions)]
 pub struct Transaction {
     #[diesel(deserialize_as = i64)]
@@ -31,10 +40,185 @@ pub struct Transaction {
     pub storage_rebate: i64,
     pub non_refundable_storage_fee: i64,
     pub gas_price: i64,
-    // BCS bytes of SenderSignedData
+    // BCS bytes of SenderSignedData, possibly compressed, see `compression`
     pub raw_transaction: Vec<u8>,
     pub transaction_effects_content: String,
     pub confirmed_local_execution: Option<bool>,
+    // payer of the gas, the sponsor if not the sender; null for transactions indexed before
+    // it was recorded
+    pub gas_owner: Option<String>,
+    // refs of the gas payment objects of the transaction data, in the same order
+    pub gas_payment_object_ids: Vec<Option<String>>,
+    pub gas_payment_object_versions: Vec<Option<i64>>,
+    pub gas_payment_object_digests: Vec<Option<String>>,
+    pub expiration_epoch: Option<i64>,
+    // gas paid by another address than the sender
+    pub sponsored: bool,
+    // execution error of failed transactions, see ExecutionFailure
+    pub failure_kind: Option<String>,
+    pub failure_command: Option<i64>,
+    pub abort_package: Option<String>,
+    pub abort_module: Option<String>,
+    pub abort_code: Option<i64>,
+}
+
+impl Transaction {
+    /// Compresses the raw transaction, read back transparently by the store.
+    pub fn with_compressed_payload(mut self, level: i32) -> Self {
+        self.raw_transaction = compress_payload(self.raw_transaction, level);
+        self
+    }
+}
+
+/// Execution error of a failed transaction as the failure columns of `transactions`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExecutionFailure {
+    // variant of ExecutionFailureStatus
+    pub kind: String,
+    pub command: Option<i64>,
+    // location and code of Move aborts
+    pub abort_package: Option<String>,
+    pub abort_module: Option<String>,
+    pub abort_code: Option<i64>,
+}
+
+impl ExecutionFailure {
+    pub fn from_status(status: &ExecutionStatus) -> Option<Self> {
+        let (error, command) = match status {
+            ExecutionStatus::Success => return None,
+            ExecutionStatus::Failure { error, command } => (error, command),
+        };
+        let mut failure = ExecutionFailure {
+            kind: failure_kind(error).to_string(),
+            command: command.map(|command| command as i64),
+            ..Default::default()
+        };
+        if let ExecutionFailureStatus::MoveAbort(location, code) = error {
+            failure.abort_package = Some(ObjectID::from(*location.module.address()).to_string());
+            failure.abort_module = Some(location.module.name().to_string());
+            // abort codes above i64::MAX, e.g. of error constants with the high bit set, are
+            // stored as negative numbers
+            failure.abort_code = Some(*code as i64);
+        }
+        Some(failure)
+    }
+}
+
+// Name of the variant of `error`, matched exhaustively so that a new variant has to be named
+// here rather than stored under a name that changes with its formatting.
+fn failure_kind(error: &ExecutionFailureStatus) -> &'static str {
+    use ExecutionFailureStatus as E;
+    match error {
+        E::InsufficientGas => "InsufficientGas",
+        E::InvalidGasObject => "InvalidGasObject",
+        E::InvariantViolation => "InvariantViolation",
+        E::FeatureNotYetSupported => "FeatureNotYetSupported",
+        E::MoveObjectTooBig { .. } => "MoveObjectTooBig",
+        E::MovePackageTooBig { .. } => "MovePackageTooBig",
+        E::CircularObjectOwnership { .. } => "CircularObjectOwnership",
+        E::InsufficientCoinBalance => "InsufficientCoinBalance",
+        E::CoinBalanceOverflow => "CoinBalanceOverflow",
+        E::PublishErrorNonZeroAddress => "PublishErrorNonZeroAddress",
+        E::SuiMoveVerificationError => "SuiMoveVerificationError",
+        E::MovePrimitiveRuntimeError(_) => "MovePrimitiveRuntimeError",
+        E::MoveAbort(..) => "MoveAbort",
+        E::VMVerificationOrDeserializationError => "VMVerificationOrDeserializationError",
+        E::VMInvariantViolation => "VMInvariantViolation",
+        E::FunctionNotFound => "FunctionNotFound",
+        E::ArityMismatch => "ArityMismatch",
+        E::TypeArityMismatch => "TypeArityMismatch",
+        E::NonEntryFunctionInvoked => "NonEntryFunctionInvoked",
+        E::CommandArgumentError { .. } => "CommandArgumentError",
+        E::TypeArgumentError { .. } => "TypeArgumentError",
+        E::UnusedValueWithoutDrop { .. } => "UnusedValueWithoutDrop",
+        E::InvalidPublicFunctionReturnType { .. } => "InvalidPublicFunctionReturnType",
+        E::InvalidTransferObject => "InvalidTransferObject",
+        E::EffectsTooLarge { .. } => "EffectsTooLarge",
+        E::PublishUpgradeMissingDependency => "PublishUpgradeMissingDependency",
+        E::PublishUpgradeDependencyDowngrade => "PublishUpgradeDependencyDowngrade",
+        E::PackageUpgradeError { .. } => "PackageUpgradeError",
+        E::WrittenObjectsTooLarge { .. } => "WrittenObjectsTooLarge",
+        E::CertificateDenied => "CertificateDenied",
+        E::SuiMoveVerificationTimedout => "SuiMoveVerificationTimedout",
+    }
+}
+
+/// Transaction aborted in a Move module, with the name of the error constant of its abort
+/// code if the module has a verified source declaring it.
+#[derive(Clone, Debug)]
+pub struct AbortedTransaction {
+    pub transaction: Transaction,
+    pub abort_constant: Option<String>,
+}
+
+impl TryFrom<AbortedTransaction> for AbortedTransactionBlock {
+    type Error = IndexerError;
+
+    fn try_from(aborted: AbortedTransaction) -> Result<Self, Self::Error> {
+        let AbortedTransaction {
+            transaction,
+            abort_constant,
+        } = aborted;
+        let digest = transaction.transaction_digest.parse().map_err(|e| {
+            IndexerError::SerdeError(format!(
+                "Failed to parse transaction digest {} : {:?}",
+                transaction.transaction_digest, e
+            ))
+        })?;
+        let abort_code = transaction.abort_code.ok_or_else(|| {
+            IndexerError::PostgresReadError(format!(
+                "Transaction {} has no abort code",
+                transaction.transaction_digest
+            ))
+        })?;
+        Ok(Self {
+            digest,
+            sender: SuiAddress::from_str(&transaction.sender)?,
+            checkpoint: transaction.checkpoint_sequence_number.map(|cp| cp as u64),
+            timestamp_ms: transaction.timestamp_ms.map(|ts| ts as u64),
+            command: transaction.failure_command.map(|command| command as u64),
+            // codes above i64::MAX are stored as negative numbers
+            abort_code: abort_code as u64,
+            abort_constant,
+        })
+    }
+}
+
+/// Gas payment objects as the `gas_payment_object_*` columns of `transactions`.
+pub fn gas_payment_columns(
+    payment: &[ObjectRef],
+) -> (Vec<Option<String>>, Vec<Option<i64>>, Vec<Option<String>>) {
+    let ids = payment
+        .iter()
+        .map(|(id, _, _)| Some(id.to_string()))
+        .collect();
+    let versions = payment
+        .iter()
+        .map(|(_, version, _)| Some(version.value() as i64))
+        .collect();
+    let digests = payment
+        .iter()
+        .map(|(_, _, digest)| Some(digest.base58_encode()))
+        .collect();
+    (ids, versions, digests)
+}
+
+/// Epoch of `expiration` as the `expiration_epoch` column of `transactions`.
+pub fn expiration_epoch(expiration: &TransactionExpiration) -> Option<i64> {
+    match expiration {
+        TransactionExpiration::None => None,
+        TransactionExpiration::Epoch(epoch) => Some(*epoch as i64),
+    }
+}
+
+/// `SuiTransactionBlockResponse` of a transaction with its input and effects, stored as
+/// JSONB for SQL queries over nested fields.
+#[derive(Clone, Debug, Queryable, Insertable, Serialize, Deserialize)]
+#[diesel(table_name = transactions_json)]
+pub struct TransactionJson {
+    pub transaction_digest: String,
+    pub checkpoint_sequence_number: i64,
+    pub response: serde_json::Value,
 }
 
 impl TryFrom<TemporaryTransactionBlockResponseStore> for Transaction {
@@ -67,6 +251,18 @@ impl TryFrom<TemporaryTransactionBlockResponseStore> for Transaction {
         let storage_cost = gas_summary.storage_cost;
         let storage_rebate = gas_summary.storage_rebate;
         let non_refundable_storage_fee = gas_summary.non_refundable_storage_fee;
+        let gas_data = transaction.data.gas_data();
+        let payment = gas_data
+            .payment
+            .iter()
+            .map(|object_ref| object_ref.to_object_ref())
+            .collect::<Vec<_>>();
+        let (gas_payment_object_ids, gas_payment_object_versions, gas_payment_object_digests) =
+            gas_payment_columns(&payment);
+        // the expiration is not part of the JSON transaction data
+        let expiration_epoch = bcs::from_bytes::<SenderSignedData>(&raw_transaction)
+            .ok()
+            .and_then(|data| expiration_epoch(data.transaction_data().expiration()));
         Ok(Transaction {
             id: None,
             transaction_digest: digest.base58_encode(),
@@ -91,6 +287,121 @@ impl TryFrom<TemporaryTransactionBlockResponseStore> for Transaction {
             raw_transaction,
             transaction_effects_content: tx_effect_json,
             confirmed_local_execution,
+            gas_owner: Some(gas_data.owner.to_string()),
+            sponsored: *transaction.data.sender() != gas_data.owner,
+            // the error of the JSON effects is a string, failures are decoded from the
+            // effects of checkpoints only
+            failure_kind: None,
+            failure_command: None,
+            abort_package: None,
+            abort_module: None,
+            abort_code: None,
+            gas_payment_object_ids,
+            gas_payment_object_versions,
+            gas_payment_object_digests,
+            expiration_epoch,
         })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use move_core_types::identifier::Identifier;
+    use move_core_types::language_storage::ModuleId;
+    use sui_json_rpc_types::AbortedTransactionBlock;
+    use sui_types::base_types::{ObjectID, SuiAddress};
+    use sui_types::digests::TransactionDigest;
+    use sui_types::execution_status::{ExecutionFailureStatus, ExecutionStatus, MoveLocation};
+
+    use super::{AbortedTransaction, ExecutionFailure, Transaction};
+
+    #[test]
+    fn test_execution_failure() {
+        assert_eq!(
+            ExecutionFailure::from_status(&ExecutionStatus::Success),
+            None
+        );
+        let failure = ExecutionFailure::from_status(&ExecutionStatus::Failure {
+            error: ExecutionFailureStatus::InsufficientGas,
+            command: None,
+        });
+        assert_eq!(
+            failure,
+            Some(ExecutionFailure {
+                kind: "InsufficientGas".to_string(),
+                ..Default::default()
+            })
+        );
+        let failure = ExecutionFailure::from_status(&ExecutionStatus::Failure {
+            error: ExecutionFailureStatus::EffectsTooLarge {
+                current_size: 2,
+                max_size: 1,
+            },
+            command: None,
+        });
+        assert_eq!(
+            failure.map(|failure| failure.kind),
+            Some("EffectsTooLarge".to_string())
+        );
+
+        let package = ObjectID::from_str("0x2").unwrap();
+        let location = MoveLocation {
+            module: ModuleId::new(package.into(), Identifier::new("pool").unwrap()),
+            function: 3,
+            instruction: 10,
+            function_name: Some("swap".to_string()),
+        };
+        let failure = ExecutionFailure::from_status(&ExecutionStatus::Failure {
+            error: ExecutionFailureStatus::MoveAbort(location, 7),
+            command: Some(1),
+        });
+        assert_eq!(
+            failure,
+            Some(ExecutionFailure {
+                kind: "MoveAbort".to_string(),
+                command: Some(1),
+                abort_package: Some(package.to_string()),
+                abort_module: Some("pool".to_string()),
+                abort_code: Some(7),
+            })
+        );
+    }
+
+    #[test]
+    fn test_aborted_transaction_block() {
+        let digest = TransactionDigest::random();
+        let sender = SuiAddress::random_for_testing_only();
+        let aborted = AbortedTransaction {
+            transaction: Transaction {
+                id: Some(5),
+                transaction_digest: digest.base58_encode(),
+                sender: sender.to_string(),
+                checkpoint_sequence_number: Some(3),
+                timestamp_ms: Some(1_000),
+                failure_command: Some(1),
+                abort_code: Some(u64::MAX as i64),
+                ..Default::default()
+            },
+            abort_constant: Some("EMax".to_string()),
+        };
+        assert_eq!(
+            AbortedTransactionBlock::try_from(aborted.clone()).unwrap(),
+            AbortedTransactionBlock {
+                digest,
+                sender,
+                checkpoint: Some(3),
+                timestamp_ms: Some(1_000),
+                command: Some(1),
+                abort_code: u64::MAX,
+                abort_constant: Some("EMax".to_string()),
+            }
+        );
+
+        // transactions that did not abort have no abort code
+        let mut not_aborted = aborted;
+        not_aborted.transaction.abort_code = None;
+        assert!(AbortedTransactionBlock::try_from(not_aborted).is_err());
+    }
+}
@@ -0,0 +1,24 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use diesel::{Insertable, Queryable};
+use serde::{Deserialize, Serialize};
+
+use crate::schema::transfers;
+
+/// Balance change by coin type of an address receiving coins from the sender of a
+/// transaction, whether split off and sent or sent whole, see `index_transfers`.
+#[derive(Queryable, Insertable, Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[diesel(table_name = transfers)]
+pub struct Transfer {
+    pub id: Option<i64>,
+    pub transaction_digest: String,
+    pub checkpoint_sequence_number: i64,
+    pub timestamp_ms: i64,
+    pub sender: String,
+    pub recipient: String,
+    // type param of the coin, e.g. 0x2::sui::SUI
+    pub coin_type: String,
+    // sum of the balances of the coins received
+    pub amount: i64,
+}
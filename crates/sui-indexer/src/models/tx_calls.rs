@@ -0,0 +1,389 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use diesel::prelude::*;
+use move_binary_format::access::ModuleAccess;
+use move_binary_format::file_format::SignatureToken;
+use move_binary_format::CompiledModule;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::identifier::IdentStr;
+use move_core_types::language_storage::TypeTag;
+use move_core_types::u256::U256;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use sui_types::base_types::{SuiAddress, SUI_ADDRESS_LENGTH};
+use sui_types::transaction::{Argument, CallArg, ProgrammableMoveCall};
+use sui_types::{MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS};
+
+use crate::schema::tx_calls;
+
+// vectors longer than this are not decoded, pure arguments are bounded by the max size of
+// a transaction anyway
+const MAX_VECTOR_LENGTH: u64 = 1 << 20;
+
+/// A Move call of a programmable transaction, with one JSON value per argument. Pure
+/// arguments are decoded by the parameter types of the called function, other arguments,
+/// and pure arguments that could not be decoded, are null.
+#[derive(Queryable, Insertable, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[diesel(table_name = tx_calls)]
+pub struct TxCall {
+    pub transaction_digest: String,
+    pub command_index: i64,
+    pub checkpoint_sequence_number: i64,
+    pub epoch: i64,
+    pub package: String,
+    pub module: String,
+    pub function: String,
+    pub arguments: Value,
+}
+
+impl TxCall {
+    /// `parameters` are the pure types of the parameters of the called function, None if
+    /// its module could not be resolved.
+    pub fn new(
+        transaction_digest: String,
+        command_index: usize,
+        checkpoint_sequence_number: i64,
+        epoch: i64,
+        call: &ProgrammableMoveCall,
+        inputs: &[CallArg],
+        parameters: Option<&[Option<PureType>]>,
+    ) -> Self {
+        let arguments = call
+            .arguments
+            .iter()
+            .enumerate()
+            .map(|(i, argument)| {
+                let bytes = match argument {
+                    Argument::Input(input) => match inputs.get(*input as usize) {
+                        Some(CallArg::Pure(bytes)) => bytes,
+                        _ => return Value::Null,
+                    },
+                    _ => return Value::Null,
+                };
+                match parameters.and_then(|parameters| parameters.get(i)) {
+                    Some(Some(type_)) => type_.decode(bytes).unwrap_or(Value::Null),
+                    _ => Value::Null,
+                }
+            })
+            .collect();
+        Self {
+            transaction_digest,
+            command_index: command_index as i64,
+            checkpoint_sequence_number,
+            epoch,
+            package: call.package.to_string(),
+            module: call.module.to_string(),
+            function: call.function.to_string(),
+            arguments: Value::Array(arguments),
+        }
+    }
+}
+
+/// Types of the values pure arguments can hold.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PureType {
+    Bool,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    U256,
+    Address,
+    /// `0x1::string::String` and `0x1::ascii::String`
+    String,
+    /// `0x2::object::ID`
+    Id,
+    Option(Box<PureType>),
+    Vector(Box<PureType>),
+}
+
+impl PureType {
+    /// Pure types of the parameters of `function` of `module` called with `type_arguments`,
+    /// None for parameters of other types, e.g. objects.
+    pub fn function_parameters(
+        module: &CompiledModule,
+        function: &IdentStr,
+        type_arguments: &[TypeTag],
+    ) -> Option<Vec<Option<PureType>>> {
+        let handle = module.function_handles().iter().find(|handle| {
+            handle.module == module.self_handle_idx()
+                && module.identifier_at(handle.name) == function
+        })?;
+        Some(
+            module
+                .signature_at(handle.parameters)
+                .0
+                .iter()
+                .map(|token| Self::from_signature_token(module, token, type_arguments))
+                .collect(),
+        )
+    }
+
+    fn from_signature_token(
+        module: &CompiledModule,
+        token: &SignatureToken,
+        type_arguments: &[TypeTag],
+    ) -> Option<Self> {
+        Some(match token {
+            SignatureToken::Bool => Self::Bool,
+            SignatureToken::U8 => Self::U8,
+            SignatureToken::U16 => Self::U16,
+            SignatureToken::U32 => Self::U32,
+            SignatureToken::U64 => Self::U64,
+            SignatureToken::U128 => Self::U128,
+            SignatureToken::U256 => Self::U256,
+            SignatureToken::Address => Self::Address,
+            SignatureToken::Vector(element) => Self::Vector(Box::new(Self::from_signature_token(
+                module,
+                element,
+                type_arguments,
+            )?)),
+            SignatureToken::Struct(index) | SignatureToken::StructInstantiation(index, _) => {
+                let handle = module.struct_handle_at(*index);
+                let module_handle = module.module_handle_at(handle.module);
+                let type_parameter = match token {
+                    SignatureToken::StructInstantiation(_, type_parameters) => {
+                        type_parameters.first().and_then(|token| {
+                            Self::from_signature_token(module, token, type_arguments)
+                        })
+                    }
+                    _ => None,
+                };
+                Self::from_struct(
+                    module.address_identifier_at(module_handle.address),
+                    module.identifier_at(module_handle.name).as_str(),
+                    module.identifier_at(handle.name).as_str(),
+                    type_parameter,
+                )?
+            }
+            SignatureToken::TypeParameter(index) => {
+                Self::from_type_tag(type_arguments.get(*index as usize)?)?
+            }
+            SignatureToken::Signer
+            | SignatureToken::Reference(_)
+            | SignatureToken::MutableReference(_) => return None,
+        })
+    }
+
+    pub fn from_type_tag(tag: &TypeTag) -> Option<Self> {
+        Some(match tag {
+            TypeTag::Bool => Self::Bool,
+            TypeTag::U8 => Self::U8,
+            TypeTag::U16 => Self::U16,
+            TypeTag::U32 => Self::U32,
+            TypeTag::U64 => Self::U64,
+            TypeTag::U128 => Self::U128,
+            TypeTag::U256 => Self::U256,
+            TypeTag::Address => Self::Address,
+            TypeTag::Vector(element) => Self::Vector(Box::new(Self::from_type_tag(element)?)),
+            TypeTag::Struct(tag) => Self::from_struct(
+                &tag.address,
+                tag.module.as_str(),
+                tag.name.as_str(),
+                tag.type_params.first().and_then(Self::from_type_tag),
+            )?,
+            TypeTag::Signer => return None,
+        })
+    }
+
+    fn from_struct(
+        address: &AccountAddress,
+        module: &str,
+        name: &str,
+        type_parameter: Option<Self>,
+    ) -> Option<Self> {
+        Some(match (*address, module, name) {
+            (MOVE_STDLIB_ADDRESS, "string", "String")
+            | (MOVE_STDLIB_ADDRESS, "ascii", "String") => Self::String,
+            (MOVE_STDLIB_ADDRESS, "option", "Option") => Self::Option(Box::new(type_parameter?)),
+            (SUI_FRAMEWORK_ADDRESS, "object", "ID") => Self::Id,
+            _ => return None,
+        })
+    }
+
+    /// JSON value of the BCS `bytes`, with integers wider than 32 bits as strings. None if
+    /// the bytes are not exactly a value of this type.
+    pub fn decode(&self, mut bytes: &[u8]) -> Option<Value> {
+        let value = self.read(&mut bytes)?;
+        bytes.is_empty().then_some(value)
+    }
+
+    fn read(&self, bytes: &mut &[u8]) -> Option<Value> {
+        Some(match self {
+            Self::Bool => match take(bytes, 1)? {
+                [0] => Value::Bool(false),
+                [1] => Value::Bool(true),
+                _ => return None,
+            },
+            Self::U8 => Value::from(take(bytes, 1)?[0]),
+            Self::U16 => Value::from(u16::from_le_bytes(take(bytes, 2)?.try_into().ok()?)),
+            Self::U32 => Value::from(u32::from_le_bytes(take(bytes, 4)?.try_into().ok()?)),
+            Self::U64 => {
+                Value::from(u64::from_le_bytes(take(bytes, 8)?.try_into().ok()?).to_string())
+            }
+            Self::U128 => {
+                Value::from(u128::from_le_bytes(take(bytes, 16)?.try_into().ok()?).to_string())
+            }
+            Self::U256 => {
+                Value::from(U256::from_le_bytes(take(bytes, 32)?.try_into().ok()?).to_string())
+            }
+            Self::Address | Self::Id => Value::from(
+                SuiAddress::from_bytes(take(bytes, SUI_ADDRESS_LENGTH)?)
+                    .ok()?
+                    .to_string(),
+            ),
+            Self::String => {
+                let length = read_length(bytes)?;
+                Value::from(String::from_utf8(take(bytes, length)?.to_vec()).ok()?)
+            }
+            Self::Option(element) => match read_length(bytes)? {
+                0 => Value::Null,
+                1 => element.read(bytes)?,
+                _ => return None,
+            },
+            Self::Vector(element) => {
+                let length = read_length(bytes)?;
+                Value::Array(
+                    (0..length)
+                        .map(|_| element.read(bytes))
+                        .collect::<Option<_>>()?,
+                )
+            }
+        })
+    }
+}
+
+fn take<'a>(bytes: &mut &'a [u8], n: usize) -> Option<&'a [u8]> {
+    if bytes.len() < n {
+        return None;
+    }
+    let (taken, rest) = bytes.split_at(n);
+    *bytes = rest;
+    Some(taken)
+}
+
+// ULEB128 length prefix of vectors
+fn read_length(bytes: &mut &[u8]) -> Option<usize> {
+    let mut length: u64 = 0;
+    for shift in (0..32).step_by(7) {
+        let byte = take(bytes, 1)?[0];
+        length |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return (length <= MAX_VECTOR_LENGTH).then_some(length as usize);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use move_core_types::identifier::Identifier;
+    use move_core_types::language_storage::TypeTag;
+    use serde_json::json;
+    use sui_types::base_types::{ObjectID, SuiAddress};
+    use sui_types::transaction::{Argument, CallArg, ObjectArg, ProgrammableMoveCall};
+
+    use super::{PureType, TxCall};
+
+    #[test]
+    fn test_decode_pure_values() {
+        let decode = |type_: &str, bytes: Vec<u8>| {
+            PureType::from_type_tag(&TypeTag::from_str(type_).unwrap())
+                .unwrap()
+                .decode(&bytes)
+        };
+        assert_eq!(
+            decode("u64", bcs::to_bytes(&u64::MAX).unwrap()),
+            Some(json!(u64::MAX.to_string()))
+        );
+        assert_eq!(decode("u8", vec![7]), Some(json!(7)));
+        assert_eq!(decode("bool", vec![2]), None);
+        // trailing bytes
+        assert_eq!(decode("u16", vec![1, 0, 0]), None);
+
+        let address = SuiAddress::random_for_testing_only();
+        assert_eq!(
+            decode("0x2::object::ID", bcs::to_bytes(&address).unwrap()),
+            Some(json!(address.to_string()))
+        );
+        assert_eq!(
+            decode("0x1::string::String", bcs::to_bytes("sui").unwrap()),
+            Some(json!("sui"))
+        );
+        assert_eq!(
+            decode(
+                "0x1::option::Option<u32>",
+                bcs::to_bytes(&Some(3u32)).unwrap()
+            ),
+            Some(json!(3))
+        );
+        assert_eq!(
+            decode(
+                "0x1::option::Option<u32>",
+                bcs::to_bytes(&None::<u32>).unwrap()
+            ),
+            Some(json!(null))
+        );
+        assert_eq!(
+            decode(
+                "vector<vector<u8>>",
+                bcs::to_bytes(&vec![vec![1u8, 2]]).unwrap()
+            ),
+            Some(json!([[1, 2]]))
+        );
+        // vector length past the bytes
+        assert_eq!(decode("vector<u8>", vec![3, 1]), None);
+        assert!(
+            PureType::from_type_tag(&TypeTag::from_str("0x2::coin::Coin<u8>").unwrap()).is_none()
+        );
+    }
+
+    #[test]
+    fn test_tx_call_arguments() {
+        let call = ProgrammableMoveCall {
+            package: ObjectID::from_single_byte(42),
+            module: Identifier::new("pool").unwrap(),
+            function: Identifier::new("swap").unwrap(),
+            type_arguments: vec![],
+            arguments: vec![
+                Argument::Input(0),
+                Argument::Input(1),
+                Argument::Result(0),
+                Argument::Input(2),
+            ],
+        };
+        let inputs = vec![
+            CallArg::Object(ObjectArg::SharedObject {
+                id: ObjectID::random(),
+                initial_shared_version: 1.into(),
+                mutable: true,
+            }),
+            CallArg::Pure(bcs::to_bytes(&1000u64).unwrap()),
+            CallArg::Pure(bcs::to_bytes(&true).unwrap()),
+        ];
+        let parameters = vec![None, Some(PureType::U64), None, Some(PureType::Bool)];
+
+        let tx_call = TxCall::new(
+            "digest".to_string(),
+            1,
+            10,
+            2,
+            &call,
+            &inputs,
+            Some(&parameters),
+        );
+        assert_eq!(tx_call.command_index, 1);
+        assert_eq!(tx_call.module, "pool");
+        assert_eq!(tx_call.function, "swap");
+        assert_eq!(tx_call.arguments, json!([null, "1000", null, true]));
+
+        // module not resolved
+        let tx_call = TxCall::new("digest".to_string(), 1, 10, 2, &call, &inputs, None);
+        assert_eq!(tx_call.arguments, json!([null, null, null, null]));
+    }
+}
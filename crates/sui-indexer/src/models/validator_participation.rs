@@ -0,0 +1,99 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use sui_types::messages_checkpoint::CertifiedCheckpointSummary;
+
+use crate::schema::validator_participation;
+
+/// Committee indices of the validators whose signatures are aggregated in the certificate
+/// of a checkpoint.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CheckpointSigners {
+    pub checkpoint_sequence_number: i64,
+    pub epoch: i64,
+    pub committee_indices: Vec<i64>,
+}
+
+impl CheckpointSigners {
+    pub fn new(checkpoint: &CertifiedCheckpointSummary) -> Self {
+        Self {
+            checkpoint_sequence_number: checkpoint.sequence_number as i64,
+            epoch: checkpoint.epoch as i64,
+            committee_indices: checkpoint
+                .auth_sig()
+                .signers_map
+                .iter()
+                .map(|index| index as i64)
+                .collect(),
+        }
+    }
+}
+
+/// Number of checkpoints of an epoch signed by the validator at `committee_index` in the
+/// committee of the epoch.
+#[derive(Queryable, Insertable, Debug, Clone, PartialEq, Eq)]
+#[diesel(table_name = validator_participation)]
+pub struct ValidatorParticipation {
+    pub epoch: i64,
+    pub committee_index: i64,
+    pub checkpoints_signed: i64,
+}
+
+impl ValidatorParticipation {
+    /// Signatures of `signers` counted per epoch and validator.
+    pub fn from_signers<'a>(
+        signers: impl IntoIterator<Item = &'a CheckpointSigners>,
+    ) -> Vec<ValidatorParticipation> {
+        let mut participation = BTreeMap::new();
+        for checkpoint in signers {
+            for index in &checkpoint.committee_indices {
+                *participation.entry((checkpoint.epoch, *index)).or_insert(0) += 1;
+            }
+        }
+        participation
+            .into_iter()
+            .map(
+                |((epoch, committee_index), checkpoints_signed)| ValidatorParticipation {
+                    epoch,
+                    committee_index,
+                    checkpoints_signed,
+                },
+            )
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{CheckpointSigners, ValidatorParticipation};
+
+    fn signers(checkpoint: i64, epoch: i64, committee_indices: &[i64]) -> CheckpointSigners {
+        CheckpointSigners {
+            checkpoint_sequence_number: checkpoint,
+            epoch,
+            committee_indices: committee_indices.to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_signatures_counted_per_epoch_and_validator() {
+        let checkpoints = vec![
+            signers(1, 0, &[0, 1, 2]),
+            signers(2, 0, &[0, 2]),
+            signers(3, 1, &[1]),
+        ];
+        let participation = ValidatorParticipation::from_signers(&checkpoints)
+            .into_iter()
+            .map(|p| (p.epoch, p.committee_index, p.checkpoints_signed))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![(0, 0, 2), (0, 1, 1), (0, 2, 2), (1, 1, 1)],
+            participation
+        );
+    }
+}
@@ -0,0 +1,176 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use diesel::{Insertable, Queryable};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use sui_types::base_types::ObjectID;
+
+use crate::errors::IndexerError;
+use crate::schema::verified_sources;
+
+static MODULE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\bmodule\s+\w+::(\w+)\s*\{").unwrap());
+static U64_CONST_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\bconst\s+(\w+)\s*:\s*u64\s*=\s*(0x[0-9a-fA-F_]+|[0-9_]+)\s*;").unwrap()
+});
+
+#[derive(Queryable, Insertable, Debug, Clone)]
+#[diesel(table_name = verified_sources)]
+pub struct DBVerifiedSource {
+    pub package_id: String,
+    pub sources: String,
+    pub verifier: String,
+    pub verified_at_ms: i64,
+    pub metadata: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifiedSource {
+    pub package_id: ObjectID,
+    /// Source file path to file content.
+    pub sources: BTreeMap<String, String>,
+    pub verifier: String,
+    pub verified_at_ms: u64,
+    pub metadata: Option<serde_json::Value>,
+}
+
+impl TryFrom<VerifiedSource> for DBVerifiedSource {
+    type Error = IndexerError;
+
+    fn try_from(source: VerifiedSource) -> Result<Self, Self::Error> {
+        let sources = serde_json::to_string(&source.sources).map_err(|e| {
+            IndexerError::SerdeError(format!(
+                "Failed serializing sources of package {} with error: {e:?}",
+                source.package_id
+            ))
+        })?;
+        Ok(Self {
+            package_id: source.package_id.to_string(),
+            sources,
+            verifier: source.verifier,
+            verified_at_ms: source.verified_at_ms as i64,
+            metadata: source.metadata.map(|m| m.to_string()),
+        })
+    }
+}
+
+impl TryFrom<DBVerifiedSource> for VerifiedSource {
+    type Error = IndexerError;
+
+    fn try_from(source: DBVerifiedSource) -> Result<Self, Self::Error> {
+        let sources = serde_json::from_str(&source.sources).map_err(|e| {
+            IndexerError::SerdeError(format!(
+                "Failed deserializing sources of package {} with error: {e:?}",
+                source.package_id
+            ))
+        })?;
+        let metadata = source
+            .metadata
+            .as_deref()
+            .map(serde_json::from_str)
+            .transpose()
+            .map_err(|e| {
+                IndexerError::SerdeError(format!(
+                    "Failed deserializing source metadata of package {} with error: {e:?}",
+                    source.package_id
+                ))
+            })?;
+        Ok(Self {
+            package_id: ObjectID::from_str(&source.package_id)?,
+            sources,
+            verifier: source.verifier,
+            verified_at_ms: source.verified_at_ms as u64,
+            metadata,
+        })
+    }
+}
+
+impl VerifiedSource {
+    /// Names of the `u64` constants declared in `module` by the sources, by value. Of the
+    /// constants of the same value, error constants, named `E...` by convention, win.
+    pub fn error_constants(&self, module: &str) -> BTreeMap<u64, String> {
+        let mut constants = BTreeMap::new();
+        for content in self.sources.values() {
+            let declarations = MODULE_REGEX.captures_iter(content).collect::<Vec<_>>();
+            for (i, declaration) in declarations.iter().enumerate() {
+                if &declaration[1] != module {
+                    continue;
+                }
+                // the body of the module runs up to the next module of the file
+                let start = declaration.get(0).unwrap().end();
+                let end = declarations
+                    .get(i + 1)
+                    .map_or(content.len(), |next| next.get(0).unwrap().start());
+                for constant in U64_CONST_REGEX.captures_iter(&content[start..end]) {
+                    let digits = constant[2].replace('_', "");
+                    let value = match digits.strip_prefix("0x") {
+                        Some(hex) => u64::from_str_radix(hex, 16),
+                        None => digits.parse(),
+                    };
+                    let value = match value {
+                        Ok(value) => value,
+                        Err(_) => continue,
+                    };
+                    let name = &constant[1];
+                    let keep_existing = constants.get(&value).map_or(false, |existing: &String| {
+                        existing.starts_with('E') || !name.starts_with('E')
+                    });
+                    if !keep_existing {
+                        constants.insert(value, name.to_string());
+                    }
+                }
+            }
+        }
+        constants
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeMap;
+
+    use sui_types::base_types::ObjectID;
+
+    use super::VerifiedSource;
+
+    #[test]
+    fn test_error_constants() {
+        let source = VerifiedSource {
+            package_id: ObjectID::ZERO,
+            sources: BTreeMap::from([(
+                "sources/bank.move".to_string(),
+                "module bank::vault {
+                    const MAX_DEPOSIT: u64 = 7;
+                    const EInsufficientBalance: u64 = 7;
+                    const ELocked: u64 = 0x1_0;
+                    const NAME: vector<u8> = b\"vault\";
+                }
+                module bank::teller {
+                    const ENotTeller: u64 = 1;
+                }"
+                .to_string(),
+            )]),
+            verifier: "test".to_string(),
+            verified_at_ms: 0,
+            metadata: None,
+        };
+        assert_eq!(
+            source.error_constants("vault"),
+            BTreeMap::from([
+                (7, "EInsufficientBalance".to_string()),
+                (16, "ELocked".to_string())
+            ])
+        );
+        assert_eq!(
+            source.error_constants("teller"),
+            BTreeMap::from([(1, "ENotTeller".to_string())])
+        );
+        assert!(source.error_constants("bank").is_empty());
+    }
+}
@@ -0,0 +1,274 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{BTreeSet, HashMap};
+use std::str::FromStr;
+
+use diesel::{Insertable, Queryable};
+use serde::Serialize;
+
+use sui_json_rpc_types::{WatchedEntity, WatchlistEntry};
+use sui_types::base_types::{ObjectID, SuiAddress};
+
+use crate::errors::IndexerError;
+use crate::models::transaction_index::{ChangedObject, InputObject, Recipient};
+use crate::models::transactions::Transaction;
+use crate::schema::watchlists;
+
+const ADDRESS_KIND: &str = "address";
+const OBJECT_KIND: &str = "object";
+
+#[derive(Queryable, Insertable, Debug, Clone)]
+#[diesel(table_name = watchlists)]
+pub struct DBWatchlistEntry {
+    pub entity_kind: String,
+    pub entity_id: String,
+    pub rule: String,
+}
+
+impl From<WatchlistEntry> for DBWatchlistEntry {
+    fn from(entry: WatchlistEntry) -> Self {
+        let (entity_kind, entity_id) = match entry.entity {
+            WatchedEntity::Address(address) => (ADDRESS_KIND, address.to_string()),
+            WatchedEntity::Object(object_id) => (OBJECT_KIND, object_id.to_string()),
+        };
+        Self {
+            entity_kind: entity_kind.to_string(),
+            entity_id,
+            rule: entry.rule,
+        }
+    }
+}
+
+impl TryFrom<DBWatchlistEntry> for WatchlistEntry {
+    type Error = IndexerError;
+
+    fn try_from(entry: DBWatchlistEntry) -> Result<Self, Self::Error> {
+        let entity = match entry.entity_kind.as_str() {
+            ADDRESS_KIND => SuiAddress::from_str(&entry.entity_id)
+                .map(WatchedEntity::Address)
+                .map_err(|e| e.to_string()),
+            OBJECT_KIND => ObjectID::from_str(&entry.entity_id)
+                .map(WatchedEntity::Object)
+                .map_err(|e| e.to_string()),
+            kind => Err(format!("unknown entity kind {kind}")),
+        }
+        .map_err(|e| {
+            IndexerError::PostgresReadError(format!(
+                "Failed parsing watched entity {} of rule {} with error: {e}",
+                entry.entity_id, entry.rule
+            ))
+        })?;
+        Ok(Self {
+            entity,
+            rule: entry.rule,
+        })
+    }
+}
+
+/// A transaction touching a watched entity.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchlistMatch {
+    pub transaction_digest: String,
+    pub checkpoint_sequence_number: i64,
+    pub entity: WatchedEntityId,
+    pub rule: String,
+}
+
+/// `WatchedEntity` as indexed, i.e. with its id formatted like in the transaction tables.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(tag = "kind", content = "id", rename_all = "camelCase")]
+pub enum WatchedEntityId {
+    Address(String),
+    Object(String),
+}
+
+/// Watched entities of all rules, looked up by the ids found in indexed transactions.
+#[derive(Debug, Default)]
+pub struct Watchlist {
+    // watched entity id to the rules watching it
+    addresses: HashMap<String, Vec<String>>,
+    objects: HashMap<String, Vec<String>>,
+}
+
+impl Watchlist {
+    pub fn new(entries: Vec<WatchlistEntry>) -> Self {
+        let mut watchlist = Self::default();
+        for entry in entries {
+            let (rules, id) = match entry.entity {
+                WatchedEntity::Address(address) => (&mut watchlist.addresses, address.to_string()),
+                WatchedEntity::Object(object_id) => (&mut watchlist.objects, object_id.to_string()),
+            };
+            rules.entry(id).or_default().push(entry.rule);
+        }
+        watchlist
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.addresses.is_empty() && self.objects.is_empty()
+    }
+
+    /// Matches of the transactions of a checkpoint, ordered by transaction digest, with
+    /// each entity reported once per transaction and rule.
+    pub fn matches(
+        &self,
+        transactions: &[Transaction],
+        recipients: &[Recipient],
+        input_objects: &[InputObject],
+        changed_objects: &[ChangedObject],
+    ) -> Vec<WatchlistMatch> {
+        if self.is_empty() {
+            return vec![];
+        }
+        let mut matches = BTreeSet::new();
+        let mut match_entity =
+            |digest: &str, checkpoint: i64, entity: WatchedEntityId, rules: &[String]| {
+                for rule in rules {
+                    matches.insert(WatchlistMatch {
+                        transaction_digest: digest.to_string(),
+                        checkpoint_sequence_number: checkpoint,
+                        entity: entity.clone(),
+                        rule: rule.clone(),
+                    });
+                }
+            };
+
+        for tx in transactions {
+            if let Some(rules) = self.addresses.get(&tx.sender) {
+                match_entity(
+                    &tx.transaction_digest,
+                    tx.checkpoint_sequence_number.unwrap_or_default(),
+                    WatchedEntityId::Address(tx.sender.clone()),
+                    rules,
+                );
+            }
+        }
+        for recipient in recipients {
+            if let Some(rules) = self.addresses.get(&recipient.recipient) {
+                match_entity(
+                    &recipient.transaction_digest,
+                    recipient.checkpoint_sequence_number,
+                    WatchedEntityId::Address(recipient.recipient.clone()),
+                    rules,
+                );
+            }
+        }
+        let objects = input_objects
+            .iter()
+            .map(|o| {
+                (
+                    &o.transaction_digest,
+                    o.checkpoint_sequence_number,
+                    &o.object_id,
+                )
+            })
+            .chain(changed_objects.iter().map(|o| {
+                (
+                    &o.transaction_digest,
+                    o.checkpoint_sequence_number,
+                    &o.object_id,
+                )
+            }));
+        for (digest, checkpoint, object_id) in objects {
+            if let Some(rules) = self.objects.get(object_id) {
+                match_entity(
+                    digest,
+                    checkpoint,
+                    WatchedEntityId::Object(object_id.clone()),
+                    rules,
+                );
+            }
+        }
+        matches.into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use sui_json_rpc_types::{WatchedEntity, WatchlistEntry};
+    use sui_types::base_types::{ObjectID, SuiAddress};
+
+    use super::{WatchedEntityId, Watchlist};
+    use crate::models::transaction_index::{ChangedObject, InputObject, Recipient};
+    use crate::models::transactions::Transaction;
+
+    #[test]
+    fn test_watchlist_matches() {
+        let alice = SuiAddress::random_for_testing_only();
+        let bob = SuiAddress::random_for_testing_only();
+        let coin = ObjectID::random();
+        let watchlist = Watchlist::new(vec![
+            WatchlistEntry {
+                entity: WatchedEntity::Address(alice),
+                rule: "alice".to_string(),
+            },
+            WatchlistEntry {
+                entity: WatchedEntity::Object(coin),
+                rule: "coin".to_string(),
+            },
+        ]);
+
+        let transactions = vec![
+            Transaction {
+                transaction_digest: "a".to_string(),
+                sender: alice.to_string(),
+                checkpoint_sequence_number: Some(1),
+                ..Default::default()
+            },
+            Transaction {
+                transaction_digest: "b".to_string(),
+                sender: bob.to_string(),
+                checkpoint_sequence_number: Some(1),
+                ..Default::default()
+            },
+        ];
+        let recipients = vec![Recipient {
+            transaction_digest: "b".to_string(),
+            checkpoint_sequence_number: 1,
+            sender: bob.to_string(),
+            recipient: alice.to_string(),
+            ..Default::default()
+        }];
+        // the coin is both an input and changed by the same transaction
+        let input_objects = vec![InputObject {
+            transaction_digest: "b".to_string(),
+            checkpoint_sequence_number: 1,
+            object_id: coin.to_string(),
+            ..Default::default()
+        }];
+        let changed_objects = vec![ChangedObject {
+            transaction_digest: "b".to_string(),
+            checkpoint_sequence_number: 1,
+            object_id: coin.to_string(),
+            ..Default::default()
+        }];
+
+        let matches =
+            watchlist.matches(&transactions, &recipients, &input_objects, &changed_objects);
+        let matches = matches
+            .into_iter()
+            .map(|m| (m.transaction_digest, m.entity, m.rule))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            matches,
+            vec![
+                (
+                    "a".to_string(),
+                    WatchedEntityId::Address(alice.to_string()),
+                    "alice".to_string()
+                ),
+                (
+                    "b".to_string(),
+                    WatchedEntityId::Address(alice.to_string()),
+                    "alice".to_string()
+                ),
+                (
+                    "b".to_string(),
+                    WatchedEntityId::Object(coin.to_string()),
+                    "coin".to_string()
+                ),
+            ]
+        );
+    }
+}
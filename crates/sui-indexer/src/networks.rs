@@ -0,0 +1,189 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashSet;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+use regex::Regex;
+use serde::Deserialize;
+use sui_types::base_types::ObjectID;
+
+use crate::errors::IndexerError;
+use crate::IndexerConfig;
+
+// Example networks config, each network is indexed into the Postgres schema of its name
+// in the database of db_url, with the other options of the indexer:
+//
+//   - name: mainnet
+//     rpc_client_url: https://fullnode.mainnet.sui.io:443
+//     rpc_server_port: 9000
+//   - name: testnet
+//     rpc_client_url: https://fullnode.testnet.sui.io:443
+//     rpc_server_port: 9001
+
+/// A network indexed by an indexer process along with other networks, into its own
+/// Postgres schema. Each schema has its own checkpoints table, so that every network
+/// resumes from its own last indexed checkpoint.
+#[derive(Clone, Debug, Deserialize)]
+pub struct NetworkConfig {
+    pub name: String,
+    pub rpc_client_url: String,
+    #[serde(default)]
+    pub additional_rpc_client_urls: Vec<String>,
+    // RPC and export servers of networks served by the same process need their own ports.
+    pub rpc_server_port: Option<u16>,
+    pub export_server_port: Option<u16>,
+    pub genesis_blob_path: Option<PathBuf>,
+    pub checkpoint_archive_config: Option<PathBuf>,
+    pub name_service_registry_id: Option<ObjectID>,
+}
+
+impl NetworkConfig {
+    /// Postgres schema of the tables of the network.
+    pub fn schema(&self) -> &str {
+        &self.name
+    }
+
+    /// Config of the indexer of the network, `config` with the options of the network.
+    pub fn indexer_config(&self, config: &IndexerConfig) -> IndexerConfig {
+        IndexerConfig {
+            rpc_client_url: self.rpc_client_url.clone(),
+            additional_rpc_client_urls: self.additional_rpc_client_urls.clone(),
+            rpc_server_port: self.rpc_server_port.unwrap_or(config.rpc_server_port),
+            export_server_port: self.export_server_port,
+            genesis_blob_path: self.genesis_blob_path.clone(),
+            checkpoint_archive_config: self.checkpoint_archive_config.clone(),
+            // packages of the same id differ across networks, e.g. the framework
+            module_cache_path: config
+                .module_cache_path
+                .as_ref()
+                .map(|path| path.join(&self.name)),
+            name_service_registry_id: self.name_service_registry_id,
+            networks_config: None,
+            ..config.clone()
+        }
+    }
+}
+
+/// Reads and checks the YAML list of networks at `path`.
+pub fn load_network_configs(path: &Path) -> Result<Vec<NetworkConfig>, IndexerError> {
+    let file = File::open(path).map_err(|e| {
+        IndexerError::InvalidArgumentError(format!(
+            "Failed opening networks config {path:?} with error {e}"
+        ))
+    })?;
+    let networks: Vec<NetworkConfig> = serde_yaml::from_reader(file).map_err(|e| {
+        IndexerError::InvalidArgumentError(format!(
+            "Failed parsing networks config {path:?} with error {e}"
+        ))
+    })?;
+    check_network_configs(&networks)?;
+    Ok(networks)
+}
+
+fn check_network_configs(networks: &[NetworkConfig]) -> Result<(), IndexerError> {
+    if networks.is_empty() {
+        return Err(IndexerError::InvalidArgumentError(
+            "Networks config has no networks".to_string(),
+        ));
+    }
+    // names are the schema names of the networks
+    let name_regex = Regex::new(r"^[a-z_][a-z0-9_]{0,62}$").unwrap();
+    let mut names = HashSet::new();
+    let mut ports = HashSet::new();
+    for network in networks {
+        let name = network.name.as_str();
+        if !name_regex.is_match(name)
+            || name.starts_with("pg_")
+            || name == "public"
+            || name == "information_schema"
+        {
+            return Err(IndexerError::InvalidArgumentError(format!(
+                "Invalid network name {name}, names are schema names of lowercase letters, \
+                digits and underscores, other than public, information_schema and pg_*"
+            )));
+        }
+        if !names.insert(name) {
+            return Err(IndexerError::InvalidArgumentError(format!(
+                "Multiple networks named {name}"
+            )));
+        }
+        for port in [network.rpc_server_port, network.export_server_port]
+            .into_iter()
+            .flatten()
+        {
+            if !ports.insert(port) {
+                return Err(IndexerError::InvalidArgumentError(format!(
+                    "Port {port} of network {name} is used by another network"
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::IndexerConfig;
+
+    use super::{check_network_configs, NetworkConfig};
+
+    fn network(name: &str, rpc_server_port: Option<u16>) -> NetworkConfig {
+        NetworkConfig {
+            name: name.to_string(),
+            rpc_client_url: format!("https://fullnode.{name}.sui.io:443"),
+            additional_rpc_client_urls: vec![],
+            rpc_server_port,
+            export_server_port: None,
+            genesis_blob_path: None,
+            checkpoint_archive_config: None,
+            name_service_registry_id: None,
+        }
+    }
+
+    #[test]
+    fn test_check_network_configs() {
+        assert!(check_network_configs(&[]).is_err());
+        assert!(
+            check_network_configs(&[network("mainnet", Some(9000)), network("testnet", None)])
+                .is_ok()
+        );
+        for name in ["Mainnet", "main-net", "1net", "public", "pg_temp", ""] {
+            assert!(check_network_configs(&[network(name, None)]).is_err());
+        }
+        assert!(
+            check_network_configs(&[network("devnet", None), network("devnet", None)]).is_err()
+        );
+        assert!(check_network_configs(&[
+            network("mainnet", Some(9000)),
+            network("testnet", Some(9000))
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_network_indexer_config() {
+        let networks: Vec<NetworkConfig> = serde_yaml::from_str(
+            "- name: mainnet\n  \
+            rpc_client_url: https://fullnode.mainnet.sui.io:443\n  \
+            rpc_server_port: 9001\n",
+        )
+        .unwrap();
+        let base = IndexerConfig {
+            checkpoint_commit_batch_rows: 100,
+            module_cache_path: Some("/var/cache/indexer".into()),
+            ..Default::default()
+        };
+        let config = networks[0].indexer_config(&base);
+        assert_eq!(networks[0].schema(), "mainnet");
+        assert_eq!(config.rpc_client_url, "https://fullnode.mainnet.sui.io:443");
+        assert_eq!(config.rpc_server_port, 9001);
+        assert_eq!(config.checkpoint_commit_batch_rows, 100);
+        assert_eq!(config.db_url, base.db_url);
+        assert_eq!(
+            config.module_cache_path,
+            Some("/var/cache/indexer/mainnet".into())
+        );
+    }
+}
@@ -0,0 +1,120 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use fastcrypto::encoding::{Base64, Encoding};
+use serde::{Deserialize, Serialize};
+
+use crate::errors::IndexerError;
+
+/// Position in a paginated query, handed to clients as an opaque string. The encoding
+/// is versioned so that the fields of a cursor can change without breaking cursors held
+/// by clients, which are only ever decoded back by the indexer.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub enum Cursor {
+    Transaction {
+        tx_sequence_number: i64,
+    },
+    ObjectVersion {
+        version: u64,
+    },
+    Epoch {
+        epoch: u64,
+    },
+    CommitteeIndex {
+        committee_index: u64,
+    },
+    Watchlist {
+        entity_kind: String,
+        entity_id: String,
+        rule: String,
+    },
+}
+
+// Every format ever handed out is kept here, new formats are only added at the end so
+// that the BCS tag of existing ones does not change.
+#[derive(Serialize, Deserialize)]
+enum VersionedCursor {
+    V1(Cursor),
+}
+
+impl Cursor {
+    pub fn encode(&self) -> String {
+        // unwrap is safe b/c BCS serialization of the cursor cannot fail.
+        Base64::encode(bcs::to_bytes(&VersionedCursor::V1(self.clone())).unwrap())
+    }
+
+    pub fn decode(cursor: &str) -> Result<Self, IndexerError> {
+        let invalid =
+            |e: String| IndexerError::InvalidArgumentError(format!("Invalid cursor {cursor}: {e}"));
+        let bytes = Base64::decode(cursor).map_err(|e| invalid(e.to_string()))?;
+        match bcs::from_bytes(&bytes).map_err(|e| invalid(e.to_string()))? {
+            VersionedCursor::V1(cursor) => Ok(cursor),
+        }
+    }
+
+    /// Decodes a cursor and extracts the position of one query from it, cursors handed out
+    /// by another query are rejected rather than reinterpreted.
+    pub fn decode_with<T>(
+        cursor: &str,
+        extract: impl FnOnce(Cursor) -> Option<T>,
+    ) -> Result<T, IndexerError> {
+        extract(Self::decode(cursor)?).ok_or_else(|| {
+            IndexerError::InvalidArgumentError(format!(
+                "Cursor {cursor} does not belong to this query"
+            ))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Cursor;
+
+    #[test]
+    fn test_cursor_round_trip() {
+        let cursors = [
+            Cursor::Transaction {
+                tx_sequence_number: 42,
+            },
+            Cursor::ObjectVersion { version: 7 },
+            Cursor::Epoch { epoch: 3 },
+            Cursor::CommitteeIndex { committee_index: 1 },
+            Cursor::Watchlist {
+                entity_kind: "Address".to_string(),
+                entity_id: "0x2".to_string(),
+                rule: "AnyTransaction".to_string(),
+            },
+        ];
+        for cursor in cursors {
+            assert_eq!(Cursor::decode(&cursor.encode()).unwrap(), cursor);
+        }
+    }
+
+    #[test]
+    fn test_invalid_cursor() {
+        assert!(Cursor::decode("not a cursor").is_err());
+        // valid base64 but an unknown version
+        assert!(Cursor::decode("Bw==").is_err());
+        let mut encoded = Cursor::Transaction {
+            tx_sequence_number: 42,
+        }
+        .encode();
+        encoded.truncate(4);
+        assert!(Cursor::decode(&encoded).is_err());
+    }
+
+    #[test]
+    fn test_cursor_of_another_query() {
+        let encoded = Cursor::Epoch { epoch: 3 }.encode();
+        let epoch = Cursor::decode_with(&encoded, |c| match c {
+            Cursor::Epoch { epoch } => Some(epoch),
+            _ => None,
+        });
+        assert_eq!(epoch.unwrap(), 3);
+        let version = Cursor::decode_with(&encoded, |c| match c {
+            Cursor::ObjectVersion { version } => Some(version),
+            _ => None,
+        });
+        assert!(version.is_err());
+    }
+}
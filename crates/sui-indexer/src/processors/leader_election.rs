@@ -0,0 +1,77 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use tracing::{error, info, warn};
+
+use crate::errors::IndexerError;
+use crate::metrics::IndexerMetrics;
+use crate::store::IndexerStore;
+
+/// Election of the one of several replicas of a fullnode sync worker that writes, through
+/// a Postgres advisory lock named `lock_name`. The lock is held by the session of a
+/// dedicated connection, so that it is released as soon as the leader dies or loses its
+/// connection, and a standby replica takes over within `interval`. Writes of the store only
+/// commit while it holds the lock, so that a leader stops writing as soon as it loses it.
+pub struct LeaderElection<S> {
+    store: S,
+    lock_name: String,
+    interval: Duration,
+    metrics: IndexerMetrics,
+}
+
+impl<S> LeaderElection<S>
+where
+    S: IndexerStore + Sync + Send + 'static,
+{
+    pub fn new(store: S, lock_name: String, interval: Duration, metrics: IndexerMetrics) -> Self {
+        Self {
+            store,
+            lock_name,
+            interval,
+            metrics,
+        }
+    }
+
+    /// Waits until this replica is the leader.
+    pub async fn acquire(&self) {
+        self.metrics.is_leader.set(0);
+        info!(
+            "Standing by until the leader lock {} is taken...",
+            self.lock_name
+        );
+        loop {
+            match self
+                .store
+                .try_acquire_leader_lock(self.lock_name.clone())
+                .await
+            {
+                Ok(true) => break,
+                Ok(false) => {}
+                Err(e) => warn!("Failed trying the leader lock with error: {:?}", e),
+            }
+            tokio::time::sleep(self.interval).await;
+        }
+        self.metrics.is_leader.set(1);
+        info!("Took the leader lock {}", self.lock_name);
+    }
+
+    /// Checks every `interval` that this replica still holds the leader lock, and returns
+    /// once it does not, e.g. as its connection was lost.
+    pub async fn wait_until_lost(&self) -> IndexerError {
+        loop {
+            tokio::time::sleep(self.interval).await;
+            match self.store.holds_leader_lock().await {
+                Ok(true) => continue,
+                Ok(false) => error!("Lost the leader lock {}", self.lock_name),
+                Err(e) => error!(
+                    "Lost the leader lock {} with error: {:?}",
+                    self.lock_name, e
+                ),
+            }
+            self.metrics.is_leader.set(0);
+            return IndexerError::LeaderLockLost(self.lock_name.clone());
+        }
+    }
+}
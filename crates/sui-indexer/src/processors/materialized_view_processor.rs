@@ -0,0 +1,68 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use tracing::{info, warn};
+
+use crate::metrics::IndexerMetrics;
+use crate::models::materialized_views::MaterializedView;
+use crate::store::IndexerStore;
+
+/// Refreshes the enabled materialized views one after the other, on startup and then
+/// every `interval`. Failed refreshes are retried on the next round.
+pub struct MaterializedViewProcessor<S> {
+    store: S,
+    views: Vec<MaterializedView>,
+    interval: Duration,
+    metrics: IndexerMetrics,
+}
+
+impl<S> MaterializedViewProcessor<S>
+where
+    S: IndexerStore + Sync + Send + 'static,
+{
+    pub fn new(
+        store: S,
+        views: Vec<MaterializedView>,
+        interval: Duration,
+        metrics: IndexerMetrics,
+    ) -> Self {
+        Self {
+            store,
+            views,
+            interval,
+            metrics,
+        }
+    }
+
+    pub async fn run(self) {
+        info!(
+            "Indexer materialized view processor started, refreshing {:?}...",
+            self.views
+        );
+        let mut interval = tokio::time::interval(self.interval);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            for view in &self.views {
+                let _timer = self
+                    .metrics
+                    .materialized_view_refresh_latency
+                    .with_label_values(&[view.name()])
+                    .start_timer();
+                if let Err(e) = self.store.refresh_materialized_view(*view).await {
+                    warn!(
+                        "Failed refreshing materialized view {} with error: {:?}",
+                        view.name(),
+                        e
+                    );
+                    self.metrics
+                        .total_materialized_view_refresh_failure
+                        .with_label_values(&[view.name()])
+                        .inc();
+                }
+            }
+        }
+    }
+}
@@ -3,5 +3,10 @@
 
 pub mod address_processor;
 pub mod checkpoint_metrics_processor;
+pub mod leader_election;
+pub mod materialized_view_processor;
 pub mod object_processor;
 pub mod processor_orchestrator;
+pub mod pruner;
+pub mod reexecution_processor;
+pub mod table_size_processor;
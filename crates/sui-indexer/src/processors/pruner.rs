@@ -0,0 +1,97 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use tracing::{info, warn};
+
+use crate::errors::IndexerError;
+use crate::metrics::IndexerMetrics;
+use crate::models::retention::RetentionPolicy;
+use crate::store::IndexerStore;
+
+const PRUNING_INTERVAL: Duration = Duration::from_secs(600);
+// checkpoints pruned per DB transaction, so that a backlog is pruned in small deletes
+const PRUNING_BATCH_CHECKPOINTS: i64 = 1000;
+
+/// Prunes each table family of the checkpoints older than its retention, on startup and
+/// then every `PRUNING_INTERVAL`. Families found through transactions are pruned before
+/// transactions, and a round stops at the first failure, to be resumed on the next one.
+pub struct Pruner<S> {
+    store: S,
+    policies: Vec<RetentionPolicy>,
+    metrics: IndexerMetrics,
+}
+
+impl<S> Pruner<S>
+where
+    S: IndexerStore + Sync + Send + 'static,
+{
+    /// `policies` must be checked and ordered by `parse_retention_policies`.
+    pub fn new(store: S, policies: Vec<RetentionPolicy>, metrics: IndexerMetrics) -> Self {
+        Self {
+            store,
+            policies,
+            metrics,
+        }
+    }
+
+    pub async fn run(self) {
+        info!(
+            "Indexer pruner started with policies {:?}...",
+            self.policies
+        );
+        let mut interval = tokio::time::interval(PRUNING_INTERVAL);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.prune().await {
+                warn!("Failed pruning with error: {:?}", e);
+            }
+        }
+    }
+
+    async fn prune(&self) -> Result<(), IndexerError> {
+        let current_epoch = self.store.get_current_epoch().await?.epoch;
+        for policy in &self.policies {
+            // the current epoch and the epochs - 1 before it are kept
+            let first_kept_epoch = match (current_epoch + 1).checked_sub(policy.epochs) {
+                Some(epoch) => epoch as i64,
+                None => continue,
+            };
+            let end = match self
+                .store
+                .get_epoch_first_checkpoint(first_kept_epoch)
+                .await?
+            {
+                Some(checkpoint) => checkpoint,
+                None => continue,
+            };
+            let mut start = match self.store.get_pruned_checkpoint(policy.table).await? {
+                Some(checkpoint) => checkpoint,
+                None => continue,
+            };
+            let mut pruned_rows = 0;
+            while start < end {
+                let batch_end = (start + PRUNING_BATCH_CHECKPOINTS).min(end);
+                let rows = self
+                    .store
+                    .prune_checkpoints(policy.table, start, batch_end)
+                    .await?;
+                self.metrics
+                    .total_pruned_rows
+                    .with_label_values(&[policy.table.name()])
+                    .inc_by(rows as u64);
+                pruned_rows += rows;
+                start = batch_end;
+            }
+            if pruned_rows > 0 {
+                info!(
+                    "Pruned {pruned_rows} rows of {} before checkpoint {end} of epoch {first_kept_epoch}",
+                    policy.table.name()
+                );
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,536 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use move_binary_format::CompiledModule;
+use move_bytecode_utils::module_cache::GetModule;
+use move_core_types::language_storage::ModuleId;
+use prometheus::Registry;
+use tokio::runtime::Handle;
+use tracing::{info, warn};
+
+use sui_json_rpc_types::{SuiTransactionBlockEffects, SuiTransactionBlockEffectsAPI};
+use sui_protocol_config::{Chain, ProtocolConfig};
+use sui_types::base_types::{ObjectID, ObjectRef, SequenceNumber};
+use sui_types::digests::{TransactionDigest, TransactionEffectsDigest};
+use sui_types::error::{SuiError, SuiResult};
+use sui_types::gas::SuiGasStatus;
+use sui_types::message_envelope::Message;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use sui_types::metrics::LimitsMetrics;
+use sui_types::object::{Object, Owner};
+use sui_types::storage::{
+    get_module_by_id, BackingPackageStore, ChildObjectResolver, ObjectStore, ParentSync,
+};
+use sui_types::transaction::{InputObjectKind, InputObjects, SenderSignedData, TransactionDataAPI};
+
+use crate::errors::IndexerError;
+use crate::metrics::IndexerMetrics;
+use crate::models::compression::decompress_payload;
+use crate::models::reexecution::ReexecutionMismatch;
+use crate::models::transactions::Transaction;
+use crate::store::IndexerStore;
+
+const REEXECUTION_BATCH_SIZE: usize = 100;
+// sampled transactions are polled this often once the processor caught up
+const REEXECUTION_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Re-executes one in `sample_interval` indexed transactions locally against the object
+/// versions of the object history, and records the ones whose effects digest differs from
+/// the one of their checkpoint. Starts from the latest sampled transactions, and only
+/// re-executes transactions of checkpoints whose objects are committed.
+pub struct ReexecutionProcessor<S> {
+    store: S,
+    sample_interval: u64,
+    metrics: IndexerMetrics,
+    limits_metrics: Arc<LimitsMetrics>,
+}
+
+impl<S> ReexecutionProcessor<S>
+where
+    S: IndexerStore + Clone + Sync + Send + 'static,
+{
+    pub fn new(store: S, sample_interval: u64, metrics: IndexerMetrics) -> Self {
+        Self {
+            store,
+            sample_interval,
+            metrics,
+            // limits of the local executions are not exported
+            limits_metrics: Arc::new(LimitsMetrics::new(&Registry::new())),
+        }
+    }
+
+    pub async fn run(self) {
+        info!(
+            "Indexer re-execution processor started, re-executing one in {} transactions...",
+            self.sample_interval
+        );
+        let chain = match self.store.get_chain_identifier().await {
+            Ok(Some(chain_identifier)) => chain_identifier.chain().unwrap_or(Chain::Unknown),
+            _ => Chain::Unknown,
+        };
+        let mut sampler = ReexecutionSampler::new(self.sample_interval);
+        loop {
+            // objects, and so the object history, are committed after transactions
+            let watermark = match self
+                .store
+                .get_latest_object_checkpoint_sequence_number()
+                .await
+            {
+                Ok(watermark) => watermark,
+                Err(e) => {
+                    warn!(
+                        "Failed reading the latest checkpoint of committed objects with error: {:?}",
+                        e
+                    );
+                    tokio::time::sleep(REEXECUTION_POLL_INTERVAL).await;
+                    continue;
+                }
+            };
+            let sample = match self
+                .store
+                .get_reexecution_sample(
+                    sampler.cursor,
+                    self.sample_interval,
+                    REEXECUTION_BATCH_SIZE,
+                )
+                .await
+            {
+                Ok(sample) => sample,
+                Err(e) => {
+                    warn!(
+                        "Failed reading transactions to re-execute with error: {:?}",
+                        e
+                    );
+                    tokio::time::sleep(REEXECUTION_POLL_INTERVAL).await;
+                    continue;
+                }
+            };
+            let sampled = sample.len();
+            let transactions = sampler.take(sample, watermark);
+            let caught_up = sampled < REEXECUTION_BATCH_SIZE || transactions.len() < sampled;
+            for transaction in transactions {
+                let digest = transaction.transaction_digest.clone();
+                match self.reexecute(transaction, chain).await {
+                    Ok(None) => self.metrics.total_transactions_reexecuted.inc(),
+                    Ok(Some(mismatch)) => {
+                        self.metrics.total_transactions_reexecuted.inc();
+                        self.metrics.total_reexecution_mismatches.inc();
+                        warn!(
+                            transaction = digest,
+                            expected = mismatch.expected_effects_digest,
+                            local = mismatch.local_effects_digest,
+                            "Re-executed transaction has effects differing from its checkpoint"
+                        );
+                        if let Err(e) = self.store.persist_reexecution_mismatch(mismatch).await {
+                            warn!(
+                                "Failed recording re-execution mismatch of transaction {} with error: {:?}",
+                                digest, e
+                            );
+                        }
+                    }
+                    Err(e) => {
+                        self.metrics.total_reexecutions_skipped.inc();
+                        warn!(
+                            "Skipped re-executing transaction {} with error: {}",
+                            digest, e
+                        );
+                    }
+                }
+            }
+            if caught_up {
+                tokio::time::sleep(REEXECUTION_POLL_INTERVAL).await;
+            }
+        }
+    }
+
+    // Re-executes `transaction` with the epoch data and protocol config of its epoch, the
+    // mismatch if its effects digest differs from the one of its checkpoint contents.
+    async fn reexecute(
+        &self,
+        transaction: Transaction,
+        chain: Chain,
+    ) -> Result<Option<ReexecutionMismatch>, IndexerError> {
+        let data: SenderSignedData =
+            bcs::from_bytes(&decompress_payload(&transaction.raw_transaction)?)?;
+        if data.transaction_data().kind().is_system_tx() {
+            return Err(IndexerError::NotSupportedError(
+                "System transactions are not re-executed".to_string(),
+            ));
+        }
+        let effects: SuiTransactionBlockEffects =
+            serde_json::from_str(&transaction.transaction_effects_content).map_err(|e| {
+                IndexerError::SerdeError(format!(
+                    "Failed parsing effects of transaction {} with error: {e}",
+                    transaction.transaction_digest
+                ))
+            })?;
+        let tx_digest: TransactionDigest = transaction.transaction_digest.parse().map_err(|e| {
+            IndexerError::SerdeError(format!(
+                "Failed parsing transaction digest {} with error: {e:?}",
+                transaction.transaction_digest
+            ))
+        })?;
+        let checkpoint = transaction.checkpoint_sequence_number.ok_or_else(|| {
+            IndexerError::NotSupportedError(format!("Transaction {tx_digest} has no checkpoint"))
+        })? as CheckpointSequenceNumber;
+        let inclusion = self
+            .store
+            .get_transaction_inclusion(tx_digest)
+            .await?
+            .ok_or_else(|| {
+                IndexerError::NotSupportedError(format!(
+                    "Checkpoint contents of transaction {tx_digest} are not indexed"
+                ))
+            })?;
+
+        let epoch = effects.executed_epoch();
+        let protocol_version = self
+            .store
+            .get_protocol_config(epoch)
+            .await?
+            .protocol_version;
+        let protocol_config = ProtocolConfig::get_for_version_if_supported(protocol_version, chain)
+            .ok_or_else(|| {
+                IndexerError::NotSupportedError(format!(
+                    "Protocol version {protocol_version:?} of epoch {epoch} is not supported"
+                ))
+            })?;
+        let epoch_info = self
+            .store
+            .get_epochs(epoch.checked_sub(1), 1, None)
+            .await?
+            .into_iter()
+            .find(|info| info.epoch == epoch)
+            .ok_or_else(|| {
+                IndexerError::NotSupportedError(format!("Epoch {epoch} is not indexed"))
+            })?;
+        let reference_gas_price = epoch_info.reference_gas_price.ok_or_else(|| {
+            IndexerError::NotSupportedError(format!("Epoch {epoch} has no reference gas price"))
+        })?;
+
+        // objects written by the transaction have its lamport version, as the gas object
+        let lamport_version = effects.gas_object().reference.version;
+        let shared_object_refs: Vec<ObjectRef> = effects
+            .shared_objects()
+            .iter()
+            .map(|shared| shared.to_object_ref())
+            .collect();
+        let dependencies: BTreeSet<TransactionDigest> =
+            effects.dependencies().iter().copied().collect();
+        let objects = HistoricalObjectStore {
+            store: self.store.clone(),
+            runtime: Handle::current(),
+            checkpoint,
+            lamport_version,
+        };
+        let limits_metrics = self.limits_metrics.clone();
+        let (local_effects_digest, local_error) = tokio::task::spawn_blocking(move || {
+            let tx_data = data.transaction_data();
+            let shared_versions: HashMap<ObjectID, SequenceNumber> = shared_object_refs
+                .iter()
+                .map(|(id, version, _)| (*id, *version))
+                .collect();
+            let input_objects = tx_data
+                .input_objects()?
+                .into_iter()
+                .map(|kind| {
+                    let object = match &kind {
+                        InputObjectKind::MovePackage(id) => objects.get_package_object(id)?,
+                        InputObjectKind::ImmOrOwnedMoveObject((id, version, _)) => {
+                            objects.get_object_by_key(id, *version)?
+                        }
+                        InputObjectKind::SharedMoveObject { id, .. } => {
+                            match shared_versions.get(id) {
+                                Some(version) => objects.get_object_by_key(id, *version)?,
+                                None => None,
+                            }
+                        }
+                    };
+                    object.map(|object| (kind, object)).ok_or_else(|| {
+                        IndexerError::NotSupportedError(format!(
+                            "Input object {:?} is not in the object history",
+                            kind.object_id()
+                        ))
+                    })
+                })
+                .collect::<Result<Vec<_>, IndexerError>>()?;
+            let gas_status = SuiGasStatus::new(
+                tx_data.gas_budget(),
+                tx_data.gas_price(),
+                reference_gas_price,
+                &protocol_config,
+            )?;
+            let executor = sui_execution::executor(&protocol_config, false, true)?;
+            let (_, local_effects, result) = executor.execute_transaction_to_effects(
+                Arc::new(objects),
+                &protocol_config,
+                limits_metrics,
+                false,
+                &HashSet::new(),
+                &epoch,
+                epoch_info.epoch_start_timestamp,
+                InputObjects::new(input_objects),
+                shared_object_refs,
+                tx_data.gas().to_vec(),
+                gas_status,
+                tx_data.kind().clone(),
+                tx_data.sender(),
+                tx_digest,
+                dependencies,
+            );
+            Ok::<_, IndexerError>((
+                local_effects.digest(),
+                result.err().map(|e| format!("{e:?}")),
+            ))
+        })
+        .await??;
+
+        Ok(compare_effects(
+            tx_digest,
+            checkpoint,
+            inclusion.effects_digest,
+            local_effects_digest,
+            local_error,
+        ))
+    }
+}
+
+// Which sampled transactions to re-execute next, in id order from the latest sampled ones
+// on. A transaction is re-executed once the objects of its checkpoint are committed, as
+// its inputs are read from the object history as of its checkpoint.
+struct ReexecutionSampler {
+    sample_interval: u64,
+    // id of the last transaction re-executed or passed over, the latest transactions are
+    // sampled first if not set
+    cursor: Option<i64>,
+}
+
+impl ReexecutionSampler {
+    fn new(sample_interval: u64) -> Self {
+        Self {
+            sample_interval: sample_interval.max(1),
+            cursor: None,
+        }
+    }
+
+    // the store samples transactions the same way
+    fn is_sampled(&self, id: i64) -> bool {
+        id % self.sample_interval as i64 == 0
+    }
+
+    // The transactions of `sample` to re-execute, moving the cursor past them. Stops at the
+    // first transaction of a checkpoint beyond `watermark`, the latest checkpoint of
+    // committed objects, which is sampled again once its objects are committed.
+    fn take(&mut self, sample: Vec<Transaction>, watermark: i64) -> Vec<Transaction> {
+        let mut sample: Vec<_> = sample
+            .into_iter()
+            .filter(|transaction| transaction.id.map_or(false, |id| self.is_sampled(id)))
+            .collect();
+        sample.sort_by_key(|transaction| transaction.id);
+        // the latest transactions are waited for rather than sampled again and again
+        if self.cursor.is_none() {
+            self.cursor = sample
+                .first()
+                .and_then(|transaction| transaction.id)
+                .map(|id| id - 1);
+        }
+        let mut taken = vec![];
+        for transaction in sample {
+            if transaction
+                .checkpoint_sequence_number
+                .map_or(false, |checkpoint| checkpoint > watermark)
+            {
+                break;
+            }
+            self.cursor = transaction.id;
+            taken.push(transaction);
+        }
+        taken
+    }
+}
+
+// The mismatch of a transaction whose local effects differ from those of its checkpoint.
+fn compare_effects(
+    transaction_digest: TransactionDigest,
+    checkpoint: CheckpointSequenceNumber,
+    expected_effects_digest: TransactionEffectsDigest,
+    local_effects_digest: TransactionEffectsDigest,
+    local_error: Option<String>,
+) -> Option<ReexecutionMismatch> {
+    if local_effects_digest == expected_effects_digest {
+        return None;
+    }
+    Some(ReexecutionMismatch {
+        transaction_digest: transaction_digest.base58_encode(),
+        checkpoint_sequence_number: checkpoint as i64,
+        expected_effects_digest: expected_effects_digest.base58_encode(),
+        local_effects_digest: local_effects_digest.base58_encode(),
+        local_error,
+        reexecuted_at_ms: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as i64,
+    })
+}
+
+// Objects as of a transaction, the latest versions below its lamport version written by
+// its checkpoint or earlier. Lookups block on the store, so executions against it run on
+// blocking threads.
+struct HistoricalObjectStore<S> {
+    store: S,
+    runtime: Handle,
+    checkpoint: CheckpointSequenceNumber,
+    lamport_version: SequenceNumber,
+}
+
+impl<S: IndexerStore> HistoricalObjectStore<S> {
+    fn get_object_before(
+        &self,
+        object_id: &ObjectID,
+        before_version: SequenceNumber,
+    ) -> SuiResult<Option<Object>> {
+        self.runtime
+            .block_on(self.store.get_object_before_version(
+                *object_id,
+                self.checkpoint,
+                before_version.min(self.lamport_version),
+            ))
+            .map_err(|e| SuiError::GenericStorageError(e.to_string()))
+    }
+}
+
+impl<S: IndexerStore> BackingPackageStore for HistoricalObjectStore<S> {
+    fn get_package_object(&self, package_id: &ObjectID) -> SuiResult<Option<Object>> {
+        Ok(self
+            .get_object_before(package_id, self.lamport_version)?
+            .filter(|object| object.is_package()))
+    }
+}
+
+impl<S: IndexerStore> ChildObjectResolver for HistoricalObjectStore<S> {
+    fn read_child_object(
+        &self,
+        parent: &ObjectID,
+        child: &ObjectID,
+        child_version_upper_bound: SequenceNumber,
+    ) -> SuiResult<Option<Object>> {
+        let before_version = SequenceNumber::from_u64(child_version_upper_bound.value() + 1);
+        let child_object = match self.get_object_before(child, before_version)? {
+            Some(child_object) => child_object,
+            None => return Ok(None),
+        };
+        if child_object.owner != Owner::ObjectOwner((*parent).into()) {
+            return Err(SuiError::InvalidChildObjectAccess {
+                object: *child,
+                given_parent: *parent,
+                actual_owner: child_object.owner,
+            });
+        }
+        Ok(Some(child_object))
+    }
+}
+
+impl<S: IndexerStore> ObjectStore for HistoricalObjectStore<S> {
+    fn get_object(&self, object_id: &ObjectID) -> SuiResult<Option<Object>> {
+        self.get_object_before(object_id, self.lamport_version)
+    }
+
+    fn get_object_by_key(
+        &self,
+        object_id: &ObjectID,
+        version: SequenceNumber,
+    ) -> SuiResult<Option<Object>> {
+        Ok(self
+            .get_object_before(object_id, SequenceNumber::from_u64(version.value() + 1))?
+            .filter(|object| object.version() == version))
+    }
+}
+
+impl<S: IndexerStore> ParentSync for HistoricalObjectStore<S> {
+    fn get_latest_parent_entry_ref(&self, object_id: ObjectID) -> SuiResult<Option<ObjectRef>> {
+        Ok(self
+            .get_object_before(&object_id, self.lamport_version)?
+            .map(|object| object.compute_object_reference()))
+    }
+}
+
+impl<S: IndexerStore> GetModule for HistoricalObjectStore<S> {
+    type Error = SuiError;
+    type Item = CompiledModule;
+
+    fn get_module_by_id(&self, id: &ModuleId) -> SuiResult<Option<CompiledModule>> {
+        get_module_by_id(self, id)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use sui_types::digests::{TransactionDigest, TransactionEffectsDigest};
+
+    use super::{compare_effects, ReexecutionSampler};
+    use crate::models::transactions::Transaction;
+
+    fn transaction(id: i64, checkpoint: i64) -> Transaction {
+        Transaction {
+            id: Some(id),
+            checkpoint_sequence_number: Some(checkpoint),
+            ..Default::default()
+        }
+    }
+
+    fn ids(transactions: &[Transaction]) -> Vec<i64> {
+        transactions.iter().filter_map(|t| t.id).collect()
+    }
+
+    #[test]
+    fn test_sampling() {
+        let mut sampler = ReexecutionSampler::new(5);
+        // the latest sample is read in descending id order
+        let sample = vec![transaction(15, 3), transaction(12, 3), transaction(10, 2)];
+        assert_eq!(vec![10, 15], ids(&sampler.take(sample, 3)));
+        assert_eq!(Some(15), sampler.cursor);
+        // an interval of 0 samples every transaction
+        let mut sampler = ReexecutionSampler::new(0);
+        let sample = vec![transaction(1, 0), transaction(2, 0)];
+        assert_eq!(vec![1, 2], ids(&sampler.take(sample, 0)));
+    }
+
+    #[test]
+    fn test_watermark() {
+        let mut sampler = ReexecutionSampler::new(1);
+        // the objects of checkpoint 2 are not committed yet
+        let sample = vec![transaction(1, 1), transaction(2, 2), transaction(3, 1)];
+        assert_eq!(vec![1], ids(&sampler.take(sample, 1)));
+        assert_eq!(Some(1), sampler.cursor);
+        // the first sample is waited for rather than skipped
+        let mut sampler = ReexecutionSampler::new(1);
+        assert!(sampler.take(vec![transaction(7, 4)], 3).is_empty());
+        assert_eq!(Some(6), sampler.cursor);
+        assert_eq!(vec![7], ids(&sampler.take(vec![transaction(7, 4)], 4)));
+        assert_eq!(Some(7), sampler.cursor);
+        // no objects committed yet
+        let mut sampler = ReexecutionSampler::new(1);
+        assert!(sampler.take(vec![transaction(0, 0)], -1).is_empty());
+    }
+
+    #[test]
+    fn test_compare_effects() {
+        let digest = TransactionDigest::random();
+        let expected = TransactionEffectsDigest::random();
+        assert!(compare_effects(digest, 1, expected, expected, None).is_none());
+        // the same effects despite an execution error are not a mismatch
+        assert!(compare_effects(digest, 1, expected, expected, Some("abort".into())).is_none());
+
+        let local = TransactionEffectsDigest::random();
+        let mismatch = compare_effects(digest, 1, expected, local, Some("abort".into())).unwrap();
+        assert_eq!(digest.base58_encode(), mismatch.transaction_digest);
+        assert_eq!(1, mismatch.checkpoint_sequence_number);
+        assert_eq!(expected.base58_encode(), mismatch.expected_effects_digest);
+        assert_eq!(local.base58_encode(), mismatch.local_effects_digest);
+        assert_eq!(Some("abort".to_string()), mismatch.local_error);
+    }
+}
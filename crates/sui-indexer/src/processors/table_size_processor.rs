@@ -0,0 +1,210 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use tracing::{info, warn};
+
+use crate::errors::IndexerError;
+use crate::metrics::IndexerMetrics;
+use crate::store::IndexerStore;
+
+// weight of the latest growth rate in the moving average of a table
+const GROWTH_AVERAGE_WEIGHT: f64 = 0.2;
+// growth rates of a table are compared to its average only once it has this many
+const MIN_GROWTH_SAMPLES: usize = 3;
+// tables growing slower than this are not alerted on, e.g. the small tables
+const MIN_ALERT_GROWTH_BYTES_PER_SEC: f64 = 1024.0;
+
+/// Samples the sizes of the tables every `interval` and exports them as gauges, warning
+/// about tables growing `alert_factor` times faster or slower than their moving average.
+pub struct TableSizeProcessor<S> {
+    store: S,
+    interval: Duration,
+    alert_factor: f64,
+    metrics: IndexerMetrics,
+}
+
+impl<S> TableSizeProcessor<S>
+where
+    S: IndexerStore + Sync + Send + 'static,
+{
+    pub fn new(store: S, interval: Duration, alert_factor: f64, metrics: IndexerMetrics) -> Self {
+        Self {
+            store,
+            interval,
+            alert_factor,
+            metrics,
+        }
+    }
+
+    pub async fn run(self) {
+        info!(
+            "Indexer table size processor started, sampling every {:?}...",
+            self.interval
+        );
+        let mut growths = HashMap::new();
+        let mut interval = tokio::time::interval(self.interval);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.sample(&mut growths).await {
+                warn!("Failed sampling table sizes with error: {:?}", e);
+            }
+        }
+    }
+
+    async fn sample(&self, growths: &mut HashMap<String, TableGrowth>) -> Result<(), IndexerError> {
+        let sizes = self.store.get_table_sizes().await?;
+        let now = Instant::now();
+        for size in sizes {
+            let table = size.table_name.as_str();
+            self.metrics
+                .table_size_bytes
+                .with_label_values(&[table])
+                .set(size.total_bytes);
+            self.metrics
+                .table_estimated_rows
+                .with_label_values(&[table])
+                .set(size.estimated_rows);
+            let growth = match growths.get_mut(table) {
+                Some(growth) => growth,
+                None => {
+                    growths.insert(table.to_string(), TableGrowth::new(size.total_bytes, now));
+                    continue;
+                }
+            };
+            let sample = match growth.record(size.total_bytes, now, self.alert_factor) {
+                Some(sample) => sample,
+                None => continue,
+            };
+            self.metrics
+                .table_growth_bytes_per_sec
+                .with_label_values(&[table])
+                .set(sample.rate as i64);
+            if sample.anomalous {
+                warn!(
+                    table,
+                    bytes = size.total_bytes,
+                    rate = sample.rate as i64,
+                    average_rate = sample.average_rate as i64,
+                    "Table growth deviates sharply from its average"
+                );
+                self.metrics
+                    .total_table_growth_anomalies
+                    .with_label_values(&[table])
+                    .inc();
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Growth rate in bytes per second of a table between two samples of its size.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct GrowthSample {
+    rate: f64,
+    // moving average of the rates before this one
+    average_rate: f64,
+    anomalous: bool,
+}
+
+/// Moving average of the growth rate of a table.
+#[derive(Debug)]
+struct TableGrowth {
+    bytes: i64,
+    sampled_at: Instant,
+    average_rate: f64,
+    samples: usize,
+}
+
+impl TableGrowth {
+    fn new(bytes: i64, sampled_at: Instant) -> Self {
+        Self {
+            bytes,
+            sampled_at,
+            average_rate: 0.0,
+            samples: 0,
+        }
+    }
+
+    fn record(
+        &mut self,
+        bytes: i64,
+        sampled_at: Instant,
+        alert_factor: f64,
+    ) -> Option<GrowthSample> {
+        let elapsed = sampled_at.duration_since(self.sampled_at).as_secs_f64();
+        if elapsed <= 0.0 {
+            return None;
+        }
+        let rate = (bytes - self.bytes) as f64 / elapsed;
+        let average_rate = self.average_rate;
+        let anomalous = self.samples >= MIN_GROWTH_SAMPLES
+            && (rate > average_rate.max(MIN_ALERT_GROWTH_BYTES_PER_SEC) * alert_factor
+                || (average_rate >= MIN_ALERT_GROWTH_BYTES_PER_SEC
+                    && rate < average_rate / alert_factor));
+        self.average_rate = if self.samples == 0 {
+            rate
+        } else {
+            GROWTH_AVERAGE_WEIGHT * rate + (1.0 - GROWTH_AVERAGE_WEIGHT) * average_rate
+        };
+        self.samples += 1;
+        self.bytes = bytes;
+        self.sampled_at = sampled_at;
+        Some(GrowthSample {
+            rate,
+            average_rate,
+            anomalous,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, Instant};
+
+    use super::TableGrowth;
+
+    #[test]
+    fn test_table_growth() {
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+        let mut growth = TableGrowth::new(0, start);
+        // steady growth of 10 KiB per second
+        for i in 1..=5 {
+            let sample = growth
+                .record(i * 10_240 * 60, at(i as u64 * 60), 3.0)
+                .unwrap();
+            assert_eq!(sample.rate, 10_240.0);
+            assert!(!sample.anomalous);
+        }
+        // a burst of 41 times the usual rate
+        let sample = growth.record(6 * 10_240 * 60 + 40 * 10_240 * 60, at(360), 3.0);
+        assert!(sample.unwrap().anomalous);
+        // writes stop
+        let mut growth = TableGrowth::new(0, start);
+        for i in 1..=4 {
+            growth.record(i * 10_240 * 60, at(i as u64 * 60), 3.0);
+        }
+        let sample = growth.record(4 * 10_240 * 60, at(300), 3.0).unwrap();
+        assert_eq!(sample.rate, 0.0);
+        assert!(sample.anomalous);
+        // same instant
+        assert!(growth.record(0, at(300), 3.0).is_none());
+    }
+
+    #[test]
+    fn test_small_table_growth() {
+        let start = Instant::now();
+        let mut growth = TableGrowth::new(0, start);
+        // tables growing by a few bytes per second are not alerted on
+        for (i, bytes) in [60, 120, 180, 1_200, 1_200].into_iter().enumerate() {
+            let sample = growth
+                .record(bytes, start + Duration::from_secs(60 * (i as u64 + 1)), 3.0)
+                .unwrap();
+            assert!(!sample.anomalous);
+        }
+    }
+}
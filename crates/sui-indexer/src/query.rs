@@ -0,0 +1,352 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Typed filters over the transactions, events and objects tables. Filters are composed with
+//! `And` and `Or` and translated to a Diesel predicate by `ToPredicate`, so that a new way of
+//! querying a table is a new variant rather than a new store method.
+
+use diesel::expression::BoxableExpression;
+use diesel::pg::Pg;
+use diesel::sql_types::Bool;
+use diesel::{BoolExpressionMethods, ExpressionMethods, IntoSql, NullableExpressionMethods};
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::StructTag;
+use serde::{Deserialize, Serialize};
+
+use sui_json_rpc_types::EventFilter as SuiEventFilter;
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::digests::TransactionDigest;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+
+use crate::errors::IndexerError;
+use crate::models::events::decompose_struct_tag;
+use crate::schema::{events, objects, transactions};
+
+pub type BoxedPredicate<T> = Box<dyn BoxableExpression<T, Pg, SqlType = Bool> + Send>;
+
+/// Translation of a filter to a predicate on the table it filters.
+pub trait ToPredicate {
+    type Table;
+
+    fn to_predicate(&self) -> BoxedPredicate<Self::Table>;
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub enum TransactionFilter {
+    Sender(SuiAddress),
+    GasOwner(SuiAddress),
+    Checkpoint(CheckpointSequenceNumber),
+    Kind(String),
+    Success(bool),
+    Sponsored(bool),
+    /// Transactions with timestamp_ms within [start_ms, end_ms).
+    TimeRange {
+        start_ms: u64,
+        end_ms: u64,
+    },
+    /// Matches all transactions if empty.
+    And(Vec<TransactionFilter>),
+    /// Matches no transaction if empty.
+    Or(Vec<TransactionFilter>),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub enum EventFilter {
+    Sender(SuiAddress),
+    Transaction(TransactionDigest),
+    Package(ObjectID),
+    Module {
+        package: ObjectID,
+        module: Identifier,
+    },
+    /// Matches all instantiations of the type if it has no type params.
+    MoveEventType(StructTag),
+    /// Events whose type is defined in `package::module`.
+    MoveEventModule {
+        package: ObjectID,
+        module: Identifier,
+    },
+    /// Events with event_time_ms within [start_ms, end_ms).
+    TimeRange {
+        start_ms: u64,
+        end_ms: u64,
+    },
+    /// Matches all events if empty.
+    And(Vec<EventFilter>),
+    /// Matches no event if empty.
+    Or(Vec<EventFilter>),
+}
+
+/// Filter over the latest version of objects.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub enum ObjectFilter {
+    ObjectId(ObjectID),
+    AddressOwner(SuiAddress),
+    /// Exact type, including type params.
+    Type(String),
+    Package(ObjectID),
+    Module {
+        package: ObjectID,
+        module: Identifier,
+    },
+    /// Matches all objects if empty.
+    And(Vec<ObjectFilter>),
+    /// Matches no object if empty.
+    Or(Vec<ObjectFilter>),
+}
+
+macro_rules! combinators {
+    ($filter:ident) => {
+        impl $filter {
+            pub fn and(self, other: Self) -> Self {
+                match self {
+                    Self::And(mut filters) => {
+                        filters.push(other);
+                        Self::And(filters)
+                    }
+                    filter => Self::And(vec![filter, other]),
+                }
+            }
+
+            pub fn or(self, other: Self) -> Self {
+                match self {
+                    Self::Or(mut filters) => {
+                        filters.push(other);
+                        Self::Or(filters)
+                    }
+                    filter => Self::Or(vec![filter, other]),
+                }
+            }
+        }
+    };
+}
+
+combinators!(TransactionFilter);
+combinators!(EventFilter);
+combinators!(ObjectFilter);
+
+fn all_of<T: 'static>(predicates: impl Iterator<Item = BoxedPredicate<T>>) -> BoxedPredicate<T> {
+    predicates
+        .reduce(|acc, p| Box::new(acc.and(p)))
+        .unwrap_or_else(|| Box::new(true.into_sql::<Bool>()))
+}
+
+fn any_of<T: 'static>(predicates: impl Iterator<Item = BoxedPredicate<T>>) -> BoxedPredicate<T> {
+    predicates
+        .reduce(|acc, p| Box::new(acc.or(p)))
+        .unwrap_or_else(|| Box::new(false.into_sql::<Bool>()))
+}
+
+impl ToPredicate for TransactionFilter {
+    type Table = transactions::table;
+
+    fn to_predicate(&self) -> BoxedPredicate<transactions::table> {
+        match self {
+            Self::Sender(sender) => Box::new(transactions::sender.eq(sender.to_string())),
+            Self::GasOwner(owner) => Box::new(
+                transactions::gas_owner
+                    .assume_not_null()
+                    .eq(owner.to_string()),
+            ),
+            Self::Checkpoint(checkpoint) => Box::new(
+                transactions::checkpoint_sequence_number
+                    .assume_not_null()
+                    .eq(*checkpoint as i64),
+            ),
+            Self::Kind(kind) => Box::new(transactions::transaction_kind.eq(kind.clone())),
+            Self::Success(success) => Box::new(transactions::execution_success.eq(*success)),
+            Self::Sponsored(sponsored) => Box::new(transactions::sponsored.eq(*sponsored)),
+            Self::TimeRange { start_ms, end_ms } => Box::new(
+                transactions::timestamp_ms
+                    .assume_not_null()
+                    .ge(*start_ms as i64)
+                    .and(
+                        transactions::timestamp_ms
+                            .assume_not_null()
+                            .lt(*end_ms as i64),
+                    ),
+            ),
+            Self::And(filters) => all_of(filters.iter().map(|f| f.to_predicate())),
+            Self::Or(filters) => any_of(filters.iter().map(|f| f.to_predicate())),
+        }
+    }
+}
+
+impl ToPredicate for EventFilter {
+    type Table = events::table;
+
+    fn to_predicate(&self) -> BoxedPredicate<events::table> {
+        match self {
+            Self::Sender(sender) => Box::new(events::sender.eq(sender.to_string())),
+            Self::Transaction(digest) => {
+                Box::new(events::transaction_digest.eq(digest.base58_encode()))
+            }
+            Self::Package(package) => Box::new(events::package.eq(package.to_string())),
+            Self::Module { package, module } => Box::new(
+                events::package
+                    .eq(package.to_string())
+                    .and(events::module.eq(module.to_string())),
+            ),
+            Self::MoveEventType(struct_tag) => {
+                let (package, module, name, type_params) = decompose_struct_tag(struct_tag);
+                let predicate = events::event_type_package
+                    .eq(package)
+                    .and(events::event_type_module.eq(module))
+                    .and(events::event_type_name.eq(name));
+                if struct_tag.type_params.is_empty() {
+                    Box::new(predicate)
+                } else {
+                    Box::new(predicate.and(events::event_type_params.eq(type_params)))
+                }
+            }
+            Self::MoveEventModule { package, module } => Box::new(
+                events::event_type_package
+                    .eq(package.to_string())
+                    .and(events::event_type_module.eq(module.to_string())),
+            ),
+            Self::TimeRange { start_ms, end_ms } => Box::new(
+                events::event_time_ms
+                    .assume_not_null()
+                    .ge(*start_ms as i64)
+                    .and(events::event_time_ms.assume_not_null().lt(*end_ms as i64)),
+            ),
+            Self::And(filters) => all_of(filters.iter().map(|f| f.to_predicate())),
+            Self::Or(filters) => any_of(filters.iter().map(|f| f.to_predicate())),
+        }
+    }
+}
+
+impl ToPredicate for ObjectFilter {
+    type Table = objects::table;
+
+    fn to_predicate(&self) -> BoxedPredicate<objects::table> {
+        match self {
+            Self::ObjectId(object_id) => Box::new(objects::object_id.eq(object_id.to_string())),
+            Self::AddressOwner(owner) => Box::new(
+                objects::owner_address
+                    .assume_not_null()
+                    .eq(owner.to_string()),
+            ),
+            Self::Type(object_type) => Box::new(objects::object_type.eq(object_type.clone())),
+            Self::Package(package) => Box::new(
+                objects::object_type_package
+                    .assume_not_null()
+                    .eq(package.to_string()),
+            ),
+            Self::Module { package, module } => Box::new(
+                objects::object_type_package
+                    .assume_not_null()
+                    .eq(package.to_string())
+                    .and(
+                        objects::object_type_module
+                            .assume_not_null()
+                            .eq(module.to_string()),
+                    ),
+            ),
+            Self::And(filters) => all_of(filters.iter().map(|f| f.to_predicate())),
+            Self::Or(filters) => any_of(filters.iter().map(|f| f.to_predicate())),
+        }
+    }
+}
+
+impl TryFrom<SuiEventFilter> for EventFilter {
+    type Error = IndexerError;
+
+    fn try_from(filter: SuiEventFilter) -> Result<Self, Self::Error> {
+        Ok(match filter {
+            SuiEventFilter::All(filters) => Self::And(
+                filters
+                    .into_iter()
+                    .map(Self::try_from)
+                    .collect::<Result<_, _>>()?,
+            ),
+            SuiEventFilter::Any(filters) => Self::Or(
+                filters
+                    .into_iter()
+                    .map(Self::try_from)
+                    .collect::<Result<_, _>>()?,
+            ),
+            SuiEventFilter::And(left, right) => Self::try_from(*left)?.and(Self::try_from(*right)?),
+            SuiEventFilter::Or(left, right) => Self::try_from(*left)?.or(Self::try_from(*right)?),
+            SuiEventFilter::Sender(sender) => Self::Sender(sender),
+            SuiEventFilter::Transaction(digest) => Self::Transaction(digest),
+            SuiEventFilter::Package(package) => Self::Package(package),
+            SuiEventFilter::MoveModule { package, module } => Self::Module { package, module },
+            SuiEventFilter::MoveEventType(struct_tag) => Self::MoveEventType(struct_tag),
+            SuiEventFilter::MoveEventModule { package, module } => {
+                Self::MoveEventModule { package, module }
+            }
+            SuiEventFilter::TimeRange {
+                start_time,
+                end_time,
+            } => Self::TimeRange {
+                start_ms: start_time,
+                end_ms: end_time,
+            },
+            // TODO: Implement EventFilter to SQL
+            filter => {
+                return Err(IndexerError::NotSupportedError(format!(
+                    "Filter type [{filter:?}] not supported by the Indexer."
+                )))
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use diesel::debug_query;
+    use diesel::pg::Pg;
+    use diesel::QueryDsl;
+    use move_core_types::identifier::Identifier;
+    use sui_types::base_types::{ObjectID, SuiAddress};
+
+    use super::{EventFilter, ObjectFilter, ToPredicate, TransactionFilter};
+    use crate::schema::{events, objects, transactions};
+
+    #[test]
+    fn test_transaction_filter_to_sql() {
+        let sender = SuiAddress::random_for_testing_only();
+        let filter = TransactionFilter::Sender(sender)
+            .and(TransactionFilter::Kind(
+                "ProgrammableTransaction".to_string(),
+            ))
+            .and(TransactionFilter::Success(false).or(TransactionFilter::Sponsored(true)));
+        let query = transactions::table
+            .select(transactions::id)
+            .filter(filter.to_predicate());
+        assert_eq!(
+            format!(
+                "SELECT \"transactions\".\"id\" FROM \"transactions\" WHERE ((\"transactions\".\"sender\" = $1 AND \"transactions\".\"transaction_kind\" = $2) AND (\"transactions\".\"execution_success\" = $3 OR \"transactions\".\"sponsored\" = $4)) -- binds: [\"{sender}\", \"ProgrammableTransaction\", false, true]"
+            ),
+            debug_query::<Pg, _>(&query).to_string()
+        );
+    }
+
+    #[test]
+    fn test_empty_combinators_to_sql() {
+        let query = events::table
+            .select(events::id)
+            .filter(EventFilter::And(vec![]).to_predicate())
+            .filter(EventFilter::Or(vec![]).to_predicate());
+        assert_eq!(
+            "SELECT \"events\".\"id\" FROM \"events\" WHERE ($1 AND $2) -- binds: [true, false]",
+            debug_query::<Pg, _>(&query).to_string()
+        );
+    }
+
+    #[test]
+    fn test_object_filter_to_sql() {
+        let filter = ObjectFilter::Module {
+            package: ObjectID::from_single_byte(2),
+            module: Identifier::new("coin").unwrap(),
+        };
+        let query = objects::table
+            .select(objects::object_id)
+            .filter(filter.to_predicate());
+        assert_eq!(
+            "SELECT \"objects\".\"object_id\" FROM \"objects\" WHERE (\"objects\".\"object_type_package\" = $1 AND \"objects\".\"object_type_module\" = $2) -- binds: [\"0x0000000000000000000000000000000000000000000000000000000000000002\", \"coin\"]",
+            debug_query::<Pg, _>(&query).to_string()
+        );
+    }
+}
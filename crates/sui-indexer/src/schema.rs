@@ -50,6 +50,20 @@ diesel::table! {
         #[max_length = 44]
         last_appearance_tx -> Varchar,
         last_appearance_time -> Int8,
+        transactions_sent -> Int8,
+        transactions_received -> Int8,
+        first_activity_checkpoint -> Nullable<Int8>,
+        last_activity_checkpoint -> Nullable<Int8>,
+    }
+}
+
+diesel::table! {
+    api_keys (key_hash) {
+        #[max_length = 64]
+        key_hash -> Varchar,
+        name -> Text,
+        requests_per_second -> Nullable<Int4>,
+        revoked -> Bool,
     }
 }
 
@@ -62,6 +76,12 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    chain_identifier (checkpoint_digest) {
+        checkpoint_digest -> Varchar,
+    }
+}
+
 diesel::table! {
     changed_objects (id) {
         id -> Int8,
@@ -76,6 +96,24 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    checkpoint_proofs (sequence_number) {
+        sequence_number -> Int8,
+        certified_summary -> Bytea,
+        contents -> Bytea,
+        effects -> Array<Nullable<Bytea>>,
+    }
+}
+
+diesel::table! {
+    checkpoint_queue (kind, sequence_number) {
+        #[max_length = 16]
+        kind -> Varchar,
+        sequence_number -> Int8,
+        payload -> Bytea,
+    }
+}
+
 diesel::table! {
     checkpoint_metrics (checkpoint) {
         checkpoint -> Int8,
@@ -89,6 +127,19 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    checkpoint_tx_digests (checkpoint_sequence_number, position) {
+        checkpoint_sequence_number -> Int8,
+        position -> Int8,
+        #[max_length = 44]
+        transaction_digest -> Varchar,
+        #[max_length = 44]
+        effects_digest -> Varchar,
+        #[max_length = 44]
+        content_digest -> Varchar,
+    }
+}
+
 diesel::table! {
     checkpoints (sequence_number) {
         sequence_number -> Int8,
@@ -110,6 +161,36 @@ diesel::table! {
         network_total_transactions -> Int8,
         timestamp_ms -> Int8,
         validator_signature -> Text,
+        checkpoint_commitments -> Array<Nullable<Bytea>>,
+    }
+}
+
+diesel::table! {
+    commit_watermarks (pipeline) {
+        #[max_length = 32]
+        pipeline -> Varchar,
+        committed_checkpoint -> Int8,
+    }
+}
+
+diesel::table! {
+    display (object_type) {
+        object_type -> Text,
+        #[max_length = 66]
+        display_id -> Varchar,
+        version -> Int4,
+        bcs -> Bytea,
+    }
+}
+
+diesel::table! {
+    epoch_storage_growth (epoch, object_type_package, object_type_module, object_type_name) {
+        epoch -> Int8,
+        object_type_package -> Varchar,
+        object_type_module -> Varchar,
+        object_type_name -> Varchar,
+        size_delta -> Int8,
+        object_changes -> Int8,
     }
 }
 
@@ -136,6 +217,18 @@ diesel::table! {
         total_gas_fees -> Nullable<Int8>,
         total_stake_rewards_distributed -> Nullable<Int8>,
         leftover_storage_fund_inflow -> Nullable<Int8>,
+        safe_mode -> Bool,
+    }
+}
+
+diesel::table! {
+    event_schemas (package_id, version, module, name) {
+        #[max_length = 66]
+        package_id -> Varchar,
+        version -> Int8,
+        module -> Text,
+        name -> Text,
+        layout -> Jsonb,
     }
 }
 
@@ -153,6 +246,30 @@ diesel::table! {
         event_type -> Text,
         event_time_ms -> Nullable<Int8>,
         event_bcs -> Bytea,
+        #[max_length = 66]
+        event_type_package -> Varchar,
+        event_type_module -> Text,
+        event_type_name -> Text,
+        event_type_params -> Text,
+        tx_sequence_number -> Int8,
+    }
+}
+
+diesel::table! {
+    hot_objects (bucket_start_ms, object_id) {
+        bucket_start_ms -> Int8,
+        #[max_length = 66]
+        object_id -> Varchar,
+        touches -> Int8,
+    }
+}
+
+diesel::table! {
+    hot_packages (bucket_start_ms, package) {
+        bucket_start_ms -> Int8,
+        #[max_length = 66]
+        package -> Varchar,
+        calls -> Int8,
     }
 }
 
@@ -169,6 +286,16 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    module_disassemblies (package_id, version, module_name) {
+        #[max_length = 66]
+        package_id -> Varchar,
+        version -> Int8,
+        module_name -> Text,
+        disassembly -> Text,
+    }
+}
+
 diesel::table! {
     move_calls (id) {
         id -> Int8,
@@ -184,6 +311,66 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    name_records (field_id) {
+        #[max_length = 66]
+        field_id -> Varchar,
+        #[max_length = 256]
+        name -> Varchar,
+        #[max_length = 66]
+        nft_id -> Varchar,
+        #[max_length = 66]
+        target_address -> Nullable<Varchar>,
+        expiration_timestamp_ms -> Int8,
+        version -> Int8,
+        checkpoint_sequence_number -> Int8,
+    }
+}
+
+diesel::table! {
+    network_totals (name) {
+        #[max_length = 64]
+        name -> Varchar,
+        total -> Int8,
+        checkpoint -> Int8,
+    }
+}
+
+diesel::table! {
+    object_ownership_history (id) {
+        id -> Int8,
+        #[max_length = 66]
+        object_id -> Varchar,
+        object_version -> Int8,
+        #[max_length = 66]
+        from_owner -> Nullable<Varchar>,
+        #[max_length = 66]
+        to_owner -> Varchar,
+        #[max_length = 44]
+        transaction_digest -> Varchar,
+        checkpoint_sequence_number -> Int8,
+    }
+}
+
+diesel::table! {
+    object_type_counts (package, module, name) {
+        package -> Varchar,
+        module -> Varchar,
+        name -> Varchar,
+        object_count -> Int8,
+    }
+}
+
+diesel::table! {
+    object_type_stats (epoch, package, module, name) {
+        epoch -> Int8,
+        package -> Varchar,
+        module -> Varchar,
+        name -> Varchar,
+        object_count -> Int8,
+    }
+}
+
 diesel::table! {
     use diesel::sql_types::*;
     use super::sql_types::OwnerType;
@@ -209,6 +396,10 @@ diesel::table! {
         has_public_transfer -> Bool,
         storage_rebate -> Int8,
         bcs -> Array<Nullable<BcsBytes>>,
+        object_type_package -> Nullable<Varchar>,
+        object_type_module -> Nullable<Varchar>,
+        object_type_name -> Nullable<Varchar>,
+        object_size -> Int8,
     }
 }
 
@@ -240,6 +431,7 @@ diesel::table! {
         has_public_transfer -> Bool,
         storage_rebate -> Int8,
         bcs -> Array<Nullable<BcsBytes>>,
+        object_size -> Int8,
     }
 }
 
@@ -257,6 +449,22 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    protocol_configs (epoch) {
+        epoch -> Int8,
+        protocol_version -> Int8,
+        protocol_config -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    pruning_watermarks (table_family) {
+        #[max_length = 32]
+        table_family -> Varchar,
+        pruned_before_checkpoint -> Int8,
+    }
+}
+
 diesel::table! {
     recipients (id) {
         id -> Int8,
@@ -271,6 +479,42 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    reexecution_mismatches (transaction_digest) {
+        #[max_length = 44]
+        transaction_digest -> Varchar,
+        checkpoint_sequence_number -> Int8,
+        #[max_length = 44]
+        expected_effects_digest -> Varchar,
+        #[max_length = 44]
+        local_effects_digest -> Varchar,
+        local_error -> Nullable<Text>,
+        reexecuted_at_ms -> Int8,
+    }
+}
+
+diesel::table! {
+    snapshot_bootstraps (epoch) {
+        epoch -> Int8,
+        checkpoint_sequence_number -> Int8,
+        object_count -> Int8,
+        package_count -> Int8,
+    }
+}
+
+diesel::table! {
+    sponsor_gas_spend (sponsor, epoch) {
+        #[max_length = 66]
+        sponsor -> Varchar,
+        epoch -> Int8,
+        transaction_count -> Int8,
+        total_gas_cost -> Int8,
+        computation_cost -> Int8,
+        storage_cost -> Int8,
+        storage_rebate -> Int8,
+    }
+}
+
 diesel::table! {
     system_states (epoch) {
         epoch -> Int8,
@@ -295,6 +539,7 @@ diesel::table! {
         inactive_pools_size -> Int8,
         validator_candidates_id -> Text,
         validator_candidates_size -> Int8,
+        summary -> Nullable<Jsonb>,
     }
 }
 
@@ -325,6 +570,106 @@ diesel::table! {
         raw_transaction -> Bytea,
         transaction_effects_content -> Text,
         confirmed_local_execution -> Nullable<Bool>,
+        #[max_length = 66]
+        gas_owner -> Nullable<Varchar>,
+        gas_payment_object_ids -> Array<Nullable<Varchar>>,
+        gas_payment_object_versions -> Array<Nullable<Int8>>,
+        gas_payment_object_digests -> Array<Nullable<Varchar>>,
+        expiration_epoch -> Nullable<Int8>,
+        sponsored -> Bool,
+        failure_kind -> Nullable<Text>,
+        failure_command -> Nullable<Int8>,
+        #[max_length = 66]
+        abort_package -> Nullable<Varchar>,
+        abort_module -> Nullable<Text>,
+        abort_code -> Nullable<Int8>,
+    }
+}
+
+diesel::table! {
+    transactions_json (transaction_digest) {
+        #[max_length = 44]
+        transaction_digest -> Varchar,
+        checkpoint_sequence_number -> Int8,
+        response -> Jsonb,
+    }
+}
+
+diesel::table! {
+    transfers (id) {
+        id -> Int8,
+        #[max_length = 44]
+        transaction_digest -> Varchar,
+        checkpoint_sequence_number -> Int8,
+        timestamp_ms -> Int8,
+        #[max_length = 66]
+        sender -> Varchar,
+        #[max_length = 66]
+        recipient -> Varchar,
+        coin_type -> Text,
+        amount -> Int8,
+    }
+}
+
+diesel::table! {
+    tx_calls (transaction_digest, command_index) {
+        #[max_length = 44]
+        transaction_digest -> Varchar,
+        command_index -> Int8,
+        checkpoint_sequence_number -> Int8,
+        epoch -> Int8,
+        #[max_length = 66]
+        package -> Varchar,
+        module -> Text,
+        function -> Text,
+        arguments -> Jsonb,
+    }
+}
+
+diesel::table! {
+    tx_commands (transaction_digest, command_index) {
+        #[max_length = 44]
+        transaction_digest -> Varchar,
+        command_index -> Int8,
+        checkpoint_sequence_number -> Int8,
+        epoch -> Int8,
+        #[max_length = 32]
+        command_kind -> Varchar,
+        #[max_length = 66]
+        package -> Nullable<Varchar>,
+        module -> Nullable<Text>,
+        function -> Nullable<Text>,
+        type_arguments -> Nullable<Text>,
+        arguments -> Text,
+    }
+}
+
+diesel::table! {
+    tx_dependencies (transaction_digest, dependency) {
+        #[max_length = 44]
+        transaction_digest -> Varchar,
+        #[max_length = 44]
+        dependency -> Varchar,
+        checkpoint_sequence_number -> Int8,
+    }
+}
+
+diesel::table! {
+    validator_epoch_events (epoch, validator_address) {
+        epoch -> Int8,
+        validator_address -> Text,
+        reported_by -> Array<Nullable<Text>>,
+        report_count -> Int8,
+        stake -> Int8,
+        stake_delta -> Int8,
+    }
+}
+
+diesel::table! {
+    validator_participation (epoch, committee_index) {
+        epoch -> Int8,
+        committee_index -> Int8,
+        checkpoints_signed -> Int8,
     }
 }
 
@@ -373,23 +718,75 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    verified_sources (package_id) {
+        #[max_length = 66]
+        package_id -> Varchar,
+        sources -> Text,
+        verifier -> Text,
+        verified_at_ms -> Int8,
+        metadata -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    watchlists (entity_kind, entity_id, rule) {
+        #[max_length = 16]
+        entity_kind -> Varchar,
+        #[max_length = 66]
+        entity_id -> Varchar,
+        rule -> Text,
+    }
+}
+
 diesel::allow_tables_to_appear_in_same_query!(
     active_addresses,
     address_stats,
     addresses,
+    api_keys,
     at_risk_validators,
+    chain_identifier,
     changed_objects,
     checkpoint_metrics,
+    checkpoint_proofs,
+    checkpoint_queue,
+    checkpoint_tx_digests,
     checkpoints,
+    commit_watermarks,
+    display,
+    epoch_storage_growth,
     epochs,
+    event_schemas,
     events,
+    hot_objects,
+    hot_packages,
     input_objects,
+    module_disassemblies,
     move_calls,
+    name_records,
+    network_totals,
+    object_ownership_history,
+    object_type_counts,
+    object_type_stats,
     objects,
     objects_history,
     packages,
+    protocol_configs,
+    pruning_watermarks,
     recipients,
+    reexecution_mismatches,
+    snapshot_bootstraps,
+    sponsor_gas_spend,
     system_states,
     transactions,
+    transactions_json,
+    transfers,
+    tx_calls,
+    tx_commands,
+    tx_dependencies,
+    validator_epoch_events,
+    validator_participation,
     validators,
+    verified_sources,
+    watchlists,
 );
@@ -0,0 +1,148 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::str::FromStr;
+
+use fastcrypto::encoding::{Base58, Encoding};
+use move_core_types::identifier::Identifier;
+use serde::{Deserialize, Serialize};
+
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::digests::TransactionDigest;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+
+/// Entity of the chain matching a search query.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub enum SearchResult {
+    Transaction(TransactionDigest),
+    Checkpoint(CheckpointSequenceNumber),
+    Object(ObjectID),
+    Package(ObjectID),
+    Address(SuiAddress),
+    Module {
+        package: ObjectID,
+        module: Identifier,
+    },
+    Struct {
+        package: ObjectID,
+        module: Identifier,
+        name: Identifier,
+    },
+    Function {
+        package: ObjectID,
+        module: Identifier,
+        name: Identifier,
+    },
+}
+
+/// Interpretation of a search query, a query can have several, e.g. a base58 digest
+/// can be the digest of a transaction or of a checkpoint.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SearchQuery {
+    CheckpointSequenceNumber(CheckpointSequenceNumber),
+    // transaction or checkpoint digest
+    Digest([u8; 32]),
+    // object, package or address
+    Id(ObjectID),
+    // `package::module` or `package::module::name`
+    MoveName {
+        package: ObjectID,
+        module: Identifier,
+        name: Option<Identifier>,
+    },
+}
+
+impl SearchQuery {
+    pub fn parse(query: &str) -> Vec<Self> {
+        let query = query.trim();
+        let mut interpretations = vec![];
+        if let Ok(sequence_number) = query.parse::<CheckpointSequenceNumber>() {
+            interpretations.push(Self::CheckpointSequenceNumber(sequence_number));
+        }
+        if let Some(digest) = Base58::decode(query)
+            .ok()
+            .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+        {
+            interpretations.push(Self::Digest(digest));
+        }
+        // hex ids are required to be 0x prefixed, otherwise any number would be an id
+        if query.starts_with("0x") {
+            if let Ok(id) = ObjectID::from_str(query) {
+                interpretations.push(Self::Id(id));
+            }
+        }
+        if let Some(move_name) = Self::parse_move_name(query) {
+            interpretations.push(move_name);
+        }
+        interpretations
+    }
+
+    fn parse_move_name(query: &str) -> Option<Self> {
+        let parts: Vec<_> = query.split("::").collect();
+        let (package, module, name) = match parts.as_slice() {
+            [package, module] => (package, module, None),
+            [package, module, name] => (package, module, Some(Identifier::new(*name).ok()?)),
+            _ => return None,
+        };
+        Some(Self::MoveName {
+            package: ObjectID::from_str(package).ok()?,
+            module: Identifier::new(*module).ok()?,
+            name,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use move_core_types::identifier::Identifier;
+    use sui_types::base_types::ObjectID;
+    use sui_types::digests::TransactionDigest;
+
+    use super::SearchQuery;
+
+    #[test]
+    fn test_parse_search_query() {
+        assert_eq!(
+            SearchQuery::parse(" 42 "),
+            vec![SearchQuery::CheckpointSequenceNumber(42)]
+        );
+
+        let digest = TransactionDigest::random();
+        assert_eq!(
+            SearchQuery::parse(&digest.base58_encode()),
+            vec![SearchQuery::Digest(digest.into_inner())]
+        );
+
+        let id = ObjectID::random();
+        assert_eq!(
+            SearchQuery::parse(&id.to_string()),
+            vec![SearchQuery::Id(id)]
+        );
+        assert_eq!(
+            SearchQuery::parse("0x2"),
+            vec![SearchQuery::Id(ObjectID::from_str("0x2").unwrap())]
+        );
+
+        assert_eq!(
+            SearchQuery::parse("0x2::coin::Coin"),
+            vec![SearchQuery::MoveName {
+                package: ObjectID::from_str("0x2").unwrap(),
+                module: Identifier::new("coin").unwrap(),
+                name: Some(Identifier::new("Coin").unwrap()),
+            }]
+        );
+        assert_eq!(
+            SearchQuery::parse("0x2::coin"),
+            vec![SearchQuery::MoveName {
+                package: ObjectID::from_str("0x2").unwrap(),
+                module: Identifier::new("coin").unwrap(),
+                name: None,
+            }]
+        );
+
+        assert!(SearchQuery::parse("0x2::coin::Coin::value").is_empty());
+        assert!(SearchQuery::parse("not a query").is_empty());
+    }
+}
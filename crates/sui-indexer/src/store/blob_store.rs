@@ -0,0 +1,234 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+use backoff::future::retry;
+use backoff::ExponentialBackoff;
+use fastcrypto::encoding::{Encoding, Hex};
+use fastcrypto::hash::{HashFunction, Sha256};
+use futures::StreamExt;
+use object_store::path::Path as ObjectPath;
+use object_store::DynObjectStore;
+use tokio::runtime::Handle;
+use tracing::warn;
+
+use sui_storage::object_store::util::get;
+
+use crate::errors::IndexerError;
+use crate::models::objects::{NamedBcsBytes, Object};
+
+// Prefix of the payloads offloaded to the blob store, followed by the path of the blob. Like
+// compressed payloads, BCS payloads never start with it followed by BLOB_PATH_PREFIX.
+pub(crate) const OFFLOADED_PAYLOAD_V1: u8 = 0xf2;
+const BLOB_PATH_PREFIX: &str = "blobs/";
+// uploads or deletions in flight at once
+const BLOB_REQUEST_CONCURRENCY: usize = 16;
+// payloads not uploaded within this duration are kept in Postgres instead
+const BLOB_UPLOAD_MAX_RETRY_DURATION: Duration = Duration::from_secs(10);
+
+/// Object store that the BCS payloads of objects larger than `threshold` bytes are offloaded
+/// to, with only a pointer to the blob stored in Postgres. Blobs are keyed by the digest of
+/// their content, so that versions of an object with the same content share a blob and
+/// uploading a payload again is a no-op. The content of an object includes its ID, so a blob
+/// is only ever pointed to by rows of a single object.
+#[derive(Clone)]
+pub struct BlobStore {
+    store: Arc<DynObjectStore>,
+    threshold: usize,
+    // the store is called from the blocking threads of the Postgres queries
+    runtime: Handle,
+}
+
+impl BlobStore {
+    pub fn new(store: Arc<DynObjectStore>, threshold: usize) -> Self {
+        Self {
+            store,
+            threshold,
+            runtime: Handle::current(),
+        }
+    }
+
+    /// Uploads the payloads of `objects` larger than the threshold concurrently, replacing
+    /// them with pointers to their blobs. Payloads that could not be uploaded within
+    /// `BLOB_UPLOAD_MAX_RETRY_DURATION` are kept as they are, so that an outage of the blob
+    /// store does not stall indexing, and counted in the returned number. Must be called from
+    /// a blocking thread.
+    pub fn offload(&self, mut objects: Vec<Object>) -> (Vec<Object>, usize) {
+        let mut paths = HashMap::new();
+        for NamedBcsBytes(_, bytes) in objects.iter().flat_map(|object| object.bcs.iter()) {
+            if self.is_offloaded(bytes) {
+                paths
+                    .entry(blob_path(bytes))
+                    .or_insert_with(|| bytes.clone());
+            }
+        }
+        let uploaded = self.runtime.block_on(
+            futures::stream::iter(paths)
+                .map(|(path, blob)| async move {
+                    match self.upload(&path, blob).await {
+                        Ok(()) => Some(path),
+                        Err(e) => {
+                            warn!("Failed uploading blob {path}, keeping it in Postgres: {e}");
+                            None
+                        }
+                    }
+                })
+                .buffer_unordered(BLOB_REQUEST_CONCURRENCY)
+                .filter_map(|path| async move { path })
+                .collect::<HashSet<_>>(),
+        );
+
+        let mut failed = 0;
+        for NamedBcsBytes(_, bytes) in objects.iter_mut().flat_map(|object| object.bcs.iter_mut()) {
+            if !self.is_offloaded(bytes) {
+                continue;
+            }
+            let path = blob_path(bytes);
+            if uploaded.contains(&path) {
+                *bytes = pointer(&path);
+            } else {
+                failed += 1;
+            }
+        }
+        (objects, failed)
+    }
+
+    /// Deletes the blobs at `paths`, e.g. no longer pointed to by any row once rows are
+    /// pruned. Must be called from a blocking thread.
+    pub fn delete(&self, paths: Vec<ObjectPath>) -> Result<(), IndexerError> {
+        let results = self.runtime.block_on(
+            futures::stream::iter(paths)
+                .map(|path| async move {
+                    match self.store.delete(&path).await {
+                        Ok(()) | Err(object_store::Error::NotFound { .. }) => Ok(()),
+                        Err(e) => Err(IndexerError::UncategorizedError(anyhow::anyhow!(
+                            "Failed deleting blob {path} with error: {e}"
+                        ))),
+                    }
+                })
+                .buffer_unordered(BLOB_REQUEST_CONCURRENCY)
+                .collect::<Vec<_>>(),
+        );
+        results.into_iter().collect()
+    }
+
+    // whether `payload` is to be uploaded rather than stored in Postgres
+    fn is_offloaded(&self, payload: &[u8]) -> bool {
+        payload.len() > self.threshold && offloaded_path(payload).is_none()
+    }
+
+    async fn upload(&self, path: &ObjectPath, blob: Vec<u8>) -> Result<(), object_store::Error> {
+        let backoff = ExponentialBackoff {
+            max_elapsed_time: Some(BLOB_UPLOAD_MAX_RETRY_DURATION),
+            ..ExponentialBackoff::default()
+        };
+        retry(backoff, || async {
+            self.store
+                .put(path, blob.clone().into())
+                .await
+                .map_err(backoff::Error::transient)
+        })
+        .await
+    }
+
+    /// Replaces the pointers among the payloads of `object` with the blobs they point to.
+    /// Must be called from a blocking thread.
+    pub fn resolve(&self, mut object: Object) -> Result<Object, IndexerError> {
+        for NamedBcsBytes(_, bytes) in object.bcs.iter_mut() {
+            if let Some(path) = offloaded_path(bytes) {
+                let blob = self
+                    .runtime
+                    .block_on(get(&path, self.store.clone()))
+                    .map_err(|e| {
+                        IndexerError::UncategorizedError(anyhow::anyhow!(
+                            "Failed downloading blob {path} with error: {e}"
+                        ))
+                    })?;
+                *bytes = blob.to_vec();
+            }
+        }
+        Ok(object)
+    }
+}
+
+fn blob_path(payload: &[u8]) -> ObjectPath {
+    ObjectPath::from(format!(
+        "{BLOB_PATH_PREFIX}{}",
+        Hex::encode(Sha256::digest(payload).digest)
+    ))
+}
+
+fn pointer(path: &ObjectPath) -> Vec<u8> {
+    [&[OFFLOADED_PAYLOAD_V1], path.as_ref().as_bytes()].concat()
+}
+
+/// Path of the blob that `payload` points to, if it was offloaded.
+pub fn offloaded_path(payload: &[u8]) -> Option<ObjectPath> {
+    match payload.split_first() {
+        Some((&OFFLOADED_PAYLOAD_V1, path)) if path.starts_with(BLOB_PATH_PREFIX.as_bytes()) => {
+            std::str::from_utf8(path).ok().map(ObjectPath::from)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use object_store::memory::InMemory;
+    use sui_types::base_types::{ObjectID, SequenceNumber, SuiAddress};
+    use sui_types::storage::WriteKind;
+
+    use super::{offloaded_path, BlobStore};
+    use crate::models::objects::{NamedBcsBytes, Object};
+
+    #[tokio::test]
+    async fn test_blob_offloading() {
+        let blobs = BlobStore::new(Arc::new(InMemory::new()), 16);
+        let object = sui_types::object::Object::with_id_owner_version_for_testing(
+            ObjectID::random(),
+            SequenceNumber::from_u64(1),
+            SuiAddress::random_for_testing_only(),
+        );
+        let object = Object {
+            bcs: vec![
+                NamedBcsBytes("small".to_string(), vec![1; 16]),
+                NamedBcsBytes("large".to_string(), vec![2; 1024]),
+            ],
+            ..Object::new(0, 0, WriteKind::Mutate, &object)
+        };
+        let store = blobs.clone();
+        let (offloaded, resolved) = tokio::task::spawn_blocking(move || {
+            let (mut offloaded, failed) = store.offload(vec![object]);
+            assert_eq!(failed, 0);
+            let offloaded = offloaded.pop().unwrap();
+            let resolved = store.resolve(offloaded.clone()).unwrap();
+            (offloaded, resolved)
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(vec![1; 16], offloaded.bcs[0].1);
+        assert!(offloaded_path(&offloaded.bcs[1].1).is_some());
+        assert!(offloaded.bcs[1].1.len() < 1024);
+        assert_eq!(vec![1; 16], resolved.bcs[0].1);
+        assert_eq!(vec![2; 1024], resolved.bcs[1].1);
+
+        // deleting a blob twice is fine
+        let path = offloaded_path(&offloaded.bcs[1].1).unwrap();
+        let store = blobs.clone();
+        let deleted = path.clone();
+        tokio::task::spawn_blocking(move || store.delete(vec![deleted.clone(), deleted]))
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(blobs.store.head(&path).await.is_err());
+        // payloads written before offloading are read as they are
+        assert!(offloaded_path(&[0xf2, 1, 2]).is_none());
+        assert!(offloaded_path(&[]).is_none());
+    }
+}
@@ -0,0 +1,131 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use crate::errors::IndexerError;
+
+pub const DEFAULT_COMMIT_CHUNK_SIZE: usize = 1000;
+// Postgres binds at most this many parameters in a statement
+const PG_MAX_BIND_PARAMETERS: usize = 65_535;
+
+/// Number of rows of each table committed per statement, `default_size` unless configured
+/// otherwise for a table, and at most the rows whose columns fit the bind parameter limit.
+#[derive(Clone, Debug)]
+pub struct CommitChunkSizes {
+    default_size: usize,
+    sizes: HashMap<String, usize>,
+    max_sizes: HashMap<String, usize>,
+}
+
+impl Default for CommitChunkSizes {
+    fn default() -> Self {
+        Self {
+            default_size: DEFAULT_COMMIT_CHUNK_SIZE,
+            sizes: HashMap::new(),
+            max_sizes: HashMap::new(),
+        }
+    }
+}
+
+impl CommitChunkSizes {
+    /// `sizes` of tables are `<table>=<rows>`, e.g. `events=5000`.
+    pub fn new(default_size: usize, sizes: &[String]) -> Result<Self, IndexerError> {
+        if default_size == 0 {
+            return Err(IndexerError::InvalidArgumentError(
+                "Commit chunk size must be positive".to_string(),
+            ));
+        }
+        let mut table_sizes = HashMap::new();
+        for size in sizes {
+            let TableChunkSize { table, rows } = TableChunkSize::from_str(size)?;
+            if table_sizes.insert(table.clone(), rows).is_some() {
+                return Err(IndexerError::InvalidArgumentError(format!(
+                    "Multiple commit chunk sizes for {table}"
+                )));
+            }
+        }
+        Ok(Self {
+            default_size,
+            sizes: table_sizes,
+            max_sizes: HashMap::new(),
+        })
+    }
+
+    /// Caps the chunk sizes of tables by their number of columns.
+    pub fn with_column_counts(mut self, column_counts: HashMap<String, usize>) -> Self {
+        self.max_sizes = column_counts
+            .into_iter()
+            .filter(|(_, columns)| *columns > 0)
+            .map(|(table, columns)| (table, PG_MAX_BIND_PARAMETERS / columns))
+            .collect();
+        self
+    }
+
+    pub fn get(&self, table: &str) -> usize {
+        let size = self.sizes.get(table).copied().unwrap_or(self.default_size);
+        match self.max_sizes.get(table) {
+            Some(max_size) => size.min(*max_size),
+            None => size,
+        }
+    }
+}
+
+struct TableChunkSize {
+    table: String,
+    rows: usize,
+}
+
+impl FromStr for TableChunkSize {
+    type Err = IndexerError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            IndexerError::InvalidArgumentError(format!(
+                "Invalid commit chunk size {s}, expected <table>=<rows> with positive rows"
+            ))
+        };
+        let (table, rows) = s.split_once('=').ok_or_else(invalid)?;
+        let rows = rows.parse::<usize>().map_err(|_| invalid())?;
+        if table.is_empty() || rows == 0 {
+            return Err(invalid());
+        }
+        Ok(Self {
+            table: table.to_string(),
+            rows,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use super::CommitChunkSizes;
+
+    #[test]
+    fn test_commit_chunk_sizes() {
+        let sizes = CommitChunkSizes::new(
+            1000,
+            &["events=5000".to_string(), "objects=200".to_string()],
+        )
+        .unwrap()
+        .with_column_counts(HashMap::from([
+            ("events".to_string(), 20),
+            ("transactions".to_string(), 100),
+        ]));
+        // capped at 65535 / 20 parameters
+        assert_eq!(sizes.get("events"), 3276);
+        assert_eq!(sizes.get("objects"), 200);
+        assert_eq!(sizes.get("transactions"), 655);
+        assert_eq!(sizes.get("transfers"), 1000);
+
+        assert!(CommitChunkSizes::new(0, &[]).is_err());
+        for invalid in ["events", "events=0", "events=x", "=10"] {
+            assert!(CommitChunkSizes::new(1000, &[invalid.to_string()]).is_err());
+        }
+        let duplicate = ["events=10".to_string(), "events=20".to_string()];
+        assert!(CommitChunkSizes::new(1000, &duplicate).is_err());
+    }
+}
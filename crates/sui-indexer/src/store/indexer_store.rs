@@ -2,15 +2,23 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
 
 use async_trait::async_trait;
 use prometheus::{Histogram, IntCounter};
+use serde::{Deserialize, Serialize};
 
+use move_binary_format::CompiledModule;
 use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::{ModuleId, StructTag};
+use move_core_types::value::MoveStructLayout;
 use sui_json_rpc_types::{
-    Checkpoint as RpcCheckpoint, CheckpointId, EpochInfo, EventFilter, EventPage, MoveCallMetrics,
-    NetworkMetrics, SuiObjectData, SuiObjectDataFilter, SuiTransactionBlockEffects,
-    SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
+    Checkpoint as RpcCheckpoint, CheckpointId, DisplayFieldsResponse, EpochInfo,
+    EpochValidatorParticipation, EventFilter, EventPage, EventSequence, HotEntity, MoveCallMetrics,
+    NetworkMetrics, ObjectOwnershipTransition, Page, ProtocolConfigResponse, SequencedEventPage,
+    SuiMoveNormalizedStruct, SuiObjectData, SuiObjectDataFilter, SuiTransactionBlockEffects,
+    SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions, WatchlistEntry,
 };
 use sui_types::base_types::{EpochId, ObjectID, SequenceNumber, SuiAddress, VersionNumber};
 use sui_types::digests::{CheckpointDigest, TransactionDigest};
@@ -19,19 +27,54 @@ use sui_types::event::EventID;
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::object::ObjectRead;
 use sui_types::storage::ObjectStore;
+use sui_types::sui_system_state::sui_system_state_summary::SuiSystemStateSummary;
 
 use crate::errors::IndexerError;
 use crate::metrics::IndexerMetrics;
-use crate::models::addresses::{ActiveAddress, Address, AddressStats};
+use crate::models::addresses::{
+    ActiveAddress, Address, AddressActivity, AddressStats, CheckpointAddressActivity,
+};
+use crate::models::api_keys::ApiKey;
+use crate::models::chain_identifier::ChainIdentifier;
 use crate::models::checkpoint_metrics::CheckpointMetrics;
-use crate::models::checkpoints::Checkpoint;
+use crate::models::checkpoint_proofs::{CheckpointProof, TransactionProof};
+use crate::models::checkpoint_queue::{QueueKind, QueuedCheckpoint};
+use crate::models::checkpoints::{
+    Checkpoint, CheckpointDiscontinuity, CheckpointTxDigest, TransactionInclusion,
+};
 use crate::models::epoch::DBEpochInfo;
 use crate::models::events::Event;
+use crate::models::hot_entities::{CheckpointHotEntities, HotWindow};
+use crate::models::materialized_views::MaterializedView;
+use crate::models::name_service::NameRecord;
+use crate::models::network_metrics::NetworkTotals;
+use crate::models::object_type_stats::ObjectTypeStat;
 use crate::models::objects::{DeletedObject, Object, ObjectStatus};
+use crate::models::ownership_history::OwnershipTransition;
 use crate::models::packages::Package;
-use crate::models::system_state::{DBSystemStateSummary, DBValidatorSummary};
-use crate::models::transaction_index::{ChangedObject, InputObject, MoveCall, Recipient};
-use crate::models::transactions::Transaction;
+use crate::models::portfolio::CoinPortfolioEntry;
+use crate::models::protocol_configs::DBProtocolConfig;
+use crate::models::reexecution::ReexecutionMismatch;
+use crate::models::retention::PrunedTable;
+use crate::models::snapshot_bootstraps::SnapshotBootstrap;
+use crate::models::sponsors::SponsorGasSpend;
+use crate::models::system_state::{
+    DBSystemStateSummary, DBValidatorEpochEvent, DBValidatorSummary, ValidatorEpochEvent,
+};
+use crate::models::table_sizes::TableSize;
+use crate::models::transaction_index::{
+    ChangedObject, InputObject, MoveCall, Recipient, TransactionDependencyNode, TxCommand,
+    TxDependency,
+};
+use crate::models::transactions::{AbortedTransaction, Transaction, TransactionJson};
+use crate::models::transfers::Transfer;
+use crate::models::tx_calls::TxCall;
+use crate::models::validator_participation::CheckpointSigners;
+use crate::models::verified_sources::VerifiedSource;
+use crate::query;
+use crate::search::SearchResult;
+use crate::store::query_plans::QueryShape;
+use crate::store::ReadonlySqlResult;
 use crate::types::CheckpointTransactionBlockResponse;
 
 #[async_trait]
@@ -39,7 +82,19 @@ pub trait IndexerStore {
     type ModuleCache;
 
     async fn get_latest_tx_checkpoint_sequence_number(&self) -> Result<i64, IndexerError>;
+    /// Whether the database role serving queries of the RPC APIs is able to modify data.
+    async fn reader_has_write_privileges(&self) -> Result<bool, IndexerError>;
     async fn get_latest_object_checkpoint_sequence_number(&self) -> Result<i64, IndexerError>;
+    /// Latest epoch committed, -1 if none.
+    async fn get_latest_epoch_id(&self) -> Result<i64, IndexerError>;
+    /// Waits until the checkpoint is committed and visible to the RPC APIs with all its data:
+    /// its transactions, events, transaction index tables and object changes, failing with
+    /// `IndexerError::CheckpointWaitTimeout` after `timeout`.
+    async fn wait_for_checkpoint(
+        &self,
+        sequence_number: i64,
+        timeout: Duration,
+    ) -> Result<(), IndexerError>;
     async fn get_checkpoint(&self, id: CheckpointId) -> Result<RpcCheckpoint, IndexerError>;
     async fn get_checkpoints(
         &self,
@@ -52,12 +107,28 @@ pub trait IndexerStore {
         cursor: i64,
         limit: usize,
     ) -> Result<Vec<Checkpoint>, IndexerError>;
+    // discontinuities between committed checkpoints of sequence numbers in [start, end]
+    async fn get_checkpoint_discontinuities(
+        &self,
+        start: i64,
+        end: i64,
+    ) -> Result<Vec<CheckpointDiscontinuity>, IndexerError>;
     async fn get_checkpoint_sequence_number(
         &self,
         digest: CheckpointDigest,
     ) -> Result<CheckpointSequenceNumber, IndexerError>;
+    /// Resolves a wall-clock timestamp to a checkpoint, returns None if no checkpoint
+    /// satisfies the rounding, e.g. flooring a timestamp before genesis.
+    async fn get_checkpoint_at_timestamp(
+        &self,
+        timestamp_ms: u64,
+        rounding: TimestampRounding,
+    ) -> Result<Option<CheckpointSequenceNumber>, IndexerError>;
 
     async fn get_event(&self, id: EventID) -> Result<Event, IndexerError>;
+    /// Events are returned in the order they were emitted in: by the sequence number of
+    /// their transaction in the network, then by their sequence in the transaction. The
+    /// cursor is exclusive, pages are stable as events are only ever added after the last.
     async fn get_events(
         &self,
         query: EventFilter,
@@ -65,6 +136,31 @@ pub trait IndexerStore {
         limit: Option<usize>,
         descending_order: bool,
     ) -> Result<EventPage, IndexerError>;
+    /// Events matching `filter`, paginated in the same way as `get_events`.
+    async fn query_events(
+        &self,
+        filter: query::EventFilter,
+        cursor: Option<EventID>,
+        limit: Option<usize>,
+        descending_order: bool,
+    ) -> Result<EventPage, IndexerError>;
+    /// Events in the same order as `get_events`, with their position in that order as cursor.
+    async fn get_events_by_sequence(
+        &self,
+        query: EventFilter,
+        cursor: Option<EventSequence>,
+        limit: Option<usize>,
+        descending_order: bool,
+    ) -> Result<SequencedEventPage, IndexerError>;
+    /// Events emitted within [start_ms, end_ms), paginated in the same way as `get_events`.
+    async fn query_events_in_time_range(
+        &self,
+        start_ms: u64,
+        end_ms: u64,
+        cursor: Option<EventID>,
+        limit: Option<usize>,
+        descending_order: bool,
+    ) -> Result<EventPage, IndexerError>;
 
     async fn get_object(
         &self,
@@ -72,6 +168,25 @@ pub trait IndexerStore {
         version: Option<SequenceNumber>,
     ) -> Result<ObjectRead, IndexerError>;
 
+    /// Display fields of the latest version of the object, rendered from the templates of
+    /// the latest Display version of its type. `data` is None if its type has no Display.
+    async fn render_display(
+        &self,
+        object_id: ObjectID,
+    ) -> Result<DisplayFieldsResponse, IndexerError>;
+
+    // up to `count` distinct objects of the objects table, picked at random
+    async fn sample_objects(&self, count: usize) -> Result<Vec<Object>, IndexerError>;
+
+    /// Latest version of `object_id` below `before_version` written by checkpoint
+    /// `checkpoint` or earlier, None if it did not exist or was deleted or wrapped then.
+    async fn get_object_before_version(
+        &self,
+        object_id: ObjectID,
+        checkpoint: CheckpointSequenceNumber,
+        before_version: SequenceNumber,
+    ) -> Result<Option<sui_types::object::Object>, IndexerError>;
+
     async fn query_objects_history(
         &self,
         filter: SuiObjectDataFilter,
@@ -87,11 +202,74 @@ pub trait IndexerStore {
         limit: usize,
     ) -> Result<Vec<ObjectRead>, IndexerError>;
 
+    /// Live objects matching `filter`, ordered by object id after the exclusive `cursor`.
+    async fn query_objects(
+        &self,
+        filter: query::ObjectFilter,
+        cursor: Option<ObjectID>,
+        limit: usize,
+    ) -> Result<Vec<ObjectRead>, IndexerError>;
+
+    /// `type_query` is either an exact type or a prefix of it, see `ObjectTypeQuery`.
+    async fn query_objects_by_type(
+        &self,
+        type_query: String,
+        cursor: Option<ObjectID>,
+        limit: usize,
+    ) -> Result<Vec<ObjectRead>, IndexerError>;
+
+    /// Per coin type balances of the coins owned by `owner`, with decimals applied.
+    async fn get_portfolio(
+        &self,
+        owner: SuiAddress,
+    ) -> Result<Vec<CoinPortfolioEntry>, IndexerError>;
+
+    /// Runs an ad hoc SELECT of a client over the allowlisted views, see `ReadonlySql`.
+    async fn execute_readonly_sql(&self, sql: String) -> Result<ReadonlySqlResult, IndexerError>;
+
     async fn get_total_transaction_number_from_checkpoints(&self) -> Result<i64, IndexerError>;
 
     // TODO: combine all get_transaction* methods
     async fn get_transaction_by_digest(&self, tx_digest: &str)
         -> Result<Transaction, IndexerError>;
+    /// Transactions whose base58 digest starts with `prefix`, ordered by digest.
+    async fn find_transactions_by_digest_prefix(
+        &self,
+        prefix: &str,
+        limit: usize,
+    ) -> Result<Vec<Transaction>, IndexerError>;
+    /// Checkpoint, position and contents digest of the checkpoint including `tx_digest`.
+    async fn get_transaction_inclusion(
+        &self,
+        tx_digest: TransactionDigest,
+    ) -> Result<Option<TransactionInclusion>, IndexerError>;
+
+    /// Certified summary, contents and effects proving the execution of `tx_digest`, if
+    /// the proof of its checkpoint was recorded.
+    async fn get_transaction_proof(
+        &self,
+        tx_digest: TransactionDigest,
+    ) -> Result<Option<TransactionProof>, IndexerError>;
+
+    /// Up to `limit` transactions after `after_id` whose id is a multiple of
+    /// `sample_interval`, or the latest ones if `after_id` is not set, in id order.
+    async fn get_reexecution_sample(
+        &self,
+        after_id: Option<i64>,
+        sample_interval: u64,
+        limit: usize,
+    ) -> Result<Vec<Transaction>, IndexerError>;
+    async fn persist_reexecution_mismatch(
+        &self,
+        mismatch: ReexecutionMismatch,
+    ) -> Result<(), IndexerError>;
+
+    /// Transaction that created, mutated or unwrapped `object_id` into `version`.
+    async fn get_transaction_for_object_version(
+        &self,
+        object_id: ObjectID,
+        version: SequenceNumber,
+    ) -> Result<Option<Transaction>, IndexerError>;
     async fn multi_get_transactions_by_digests(
         &self,
         tx_digests: &[String],
@@ -110,6 +288,16 @@ pub trait IndexerStore {
         is_descending: bool,
     ) -> Result<Vec<Transaction>, IndexerError>;
 
+    /// Transactions matching `filter`, paginated by their sequence number like the other
+    /// transaction pages.
+    async fn query_transactions(
+        &self,
+        filter: query::TransactionFilter,
+        start_sequence: Option<i64>,
+        limit: usize,
+        is_descending: bool,
+    ) -> Result<Vec<Transaction>, IndexerError>;
+
     async fn get_transaction_page_by_checkpoint(
         &self,
         checkpoint_sequence_number: i64,
@@ -118,6 +306,16 @@ pub trait IndexerStore {
         is_descending: bool,
     ) -> Result<Vec<Transaction>, IndexerError>;
 
+    /// Transactions with timestamp_ms within [start_ms, end_ms).
+    async fn query_transactions_in_time_range(
+        &self,
+        start_ms: u64,
+        end_ms: u64,
+        start_sequence: Option<i64>,
+        limit: usize,
+        is_descending: bool,
+    ) -> Result<Vec<Transaction>, IndexerError>;
+
     async fn get_transaction_page_by_transaction_kinds(
         &self,
         kind_names: Vec<String>,
@@ -134,6 +332,59 @@ pub trait IndexerStore {
         is_descending: bool,
     ) -> Result<Vec<Transaction>, IndexerError>;
 
+    /// Transactions with `object_id` among their gas payment objects, e.g. the coins of a
+    /// gas station.
+    async fn get_transactions_by_gas_object(
+        &self,
+        object_id: ObjectID,
+        start_sequence: Option<i64>,
+        limit: usize,
+        is_descending: bool,
+    ) -> Result<Vec<Transaction>, IndexerError>;
+
+    /// Transactions paid by `gas_owner`, only those of other senders if `sponsored_only`.
+    async fn get_transactions_by_gas_owner(
+        &self,
+        gas_owner: SuiAddress,
+        sponsored_only: bool,
+        start_sequence: Option<i64>,
+        limit: usize,
+        is_descending: bool,
+    ) -> Result<Vec<Transaction>, IndexerError>;
+
+    /// Transactions of other senders paid by `sponsor`, with timestamp_ms within
+    /// [start_ms, end_ms).
+    async fn get_sponsored_transactions(
+        &self,
+        sponsor: SuiAddress,
+        start_ms: u64,
+        end_ms: u64,
+        start_sequence: Option<i64>,
+        limit: usize,
+        is_descending: bool,
+    ) -> Result<Vec<Transaction>, IndexerError>;
+
+    /// Gas spent by `sponsor` per epoch, after epoch `cursor` if any.
+    async fn get_sponsor_gas_spend(
+        &self,
+        sponsor: SuiAddress,
+        cursor: Option<EpochId>,
+        limit: usize,
+        is_descending: bool,
+    ) -> Result<Vec<SponsorGasSpend>, IndexerError>;
+
+    /// Transactions that aborted in `module` of `package`, with abort code `code` if set,
+    /// along with the error constant names of their abort codes.
+    async fn get_failed_transactions_by_abort(
+        &self,
+        package: ObjectID,
+        module: String,
+        code: Option<u64>,
+        start_sequence: Option<i64>,
+        limit: usize,
+        is_descending: bool,
+    ) -> Result<Vec<AbortedTransaction>, IndexerError>;
+
     async fn get_transaction_page_by_recipient_address(
         &self,
         sender_address: Option<SuiAddress>,
@@ -211,12 +462,23 @@ pub trait IndexerStore {
     ) -> Result<Option<i64>, IndexerError>;
 
     async fn get_network_metrics(&self) -> Result<NetworkMetrics, IndexerError>;
+    /// Running totals of the network, maintained on commit so that reading them is cheap.
+    async fn get_network_totals(&self) -> Result<NetworkTotals, IndexerError>;
     async fn get_move_call_metrics(&self) -> Result<MoveCallMetrics, IndexerError>;
 
+    /// Commits of indexed checkpoints, objects and epochs are idempotent: committing any of
+    /// them again, e.g. after a retry or a restart, leaves every table as it is. Rows that
+    /// later checkpoints upserted, e.g. objects and name records of later versions, are kept.
     async fn persist_checkpoint_transactions(
         &self,
         checkpoints: &[Checkpoint],
         transactions: &[Transaction],
+        transactions_json: &[TransactionJson],
+        checkpoint_tx_digests: &[CheckpointTxDigest],
+        hot_entities: &[CheckpointHotEntities],
+        address_activity: &[CheckpointAddressActivity],
+        signers: &[CheckpointSigners],
+        proofs: &[CheckpointProof],
         counter_committed_tx: IntCounter,
     ) -> Result<(), IndexerError>;
     async fn persist_object_changes(
@@ -227,22 +489,178 @@ pub trait IndexerStore {
         object_commit_chunk_counter: IntCounter,
     ) -> Result<(), IndexerError>;
     async fn persist_events(&self, events: &[Event]) -> Result<(), IndexerError>;
+    /// Records that the checkpoints up to `sequence_number` are committed along with their
+    /// events and transaction index tables, which are committed by tasks of their own.
+    async fn persist_commit_watermark(&self, sequence_number: i64) -> Result<(), IndexerError>;
     async fn persist_addresses(
         &self,
         addresses: &[Address],
         active_addresses: &[ActiveAddress],
     ) -> Result<(), IndexerError>;
     async fn persist_packages(&self, packages: &[Package]) -> Result<(), IndexerError>;
+    /// Insert or replace the verified sources of a package, either reported by the
+    /// source verification service or uploaded.
+    async fn persist_verified_source(&self, source: VerifiedSource) -> Result<(), IndexerError>;
+    async fn get_verified_source(
+        &self,
+        package: ObjectID,
+    ) -> Result<Option<VerifiedSource>, IndexerError>;
+    /// Watch `entry.entity` for transactions touching it, no-op if already watched by the
+    /// same rule. Written from the admin server of the indexer writing the network.
+    async fn persist_watchlist_entry(&self, entry: WatchlistEntry) -> Result<(), IndexerError>;
+    async fn delete_watchlist_entry(&self, entry: WatchlistEntry) -> Result<(), IndexerError>;
+    async fn get_watchlist_entries(&self) -> Result<Vec<WatchlistEntry>, IndexerError>;
+    /// Entries ordered by kind, id and rule of the watched entity, after `cursor` if any.
+    async fn get_watchlist(
+        &self,
+        cursor: Option<WatchlistEntry>,
+        limit: usize,
+    ) -> Result<Vec<WatchlistEntry>, IndexerError>;
+    /// All keys of the api_keys table, including revoked ones.
+    async fn get_api_keys(&self) -> Result<Vec<ApiKey>, IndexerError>;
+    /// Latest formal snapshot the objects table was bootstrapped from, if any.
+    async fn get_snapshot_bootstrap(&self) -> Result<Option<SnapshotBootstrap>, IndexerError>;
+    async fn persist_snapshot_bootstrap(
+        &self,
+        bootstrap: SnapshotBootstrap,
+    ) -> Result<(), IndexerError>;
+    /// Genesis checkpoint digest of the indexed chain, recorded on the first start.
+    async fn get_chain_identifier(&self) -> Result<Option<ChainIdentifier>, IndexerError>;
+    async fn persist_chain_identifier(
+        &self,
+        chain_identifier: ChainIdentifier,
+    ) -> Result<(), IndexerError>;
+    /// Queues checkpoints indexed by an indexer of a split pipeline for its committer,
+    /// checkpoints already queued are left as they are. Returns the number queued.
+    async fn enqueue_checkpoints(
+        &self,
+        checkpoints: Vec<QueuedCheckpoint>,
+    ) -> Result<usize, IndexerError>;
+    /// Up to `limit` queued checkpoints of `kind` from `sequence_number` on, in order.
+    async fn get_queued_checkpoints(
+        &self,
+        kind: QueueKind,
+        sequence_number: i64,
+        limit: usize,
+    ) -> Result<Vec<QueuedCheckpoint>, IndexerError>;
+    /// Deletes the queued checkpoints of `kind` up to `sequence_number` included.
+    async fn delete_queued_checkpoints(
+        &self,
+        kind: QueueKind,
+        sequence_number: i64,
+    ) -> Result<usize, IndexerError>;
+    /// Sequence number of the last checkpoint, or epoch, of `kind` committed, -1 if none.
+    async fn get_committed_queue_sequence_number(
+        &self,
+        kind: QueueKind,
+    ) -> Result<i64, IndexerError>;
+    /// Tries to take the leader lock `name` of the replicas of a fullnode sync worker,
+    /// returns whether this replica holds it. The lock is held until the store is dropped
+    /// or its connection is lost. From then on writes only commit while this replica holds
+    /// the lock.
+    async fn try_acquire_leader_lock(&self, name: String) -> Result<bool, IndexerError>;
+    async fn holds_leader_lock(&self) -> Result<bool, IndexerError>;
+
+    async fn get_epoch_first_checkpoint(&self, epoch: i64) -> Result<Option<i64>, IndexerError>;
+    /// Last committed checkpoint of `epoch`, None if none is.
+    async fn get_epoch_last_checkpoint(&self, epoch: i64) -> Result<Option<i64>, IndexerError>;
+    /// First checkpoint of `table` not pruned yet, None if nothing was indexed.
+    async fn get_pruned_checkpoint(&self, table: PrunedTable) -> Result<Option<i64>, IndexerError>;
+    /// Deletes the rows of `table` of checkpoints in [start, end) and moves its pruning
+    /// watermark to `end`, returns the number of rows deleted.
+    async fn prune_checkpoints(
+        &self,
+        table: PrunedTable,
+        start: i64,
+        end: i64,
+    ) -> Result<usize, IndexerError>;
     // NOTE: these tables are for tx query performance optimization
+    #[allow(clippy::too_many_arguments)]
     async fn persist_transaction_index_tables(
         &self,
         input_objects: &[InputObject],
         changed_objects: &[ChangedObject],
         move_calls: &[MoveCall],
+        tx_commands: &[TxCommand],
+        tx_calls: &[TxCall],
         recipients: &[Recipient],
+        tx_dependencies: &[TxDependency],
+        transfers: &[Transfer],
+        ownership_transitions: &[OwnershipTransition],
     ) -> Result<(), IndexerError>;
 
+    /// Addresses that a non-coin object was passed to, by object version, after version
+    /// `cursor` if any.
+    async fn get_ownership_history(
+        &self,
+        object_id: ObjectID,
+        cursor: Option<u64>,
+        limit: usize,
+        is_descending: bool,
+    ) -> Result<Vec<ObjectOwnershipTransition>, IndexerError>;
+
+    /// Address a name of the name service points to, None if the name is not registered,
+    /// expired as of the latest checkpoint or points to no address.
+    async fn resolve_name(&self, name: String) -> Result<Option<SuiAddress>, IndexerError>;
+    /// Unexpired names pointing to `address`, by the id of their record, which is the
+    /// exclusive cursor.
+    async fn lookup_names(
+        &self,
+        address: SuiAddress,
+        cursor: Option<ObjectID>,
+        limit: usize,
+    ) -> Result<Page<String, ObjectID>, IndexerError>;
+
+    /// Objects touched by the most transactions within `window`, most touched first.
+    async fn get_hot_objects(
+        &self,
+        window: HotWindow,
+        limit: usize,
+    ) -> Result<Vec<HotEntity>, IndexerError>;
+    /// Packages with the most Move calls within `window`, most called first.
+    async fn get_hot_packages(
+        &self,
+        window: HotWindow,
+        limit: usize,
+    ) -> Result<Vec<HotEntity>, IndexerError>;
+
+    /// Transactions `transaction_digest` transitively depends on, up to `depth` hops away.
+    async fn get_transaction_ancestors(
+        &self,
+        transaction_digest: TransactionDigest,
+        depth: usize,
+    ) -> Result<Vec<TransactionDependencyNode>, IndexerError>;
+
+    /// Transactions transitively depending on `transaction_digest`, up to `depth` hops away.
+    async fn get_transaction_descendants(
+        &self,
+        transaction_digest: TransactionDigest,
+        depth: usize,
+    ) -> Result<Vec<TransactionDependencyNode>, IndexerError>;
+
     async fn persist_epoch(&self, data: &TemporaryEpochStore) -> Result<(), IndexerError>;
+
+    /// Recomputes `view` from the tables it aggregates, concurrently with reads of it
+    /// once it has been populated.
+    async fn refresh_materialized_view(&self, view: MaterializedView) -> Result<(), IndexerError>;
+
+    /// Whether object changes are recorded in objects_history.
+    async fn set_objects_history_enabled(&self, enabled: bool) -> Result<(), IndexerError>;
+
+    /// Records the live objects of each struct type, as counted while objects are committed,
+    /// as the stats of `epoch`, once the objects of its last checkpoint are committed.
+    async fn persist_object_type_stats(&self, epoch: EpochId) -> Result<(), IndexerError>;
+    /// Live object counts per struct type at the end of `epoch`, largest first.
+    async fn get_object_type_stats(
+        &self,
+        epoch: EpochId,
+    ) -> Result<Vec<ObjectTypeStat>, IndexerError>;
+
+    /// Sizes of the tables of the indexer, read from the catalog rather than by scanning.
+    async fn get_table_sizes(&self) -> Result<Vec<TableSize>, IndexerError>;
+    /// Plan of the query of `shape` in the JSON format of EXPLAIN, without running it.
+    async fn explain_query(&self, shape: QueryShape) -> Result<serde_json::Value, IndexerError>;
+
     async fn get_network_total_transactions_previous_epoch(
         &self,
         epoch: i64,
@@ -257,7 +675,79 @@ pub trait IndexerStore {
 
     async fn get_current_epoch(&self) -> Result<EpochInfo, IndexerError>;
 
+    /// Reference gas price of each epoch within [start_epoch, end_epoch), in epoch order.
+    async fn get_reference_gas_price_history(
+        &self,
+        start_epoch: EpochId,
+        end_epoch: EpochId,
+    ) -> Result<Vec<(EpochId, u64)>, IndexerError>;
+
+    /// Reference gas price of the latest epoch.
+    async fn get_reference_gas_price(&self) -> Result<u64, IndexerError>;
+
+    /// Protocol config recorded at the start of `epoch`.
+    async fn get_protocol_config(
+        &self,
+        epoch: EpochId,
+    ) -> Result<ProtocolConfigResponse, IndexerError>;
+
+    /// Full system state summary recorded at the start of `epoch`.
+    async fn get_system_state_at_epoch(
+        &self,
+        epoch: EpochId,
+    ) -> Result<SuiSystemStateSummary, IndexerError>;
+
+    /// Reporting records and stake changes of `validator` at each epoch start,
+    /// paginated by epoch.
+    async fn get_validator_epoch_events(
+        &self,
+        validator: SuiAddress,
+        cursor: Option<EpochId>,
+        limit: usize,
+        descending_order: bool,
+    ) -> Result<Vec<ValidatorEpochEvent>, IndexerError>;
+
+    /// Checkpoints of `epoch` signed by each validator of its committee, in committee order,
+    /// after committee index `cursor` if any.
+    async fn get_validator_participation(
+        &self,
+        epoch: EpochId,
+        cursor: Option<u64>,
+        limit: usize,
+    ) -> Result<Vec<EpochValidatorParticipation>, IndexerError>;
+
+    /// Disassembled bytecode of `module` in the latest version of `package`.
+    async fn get_module_disassembly(
+        &self,
+        package: ObjectID,
+        module: String,
+    ) -> Result<String, IndexerError>;
+
+    /// Layout of the fields of events of `event_type`, as recorded from the package declaring
+    /// it. Type parameters of `event_type` are ignored.
+    async fn get_event_schema(
+        &self,
+        event_type: StructTag,
+    ) -> Result<SuiMoveNormalizedStruct, IndexerError>;
+
+    /// Layout of `struct_tag`, with field names and types, resolved from the modules of
+    /// indexed packages, to decode the BCS of objects and events of the type. Generic types
+    /// need all their type arguments.
+    async fn get_type_layout(
+        &self,
+        struct_tag: StructTag,
+    ) -> Result<MoveStructLayout, IndexerError>;
+
+    /// Transactions, checkpoints, objects, addresses and Move definitions matching
+    /// `query`, see `SearchQuery` for the recognized formats.
+    async fn search(&self, query: &str) -> Result<Vec<SearchResult>, IndexerError>;
+
     fn module_cache(&self) -> &Self::ModuleCache;
+    /// Modules of indexed packages among `ids`, modules failing to resolve are left out.
+    async fn get_modules(
+        &self,
+        ids: Vec<ModuleId>,
+    ) -> Result<HashMap<ModuleId, Arc<CompiledModule>>, IndexerError>;
 
     fn indexer_metrics(&self) -> &IndexerMetrics;
 
@@ -266,6 +756,11 @@ pub trait IndexerStore {
     async fn calculate_address_stats(&self, checkpoint: i64) -> Result<AddressStats, IndexerError>;
     async fn persist_address_stats(&self, addr_stats: &AddressStats) -> Result<(), IndexerError>;
     async fn get_latest_address_stats(&self) -> Result<AddressStats, IndexerError>;
+    /// Counters of the transactions sent and received by `address`, if it has any.
+    async fn get_address_activity(
+        &self,
+        address: SuiAddress,
+    ) -> Result<Option<AddressActivity>, IndexerError>;
     async fn get_checkpoint_address_stats(
         &self,
         checkpoint: i64,
@@ -297,6 +792,14 @@ pub trait IndexerStore {
     ) -> Result<f64, IndexerError>;
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimestampRounding {
+    // the last checkpoint with timestamp_ms <= the given timestamp
+    Floor,
+    // the first checkpoint with timestamp_ms >= the given timestamp
+    Ceil,
+}
+
 #[derive(Clone, Debug)]
 pub struct CheckpointTxData {
     pub checkpoint: RpcCheckpoint,
@@ -344,28 +847,63 @@ pub struct CheckpointObjectData {
 }
 
 // Per checkpoint indexing
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct TemporaryCheckpointStore {
     pub checkpoint: Checkpoint,
     pub transactions: Vec<Transaction>,
+    // empty unless transactions are indexed as JSON
+    pub transactions_json: Vec<TransactionJson>,
+    pub checkpoint_tx_digests: Vec<CheckpointTxDigest>,
     pub events: Vec<Event>,
     pub input_objects: Vec<InputObject>,
     pub changed_objects: Vec<ChangedObject>,
     pub move_calls: Vec<MoveCall>,
+    pub tx_commands: Vec<TxCommand>,
+    pub tx_calls: Vec<TxCall>,
     pub recipients: Vec<Recipient>,
+    pub tx_dependencies: Vec<TxDependency>,
+    pub transfers: Vec<Transfer>,
+    pub ownership_transitions: Vec<OwnershipTransition>,
+    pub signers: CheckpointSigners,
+    // none unless the checkpoint_proofs pipeline is enabled
+    pub proof: Option<CheckpointProof>,
 }
 
-#[derive(Clone, Debug)]
+impl TemporaryCheckpointStore {
+    /// Number of rows committed for the checkpoint, across all tables.
+    pub fn row_count(&self) -> usize {
+        1 + self.transactions.len()
+            + self.transactions_json.len()
+            + self.checkpoint_tx_digests.len()
+            + self.events.len()
+            + self.input_objects.len()
+            + self.changed_objects.len()
+            + self.move_calls.len()
+            + self.tx_commands.len()
+            + self.tx_calls.len()
+            + self.recipients.len()
+            + self.tx_dependencies.len()
+            + self.transfers.len()
+            + self.ownership_transitions.len()
+            + usize::from(self.proof.is_some())
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct TransactionObjectChanges {
     pub changed_objects: Vec<Object>,
     pub deleted_objects: Vec<DeletedObject>,
+    // records of the name service among the changed objects
+    pub name_records: Vec<NameRecord>,
 }
 
 // Per epoch indexing
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct TemporaryEpochStore {
     pub last_epoch: Option<DBEpochInfo>,
     pub new_epoch: DBEpochInfo,
     pub system_state: DBSystemStateSummary,
     pub validators: Vec<DBValidatorSummary>,
+    pub validator_epoch_events: Vec<DBValidatorEpochEvent>,
+    pub protocol_config: DBProtocolConfig,
 }
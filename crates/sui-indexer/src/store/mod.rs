@@ -1,26 +1,78 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+pub use blob_store::BlobStore;
+pub use commit_chunks::CommitChunkSizes;
 pub use indexer_store::*;
 pub use pg_indexer_store::PgIndexerStore;
+pub use readonly_sql::{ReadonlySql, ReadonlySqlResult};
 
+mod blob_store;
+mod commit_chunks;
 mod indexer_store;
 mod module_resolver;
+mod page_queries;
 mod pg_indexer_store;
 mod query;
+mod query_cache;
+pub mod query_plans;
+mod readonly_sql;
+mod slow_query_log;
 
 mod diesel_marco {
+    // `$timeout` overrides the statement timeout of the connection for this query only.
     macro_rules! read_only_blocking {
+        ($pool:expr, $timeout:expr, $query:expr) => {{
+            let mut pg_pool_conn = crate::get_pg_pool_connection($pool)?;
+            pg_pool_conn
+                .build_transaction()
+                .read_only()
+                .run(crate::store::diesel_marco::with_statement_timeout(
+                    $timeout, $query,
+                ))
+                .map_err(crate::store::diesel_marco::read_error)
+        }};
         ($pool:expr, $query:expr) => {{
             let mut pg_pool_conn = crate::get_pg_pool_connection($pool)?;
             pg_pool_conn
                 .build_transaction()
                 .read_only()
                 .run($query)
-                .map_err(|e| IndexerError::PostgresReadError(e.to_string()))
+                .map_err(crate::store::diesel_marco::read_error)
         }};
     }
 
+    pub(crate) fn with_statement_timeout<T, E, F>(
+        timeout: std::time::Duration,
+        query: F,
+    ) -> impl FnOnce(&mut diesel::PgConnection) -> Result<T, E>
+    where
+        F: FnOnce(&mut diesel::PgConnection) -> Result<T, E>,
+        E: From<diesel::result::Error>,
+    {
+        move |conn| {
+            // SET LOCAL only lasts until the end of the enclosing transaction
+            diesel::RunQueryDsl::execute(
+                diesel::sql_query(format!(
+                    "SET LOCAL statement_timeout = {}",
+                    timeout.as_millis()
+                )),
+                conn,
+            )?;
+            query(conn)
+        }
+    }
+
+    pub(crate) fn read_error(e: impl std::fmt::Display) -> crate::errors::IndexerError {
+        let msg = e.to_string();
+        // Postgres cancels statements running past statement_timeout with this message.
+        if msg.contains("canceling statement due to statement timeout") {
+            crate::errors::IndexerError::QueryTimeout(msg)
+        } else {
+            crate::errors::IndexerError::PostgresReadError(msg)
+        }
+    }
+
     macro_rules! transactional_blocking {
         ($pool:expr, $query:expr) => {{
             let mut pg_pool_conn = crate::get_pg_pool_connection($pool)?;
@@ -32,6 +84,21 @@ mod diesel_marco {
                 .map_err(|e| IndexerError::PostgresWriteError(e.to_string()))
         }};
     }
+    // `transactional_blocking` on the writer pool of the store `$store`, fenced by its
+    // leader lock if it takes part in leader election.
+    macro_rules! fenced_transactional_blocking {
+        ($store:expr, $query:expr) => {{
+            let store = $store;
+            let mut pg_pool_conn = crate::get_pg_pool_connection(&store.blocking_cp)?;
+            pg_pool_conn
+                .build_transaction()
+                .serializable()
+                .read_write()
+                .run(store.fenced($query))
+                .map_err(|e| IndexerError::PostgresWriteError(e.to_string()))
+        }};
+    }
+    pub(crate) use fenced_transactional_blocking;
     pub(crate) use read_only_blocking;
     pub(crate) use transactional_blocking;
 }
@@ -1,19 +1,35 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::{Arc, RwLock};
+
+use anyhow::anyhow;
+
 use diesel::pg::sql_types::Bytea;
 use diesel::sql_types::Text;
 use diesel::QueryableByName;
 use diesel::RunQueryDsl;
+use move_binary_format::CompiledModule;
+use move_bytecode_utils::module_cache::GetModule;
 use move_core_types::language_storage::ModuleId;
 use move_core_types::resolver::ModuleResolver;
+use prometheus::IntCounter;
+use tracing::warn;
+use typed_store::rocks::{DBMap, MetricConf, ReadWriteOptions};
+use typed_store::Map;
 
 use sui_types::base_types::ObjectID;
+use sui_types::is_system_package;
 
 use crate::errors::{Context, IndexerError};
 use crate::store::diesel_marco::read_only_blocking;
 use crate::PgConnectionPool;
 
+/// Reads module bytes of the latest version of a package from the packages table.
+/// Packages are stored as raw module bytes when indexed, a module is only deserialized
+/// when first resolved through the `IndexerModuleCache` wrapping this resolver.
 pub struct IndexerModuleResolver {
     cp: PgConnectionPool,
 }
@@ -24,6 +40,132 @@ impl IndexerModuleResolver {
     }
 }
 
+/// In-memory cache of modules, deserialized on first use from the on-disk cache if set,
+/// or else from Postgres. Modules that fail to deserialize, e.g. of a bytecode version
+/// newer than this binary supports, are counted and kept out of both caches, so that they
+/// are read from Postgres again once the indexer is upgraded.
+pub struct IndexerModuleCache {
+    cache: RwLock<BTreeMap<ModuleId, Arc<CompiledModule>>>,
+    resolver: IndexerModuleResolver,
+    disk_cache: Option<PersistentModuleCache>,
+    unparseable_modules: IntCounter,
+}
+
+impl IndexerModuleCache {
+    pub fn new(resolver: IndexerModuleResolver, unparseable_modules: IntCounter) -> Self {
+        Self {
+            cache: RwLock::new(BTreeMap::new()),
+            resolver,
+            disk_cache: None,
+            unparseable_modules,
+        }
+    }
+
+    /// Module bytes read from Postgres are also kept in `disk_cache`, which is consulted
+    /// before Postgres.
+    pub fn with_disk_cache(mut self, disk_cache: PersistentModuleCache) -> Self {
+        self.disk_cache = Some(disk_cache);
+        self
+    }
+
+    fn get_cached_module(&self, id: &ModuleId) -> Option<Arc<CompiledModule>> {
+        let disk_cache = self.disk_cache.as_ref()?;
+        let bytes = match disk_cache.get(id) {
+            Ok(bytes) => bytes?,
+            Err(e) => {
+                warn!("Failed reading module {id} from disk cache with error: {e}");
+                return None;
+            }
+        };
+        match CompiledModule::deserialize_with_defaults(&bytes) {
+            Ok(module) => Some(Arc::new(module)),
+            // evicted so that the module is resolved from Postgres instead
+            Err(e) => {
+                warn!("Failed deserializing cached module {id} with error: {e:?}");
+                if let Err(e) = disk_cache.remove(id) {
+                    warn!("Failed evicting module {id} from disk cache with error: {e}");
+                }
+                None
+            }
+        }
+    }
+}
+
+impl GetModule for IndexerModuleCache {
+    type Error = anyhow::Error;
+    type Item = Arc<CompiledModule>;
+
+    fn get_module_by_id(&self, id: &ModuleId) -> Result<Option<Arc<CompiledModule>>, Self::Error> {
+        if let Some(module) = self.cache.read().unwrap().get(id) {
+            return Ok(Some(module.clone()));
+        }
+
+        let module = match self.get_cached_module(id) {
+            Some(module) => module,
+            None => {
+                let Some(bytes) = self.resolver.get_module(id)? else {
+                    return Ok(None);
+                };
+                let module = CompiledModule::deserialize_with_defaults(&bytes).map_err(|e| {
+                    self.unparseable_modules.inc();
+                    anyhow!("Failure deserializing module {id} with error: {e:?}")
+                })?;
+                if let Some(disk_cache) = &self.disk_cache {
+                    if let Err(e) = disk_cache.insert(id, &bytes) {
+                        warn!("Failed writing module {id} to disk cache with error: {e}");
+                    }
+                }
+                Arc::new(module)
+            }
+        };
+        self.cache
+            .write()
+            .unwrap()
+            .insert(id.clone(), module.clone());
+        Ok(Some(module))
+    }
+}
+
+/// On-disk cache of module bytes keyed by (package id, module name), kept across
+/// restarts.
+#[derive(Clone)]
+pub struct PersistentModuleCache {
+    modules: DBMap<(ObjectID, String), Vec<u8>>,
+}
+
+impl PersistentModuleCache {
+    pub fn open(path: &Path) -> Result<Self, IndexerError> {
+        let modules = DBMap::open(
+            path,
+            MetricConf::default(),
+            None,
+            None,
+            &ReadWriteOptions::default(),
+        )?;
+        Ok(Self { modules })
+    }
+
+    pub fn get(&self, id: &ModuleId) -> Result<Option<Vec<u8>>, IndexerError> {
+        Ok(self.modules.get(&Self::key(id))?)
+    }
+
+    pub fn insert(&self, id: &ModuleId, bytes: &[u8]) -> Result<(), IndexerError> {
+        // system packages are upgraded in place, their modules can change under the same id.
+        if is_system_package(ObjectID::from(*id.address())) {
+            return Ok(());
+        }
+        Ok(self.modules.insert(&Self::key(id), &bytes.to_vec())?)
+    }
+
+    pub fn remove(&self, id: &ModuleId) -> Result<(), IndexerError> {
+        Ok(self.modules.remove(&Self::key(id))?)
+    }
+
+    fn key(id: &ModuleId) -> (ObjectID, String) {
+        (ObjectID::from(*id.address()), id.name().to_string())
+    }
+}
+
 const LATEST_MODULE_QUERY: &str = "SELECT (t2.module).data
 FROM (SELECT UNNEST(data) AS module
       FROM (SELECT data FROM packages WHERE package_id = $1 ORDER BY version DESC FETCH FIRST 1 ROW ONLY) t1) t2
@@ -50,7 +192,6 @@ impl ModuleResolver for IndexerModuleResolver {
                 .get_result::<ModuleBytes>(conn)
         })
         .context("Error reading module.")?;
-
         Ok(Some(module_bytes.data))
     }
 }
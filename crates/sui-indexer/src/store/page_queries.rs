@@ -0,0 +1,291 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use diesel::pg::Pg;
+use diesel::{BoolExpressionMethods, ExpressionMethods, PgArrayExpressionMethods, QueryDsl};
+use move_core_types::identifier::Identifier;
+
+use sui_types::base_types::{ObjectID, SuiAddress};
+
+use crate::query::{self, ToPredicate};
+use crate::schema::{events, transactions};
+
+// Queries of the paginated reads of the store, shared with the query shapes explained by the
+// index advisor so that the advisor explains exactly what the store runs.
+
+pub(crate) type TransactionsQuery = transactions::BoxedQuery<'static, Pg>;
+pub(crate) type EventsQuery = events::BoxedQuery<'static, Pg>;
+
+/// Columns of the objects queries built as SQL, read into the `Object` model.
+pub(crate) const OBJECT_COLUMNS: [&str; 15] = [
+    "epoch",
+    "checkpoint",
+    "object_id",
+    "version",
+    "object_digest",
+    "owner_type",
+    "owner_address",
+    "initial_shared_version",
+    "previous_transaction",
+    "object_type",
+    "object_status",
+    "has_public_transfer",
+    "storage_rebate",
+    "bcs",
+    "object_size",
+];
+
+pub(crate) fn transactions_query(filter: &query::TransactionFilter) -> TransactionsQuery {
+    transactions::table
+        .filter(filter.to_predicate())
+        .into_boxed()
+}
+
+pub(crate) fn transaction_by_digest_query(digest: &str) -> TransactionsQuery {
+    transactions::table
+        .filter(transactions::transaction_digest.eq(digest.to_string()))
+        .into_boxed()
+}
+
+pub(crate) fn transactions_by_gas_object_query(object_id: ObjectID) -> TransactionsQuery {
+    transactions::table
+        .filter(transactions::gas_payment_object_ids.contains(vec![Some(object_id.to_string())]))
+        .into_boxed()
+}
+
+/// Transactions aborted in `package::module`, with abort code `code` if set.
+pub(crate) fn transactions_by_abort_query(
+    package: ObjectID,
+    module: &str,
+    code: Option<u64>,
+) -> TransactionsQuery {
+    let query = transactions::table
+        .filter(transactions::abort_package.eq(package.to_string()))
+        .filter(transactions::abort_module.eq(module.to_string()))
+        .into_boxed();
+    match code {
+        Some(code) => query.filter(transactions::abort_code.eq(code as i64)),
+        None => query,
+    }
+}
+
+pub(crate) fn transaction_kinds_filter(kinds: Vec<String>) -> query::TransactionFilter {
+    query::TransactionFilter::Or(
+        kinds
+            .into_iter()
+            .map(query::TransactionFilter::Kind)
+            .collect(),
+    )
+}
+
+pub(crate) fn gas_owner_filter(
+    gas_owner: SuiAddress,
+    sponsored_only: bool,
+) -> query::TransactionFilter {
+    let filter = query::TransactionFilter::GasOwner(gas_owner);
+    if sponsored_only {
+        filter.and(query::TransactionFilter::Sponsored(true))
+    } else {
+        filter
+    }
+}
+
+/// Transactions with gas paid by `sponsor` on behalf of their sender within
+/// [start_ms, end_ms).
+pub(crate) fn sponsored_transactions_filter(
+    sponsor: SuiAddress,
+    start_ms: u64,
+    end_ms: u64,
+) -> query::TransactionFilter {
+    query::TransactionFilter::Sponsored(true)
+        .and(query::TransactionFilter::GasOwner(sponsor))
+        .and(query::TransactionFilter::TimeRange { start_ms, end_ms })
+}
+
+/// Page of `query` after the transaction with id `start_sequence`, in the order of ids.
+pub(crate) fn transactions_page(
+    query: TransactionsQuery,
+    start_sequence: Option<i64>,
+    limit: usize,
+    is_descending: bool,
+) -> TransactionsQuery {
+    let query = match start_sequence {
+        Some(start_sequence) if is_descending => query.filter(transactions::id.lt(start_sequence)),
+        Some(start_sequence) => query.filter(transactions::id.gt(start_sequence)),
+        None => query,
+    };
+    let query = if is_descending {
+        query.order(transactions::id.desc())
+    } else {
+        query.order(transactions::id.asc())
+    };
+    query.limit(limit as i64)
+}
+
+/// Page of the events matching `filter` after the (tx_sequence_number, event_sequence)
+/// position `cursor`, in the order of positions.
+pub(crate) fn events_page(
+    filter: &query::EventFilter,
+    cursor: Option<(i64, i64)>,
+    limit: usize,
+    descending_order: bool,
+) -> EventsQuery {
+    let mut query = events::table.filter(filter.to_predicate()).into_boxed();
+    if let Some((tx_sequence_number, event_sequence)) = cursor {
+        if descending_order {
+            query = query.filter(
+                events::tx_sequence_number
+                    .lt(tx_sequence_number)
+                    .or(events::tx_sequence_number
+                        .eq(tx_sequence_number)
+                        .and(events::event_sequence.lt(event_sequence))),
+            );
+        } else {
+            query = query.filter(
+                events::tx_sequence_number
+                    .gt(tx_sequence_number)
+                    .or(events::tx_sequence_number
+                        .eq(tx_sequence_number)
+                        .and(events::event_sequence.gt(event_sequence))),
+            );
+        }
+    }
+    let query = if descending_order {
+        query.order((
+            events::tx_sequence_number.desc(),
+            events::event_sequence.desc(),
+        ))
+    } else {
+        query.order((
+            events::tx_sequence_number.asc(),
+            events::event_sequence.asc(),
+        ))
+    };
+    query.limit(limit as i64)
+}
+
+fn id_range_clause(start_sequence: Option<i64>, is_descending: bool) -> String {
+    match start_sequence {
+        Some(start_sequence) if is_descending => format!("AND id < {}", start_sequence),
+        Some(start_sequence) => format!("AND id > {}", start_sequence),
+        None => "".to_string(),
+    }
+}
+
+fn order(is_descending: bool) -> &'static str {
+    if is_descending {
+        "DESC"
+    } else {
+        "ASC"
+    }
+}
+
+/// Digests of a page of the transactions of the rows of `object_id` in `table`, either
+/// `input_objects` or `changed_objects`, of version `version` if set.
+pub(crate) fn object_transactions_page_sql(
+    table: &str,
+    object_id: ObjectID,
+    version: Option<i64>,
+    start_sequence: Option<i64>,
+    limit: usize,
+    is_descending: bool,
+) -> String {
+    format!(
+        "SELECT transaction_digest as digest_name
+         FROM {}
+         WHERE object_id = '{}' {} {}
+         ORDER BY id {} LIMIT {}",
+        table,
+        object_id,
+        id_range_clause(start_sequence, is_descending),
+        if let Some(version) = version {
+            format!("AND version = {}", version)
+        } else {
+            "".to_string()
+        },
+        order(is_descending),
+        limit
+    )
+}
+
+/// Digests of a page of the transactions calling `package`, or functions of it.
+pub(crate) fn move_call_transactions_page_sql(
+    package: ObjectID,
+    module: Option<&Identifier>,
+    function: Option<&Identifier>,
+    start_sequence: Option<i64>,
+    limit: usize,
+    is_descending: bool,
+) -> String {
+    // note: module and function are user-controlled, which is scary.
+    // however, but valid Move identifiers can only contain 0-9, a-z, A-Z, and _,
+    // so it is safe to use them as-is in the query below
+    format!(
+        "SELECT transaction_digest as digest_name
+         FROM move_calls
+         WHERE move_package = '{}' {} {} {}
+         ORDER BY id {} LIMIT {}",
+        package,
+        id_range_clause(start_sequence, is_descending),
+        if let Some(module) = module {
+            format!("AND move_module = '{}'", module)
+        } else {
+            "".to_string()
+        },
+        if let Some(function) = function {
+            format!("AND move_function = '{}'", function)
+        } else {
+            "".to_string()
+        },
+        order(is_descending),
+        limit
+    )
+}
+
+/// Digests of a page of the transactions sending objects or coins to `to`, from `from` if
+/// set.
+pub(crate) fn recipient_transactions_page_sql(
+    from: Option<SuiAddress>,
+    to: SuiAddress,
+    start_sequence: Option<i64>,
+    limit: usize,
+    is_descending: bool,
+) -> String {
+    format!(
+        "SELECT transaction_digest as digest_name FROM recipients
+         WHERE recipient = '{}' {} {}
+         ORDER BY id {} LIMIT {}",
+        to,
+        id_range_clause(start_sequence, is_descending),
+        if let Some(from) = from {
+            format!("AND sender = '{}'", from)
+        } else {
+            "".to_string()
+        },
+        order(is_descending),
+        limit
+    )
+}
+
+/// Digests of a page of the transactions sent by or to `address`.
+pub(crate) fn address_transactions_page_sql(
+    address: SuiAddress,
+    start_sequence: Option<i64>,
+    limit: usize,
+    is_descending: bool,
+) -> String {
+    format!(
+        "SELECT transaction_digest as digest_name FROM (
+            SELECT transaction_digest, max(id) AS max_id
+            FROM recipients
+            WHERE recipient = '{}' OR sender = '{}'
+            {} GROUP BY transaction_digest
+            ORDER BY max_id {} LIMIT {}
+        ) AS t",
+        address,
+        address,
+        id_range_clause(start_sequence, is_descending),
+        order(is_descending),
+        limit
+    )
+}
@@ -1,30 +1,44 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::Path;
 use std::str::FromStr;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
 
 use anyhow::anyhow;
 use async_trait::async_trait;
 use cached::proc_macro::once;
-use diesel::dsl::{count, max};
+use cached::{Cached, SizedCache, TimedSizedCache};
+use diesel::connection::SimpleConnection;
+use diesel::dsl::{count, max, min, SqlLiteral};
+use diesel::expression::TypedExpressionType;
 use diesel::pg::PgConnection;
-use diesel::sql_types::{BigInt, VarChar};
+use diesel::sql_types::{Array, BigInt, Bool, Bytea, Integer, Nullable, VarChar};
 use diesel::upsert::excluded;
-use diesel::ExpressionMethods;
+use diesel::{BoolExpressionMethods, Column, ExpressionMethods};
 use diesel::{OptionalExtension, QueryableByName};
 use diesel::{QueryDsl, RunQueryDsl};
-use fastcrypto::hash::Digest;
 use fastcrypto::traits::ToFromBytes;
-use move_bytecode_utils::module_cache::SyncModuleCache;
+use move_binary_format::access::ModuleAccess;
+use move_binary_format::file_format::SignatureToken;
+use move_binary_format::CompiledModule;
+use move_bytecode_utils::module_cache::GetModule;
 use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::{ModuleId, StructTag, TypeTag};
+use move_core_types::value::MoveStructLayout;
+use object_store::path::Path as ObjectPath;
 use prometheus::{Histogram, IntCounter};
-use tracing::info;
+use tokio::sync::Notify;
+use tracing::{info, warn};
 
+use sui_json_rpc::read_api::get_rendered_fields;
 use sui_json_rpc_types::{
-    CheckpointId, EpochInfo, EventFilter, EventPage, MoveCallMetrics, MoveFunctionName,
-    NetworkMetrics, SuiEvent, SuiObjectDataFilter,
+    CheckpointId, DisplayFieldsResponse, EpochInfo, EpochValidatorParticipation, EventFilter,
+    EventPage, EventSequence, HotEntity, MoveCallMetrics, MoveFunctionName, NetworkMetrics,
+    ObjectOwnershipTransition, Page, ProtocolConfigResponse, SequencedEventPage, SuiEvent,
+    SuiMoveNormalizedStruct, SuiObjectDataFilter, WatchlistEntry,
 };
 use sui_json_rpc_types::{
     SuiTransactionBlock, SuiTransactionBlockEffects, SuiTransactionBlockEvents,
@@ -35,42 +49,131 @@ use sui_types::committee::{EpochId, ProtocolVersion};
 use sui_types::crypto::AuthorityPublicKeyBytes;
 use sui_types::digests::CheckpointDigest;
 use sui_types::digests::TransactionDigest;
+use sui_types::error::SuiObjectResponseError;
 use sui_types::event::EventID;
-use sui_types::messages_checkpoint::{
-    CheckpointCommitment, CheckpointSequenceNumber, ECMHLiveObjectSetDigest, EndOfEpochData,
-};
-use sui_types::object::ObjectRead;
+use sui_types::messages_checkpoint::{CheckpointSequenceNumber, EndOfEpochData};
+use sui_types::object::{MoveObject, ObjectFormatOptions, ObjectRead};
+use sui_types::sui_system_state::sui_system_state_summary::SuiSystemStateSummary;
 use sui_types::transaction::SenderSignedData;
 
 use crate::errors::{Context, IndexerError};
 use crate::metrics::IndexerMetrics;
-use crate::models::addresses::{ActiveAddress, Address, AddressStats, DBAddressStats};
+use crate::models::addresses::{
+    ActiveAddress, Address, AddressActivity, AddressStats, CheckpointAddressActivity,
+    DBAddressStats,
+};
+use crate::models::api_keys::ApiKey;
+use crate::models::chain_identifier::ChainIdentifier;
 use crate::models::checkpoint_metrics::{CheckpointMetrics, Tps};
-use crate::models::checkpoints::Checkpoint;
+use crate::models::checkpoint_proofs::{CheckpointProof, TransactionProof};
+use crate::models::checkpoint_queue::{QueueKind, QueuedCheckpoint};
+use crate::models::checkpoints::{
+    commitments_from_column, Checkpoint, CheckpointDiscontinuity, CheckpointTxDigest,
+    TransactionInclusion,
+};
+use crate::models::compression::decompress_payload;
+use crate::models::display::DBDisplay;
 use crate::models::epoch::DBEpochInfo;
+use crate::models::event_schemas::EventSchema;
 use crate::models::events::Event;
-use crate::models::network_metrics::{DBMoveCallMetrics, DBNetworkMetrics};
+use crate::models::hot_entities::{
+    bucket_start_ms, CheckpointHotEntities, DBHotEntity, HotObject, HotPackage, HotWindow,
+};
+use crate::models::materialized_views::{MaterializedView, MaterializedViewStatus};
+use crate::models::name_service::NameRecord;
+use crate::models::network_metrics::{
+    DBMoveCallMetrics, DBNetworkMetrics, NetworkTotals, TOTAL_EVENTS, TOTAL_LIVE_OBJECTS,
+    TOTAL_PACKAGES, TOTAL_TRANSACTION_BLOCKS,
+};
+use crate::models::object_type_stats::ObjectTypeStat;
 use crate::models::objects::{
-    compose_object_bulk_insert_update_query, filter_latest_objects, Object,
+    compose_object_bulk_insert_update_query, filter_latest_objects, Object, ObjectStatus,
+    UNCHANGED_OBJECTS_UPDATE_QUERY,
+};
+use crate::models::ownership_history::OwnershipTransition;
+use crate::models::packages::{ModuleDisassembly, Package};
+use crate::models::portfolio::{compose_portfolio, CoinPortfolioEntry, DBCoinWithMetadata};
+use crate::models::protocol_configs::DBProtocolConfig;
+use crate::models::reexecution::ReexecutionMismatch;
+use crate::models::retention::PrunedTable;
+use crate::models::snapshot_bootstraps::SnapshotBootstrap;
+use crate::models::sponsors::SponsorGasSpend;
+use crate::models::system_state::{DBValidatorEpochEvent, DBValidatorSummary, ValidatorEpochEvent};
+use crate::models::table_sizes::TableSize;
+use crate::models::transaction_index::{
+    ChangedObject, DBTxDependencyNode, InputObject, MoveCall, Recipient, TransactionDependencyNode,
+    TxCommand, TxDependency,
 };
-use crate::models::packages::Package;
-use crate::models::system_state::DBValidatorSummary;
-use crate::models::transaction_index::{ChangedObject, InputObject, MoveCall, Recipient};
-use crate::models::transactions::Transaction;
+use crate::models::transactions::{AbortedTransaction, Transaction, TransactionJson};
+use crate::models::transfers::Transfer;
+use crate::models::tx_calls::TxCall;
+use crate::models::validator_participation::{CheckpointSigners, ValidatorParticipation};
+use crate::models::verified_sources::{DBVerifiedSource, VerifiedSource};
+use crate::models::watchlists::DBWatchlistEntry;
+use crate::query::{self, ToPredicate};
 use crate::schema::{
-    active_addresses, address_stats, addresses, changed_objects, checkpoint_metrics, checkpoints,
-    epochs, events, input_objects, move_calls, objects, objects_history, packages, recipients,
-    system_states, transactions, validators,
+    active_addresses, address_stats, addresses, api_keys, chain_identifier, changed_objects,
+    checkpoint_metrics, checkpoint_proofs, checkpoint_queue, checkpoint_tx_digests, checkpoints,
+    commit_watermarks, display, epoch_storage_growth, epochs, event_schemas, events, hot_objects,
+    hot_packages, input_objects, module_disassemblies, move_calls, name_records, network_totals,
+    object_ownership_history, object_type_stats, objects, objects_history, packages,
+    protocol_configs, pruning_watermarks, recipients, reexecution_mismatches, snapshot_bootstraps,
+    sponsor_gas_spend, system_states, transactions, transactions_json, transfers, tx_calls,
+    tx_commands, tx_dependencies, validator_epoch_events, validator_participation, validators,
+    verified_sources, watchlists,
+};
+use crate::search::{SearchQuery, SearchResult};
+use crate::store::blob_store::{offloaded_path, BlobStore, OFFLOADED_PAYLOAD_V1};
+use crate::store::commit_chunks::CommitChunkSizes;
+use crate::store::diesel_marco::{
+    fenced_transactional_blocking, read_only_blocking, transactional_blocking,
+    with_statement_timeout,
+};
+use crate::store::module_resolver::{
+    IndexerModuleCache, IndexerModuleResolver, PersistentModuleCache,
 };
-use crate::store::diesel_marco::{read_only_blocking, transactional_blocking};
-use crate::store::module_resolver::IndexerModuleResolver;
-use crate::store::query::DBFilter;
+use crate::store::page_queries::{
+    address_transactions_page_sql, events_page, gas_owner_filter, move_call_transactions_page_sql,
+    object_transactions_page_sql, recipient_transactions_page_sql, sponsored_transactions_filter,
+    transaction_by_digest_query, transaction_kinds_filter, transactions_by_abort_query,
+    transactions_by_gas_object_query, transactions_page, transactions_query, OBJECT_COLUMNS,
+};
+use crate::store::query::{DBFilter, ObjectTypeQuery};
+use crate::store::query_cache::QueryCache;
+use crate::store::query_plans::{Explain, QueryShape};
+use crate::store::readonly_sql::{ReadonlySql, ReadonlySqlResult};
+use crate::store::slow_query_log::SlowQueryLog;
 use crate::store::TransactionObjectChanges;
-use crate::store::{IndexerStore, TemporaryEpochStore};
-use crate::PgConnectionPool;
+use crate::store::{IndexerStore, TemporaryEpochStore, TimestampRounding};
+use crate::{get_pg_pool_connection, PgConnectionPool};
 
 const MAX_EVENT_PAGE_SIZE: usize = 1000;
-const PG_COMMIT_CHUNK_SIZE: usize = 1000;
+const MAX_TX_DEPENDENCY_DEPTH: usize = 100;
+const MAX_DIGEST_PREFIX_MATCHES: usize = 100;
+const RENDERED_DISPLAY_CACHE_SIZE: usize = 10_000;
+const ABORT_CONSTANT_CACHE_SIZE: usize = 1_000;
+// sources verified through another process only clear the cache of that process, so
+// cached constants expire to pick them up
+const ABORT_CONSTANT_CACHE_TTL_SECS: u64 = 300;
+// leaderboards by query, window and limit
+const HOT_ENTITY_CACHE_SIZE: usize = 100;
+// leaderboards change by whole buckets, a few seconds of staleness spare clients polling
+// them from aggregating the buckets on every request
+const HOT_ENTITY_CACHE_TTL_SECS: u64 = 10;
+const OBJECT_CONTENT_DIGEST_CACHE_SIZE: usize = 100_000;
+const TYPE_LAYOUT_CACHE_SIZE: usize = 10_000;
+// checkpoints committed by another process, or visible on a read replica only after
+// replication, are noticed by polling
+const CHECKPOINT_WAIT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+// pipeline of the commit watermark of checkpoints with their transactions, events and
+// transaction index tables
+const CHECKPOINTS_COMMIT_WATERMARK: &str = "checkpoints";
+// watermarks only move forward, re-ingested checkpoints leave them as they are
+const COMMIT_WATERMARK_UPSERT_QUERY: &str = "INSERT INTO commit_watermarks (pipeline, committed_checkpoint)
+VALUES ($1, $2)
+ON CONFLICT (pipeline) DO UPDATE
+SET committed_checkpoint = GREATEST(commit_watermarks.committed_checkpoint, EXCLUDED.committed_checkpoint)";
+const BASE58_ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
 
 const GET_PARTITION_SQL: &str = r#"
 SELECT parent.relname                           AS table_name,
@@ -84,41 +187,668 @@ WHERE parent.relkind = 'p'
 GROUP BY table_name;
 "#;
 
+// Coins owned by $1, each joined with the CoinMetadata of its coin type if indexed.
+const PORTFOLIO_SQL: &str = r#"
+SELECT substring(c.object_type FROM '^0x2::coin::Coin<(.*)>$') AS coin_type,
+       c.bcs,
+       m.bcs                                                   AS metadata_bcs
+FROM objects c
+         LEFT JOIN LATERAL (SELECT md.bcs
+                            FROM objects md
+                            WHERE md.object_type_package = c.object_type_package
+                              AND md.object_type_module = 'coin'
+                              AND md.object_type_name = 'CoinMetadata'
+                              AND md.object_type = replace(c.object_type, '::coin::Coin<', '::coin::CoinMetadata<')
+                            LIMIT 1) m ON TRUE
+WHERE c.owner_type = 'address_owner'
+  AND c.owner_address = $1
+  AND c.object_type_package = '0x0000000000000000000000000000000000000000000000000000000000000002'
+  AND c.object_type_module = 'coin'
+  AND c.object_type_name = 'Coin'
+  AND c.object_status NOT IN ('deleted', 'wrapped', 'unwrapped_then_deleted');
+"#;
+
+// Transactions reachable from $1 by following dependency edges, at most $2 hops away.
+const TX_ANCESTORS_QUERY: &str = r#"
+WITH RECURSIVE ancestors (transaction_digest, depth) AS (
+    SELECT dependency, 1::BIGINT
+    FROM tx_dependencies
+    WHERE transaction_digest = $1
+    UNION
+    SELECT d.dependency, a.depth + 1
+    FROM tx_dependencies d
+             JOIN ancestors a ON d.transaction_digest = a.transaction_digest
+    WHERE a.depth < $2
+)
+SELECT transaction_digest, MIN(depth) AS depth
+FROM ancestors
+GROUP BY transaction_digest
+ORDER BY depth, transaction_digest;
+"#;
+
+// Transactions reaching $1 by following dependency edges, at most $2 hops away.
+const TX_DESCENDANTS_QUERY: &str = r#"
+WITH RECURSIVE descendants (transaction_digest, depth) AS (
+    SELECT transaction_digest, 1::BIGINT
+    FROM tx_dependencies
+    WHERE dependency = $1
+    UNION
+    SELECT d.transaction_digest, a.depth + 1
+    FROM tx_dependencies d
+             JOIN descendants a ON d.dependency = a.transaction_digest
+    WHERE a.depth < $2
+)
+SELECT transaction_digest, MIN(depth) AS depth
+FROM descendants
+GROUP BY transaction_digest
+ORDER BY depth, transaction_digest;
+"#;
+
+// Objects touched by the most transactions in the buckets starting at $1 or later, at most $2.
+const HOT_OBJECTS_QUERY: &str = r#"
+SELECT object_id AS id, SUM(touches)::BIGINT AS count
+FROM hot_objects
+WHERE bucket_start_ms >= $1
+GROUP BY object_id
+ORDER BY count DESC, id
+LIMIT $2;
+"#;
+
+// Packages with the most Move calls in the buckets starting at $1 or later, at most $2.
+const HOT_PACKAGES_QUERY: &str = r#"
+SELECT package AS id, SUM(calls)::BIGINT AS count
+FROM hot_packages
+WHERE bucket_start_ms >= $1
+GROUP BY package
+ORDER BY count DESC, id
+LIMIT $2;
+"#;
+
+// the collation has to match transactions_transaction_digest_prefix for the index to be used
+const TX_DIGEST_PREFIX_QUERY: &str = r#"
+SELECT *
+FROM transactions
+WHERE transaction_digest COLLATE "C" LIKE $1
+ORDER BY transaction_digest COLLATE "C"
+LIMIT $2;
+"#;
+
+// the last checkpoint of the range has no next one, its NULL comparisons filter it out
+const CHECKPOINT_DISCONTINUITIES_QUERY: &str = r#"
+SELECT *
+FROM (
+    SELECT
+        sequence_number,
+        network_total_transactions,
+        LEAD(sequence_number) OVER w AS next_sequence_number,
+        LEAD(network_total_transactions) OVER w AS next_network_total_transactions,
+        LEAD(total_transaction_blocks) OVER w AS next_total_transaction_blocks
+    FROM checkpoints
+    WHERE sequence_number BETWEEN $1 AND $2
+    WINDOW w AS (ORDER BY sequence_number)
+) c
+WHERE next_sequence_number <> sequence_number + 1
+    OR next_network_total_transactions <> network_total_transactions + next_total_transaction_blocks
+ORDER BY sequence_number;
+"#;
+
+const MATERIALIZED_VIEW_STATUS_QUERY: &str = r#"
+SELECT ispopulated
+FROM pg_matviews
+WHERE matviewname = $1;
+"#;
+
+// growth of epochs $1 onwards, from the checkpoints of these epochs only; the size of the
+// previous version of each object is looked up by object id and version
+const EPOCH_STORAGE_GROWTH_INSERT_QUERY: &str = r#"
+INSERT INTO epoch_storage_growth
+SELECT h.epoch,
+       COALESCE(CASE WHEN h.object_type LIKE '0x%::%::%'
+           THEN '0x' || lpad(substr(split_part(h.object_type, '::', 1), 3), 64, '0')
+           END, ''),
+       COALESCE(CASE WHEN h.object_type LIKE '0x%::%::%'
+           THEN split_part(h.object_type, '::', 2)
+           END, ''),
+       COALESCE(CASE WHEN h.object_type LIKE '0x%::%::%'
+           THEN split_part(split_part(h.object_type, '::', 3), '<', 1)
+           END, ''),
+       SUM(h.object_size - COALESCE(p.object_size, 0))::BIGINT,
+       COUNT(*)
+FROM objects_history h
+LEFT JOIN LATERAL (
+    SELECT object_size
+    FROM objects_history p
+    WHERE p.object_id = h.object_id AND p.version < h.version AND p.checkpoint >= 0
+    ORDER BY p.version DESC
+    LIMIT 1
+) p ON TRUE
+-- the fast path partition holds versions not yet in a checkpoint
+WHERE h.checkpoint >= GREATEST(COALESCE((SELECT first_checkpoint_id FROM epochs WHERE epoch = $1), 0), 0)
+    AND h.epoch >= $1
+GROUP BY 1, 2, 3, 4;
+"#;
+
+// live objects of each struct type as of the objects committed so far, as counted when
+// they are committed, one row per type rather than a scan of the objects
+const OBJECT_TYPE_STATS_QUERY: &str = r#"
+INSERT INTO object_type_stats (epoch, package, module, name, object_count)
+SELECT $1, package, module, name, object_count
+FROM object_type_counts
+WHERE object_count > 0;
+"#;
+
+// version, liveness and struct type of the rows of objects $1, the type columns being
+// generated by Postgres. Locked when read before a write so that the counts of the rows
+// cannot change between the read and the write.
+const OBJECT_TYPE_STATES_QUERY: &str = r#"
+SELECT object_id::TEXT AS object_id, version,
+    object_status NOT IN ('deleted', 'wrapped', 'unwrapped_then_deleted') AS live,
+    object_type_package, object_type_module, object_type_name
+FROM objects
+WHERE object_id = ANY($1)
+"#;
+
+// counts are updated in key order so that concurrent commits do not deadlock
+const OBJECT_TYPE_COUNTS_UPDATE_QUERY: &str = r#"
+INSERT INTO object_type_counts AS c (package, module, name, object_count)
+SELECT * FROM UNNEST($1::VARCHAR[], $2::VARCHAR[], $3::VARCHAR[], $4::BIGINT[])
+    AS d(package, module, name, object_count)
+ORDER BY package, module, name
+ON CONFLICT (package, module, name)
+    DO UPDATE SET object_count = c.object_count + EXCLUDED.object_count;
+"#;
+
+#[derive(QueryableByName, Debug, Clone)]
+struct ObjectTypeState {
+    #[diesel(sql_type = VarChar)]
+    object_id: String,
+    #[diesel(sql_type = BigInt)]
+    version: i64,
+    #[diesel(sql_type = Bool)]
+    live: bool,
+    #[diesel(sql_type = Nullable<VarChar>)]
+    object_type_package: Option<String>,
+    #[diesel(sql_type = Nullable<VarChar>)]
+    object_type_module: Option<String>,
+    #[diesel(sql_type = Nullable<VarChar>)]
+    object_type_name: Option<String>,
+}
+
+impl ObjectTypeState {
+    /// The struct type the row counts towards, if it is a live object of a struct type.
+    fn counted_type(self) -> Option<(String, String, String)> {
+        match (
+            self.live,
+            self.object_type_package,
+            self.object_type_module,
+            self.object_type_name,
+        ) {
+            (true, Some(package), Some(module), Some(name)) => Some((package, module, name)),
+            _ => None,
+        }
+    }
+}
+
+// partitions are counted towards their parent, whose own size is 0
+const TABLE_SIZES_QUERY: &str = r#"
+SELECT COALESCE(p.relname, c.relname)::TEXT AS table_name,
+    SUM(pg_total_relation_size(c.oid))::BIGINT AS total_bytes,
+    SUM(GREATEST(c.reltuples, 0))::BIGINT AS estimated_rows
+FROM pg_class c
+JOIN pg_namespace n ON n.oid = c.relnamespace
+LEFT JOIN pg_inherits i ON i.inhrelid = c.oid
+LEFT JOIN pg_class p ON p.oid = i.inhparent
+WHERE n.nspname = current_schema()
+    AND c.relkind IN ('r', 'p')
+GROUP BY 1
+ORDER BY 1;
+"#;
+
+// superusers bypass privilege checks, owners of the tables have all privileges on them
+const WRITE_PRIVILEGES_QUERY: &str = r#"
+SELECT r.rolsuper
+    OR has_schema_privilege(current_schema(), 'CREATE')
+    OR EXISTS (
+        SELECT 1
+        FROM pg_tables t
+        WHERE t.schemaname = current_schema()
+            AND has_table_privilege(
+                format('%I.%I', t.schemaname, t.tablename),
+                'INSERT, UPDATE, DELETE, TRUNCATE'
+            )
+    ) AS can_write
+FROM pg_roles r
+WHERE r.rolname = current_user;
+"#;
+
+#[derive(QueryableByName, Debug, Clone)]
+struct WritePrivileges {
+    #[diesel(sql_type = Bool)]
+    can_write: bool,
+}
+
+#[derive(QueryableByName, Debug, Clone)]
+struct JsonRow {
+    #[diesel(sql_type = diesel::sql_types::Json)]
+    row: serde_json::Value,
+}
+
+// generated columns are not written, so not bound either
+const COLUMN_COUNTS_QUERY: &str = r#"
+SELECT table_name::TEXT AS table_name, COUNT(*)::BIGINT AS column_count
+FROM information_schema.columns
+WHERE table_schema = current_schema() AND is_generated = 'NEVER'
+GROUP BY table_name
+"#;
+
+#[derive(QueryableByName, Debug, Clone)]
+struct ColumnCount {
+    #[diesel(sql_type = VarChar)]
+    table_name: String,
+    #[diesel(sql_type = BigInt)]
+    column_count: i64,
+}
+
+// relations and functions outside of the system catalogs that the role of read only SQL
+// queries can read and execute, which requires USAGE on their schema too
+const READONLY_SQL_RELATIONS_QUERY: &str = r#"
+SELECT n.nspname = current_schema() AS in_schema, c.relname::TEXT AS name
+FROM pg_class c JOIN pg_namespace n ON n.oid = c.relnamespace
+WHERE n.nspname NOT IN ('pg_catalog', 'information_schema') AND n.nspname NOT LIKE 'pg_toast%'
+    AND c.relkind IN ('r', 'v', 'm', 'p', 'f')
+    AND has_schema_privilege($1, n.oid, 'USAGE')
+    AND has_table_privilege($1, c.oid, 'SELECT')
+"#;
+
+const READONLY_SQL_FUNCTIONS_QUERY: &str = r#"
+SELECT n.nspname = current_schema() AS in_schema, p.proname::TEXT AS name
+FROM pg_proc p JOIN pg_namespace n ON n.oid = p.pronamespace
+WHERE n.nspname NOT IN ('pg_catalog', 'information_schema')
+    AND has_schema_privilege($1, n.oid, 'USAGE')
+    AND has_function_privilege($1, p.oid, 'EXECUTE')
+"#;
+
+const READONLY_SQL_ROLE_QUERY: &str = r#"
+SELECT pg_has_role(current_user, rolname, 'MEMBER')
+    AND NOT (rolsuper OR rolbypassrls OR rolcreaterole OR rolcreatedb) AS usable
+FROM pg_roles WHERE rolname = $1
+"#;
+
+#[derive(QueryableByName, Debug, Clone)]
+struct ReadonlySqlGrant {
+    #[diesel(sql_type = Bool)]
+    in_schema: bool,
+    #[diesel(sql_type = VarChar)]
+    name: String,
+}
+
+#[derive(QueryableByName, Debug, Clone)]
+struct ReadonlySqlRole {
+    #[diesel(sql_type = Bool)]
+    usable: bool,
+}
+
+// pointers to blobs among the payloads of the objects_history rows of checkpoints [$1, $2), the
+// payloads starting with $3
+const PRUNED_OBJECT_BLOBS_QUERY: &str = r#"
+SELECT DISTINCT h.object_id::TEXT AS object_id, (b).data AS pointer
+FROM objects_history h, unnest(h.bcs) b
+WHERE h.checkpoint >= $1 AND h.checkpoint < $2 AND substring((b).data FROM 1 FOR 1) = $3
+"#;
+
+// pointers to blobs among the payloads of the rows of objects $1, in objects or objects_history
+const OBJECT_BLOBS_QUERY: &str = r#"
+SELECT DISTINCT o.object_id::TEXT AS object_id, (b).data AS pointer
+FROM (
+    SELECT object_id, bcs FROM objects WHERE object_id = ANY($1)
+    UNION ALL
+    SELECT object_id, bcs FROM objects_history WHERE object_id = ANY($1)
+) o, unnest(o.bcs) b
+WHERE substring((b).data FROM 1 FOR 1) = $2
+"#;
+
+#[derive(QueryableByName, Debug, Clone)]
+struct ObjectBlob {
+    #[diesel(sql_type = VarChar)]
+    object_id: String,
+    #[diesel(sql_type = Bytea)]
+    pointer: Vec<u8>,
+}
+
+// 'D' is a disabled trigger, other states fire in some replication role
+const OBJECTS_HISTORY_TRIGGER_QUERY: &str = r#"
+SELECT tgenabled <> 'D' AS enabled
+FROM pg_trigger
+WHERE tgrelid = 'objects'::regclass AND tgname = 'objects_history'
+"#;
+
+#[derive(QueryableByName, Debug, Clone)]
+struct TriggerStatus {
+    #[diesel(sql_type = Bool)]
+    enabled: bool,
+}
+
+// advisory locks are per database, the lock is named after the schema to elect a leader
+// per network of a database
+const TRY_LEADER_LOCK_QUERY: &str = "SELECT pg_try_advisory_lock(k.key) AS locked, \
+    pg_backend_pid() AS pid, k.key \
+    FROM (SELECT hashtext(current_schema() || ':' || $1)::BIGINT AS key) k";
+// whether the backend `$2` holds the advisory lock of key `$1`, whose high and low halves
+// are the classid and objid of the lock
+const HOLDS_LEADER_LOCK_QUERY: &str = "SELECT EXISTS (SELECT 1 FROM pg_locks \
+    WHERE locktype = 'advisory' AND granted AND objsubid = 1 \
+    AND database = (SELECT oid FROM pg_database WHERE datname = current_database()) \
+    AND ((classid::BIGINT << 32) | objid::BIGINT) = $1 AND pid = $2) AS locked";
+
+#[derive(QueryableByName, Debug, Clone)]
+struct LeaderLock {
+    #[diesel(sql_type = Bool)]
+    locked: bool,
+}
+
+#[derive(QueryableByName, Debug, Clone)]
+struct LeaderLockAttempt {
+    #[diesel(sql_type = Bool)]
+    locked: bool,
+    #[diesel(sql_type = Integer)]
+    pid: i32,
+    #[diesel(sql_type = BigInt)]
+    key: i64,
+}
+
+// Fence of the writes of a replica taking part in leader election, which only commit while
+// the backend of its leader connection holds the leader lock.
+#[derive(Debug, Clone, Copy)]
+struct LeaderFence {
+    key: i64,
+    // backend of the leader connection, none while standing by or once the lock is lost
+    pid: Option<i32>,
+}
+
+#[derive(QueryableByName, Debug, Clone)]
+struct UpdatedObject {
+    #[diesel(sql_type = VarChar)]
+    object_id: String,
+}
+
 #[derive(QueryableByName, Debug, Clone)]
 struct TempDigestTable {
     #[diesel(sql_type = VarChar)]
     digest_name: String,
 }
 
+type LeaderLockConnector = Arc<dyn Fn() -> Result<PgConnection, IndexerError> + Send + Sync>;
+
 #[derive(Clone)]
 pub struct PgIndexerStore {
     blocking_cp: PgConnectionPool,
+    // pool of the reader role if any, possibly of a read replica, serving queries of the
+    // RPC APIs. Reads of the indexing pipeline and module resolution stay on blocking_cp,
+    // as they depend on data just committed.
+    read_cp: PgConnectionPool,
+    // statement timeout of queries on read_cp, so that an expensive query of an RPC
+    // client cannot hold a connection for long.
+    query_timeout: Duration,
+    // whether reads of the RPC APIs may write back what they computed, e.g. event schemas
+    // of packages indexed before schemas were recorded, only ever through
+    // `write_from_read_path`. RPC servers are expected to hold a reader role only.
+    read_path_writes: bool,
+    query_cache: Option<QueryCache>,
+    slow_query_log: Option<SlowQueryLog>,
     // MUSTFIX(gegaowp): temporarily disable partition management.
     #[allow(dead_code)]
     partition_manager: PartitionManager,
-    module_cache: Arc<SyncModuleCache<IndexerModuleResolver>>,
+    module_cache: Arc<IndexerModuleCache>,
+    // rendered Display fields by object id and version and Display version, which
+    // together determine the rendering
+    rendered_displays:
+        Arc<Mutex<SizedCache<(ObjectID, SequenceNumber, i32), DisplayFieldsResponse>>>,
+    // error constant names by abort code of modules with a verified source by package and
+    // module name, cleared when a verified source is written and expiring otherwise
+    abort_constants: Arc<Mutex<TimedSizedCache<(ObjectID, String), Arc<BTreeMap<u64, String>>>>>,
+    hot_entities: Arc<Mutex<TimedSizedCache<(&'static str, HotWindow, usize), Vec<HotEntity>>>>,
+    // content digests of the objects last written to the objects table by this process, to
+    // tell mutations leaving an object unchanged apart
+    object_content_digests: Arc<Mutex<SizedCache<String, [u8; 32]>>>,
+    // layouts of fully instantiated struct types, which never change as packages are
+    // immutable
+    type_layouts: Arc<Mutex<SizedCache<StructTag, MoveStructLayout>>>,
+    // zstd level of the BCS of objects and raw transactions written, uncompressed if not set
+    payload_compression_level: Option<i32>,
+    // store of the BCS of large objects, kept in Postgres if not set
+    blob_store: Option<BlobStore>,
+    // read by object commits from the upload of their blobs until their rows are committed,
+    // written while the blobs of pruned rows are checked for references and deleted
+    blob_references: Arc<RwLock<()>>,
+    // ad hoc queries of clients over allowlisted views, rejected if not set
+    readonly_sql: Option<ReadonlySql>,
+    commit_chunk_sizes: CommitChunkSizes,
+    // opens the connection of the leader lock, leader election is not supported if not set
+    leader_lock_connector: Option<LeaderLockConnector>,
+    // connection holding the leader lock, if this replica is the leader, kept out of the
+    // pool so that it is closed, releasing the lock, once the lock is found lost
+    leader_connection: Arc<Mutex<Option<PgConnection>>>,
+    leader_fence: Arc<Mutex<Option<LeaderFence>>>,
     metrics: IndexerMetrics,
+    // wakes up waiters of checkpoints committed by this process
+    checkpoint_committed: Arc<Notify>,
 }
 
 impl PgIndexerStore {
+    pub const DEFAULT_QUERY_TIMEOUT: Duration = Duration::from_secs(10);
+
     pub fn new(blocking_cp: PgConnectionPool, metrics: IndexerMetrics) -> Self {
-        let module_cache = Arc::new(SyncModuleCache::new(IndexerModuleResolver::new(
-            blocking_cp.clone(),
-        )));
+        let module_cache = Arc::new(IndexerModuleCache::new(
+            IndexerModuleResolver::new(blocking_cp.clone()),
+            metrics.total_unparseable_module.clone(),
+        ));
         PgIndexerStore {
             blocking_cp: blocking_cp.clone(),
+            read_cp: blocking_cp.clone(),
+            query_timeout: Self::DEFAULT_QUERY_TIMEOUT,
+            read_path_writes: false,
+            query_cache: None,
+            slow_query_log: None,
             partition_manager: PartitionManager::new(blocking_cp).unwrap(),
             module_cache,
+            rendered_displays: Arc::new(Mutex::new(SizedCache::with_size(
+                RENDERED_DISPLAY_CACHE_SIZE,
+            ))),
+            abort_constants: Arc::new(Mutex::new(TimedSizedCache::with_size_and_lifespan(
+                ABORT_CONSTANT_CACHE_SIZE,
+                ABORT_CONSTANT_CACHE_TTL_SECS,
+            ))),
+            hot_entities: Arc::new(Mutex::new(TimedSizedCache::with_size_and_lifespan(
+                HOT_ENTITY_CACHE_SIZE,
+                HOT_ENTITY_CACHE_TTL_SECS,
+            ))),
+            object_content_digests: Arc::new(Mutex::new(SizedCache::with_size(
+                OBJECT_CONTENT_DIGEST_CACHE_SIZE,
+            ))),
+            type_layouts: Arc::new(Mutex::new(SizedCache::with_size(TYPE_LAYOUT_CACHE_SIZE))),
+            payload_compression_level: None,
+            blob_store: None,
+            blob_references: Arc::new(RwLock::new(())),
+            readonly_sql: None,
+            commit_chunk_sizes: CommitChunkSizes::default(),
+            leader_lock_connector: None,
+            leader_connection: Arc::new(Mutex::new(None)),
+            leader_fence: Arc::new(Mutex::new(None)),
             metrics,
+            checkpoint_committed: Arc::new(Notify::new()),
+        }
+    }
+
+    /// Serves queries of the RPC APIs from `read_cp`, writes keep going to the writer pool.
+    pub fn with_reader_pool(mut self, read_cp: PgConnectionPool) -> Self {
+        self.read_cp = read_cp;
+        self
+    }
+
+    /// Takes the leader lock with connections opened by `connect`, which must be outside of
+    /// any pool, e.g. of `new_pg_connection_in_schema`.
+    pub fn with_leader_lock_connector(
+        mut self,
+        connect: impl Fn() -> Result<PgConnection, IndexerError> + Send + Sync + 'static,
+    ) -> Self {
+        self.leader_lock_connector = Some(Arc::new(connect));
+        self
+    }
+
+    /// Lets reads of the RPC APIs write back what they computed, which they otherwise only
+    /// return, for processes whose role is allowed to write.
+    pub fn with_read_path_writes(mut self) -> Self {
+        self.read_path_writes = true;
+        self
+    }
+
+    /// Queries of the RPC APIs running longer than `query_timeout` are cancelled and fail
+    /// with `IndexerError::QueryTimeout`.
+    pub fn with_query_timeout(mut self, query_timeout: Duration) -> Self {
+        self.query_timeout = query_timeout;
+        self
+    }
+
+    /// Caches the latest checkpoint and current epoch, read on most RPC requests, for a
+    /// short time.
+    pub fn with_query_cache(mut self) -> Self {
+        self.query_cache = Some(QueryCache::default());
+        self
+    }
+
+    /// Compresses the BCS of objects and raw transactions written with zstd at `level`. Rows
+    /// are read back the same with or without compression, so it can be turned on and off.
+    pub fn with_payload_compression(mut self, level: i32) -> Self {
+        self.payload_compression_level = Some(level);
+        self
+    }
+
+    /// Offloads the BCS of objects written that is larger than the threshold of `blob_store`
+    /// to it, objects are read back from it transparently. Objects written with offloading
+    /// can only be read by a store with the same blob store.
+    pub fn with_blob_store(mut self, blob_store: BlobStore) -> Self {
+        self.blob_store = Some(blob_store);
+        self
+    }
+
+    /// Serves ad hoc read only queries of clients over the views allowlisted by
+    /// `readonly_sql`, from the reader pool. Fails unless the role of the queries can read
+    /// exactly the allowlisted views and execute no functions other than the built-in ones,
+    /// see `ReadonlySql`.
+    pub fn with_readonly_sql(mut self, readonly_sql: ReadonlySql) -> Result<Self, IndexerError> {
+        let role = readonly_sql.role.clone();
+        let (usable, relations, functions) = read_only_blocking!(&self.read_cp, |conn| {
+            let usable = diesel::sql_query(READONLY_SQL_ROLE_QUERY)
+                .bind::<VarChar, _>(&role)
+                .get_result::<ReadonlySqlRole>(conn)
+                .optional()?;
+            let relations = diesel::sql_query(READONLY_SQL_RELATIONS_QUERY)
+                .bind::<VarChar, _>(&role)
+                .load::<ReadonlySqlGrant>(conn)?;
+            let functions = diesel::sql_query(READONLY_SQL_FUNCTIONS_QUERY)
+                .bind::<VarChar, _>(&role)
+                .load::<ReadonlySqlGrant>(conn)?;
+            Ok::<_, diesel::result::Error>((usable, relations, functions))
+        })
+        .context(&format!(
+            "Failed reading grants of role {role} from PostgresDB"
+        ))?;
+        let invalid = |msg: String| {
+            Err(IndexerError::InvalidArgumentError(format!(
+                "Role {role} of read only SQL queries {msg}"
+            )))
+        };
+        if !usable.map_or(false, |role| role.usable) {
+            return invalid(
+                "must exist, be granted to the indexer user and have no attributes".to_string(),
+            );
+        }
+        let readable = relations
+            .iter()
+            .filter(|grant| grant.in_schema)
+            .map(|grant| grant.name.as_str())
+            .collect::<BTreeSet<_>>();
+        if let Some(view) = readonly_sql
+            .views()
+            .iter()
+            .find(|view| !readable.contains(view.as_str()))
+        {
+            return invalid(format!("cannot SELECT from {view}"));
+        }
+        if let Some(relation) = relations
+            .iter()
+            .find(|grant| !(grant.in_schema && readonly_sql.views().contains(&grant.name)))
+        {
+            return invalid(format!(
+                "can SELECT from {}, which is not an allowlisted view",
+                relation.name
+            ));
+        }
+        if let Some(function) = functions.first() {
+            return invalid(format!("can EXECUTE function {}", function.name));
+        }
+        self.readonly_sql = Some(readonly_sql);
+        Ok(self)
+    }
+
+    // objects with the payloads offloaded to the blob store as they were written
+    fn resolve_blobs(&self, object: Object) -> Result<Object, IndexerError> {
+        match &self.blob_store {
+            Some(blob_store) => blob_store.resolve(object),
+            None => Ok(object),
         }
     }
 
+    /// Commits rows in chunks of `chunk_sizes`, capped by the columns of the tables in the
+    /// database so that a chunk never exceeds the bind parameter limit of Postgres.
+    pub fn with_commit_chunk_sizes(
+        mut self,
+        chunk_sizes: CommitChunkSizes,
+    ) -> Result<Self, IndexerError> {
+        let column_counts = read_only_blocking!(&self.blocking_cp, |conn| {
+            diesel::sql_query(COLUMN_COUNTS_QUERY).load::<ColumnCount>(conn)
+        })
+        .context("Failed reading column counts of tables from PostgresDB")?;
+        self.commit_chunk_sizes = chunk_sizes.with_column_counts(
+            column_counts
+                .into_iter()
+                .map(|count| (count.table_name, count.column_count as usize))
+                .collect(),
+        );
+        Ok(self)
+    }
+
+    /// Logs reads of the RPC APIs taking longer than `threshold`, with their parameters,
+    /// and counts them in `total_slow_store_reads` by store method.
+    pub fn with_slow_query_log(mut self, threshold: Duration) -> Self {
+        self.slow_query_log = Some(SlowQueryLog::new(
+            threshold,
+            self.metrics.total_slow_store_reads.clone(),
+        ));
+        self
+    }
+
+    /// Backs module resolution with an on-disk module cache at `path`, consulted before
+    /// Postgres for modules not yet in the in-memory module cache.
+    pub fn with_persistent_module_cache(mut self, path: &Path) -> Result<Self, IndexerError> {
+        let disk_cache = PersistentModuleCache::open(path)?;
+        self.module_cache = Arc::new(
+            IndexerModuleCache::new(
+                IndexerModuleResolver::new(self.blocking_cp.clone()),
+                self.metrics.total_unparseable_module.clone(),
+            )
+            .with_disk_cache(disk_cache),
+        );
+        info!("Opened persistent module cache at {:?}", path);
+        Ok(self)
+    }
+
     pub fn get_sui_types_object(
         &self,
         object_id: &ObjectID,
         version: &SequenceNumber,
     ) -> Result<sui_types::object::Object, IndexerError> {
-        let pg_object = read_only_blocking!(&self.blocking_cp, |conn| {
+        let pg_object = read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
             objects_history::dsl::objects_history
                 .select((
                     objects_history::epoch,
@@ -135,6 +865,7 @@ impl PgIndexerStore {
                     objects_history::has_public_transfer,
                     objects_history::storage_rebate,
                     objects_history::bcs,
+                    objects_history::object_size,
                 ))
                 .filter(objects_history::object_id.eq(object_id.to_string()))
                 .filter(objects_history::version.eq(version.value() as i64))
@@ -145,7 +876,7 @@ impl PgIndexerStore {
         .context("Failed reading Object from PostgresDB");
         match pg_object {
             Ok(pg_object) => {
-                let object = sui_types::object::Object::try_from(pg_object)?;
+                let object = sui_types::object::Object::try_from(self.resolve_blobs(pg_object)?)?;
                 Ok(object)
             }
             Err(e) => Err(e),
@@ -157,7 +888,7 @@ impl PgIndexerStore {
         id: &ObjectID,
         version: &SequenceNumber,
     ) -> Result<Option<sui_types::object::Object>, IndexerError> {
-        let pg_object = read_only_blocking!(&self.blocking_cp, |conn| {
+        let pg_object = read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
             objects_history::dsl::objects_history
                 .select((
                     objects_history::epoch,
@@ -174,6 +905,7 @@ impl PgIndexerStore {
                     objects_history::has_public_transfer,
                     objects_history::storage_rebate,
                     objects_history::bcs,
+                    objects_history::object_size,
                 ))
                 .filter(objects_history::object_id.eq(id.to_string()))
                 .filter(objects_history::version.le(version.value() as i64))
@@ -186,7 +918,7 @@ impl PgIndexerStore {
         .context("Failed reading Object before version from PostgresDB");
         match pg_object {
             Ok(Some(pg_object)) => {
-                let object = sui_types::object::Object::try_from(pg_object)?;
+                let object = sui_types::object::Object::try_from(self.resolve_blobs(pg_object)?)?;
                 Ok(Some(object))
             }
             Ok(None) => Ok(None),
@@ -195,14 +927,58 @@ impl PgIndexerStore {
     }
 
     fn get_latest_tx_checkpoint_sequence_number(&self) -> Result<i64, IndexerError> {
-        read_only_blocking!(&self.blocking_cp, |conn| {
-            checkpoints::dsl::checkpoints
-                .select(max(checkpoints::sequence_number))
-                .first::<Option<i64>>(conn)
-                // -1 to differentiate between no checkpoints and the first checkpoint
-                .map(|o| o.unwrap_or(-1))
+        let read = || {
+            read_only_blocking!(&self.blocking_cp, |conn| {
+                checkpoints::dsl::checkpoints
+                    .select(max(checkpoints::sequence_number))
+                    .first::<Option<i64>>(conn)
+                    // -1 to differentiate between no checkpoints and the first checkpoint
+                    .map(|o| o.unwrap_or(-1))
+            })
+            .context("Failed reading latest checkpoint sequence number from PostgresDB")
+        };
+        match &self.query_cache {
+            Some(cache) => cache.latest_checkpoint.get_or_try_insert_with(read),
+            None => read(),
+        }
+    }
+
+    // The only writer of the RPC APIs, failing unless enabled with `with_read_path_writes`.
+    fn write_from_rpc(
+        &self,
+        what: &str,
+        write: impl FnOnce(&mut PgConnection) -> Result<usize, diesel::result::Error>,
+    ) -> Result<usize, IndexerError> {
+        if !self.read_path_writes {
+            return Err(IndexerError::InvalidArgumentError(format!(
+                "Writing {what} is not allowed, the RPC server is read-only"
+            )));
+        }
+        fenced_transactional_blocking!(self, write)
+            .context(&format!("Failed writing {what} to PostgresDB"))
+    }
+
+    // Writes back what reads of the RPC APIs computed, a no-op unless enabled with
+    // `with_read_path_writes`. Failing to write back leaves the read to succeed.
+    fn write_from_read_path(
+        &self,
+        what: &str,
+        write: impl FnOnce(&mut PgConnection) -> Result<usize, diesel::result::Error>,
+    ) {
+        if !self.read_path_writes {
+            return;
+        }
+        if let Err(e) = self.write_from_rpc(what, write) {
+            warn!("Failed writing {what} from a read with error: {e}");
+        }
+    }
+
+    fn reader_has_write_privileges(&self) -> Result<bool, IndexerError> {
+        read_only_blocking!(&self.read_cp, |conn| {
+            diesel::sql_query(WRITE_PRIVILEGES_QUERY).get_result::<WritePrivileges>(conn)
         })
-        .context("Failed reading latest checkpoint sequence number from PostgresDB")
+        .map(|privileges| privileges.can_write)
+        .context("Failed checking write privileges of the reader role")
     }
 
     fn get_latest_object_checkpoint_sequence_number(&self) -> Result<i64, IndexerError> {
@@ -216,11 +992,50 @@ impl PgIndexerStore {
         .context("Failed reading latest object checkpoint sequence number from PostgresDB")
     }
 
+    fn get_latest_epoch_id(&self) -> Result<i64, IndexerError> {
+        read_only_blocking!(&self.blocking_cp, |conn| {
+            epochs::dsl::epochs
+                .select(max(epochs::epoch))
+                .first::<Option<i64>>(conn)
+                .map(|o| o.unwrap_or(-1))
+        })
+        .context("Failed reading latest epoch from PostgresDB")
+    }
+
+    // Whether the checkpoint and all its data are committed: its row, the tables committed
+    // by the tasks of the checkpoint commit task, as of the commit watermark, and its object
+    // changes, as of the latest checkpoint of objects.
+    fn is_checkpoint_committed(&self, sequence_number: i64) -> Result<bool, IndexerError> {
+        read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
+            let checkpoint = checkpoints::dsl::checkpoints
+                .select(checkpoints::sequence_number)
+                .filter(checkpoints::sequence_number.eq(sequence_number))
+                .first::<i64>(conn)
+                .optional()?;
+            let committed_checkpoint = commit_watermarks::table
+                .select(commit_watermarks::committed_checkpoint)
+                .filter(commit_watermarks::pipeline.eq(CHECKPOINTS_COMMIT_WATERMARK))
+                .first::<i64>(conn)
+                .optional()?;
+            let object_checkpoint = objects::table
+                .select(max(objects::checkpoint))
+                .first::<Option<i64>>(conn)?;
+            Ok::<_, diesel::result::Error>(
+                checkpoint.is_some()
+                    && committed_checkpoint.map_or(false, |c| c >= sequence_number)
+                    && object_checkpoint.map_or(false, |c| c >= sequence_number),
+            )
+        })
+        .context(&format!(
+            "Failed reading commit watermarks of checkpoint {sequence_number} from PostgresDB"
+        ))
+    }
+
     fn get_checkpoint(
         &self,
         id: CheckpointId,
     ) -> Result<sui_json_rpc_types::Checkpoint, IndexerError> {
-        read_only_blocking!(&self.blocking_cp, |conn| {
+        read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
             let cp: Checkpoint = match id {
                 CheckpointId::SequenceNumber(seq) => checkpoints::dsl::checkpoints
                     .filter(checkpoints::sequence_number.eq(seq as i64))
@@ -262,18 +1077,7 @@ impl PgIndexerStore {
                     })
                     .collect::<Result<Vec<_>, _>>()?;
 
-                let epoch_commitments = epoch_commitments
-                    .iter()
-                    .flatten()
-                    .flat_map(|v| {
-                        if let Ok(v) = v.clone().try_into() {
-                            return Some(CheckpointCommitment::ECMHLiveObjectSetDigest(
-                                ECMHLiveObjectSetDigest::from(Digest::new(v)),
-                            ));
-                        }
-                        None
-                    })
-                    .collect::<Vec<_>>();
+                let epoch_commitments = commitments_from_column(&epoch_commitments);
 
                 next_version.map(|next_epoch_protocol_version| EndOfEpochData {
                     next_epoch_committee,
@@ -305,7 +1109,7 @@ impl PgIndexerStore {
         cursor: Option<CheckpointId>,
         limit: usize,
     ) -> Result<Vec<sui_json_rpc_types::Checkpoint>, IndexerError> {
-        read_only_blocking!(&self.blocking_cp, |conn| {
+        read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
             let cp_vec: Vec<Checkpoint> = match cursor {
                 Some(CheckpointId::SequenceNumber(seq)) => checkpoints::dsl::checkpoints
                     .filter(checkpoints::sequence_number.gt(seq as i64))
@@ -356,21 +1160,73 @@ impl PgIndexerStore {
         )
     }
 
+    fn get_checkpoint_discontinuities(
+        &self,
+        start: i64,
+        end: i64,
+    ) -> Result<Vec<CheckpointDiscontinuity>, IndexerError> {
+        read_only_blocking!(&self.blocking_cp, |conn| {
+            diesel::sql_query(CHECKPOINT_DISCONTINUITIES_QUERY)
+                .bind::<BigInt, _>(start)
+                .bind::<BigInt, _>(end)
+                .load::<CheckpointDiscontinuity>(conn)
+        })
+        .context(&format!(
+            "Failed reading discontinuities of checkpoints {start} to {end}"
+        ))
+    }
+
     fn get_checkpoint_sequence_number(
         &self,
         digest: CheckpointDigest,
     ) -> Result<CheckpointSequenceNumber, IndexerError> {
         Ok(
-            read_only_blocking!(&self.blocking_cp, |conn| checkpoints::dsl::checkpoints
-                .select(checkpoints::sequence_number)
-                .filter(checkpoints::checkpoint_digest.eq(digest.base58_encode()))
-                .first::<i64>(conn))
+            read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
+                checkpoints::dsl::checkpoints
+                    .select(checkpoints::sequence_number)
+                    .filter(checkpoints::checkpoint_digest.eq(digest.base58_encode()))
+                    .first::<i64>(conn)
+            })
             .context("Failed reading checkpoint seq number from PostgresDB")? as u64,
         )
     }
 
+    fn get_checkpoint_at_timestamp(
+        &self,
+        timestamp_ms: u64,
+        rounding: TimestampRounding,
+    ) -> Result<Option<CheckpointSequenceNumber>, IndexerError> {
+        // checkpoint timestamps are non-decreasing in sequence number, so this is a
+        // single lookup on the timestamp_ms index instead of probing checkpoint by checkpoint.
+        let seq = read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
+            let query = checkpoints::dsl::checkpoints
+                .select(checkpoints::sequence_number)
+                .into_boxed();
+            match rounding {
+                TimestampRounding::Floor => query
+                    .filter(checkpoints::timestamp_ms.le(timestamp_ms as i64))
+                    .order((
+                        checkpoints::timestamp_ms.desc(),
+                        checkpoints::sequence_number.desc(),
+                    )),
+                TimestampRounding::Ceil => query
+                    .filter(checkpoints::timestamp_ms.ge(timestamp_ms as i64))
+                    .order((
+                        checkpoints::timestamp_ms.asc(),
+                        checkpoints::sequence_number.asc(),
+                    )),
+            }
+            .first::<i64>(conn)
+            .optional()
+        })
+        .context(&format!(
+            "Failed reading checkpoint at timestamp {timestamp_ms} with rounding {rounding:?}"
+        ))?;
+        Ok(seq.map(|seq| seq as CheckpointSequenceNumber))
+    }
+
     fn get_event(&self, id: EventID) -> Result<Event, IndexerError> {
-        read_only_blocking!(&self.blocking_cp, |conn| events::table
+        read_only_blocking!(&self.read_cp, self.query_timeout, |conn| events::table
             .filter(events::dsl::transaction_digest.eq(id.tx_digest.base58_encode()))
             .filter(events::dsl::event_sequence.eq(id.event_seq as i64))
             .first::<Event>(conn))
@@ -384,41 +1240,64 @@ impl PgIndexerStore {
         limit: Option<usize>,
         descending_order: bool,
     ) -> Result<EventPage, IndexerError> {
-        let mut boxed_query = events::table.into_boxed();
-        match query {
-            EventFilter::All(..) => {}
-            EventFilter::Transaction(digest) => {
-                boxed_query =
-                    boxed_query.filter(events::dsl::transaction_digest.eq(digest.base58_encode()));
-            }
-            EventFilter::MoveModule { package, module } => {
-                boxed_query = boxed_query
-                    .filter(events::dsl::package.eq(package.to_string()))
-                    .filter(events::dsl::module.eq(module.to_string()));
-            }
-            EventFilter::MoveEventType(struct_name) => {
-                boxed_query =
-                    boxed_query.filter(events::dsl::event_type.eq(struct_name.to_string()));
-            }
-            EventFilter::Sender(sender) => {
-                boxed_query = boxed_query.filter(events::dsl::sender.eq(sender.to_string()));
-            }
-            EventFilter::TimeRange {
-                start_time,
-                end_time,
-            } => {
-                boxed_query = boxed_query
-                    .filter(events::dsl::event_time_ms.ge(start_time as i64))
-                    .filter(events::dsl::event_time_ms.lt(end_time as i64));
-            }
-            // TODO: Implement EventFilter to SQL
-            _ => {
-                return Err(IndexerError::NotSupportedError(format!(
-                    "Filter type [{query:?}] not supported by the Indexer."
-                )))
-            }
-        }
+        self.query_events(query.try_into()?, cursor, limit, descending_order)
+    }
+
+    fn query_events(
+        &self,
+        filter: query::EventFilter,
+        cursor: Option<EventID>,
+        limit: Option<usize>,
+        descending_order: bool,
+    ) -> Result<EventPage, IndexerError> {
+        let pg_cursor = if let Some(cursor) = cursor {
+            let event = self.get_event(cursor)?;
+            Some((event.tx_sequence_number, event.event_sequence))
+        } else {
+            None
+        };
+        let (events, has_next_page) =
+            self.query_events_after(filter, pg_cursor, limit, descending_order)?;
+        let next_cursor = events.last().map(|(_, e)| e.id.clone());
+        Ok(EventPage {
+            data: events.into_iter().map(|(_, e)| e).collect(),
+            next_cursor,
+            has_next_page,
+        })
+    }
+
+    fn get_events_by_sequence(
+        &self,
+        query: EventFilter,
+        cursor: Option<EventSequence>,
+        limit: Option<usize>,
+        descending_order: bool,
+    ) -> Result<SequencedEventPage, IndexerError> {
+        let pg_cursor = cursor.map(|cursor| {
+            (
+                cursor.tx_sequence_number as i64,
+                cursor.event_sequence as i64,
+            )
+        });
+        let (events, has_next_page) =
+            self.query_events_after(query.try_into()?, pg_cursor, limit, descending_order)?;
+        let next_cursor = events.last().map(|(sequence, _)| *sequence);
+        Ok(SequencedEventPage {
+            data: events.into_iter().map(|(_, e)| e).collect(),
+            next_cursor,
+            has_next_page,
+        })
+    }
 
+    // A page of events matching `filter` after the (tx_sequence_number, event_sequence)
+    // position `pg_cursor`, with their positions, and whether there is a next page.
+    fn query_events_after(
+        &self,
+        filter: query::EventFilter,
+        pg_cursor: Option<(i64, i64)>,
+        limit: Option<usize>,
+        descending_order: bool,
+    ) -> Result<(Vec<(EventSequence, SuiEvent)>, bool), IndexerError> {
         let mut page_limit = limit.unwrap_or(MAX_EVENT_PAGE_SIZE);
         if page_limit > MAX_EVENT_PAGE_SIZE {
             Err(IndexerError::InvalidArgumentError(format!(
@@ -429,76 +1308,243 @@ impl PgIndexerStore {
         // fetch one more item to tell if there is next page
         page_limit += 1;
 
-        let pg_cursor =
-            if let Some(cursor) = cursor {
-                Some(self.get_event(cursor)?.id.ok_or_else(|| {
-                    IndexerError::PostgresReadError("Event ID is None".to_string())
-                })?)
-            } else {
-                None
-            };
-
-        let events_vec: Vec<Event> = read_only_blocking!(&self.blocking_cp, |conn| {
-            if let Some(pg_cursor) = pg_cursor {
-                if descending_order {
-                    boxed_query = boxed_query.filter(events::dsl::id.lt(pg_cursor));
-                } else {
-                    boxed_query = boxed_query.filter(events::dsl::id.gt(pg_cursor));
-                }
-            }
-            if descending_order {
-                boxed_query = boxed_query.order(events::id.desc());
-            } else {
-                boxed_query = boxed_query.order(events::id.asc());
-            }
-            boxed_query.load(conn)
-        })
-        .context("Failed reading events from PostgresDB")?;
+        let events_vec: Vec<Event> =
+            read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
+                events_page(&filter, pg_cursor, page_limit, descending_order).load(conn)
+            })
+            .context(&format!("Failed reading events with filter {filter:?}"))?;
 
-        let mut sui_event_vec = events_vec
-            .into_iter()
-            .map(|event| event.try_into(&self.module_cache))
-            .collect::<Result<Vec<SuiEvent>, _>>()?;
         // reset to original limit for checking and truncating
         page_limit -= 1;
-        let has_next_page = sui_event_vec.len() > page_limit;
-        sui_event_vec.truncate(page_limit);
-        let next_cursor = sui_event_vec.last().map(|e| e.id.clone());
-        Ok(EventPage {
-            data: sui_event_vec,
-            next_cursor,
-            has_next_page,
-        })
+        let has_next_page = events_vec.len() > page_limit;
+        let sui_event_vec = events_vec
+            .into_iter()
+            .take(page_limit)
+            .map(|event| {
+                let sequence = EventSequence {
+                    tx_sequence_number: event.tx_sequence_number as u64,
+                    event_sequence: event.event_sequence as u64,
+                };
+                Ok((sequence, event.try_into(&self.module_cache)?))
+            })
+            .collect::<Result<Vec<_>, IndexerError>>()?;
+        Ok((sui_event_vec, has_next_page))
     }
 
-    fn get_total_transaction_number_from_checkpoints(&self) -> Result<i64, IndexerError> {
-        let checkpoint: Checkpoint = read_only_blocking!(&self.blocking_cp, |conn| {
-            checkpoints::dsl::checkpoints
-                .order(checkpoints::dsl::network_total_transactions.desc())
-                .first::<Checkpoint>(conn)
-        })
-        .context("Failed reading total transaction number")?;
+    fn query_events_in_time_range(
+        &self,
+        start_ms: u64,
+        end_ms: u64,
+        cursor: Option<EventID>,
+        limit: Option<usize>,
+        descending_order: bool,
+    ) -> Result<EventPage, IndexerError> {
+        if start_ms >= end_ms {
+            return Err(IndexerError::InvalidArgumentError(format!(
+                "Invalid time range, start_ms {start_ms} should be smaller than end_ms {end_ms}"
+            )));
+        }
+        let filter = query::EventFilter::TimeRange { start_ms, end_ms };
+        self.query_events(filter, cursor, limit, descending_order)
+    }
+
+    fn get_total_transaction_number_from_checkpoints(&self) -> Result<i64, IndexerError> {
+        let checkpoint: Checkpoint =
+            read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
+                checkpoints::dsl::checkpoints
+                    .order(checkpoints::dsl::network_total_transactions.desc())
+                    .first::<Checkpoint>(conn)
+            })
+            .context("Failed reading total transaction number")?;
         Ok(checkpoint.network_total_transactions)
     }
 
     fn get_transaction_by_digest(&self, tx_digest: &str) -> Result<Transaction, IndexerError> {
-        read_only_blocking!(&self.blocking_cp, |conn| {
-            transactions::dsl::transactions
-                .filter(transactions::dsl::transaction_digest.eq(tx_digest))
-                .first::<Transaction>(conn)
+        read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
+            transaction_by_digest_query(tx_digest).first::<Transaction>(conn)
         })
         .context(&format!(
             "Failed reading transaction with digest {tx_digest}"
         ))
     }
 
+    fn find_transactions_by_digest_prefix(
+        &self,
+        prefix: &str,
+        limit: usize,
+    ) -> Result<Vec<Transaction>, IndexerError> {
+        // base58 characters have no special meaning in LIKE patterns
+        if prefix.is_empty()
+            || prefix.len() > 44
+            || !prefix.chars().all(|c| BASE58_ALPHABET.contains(c))
+        {
+            return Err(IndexerError::InvalidArgumentError(format!(
+                "Digest prefix must be 1 to 44 base58 characters, got {prefix}"
+            )));
+        }
+        if limit == 0 || limit > MAX_DIGEST_PREFIX_MATCHES {
+            return Err(IndexerError::InvalidArgumentError(format!(
+                "Limit must be between 1 and {MAX_DIGEST_PREFIX_MATCHES}, got {limit}"
+            )));
+        }
+        read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
+            diesel::sql_query(TX_DIGEST_PREFIX_QUERY)
+                .bind::<VarChar, _>(format!("{prefix}%"))
+                .bind::<BigInt, _>(limit as i64)
+                .load::<Transaction>(conn)
+        })
+        .context(&format!(
+            "Failed reading transactions with digest prefix {prefix}"
+        ))
+    }
+
+    fn get_transaction_inclusion(
+        &self,
+        tx_digest: TransactionDigest,
+    ) -> Result<Option<TransactionInclusion>, IndexerError> {
+        let inclusion: Option<CheckpointTxDigest> =
+            read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
+                checkpoint_tx_digests::dsl::checkpoint_tx_digests
+                    .filter(checkpoint_tx_digests::transaction_digest.eq(tx_digest.base58_encode()))
+                    .first::<CheckpointTxDigest>(conn)
+                    .optional()
+            })
+            .context(&format!(
+                "Failed reading checkpoint inclusion of transaction {tx_digest}"
+            ))?;
+        inclusion.map(TransactionInclusion::try_from).transpose()
+    }
+
+    fn get_transaction_proof(
+        &self,
+        tx_digest: TransactionDigest,
+    ) -> Result<Option<TransactionProof>, IndexerError> {
+        let Some(inclusion) = self.get_transaction_inclusion(tx_digest)? else {
+            return Ok(None);
+        };
+        let proof: Option<CheckpointProof> =
+            read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
+                checkpoint_proofs::dsl::checkpoint_proofs
+                    .filter(checkpoint_proofs::sequence_number.eq(inclusion.checkpoint as i64))
+                    .first::<CheckpointProof>(conn)
+                    .optional()
+            })
+            .context(&format!(
+                "Failed reading proof of checkpoint {}",
+                inclusion.checkpoint
+            ))?;
+        let Some(proof) = proof else {
+            return Ok(None);
+        };
+        let effects = proof
+            .effects
+            .into_iter()
+            .nth(inclusion.position as usize)
+            .flatten()
+            .ok_or_else(|| {
+                IndexerError::PostgresReadError(format!(
+                    "Proof of checkpoint {} has no effects of transaction {tx_digest}",
+                    inclusion.checkpoint
+                ))
+            })?;
+        Ok(Some(TransactionProof {
+            checkpoint: inclusion.checkpoint,
+            position: inclusion.position,
+            transaction_digest: tx_digest,
+            certified_summary: proof.certified_summary,
+            contents: proof.contents,
+            effects,
+        }))
+    }
+
+    fn get_reexecution_sample(
+        &self,
+        after_id: Option<i64>,
+        sample_interval: u64,
+        limit: usize,
+    ) -> Result<Vec<Transaction>, IndexerError> {
+        let sample_interval = sample_interval.max(1);
+        let mut transactions = read_only_blocking!(&self.blocking_cp, |conn| {
+            let boxed_query = transactions::dsl::transactions
+                .filter(diesel::dsl::sql::<Bool>(&format!(
+                    "id % {sample_interval} = 0"
+                )))
+                .limit(limit as i64)
+                .into_boxed();
+            match after_id {
+                Some(after_id) => boxed_query
+                    .filter(transactions::dsl::id.gt(after_id))
+                    .order(transactions::dsl::id.asc())
+                    .load::<Transaction>(conn),
+                None => boxed_query
+                    .order(transactions::dsl::id.desc())
+                    .load::<Transaction>(conn),
+            }
+        })
+        .context(&format!(
+            "Failed reading one in {sample_interval} transactions after {after_id:?}"
+        ))?;
+        if after_id.is_none() {
+            transactions.reverse();
+        }
+        Ok(transactions)
+    }
+
+    fn persist_reexecution_mismatch(
+        &self,
+        mismatch: ReexecutionMismatch,
+    ) -> Result<(), IndexerError> {
+        fenced_transactional_blocking!(self, |conn| {
+            diesel::insert_into(reexecution_mismatches::table)
+                .values(&mismatch)
+                .on_conflict(reexecution_mismatches::transaction_digest)
+                .do_update()
+                .set((
+                    reexecution_mismatches::local_effects_digest
+                        .eq(excluded(reexecution_mismatches::local_effects_digest)),
+                    reexecution_mismatches::local_error
+                        .eq(excluded(reexecution_mismatches::local_error)),
+                    reexecution_mismatches::reexecuted_at_ms
+                        .eq(excluded(reexecution_mismatches::reexecuted_at_ms)),
+                ))
+                .execute(conn)
+        })
+        .context(&format!(
+            "Failed writing re-execution mismatch of transaction {}",
+            mismatch.transaction_digest
+        ))?;
+        Ok(())
+    }
+
+    fn get_transaction_for_object_version(
+        &self,
+        object_id: ObjectID,
+        version: SequenceNumber,
+    ) -> Result<Option<Transaction>, IndexerError> {
+        let tx_digest: Option<String> =
+            read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
+                changed_objects::dsl::changed_objects
+                    .select(changed_objects::transaction_digest)
+                    .filter(changed_objects::object_id.eq(object_id.to_string()))
+                    .filter(changed_objects::object_version.eq(version.value() as i64))
+                    .first::<String>(conn)
+                    .optional()
+            })
+            .context(&format!(
+                "Failed reading transaction of object {object_id} at version {version}"
+            ))?;
+        tx_digest
+            .map(|digest| self.get_transaction_by_digest(&digest))
+            .transpose()
+    }
+
     fn compose_sui_transaction_block_response(
         &self,
         tx: Transaction,
         options: Option<&SuiTransactionBlockResponseOptions>,
     ) -> Result<SuiTransactionBlockResponse, IndexerError> {
         let sender_signed_data: SenderSignedData =
-            bcs::from_bytes(&tx.raw_transaction).map_err(|err| {
+            bcs::from_bytes(&decompress_payload(&tx.raw_transaction)?).map_err(|err| {
                 IndexerError::InsertableParsingError(format!(
                     "Failed converting transaction BCS to SenderSignedData with error: {:?}",
                     err
@@ -562,7 +1608,7 @@ impl PgIndexerStore {
                 tx_opt = Some(transaction);
             }
             if options.show_raw_input {
-                raw_tx = tx.raw_transaction;
+                raw_tx = decompress_payload(&tx.raw_transaction)?.into_owned();
             }
             if options.show_effects {
                 effects_opt = Some(effects);
@@ -588,7 +1634,7 @@ impl PgIndexerStore {
         &self,
         tx_digests: &[String],
     ) -> Result<Vec<Transaction>, IndexerError> {
-        let transactions = read_only_blocking!(&self.blocking_cp, |conn| {
+        let transactions = read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
             transactions::dsl::transactions
                 .filter(transactions::dsl::transaction_digest.eq_any(tx_digests))
                 .load::<Transaction>(conn)
@@ -612,7 +1658,7 @@ impl PgIndexerStore {
         tx_digest: Option<String>,
         is_descending: bool,
     ) -> Result<Option<i64>, IndexerError> {
-        read_only_blocking!(&self.blocking_cp, |conn| {
+        read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
             if let Some(digest) = &tx_digest {
                 let mut boxed_query = transactions::dsl::transactions
                     .filter(transactions::dsl::transaction_digest.eq(digest))
@@ -634,13 +1680,167 @@ impl PgIndexerStore {
         ))
     }
 
+    fn get_object_before_version(
+        &self,
+        object_id: ObjectID,
+        checkpoint: CheckpointSequenceNumber,
+        before_version: SequenceNumber,
+    ) -> Result<Option<sui_types::object::Object>, IndexerError> {
+        let object = read_only_blocking!(&self.blocking_cp, |conn| {
+            objects_history::dsl::objects_history
+                .select((
+                    objects_history::epoch,
+                    objects_history::checkpoint,
+                    objects_history::object_id,
+                    objects_history::version,
+                    objects_history::object_digest,
+                    objects_history::owner_type,
+                    objects_history::owner_address,
+                    objects_history::initial_shared_version,
+                    objects_history::previous_transaction,
+                    objects_history::object_type,
+                    objects_history::object_status,
+                    objects_history::has_public_transfer,
+                    objects_history::storage_rebate,
+                    objects_history::bcs,
+                    objects_history::object_size,
+                ))
+                .filter(objects_history::object_id.eq(object_id.to_string()))
+                .filter(objects_history::version.lt(before_version.value() as i64))
+                .filter(objects_history::checkpoint.le(checkpoint as i64))
+                .order(objects_history::version.desc())
+                .first::<Object>(conn)
+                .optional()
+        })
+        .context(&format!(
+            "Failed reading object {object_id} before version {before_version} at checkpoint {checkpoint}"
+        ))?;
+        match object {
+            Some(object)
+                if !matches!(
+                    object.object_status,
+                    ObjectStatus::Deleted
+                        | ObjectStatus::Wrapped
+                        | ObjectStatus::UnwrappedThenDeleted
+                ) =>
+            {
+                Ok(Some(self.resolve_blobs(object)?.try_into()?))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn sample_objects(&self, count: usize) -> Result<Vec<Object>, IndexerError> {
+        read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
+            let mut sampled = BTreeMap::new();
+            // object ids are uniformly distributed, seeking to random ids keeps sampling
+            // to index lookups
+            for _ in 0..count {
+                // wrap around to the smallest object id past the largest one
+                for start in [ObjectID::random().to_string(), String::new()] {
+                    let object = objects::dsl::objects
+                        .select((
+                            objects::epoch,
+                            objects::checkpoint,
+                            objects::object_id,
+                            objects::version,
+                            objects::object_digest,
+                            objects::owner_type,
+                            objects::owner_address,
+                            objects::initial_shared_version,
+                            objects::previous_transaction,
+                            objects::object_type,
+                            objects::object_status,
+                            objects::has_public_transfer,
+                            objects::storage_rebate,
+                            objects::bcs,
+                            objects::object_size,
+                        ))
+                        .filter(objects::object_id.ge(start))
+                        .order_by(objects::object_id)
+                        .first::<Object>(conn)
+                        .optional()?;
+                    if let Some(object) = object {
+                        sampled.insert(object.object_id.clone(), object);
+                        break;
+                    }
+                }
+            }
+            Ok::<_, diesel::result::Error>(sampled.into_values().collect())
+        })
+        .context(&format!("Failed sampling {count} objects from PostgresDB"))
+    }
+
+    fn render_display(&self, object_id: ObjectID) -> Result<DisplayFieldsResponse, IndexerError> {
+        let (object, layout) = match self.get_object(object_id, None)? {
+            ObjectRead::Exists(_, object, Some(layout)) => (object, layout),
+            // packages have no type to be displayed
+            ObjectRead::Exists(..) => {
+                return Ok(DisplayFieldsResponse {
+                    data: None,
+                    error: None,
+                })
+            }
+            ObjectRead::Deleted((object_id, version, digest)) => {
+                return Err(SuiObjectResponseError::Deleted {
+                    object_id,
+                    version,
+                    digest,
+                }
+                .into())
+            }
+            ObjectRead::NotExists(object_id) => {
+                return Err(SuiObjectResponseError::NotExists { object_id }.into())
+            }
+        };
+        let move_object = object.data.try_as_move().ok_or_else(|| {
+            IndexerError::DataInconsistency(format!(
+                "Object {object_id} with layout is not a Move object"
+            ))
+        })?;
+        // in the format of the type params of the events the displays are indexed from
+        let object_type = TypeTag::Struct(Box::new(move_object.type_().clone().into())).to_string();
+        let display = read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
+            display::dsl::display
+                .filter(display::object_type.eq(&object_type))
+                .first::<DBDisplay>(conn)
+                .optional()
+        })
+        .context(&format!("Failed reading display of type {object_type}"))?;
+        let display = match display {
+            Some(display) => display,
+            None => {
+                return Ok(DisplayFieldsResponse {
+                    data: None,
+                    error: None,
+                })
+            }
+        };
+
+        let key = (object_id, object.version(), display.version);
+        if let Some(rendered) = self.rendered_displays.lock().unwrap().cache_get(&key) {
+            return Ok(rendered.clone());
+        }
+        let move_struct = move_object.to_move_struct(&layout)?;
+        let rendered = get_rendered_fields(display.fields()?, &move_struct).map_err(|e| {
+            IndexerError::SerdeError(format!(
+                "Failed rendering display of object {object_id} with error: {e}"
+            ))
+        })?;
+        self.rendered_displays
+            .lock()
+            .unwrap()
+            .cache_set(key, rendered.clone());
+        Ok(rendered)
+    }
+
     fn get_object(
         &self,
         object_id: ObjectID,
         version: Option<SequenceNumber>,
     ) -> Result<ObjectRead, IndexerError> {
         // MUSTFIX (jian): add display field error support on implementation
-        let object = read_only_blocking!(&self.blocking_cp, |conn| {
+        let object = read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
             if let Some(version) = version {
                 objects_history::dsl::objects_history
                     .select((
@@ -658,6 +1858,7 @@ impl PgIndexerStore {
                         objects_history::has_public_transfer,
                         objects_history::storage_rebate,
                         objects_history::bcs,
+                        objects_history::object_size,
                     ))
                     .filter(objects_history::object_id.eq(object_id.to_string()))
                     .filter(objects_history::version.eq(version.value() as i64))
@@ -665,6 +1866,23 @@ impl PgIndexerStore {
                     .optional()
             } else {
                 objects::dsl::objects
+                    .select((
+                        objects::epoch,
+                        objects::checkpoint,
+                        objects::object_id,
+                        objects::version,
+                        objects::object_digest,
+                        objects::owner_type,
+                        objects::owner_address,
+                        objects::initial_shared_version,
+                        objects::previous_transaction,
+                        objects::object_type,
+                        objects::object_status,
+                        objects::has_public_transfer,
+                        objects::storage_rebate,
+                        objects::bcs,
+                        objects::object_size,
+                    ))
                     .filter(objects::dsl::object_id.eq(object_id.to_string()))
                     .first::<Object>(conn)
                     .optional()
@@ -674,7 +1892,9 @@ impl PgIndexerStore {
 
         match object {
             None => Ok(ObjectRead::NotExists(object_id)),
-            Some(o) => o.try_into_object_read(&self.module_cache),
+            Some(o) => self
+                .resolve_blobs(o)?
+                .try_into_object_read(&self.module_cache),
         }
     }
 
@@ -685,23 +1905,8 @@ impl PgIndexerStore {
         cursor: Option<ObjectID>,
         limit: usize,
     ) -> Result<Vec<ObjectRead>, IndexerError> {
-        let objects = read_only_blocking!(&self.blocking_cp, |conn| {
-            let columns = vec![
-                "epoch",
-                "checkpoint",
-                "object_id",
-                "version",
-                "object_digest",
-                "owner_type",
-                "owner_address",
-                "initial_shared_version",
-                "previous_transaction",
-                "object_type",
-                "object_status",
-                "has_public_transfer",
-                "storage_rebate",
-                "bcs",
-            ];
+        let objects = read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
+            let columns = OBJECT_COLUMNS.to_vec();
             diesel::sql_query(filter.to_objects_history_sql(cursor, limit, columns))
                 .bind::<BigInt, _>(at_checkpoint as i64)
                 .get_results::<Object>(conn)
@@ -709,7 +1914,10 @@ impl PgIndexerStore {
 
         objects
             .into_iter()
-            .map(|object| object.try_into_object_read(&self.module_cache))
+            .map(|object| {
+                self.resolve_blobs(object)?
+                    .try_into_object_read(&self.module_cache)
+            })
             .collect()
     }
 
@@ -720,40 +1928,141 @@ impl PgIndexerStore {
         cursor: Option<ObjectID>,
         limit: usize,
     ) -> Result<Vec<ObjectRead>, IndexerError> {
-        let columns = vec![
-            "epoch",
-            "checkpoint",
-            "object_id",
-            "version",
-            "object_digest",
-            "owner_type",
-            "owner_address",
-            "initial_shared_version",
-            "previous_transaction",
-            "object_type",
-            "object_status",
-            "has_public_transfer",
-            "storage_rebate",
-            "bcs",
-        ];
-
-        let objects = read_only_blocking!(&self.blocking_cp, |conn| diesel::sql_query(
-            filter.to_latest_objects_sql(cursor, limit, columns)
-        )
-        .get_results::<Object>(conn))?;
+        let columns = OBJECT_COLUMNS.to_vec();
+
+        let objects =
+            read_only_blocking!(&self.read_cp, self.query_timeout, |conn| diesel::sql_query(
+                filter.to_latest_objects_sql(cursor, limit, columns)
+            )
+            .get_results::<Object>(conn))?;
+
+        objects
+            .into_iter()
+            .map(|object| {
+                self.resolve_blobs(object)?
+                    .try_into_object_read(&self.module_cache)
+            })
+            .collect()
+    }
+
+    fn query_objects(
+        &self,
+        filter: query::ObjectFilter,
+        cursor: Option<ObjectID>,
+        limit: usize,
+    ) -> Result<Vec<ObjectRead>, IndexerError> {
+        let objects = read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
+            let mut boxed_query = objects::table
+                .select((
+                    objects::epoch,
+                    objects::checkpoint,
+                    objects::object_id,
+                    objects::version,
+                    objects::object_digest,
+                    objects::owner_type,
+                    objects::owner_address,
+                    objects::initial_shared_version,
+                    objects::previous_transaction,
+                    objects::object_type,
+                    objects::object_status,
+                    objects::has_public_transfer,
+                    objects::storage_rebate,
+                    objects::bcs,
+                    objects::object_size,
+                ))
+                .filter(objects::object_status.ne(ObjectStatus::Deleted))
+                .filter(objects::object_status.ne(ObjectStatus::Wrapped))
+                .filter(objects::object_status.ne(ObjectStatus::UnwrappedThenDeleted))
+                .filter(filter.to_predicate())
+                .into_boxed();
+            if let Some(cursor) = cursor {
+                boxed_query = boxed_query.filter(objects::object_id.gt(cursor.to_string()));
+            }
+            boxed_query
+                .order(objects::object_id.asc())
+                .limit(limit as i64)
+                .load::<Object>(conn)
+        })
+        .context(&format!("Failed reading objects with filter {filter:?}"))?;
+
+        objects
+            .into_iter()
+            .map(|object| {
+                self.resolve_blobs(object)?
+                    .try_into_object_read(&self.module_cache)
+            })
+            .collect()
+    }
+
+    fn query_objects_by_type(
+        &self,
+        type_query: String,
+        cursor: Option<ObjectID>,
+        limit: usize,
+    ) -> Result<Vec<ObjectRead>, IndexerError> {
+        let type_query = ObjectTypeQuery::from_str(&type_query)?;
+        let columns = OBJECT_COLUMNS.to_vec();
+
+        let objects =
+            read_only_blocking!(&self.read_cp, self.query_timeout, |conn| diesel::sql_query(
+                type_query.to_objects_sql(cursor, limit, columns)
+            )
+            .get_results::<Object>(conn))
+            .context(&format!("Failed reading objects by type {type_query:?}"))?;
 
         objects
             .into_iter()
-            .map(|object| object.try_into_object_read(&self.module_cache))
+            .map(|object| {
+                self.resolve_blobs(object)?
+                    .try_into_object_read(&self.module_cache)
+            })
             .collect()
     }
 
+    fn execute_readonly_sql(&self, sql: &str) -> Result<ReadonlySqlResult, IndexerError> {
+        let Some(readonly_sql) = &self.readonly_sql else {
+            return Err(IndexerError::NotSupportedError(
+                "Read only SQL queries are not enabled on this indexer".to_string(),
+            ));
+        };
+        let query = readonly_sql.check(sql)?;
+        // the newlines end comments at the end of the query, one more row than the cap is
+        // read to tell if the result is truncated. Errors are returned without context, as
+        // they are sent to the client as they are.
+        let mut rows = read_only_blocking!(&self.read_cp, readonly_sql.timeout, |conn| {
+            // the role, checked as the store was built, is what keeps the query to the views,
+            // SET LOCAL only lasts until the end of the transaction
+            conn.batch_execute(&format!("SET LOCAL ROLE \"{}\"", readonly_sql.role))?;
+            diesel::sql_query(format!(
+                "SELECT row_to_json(q) AS row FROM (\n{query}\n) AS q LIMIT $1"
+            ))
+            .bind::<BigInt, _>(readonly_sql.max_rows as i64 + 1)
+            .load::<JsonRow>(conn)
+        })?;
+        let truncated = rows.len() > readonly_sql.max_rows;
+        rows.truncate(readonly_sql.max_rows);
+        Ok(ReadonlySqlResult {
+            rows: rows.into_iter().map(|r| r.row).collect(),
+            truncated,
+        })
+    }
+
+    fn get_portfolio(&self, owner: SuiAddress) -> Result<Vec<CoinPortfolioEntry>, IndexerError> {
+        let coins = read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
+            diesel::sql_query(PORTFOLIO_SQL)
+                .bind::<VarChar, _>(owner.to_string())
+                .get_results::<DBCoinWithMetadata>(conn)
+        })
+        .context(&format!("Failed reading coins of owner {owner}"))?;
+        compose_portfolio(coins)
+    }
+
     fn get_move_call_sequence_by_digest(
         &self,
         tx_digest: Option<String>,
         is_descending: bool,
     ) -> Result<Option<i64>, IndexerError> {
-        read_only_blocking!(&self.blocking_cp, |conn| {
+        read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
             if let Some(digest) = &tx_digest {
                 let mut boxed_query = move_calls::dsl::move_calls
                     .filter(move_calls::dsl::transaction_digest.eq(digest))
@@ -780,7 +2089,7 @@ impl PgIndexerStore {
         tx_digest: Option<String>,
         is_descending: bool,
     ) -> Result<Option<i64>, IndexerError> {
-        read_only_blocking!(&self.blocking_cp, |conn| {
+        read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
             if let Some(digest) = &tx_digest {
                 let mut boxed_query = input_objects::dsl::input_objects
                     .filter(input_objects::dsl::transaction_digest.eq(digest))
@@ -807,7 +2116,7 @@ impl PgIndexerStore {
         tx_digest: Option<String>,
         is_descending: bool,
     ) -> Result<Option<i64>, IndexerError> {
-        read_only_blocking!(&self.blocking_cp, |conn| {
+        read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
             if let Some(digest) = &tx_digest {
                 let mut boxed_query = changed_objects::dsl::changed_objects
                     .filter(changed_objects::dsl::transaction_digest.eq(digest))
@@ -834,7 +2143,7 @@ impl PgIndexerStore {
         tx_digest: Option<String>,
         is_descending: bool,
     ) -> Result<Option<i64>, IndexerError> {
-        read_only_blocking!(&self.blocking_cp, |conn| {
+        read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
             if let Some(digest) = &tx_digest {
                 let mut boxed_query = recipients::dsl::recipients
                     .filter(recipients::dsl::transaction_digest.eq(digest))
@@ -865,160 +2174,291 @@ impl PgIndexerStore {
         limit: usize,
         is_descending: bool,
     ) -> Result<Vec<Transaction>, IndexerError> {
-        read_only_blocking!(&self.blocking_cp, |conn| {
-            let mut boxed_query = transactions::dsl::transactions.into_boxed();
-            if let Some(start_sequence) = start_sequence {
-                if is_descending {
-                    boxed_query = boxed_query.filter(transactions::dsl::id.lt(start_sequence));
-                } else {
-                    boxed_query = boxed_query.filter(transactions::dsl::id.gt(start_sequence));
-                }
-            }
-
-            if is_descending {
-                boxed_query
-                    .order(transactions::dsl::id.desc())
-                    .limit((limit) as i64)
-                    .load::<Transaction>(conn)
-            } else {
-                boxed_query
-                    .order(transactions::dsl::id.asc())
-                    .limit((limit) as i64)
-                    .load::<Transaction>(conn)
-            }
+        read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
+            transactions_page(
+                transactions::table.into_boxed(),
+                start_sequence,
+                limit,
+                is_descending,
+            )
+            .load::<Transaction>(conn)
         }).context(&format!("Failed reading all transaction digests with start_sequence {start_sequence:?} and limit {limit}"))
     }
 
-    fn get_transaction_page_by_checkpoint(
+    fn query_transactions(
         &self,
-        checkpoint_sequence_number: i64,
+        filter: query::TransactionFilter,
         start_sequence: Option<i64>,
         limit: usize,
         is_descending: bool,
     ) -> Result<Vec<Transaction>, IndexerError> {
-        read_only_blocking!(&self.blocking_cp, |conn| {
-            let mut boxed_query = transactions::dsl::transactions
-                .filter(transactions::dsl::checkpoint_sequence_number.eq(checkpoint_sequence_number))
-                .into_boxed();
-            if let Some(start_sequence) = start_sequence {
-                if is_descending {
-                    boxed_query = boxed_query.filter(transactions::dsl::id.lt(start_sequence));
-                } else {
-                    boxed_query = boxed_query.filter(transactions::dsl::id.gt(start_sequence));
-                }
-            }
-            if is_descending {
-                boxed_query
-                    .order(transactions::dsl::id.desc())
-                    .limit((limit) as i64)
-                    .load::<Transaction>(conn)
-            } else {
-                boxed_query
-                    .order(transactions::dsl::id.asc())
-                    .limit((limit) as i64)
-                    .load::<Transaction>(conn)
-            }
-        }).context(&format!("Failed reading transaction digests with checkpoint_sequence_number {checkpoint_sequence_number:?} and start_sequence {start_sequence:?} and limit {limit}"))
+        read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
+            transactions_page(
+                transactions_query(&filter),
+                start_sequence,
+                limit,
+                is_descending,
+            )
+            .load::<Transaction>(conn)
+        }).context(&format!("Failed reading transactions with filter {filter:?} and start_sequence {start_sequence:?} and limit {limit}"))
     }
 
-    fn get_transaction_page_by_transaction_kinds(
+    fn get_transaction_page_by_checkpoint(
         &self,
-        kinds: Vec<String>,
+        checkpoint_sequence_number: i64,
         start_sequence: Option<i64>,
         limit: usize,
         is_descending: bool,
     ) -> Result<Vec<Transaction>, IndexerError> {
-        read_only_blocking!(&self.blocking_cp, |conn| {
-            let mut boxed_query = transactions::dsl::transactions
-                .filter(transactions::dsl::transaction_kind.eq_any(kinds.clone()))
-                .into_boxed();
-            if let Some(start_sequence) = start_sequence {
-                if is_descending {
-                    boxed_query = boxed_query.filter(transactions::dsl::id.lt(start_sequence));
-                } else {
-                    boxed_query = boxed_query.filter(transactions::dsl::id.gt(start_sequence));
-                }
-            }
-
-            if is_descending {
-                boxed_query
-                    .order(transactions::dsl::id.desc())
-                    .limit((limit) as i64)
-                    .load::<Transaction>(conn)
-            } else {
-               boxed_query
-                    .order(transactions::dsl::id.asc())
-                    .limit((limit) as i64)
-                    .load::<Transaction>(conn)
-            }
-        }).context(&format!("Failed reading transaction digests with kind {kinds:?} and start_sequence {start_sequence:?} and limit {limit}"))
+        self.query_transactions(
+            query::TransactionFilter::Checkpoint(checkpoint_sequence_number as u64),
+            start_sequence,
+            limit,
+            is_descending,
+        )
     }
 
-    fn get_transaction_page_by_sender_address(
+    fn query_transactions_in_time_range(
         &self,
-        sender_address: String,
+        start_ms: u64,
+        end_ms: u64,
         start_sequence: Option<i64>,
         limit: usize,
         is_descending: bool,
     ) -> Result<Vec<Transaction>, IndexerError> {
-        read_only_blocking!(&self.blocking_cp, |conn| {
-            let mut boxed_query = transactions::dsl::transactions
-                .filter(transactions::dsl::sender.eq(sender_address.clone()))
-                .into_boxed();
-            if let Some(start_sequence) = start_sequence {
-                if is_descending {
-                    boxed_query = boxed_query.filter(transactions::dsl::id.lt(start_sequence));
-                } else {
-                    boxed_query = boxed_query.filter(transactions::dsl::id.gt(start_sequence));
-                }
-            }
-
-            if is_descending {
-                boxed_query
-                    .order(transactions::dsl::id.desc())
-                    .limit(limit as i64)
-                    .load::<Transaction>(conn)
-            } else {
-               boxed_query
-                    .order(transactions::dsl::id.asc())
-                    .limit(limit as i64)
-                    .load::<Transaction>(conn)
-            }
-        }).context(&format!("Failed reading transaction digests by sender address {sender_address} with start_sequence {start_sequence:?} and limit {limit}"))
+        if start_ms >= end_ms {
+            return Err(IndexerError::InvalidArgumentError(format!(
+                "Invalid time range, start_ms {start_ms} should be smaller than end_ms {end_ms}"
+            )));
+        }
+        self.query_transactions(
+            query::TransactionFilter::TimeRange { start_ms, end_ms },
+            start_sequence,
+            limit,
+            is_descending,
+        )
     }
 
-    fn get_transaction_page_by_input_object(
+    fn get_transaction_page_by_transaction_kinds(
         &self,
-        object_id: ObjectID,
-        version: Option<i64>,
+        kinds: Vec<String>,
         start_sequence: Option<i64>,
         limit: usize,
         is_descending: bool,
     ) -> Result<Vec<Transaction>, IndexerError> {
-        let sql_query = format!(
-            "SELECT transaction_digest as digest_name
-             FROM input_objects
-             WHERE object_id = '{}' {} {}
-             ORDER BY id {} LIMIT {}",
-            object_id,
-            if let Some(start_sequence) = start_sequence {
-                if is_descending {
-                    format!("AND id < {}", start_sequence)
-                } else {
-                    format!("AND id > {}", start_sequence)
+        self.query_transactions(
+            transaction_kinds_filter(kinds),
+            start_sequence,
+            limit,
+            is_descending,
+        )
+    }
+
+    fn get_transaction_page_by_sender_address(
+        &self,
+        sender_address: String,
+        start_sequence: Option<i64>,
+        limit: usize,
+        is_descending: bool,
+    ) -> Result<Vec<Transaction>, IndexerError> {
+        let sender = SuiAddress::from_str(&sender_address).map_err(|e| {
+            IndexerError::InvalidArgumentError(format!(
+                "Invalid sender address {sender_address} with error: {e}"
+            ))
+        })?;
+        self.query_transactions(
+            query::TransactionFilter::Sender(sender),
+            start_sequence,
+            limit,
+            is_descending,
+        )
+    }
+
+    fn get_transactions_by_gas_object(
+        &self,
+        object_id: ObjectID,
+        start_sequence: Option<i64>,
+        limit: usize,
+        is_descending: bool,
+    ) -> Result<Vec<Transaction>, IndexerError> {
+        read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
+            transactions_page(
+                transactions_by_gas_object_query(object_id),
+                start_sequence,
+                limit,
+                is_descending,
+            )
+            .load::<Transaction>(conn)
+        })
+        .context(&format!("Failed reading transactions paid with gas object {object_id} with start_sequence {start_sequence:?} and limit {limit}"))
+    }
+
+    fn get_transactions_by_gas_owner(
+        &self,
+        gas_owner: SuiAddress,
+        sponsored_only: bool,
+        start_sequence: Option<i64>,
+        limit: usize,
+        is_descending: bool,
+    ) -> Result<Vec<Transaction>, IndexerError> {
+        self.query_transactions(
+            gas_owner_filter(gas_owner, sponsored_only),
+            start_sequence,
+            limit,
+            is_descending,
+        )
+    }
+
+    fn get_sponsored_transactions(
+        &self,
+        sponsor: SuiAddress,
+        start_ms: u64,
+        end_ms: u64,
+        start_sequence: Option<i64>,
+        limit: usize,
+        is_descending: bool,
+    ) -> Result<Vec<Transaction>, IndexerError> {
+        if start_ms >= end_ms {
+            return Err(IndexerError::InvalidArgumentError(format!(
+                "Invalid time range [{start_ms}, {end_ms}), start must be before end"
+            )));
+        }
+        self.query_transactions(
+            sponsored_transactions_filter(sponsor, start_ms, end_ms),
+            start_sequence,
+            limit,
+            is_descending,
+        )
+    }
+
+    fn get_sponsor_gas_spend(
+        &self,
+        sponsor: SuiAddress,
+        cursor: Option<EpochId>,
+        limit: usize,
+        is_descending: bool,
+    ) -> Result<Vec<SponsorGasSpend>, IndexerError> {
+        read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
+            let mut boxed_query = sponsor_gas_spend::table
+                .filter(sponsor_gas_spend::sponsor.eq(sponsor.to_string()))
+                .into_boxed();
+            if let Some(cursor) = cursor {
+                if is_descending {
+                    boxed_query = boxed_query.filter(sponsor_gas_spend::epoch.lt(cursor as i64));
+                } else {
+                    boxed_query = boxed_query.filter(sponsor_gas_spend::epoch.gt(cursor as i64));
                 }
+            }
+            if is_descending {
+                boxed_query
+                    .order(sponsor_gas_spend::epoch.desc())
+                    .limit(limit as i64)
+                    .load::<SponsorGasSpend>(conn)
             } else {
-                "".to_string()
-            },
-            if let Some(version) = version {
-                format!("AND version = {}", version)
-            } else {
-                "".to_string()
-            },
-            if is_descending { "DESC" } else { "ASC" },
-            limit
+                boxed_query
+                    .order(sponsor_gas_spend::epoch.asc())
+                    .limit(limit as i64)
+                    .load::<SponsorGasSpend>(conn)
+            }
+        })
+        .context(&format!(
+            "Failed reading gas spend of sponsor {sponsor} with cursor {cursor:?} and limit {limit}"
+        ))
+    }
+
+    fn get_failed_transactions_by_abort(
+        &self,
+        package: ObjectID,
+        module: String,
+        code: Option<u64>,
+        start_sequence: Option<i64>,
+        limit: usize,
+        is_descending: bool,
+    ) -> Result<Vec<AbortedTransaction>, IndexerError> {
+        let transactions = read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
+            transactions_page(
+                transactions_by_abort_query(package, &module, code),
+                start_sequence,
+                limit,
+                is_descending,
+            )
+            .load::<Transaction>(conn)
+        })
+        .context(&format!("Failed reading transactions aborted in {package}::{module} with code {code:?} with start_sequence {start_sequence:?} and limit {limit}"))?;
+        let constants = self.get_abort_constants(package, &module)?;
+        Ok(transactions
+            .into_iter()
+            .map(|transaction| AbortedTransaction {
+                abort_constant: transaction
+                    .abort_code
+                    .and_then(|code| constants.get(&(code as u64)).cloned()),
+                transaction,
+            })
+            .collect())
+    }
+
+    // Names of the error constants of `module` of `package` by value, of the u64 constants
+    // both declared by the verified source of the package and in the constant pool of the
+    // deployed module, so that sources not matching the bytecode name no codes.
+    fn get_abort_constants(
+        &self,
+        package: ObjectID,
+        module: &str,
+    ) -> Result<Arc<BTreeMap<u64, String>>, IndexerError> {
+        let key = (package, module.to_string());
+        if let Some(constants) = self.abort_constants.lock().unwrap().cache_get(&key) {
+            return Ok(constants.clone());
+        }
+        let mut constants = match self.get_verified_source(package)? {
+            Some(source) => source.error_constants(module),
+            None => BTreeMap::new(),
+        };
+        if !constants.is_empty() {
+            let deployed = Identifier::new(module)
+                .ok()
+                .map(|name| ModuleId::new(package.into(), name))
+                .and_then(|id| self.get_modules(vec![id]).into_values().next());
+            let deployed_values = deployed
+                .map(|module| {
+                    module
+                        .constant_pool()
+                        .iter()
+                        .filter(|constant| constant.type_ == SignatureToken::U64)
+                        .filter_map(|constant| bcs::from_bytes::<u64>(&constant.data).ok())
+                        .collect::<HashSet<_>>()
+                })
+                .unwrap_or_default();
+            constants.retain(|value, _| deployed_values.contains(value));
+        }
+        let constants = Arc::new(constants);
+        // modules without a verified source yet are looked up again, to name their codes as
+        // soon as the source is verified
+        if !constants.is_empty() {
+            self.abort_constants
+                .lock()
+                .unwrap()
+                .cache_set(key, constants.clone());
+        }
+        Ok(constants)
+    }
+
+    fn get_transaction_page_by_input_object(
+        &self,
+        object_id: ObjectID,
+        version: Option<i64>,
+        start_sequence: Option<i64>,
+        limit: usize,
+        is_descending: bool,
+    ) -> Result<Vec<Transaction>, IndexerError> {
+        let sql_query = object_transactions_page_sql(
+            "input_objects",
+            object_id,
+            version,
+            start_sequence,
+            limit,
+            is_descending,
         );
-        let tx_digests: Vec<String> = read_only_blocking!(&self.blocking_cp, |conn| diesel::sql_query(sql_query).load(conn))
+        let tx_digests: Vec<String> = read_only_blocking!(&self.read_cp, self.query_timeout, |conn| diesel::sql_query(sql_query).load(conn))
                 .context(&format!("Failed reading transaction digests by input object ID {object_id} and version {version:?} with start_sequence {start_sequence:?} and limit {limit}"))?
                 .into_iter()
                 .map(|table: TempDigestTable| table.digest_name)
@@ -1034,30 +2474,15 @@ impl PgIndexerStore {
         limit: usize,
         is_descending: bool,
     ) -> Result<Vec<Transaction>, IndexerError> {
-        let sql_query = format!(
-            "SELECT transaction_digest as digest_name
-             FROM changed_objects
-             WHERE object_id = '{}' {} {}
-             ORDER BY id {} LIMIT {}",
+        let sql_query = object_transactions_page_sql(
+            "changed_objects",
             object_id,
-            if let Some(start_sequence) = start_sequence {
-                if is_descending {
-                    format!("AND id < {}", start_sequence)
-                } else {
-                    format!("AND id > {}", start_sequence)
-                }
-            } else {
-                "".to_string()
-            },
-            if let Some(version) = version {
-                format!("AND version = {}", version)
-            } else {
-                "".to_string()
-            },
-            if is_descending { "DESC" } else { "ASC" },
-            limit
+            version,
+            start_sequence,
+            limit,
+            is_descending,
         );
-        let tx_digests: Vec<String> = read_only_blocking!(&self.blocking_cp, |conn| diesel::sql_query(sql_query).load(conn))
+        let tx_digests: Vec<String> = read_only_blocking!(&self.read_cp, self.query_timeout, |conn| diesel::sql_query(sql_query).load(conn))
                 .context(&format!("Failed reading transaction digests by changed object ID {object_id} and version {version:?} with start_sequence {start_sequence:?} and limit {limit}"))?
                 .into_iter()
                 .map(|table: TempDigestTable| table.digest_name)
@@ -1074,38 +2499,15 @@ impl PgIndexerStore {
         limit: usize,
         is_descending: bool,
     ) -> Result<Vec<Transaction>, IndexerError> {
-        // note: module_name and function_name are user-controlled, which is scary.
-        // however, but valid Move identifiers can only contain 0-9, a-z, A-Z, and _,
-        // so it is safe to use them as-is in the query below
-        let sql_query = format!(
-            "SELECT transaction_digest as digest_name
-             FROM move_calls
-             WHERE move_package = '{}' {} {} {}
-             ORDER BY id {} LIMIT {}",
+        let sql_query = move_call_transactions_page_sql(
             package_name,
-            if let Some(start_sequence) = start_sequence {
-                if is_descending {
-                    format!("AND id < {}", start_sequence)
-                } else {
-                    format!("AND id > {}", start_sequence)
-                }
-            } else {
-                "".to_string()
-            },
-            if let Some(module_name) = module_name.clone() {
-                format!("AND move_module = '{}'", module_name)
-            } else {
-                "".to_string()
-            },
-            if let Some(function_name) = function_name.clone() {
-                format!("AND move_function = '{}'", function_name)
-            } else {
-                "".to_string()
-            },
-            if is_descending { "DESC" } else { "ASC" },
-            limit
+            module_name.as_ref(),
+            function_name.as_ref(),
+            start_sequence,
+            limit,
+            is_descending,
         );
-        let tx_digests: Vec<String> = read_only_blocking!(&self.blocking_cp, |conn| diesel::sql_query(sql_query).load(conn))
+        let tx_digests: Vec<String> = read_only_blocking!(&self.read_cp, self.query_timeout, |conn| diesel::sql_query(sql_query).load(conn))
                 .context(&format!(
                         "Failed reading transaction digests with package_name {} module_name {:?} and function_name {:?} and start_sequence {:?} and limit {}",
                         package_name, module_name, function_name, start_sequence, limit))?
@@ -1123,29 +2525,9 @@ impl PgIndexerStore {
         limit: usize,
         is_descending: bool,
     ) -> Result<Vec<Transaction>, IndexerError> {
-        let sql_query = format!(
-            "SELECT transaction_digest as digest_name FROM recipients
-             WHERE recipient = '{}' {} {}
-             ORDER BY id {} LIMIT {}",
-            to,
-            if let Some(start_sequence) = start_sequence {
-                if is_descending {
-                    format!("AND id < {}", start_sequence)
-                } else {
-                    format!("AND id > {}", start_sequence)
-                }
-            } else {
-                "".to_string()
-            },
-            if let Some(from) = from {
-                format!("AND sender = '{}'", from)
-            } else {
-                "".to_string()
-            },
-            if is_descending { "DESC" } else { "ASC" },
-            limit
-        );
-        let tx_digests: Vec<String> = read_only_blocking!(&self.blocking_cp, |conn| diesel::sql_query(sql_query).load(conn))
+        let sql_query =
+            recipient_transactions_page_sql(from, to, start_sequence, limit, is_descending);
+        let tx_digests: Vec<String> = read_only_blocking!(&self.read_cp, self.query_timeout, |conn| diesel::sql_query(sql_query).load(conn))
                 .context(&format!("Failed reading transaction digests by recipient address {to} with start_sequence {start_sequence:?} and limit {limit}"))?
                 .into_iter()
                 .map(|table: TempDigestTable| table.digest_name)
@@ -1160,29 +2542,9 @@ impl PgIndexerStore {
         limit: usize,
         is_descending: bool,
     ) -> Result<Vec<Transaction>, IndexerError> {
-        let sql_query = format!(
-            "SELECT transaction_digest as digest_name FROM (
-                SELECT transaction_digest, max(id) AS max_id
-                FROM recipients
-                WHERE recipient = '{}' OR sender = '{}'
-                {} GROUP BY transaction_digest
-                ORDER BY max_id {} LIMIT {}
-            ) AS t",
-            address,
-            address,
-            if let Some(start_sequence) = start_sequence {
-                if is_descending {
-                    format!("AND id < {}", start_sequence)
-                } else {
-                    format!("AND id > {}", start_sequence)
-                }
-            } else {
-                "".to_string()
-            },
-            if is_descending { "DESC" } else { "ASC" },
-            limit
-        );
-        let tx_digests: Vec<String> = read_only_blocking!(&self.blocking_cp, |conn| diesel::sql_query(sql_query).load(conn))
+        let sql_query =
+            address_transactions_page_sql(address, start_sequence, limit, is_descending);
+        let tx_digests: Vec<String> = read_only_blocking!(&self.read_cp, self.query_timeout, |conn| diesel::sql_query(sql_query).load(conn))
                 .context(&format!("Failed reading transaction digests by address {address} with start_sequence {start_sequence:?} and limit {limit}"))?
                 .into_iter()
                 .map(|table: TempDigestTable| table.digest_name)
@@ -1191,11 +2553,21 @@ impl PgIndexerStore {
     }
 
     fn get_network_metrics(&self) -> Result<NetworkMetrics, IndexerError> {
-        get_network_metrics_cached(&self.blocking_cp)
+        get_network_metrics_cached(&self.read_cp)
+    }
+
+    fn get_network_totals(&self) -> Result<NetworkTotals, IndexerError> {
+        let totals = read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
+            network_totals::table
+                .select((network_totals::name, network_totals::total))
+                .load::<(String, i64)>(conn)
+        })
+        .context("Failed reading network totals from PostgresDB")?;
+        Ok(NetworkTotals::from_rows(totals))
     }
 
     fn get_move_call_metrics(&self) -> Result<MoveCallMetrics, IndexerError> {
-        let metrics = read_only_blocking!(&self.blocking_cp, |conn| {
+        let metrics = read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
             diesel::sql_query("SELECT * FROM epoch_move_call_metrics;")
                 .get_results::<DBMoveCallMetrics>(conn)
         })?;
@@ -1232,12 +2604,21 @@ impl PgIndexerStore {
         &self,
         checkpoints: &[Checkpoint],
         transactions: &[Transaction],
+        transactions_json: &[TransactionJson],
+        checkpoint_tx_digests: &[CheckpointTxDigest],
+        hot_entities: &[CheckpointHotEntities],
+        address_activity: &[CheckpointAddressActivity],
+        signers: &[CheckpointSigners],
+        proofs: &[CheckpointProof],
         counter_committed_tx: IntCounter,
     ) -> Result<(), IndexerError> {
-        transactional_blocking!(&self.blocking_cp, |conn| {
+        fenced_transactional_blocking!(self, |conn| {
             // Commit indexed transactions
-            for transaction_chunk in transactions.chunks(PG_COMMIT_CHUNK_SIZE) {
-                diesel::insert_into(transactions::table)
+            let mut inserted_transactions = 0;
+            for transaction_chunk in
+                transactions.chunks(self.commit_chunk_sizes.get("transactions"))
+            {
+                inserted_transactions += diesel::insert_into(transactions::table)
                     .values(transaction_chunk)
                     .on_conflict_do_nothing()
                     .execute(conn)
@@ -1245,18 +2626,78 @@ impl PgIndexerStore {
                     .context("Failed writing transactions to PostgresDB")?;
                 counter_committed_tx.inc();
             }
+            add_to_network_total(conn, TOTAL_TRANSACTION_BLOCKS, inserted_transactions as i64)?;
+
+            for transaction_json_chunk in
+                transactions_json.chunks(self.commit_chunk_sizes.get("transactions_json"))
+            {
+                diesel::insert_into(transactions_json::table)
+                    .values(transaction_json_chunk)
+                    .on_conflict_do_nothing()
+                    .execute(conn)
+                    .map_err(IndexerError::from)
+                    .context("Failed writing transactions JSON to PostgresDB")?;
+            }
+
+            for digests_chunk in
+                checkpoint_tx_digests.chunks(self.commit_chunk_sizes.get("checkpoint_tx_digests"))
+            {
+                diesel::insert_into(checkpoint_tx_digests::table)
+                    .values(digests_chunk)
+                    .on_conflict_do_nothing()
+                    .execute(conn)
+                    .map_err(IndexerError::from)
+                    .context("Failed writing checkpoint_tx_digests to PostgresDB")?;
+            }
+
+            for proofs_chunk in proofs.chunks(self.commit_chunk_sizes.get("checkpoint_proofs")) {
+                diesel::insert_into(checkpoint_proofs::table)
+                    .values(proofs_chunk)
+                    .on_conflict_do_nothing()
+                    .execute(conn)
+                    .map_err(IndexerError::from)
+                    .context("Failed writing checkpoint_proofs to PostgresDB")?;
+            }
 
             // Commit indexed checkpoint last, so that if the checkpoint is committed,
             // all related data have been committed as well.
-            for checkpoint_chunk in checkpoints.chunks(PG_COMMIT_CHUNK_SIZE) {
-                diesel::insert_into(checkpoints::table)
+            let mut inserted_checkpoints = HashSet::new();
+            for checkpoint_chunk in checkpoints.chunks(self.commit_chunk_sizes.get("checkpoints")) {
+                let inserted = diesel::insert_into(checkpoints::table)
                     .values(checkpoint_chunk)
                     .on_conflict_do_nothing()
-                    .execute(conn)
+                    .returning(checkpoints::sequence_number)
+                    .get_results::<i64>(conn)
                     .map_err(IndexerError::from)
                     .context("Failed writing checkpoint to PostgresDB")?;
+                inserted_checkpoints.extend(inserted);
                 counter_committed_tx.inc();
             }
+            persist_hot_entities(
+                conn,
+                &self.commit_chunk_sizes,
+                hot_entities,
+                &inserted_checkpoints,
+            )?;
+            persist_sponsor_gas_spend(
+                conn,
+                &self.commit_chunk_sizes,
+                checkpoints,
+                transactions,
+                &inserted_checkpoints,
+            )?;
+            persist_address_activity(
+                conn,
+                &self.commit_chunk_sizes,
+                address_activity,
+                &inserted_checkpoints,
+            )?;
+            persist_validator_participation(
+                conn,
+                &self.commit_chunk_sizes,
+                signers,
+                &inserted_checkpoints,
+            )?;
             Ok::<(), IndexerError>(())
         })
     }
@@ -1277,15 +2718,98 @@ impl PgIndexerStore {
             .map(|changed_object| changed_object.to_owned())
             .collect();
 
-        transactional_blocking!(&self.blocking_cp, |conn| {
-            persist_object_mutations(
+        // objects with the content they were last written with only need their version and
+        // the columns derived from it updated, the digests being cached by this process only
+        let content_digests = mutated_objects
+            .iter()
+            .map(|object| (object.object_id.clone(), object.content_digest()))
+            .collect::<Vec<_>>();
+        let unchanged_object_ids = {
+            let mut cache = self.object_content_digests.lock().unwrap();
+            content_digests
+                .iter()
+                .filter(|(object_id, digest)| {
+                    digest.is_some() && cache.cache_get(object_id) == digest.as_ref()
+                })
+                .map(|(object_id, _)| object_id.clone())
+                .collect::<HashSet<_>>()
+        };
+        let (unchanged_objects, changed_objects): (Vec<_>, Vec<_>) = mutated_objects
+            .into_iter()
+            .partition(|object| unchanged_object_ids.contains(&object.object_id));
+        // payloads are uploaded to the blob store before the transaction, not within it, and
+        // must not be deleted as blobs of pruned rows until the rows pointing to them commit
+        let blob_references = self.blob_references.read().unwrap();
+        let changed_objects = self.encode_object_payloads(changed_objects);
+
+        let mutated_object_ids = content_digests
+            .iter()
+            .map(|(object_id, _)| object_id.clone())
+            .collect::<Vec<_>>();
+        let updated_object_ids = fenced_transactional_blocking!(self, |conn| {
+            with_object_type_counts(
                 conn,
-                mutated_objects,
-                object_mutation_latency,
-                object_commit_chunk_counter.clone(),
-            )?;
-            Ok::<(), IndexerError>(())
+                &self.commit_chunk_sizes,
+                &mutated_object_ids,
+                |conn| {
+                    let updated_object_ids = update_unchanged_objects(
+                        conn,
+                        &self.commit_chunk_sizes,
+                        &unchanged_objects,
+                    )?;
+                    persist_object_mutations(
+                        conn,
+                        &self.commit_chunk_sizes,
+                        changed_objects,
+                        object_mutation_latency.clone(),
+                        object_commit_chunk_counter.clone(),
+                    )?;
+                    Ok(updated_object_ids)
+                },
+            )
         })?;
+        self.metrics
+            .total_unchanged_object_mutations
+            .inc_by(updated_object_ids.len() as u64);
+        // rows which were not there to update, e.g. written by another process or pruned, are
+        // written in full like the changed objects, a commit failing in between is retried
+        let missing_objects = unchanged_objects
+            .into_iter()
+            .filter(|object| !updated_object_ids.contains(&object.object_id))
+            .collect::<Vec<_>>();
+        if !missing_objects.is_empty() {
+            let missing_object_ids = missing_objects
+                .iter()
+                .map(|object| object.object_id.clone())
+                .collect::<Vec<_>>();
+            let missing_objects = self.encode_object_payloads(missing_objects);
+            fenced_transactional_blocking!(self, |conn| {
+                with_object_type_counts(
+                    conn,
+                    &self.commit_chunk_sizes,
+                    &missing_object_ids,
+                    |conn| {
+                        persist_object_mutations(
+                            conn,
+                            &self.commit_chunk_sizes,
+                            missing_objects,
+                            object_mutation_latency,
+                            object_commit_chunk_counter.clone(),
+                        )
+                    },
+                )
+            })?;
+        }
+        drop(blob_references);
+        {
+            let mut cache = self.object_content_digests.lock().unwrap();
+            for (object_id, digest) in content_digests {
+                match digest {
+                    Some(digest) => cache.cache_set(object_id, digest),
+                    None => cache.cache_remove(&object_id),
+                };
+            }
+        }
 
         let deleted_objects: Vec<Object> = tx_object_changes
             .iter()
@@ -1295,51 +2819,125 @@ impl PgIndexerStore {
             .into_values()
             .map(|deleted_object| deleted_object.to_owned().into())
             .collect();
+        let deleted_object_ids = deleted_objects
+            .iter()
+            .map(|object| object.object_id.clone())
+            .collect::<Vec<_>>();
 
         // commit object deletions after mutations b/c objects cannot be mutated after deletion,
         // otherwise object mutations might override object deletions.
-        transactional_blocking!(&self.blocking_cp, |conn| {
-            persist_object_deletions(
+        fenced_transactional_blocking!(self, |conn| {
+            with_object_type_counts(
                 conn,
-                deleted_objects,
-                object_deletion_latency,
-                object_commit_chunk_counter,
+                &self.commit_chunk_sizes,
+                &deleted_object_ids,
+                |conn| {
+                    persist_object_deletions(
+                        conn,
+                        &self.commit_chunk_sizes,
+                        deleted_objects,
+                        object_deletion_latency,
+                        object_commit_chunk_counter,
+                    )
+                },
             )?;
+            update_total_live_objects(conn, tx_object_changes)?;
+            persist_name_record_changes(conn, &self.commit_chunk_sizes, tx_object_changes)?;
             Ok::<(), IndexerError>(())
         })?;
+        let mut cache = self.object_content_digests.lock().unwrap();
+        for object_id in deleted_object_ids {
+            cache.cache_remove(&object_id);
+        }
         Ok(())
     }
 
+    /// Compresses and offloads the BCS of `objects` as configured.
+    fn encode_object_payloads(&self, objects: Vec<Object>) -> Vec<Object> {
+        let objects = match self.payload_compression_level {
+            Some(level) => objects
+                .into_iter()
+                .map(|object| object.with_compressed_payload(level))
+                .collect(),
+            None => objects,
+        };
+        match &self.blob_store {
+            Some(blob_store) => {
+                let (objects, failed) = blob_store.offload(objects);
+                self.metrics.total_blob_upload_failure.inc_by(failed as u64);
+                objects
+            }
+            None => objects,
+        }
+    }
+
     fn persist_events(&self, events: &[Event]) -> Result<(), IndexerError> {
-        transactional_blocking!(&self.blocking_cp, |conn| {
-            for event_chunk in events.chunks(PG_COMMIT_CHUNK_SIZE) {
-                diesel::insert_into(events::table)
+        let displays = DBDisplay::from_events(events)?;
+        fenced_transactional_blocking!(self, |conn| {
+            let mut inserted_events = 0;
+            for event_chunk in events.chunks(self.commit_chunk_sizes.get("events")) {
+                inserted_events += diesel::insert_into(events::table)
                     .values(event_chunk)
                     .on_conflict_do_nothing()
                     .execute(conn)
                     .map_err(IndexerError::from)
                     .context("Failed writing events to PostgresDB")?;
             }
+            add_to_network_total(conn, TOTAL_EVENTS, inserted_events as i64)?;
+            if !displays.is_empty() {
+                diesel::insert_into(display::table)
+                    .values(&displays)
+                    .on_conflict(display::object_type)
+                    .do_update()
+                    .set((
+                        display::display_id.eq(latest_display(display::display_id)),
+                        display::version.eq(latest_display(display::version)),
+                        display::bcs.eq(latest_display(display::bcs)),
+                    ))
+                    .execute(conn)
+                    .map_err(IndexerError::from)
+                    .context("Failed writing displays to PostgresDB")?;
+            }
             Ok::<(), IndexerError>(())
         })?;
         Ok(())
     }
 
+    fn persist_commit_watermark(&self, sequence_number: i64) -> Result<(), IndexerError> {
+        fenced_transactional_blocking!(self, |conn| {
+            diesel::sql_query(COMMIT_WATERMARK_UPSERT_QUERY)
+                .bind::<VarChar, _>(CHECKPOINTS_COMMIT_WATERMARK)
+                .bind::<BigInt, _>(sequence_number)
+                .execute(conn)
+        })
+        .context(&format!(
+            "Failed writing commit watermark {sequence_number} to PostgresDB"
+        ))?;
+        Ok(())
+    }
+
     fn persist_addresses(
         &self,
         addresses: &[Address],
         active_addresses: &[ActiveAddress],
     ) -> Result<(), IndexerError> {
-        transactional_blocking!(&self.blocking_cp, |conn| {
-            for address_chunk in addresses.chunks(PG_COMMIT_CHUNK_SIZE) {
+        fenced_transactional_blocking!(self, |conn| {
+            for address_chunk in addresses.chunks(self.commit_chunk_sizes.get("addresses")) {
                 diesel::insert_into(addresses::table)
                     .values(address_chunk)
                     .on_conflict(addresses::account_address)
                     .do_update()
                     .set((
-                        addresses::last_appearance_time
-                            .eq(excluded(addresses::last_appearance_time)),
-                        addresses::last_appearance_tx.eq(excluded(addresses::last_appearance_tx)),
+                        addresses::last_appearance_time.eq(latest(
+                            addresses::last_appearance_time,
+                            "addresses",
+                            "last_appearance_time",
+                        )),
+                        addresses::last_appearance_tx.eq(latest(
+                            addresses::last_appearance_tx,
+                            "addresses",
+                            "last_appearance_time",
+                        )),
                     ))
                     .execute(conn)
                     .map_err(IndexerError::from)
@@ -1351,16 +2949,24 @@ impl PgIndexerStore {
                         .as_str(),
                     )?;
             }
-            for active_address_chunk in active_addresses.chunks(PG_COMMIT_CHUNK_SIZE) {
+            for active_address_chunk in
+                active_addresses.chunks(self.commit_chunk_sizes.get("active_addresses"))
+            {
                 diesel::insert_into(active_addresses::table)
                     .values(active_address_chunk)
                     .on_conflict(active_addresses::account_address)
                     .do_update()
                     .set((
-                        active_addresses::last_appearance_time
-                            .eq(excluded(active_addresses::last_appearance_time)),
-                        active_addresses::last_appearance_tx
-                            .eq(excluded(active_addresses::last_appearance_tx)),
+                        active_addresses::last_appearance_time.eq(latest(
+                            active_addresses::last_appearance_time,
+                            "active_addresses",
+                            "last_appearance_time",
+                        )),
+                        active_addresses::last_appearance_tx.eq(latest(
+                            active_addresses::last_appearance_tx,
+                            "active_addresses",
+                            "last_appearance_time",
+                        )),
                     ))
                     .execute(conn)
                     .map_err(IndexerError::from)
@@ -1377,77 +2983,929 @@ impl PgIndexerStore {
         Ok(())
     }
     fn persist_packages(&self, packages: &[Package]) -> Result<(), IndexerError> {
-        transactional_blocking!(&self.blocking_cp, |conn| {
-            for packages_chunk in packages.chunks(PG_COMMIT_CHUNK_SIZE) {
-                diesel::insert_into(packages::table)
+        let mut event_schemas = vec![];
+        for package in packages {
+            event_schemas.extend(EventSchema::from_package(package)?);
+        }
+        fenced_transactional_blocking!(self, |conn| {
+            let mut inserted_packages = 0;
+            for packages_chunk in packages.chunks(self.commit_chunk_sizes.get("packages")) {
+                inserted_packages += diesel::insert_into(packages::table)
                     .values(packages_chunk)
                     .on_conflict_do_nothing()
                     .execute(conn)
                     .map_err(IndexerError::from)
                     .context("Failed writing packages to PostgresDB")?;
             }
-            Ok::<(), IndexerError>(())
-        })?;
-        Ok(())
-    }
-
-    fn persist_transaction_index_tables(
-        &self,
-        input_objects: &[InputObject],
-        changed_objects: &[ChangedObject],
-        move_calls: &[MoveCall],
-        recipients: &[Recipient],
-    ) -> Result<(), IndexerError> {
-        transactional_blocking!(&self.blocking_cp, |conn| {
-            // Commit indexed move calls
-            for move_calls_chunk in move_calls.chunks(PG_COMMIT_CHUNK_SIZE) {
-                diesel::insert_into(move_calls::table)
-                    .values(move_calls_chunk)
-                    .on_conflict_do_nothing()
-                    .execute(conn)
-                    .map_err(IndexerError::from)
-                    .context("Failed writing move_calls to PostgresDB")?;
-            }
-
-            // Commit indexed input objects
-            for input_objects_chunk in input_objects.chunks(PG_COMMIT_CHUNK_SIZE) {
-                diesel::insert_into(input_objects::table)
-                    .values(input_objects_chunk)
-                    .on_conflict_do_nothing()
-                    .execute(conn)
-                    .map_err(IndexerError::from)
-                    .context("Failed writing input_objects to PostgresDB")?;
-            }
-
-            // Commit indexed changed objects
-            for changed_objects_chunk in changed_objects.chunks(PG_COMMIT_CHUNK_SIZE) {
-                diesel::insert_into(changed_objects::table)
-                    .values(changed_objects_chunk)
-                    .on_conflict_do_nothing()
-                    .execute(conn)
-                    .map_err(IndexerError::from)
-                    .context("Failed writing changed_objects to PostgresDB")?;
-            }
-
-            // Commit indexed recipients
-            for recipients_chunk in recipients.chunks(PG_COMMIT_CHUNK_SIZE) {
-                diesel::insert_into(recipients::table)
-                    .values(recipients_chunk)
+            add_to_network_total(conn, TOTAL_PACKAGES, inserted_packages as i64)?;
+            for schemas_chunk in event_schemas.chunks(self.commit_chunk_sizes.get("event_schemas"))
+            {
+                diesel::insert_into(event_schemas::table)
+                    .values(schemas_chunk)
                     .on_conflict_do_nothing()
                     .execute(conn)
                     .map_err(IndexerError::from)
-                    .context("Failed writing recipients to PostgresDB")?;
+                    .context("Failed writing event schemas to PostgresDB")?;
             }
             Ok::<(), IndexerError>(())
         })?;
         Ok(())
     }
 
-    fn get_network_total_transactions_previous_epoch(
-        &self,
-        epoch: i64,
-    ) -> Result<i64, IndexerError> {
-        read_only_blocking!(&self.blocking_cp, |conn| {
+    fn persist_verified_source(&self, source: VerifiedSource) -> Result<(), IndexerError> {
+        let source = DBVerifiedSource::try_from(source)?;
+        fenced_transactional_blocking!(self, |conn| {
+            diesel::insert_into(verified_sources::table)
+                .values(&source)
+                .on_conflict(verified_sources::package_id)
+                .do_update()
+                .set((
+                    verified_sources::sources.eq(excluded(verified_sources::sources)),
+                    verified_sources::verifier.eq(excluded(verified_sources::verifier)),
+                    verified_sources::verified_at_ms.eq(excluded(verified_sources::verified_at_ms)),
+                    verified_sources::metadata.eq(excluded(verified_sources::metadata)),
+                ))
+                .execute(conn)
+        })
+        .context(&format!(
+            "Failed writing verified source of package {}",
+            source.package_id
+        ))?;
+        self.abort_constants.lock().unwrap().cache_clear();
+        Ok(())
+    }
+
+    fn get_verified_source(
+        &self,
+        package: ObjectID,
+    ) -> Result<Option<VerifiedSource>, IndexerError> {
+        let source: Option<DBVerifiedSource> =
+            read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
+                verified_sources::dsl::verified_sources
+                    .filter(verified_sources::package_id.eq(package.to_string()))
+                    .first::<DBVerifiedSource>(conn)
+                    .optional()
+            })
+            .context(&format!(
+                "Failed reading verified source of package {package}"
+            ))?;
+        source.map(VerifiedSource::try_from).transpose()
+    }
+
+    fn persist_watchlist_entry(&self, entry: WatchlistEntry) -> Result<(), IndexerError> {
+        let entry = DBWatchlistEntry::from(entry);
+        let what = format!(
+            "watchlist entry of {} {}",
+            entry.entity_kind, entry.entity_id
+        );
+        transactional_blocking!(&self.blocking_cp, |conn| {
+            diesel::insert_into(watchlists::table)
+                .values(&entry)
+                .on_conflict_do_nothing()
+                .execute(conn)
+        })
+        .context(&format!("Failed writing {what} to PostgresDB"))?;
+        Ok(())
+    }
+
+    fn delete_watchlist_entry(&self, entry: WatchlistEntry) -> Result<(), IndexerError> {
+        let entry = DBWatchlistEntry::from(entry);
+        let what = format!(
+            "deletion of watchlist entry of {} {}",
+            entry.entity_kind, entry.entity_id
+        );
+        transactional_blocking!(&self.blocking_cp, |conn| {
+            diesel::delete(
+                watchlists::table
+                    .filter(watchlists::entity_kind.eq(&entry.entity_kind))
+                    .filter(watchlists::entity_id.eq(&entry.entity_id))
+                    .filter(watchlists::rule.eq(&entry.rule)),
+            )
+            .execute(conn)
+        })
+        .context(&format!("Failed writing {what} to PostgresDB"))?;
+        Ok(())
+    }
+
+    fn get_epoch_first_checkpoint(&self, epoch: i64) -> Result<Option<i64>, IndexerError> {
+        read_only_blocking!(&self.blocking_cp, |conn| {
+            epochs::dsl::epochs
+                .select(epochs::first_checkpoint_id)
+                .filter(epochs::epoch.eq(epoch))
+                .first::<i64>(conn)
+                .optional()
+        })
+        .context(&format!(
+            "Failed reading first checkpoint of epoch {epoch} from PostgresDB"
+        ))
+    }
+
+    fn get_epoch_last_checkpoint(&self, epoch: i64) -> Result<Option<i64>, IndexerError> {
+        read_only_blocking!(&self.blocking_cp, |conn| {
+            checkpoints::dsl::checkpoints
+                .select(max(checkpoints::sequence_number))
+                .filter(checkpoints::epoch.eq(epoch))
+                .first::<Option<i64>>(conn)
+        })
+        .context(&format!(
+            "Failed reading last checkpoint of epoch {epoch} from PostgresDB"
+        ))
+    }
+
+    fn get_pruned_checkpoint(&self, table: PrunedTable) -> Result<Option<i64>, IndexerError> {
+        read_only_blocking!(&self.blocking_cp, |conn| {
+            let watermark = pruning_watermarks::dsl::pruning_watermarks
+                .select(pruning_watermarks::pruned_before_checkpoint)
+                .filter(pruning_watermarks::table_family.eq(table.name()))
+                .first::<i64>(conn)
+                .optional()?;
+            if watermark.is_some() {
+                return Ok(watermark);
+            }
+            // never pruned, starting from the earliest indexed checkpoint
+            match table {
+                PrunedTable::ObjectsHistory => objects_history::dsl::objects_history
+                    .select(min(objects_history::checkpoint))
+                    .first::<Option<i64>>(conn),
+                _ => transactions::dsl::transactions
+                    .select(min(transactions::checkpoint_sequence_number))
+                    .first::<Option<i64>>(conn),
+            }
+        })
+        .context(&format!(
+            "Failed reading pruning watermark of {} from PostgresDB",
+            table.name()
+        ))
+    }
+
+    fn prune_checkpoints(
+        &self,
+        table: PrunedTable,
+        start: i64,
+        end: i64,
+    ) -> Result<usize, IndexerError> {
+        // digests of the transactions of the pruned checkpoints
+        let pruned_transactions = || {
+            transactions::table
+                .select(transactions::transaction_digest)
+                .filter(transactions::checkpoint_sequence_number.ge(start))
+                .filter(transactions::checkpoint_sequence_number.lt(end))
+        };
+        let mut pruned_blobs = vec![];
+        let rows = fenced_transactional_blocking!(self, |conn| {
+            let rows =
+                match table {
+                    PrunedTable::Events => {
+                        // the transactions of checkpoints `start..end` are numbered from the
+                        // network total of checkpoint `start - 1` to that of `end - 1`, the
+                        // checkpoints are never pruned so the events are pruned even after
+                        // the transactions are
+                        let first_tx = checkpoints::table
+                            .select(
+                                checkpoints::network_total_transactions
+                                    - checkpoints::total_transaction_blocks,
+                            )
+                            .filter(checkpoints::sequence_number.eq(start))
+                            .first::<i64>(conn)
+                            .optional()?;
+                        let end_tx = checkpoints::table
+                            .select(checkpoints::network_total_transactions)
+                            .filter(checkpoints::sequence_number.eq(end - 1))
+                            .first::<i64>(conn)
+                            .optional()?;
+                        match (first_tx, end_tx) {
+                            (Some(first_tx), Some(end_tx)) => diesel::delete(
+                                events::table
+                                    .filter(events::tx_sequence_number.ge(first_tx))
+                                    .filter(events::tx_sequence_number.lt(end_tx)),
+                            )
+                            .execute(conn)?,
+                            _ => 0,
+                        }
+                    }
+                    PrunedTable::Transactions => {
+                        diesel::delete(
+                            transactions_json::table
+                                .filter(transactions_json::checkpoint_sequence_number.ge(start))
+                                .filter(transactions_json::checkpoint_sequence_number.lt(end)),
+                        )
+                        .execute(conn)?
+                            + diesel::delete(
+                                checkpoint_proofs::table
+                                    .filter(checkpoint_proofs::sequence_number.ge(start))
+                                    .filter(checkpoint_proofs::sequence_number.lt(end)),
+                            )
+                            .execute(conn)?
+                            + diesel::delete(
+                                checkpoint_tx_digests::table
+                                    .filter(
+                                        checkpoint_tx_digests::checkpoint_sequence_number.ge(start),
+                                    )
+                                    .filter(
+                                        checkpoint_tx_digests::checkpoint_sequence_number.lt(end),
+                                    ),
+                            )
+                            .execute(conn)?
+                            + diesel::delete(
+                                transactions::table
+                                    .filter(transactions::checkpoint_sequence_number.ge(start))
+                                    .filter(transactions::checkpoint_sequence_number.lt(end)),
+                            )
+                            .execute(conn)?
+                    }
+                    PrunedTable::TxIndices => {
+                        diesel::delete(input_objects::table.filter(
+                            input_objects::transaction_digest.eq_any(pruned_transactions()),
+                        ))
+                        .execute(conn)?
+                            + diesel::delete(changed_objects::table.filter(
+                                changed_objects::transaction_digest.eq_any(pruned_transactions()),
+                            ))
+                            .execute(conn)?
+                            + diesel::delete(move_calls::table.filter(
+                                move_calls::transaction_digest.eq_any(pruned_transactions()),
+                            ))
+                            .execute(conn)?
+                            + diesel::delete(tx_commands::table.filter(
+                                tx_commands::transaction_digest.eq_any(pruned_transactions()),
+                            ))
+                            .execute(conn)?
+                            + diesel::delete(
+                                tx_calls::table.filter(
+                                    tx_calls::transaction_digest.eq_any(pruned_transactions()),
+                                ),
+                            )
+                            .execute(conn)?
+                            + diesel::delete(recipients::table.filter(
+                                recipients::transaction_digest.eq_any(pruned_transactions()),
+                            ))
+                            .execute(conn)?
+                            + diesel::delete(tx_dependencies::table.filter(
+                                tx_dependencies::transaction_digest.eq_any(pruned_transactions()),
+                            ))
+                            .execute(conn)?
+                    }
+                    PrunedTable::Transfers => diesel::delete(
+                        transfers::table
+                            .filter(transfers::transaction_digest.eq_any(pruned_transactions())),
+                    )
+                    .execute(conn)?,
+                    PrunedTable::ObjectsHistory => {
+                        let blobs = match &self.blob_store {
+                            Some(_) => diesel::sql_query(PRUNED_OBJECT_BLOBS_QUERY)
+                                .bind::<BigInt, _>(start)
+                                .bind::<BigInt, _>(end)
+                                .bind::<Bytea, _>(vec![OFFLOADED_PAYLOAD_V1])
+                                .load::<ObjectBlob>(conn)?,
+                            None => vec![],
+                        };
+                        let rows = diesel::delete(
+                            objects_history::table
+                                .filter(objects_history::checkpoint.ge(start))
+                                .filter(objects_history::checkpoint.lt(end)),
+                        )
+                        .execute(conn)?;
+                        pruned_blobs = blobs;
+                        rows
+                    }
+                };
+            diesel::insert_into(pruning_watermarks::table)
+                .values((
+                    pruning_watermarks::table_family.eq(table.name()),
+                    pruning_watermarks::pruned_before_checkpoint.eq(end),
+                ))
+                .on_conflict(pruning_watermarks::table_family)
+                .do_update()
+                .set(pruning_watermarks::pruned_before_checkpoint.eq(end))
+                .execute(conn)?;
+            Ok::<_, diesel::result::Error>(rows)
+        })
+        .context(&format!(
+            "Failed pruning {} of checkpoints [{start}, {end})",
+            table.name()
+        ))?;
+        // blobs are deleted once no committed row points to them, failures leave them behind.
+        // Rows committed since the pruning may point to a blob again, so references are read
+        // just before the deletion, with the object commits of this process, which may have
+        // uploaded the blob again but not committed its rows yet, held back until it is done.
+        if let Some(blob_store) = &self.blob_store {
+            if !pruned_blobs.is_empty() {
+                let _guard = self.blob_references.write().unwrap();
+                let deleted = self
+                    .unreferenced_blobs(pruned_blobs)
+                    .and_then(|paths| blob_store.delete(paths));
+                if let Err(e) = deleted {
+                    warn!("Failed deleting blobs of pruned objects_history rows: {e}");
+                }
+            }
+        }
+        Ok(rows)
+    }
+
+    /// Paths of the blobs among `blobs` that no row of objects or objects_history points to.
+    fn unreferenced_blobs(&self, blobs: Vec<ObjectBlob>) -> Result<Vec<ObjectPath>, IndexerError> {
+        // a blob is only pointed to by rows of its object
+        let object_ids = blobs
+            .iter()
+            .map(|blob| blob.object_id.clone())
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+        let referenced = read_only_blocking!(&self.blocking_cp, |conn| {
+            diesel::sql_query(OBJECT_BLOBS_QUERY)
+                .bind::<Array<VarChar>, _>(object_ids)
+                .bind::<Bytea, _>(vec![OFFLOADED_PAYLOAD_V1])
+                .load::<ObjectBlob>(conn)
+        })
+        .context("Failed reading blobs of objects from PostgresDB")?
+        .into_iter()
+        .map(|blob| blob.pointer)
+        .collect::<HashSet<_>>();
+        Ok(blobs
+            .into_iter()
+            .filter(|blob| !referenced.contains(&blob.pointer))
+            .filter_map(|blob| offloaded_path(&blob.pointer))
+            .collect())
+    }
+
+    fn get_watchlist_entries(&self) -> Result<Vec<WatchlistEntry>, IndexerError> {
+        let entries: Vec<DBWatchlistEntry> = read_only_blocking!(&self.blocking_cp, |conn| {
+            watchlists::dsl::watchlists.load::<DBWatchlistEntry>(conn)
+        })
+        .context("Failed reading watchlist entries from PostgresDB")?;
+        entries.into_iter().map(WatchlistEntry::try_from).collect()
+    }
+
+    fn get_watchlist(
+        &self,
+        cursor: Option<WatchlistEntry>,
+        limit: usize,
+    ) -> Result<Vec<WatchlistEntry>, IndexerError> {
+        let cursor = cursor.map(DBWatchlistEntry::from);
+        let entries: Vec<DBWatchlistEntry> =
+            read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
+                let mut boxed_query = watchlists::dsl::watchlists.into_boxed();
+                if let Some(cursor) = &cursor {
+                    // entries after the cursor in the order of the primary key
+                    boxed_query = boxed_query.filter(
+                        watchlists::entity_kind.gt(cursor.entity_kind.clone()).or(
+                            watchlists::entity_kind.eq(cursor.entity_kind.clone()).and(
+                                watchlists::entity_id.gt(cursor.entity_id.clone()).or(
+                                    watchlists::entity_id
+                                        .eq(cursor.entity_id.clone())
+                                        .and(watchlists::rule.gt(cursor.rule.clone())),
+                                ),
+                            ),
+                        ),
+                    );
+                }
+                boxed_query
+                    .order((
+                        watchlists::entity_kind.asc(),
+                        watchlists::entity_id.asc(),
+                        watchlists::rule.asc(),
+                    ))
+                    .limit(limit as i64)
+                    .load::<DBWatchlistEntry>(conn)
+            })
+            .context(&format!(
+                "Failed reading watchlist entries after {cursor:?} with limit {limit}"
+            ))?;
+        entries.into_iter().map(WatchlistEntry::try_from).collect()
+    }
+
+    fn get_api_keys(&self) -> Result<Vec<ApiKey>, IndexerError> {
+        read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
+            api_keys::dsl::api_keys.load::<ApiKey>(conn)
+        })
+        .context("Failed reading API keys from PostgresDB")
+    }
+
+    fn get_snapshot_bootstrap(&self) -> Result<Option<SnapshotBootstrap>, IndexerError> {
+        read_only_blocking!(&self.blocking_cp, |conn| {
+            snapshot_bootstraps::dsl::snapshot_bootstraps
+                .order(snapshot_bootstraps::epoch.desc())
+                .first::<SnapshotBootstrap>(conn)
+                .optional()
+        })
+        .context("Failed reading snapshot bootstrap from PostgresDB")
+    }
+
+    fn persist_snapshot_bootstrap(&self, bootstrap: SnapshotBootstrap) -> Result<(), IndexerError> {
+        fenced_transactional_blocking!(self, |conn| {
+            diesel::insert_into(snapshot_bootstraps::table)
+                .values(&bootstrap)
+                .execute(conn)
+                .map_err(IndexerError::from)
+                .context("Failed writing snapshot bootstrap to PostgresDB")
+        })?;
+        Ok(())
+    }
+
+    fn get_chain_identifier(&self) -> Result<Option<ChainIdentifier>, IndexerError> {
+        read_only_blocking!(&self.blocking_cp, |conn| {
+            chain_identifier::dsl::chain_identifier
+                .first::<ChainIdentifier>(conn)
+                .optional()
+        })
+        .context("Failed reading chain identifier from PostgresDB")
+    }
+
+    fn persist_chain_identifier(&self, identifier: ChainIdentifier) -> Result<(), IndexerError> {
+        fenced_transactional_blocking!(self, |conn| {
+            diesel::insert_into(chain_identifier::table)
+                .values(&identifier)
+                .on_conflict_do_nothing()
+                .execute(conn)
+                .map_err(IndexerError::from)
+                .context("Failed writing chain identifier to PostgresDB")
+        })?;
+        Ok(())
+    }
+
+    fn enqueue_checkpoints(
+        &self,
+        checkpoints: Vec<QueuedCheckpoint>,
+    ) -> Result<usize, IndexerError> {
+        fenced_transactional_blocking!(self, |conn| {
+            let mut queued = 0;
+            for checkpoint_chunk in
+                checkpoints.chunks(self.commit_chunk_sizes.get("checkpoint_queue"))
+            {
+                queued += diesel::insert_into(checkpoint_queue::table)
+                    .values(checkpoint_chunk)
+                    .on_conflict_do_nothing()
+                    .execute(conn)
+                    .map_err(IndexerError::from)
+                    .context("Failed writing queued checkpoints to PostgresDB")?;
+            }
+            Ok::<_, IndexerError>(queued)
+        })
+    }
+
+    fn get_queued_checkpoints(
+        &self,
+        kind: QueueKind,
+        sequence_number: i64,
+        limit: usize,
+    ) -> Result<Vec<QueuedCheckpoint>, IndexerError> {
+        read_only_blocking!(&self.blocking_cp, |conn| {
+            checkpoint_queue::dsl::checkpoint_queue
+                .filter(checkpoint_queue::kind.eq(kind.name()))
+                .filter(checkpoint_queue::sequence_number.ge(sequence_number))
+                .order(checkpoint_queue::sequence_number.asc())
+                .limit(limit as i64)
+                .load::<QueuedCheckpoint>(conn)
+        })
+        .context(&format!(
+            "Failed reading queued {} from {sequence_number} from PostgresDB",
+            kind.name()
+        ))
+    }
+
+    fn delete_queued_checkpoints(
+        &self,
+        kind: QueueKind,
+        sequence_number: i64,
+    ) -> Result<usize, IndexerError> {
+        fenced_transactional_blocking!(self, |conn| {
+            diesel::delete(
+                checkpoint_queue::table
+                    .filter(checkpoint_queue::kind.eq(kind.name()))
+                    .filter(checkpoint_queue::sequence_number.le(sequence_number)),
+            )
+            .execute(conn)
+            .map_err(IndexerError::from)
+            .context(&format!(
+                "Failed deleting queued {} up to {sequence_number} from PostgresDB",
+                kind.name()
+            ))
+        })
+    }
+
+    fn try_acquire_leader_lock(&self, name: &str) -> Result<bool, IndexerError> {
+        let mut leader_connection = self.leader_connection.lock().unwrap();
+        if leader_connection.is_some() {
+            return Ok(true);
+        }
+        let connect = self.leader_lock_connector.as_ref().ok_or_else(|| {
+            IndexerError::InvalidArgumentError(
+                "Leader election requires a leader lock connector".to_string(),
+            )
+        })?;
+        let mut conn = connect()?;
+        let attempt = diesel::sql_query(TRY_LEADER_LOCK_QUERY)
+            .bind::<VarChar, _>(name)
+            .get_result::<LeaderLockAttempt>(&mut conn)
+            .map_err(IndexerError::from)
+            .context(&format!("Failed trying leader lock {name} in PostgresDB"))?;
+        // writes are fenced from now on, standbys do not write
+        *self.leader_fence.lock().unwrap() = Some(LeaderFence {
+            key: attempt.key,
+            pid: attempt.locked.then_some(attempt.pid),
+        });
+        if attempt.locked {
+            *leader_connection = Some(conn);
+        }
+        Ok(attempt.locked)
+    }
+
+    fn holds_leader_lock(&self) -> Result<bool, IndexerError> {
+        let mut leader_connection = self.leader_connection.lock().unwrap();
+        let (conn, fence) = match (leader_connection.as_mut(), self.leader_fence()) {
+            (Some(conn), Some(fence)) => (conn, fence),
+            _ => return Ok(false),
+        };
+        let held = diesel::sql_query(HOLDS_LEADER_LOCK_QUERY)
+            .bind::<BigInt, _>(fence.key)
+            .bind::<Integer, _>(fence.pid.unwrap_or_default())
+            .get_result::<LeaderLock>(conn)
+            .map(|lock| lock.locked);
+        if !matches!(held, Ok(true)) {
+            // closing the connection releases the lock if it still holds it
+            *leader_connection = None;
+            if let Some(fence) = self.leader_fence.lock().unwrap().as_mut() {
+                fence.pid = None;
+            }
+        }
+        held.map_err(IndexerError::from)
+            .context("Failed checking leader lock in PostgresDB")
+    }
+
+    fn leader_fence(&self) -> Option<LeaderFence> {
+        *self.leader_fence.lock().unwrap()
+    }
+
+    // `query` preceded by the check of the leader fence, in the same transaction.
+    fn fenced<'a, T, E>(
+        &'a self,
+        query: impl FnOnce(&mut PgConnection) -> Result<T, E> + 'a,
+    ) -> impl FnOnce(&mut PgConnection) -> Result<T, E> + 'a
+    where
+        E: From<diesel::result::Error>,
+    {
+        move |conn| {
+            self.check_leader_fence(conn)?;
+            query(conn)
+        }
+    }
+
+    // Fails the write transaction of `conn` unless the leader lock is held by the leader
+    // connection of this replica, if it takes part in leader election. Checked within the
+    // transaction rather than every leader election interval, so that a replica which lost
+    // the lock stops writing before a standby takes over.
+    fn check_leader_fence(&self, conn: &mut PgConnection) -> Result<(), diesel::result::Error> {
+        let fence = match self.leader_fence() {
+            Some(fence) => fence,
+            None => return Ok(()),
+        };
+        let held = match fence.pid {
+            Some(pid) => {
+                diesel::sql_query(HOLDS_LEADER_LOCK_QUERY)
+                    .bind::<BigInt, _>(fence.key)
+                    .bind::<Integer, _>(pid)
+                    .get_result::<LeaderLock>(conn)?
+                    .locked
+            }
+            None => false,
+        };
+        if held {
+            return Ok(());
+        }
+        Err(diesel::result::Error::QueryBuilderError(
+            "Not committing, this replica does not hold the leader lock".into(),
+        ))
+    }
+
+    fn get_committed_queue_sequence_number(&self, kind: QueueKind) -> Result<i64, IndexerError> {
+        match kind {
+            QueueKind::Transactions => self.get_latest_tx_checkpoint_sequence_number(),
+            QueueKind::Objects => self.get_latest_object_checkpoint_sequence_number(),
+            QueueKind::Epochs => self.get_latest_epoch_id(),
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn persist_transaction_index_tables(
+        &self,
+        input_objects: &[InputObject],
+        changed_objects: &[ChangedObject],
+        move_calls: &[MoveCall],
+        tx_commands: &[TxCommand],
+        tx_calls: &[TxCall],
+        recipients: &[Recipient],
+        tx_dependencies: &[TxDependency],
+        transfers: &[Transfer],
+        ownership_transitions: &[OwnershipTransition],
+    ) -> Result<(), IndexerError> {
+        fenced_transactional_blocking!(self, |conn| {
+            // Commit indexed move calls
+            for move_calls_chunk in move_calls.chunks(self.commit_chunk_sizes.get("move_calls")) {
+                diesel::insert_into(move_calls::table)
+                    .values(move_calls_chunk)
+                    .on_conflict_do_nothing()
+                    .execute(conn)
+                    .map_err(IndexerError::from)
+                    .context("Failed writing move_calls to PostgresDB")?;
+            }
+
+            // Commit indexed programmable transaction commands
+            for tx_commands_chunk in tx_commands.chunks(self.commit_chunk_sizes.get("tx_commands"))
+            {
+                diesel::insert_into(tx_commands::table)
+                    .values(tx_commands_chunk)
+                    .on_conflict_do_nothing()
+                    .execute(conn)
+                    .map_err(IndexerError::from)
+                    .context("Failed writing tx_commands to PostgresDB")?;
+            }
+
+            // Commit Move calls with their decoded arguments
+            for tx_calls_chunk in tx_calls.chunks(self.commit_chunk_sizes.get("tx_calls")) {
+                diesel::insert_into(tx_calls::table)
+                    .values(tx_calls_chunk)
+                    .on_conflict_do_nothing()
+                    .execute(conn)
+                    .map_err(IndexerError::from)
+                    .context("Failed writing tx_calls to PostgresDB")?;
+            }
+
+            // Commit indexed input objects
+            for input_objects_chunk in
+                input_objects.chunks(self.commit_chunk_sizes.get("input_objects"))
+            {
+                diesel::insert_into(input_objects::table)
+                    .values(input_objects_chunk)
+                    .on_conflict_do_nothing()
+                    .execute(conn)
+                    .map_err(IndexerError::from)
+                    .context("Failed writing input_objects to PostgresDB")?;
+            }
+
+            // Commit indexed changed objects
+            for changed_objects_chunk in
+                changed_objects.chunks(self.commit_chunk_sizes.get("changed_objects"))
+            {
+                diesel::insert_into(changed_objects::table)
+                    .values(changed_objects_chunk)
+                    .on_conflict_do_nothing()
+                    .execute(conn)
+                    .map_err(IndexerError::from)
+                    .context("Failed writing changed_objects to PostgresDB")?;
+            }
+
+            // Commit indexed recipients
+            for recipients_chunk in recipients.chunks(self.commit_chunk_sizes.get("recipients")) {
+                diesel::insert_into(recipients::table)
+                    .values(recipients_chunk)
+                    .on_conflict_do_nothing()
+                    .execute(conn)
+                    .map_err(IndexerError::from)
+                    .context("Failed writing recipients to PostgresDB")?;
+            }
+
+            // Commit indexed transaction dependencies
+            for tx_dependencies_chunk in
+                tx_dependencies.chunks(self.commit_chunk_sizes.get("tx_dependencies"))
+            {
+                diesel::insert_into(tx_dependencies::table)
+                    .values(tx_dependencies_chunk)
+                    .on_conflict_do_nothing()
+                    .execute(conn)
+                    .map_err(IndexerError::from)
+                    .context("Failed writing tx_dependencies to PostgresDB")?;
+            }
+
+            // Commit indexed transfers
+            for transfers_chunk in transfers.chunks(self.commit_chunk_sizes.get("transfers")) {
+                diesel::insert_into(transfers::table)
+                    .values(transfers_chunk)
+                    .on_conflict_do_nothing()
+                    .execute(conn)
+                    .map_err(IndexerError::from)
+                    .context("Failed writing transfers to PostgresDB")?;
+            }
+
+            // Commit indexed ownership transitions
+            for transitions_chunk in ownership_transitions
+                .chunks(self.commit_chunk_sizes.get("object_ownership_history"))
+            {
+                diesel::insert_into(object_ownership_history::table)
+                    .values(transitions_chunk)
+                    .on_conflict_do_nothing()
+                    .execute(conn)
+                    .map_err(IndexerError::from)
+                    .context("Failed writing object_ownership_history to PostgresDB")?;
+            }
+            Ok::<(), IndexerError>(())
+        })?;
+        Ok(())
+    }
+
+    fn get_ownership_history(
+        &self,
+        object_id: ObjectID,
+        cursor: Option<u64>,
+        limit: usize,
+        is_descending: bool,
+    ) -> Result<Vec<ObjectOwnershipTransition>, IndexerError> {
+        let transitions: Vec<OwnershipTransition> =
+            read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
+                let mut boxed_query = object_ownership_history::table
+                    .filter(object_ownership_history::object_id.eq(object_id.to_string()))
+                    .into_boxed();
+                if let Some(cursor) = cursor {
+                    if is_descending {
+                        boxed_query = boxed_query
+                            .filter(object_ownership_history::object_version.lt(cursor as i64));
+                    } else {
+                        boxed_query = boxed_query
+                            .filter(object_ownership_history::object_version.gt(cursor as i64));
+                    }
+                }
+                if is_descending {
+                    boxed_query
+                        .order(object_ownership_history::object_version.desc())
+                        .limit(limit as i64)
+                        .load::<OwnershipTransition>(conn)
+                } else {
+                    boxed_query
+                        .order(object_ownership_history::object_version.asc())
+                        .limit(limit as i64)
+                        .load::<OwnershipTransition>(conn)
+                }
+            })
+            .context(&format!(
+                "Failed reading ownership history of object {object_id} with cursor {cursor:?} and limit {limit}"
+            ))?;
+        transitions
+            .into_iter()
+            .map(ObjectOwnershipTransition::try_from)
+            .collect()
+    }
+
+    fn resolve_name(&self, name: String) -> Result<Option<SuiAddress>, IndexerError> {
+        let target_address: Option<Option<String>> =
+            read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
+                let now_ms = latest_checkpoint_timestamp_ms(conn)?;
+                name_records::table
+                    .select(name_records::target_address)
+                    .filter(name_records::name.eq(&name))
+                    .filter(name_records::expiration_timestamp_ms.gt(now_ms))
+                    .first::<Option<String>>(conn)
+                    .optional()
+            })
+            .context(&format!("Failed resolving name {name}"))?;
+        Ok(match target_address.flatten() {
+            Some(address) => Some(SuiAddress::from_str(&address)?),
+            None => None,
+        })
+    }
+
+    fn lookup_names(
+        &self,
+        address: SuiAddress,
+        cursor: Option<ObjectID>,
+        limit: usize,
+    ) -> Result<Page<String, ObjectID>, IndexerError> {
+        let mut records: Vec<(String, String)> =
+            read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
+                let now_ms = latest_checkpoint_timestamp_ms(conn)?;
+                let mut query = name_records::table
+                    .select((name_records::field_id, name_records::name))
+                    .filter(name_records::target_address.eq(address.to_string()))
+                    .filter(name_records::expiration_timestamp_ms.gt(now_ms))
+                    .order(name_records::field_id.asc())
+                    .limit(limit as i64 + 1)
+                    .into_boxed();
+                if let Some(cursor) = cursor {
+                    query = query.filter(name_records::field_id.gt(cursor.to_string()));
+                }
+                query.load(conn)
+            })
+            .context(&format!("Failed looking up names of address {address}"))?;
+        let has_next_page = records.len() > limit;
+        records.truncate(limit);
+        let next_cursor = records
+            .last()
+            .map(|(field_id, _)| ObjectID::from_str(field_id))
+            .transpose()?;
+        Ok(Page {
+            data: records.into_iter().map(|(_, name)| name).collect(),
+            next_cursor,
+            has_next_page,
+        })
+    }
+
+    // Modules are looked up one by one, those already resolved are served from the module
+    // cache.
+    fn get_modules(&self, ids: Vec<ModuleId>) -> HashMap<ModuleId, Arc<CompiledModule>> {
+        ids.into_iter()
+            .filter_map(|id| match self.module_cache.get_module_by_id(&id) {
+                Ok(Some(module)) => Some((id, module)),
+                Ok(None) => None,
+                Err(e) => {
+                    warn!("Failed resolving module {id} with error: {e}");
+                    None
+                }
+            })
+            .collect()
+    }
+
+    fn get_hot_objects(
+        &self,
+        window: HotWindow,
+        limit: usize,
+    ) -> Result<Vec<HotEntity>, IndexerError> {
+        self.get_hot_entities(HOT_OBJECTS_QUERY, window, limit)
+    }
+
+    fn get_hot_packages(
+        &self,
+        window: HotWindow,
+        limit: usize,
+    ) -> Result<Vec<HotEntity>, IndexerError> {
+        self.get_hot_entities(HOT_PACKAGES_QUERY, window, limit)
+    }
+
+    fn get_hot_entities(
+        &self,
+        query: &'static str,
+        window: HotWindow,
+        limit: usize,
+    ) -> Result<Vec<HotEntity>, IndexerError> {
+        let key = (query, window, limit);
+        if let Some(entities) = self.hot_entities.lock().unwrap().cache_get(&key) {
+            return Ok(entities.clone());
+        }
+        let entities: Vec<DBHotEntity> =
+            read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
+                let latest_bucket_start_ms = bucket_start_ms(latest_checkpoint_timestamp_ms(conn)?);
+                diesel::sql_query(query)
+                    .bind::<BigInt, _>(latest_bucket_start_ms - window.duration_ms())
+                    .bind::<BigInt, _>(limit as i64)
+                    .load(conn)
+            })
+            .context(&format!(
+                "Failed reading hot entities of the last {}",
+                window.name()
+            ))?;
+        let entities = entities
+            .into_iter()
+            .map(HotEntity::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        self.hot_entities
+            .lock()
+            .unwrap()
+            .cache_set(key, entities.clone());
+        Ok(entities)
+    }
+
+    fn get_transaction_ancestors(
+        &self,
+        transaction_digest: TransactionDigest,
+        depth: usize,
+    ) -> Result<Vec<TransactionDependencyNode>, IndexerError> {
+        self.walk_tx_dependencies(TX_ANCESTORS_QUERY, transaction_digest, depth)
+    }
+
+    fn get_transaction_descendants(
+        &self,
+        transaction_digest: TransactionDigest,
+        depth: usize,
+    ) -> Result<Vec<TransactionDependencyNode>, IndexerError> {
+        self.walk_tx_dependencies(TX_DESCENDANTS_QUERY, transaction_digest, depth)
+    }
+
+    fn walk_tx_dependencies(
+        &self,
+        query: &'static str,
+        transaction_digest: TransactionDigest,
+        depth: usize,
+    ) -> Result<Vec<TransactionDependencyNode>, IndexerError> {
+        if depth == 0 || depth > MAX_TX_DEPENDENCY_DEPTH {
+            return Err(IndexerError::InvalidArgumentError(format!(
+                "Dependency depth must be between 1 and {MAX_TX_DEPENDENCY_DEPTH}, got {depth}"
+            )));
+        }
+        let nodes: Vec<DBTxDependencyNode> =
+            read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
+                diesel::sql_query(query)
+                    .bind::<VarChar, _>(transaction_digest.to_string())
+                    .bind::<BigInt, _>(depth as i64)
+                    .load(conn)
+            })
+            .context(&format!(
+                "Failed walking dependencies of transaction {transaction_digest}"
+            ))?;
+        Ok(nodes.into_iter().map(|n| n.into()).collect())
+    }
+
+    fn get_network_total_transactions_previous_epoch(
+        &self,
+        epoch: i64,
+    ) -> Result<i64, IndexerError> {
+        read_only_blocking!(&self.blocking_cp, |conn| {
             checkpoints::table
                 .filter(checkpoints::epoch.eq(epoch - 1))
                 .select(max(checkpoints::network_total_transactions))
@@ -1457,6 +3915,113 @@ impl PgIndexerStore {
         .context("Failed to count network transactions in previous epoch")
     }
 
+    fn persist_object_type_stats(&self, epoch: EpochId) -> Result<(), IndexerError> {
+        fenced_transactional_blocking!(self, |conn| {
+            // recounted from scratch when the epoch is committed again, e.g. after a restart
+            diesel::delete(object_type_stats::table)
+                .filter(object_type_stats::epoch.eq(epoch as i64))
+                .execute(conn)?;
+            diesel::sql_query(OBJECT_TYPE_STATS_QUERY)
+                .bind::<BigInt, _>(epoch as i64)
+                .execute(conn)
+                .map_err(IndexerError::from)
+                .context(&format!(
+                    "Failed writing object type stats of epoch {epoch} to PostgresDB"
+                ))
+        })?;
+        Ok(())
+    }
+
+    fn get_object_type_stats(&self, epoch: EpochId) -> Result<Vec<ObjectTypeStat>, IndexerError> {
+        read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
+            object_type_stats::table
+                .filter(object_type_stats::epoch.eq(epoch as i64))
+                .order_by(object_type_stats::object_count.desc())
+                .load::<ObjectTypeStat>(conn)
+        })
+        .context(&format!(
+            "Failed reading object type stats of epoch {epoch} from PostgresDB"
+        ))
+    }
+
+    fn get_table_sizes(&self) -> Result<Vec<TableSize>, IndexerError> {
+        read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
+            diesel::sql_query(TABLE_SIZES_QUERY).load::<TableSize>(conn)
+        })
+        .context("Failed reading table sizes from PostgresDB")
+    }
+
+    fn explain_query(&self, shape: QueryShape) -> Result<serde_json::Value, IndexerError> {
+        let name = shape.name;
+        read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
+            Explain(shape.query).get_result::<serde_json::Value>(conn)
+        })
+        .context(&format!("Failed explaining query shape {name}"))
+    }
+
+    fn set_objects_history_enabled(&self, enabled: bool) -> Result<(), IndexerError> {
+        // objects_history is filled by a trigger on objects, see the objects migration
+        let action = if enabled { "ENABLE" } else { "DISABLE" };
+        let mut conn = get_pg_pool_connection(&self.blocking_cp)?;
+        // ALTER TABLE locks objects against reads too, so it is only run to change the state
+        let status = diesel::sql_query(OBJECTS_HISTORY_TRIGGER_QUERY)
+            .get_result::<TriggerStatus>(&mut conn)
+            .map_err(|e| {
+                IndexerError::PostgresReadError(format!(
+                    "Failed reading status of objects_history trigger with error: {e}"
+                ))
+            })?;
+        if status.enabled == enabled {
+            return Ok(());
+        }
+        diesel::sql_query(format!(
+            "ALTER TABLE objects {action} TRIGGER objects_history"
+        ))
+        .execute(&mut conn)
+        .map_err(|e| {
+            IndexerError::PostgresWriteError(format!(
+                "Failed to {action} objects_history trigger with error: {e}"
+            ))
+        })?;
+        Ok(())
+    }
+
+    fn refresh_materialized_view(&self, view: MaterializedView) -> Result<(), IndexerError> {
+        let mut conn = get_pg_pool_connection(&self.blocking_cp)?;
+        // a refresh runs past the statement timeout of the pool on real data, the transaction
+        // only scopes lifting it; a refresh still locks no more than the view itself
+        conn.build_transaction()
+            .read_write()
+            .run(with_statement_timeout(Duration::ZERO, |conn| {
+                if view == MaterializedView::EpochStorageGrowth {
+                    return update_epoch_storage_growth(conn);
+                }
+                let status = diesel::sql_query(MATERIALIZED_VIEW_STATUS_QUERY)
+                    .bind::<VarChar, _>(view.name())
+                    .get_result::<MaterializedViewStatus>(conn)
+                    .map_err(IndexerError::from)
+                    .context(&format!(
+                        "Failed reading status of materialized view {}",
+                        view.name()
+                    ))?;
+                // concurrent refreshes keep the view readable, but require it to be populated
+                let refresh = if status.ispopulated {
+                    "REFRESH MATERIALIZED VIEW CONCURRENTLY"
+                } else {
+                    "REFRESH MATERIALIZED VIEW"
+                };
+                diesel::sql_query(format!("{refresh} {}", view.name()))
+                    .execute(conn)
+                    .map_err(|e| {
+                        IndexerError::PostgresWriteError(format!(
+                            "Failed refreshing materialized view {} with error: {e}",
+                            view.name()
+                        ))
+                    })?;
+                Ok(())
+            }))
+    }
+
     fn persist_epoch(&self, data: &TemporaryEpochStore) -> Result<(), IndexerError> {
         // MUSTFIX(gegaowp): temporarily disable the epoch advance logic.
         // let last_epoch_cp_id = if data.last_epoch.is_none() {
@@ -1469,7 +4034,7 @@ impl PgIndexerStore {
             self.partition_manager
                 .advance_epoch(&data.new_epoch, last_epoch_cp_id)?;
         }
-        transactional_blocking!(&self.blocking_cp, |conn| {
+        fenced_transactional_blocking!(self, |conn| {
             if let Some(last_epoch) = &data.last_epoch {
                 info!("Persisting at the end of epoch {}", last_epoch.epoch);
                 diesel::insert_into(epochs::table)
@@ -1500,6 +4065,7 @@ impl PgIndexerStore {
                             .eq(excluded(epochs::total_stake_rewards_distributed)),
                         epochs::leftover_storage_fund_inflow
                             .eq(excluded(epochs::leftover_storage_fund_inflow)),
+                        epochs::safe_mode.eq(excluded(epochs::safe_mode)),
                     ))
                     .execute(conn)?;
                 info!("Persisted epoch {}", last_epoch.epoch);
@@ -1512,10 +4078,40 @@ impl PgIndexerStore {
             diesel::insert_into(validators::table)
                 .values(&data.validators)
                 .on_conflict_do_nothing()
+                .execute(conn)?;
+
+            let previous_stakes: HashMap<String, i64> = validator_epoch_events::table
+                .filter(validator_epoch_events::epoch.eq(data.new_epoch.epoch - 1))
+                .select((
+                    validator_epoch_events::validator_address,
+                    validator_epoch_events::stake,
+                ))
+                .load::<(String, i64)>(conn)?
+                .into_iter()
+                .collect();
+            let validator_epoch_events: Vec<DBValidatorEpochEvent> = data
+                .validator_epoch_events
+                .iter()
+                .cloned()
+                .map(|mut event| {
+                    if let Some(previous_stake) = previous_stakes.get(&event.validator_address) {
+                        event.stake_delta = event.stake - previous_stake;
+                    }
+                    event
+                })
+                .collect();
+            diesel::insert_into(validator_epoch_events::table)
+                .values(&validator_epoch_events)
+                .on_conflict_do_nothing()
+                .execute(conn)?;
+
+            diesel::insert_into(protocol_configs::table)
+                .values(&data.protocol_config)
+                .on_conflict_do_nothing()
                 .execute(conn)
         })?;
         info!("Persisting initial state of epoch {}", data.new_epoch.epoch);
-        transactional_blocking!(&self.blocking_cp, |conn| {
+        fenced_transactional_blocking!(self, |conn| {
             diesel::insert_into(epochs::table)
                 .values(&data.new_epoch)
                 .on_conflict_do_nothing()
@@ -1546,20 +4142,23 @@ impl PgIndexerStore {
                 .order_by(epochs::epoch.asc());
         }
 
-        let epoch_info: Vec<DBEpochInfo> = read_only_blocking!(&self.blocking_cp, |conn| query
-            .limit(limit as i64)
-            .load(conn))
-        .map_err(|e| {
-            IndexerError::PostgresReadError(format!(
-                "Failed reading epochs from PostgresDB with error {:?}",
-                e
-            ))
-        })?;
+        let epoch_info: Vec<DBEpochInfo> =
+            read_only_blocking!(&self.read_cp, self.query_timeout, |conn| query
+                .limit(limit as i64)
+                .load(conn))
+            .map_err(|e| {
+                IndexerError::PostgresReadError(format!(
+                    "Failed reading epochs from PostgresDB with error {:?}",
+                    e
+                ))
+            })?;
 
         let validators: Vec<DBValidatorSummary> =
-            read_only_blocking!(&self.blocking_cp, |conn| validators::dsl::validators
-                .filter(validators::epoch.gt(id))
-                .load(conn))
+            read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
+                validators::dsl::validators
+                    .filter(validators::epoch.gt(id))
+                    .load(conn)
+            })
             .map_err(|e| {
                 IndexerError::PostgresReadError(format!(
                     "Failed reading validators from PostgresDB with error {:?}",
@@ -1585,21 +4184,464 @@ impl PgIndexerStore {
     }
 
     fn get_current_epoch(&self) -> Result<EpochInfo, IndexerError> {
-        let epoch_info: DBEpochInfo = read_only_blocking!(&self.blocking_cp, |conn| {
-            epochs::dsl::epochs
-                .order_by(epochs::epoch.desc())
-                .first::<DBEpochInfo>(conn)
+        let read = || {
+            let epoch_info: DBEpochInfo =
+                read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
+                    epochs::dsl::epochs
+                        .order_by(epochs::epoch.desc())
+                        .first::<DBEpochInfo>(conn)
+                })
+                .context("Failed reading current epoch")?;
+
+            let validators: Vec<DBValidatorSummary> =
+                read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
+                    validators::dsl::validators
+                        .filter(validators::epoch.eq(epoch_info.epoch))
+                        .load(conn)
+                })
+                .context("Failed reading latest validator summary")?;
+            Ok::<_, IndexerError>((epoch_info, validators))
+        };
+        let (epoch_info, validators) = match &self.query_cache {
+            Some(cache) => cache.current_epoch.get_or_try_insert_with(read)?,
+            None => read()?,
+        };
+        epoch_info.to_epoch_info(validators)
+    }
+
+    // The reference gas price of an epoch is only recorded in the epochs table once the epoch
+    // ends, the system state of every epoch, including the ongoing one, is read instead.
+    fn get_reference_gas_price_history(
+        &self,
+        start_epoch: EpochId,
+        end_epoch: EpochId,
+    ) -> Result<Vec<(EpochId, u64)>, IndexerError> {
+        let prices: Vec<(i64, i64)> =
+            read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
+                system_states::table
+                    .select((system_states::epoch, system_states::reference_gas_price))
+                    .filter(system_states::epoch.ge(start_epoch as i64))
+                    .filter(system_states::epoch.lt(end_epoch as i64))
+                    .order_by(system_states::epoch.asc())
+                    .load(conn)
+            })
+            .context(&format!(
+                "Failed reading reference gas prices of epochs [{start_epoch}, {end_epoch})"
+            ))?;
+        Ok(prices
+            .into_iter()
+            .map(|(epoch, price)| (epoch as EpochId, price as u64))
+            .collect())
+    }
+
+    fn get_reference_gas_price(&self) -> Result<u64, IndexerError> {
+        let read = || {
+            read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
+                system_states::table
+                    .select(system_states::reference_gas_price)
+                    .order_by(system_states::epoch.desc())
+                    .first::<i64>(conn)
+            })
+            .context("Failed reading reference gas price of the latest epoch")
+        };
+        let price = match &self.query_cache {
+            Some(cache) => cache.reference_gas_price.get_or_try_insert_with(read)?,
+            None => read()?,
+        };
+        Ok(price as u64)
+    }
+
+    fn get_protocol_config(&self, epoch: EpochId) -> Result<ProtocolConfigResponse, IndexerError> {
+        let config: DBProtocolConfig =
+            read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
+                protocol_configs::dsl::protocol_configs
+                    .filter(protocol_configs::epoch.eq(epoch as i64))
+                    .first::<DBProtocolConfig>(conn)
+            })
+            .context(&format!("Failed reading protocol config of epoch {epoch}"))?;
+        config.try_into()
+    }
+
+    fn get_system_state_at_epoch(
+        &self,
+        epoch: EpochId,
+    ) -> Result<SuiSystemStateSummary, IndexerError> {
+        let summary: Option<serde_json::Value> =
+            read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
+                system_states::table
+                    .select(system_states::summary)
+                    .filter(system_states::epoch.eq(epoch as i64))
+                    .first(conn)
+            })
+            .context(&format!("Failed reading system state of epoch {epoch}"))?;
+        // epochs indexed before summaries were recorded only have the flattened columns
+        let summary = summary.ok_or_else(|| {
+            IndexerError::NotSupportedError(format!(
+                "System state summary of epoch {epoch} was not recorded"
+            ))
+        })?;
+        serde_json::from_value(summary).map_err(|e| {
+            IndexerError::SerdeError(format!(
+                "Failed deserializing system state of epoch {epoch} with error: {e}"
+            ))
         })
-        .context("Failed reading current epoch")?;
+    }
 
-        let validators: Vec<DBValidatorSummary> = read_only_blocking!(&self.blocking_cp, |conn| {
-            validators::dsl::validators
-                .filter(validators::epoch.eq(epoch_info.epoch))
-                .load(conn)
+    // Latest version of a package, without loading its modules.
+    fn get_latest_package_version(&self, package_id: &str) -> Result<i64, IndexerError> {
+        read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
+            packages::dsl::packages
+                .select(packages::version)
+                .filter(packages::package_id.eq(package_id))
+                .order_by(packages::version.desc())
+                .first::<i64>(conn)
         })
-        .context("Failed reading latest validator summary")?;
+        .context(&format!("Failed reading version of package {package_id}"))
+    }
 
-        epoch_info.to_epoch_info(validators)
+    fn get_package_version(&self, package_id: &str, version: i64) -> Result<Package, IndexerError> {
+        read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
+            packages::dsl::packages
+                .filter(packages::package_id.eq(package_id))
+                .filter(packages::version.eq(version))
+                .first::<Package>(conn)
+        })
+        .context(&format!(
+            "Failed reading package {package_id} at version {version}"
+        ))
+    }
+
+    fn get_module_disassembly(
+        &self,
+        package: ObjectID,
+        module: String,
+    ) -> Result<String, IndexerError> {
+        let package_id = package.to_string();
+        let version = self.get_latest_package_version(&package_id)?;
+        let cached: Option<String> =
+            read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
+                module_disassemblies::dsl::module_disassemblies
+                    .select(module_disassemblies::disassembly)
+                    .filter(module_disassemblies::package_id.eq(&package_id))
+                    .filter(module_disassemblies::version.eq(version))
+                    .filter(module_disassemblies::module_name.eq(&module))
+                    .first::<String>(conn)
+                    .optional()
+            })
+            .context(&format!(
+                "Failed reading disassembly of module {module} in package {package_id}"
+            ))?;
+        if let Some(disassembly) = cached {
+            return Ok(disassembly);
+        }
+
+        let package = self.get_package_version(&package_id, version)?;
+        let disassembled = ModuleDisassembly::try_from_package(&package, &module)?;
+        self.write_from_read_path(
+            &format!("disassembly of module {module} in package {package_id}"),
+            |conn| {
+                diesel::insert_into(module_disassemblies::table)
+                    .values(&disassembled)
+                    .on_conflict_do_nothing()
+                    .execute(conn)
+            },
+        );
+        Ok(disassembled.disassembly)
+    }
+
+    fn get_event_schema(
+        &self,
+        event_type: StructTag,
+    ) -> Result<SuiMoveNormalizedStruct, IndexerError> {
+        let package_id = ObjectID::from(event_type.address).to_string();
+        let module = event_type.module.to_string();
+        let name = event_type.name.to_string();
+        let version = self.get_latest_package_version(&package_id)?;
+        let schema: Option<EventSchema> =
+            read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
+                event_schemas::dsl::event_schemas
+                    .filter(event_schemas::package_id.eq(&package_id))
+                    .filter(event_schemas::version.eq(version))
+                    .filter(event_schemas::module.eq(&module))
+                    .filter(event_schemas::name.eq(&name))
+                    .first::<EventSchema>(conn)
+                    .optional()
+            })
+            .context(&format!("Failed reading event schema of {event_type}"))?;
+        if let Some(schema) = schema {
+            return schema.try_into();
+        }
+
+        // packages indexed before event schemas were recorded
+        let package = self.get_package_version(&package_id, version)?;
+        let schemas = EventSchema::from_package(&package)?;
+        self.write_from_read_path(&format!("event schemas of package {package_id}"), |conn| {
+            diesel::insert_into(event_schemas::table)
+                .values(&schemas)
+                .on_conflict_do_nothing()
+                .execute(conn)
+        });
+        schemas
+            .into_iter()
+            .find(|schema| schema.module == module && schema.name == name)
+            .ok_or_else(|| {
+                IndexerError::InvalidArgumentError(format!(
+                    "{event_type} is not an event type of package {package_id}"
+                ))
+            })?
+            .try_into()
+    }
+
+    fn get_type_layout(&self, struct_tag: StructTag) -> Result<MoveStructLayout, IndexerError> {
+        if let Some(layout) = self.type_layouts.lock().unwrap().cache_get(&struct_tag) {
+            return Ok(layout.clone());
+        }
+        let layout = MoveObject::get_layout_from_struct_tag(
+            struct_tag.clone(),
+            ObjectFormatOptions::with_types(),
+            &self.module_cache,
+        )
+        .map_err(|e| {
+            IndexerError::InvalidArgumentError(format!(
+                "Failed resolving layout of {struct_tag} with error: {e}"
+            ))
+        })?;
+        self.type_layouts
+            .lock()
+            .unwrap()
+            .cache_set(struct_tag, layout.clone());
+        Ok(layout)
+    }
+
+    fn search(&self, query: &str) -> Result<Vec<SearchResult>, IndexerError> {
+        let mut results = vec![];
+        for interpretation in SearchQuery::parse(query) {
+            match interpretation {
+                SearchQuery::CheckpointSequenceNumber(sequence_number) => {
+                    let checkpoint: Option<i64> =
+                        read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
+                            checkpoints::table
+                                .select(checkpoints::sequence_number)
+                                .filter(checkpoints::sequence_number.eq(sequence_number as i64))
+                                .first::<i64>(conn)
+                                .optional()
+                        })
+                        .context(&format!("Failed searching checkpoint {sequence_number}"))?;
+                    results.extend(checkpoint.map(|c| SearchResult::Checkpoint(c as u64)));
+                }
+                SearchQuery::Digest(digest) => {
+                    let digest = TransactionDigest::new(digest);
+                    let (transaction, checkpoint): (Option<i64>, Option<i64>) =
+                        read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
+                            let transaction = transactions::table
+                                .select(transactions::id)
+                                .filter(transactions::transaction_digest.eq(digest.base58_encode()))
+                                .first::<i64>(conn)
+                                .optional()?;
+                            let checkpoint = checkpoints::table
+                                .select(checkpoints::sequence_number)
+                                .filter(checkpoints::checkpoint_digest.eq(digest.base58_encode()))
+                                .first::<i64>(conn)
+                                .optional()?;
+                            Ok::<_, diesel::result::Error>((transaction, checkpoint))
+                        })
+                        .context(&format!("Failed searching digest {digest}"))?;
+                    if transaction.is_some() {
+                        results.push(SearchResult::Transaction(digest));
+                    }
+                    results.extend(checkpoint.map(|c| SearchResult::Checkpoint(c as u64)));
+                }
+                SearchQuery::Id(id) => results.extend(self.search_id(id)?),
+                SearchQuery::MoveName {
+                    package,
+                    module,
+                    name,
+                } => results.extend(self.search_move_name(package, module, name)?),
+            }
+        }
+        Ok(results)
+    }
+
+    fn search_id(&self, id: ObjectID) -> Result<Vec<SearchResult>, IndexerError> {
+        let (package, object, address): (Option<String>, Option<String>, Option<String>) =
+            read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
+                let package = packages::table
+                    .select(packages::package_id)
+                    .filter(packages::package_id.eq(id.to_string()))
+                    .first::<String>(conn)
+                    .optional()?;
+                let object = objects::table
+                    .select(objects::object_id)
+                    .filter(objects::object_id.eq(id.to_string()))
+                    .first::<String>(conn)
+                    .optional()?;
+                let address = addresses::table
+                    .select(addresses::account_address)
+                    .filter(addresses::account_address.eq(id.to_string()))
+                    .first::<String>(conn)
+                    .optional()?;
+                Ok::<_, diesel::result::Error>((package, object, address))
+            })
+            .context(&format!("Failed searching id {id}"))?;
+
+        let mut results = vec![];
+        // packages are objects as well, only the more specific match is returned
+        if package.is_some() {
+            results.push(SearchResult::Package(id));
+        } else if object.is_some() {
+            results.push(SearchResult::Object(id));
+        }
+        if address.is_some() {
+            results.push(SearchResult::Address(id.into()));
+        }
+        Ok(results)
+    }
+
+    fn search_move_name(
+        &self,
+        package_id: ObjectID,
+        module: Identifier,
+        name: Option<Identifier>,
+    ) -> Result<Vec<SearchResult>, IndexerError> {
+        let package: Option<Package> =
+            read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
+                packages::dsl::packages
+                    .filter(packages::package_id.eq(package_id.to_string()))
+                    .order_by(packages::version.desc())
+                    .first::<Package>(conn)
+                    .optional()
+            })
+            .context(&format!("Failed searching package {package_id}"))?;
+        let bytes = match package.and_then(|package| {
+            package
+                .data
+                .into_iter()
+                .find(|m| m.0 == module.as_str())
+                .map(|m| m.1)
+        }) {
+            Some(bytes) => bytes,
+            None => return Ok(vec![]),
+        };
+        let name = match name {
+            Some(name) => name,
+            None => {
+                return Ok(vec![SearchResult::Module {
+                    package: package_id,
+                    module,
+                }])
+            }
+        };
+
+        let compiled = CompiledModule::deserialize_with_defaults(&bytes).map_err(|e| {
+            IndexerError::ModuleDeserializationError(format!(
+                "Failed deserializing module {package_id}::{module} with error: {e:?}"
+            ))
+        })?;
+        let is_struct = compiled.struct_defs().iter().any(|def| {
+            compiled.identifier_at(compiled.struct_handle_at(def.struct_handle).name)
+                == name.as_ident_str()
+        });
+        let is_function = compiled.function_defs().iter().any(|def| {
+            compiled.identifier_at(compiled.function_handle_at(def.function).name)
+                == name.as_ident_str()
+        });
+        Ok(if is_struct {
+            vec![SearchResult::Struct {
+                package: package_id,
+                module,
+                name,
+            }]
+        } else if is_function {
+            vec![SearchResult::Function {
+                package: package_id,
+                module,
+                name,
+            }]
+        } else {
+            vec![]
+        })
+    }
+
+    fn get_validator_epoch_events(
+        &self,
+        validator: SuiAddress,
+        cursor: Option<EpochId>,
+        limit: usize,
+        descending_order: bool,
+    ) -> Result<Vec<ValidatorEpochEvent>, IndexerError> {
+        let mut query = validator_epoch_events::dsl::validator_epoch_events
+            .filter(validator_epoch_events::validator_address.eq(validator.to_string()))
+            .into_boxed();
+        if descending_order {
+            if let Some(cursor) = cursor {
+                query = query.filter(validator_epoch_events::epoch.lt(cursor as i64));
+            }
+            query = query.order_by(validator_epoch_events::epoch.desc());
+        } else {
+            if let Some(cursor) = cursor {
+                query = query.filter(validator_epoch_events::epoch.gt(cursor as i64));
+            }
+            query = query.order_by(validator_epoch_events::epoch.asc());
+        }
+        let events: Vec<DBValidatorEpochEvent> =
+            read_only_blocking!(&self.read_cp, self.query_timeout, |conn| query
+                .limit(limit as i64)
+                .load(conn))
+            .context(&format!(
+                "Failed reading epoch events of validator {validator}"
+            ))?;
+        events.into_iter().map(|e| e.try_into()).collect()
+    }
+
+    // Validators are indexed in the committee of an epoch in the order of their protocol
+    // public keys, see Committee::new.
+    fn get_validator_participation(
+        &self,
+        epoch: EpochId,
+        cursor: Option<u64>,
+        limit: usize,
+    ) -> Result<Vec<EpochValidatorParticipation>, IndexerError> {
+        let (committee, signed, epoch_checkpoints) =
+            read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
+                let committee: Vec<(String, String)> = validators::table
+                    .select((validators::sui_address, validators::name))
+                    .filter(validators::epoch.eq(epoch as i64))
+                    .order_by(validators::protocol_pubkey_bytes.asc())
+                    .load(conn)?;
+                let signed: Vec<(i64, i64)> = validator_participation::table
+                    .select((
+                        validator_participation::committee_index,
+                        validator_participation::checkpoints_signed,
+                    ))
+                    .filter(validator_participation::epoch.eq(epoch as i64))
+                    .load(conn)?;
+                let epoch_checkpoints: i64 = checkpoints::table
+                    .filter(checkpoints::epoch.eq(epoch as i64))
+                    .count()
+                    .get_result(conn)?;
+                Ok::<_, diesel::result::Error>((committee, signed, epoch_checkpoints))
+            })
+            .context(&format!(
+                "Failed reading validator participation of epoch {epoch}"
+            ))?;
+        let signed = signed.into_iter().collect::<HashMap<_, _>>();
+        // committee indices follow from the order of the whole committee, which is small
+        // enough to be paged in memory
+        committee
+            .into_iter()
+            .enumerate()
+            .skip(cursor.map_or(0, |cursor| cursor as usize + 1))
+            .take(limit)
+            .map(|(index, (sui_address, name))| -> Result<_, IndexerError> {
+                Ok(EpochValidatorParticipation {
+                    committee_index: index as u64,
+                    sui_address: SuiAddress::from_str(&sui_address)?,
+                    name,
+                    checkpoints_signed: signed.get(&(index as i64)).copied().unwrap_or(0) as u64,
+                    epoch_checkpoints: epoch_checkpoints as u64,
+                })
+            })
+            .collect()
     }
 
     /// address stats methods
@@ -1648,7 +4690,7 @@ impl PgIndexerStore {
     }
 
     fn persist_address_stats(&self, addr_stats: &AddressStats) -> Result<(), IndexerError> {
-        transactional_blocking!(&self.blocking_cp, |conn| {
+        fenced_transactional_blocking!(self, |conn| {
             diesel::insert_into(address_stats::dsl::address_stats)
                 .values(addr_stats)
                 .execute(conn)
@@ -1658,7 +4700,7 @@ impl PgIndexerStore {
     }
 
     fn get_latest_address_stats(&self) -> Result<AddressStats, IndexerError> {
-        read_only_blocking!(&self.blocking_cp, |conn| {
+        read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
             address_stats::dsl::address_stats
                 .order_by(address_stats::checkpoint.desc())
                 .first(conn)
@@ -1666,8 +4708,21 @@ impl PgIndexerStore {
         .context("Failed reading latest address stats from PostgresDB")
     }
 
+    fn get_address_activity(
+        &self,
+        address: SuiAddress,
+    ) -> Result<Option<AddressActivity>, IndexerError> {
+        read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
+            addresses::table
+                .filter(addresses::account_address.eq(address.to_string()))
+                .first::<AddressActivity>(conn)
+                .optional()
+        })
+        .context(&format!("Failed reading activity of address {address}"))
+    }
+
     fn get_checkpoint_address_stats(&self, checkpoint: i64) -> Result<AddressStats, IndexerError> {
-        read_only_blocking!(&self.blocking_cp, |conn| {
+        read_only_blocking!(&self.read_cp, self.query_timeout, |conn| {
             address_stats::dsl::address_stats
                 .filter(address_stats::checkpoint.eq(checkpoint))
                 .order_by(address_stats::epoch.asc())
@@ -1702,11 +4757,12 @@ impl PgIndexerStore {
               if is_descending { "DESC" } else { "ASC" },
         );
 
-        let db_addr_stats = read_only_blocking!(&self.blocking_cp, |conn| diesel::sql_query(
-            epoch_addr_stats_query
-        )
-        .load::<DBAddressStats>(conn))
-        .context("Failed reading all epoch address stats from PostgresDB")?;
+        let db_addr_stats =
+            read_only_blocking!(&self.read_cp, self.query_timeout, |conn| diesel::sql_query(
+                epoch_addr_stats_query
+            )
+            .load::<DBAddressStats>(conn))
+            .context("Failed reading all epoch address stats from PostgresDB")?;
         Ok(db_addr_stats
             .into_iter()
             .map(|db_addr_stats| db_addr_stats.into())
@@ -1775,7 +4831,7 @@ impl PgIndexerStore {
         &self,
         checkpoint_metrics: &CheckpointMetrics,
     ) -> Result<(), IndexerError> {
-        transactional_blocking!(&self.blocking_cp, |conn| {
+        fenced_transactional_blocking!(self, |conn| {
             diesel::insert_into(checkpoint_metrics::dsl::checkpoint_metrics)
                 .values(checkpoint_metrics)
                 .execute(conn)
@@ -1886,28 +4942,89 @@ impl PgIndexerStore {
             .map_err(Into::into)
             .and_then(std::convert::identity)
     }
+
+    // `spawn_blocking` of a read of the RPC APIs, recorded in the slow query log if any.
+    // Use through `spawn_read!`, which formats the parameters only if the log is enabled.
+    async fn spawn_read<F, R>(
+        &self,
+        method: &'static str,
+        params: Option<String>,
+        f: F,
+    ) -> Result<R, IndexerError>
+    where
+        F: FnOnce(Self) -> Result<R, IndexerError> + Send + 'static,
+        R: Send + 'static,
+    {
+        let start = Instant::now();
+        let result = self.spawn_blocking(f).await;
+        if let (Some(slow_query_log), Some(params)) = (&self.slow_query_log, params) {
+            slow_query_log.record(method, &params, start.elapsed());
+        }
+        result
+    }
+}
+
+macro_rules! spawn_read {
+    ($store:expr, $method:ident($($arg:expr),*)) => {{
+        let params = $store.slow_query_log.as_ref().map(|_| {
+            let params: Vec<String> = vec![$(format!("{:?}", $arg)),*];
+            params.join(", ")
+        });
+        $store.spawn_read(stringify!($method), params, move |this| this.$method($($arg),*))
+    }};
 }
 
 #[async_trait]
 impl IndexerStore for PgIndexerStore {
-    type ModuleCache = SyncModuleCache<IndexerModuleResolver>;
+    type ModuleCache = IndexerModuleCache;
 
     async fn get_latest_tx_checkpoint_sequence_number(&self) -> Result<i64, IndexerError> {
         self.spawn_blocking(|this| this.get_latest_tx_checkpoint_sequence_number())
             .await
     }
 
+    async fn reader_has_write_privileges(&self) -> Result<bool, IndexerError> {
+        self.spawn_blocking(|this| this.reader_has_write_privileges())
+            .await
+    }
+
     async fn get_latest_object_checkpoint_sequence_number(&self) -> Result<i64, IndexerError> {
         self.spawn_blocking(|this| this.get_latest_object_checkpoint_sequence_number())
             .await
     }
 
+    async fn get_latest_epoch_id(&self) -> Result<i64, IndexerError> {
+        self.spawn_blocking(|this| this.get_latest_epoch_id()).await
+    }
+
+    async fn wait_for_checkpoint(
+        &self,
+        sequence_number: i64,
+        timeout: Duration,
+    ) -> Result<(), IndexerError> {
+        let wait = async {
+            loop {
+                // registered before checking, so that a commit in between is not missed
+                let committed = self.checkpoint_committed.notified();
+                if self
+                    .spawn_blocking(move |this| this.is_checkpoint_committed(sequence_number))
+                    .await?
+                {
+                    return Ok::<_, IndexerError>(());
+                }
+                let _ = tokio::time::timeout(CHECKPOINT_WAIT_POLL_INTERVAL, committed).await;
+            }
+        };
+        tokio::time::timeout(timeout, wait)
+            .await
+            .map_err(|_| IndexerError::CheckpointWaitTimeout(sequence_number, timeout))?
+    }
+
     async fn get_checkpoint(
         &self,
         id: CheckpointId,
     ) -> Result<sui_json_rpc_types::Checkpoint, IndexerError> {
-        self.spawn_blocking(move |this| this.get_checkpoint(id))
-            .await
+        spawn_read!(self, get_checkpoint(id)).await
     }
 
     async fn get_checkpoints(
@@ -1915,8 +5032,15 @@ impl IndexerStore for PgIndexerStore {
         cursor: Option<CheckpointId>,
         limit: usize,
     ) -> Result<Vec<sui_json_rpc_types::Checkpoint>, IndexerError> {
-        self.spawn_blocking(move |this| this.get_checkpoints(cursor, limit))
-            .await
+        spawn_read!(self, get_checkpoints(cursor, limit)).await
+    }
+
+    async fn get_checkpoint_at_timestamp(
+        &self,
+        timestamp_ms: u64,
+        rounding: TimestampRounding,
+    ) -> Result<Option<CheckpointSequenceNumber>, IndexerError> {
+        spawn_read!(self, get_checkpoint_at_timestamp(timestamp_ms, rounding)).await
     }
 
     async fn get_indexer_checkpoint(&self) -> Result<Checkpoint, IndexerError> {
@@ -1933,64 +5057,207 @@ impl IndexerStore for PgIndexerStore {
             .await
     }
 
+    async fn get_checkpoint_discontinuities(
+        &self,
+        start: i64,
+        end: i64,
+    ) -> Result<Vec<CheckpointDiscontinuity>, IndexerError> {
+        self.spawn_blocking(move |this| this.get_checkpoint_discontinuities(start, end))
+            .await
+    }
+
     async fn get_checkpoint_sequence_number(
         &self,
         digest: CheckpointDigest,
     ) -> Result<CheckpointSequenceNumber, IndexerError> {
-        self.spawn_blocking(move |this| this.get_checkpoint_sequence_number(digest))
-            .await
+        spawn_read!(self, get_checkpoint_sequence_number(digest)).await
     }
 
     async fn get_event(&self, id: EventID) -> Result<Event, IndexerError> {
-        self.spawn_blocking(move |this| this.get_event(id)).await
+        spawn_read!(self, get_event(id)).await
+    }
+
+    async fn get_events(
+        &self,
+        query: EventFilter,
+        cursor: Option<EventID>,
+        limit: Option<usize>,
+        descending_order: bool,
+    ) -> Result<EventPage, IndexerError> {
+        spawn_read!(self, get_events(query, cursor, limit, descending_order)).await
+    }
+
+    async fn query_events(
+        &self,
+        filter: query::EventFilter,
+        cursor: Option<EventID>,
+        limit: Option<usize>,
+        descending_order: bool,
+    ) -> Result<EventPage, IndexerError> {
+        spawn_read!(self, query_events(filter, cursor, limit, descending_order)).await
+    }
+
+    async fn get_events_by_sequence(
+        &self,
+        query: EventFilter,
+        cursor: Option<EventSequence>,
+        limit: Option<usize>,
+        descending_order: bool,
+    ) -> Result<SequencedEventPage, IndexerError> {
+        spawn_read!(
+            self,
+            get_events_by_sequence(query, cursor, limit, descending_order)
+        )
+        .await
+    }
+
+    async fn query_events_in_time_range(
+        &self,
+        start_ms: u64,
+        end_ms: u64,
+        cursor: Option<EventID>,
+        limit: Option<usize>,
+        descending_order: bool,
+    ) -> Result<EventPage, IndexerError> {
+        self.spawn_blocking(move |this| {
+            this.query_events_in_time_range(start_ms, end_ms, cursor, limit, descending_order)
+        })
+        .await
+    }
+
+    async fn get_object(
+        &self,
+        object_id: ObjectID,
+        version: Option<SequenceNumber>,
+    ) -> Result<ObjectRead, IndexerError> {
+        spawn_read!(self, get_object(object_id, version)).await
+    }
+
+    async fn render_display(
+        &self,
+        object_id: ObjectID,
+    ) -> Result<DisplayFieldsResponse, IndexerError> {
+        spawn_read!(self, render_display(object_id)).await
+    }
+
+    async fn sample_objects(&self, count: usize) -> Result<Vec<Object>, IndexerError> {
+        spawn_read!(self, sample_objects(count)).await
+    }
+
+    async fn get_object_before_version(
+        &self,
+        object_id: ObjectID,
+        checkpoint: CheckpointSequenceNumber,
+        before_version: SequenceNumber,
+    ) -> Result<Option<sui_types::object::Object>, IndexerError> {
+        self.spawn_blocking(move |this| {
+            this.get_object_before_version(object_id, checkpoint, before_version)
+        })
+        .await
+    }
+
+    async fn query_objects_history(
+        &self,
+        filter: SuiObjectDataFilter,
+        at_checkpoint: CheckpointSequenceNumber,
+        cursor: Option<ObjectID>,
+        limit: usize,
+    ) -> Result<Vec<ObjectRead>, IndexerError> {
+        spawn_read!(
+            self,
+            query_objects_history(filter, at_checkpoint, cursor, limit)
+        )
+        .await
+    }
+
+    async fn query_latest_objects(
+        &self,
+        filter: SuiObjectDataFilter,
+        cursor: Option<ObjectID>,
+        limit: usize,
+    ) -> Result<Vec<ObjectRead>, IndexerError> {
+        spawn_read!(self, query_latest_objects(filter, cursor, limit)).await
+    }
+
+    async fn query_objects(
+        &self,
+        filter: query::ObjectFilter,
+        cursor: Option<ObjectID>,
+        limit: usize,
+    ) -> Result<Vec<ObjectRead>, IndexerError> {
+        spawn_read!(self, query_objects(filter, cursor, limit)).await
+    }
+
+    async fn query_objects_by_type(
+        &self,
+        type_query: String,
+        cursor: Option<ObjectID>,
+        limit: usize,
+    ) -> Result<Vec<ObjectRead>, IndexerError> {
+        spawn_read!(self, query_objects_by_type(type_query, cursor, limit)).await
+    }
+
+    async fn get_portfolio(
+        &self,
+        owner: SuiAddress,
+    ) -> Result<Vec<CoinPortfolioEntry>, IndexerError> {
+        spawn_read!(self, get_portfolio(owner)).await
+    }
+
+    async fn execute_readonly_sql(&self, sql: String) -> Result<ReadonlySqlResult, IndexerError> {
+        // held until the query is done, queries beyond the cap are rejected
+        let _permit = self
+            .readonly_sql
+            .as_ref()
+            .map(ReadonlySql::try_acquire)
+            .transpose()?;
+        spawn_read!(self, execute_readonly_sql(&sql)).await
+    }
+
+    async fn get_total_transaction_number_from_checkpoints(&self) -> Result<i64, IndexerError> {
+        spawn_read!(self, get_total_transaction_number_from_checkpoints()).await
     }
 
-    async fn get_events(
+    async fn get_transaction_inclusion(
         &self,
-        query: EventFilter,
-        cursor: Option<EventID>,
-        limit: Option<usize>,
-        descending_order: bool,
-    ) -> Result<EventPage, IndexerError> {
-        self.spawn_blocking(move |this| this.get_events(query, cursor, limit, descending_order))
-            .await
+        tx_digest: TransactionDigest,
+    ) -> Result<Option<TransactionInclusion>, IndexerError> {
+        spawn_read!(self, get_transaction_inclusion(tx_digest)).await
     }
 
-    async fn get_object(
+    async fn get_transaction_proof(
         &self,
-        object_id: ObjectID,
-        version: Option<SequenceNumber>,
-    ) -> Result<ObjectRead, IndexerError> {
-        self.spawn_blocking(move |this| this.get_object(object_id, version))
-            .await
+        tx_digest: TransactionDigest,
+    ) -> Result<Option<TransactionProof>, IndexerError> {
+        spawn_read!(self, get_transaction_proof(tx_digest)).await
     }
 
-    async fn query_objects_history(
+    async fn get_reexecution_sample(
         &self,
-        filter: SuiObjectDataFilter,
-        at_checkpoint: CheckpointSequenceNumber,
-        cursor: Option<ObjectID>,
+        after_id: Option<i64>,
+        sample_interval: u64,
         limit: usize,
-    ) -> Result<Vec<ObjectRead>, IndexerError> {
+    ) -> Result<Vec<Transaction>, IndexerError> {
         self.spawn_blocking(move |this| {
-            this.query_objects_history(filter, at_checkpoint, cursor, limit)
+            this.get_reexecution_sample(after_id, sample_interval, limit)
         })
         .await
     }
 
-    async fn query_latest_objects(
+    async fn persist_reexecution_mismatch(
         &self,
-        filter: SuiObjectDataFilter,
-        cursor: Option<ObjectID>,
-        limit: usize,
-    ) -> Result<Vec<ObjectRead>, IndexerError> {
-        self.spawn_blocking(move |this| this.query_latest_objects(filter, cursor, limit))
+        mismatch: ReexecutionMismatch,
+    ) -> Result<(), IndexerError> {
+        self.spawn_blocking(move |this| this.persist_reexecution_mismatch(mismatch))
             .await
     }
 
-    async fn get_total_transaction_number_from_checkpoints(&self) -> Result<i64, IndexerError> {
-        self.spawn_blocking(move |this| this.get_total_transaction_number_from_checkpoints())
-            .await
+    async fn get_transaction_for_object_version(
+        &self,
+        object_id: ObjectID,
+        version: SequenceNumber,
+    ) -> Result<Option<Transaction>, IndexerError> {
+        spawn_read!(self, get_transaction_for_object_version(object_id, version)).await
     }
 
     async fn get_transaction_by_digest(
@@ -1998,8 +5265,16 @@ impl IndexerStore for PgIndexerStore {
         tx_digest: &str,
     ) -> Result<Transaction, IndexerError> {
         let tx_digest = tx_digest.to_owned();
-        self.spawn_blocking(move |this| this.get_transaction_by_digest(&tx_digest))
-            .await
+        spawn_read!(self, get_transaction_by_digest(&tx_digest)).await
+    }
+
+    async fn find_transactions_by_digest_prefix(
+        &self,
+        prefix: &str,
+        limit: usize,
+    ) -> Result<Vec<Transaction>, IndexerError> {
+        let prefix = prefix.to_owned();
+        spawn_read!(self, find_transactions_by_digest_prefix(&prefix, limit)).await
     }
 
     async fn multi_get_transactions_by_digests(
@@ -2007,8 +5282,7 @@ impl IndexerStore for PgIndexerStore {
         tx_digests: &[String],
     ) -> Result<Vec<Transaction>, IndexerError> {
         let tx_digests = tx_digests.to_owned();
-        self.spawn_blocking(move |this| this.multi_get_transactions_by_digests(&tx_digests))
-            .await
+        spawn_read!(self, multi_get_transactions_by_digests(&tx_digests)).await
     }
 
     async fn compose_sui_transaction_block_response(
@@ -2029,9 +5303,24 @@ impl IndexerStore for PgIndexerStore {
         limit: usize,
         is_descending: bool,
     ) -> Result<Vec<Transaction>, IndexerError> {
-        self.spawn_blocking(move |this| {
-            this.get_all_transaction_page(start_sequence, limit, is_descending)
-        })
+        spawn_read!(
+            self,
+            get_all_transaction_page(start_sequence, limit, is_descending)
+        )
+        .await
+    }
+
+    async fn query_transactions(
+        &self,
+        filter: query::TransactionFilter,
+        start_sequence: Option<i64>,
+        limit: usize,
+        is_descending: bool,
+    ) -> Result<Vec<Transaction>, IndexerError> {
+        spawn_read!(
+            self,
+            query_transactions(filter, start_sequence, limit, is_descending)
+        )
         .await
     }
 
@@ -2042,14 +5331,36 @@ impl IndexerStore for PgIndexerStore {
         limit: usize,
         is_descending: bool,
     ) -> Result<Vec<Transaction>, IndexerError> {
-        self.spawn_blocking(move |this| {
-            this.get_transaction_page_by_checkpoint(
+        spawn_read!(
+            self,
+            get_transaction_page_by_checkpoint(
                 checkpoint_sequence_number,
                 start_sequence,
                 limit,
-                is_descending,
+                is_descending
             )
-        })
+        )
+        .await
+    }
+
+    async fn query_transactions_in_time_range(
+        &self,
+        start_ms: u64,
+        end_ms: u64,
+        start_sequence: Option<i64>,
+        limit: usize,
+        is_descending: bool,
+    ) -> Result<Vec<Transaction>, IndexerError> {
+        spawn_read!(
+            self,
+            query_transactions_in_time_range(
+                start_ms,
+                end_ms,
+                start_sequence,
+                limit,
+                is_descending
+            )
+        )
         .await
     }
 
@@ -2060,14 +5371,15 @@ impl IndexerStore for PgIndexerStore {
         limit: usize,
         is_descending: bool,
     ) -> Result<Vec<Transaction>, IndexerError> {
-        self.spawn_blocking(move |this| {
-            this.get_transaction_page_by_transaction_kinds(
+        spawn_read!(
+            self,
+            get_transaction_page_by_transaction_kinds(
                 kind_names,
                 start_sequence,
                 limit,
-                is_descending,
+                is_descending
             )
-        })
+        )
         .await
     }
 
@@ -2078,14 +5390,15 @@ impl IndexerStore for PgIndexerStore {
         limit: usize,
         is_descending: bool,
     ) -> Result<Vec<Transaction>, IndexerError> {
-        self.spawn_blocking(move |this| {
-            this.get_transaction_page_by_sender_address(
+        spawn_read!(
+            self,
+            get_transaction_page_by_sender_address(
                 sender_address,
                 start_sequence,
                 limit,
-                is_descending,
+                is_descending
             )
-        })
+        )
         .await
     }
 
@@ -2097,15 +5410,16 @@ impl IndexerStore for PgIndexerStore {
         limit: usize,
         is_descending: bool,
     ) -> Result<Vec<Transaction>, IndexerError> {
-        self.spawn_blocking(move |this| {
-            this.get_transaction_page_by_recipient_address(
+        spawn_read!(
+            self,
+            get_transaction_page_by_recipient_address(
                 sender_address,
                 recipient_address,
                 start_sequence,
                 limit,
-                is_descending,
+                is_descending
             )
-        })
+        )
         .await
     }
 
@@ -2116,9 +5430,105 @@ impl IndexerStore for PgIndexerStore {
         limit: usize,
         is_descending: bool,
     ) -> Result<Vec<Transaction>, IndexerError> {
-        self.spawn_blocking(move |this| {
-            this.get_transaction_page_by_address(address, start_sequence, limit, is_descending)
-        })
+        spawn_read!(
+            self,
+            get_transaction_page_by_address(address, start_sequence, limit, is_descending)
+        )
+        .await
+    }
+
+    async fn get_transactions_by_gas_object(
+        &self,
+        object_id: ObjectID,
+        start_sequence: Option<i64>,
+        limit: usize,
+        is_descending: bool,
+    ) -> Result<Vec<Transaction>, IndexerError> {
+        spawn_read!(
+            self,
+            get_transactions_by_gas_object(object_id, start_sequence, limit, is_descending)
+        )
+        .await
+    }
+
+    async fn get_transactions_by_gas_owner(
+        &self,
+        gas_owner: SuiAddress,
+        sponsored_only: bool,
+        start_sequence: Option<i64>,
+        limit: usize,
+        is_descending: bool,
+    ) -> Result<Vec<Transaction>, IndexerError> {
+        spawn_read!(
+            self,
+            get_transactions_by_gas_owner(
+                gas_owner,
+                sponsored_only,
+                start_sequence,
+                limit,
+                is_descending
+            )
+        )
+        .await
+    }
+
+    async fn get_sponsored_transactions(
+        &self,
+        sponsor: SuiAddress,
+        start_ms: u64,
+        end_ms: u64,
+        start_sequence: Option<i64>,
+        limit: usize,
+        is_descending: bool,
+    ) -> Result<Vec<Transaction>, IndexerError> {
+        spawn_read!(
+            self,
+            get_sponsored_transactions(
+                sponsor,
+                start_ms,
+                end_ms,
+                start_sequence,
+                limit,
+                is_descending
+            )
+        )
+        .await
+    }
+
+    async fn get_sponsor_gas_spend(
+        &self,
+        sponsor: SuiAddress,
+        cursor: Option<EpochId>,
+        limit: usize,
+        is_descending: bool,
+    ) -> Result<Vec<SponsorGasSpend>, IndexerError> {
+        spawn_read!(
+            self,
+            get_sponsor_gas_spend(sponsor, cursor, limit, is_descending)
+        )
+        .await
+    }
+
+    async fn get_failed_transactions_by_abort(
+        &self,
+        package: ObjectID,
+        module: String,
+        code: Option<u64>,
+        start_sequence: Option<i64>,
+        limit: usize,
+        is_descending: bool,
+    ) -> Result<Vec<AbortedTransaction>, IndexerError> {
+        spawn_read!(
+            self,
+            get_failed_transactions_by_abort(
+                package,
+                module,
+                code,
+                start_sequence,
+                limit,
+                is_descending
+            )
+        )
         .await
     }
 
@@ -2130,15 +5540,16 @@ impl IndexerStore for PgIndexerStore {
         limit: usize,
         is_descending: bool,
     ) -> Result<Vec<Transaction>, IndexerError> {
-        self.spawn_blocking(move |this| {
-            this.get_transaction_page_by_input_object(
+        spawn_read!(
+            self,
+            get_transaction_page_by_input_object(
                 object_id,
                 version,
                 start_sequence,
                 limit,
-                is_descending,
+                is_descending
             )
-        })
+        )
         .await
     }
 
@@ -2150,15 +5561,16 @@ impl IndexerStore for PgIndexerStore {
         limit: usize,
         is_descending: bool,
     ) -> Result<Vec<Transaction>, IndexerError> {
-        self.spawn_blocking(move |this| {
-            this.get_transaction_page_by_changed_object(
+        spawn_read!(
+            self,
+            get_transaction_page_by_changed_object(
                 object_id,
                 version,
                 start_sequence,
                 limit,
-                is_descending,
+                is_descending
             )
-        })
+        )
         .await
     }
 
@@ -2171,16 +5583,17 @@ impl IndexerStore for PgIndexerStore {
         limit: usize,
         is_descending: bool,
     ) -> Result<Vec<Transaction>, IndexerError> {
-        self.spawn_blocking(move |this| {
-            this.get_transaction_page_by_move_call(
+        spawn_read!(
+            self,
+            get_transaction_page_by_move_call(
                 package,
                 module,
                 function,
                 start_sequence,
                 limit,
-                is_descending,
+                is_descending
             )
-        })
+        )
         .await
     }
 
@@ -2189,9 +5602,10 @@ impl IndexerStore for PgIndexerStore {
         tx_digest: Option<String>,
         is_descending: bool,
     ) -> Result<Option<i64>, IndexerError> {
-        self.spawn_blocking(move |this| {
-            this.get_transaction_sequence_by_digest(tx_digest, is_descending)
-        })
+        spawn_read!(
+            self,
+            get_transaction_sequence_by_digest(tx_digest, is_descending)
+        )
         .await
     }
 
@@ -2200,9 +5614,10 @@ impl IndexerStore for PgIndexerStore {
         tx_digest: Option<String>,
         is_descending: bool,
     ) -> Result<Option<i64>, IndexerError> {
-        self.spawn_blocking(move |this| {
-            this.get_move_call_sequence_by_digest(tx_digest, is_descending)
-        })
+        spawn_read!(
+            self,
+            get_move_call_sequence_by_digest(tx_digest, is_descending)
+        )
         .await
     }
 
@@ -2211,9 +5626,10 @@ impl IndexerStore for PgIndexerStore {
         tx_digest: Option<String>,
         is_descending: bool,
     ) -> Result<Option<i64>, IndexerError> {
-        self.spawn_blocking(move |this| {
-            this.get_input_object_sequence_by_digest(tx_digest, is_descending)
-        })
+        spawn_read!(
+            self,
+            get_input_object_sequence_by_digest(tx_digest, is_descending)
+        )
         .await
     }
 
@@ -2222,9 +5638,10 @@ impl IndexerStore for PgIndexerStore {
         tx_digest: Option<String>,
         is_descending: bool,
     ) -> Result<Option<i64>, IndexerError> {
-        self.spawn_blocking(move |this| {
-            this.get_changed_object_sequence_by_digest(tx_digest, is_descending)
-        })
+        spawn_read!(
+            self,
+            get_changed_object_sequence_by_digest(tx_digest, is_descending)
+        )
         .await
     }
 
@@ -2233,34 +5650,74 @@ impl IndexerStore for PgIndexerStore {
         tx_digest: Option<String>,
         is_descending: bool,
     ) -> Result<Option<i64>, IndexerError> {
-        self.spawn_blocking(move |this| {
-            this.get_recipient_sequence_by_digest(tx_digest, is_descending)
-        })
+        spawn_read!(
+            self,
+            get_recipient_sequence_by_digest(tx_digest, is_descending)
+        )
         .await
     }
 
     async fn get_network_metrics(&self) -> Result<NetworkMetrics, IndexerError> {
-        self.spawn_blocking(move |this| this.get_network_metrics())
-            .await
+        spawn_read!(self, get_network_metrics()).await
+    }
+
+    async fn get_network_totals(&self) -> Result<NetworkTotals, IndexerError> {
+        spawn_read!(self, get_network_totals()).await
     }
 
     async fn get_move_call_metrics(&self) -> Result<MoveCallMetrics, IndexerError> {
-        self.spawn_blocking(move |this| this.get_move_call_metrics())
-            .await
+        spawn_read!(self, get_move_call_metrics()).await
     }
 
     async fn persist_checkpoint_transactions(
         &self,
         checkpoints: &[Checkpoint],
         transactions: &[Transaction],
+        transactions_json: &[TransactionJson],
+        checkpoint_tx_digests: &[CheckpointTxDigest],
+        hot_entities: &[CheckpointHotEntities],
+        address_activity: &[CheckpointAddressActivity],
+        signers: &[CheckpointSigners],
+        proofs: &[CheckpointProof],
         counter_committed_tx: IntCounter,
     ) -> Result<(), IndexerError> {
         let checkpoints = checkpoints.to_owned();
         let transactions = transactions.to_owned();
-        self.spawn_blocking(move |this| {
-            this.persist_checkpoint_transactions(&checkpoints, &transactions, counter_committed_tx)
-        })
-        .await
+        let transactions_json = transactions_json.to_owned();
+        let checkpoint_tx_digests = checkpoint_tx_digests.to_owned();
+        let hot_entities = hot_entities.to_owned();
+        let address_activity = address_activity.to_owned();
+        let signers = signers.to_owned();
+        let proofs = proofs.to_owned();
+        let result = self
+            .spawn_blocking(move |this| {
+                let transactions = match this.payload_compression_level {
+                    Some(level) => transactions
+                        .into_iter()
+                        .map(|transaction| transaction.with_compressed_payload(level))
+                        .collect(),
+                    None => transactions,
+                };
+                this.persist_checkpoint_transactions(
+                    &checkpoints,
+                    &transactions,
+                    &transactions_json,
+                    &checkpoint_tx_digests,
+                    &hot_entities,
+                    &address_activity,
+                    &signers,
+                    &proofs,
+                    counter_committed_tx,
+                )
+            })
+            .await;
+        if let Some(cache) = &self.query_cache {
+            cache.latest_checkpoint.invalidate();
+        }
+        if result.is_ok() {
+            self.checkpoint_committed.notify_waiters();
+        }
+        result
     }
 
     async fn persist_object_changes(
@@ -2271,65 +5728,327 @@ impl IndexerStore for PgIndexerStore {
         object_commit_chunk_counter: IntCounter,
     ) -> Result<(), IndexerError> {
         let tx_object_changes = tx_object_changes.to_owned();
-        self.spawn_blocking(move |this| {
-            this.persist_object_changes(
-                &tx_object_changes,
-                object_mutation_latency,
-                object_deletion_latency,
-                object_commit_chunk_counter,
-            )
-        })
-        .await
+        let result = self
+            .spawn_blocking(move |this| {
+                this.persist_object_changes(
+                    &tx_object_changes,
+                    object_mutation_latency,
+                    object_deletion_latency,
+                    object_commit_chunk_counter,
+                )
+            })
+            .await;
+        if result.is_ok() {
+            self.checkpoint_committed.notify_waiters();
+        }
+        result
+    }
+
+    async fn persist_events(&self, events: &[Event]) -> Result<(), IndexerError> {
+        let events = events.to_owned();
+        self.spawn_blocking(move |this| this.persist_events(&events))
+            .await
+    }
+
+    async fn persist_commit_watermark(&self, sequence_number: i64) -> Result<(), IndexerError> {
+        let result = self
+            .spawn_blocking(move |this| this.persist_commit_watermark(sequence_number))
+            .await;
+        if result.is_ok() {
+            self.checkpoint_committed.notify_waiters();
+        }
+        result
+    }
+
+    async fn persist_addresses(
+        &self,
+        addresses: &[Address],
+        active_addresses: &[ActiveAddress],
+    ) -> Result<(), IndexerError> {
+        let addresses = addresses.to_owned();
+        let active_addresses = active_addresses.to_owned();
+        self.spawn_blocking(move |this| this.persist_addresses(&addresses, &active_addresses))
+            .await
+    }
+
+    async fn persist_packages(&self, packages: &[Package]) -> Result<(), IndexerError> {
+        let packages = packages.to_owned();
+        self.spawn_blocking(move |this| this.persist_packages(&packages))
+            .await
+    }
+
+    async fn persist_verified_source(&self, source: VerifiedSource) -> Result<(), IndexerError> {
+        self.spawn_blocking(move |this| this.persist_verified_source(source))
+            .await
+    }
+
+    async fn get_verified_source(
+        &self,
+        package: ObjectID,
+    ) -> Result<Option<VerifiedSource>, IndexerError> {
+        spawn_read!(self, get_verified_source(package)).await
+    }
+
+    async fn persist_watchlist_entry(&self, entry: WatchlistEntry) -> Result<(), IndexerError> {
+        self.spawn_blocking(move |this| this.persist_watchlist_entry(entry))
+            .await
+    }
+
+    async fn delete_watchlist_entry(&self, entry: WatchlistEntry) -> Result<(), IndexerError> {
+        self.spawn_blocking(move |this| this.delete_watchlist_entry(entry))
+            .await
+    }
+
+    async fn get_epoch_first_checkpoint(&self, epoch: i64) -> Result<Option<i64>, IndexerError> {
+        self.spawn_blocking(move |this| this.get_epoch_first_checkpoint(epoch))
+            .await
+    }
+
+    async fn get_epoch_last_checkpoint(&self, epoch: i64) -> Result<Option<i64>, IndexerError> {
+        self.spawn_blocking(move |this| this.get_epoch_last_checkpoint(epoch))
+            .await
+    }
+
+    async fn get_pruned_checkpoint(&self, table: PrunedTable) -> Result<Option<i64>, IndexerError> {
+        self.spawn_blocking(move |this| this.get_pruned_checkpoint(table))
+            .await
+    }
+
+    async fn prune_checkpoints(
+        &self,
+        table: PrunedTable,
+        start: i64,
+        end: i64,
+    ) -> Result<usize, IndexerError> {
+        self.spawn_blocking(move |this| this.prune_checkpoints(table, start, end))
+            .await
+    }
+
+    async fn get_watchlist_entries(&self) -> Result<Vec<WatchlistEntry>, IndexerError> {
+        self.spawn_blocking(|this| this.get_watchlist_entries())
+            .await
+    }
+
+    async fn get_watchlist(
+        &self,
+        cursor: Option<WatchlistEntry>,
+        limit: usize,
+    ) -> Result<Vec<WatchlistEntry>, IndexerError> {
+        spawn_read!(self, get_watchlist(cursor, limit)).await
+    }
+
+    async fn get_api_keys(&self) -> Result<Vec<ApiKey>, IndexerError> {
+        spawn_read!(self, get_api_keys()).await
+    }
+
+    async fn get_snapshot_bootstrap(&self) -> Result<Option<SnapshotBootstrap>, IndexerError> {
+        self.spawn_blocking(|this| this.get_snapshot_bootstrap())
+            .await
+    }
+
+    async fn persist_snapshot_bootstrap(
+        &self,
+        bootstrap: SnapshotBootstrap,
+    ) -> Result<(), IndexerError> {
+        self.spawn_blocking(move |this| this.persist_snapshot_bootstrap(bootstrap))
+            .await
+    }
+
+    async fn get_chain_identifier(&self) -> Result<Option<ChainIdentifier>, IndexerError> {
+        self.spawn_blocking(|this| this.get_chain_identifier())
+            .await
+    }
+
+    async fn persist_chain_identifier(
+        &self,
+        identifier: ChainIdentifier,
+    ) -> Result<(), IndexerError> {
+        self.spawn_blocking(move |this| this.persist_chain_identifier(identifier))
+            .await
     }
 
-    async fn persist_events(&self, events: &[Event]) -> Result<(), IndexerError> {
-        let events = events.to_owned();
-        self.spawn_blocking(move |this| this.persist_events(&events))
+    async fn enqueue_checkpoints(
+        &self,
+        checkpoints: Vec<QueuedCheckpoint>,
+    ) -> Result<usize, IndexerError> {
+        self.spawn_blocking(move |this| this.enqueue_checkpoints(checkpoints))
             .await
     }
 
-    async fn persist_addresses(
+    async fn get_queued_checkpoints(
         &self,
-        addresses: &[Address],
-        active_addresses: &[ActiveAddress],
-    ) -> Result<(), IndexerError> {
-        let addresses = addresses.to_owned();
-        let active_addresses = active_addresses.to_owned();
-        self.spawn_blocking(move |this| this.persist_addresses(&addresses, &active_addresses))
+        kind: QueueKind,
+        sequence_number: i64,
+        limit: usize,
+    ) -> Result<Vec<QueuedCheckpoint>, IndexerError> {
+        self.spawn_blocking(move |this| this.get_queued_checkpoints(kind, sequence_number, limit))
             .await
     }
 
-    async fn persist_packages(&self, packages: &[Package]) -> Result<(), IndexerError> {
-        let packages = packages.to_owned();
-        self.spawn_blocking(move |this| this.persist_packages(&packages))
+    async fn delete_queued_checkpoints(
+        &self,
+        kind: QueueKind,
+        sequence_number: i64,
+    ) -> Result<usize, IndexerError> {
+        self.spawn_blocking(move |this| this.delete_queued_checkpoints(kind, sequence_number))
+            .await
+    }
+
+    async fn get_committed_queue_sequence_number(
+        &self,
+        kind: QueueKind,
+    ) -> Result<i64, IndexerError> {
+        self.spawn_blocking(move |this| this.get_committed_queue_sequence_number(kind))
+            .await
+    }
+
+    async fn try_acquire_leader_lock(&self, name: String) -> Result<bool, IndexerError> {
+        self.spawn_blocking(move |this| this.try_acquire_leader_lock(&name))
             .await
     }
 
+    async fn holds_leader_lock(&self) -> Result<bool, IndexerError> {
+        self.spawn_blocking(|this| this.holds_leader_lock()).await
+    }
+
     async fn persist_transaction_index_tables(
         &self,
         input_objects: &[InputObject],
         changed_objects: &[ChangedObject],
         move_calls: &[MoveCall],
+        tx_commands: &[TxCommand],
+        tx_calls: &[TxCall],
         recipients: &[Recipient],
+        tx_dependencies: &[TxDependency],
+        transfers: &[Transfer],
+        ownership_transitions: &[OwnershipTransition],
     ) -> Result<(), IndexerError> {
         let input_objects = input_objects.to_owned();
         let changed_objects = changed_objects.to_owned();
         let move_calls = move_calls.to_owned();
+        let tx_commands = tx_commands.to_owned();
+        let tx_calls = tx_calls.to_owned();
         let recipients = recipients.to_owned();
+        let tx_dependencies = tx_dependencies.to_owned();
+        let transfers = transfers.to_owned();
+        let ownership_transitions = ownership_transitions.to_owned();
         self.spawn_blocking(move |this| {
             this.persist_transaction_index_tables(
                 &input_objects,
                 &changed_objects,
                 &move_calls,
+                &tx_commands,
+                &tx_calls,
                 &recipients,
+                &tx_dependencies,
+                &transfers,
+                &ownership_transitions,
             )
         })
         .await
     }
 
+    async fn get_ownership_history(
+        &self,
+        object_id: ObjectID,
+        cursor: Option<u64>,
+        limit: usize,
+        is_descending: bool,
+    ) -> Result<Vec<ObjectOwnershipTransition>, IndexerError> {
+        spawn_read!(
+            self,
+            get_ownership_history(object_id, cursor, limit, is_descending)
+        )
+        .await
+    }
+
+    async fn resolve_name(&self, name: String) -> Result<Option<SuiAddress>, IndexerError> {
+        spawn_read!(self, resolve_name(name)).await
+    }
+
+    async fn lookup_names(
+        &self,
+        address: SuiAddress,
+        cursor: Option<ObjectID>,
+        limit: usize,
+    ) -> Result<Page<String, ObjectID>, IndexerError> {
+        spawn_read!(self, lookup_names(address, cursor, limit)).await
+    }
+
+    async fn get_hot_objects(
+        &self,
+        window: HotWindow,
+        limit: usize,
+    ) -> Result<Vec<HotEntity>, IndexerError> {
+        spawn_read!(self, get_hot_objects(window, limit)).await
+    }
+
+    async fn get_hot_packages(
+        &self,
+        window: HotWindow,
+        limit: usize,
+    ) -> Result<Vec<HotEntity>, IndexerError> {
+        spawn_read!(self, get_hot_packages(window, limit)).await
+    }
+
+    async fn get_transaction_ancestors(
+        &self,
+        transaction_digest: TransactionDigest,
+        depth: usize,
+    ) -> Result<Vec<TransactionDependencyNode>, IndexerError> {
+        self.spawn_blocking(move |this| this.get_transaction_ancestors(transaction_digest, depth))
+            .await
+    }
+
+    async fn get_transaction_descendants(
+        &self,
+        transaction_digest: TransactionDigest,
+        depth: usize,
+    ) -> Result<Vec<TransactionDependencyNode>, IndexerError> {
+        self.spawn_blocking(move |this| this.get_transaction_descendants(transaction_digest, depth))
+            .await
+    }
+
     async fn persist_epoch(&self, data: &TemporaryEpochStore) -> Result<(), IndexerError> {
         let data = data.to_owned();
-        self.spawn_blocking(move |this| this.persist_epoch(&data))
+        let result = self
+            .spawn_blocking(move |this| this.persist_epoch(&data))
+            .await;
+        if let Some(cache) = &self.query_cache {
+            cache.current_epoch.invalidate();
+            cache.reference_gas_price.invalidate();
+        }
+        result
+    }
+
+    async fn refresh_materialized_view(&self, view: MaterializedView) -> Result<(), IndexerError> {
+        self.spawn_blocking(move |this| this.refresh_materialized_view(view))
+            .await
+    }
+
+    async fn set_objects_history_enabled(&self, enabled: bool) -> Result<(), IndexerError> {
+        self.spawn_blocking(move |this| this.set_objects_history_enabled(enabled))
+            .await
+    }
+
+    async fn persist_object_type_stats(&self, epoch: EpochId) -> Result<(), IndexerError> {
+        self.spawn_blocking(move |this| this.persist_object_type_stats(epoch))
+            .await
+    }
+
+    async fn get_object_type_stats(
+        &self,
+        epoch: EpochId,
+    ) -> Result<Vec<ObjectTypeStat>, IndexerError> {
+        spawn_read!(self, get_object_type_stats(epoch)).await
+    }
+
+    async fn get_table_sizes(&self) -> Result<Vec<TableSize>, IndexerError> {
+        self.spawn_blocking(|this| this.get_table_sizes()).await
+    }
+
+    async fn explain_query(&self, shape: QueryShape) -> Result<serde_json::Value, IndexerError> {
+        self.spawn_blocking(move |this| this.explain_query(shape))
             .await
     }
 
@@ -2347,19 +6066,106 @@ impl IndexerStore for PgIndexerStore {
         limit: usize,
         descending_order: Option<bool>,
     ) -> Result<Vec<EpochInfo>, IndexerError> {
-        self.spawn_blocking(move |this| this.get_epochs(cursor, limit, descending_order))
-            .await
+        spawn_read!(self, get_epochs(cursor, limit, descending_order)).await
+    }
+
+    async fn get_reference_gas_price_history(
+        &self,
+        start_epoch: EpochId,
+        end_epoch: EpochId,
+    ) -> Result<Vec<(EpochId, u64)>, IndexerError> {
+        spawn_read!(
+            self,
+            get_reference_gas_price_history(start_epoch, end_epoch)
+        )
+        .await
+    }
+
+    async fn get_reference_gas_price(&self) -> Result<u64, IndexerError> {
+        spawn_read!(self, get_reference_gas_price()).await
     }
 
     async fn get_current_epoch(&self) -> Result<EpochInfo, IndexerError> {
-        self.spawn_blocking(move |this| this.get_current_epoch())
+        spawn_read!(self, get_current_epoch()).await
+    }
+
+    async fn get_protocol_config(
+        &self,
+        epoch: EpochId,
+    ) -> Result<ProtocolConfigResponse, IndexerError> {
+        spawn_read!(self, get_protocol_config(epoch)).await
+    }
+
+    async fn get_system_state_at_epoch(
+        &self,
+        epoch: EpochId,
+    ) -> Result<SuiSystemStateSummary, IndexerError> {
+        spawn_read!(self, get_system_state_at_epoch(epoch)).await
+    }
+
+    async fn get_module_disassembly(
+        &self,
+        package: ObjectID,
+        module: String,
+    ) -> Result<String, IndexerError> {
+        spawn_read!(self, get_module_disassembly(package, module)).await
+    }
+
+    async fn get_event_schema(
+        &self,
+        event_type: StructTag,
+    ) -> Result<SuiMoveNormalizedStruct, IndexerError> {
+        spawn_read!(self, get_event_schema(event_type)).await
+    }
+
+    async fn get_type_layout(
+        &self,
+        struct_tag: StructTag,
+    ) -> Result<MoveStructLayout, IndexerError> {
+        self.spawn_blocking(move |this| this.get_type_layout(struct_tag))
             .await
     }
 
+    async fn search(&self, query: &str) -> Result<Vec<SearchResult>, IndexerError> {
+        let query = query.to_owned();
+        spawn_read!(self, search(&query)).await
+    }
+
+    async fn get_validator_epoch_events(
+        &self,
+        validator: SuiAddress,
+        cursor: Option<EpochId>,
+        limit: usize,
+        descending_order: bool,
+    ) -> Result<Vec<ValidatorEpochEvent>, IndexerError> {
+        spawn_read!(
+            self,
+            get_validator_epoch_events(validator, cursor, limit, descending_order)
+        )
+        .await
+    }
+
+    async fn get_validator_participation(
+        &self,
+        epoch: EpochId,
+        cursor: Option<u64>,
+        limit: usize,
+    ) -> Result<Vec<EpochValidatorParticipation>, IndexerError> {
+        spawn_read!(self, get_validator_participation(epoch, cursor, limit)).await
+    }
+
     fn module_cache(&self) -> &Self::ModuleCache {
         &self.module_cache
     }
 
+    async fn get_modules(
+        &self,
+        ids: Vec<ModuleId>,
+    ) -> Result<HashMap<ModuleId, Arc<CompiledModule>>, IndexerError> {
+        self.spawn_blocking(move |this| Ok(this.get_modules(ids)))
+            .await
+    }
+
     fn indexer_metrics(&self) -> &IndexerMetrics {
         &self.metrics
     }
@@ -2381,24 +6187,28 @@ impl IndexerStore for PgIndexerStore {
     }
 
     async fn get_latest_address_stats(&self) -> Result<AddressStats, IndexerError> {
-        self.spawn_blocking(move |this| this.get_latest_address_stats())
-            .await
+        spawn_read!(self, get_latest_address_stats()).await
+    }
+
+    async fn get_address_activity(
+        &self,
+        address: SuiAddress,
+    ) -> Result<Option<AddressActivity>, IndexerError> {
+        spawn_read!(self, get_address_activity(address)).await
     }
 
     async fn get_checkpoint_address_stats(
         &self,
         checkpoint: i64,
     ) -> Result<AddressStats, IndexerError> {
-        self.spawn_blocking(move |this| this.get_checkpoint_address_stats(checkpoint))
-            .await
+        spawn_read!(self, get_checkpoint_address_stats(checkpoint)).await
     }
 
     async fn get_all_epoch_address_stats(
         &self,
         descending_order: Option<bool>,
     ) -> Result<Vec<AddressStats>, IndexerError> {
-        self.spawn_blocking(move |this| this.get_all_epoch_address_stats(descending_order))
-            .await
+        spawn_read!(self, get_all_epoch_address_stats(descending_order)).await
     }
 
     async fn calculate_checkpoint_metrics(
@@ -2452,13 +6262,14 @@ impl IndexerStore for PgIndexerStore {
 
 fn persist_object_mutations(
     conn: &mut PgConnection,
+    chunk_sizes: &CommitChunkSizes,
     mutated_objects: Vec<Object>,
     object_mutation_latency: Histogram,
     object_commit_chunk_counter: IntCounter,
 ) -> Result<(), IndexerError> {
     let mutated_objects = filter_latest_objects(mutated_objects);
     let object_mutation_guard = object_mutation_latency.start_timer();
-    for mutated_object_change_chunk in mutated_objects.chunks(PG_COMMIT_CHUNK_SIZE) {
+    for mutated_object_change_chunk in mutated_objects.chunks(chunk_sizes.get("objects")) {
         // bulk insert/update via UNNEST trick to bypass the 65535 parameters limit
         // ref: https://klotzandrew.com/blog/postgres-passing-65535-parameter-limit
         let insert_update_query =
@@ -2479,24 +6290,73 @@ fn persist_object_mutations(
     Ok(())
 }
 
+fn update_unchanged_objects(
+    conn: &mut PgConnection,
+    chunk_sizes: &CommitChunkSizes,
+    unchanged_objects: &[Object],
+) -> Result<HashSet<String>, IndexerError> {
+    let mut updated_object_ids = HashSet::new();
+    for chunk in unchanged_objects.chunks(chunk_sizes.get("objects")) {
+        let object_statuses = chunk
+            .iter()
+            .map(|object| {
+                serde_json::to_string(&object.object_status)
+                    .map(|status| status.trim_matches('"').to_string())
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| IndexerError::SerdeError(e.to_string()))?;
+        let updated = diesel::sql_query(UNCHANGED_OBJECTS_UPDATE_QUERY)
+            .bind::<Array<VarChar>, _>(chunk.iter().map(|o| o.object_id.clone()).collect::<Vec<_>>())
+            .bind::<Array<BigInt>, _>(chunk.iter().map(|o| o.epoch).collect::<Vec<_>>())
+            .bind::<Array<BigInt>, _>(chunk.iter().map(|o| o.checkpoint).collect::<Vec<_>>())
+            .bind::<Array<BigInt>, _>(chunk.iter().map(|o| o.version).collect::<Vec<_>>())
+            .bind::<Array<VarChar>, _>(
+                chunk.iter().map(|o| o.object_digest.clone()).collect::<Vec<_>>(),
+            )
+            .bind::<Array<VarChar>, _>(
+                chunk
+                    .iter()
+                    .map(|o| o.previous_transaction.clone())
+                    .collect::<Vec<_>>(),
+            )
+            .bind::<Array<VarChar>, _>(object_statuses)
+            .load::<UpdatedObject>(conn)
+            .map_err(|e| {
+                IndexerError::PostgresWriteError(format!(
+                    "Failed updating unchanged objects in PostgresDB with error: {:?}. Chunk length: {}",
+                    e,
+                    chunk.len(),
+                ))
+            })?;
+        updated_object_ids.extend(updated.into_iter().map(|o| o.object_id));
+    }
+    Ok(updated_object_ids)
+}
+
 fn persist_object_deletions(
     conn: &mut PgConnection,
+    chunk_sizes: &CommitChunkSizes,
     deleted_objects: Vec<Object>,
     object_deletion_latency: Histogram,
     object_commit_chunk_counter: IntCounter,
 ) -> Result<(), IndexerError> {
     let object_deletion_guard = object_deletion_latency.start_timer();
-    for deleted_object_change_chunk in deleted_objects.chunks(PG_COMMIT_CHUNK_SIZE) {
+    for deleted_object_change_chunk in deleted_objects.chunks(chunk_sizes.get("objects")) {
         diesel::insert_into(objects::table)
             .values(deleted_object_change_chunk)
             .on_conflict(objects::object_id)
             .do_update()
             .set((
-                objects::epoch.eq(excluded(objects::epoch)),
-                objects::checkpoint.eq(excluded(objects::checkpoint)),
-                objects::version.eq(excluded(objects::version)),
-                objects::previous_transaction.eq(excluded(objects::previous_transaction)),
-                objects::object_status.eq(excluded(objects::object_status)),
+                objects::epoch.eq(latest(objects::epoch, "objects", "version")),
+                objects::checkpoint.eq(latest(objects::checkpoint, "objects", "version")),
+                objects::version.eq(latest(objects::version, "objects", "version")),
+                objects::previous_transaction.eq(latest(
+                    objects::previous_transaction,
+                    "objects",
+                    "version",
+                )),
+                objects::object_status.eq(latest(objects::object_status, "objects", "version")),
+                objects::object_size.eq(latest(objects::object_size, "objects", "version")),
             ))
             .execute(conn)
             .map_err(|e| {
@@ -2513,6 +6373,512 @@ fn persist_object_deletions(
     Ok(())
 }
 
+// Runs `write` of the rows of objects `object_ids` and applies the change it makes to the
+// live objects of each struct type to object_type_counts, in the transaction of `write`.
+// Only the rows whose version `write` changed are counted, as rows of later versions are
+// left as they are, e.g. when a checkpoint is committed again.
+fn with_object_type_counts<T>(
+    conn: &mut PgConnection,
+    chunk_sizes: &CommitChunkSizes,
+    object_ids: &[String],
+    write: impl FnOnce(&mut PgConnection) -> Result<T, IndexerError>,
+) -> Result<T, IndexerError> {
+    let locked_query = format!("{OBJECT_TYPE_STATES_QUERY} FOR UPDATE");
+    let before = object_type_states(conn, chunk_sizes, object_ids, &locked_query)?
+        .into_iter()
+        .map(|state| (state.object_id.clone(), state))
+        .collect::<HashMap<_, _>>();
+    let result = write(conn)?;
+    let after = object_type_states(conn, chunk_sizes, object_ids, OBJECT_TYPE_STATES_QUERY)?;
+
+    let mut deltas = BTreeMap::new();
+    for state in after {
+        let previous = before.get(&state.object_id);
+        if previous.map(|previous| previous.version) == Some(state.version) {
+            continue;
+        }
+        if let Some(object_type) = previous.cloned().and_then(ObjectTypeState::counted_type) {
+            *deltas.entry(object_type).or_insert(0i64) -= 1;
+        }
+        if let Some(object_type) = state.counted_type() {
+            *deltas.entry(object_type).or_insert(0i64) += 1;
+        }
+    }
+    deltas.retain(|_, delta| *delta != 0);
+    if deltas.is_empty() {
+        return Ok(result);
+    }
+    let (mut packages, mut modules, mut names, mut counts) = (vec![], vec![], vec![], vec![]);
+    for ((package, module, name), delta) in deltas {
+        packages.push(package);
+        modules.push(module);
+        names.push(name);
+        counts.push(delta);
+    }
+    diesel::sql_query(OBJECT_TYPE_COUNTS_UPDATE_QUERY)
+        .bind::<Array<VarChar>, _>(packages)
+        .bind::<Array<VarChar>, _>(modules)
+        .bind::<Array<VarChar>, _>(names)
+        .bind::<Array<BigInt>, _>(counts)
+        .execute(conn)
+        .map_err(IndexerError::from)
+        .context("Failed updating object type counts in PostgresDB")?;
+    Ok(result)
+}
+
+fn object_type_states(
+    conn: &mut PgConnection,
+    chunk_sizes: &CommitChunkSizes,
+    object_ids: &[String],
+    query: &str,
+) -> Result<Vec<ObjectTypeState>, IndexerError> {
+    let mut states = vec![];
+    for chunk in object_ids.chunks(chunk_sizes.get("objects")) {
+        states.extend(
+            diesel::sql_query(query)
+                .bind::<Array<VarChar>, _>(chunk.to_vec())
+                .load::<ObjectTypeState>(conn)
+                .map_err(IndexerError::from)
+                .context("Failed reading object types from PostgresDB")?,
+        );
+    }
+    Ok(states)
+}
+
+// Value of `column` of the row being upserted into `table`, unless the existing row is of a
+// later `order`, e.g. version, so that committing a checkpoint again never overwrites what
+// later checkpoints wrote.
+fn latest<C>(_column: C, table: &str, order: &str) -> SqlLiteral<C::SqlType>
+where
+    C: Column,
+    C::SqlType: TypedExpressionType,
+{
+    diesel::dsl::sql(&format!(
+        "CASE WHEN excluded.{order} >= {table}.{order} THEN excluded.{column} \
+        ELSE {table}.{column} END",
+        column = C::NAME
+    ))
+}
+
+// Like `latest` for the display of a type, whose versions are those of its Display object,
+// a display of another Display object is always written.
+fn latest_display<C>(_column: C) -> SqlLiteral<C::SqlType>
+where
+    C: Column,
+    C::SqlType: TypedExpressionType,
+{
+    diesel::dsl::sql(&format!(
+        "CASE WHEN excluded.display_id <> display.display_id \
+        OR excluded.version >= display.version THEN excluded.{column} \
+        ELSE display.{column} END",
+        column = C::NAME
+    ))
+}
+
+// Rows inserted with `on_conflict_do_nothing` are only counted once, so totals updated by
+// the number of inserted rows stay correct when a commit is retried.
+fn add_to_network_total(
+    conn: &mut PgConnection,
+    name: &str,
+    delta: i64,
+) -> Result<(), IndexerError> {
+    diesel::update(network_totals::table)
+        .filter(network_totals::name.eq(name))
+        .set(network_totals::total.eq(network_totals::total + delta))
+        .execute(conn)
+        .map_err(IndexerError::from)
+        .context(&format!(
+            "Failed updating network total {name} in PostgresDB"
+        ))?;
+    Ok(())
+}
+
+// Names expire as of the timestamp of the latest checkpoint rather than the wall clock, so
+// that an indexer catching up serves the names as of where it is.
+fn latest_checkpoint_timestamp_ms(conn: &mut PgConnection) -> diesel::QueryResult<i64> {
+    checkpoints::table
+        .select(checkpoints::timestamp_ms)
+        .order(checkpoints::sequence_number.desc())
+        .first::<i64>(conn)
+        .optional()
+        .map(Option::unwrap_or_default)
+}
+
+// Adds the activity of the checkpoints inserted by this commit to the counters of their
+// addresses, like the counts of persist_hot_entities. The appearance columns are left to
+// the address processor for addresses it has already recorded.
+fn persist_address_activity(
+    conn: &mut PgConnection,
+    chunk_sizes: &CommitChunkSizes,
+    address_activity: &[CheckpointAddressActivity],
+    inserted_checkpoints: &HashSet<i64>,
+) -> Result<(), IndexerError> {
+    let mut inserted = address_activity
+        .iter()
+        .filter(|activity| inserted_checkpoints.contains(&activity.checkpoint_sequence_number))
+        .collect::<Vec<_>>();
+    inserted.sort_by_key(|activity| activity.checkpoint_sequence_number);
+    // a row can only be upserted once per statement
+    let mut addresses: BTreeMap<&str, AddressActivity> = BTreeMap::new();
+    for activity in inserted
+        .into_iter()
+        .flat_map(|activity| &activity.addresses)
+    {
+        match addresses.get_mut(activity.account_address.as_str()) {
+            Some(address) => address.merge(activity),
+            None => {
+                addresses.insert(&activity.account_address, activity.clone());
+            }
+        }
+    }
+    let addresses = addresses.into_values().collect::<Vec<_>>();
+    for address_chunk in addresses.chunks(chunk_sizes.get("addresses")) {
+        diesel::insert_into(addresses::table)
+            .values(address_chunk)
+            .on_conflict(addresses::account_address)
+            .do_update()
+            .set((
+                addresses::transactions_sent
+                    .eq(addresses::transactions_sent + excluded(addresses::transactions_sent)),
+                addresses::transactions_received.eq(
+                    addresses::transactions_received + excluded(addresses::transactions_received)
+                ),
+                addresses::first_activity_checkpoint.eq(diesel::dsl::sql::<
+                    diesel::sql_types::Nullable<BigInt>,
+                >(
+                    "LEAST(addresses.first_activity_checkpoint, excluded.first_activity_checkpoint)",
+                )),
+                addresses::last_activity_checkpoint.eq(diesel::dsl::sql::<
+                    diesel::sql_types::Nullable<BigInt>,
+                >(
+                    "GREATEST(addresses.last_activity_checkpoint, excluded.last_activity_checkpoint)",
+                )),
+            ))
+            .execute(conn)
+            .map_err(IndexerError::from)
+            .context("Failed writing address activity to PostgresDB")?;
+    }
+    Ok(())
+}
+
+// Adds the signatures of the checkpoints inserted by this commit to the participation of
+// their signers, like the counts of persist_hot_entities.
+fn persist_validator_participation(
+    conn: &mut PgConnection,
+    chunk_sizes: &CommitChunkSizes,
+    signers: &[CheckpointSigners],
+    inserted_checkpoints: &HashSet<i64>,
+) -> Result<(), IndexerError> {
+    let participation = ValidatorParticipation::from_signers(
+        signers
+            .iter()
+            .filter(|signers| inserted_checkpoints.contains(&signers.checkpoint_sequence_number)),
+    );
+    for participation_chunk in participation.chunks(chunk_sizes.get("validator_participation")) {
+        diesel::insert_into(validator_participation::table)
+            .values(participation_chunk)
+            .on_conflict((
+                validator_participation::epoch,
+                validator_participation::committee_index,
+            ))
+            .do_update()
+            .set(
+                validator_participation::checkpoints_signed
+                    .eq(validator_participation::checkpoints_signed
+                        + excluded(validator_participation::checkpoints_signed)),
+            )
+            .execute(conn)
+            .map_err(IndexerError::from)
+            .context("Failed writing validator participation to PostgresDB")?;
+    }
+    Ok(())
+}
+
+// Adds the gas spent on the sponsored transactions of the checkpoints inserted by this commit
+// to the spend of their sponsors, like the counts of persist_hot_entities.
+fn persist_sponsor_gas_spend(
+    conn: &mut PgConnection,
+    chunk_sizes: &CommitChunkSizes,
+    checkpoints: &[Checkpoint],
+    transactions: &[Transaction],
+    inserted_checkpoints: &HashSet<i64>,
+) -> Result<(), IndexerError> {
+    let checkpoint_epochs = checkpoints
+        .iter()
+        .filter(|checkpoint| inserted_checkpoints.contains(&checkpoint.sequence_number))
+        .map(|checkpoint| (checkpoint.sequence_number, checkpoint.epoch))
+        .collect::<HashMap<_, _>>();
+    let spend = SponsorGasSpend::from_transactions(transactions, &checkpoint_epochs);
+    for spend_chunk in spend.chunks(chunk_sizes.get("sponsor_gas_spend")) {
+        diesel::insert_into(sponsor_gas_spend::table)
+            .values(spend_chunk)
+            .on_conflict((sponsor_gas_spend::sponsor, sponsor_gas_spend::epoch))
+            .do_update()
+            .set(
+                (
+                    sponsor_gas_spend::transaction_count.eq(sponsor_gas_spend::transaction_count
+                        + excluded(sponsor_gas_spend::transaction_count)),
+                    sponsor_gas_spend::total_gas_cost.eq(sponsor_gas_spend::total_gas_cost
+                        + excluded(sponsor_gas_spend::total_gas_cost)),
+                    sponsor_gas_spend::computation_cost.eq(sponsor_gas_spend::computation_cost
+                        + excluded(sponsor_gas_spend::computation_cost)),
+                    sponsor_gas_spend::storage_cost
+                        .eq(sponsor_gas_spend::storage_cost
+                            + excluded(sponsor_gas_spend::storage_cost)),
+                    sponsor_gas_spend::storage_rebate.eq(sponsor_gas_spend::storage_rebate
+                        + excluded(sponsor_gas_spend::storage_rebate)),
+                ),
+            )
+            .execute(conn)
+            .map_err(IndexerError::from)
+            .context("Failed writing sponsor_gas_spend to PostgresDB")?;
+    }
+    Ok(())
+}
+
+// Adds the counts of the checkpoints inserted by this commit to the leaderboards, so that
+// a checkpoint committed again is not counted twice, and drops the buckets that have left
+// the longest window.
+fn persist_hot_entities(
+    conn: &mut PgConnection,
+    chunk_sizes: &CommitChunkSizes,
+    hot_entities: &[CheckpointHotEntities],
+    inserted_checkpoints: &HashSet<i64>,
+) -> Result<(), IndexerError> {
+    let inserted = hot_entities
+        .iter()
+        .filter(|hot| inserted_checkpoints.contains(&hot.checkpoint_sequence_number));
+    // a row can only be upserted once per statement
+    let mut objects = BTreeMap::new();
+    let mut packages = BTreeMap::new();
+    for hot in inserted {
+        for object in &hot.objects {
+            *objects
+                .entry((object.bucket_start_ms, object.object_id.clone()))
+                .or_insert(0) += object.touches;
+        }
+        for package in &hot.packages {
+            *packages
+                .entry((package.bucket_start_ms, package.package.clone()))
+                .or_insert(0) += package.calls;
+        }
+    }
+    let latest_bucket_start_ms = objects
+        .keys()
+        .chain(packages.keys())
+        .map(|(bucket_start_ms, _)| *bucket_start_ms)
+        .max();
+    let latest_bucket_start_ms = match latest_bucket_start_ms {
+        Some(bucket_start_ms) => bucket_start_ms,
+        None => return Ok(()),
+    };
+
+    let objects = objects
+        .into_iter()
+        .map(|((bucket_start_ms, object_id), touches)| HotObject {
+            bucket_start_ms,
+            object_id,
+            touches,
+        })
+        .collect::<Vec<_>>();
+    for objects_chunk in objects.chunks(chunk_sizes.get("hot_objects")) {
+        diesel::insert_into(hot_objects::table)
+            .values(objects_chunk)
+            .on_conflict((hot_objects::bucket_start_ms, hot_objects::object_id))
+            .do_update()
+            .set(hot_objects::touches.eq(hot_objects::touches + excluded(hot_objects::touches)))
+            .execute(conn)
+            .map_err(IndexerError::from)
+            .context("Failed writing hot_objects to PostgresDB")?;
+    }
+    let packages = packages
+        .into_iter()
+        .map(|((bucket_start_ms, package), calls)| HotPackage {
+            bucket_start_ms,
+            package,
+            calls,
+        })
+        .collect::<Vec<_>>();
+    for packages_chunk in packages.chunks(chunk_sizes.get("hot_packages")) {
+        diesel::insert_into(hot_packages::table)
+            .values(packages_chunk)
+            .on_conflict((hot_packages::bucket_start_ms, hot_packages::package))
+            .do_update()
+            .set(hot_packages::calls.eq(hot_packages::calls + excluded(hot_packages::calls)))
+            .execute(conn)
+            .map_err(IndexerError::from)
+            .context("Failed writing hot_packages to PostgresDB")?;
+    }
+
+    let oldest_bucket_start_ms = latest_bucket_start_ms - HotWindow::max_duration_ms();
+    diesel::delete(
+        hot_objects::table.filter(hot_objects::bucket_start_ms.lt(oldest_bucket_start_ms)),
+    )
+    .execute(conn)
+    .map_err(IndexerError::from)
+    .context("Failed pruning hot_objects in PostgresDB")?;
+    diesel::delete(
+        hot_packages::table.filter(hot_packages::bucket_start_ms.lt(oldest_bucket_start_ms)),
+    )
+    .execute(conn)
+    .map_err(IndexerError::from)
+    .context("Failed pruning hot_packages in PostgresDB")?;
+    Ok(())
+}
+
+// Writes the name records as of the end of a batch of object changes. The type of a
+// deleted object is not known, so the records of all deleted objects are removed, which
+// are looked up by primary key.
+fn persist_name_record_changes(
+    conn: &mut PgConnection,
+    chunk_sizes: &CommitChunkSizes,
+    tx_object_changes: &[TransactionObjectChanges],
+) -> Result<(), IndexerError> {
+    // None for deleted records, a record deleted and registered again keeps its field id
+    let mut latest_records = HashMap::new();
+    for changes in tx_object_changes {
+        for deleted_object in &changes.deleted_objects {
+            latest_records.insert(
+                deleted_object.object_id.as_str(),
+                (deleted_object.version, None),
+            );
+        }
+        for record in &changes.name_records {
+            latest_records.insert(record.field_id.as_str(), (record.version, Some(record)));
+        }
+    }
+    let mut records = vec![];
+    let mut deleted_records = vec![];
+    for (field_id, (version, record)) in latest_records {
+        match record {
+            Some(record) => records.push(record.clone()),
+            None => deleted_records.push((field_id, version)),
+        }
+    }
+
+    for record_chunk in records.chunks(chunk_sizes.get("name_records")) {
+        diesel::insert_into(name_records::table)
+            .values(record_chunk)
+            .on_conflict(name_records::field_id)
+            .do_update()
+            .set((
+                name_records::nft_id.eq(latest(name_records::nft_id, "name_records", "version")),
+                name_records::target_address.eq(latest(
+                    name_records::target_address,
+                    "name_records",
+                    "version",
+                )),
+                name_records::expiration_timestamp_ms.eq(latest(
+                    name_records::expiration_timestamp_ms,
+                    "name_records",
+                    "version",
+                )),
+                name_records::version.eq(latest(name_records::version, "name_records", "version")),
+                name_records::checkpoint_sequence_number.eq(latest(
+                    name_records::checkpoint_sequence_number,
+                    "name_records",
+                    "version",
+                )),
+            ))
+            .execute(conn)
+            .map_err(IndexerError::from)
+            .context("Failed writing name records to PostgresDB")?;
+    }
+    // records registered again after the deletion, by a later checkpoint, are kept
+    for deleted_chunk in deleted_records.chunks(chunk_sizes.get("name_records")) {
+        let mut delete = diesel::delete(name_records::table).into_boxed();
+        for (field_id, version) in deleted_chunk {
+            delete = delete.or_filter(
+                name_records::field_id
+                    .eq(*field_id)
+                    .and(name_records::version.lt(*version)),
+            );
+        }
+        delete
+            .execute(conn)
+            .map_err(IndexerError::from)
+            .context("Failed deleting name records from PostgresDB")?;
+    }
+    Ok(())
+}
+
+// Applies the change in live objects of a batch of checkpoints to the network totals,
+// unless the batch has already been counted, as object changes are committed in two DB
+// transactions and the whole batch is retried if the second one fails.
+fn update_total_live_objects(
+    conn: &mut PgConnection,
+    tx_object_changes: &[TransactionObjectChanges],
+) -> Result<(), IndexerError> {
+    let changed_objects = tx_object_changes
+        .iter()
+        .flat_map(|changes| changes.changed_objects.iter());
+    let deleted_objects = tx_object_changes
+        .iter()
+        .flat_map(|changes| changes.deleted_objects.iter());
+    let checkpoint = changed_objects
+        .clone()
+        .map(|o| o.checkpoint)
+        .chain(deleted_objects.clone().filter_map(|o| o.checkpoint))
+        .max();
+    let checkpoint = match checkpoint {
+        Some(checkpoint) => checkpoint,
+        None => return Ok(()),
+    };
+    let statuses = changed_objects
+        .map(|o| &o.object_status)
+        .chain(deleted_objects.map(|o| &o.object_status));
+    let live_objects_delta: i64 = statuses.map(live_objects_delta).sum();
+
+    diesel::update(network_totals::table)
+        .filter(network_totals::name.eq(TOTAL_LIVE_OBJECTS))
+        .filter(network_totals::checkpoint.lt(checkpoint))
+        .set((
+            network_totals::total.eq(network_totals::total + live_objects_delta),
+            network_totals::checkpoint.eq(checkpoint),
+        ))
+        .execute(conn)
+        .map_err(IndexerError::from)
+        .context("Failed updating total live objects in PostgresDB")?;
+    Ok(())
+}
+
+// Recomputes the growth of the last epoch of epoch_storage_growth, which may have grown
+// since it was computed, and adds the epochs after it, so that each update only reads the
+// objects_history rows of these epochs.
+fn update_epoch_storage_growth(conn: &mut PgConnection) -> Result<(), IndexerError> {
+    let start_epoch = epoch_storage_growth::table
+        .select(max(epoch_storage_growth::epoch))
+        .first::<Option<i64>>(conn)
+        .map_err(IndexerError::from)
+        .context("Failed reading last epoch of epoch_storage_growth")?
+        .unwrap_or(0);
+    diesel::delete(epoch_storage_growth::table)
+        .filter(epoch_storage_growth::epoch.ge(start_epoch))
+        .execute(conn)
+        .map_err(IndexerError::from)
+        .context(&format!(
+            "Failed deleting storage growth of epochs from {start_epoch}"
+        ))?;
+    diesel::sql_query(EPOCH_STORAGE_GROWTH_INSERT_QUERY)
+        .bind::<BigInt, _>(start_epoch)
+        .execute(conn)
+        .map_err(IndexerError::from)
+        .context(&format!(
+            "Failed computing storage growth of epochs from {start_epoch}"
+        ))?;
+    Ok(())
+}
+
+// An unwrapped then deleted object was wrapped, and stopped being live, in an earlier
+// transaction.
+fn live_objects_delta(status: &ObjectStatus) -> i64 {
+    match status {
+        ObjectStatus::Created | ObjectStatus::Unwrapped => 1,
+        ObjectStatus::Deleted | ObjectStatus::Wrapped => -1,
+        ObjectStatus::Mutated | ObjectStatus::UnwrappedThenDeleted => 0,
+    }
+}
+
 #[derive(Clone)]
 struct PartitionManager {
     cp: PgConnectionPool,
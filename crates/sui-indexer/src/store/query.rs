@@ -1,8 +1,17 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::str::FromStr;
+
+use move_core_types::account_address::AccountAddress;
+use move_core_types::identifier::Identifier;
+
 use sui_json_rpc_types::SuiObjectDataFilter;
 use sui_types::base_types::ObjectID;
+use sui_types::parse_sui_struct_tag;
+use sui_types::sui_serde::to_sui_struct_tag_string;
+
+use crate::errors::IndexerError;
 
 pub trait DBFilter<C> {
     fn to_objects_history_sql(&self, cursor: Option<C>, limit: usize, columns: Vec<&str>)
@@ -210,6 +219,121 @@ fn to_outer_clauses(filter: &SuiObjectDataFilter) -> Option<String> {
     }
 }
 
+/// Type query against the decomposed `object_type_*` columns of the objects table.
+/// Accepted forms are `0xabc`, `0xabc::pool`, `0xabc::pool::Pool`, `0xabc::pool::Pool<*>`
+/// and fully instantiated types like `0xabc::pool::Pool<0x2::sui::SUI>`, only the
+/// last one is matched against the full type string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObjectTypeQuery {
+    pub package: String,
+    pub module: Option<String>,
+    pub name: Option<String>,
+    pub exact_type: Option<String>,
+}
+
+impl FromStr for ObjectTypeQuery {
+    type Err = IndexerError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (head, type_params) = match s.find('<') {
+            Some(idx) => (&s[..idx], Some(&s[idx..])),
+            None => (s, None),
+        };
+        let parts = head.split("::").collect::<Vec<_>>();
+        if parts.len() > 3 {
+            return Err(IndexerError::InvalidArgumentError(format!(
+                "Invalid object type query {s}, expected at most package::module::name"
+            )));
+        }
+        let package = AccountAddress::from_hex_literal(parts[0]).map_err(|e| {
+            IndexerError::InvalidArgumentError(format!(
+                "Invalid package {} in object type query {s}, error: {e}",
+                parts[0]
+            ))
+        })?;
+        let identifiers = parts[1..]
+            .iter()
+            .map(|ident| {
+                if Identifier::is_valid(ident) {
+                    Ok(ident.to_string())
+                } else {
+                    Err(IndexerError::InvalidArgumentError(format!(
+                        "Invalid identifier {ident} in object type query {s}"
+                    )))
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let module = identifiers.first().cloned();
+        let name = identifiers.get(1).cloned();
+
+        let exact_type = match type_params {
+            None | Some("<*>") => None,
+            Some(_) if name.is_none() => {
+                return Err(IndexerError::InvalidArgumentError(format!(
+                    "Type parameters in object type query {s} require a struct name"
+                )));
+            }
+            Some(_) => {
+                let struct_tag = parse_sui_struct_tag(s).map_err(|e| {
+                    IndexerError::InvalidArgumentError(format!(
+                        "Failed parsing object type query {s} with error: {e}"
+                    ))
+                })?;
+                Some(to_sui_struct_tag_string(&struct_tag).map_err(|e| {
+                    IndexerError::SerdeError(format!(
+                        "Failed formatting struct tag {struct_tag} with error: {e}"
+                    ))
+                })?)
+            }
+        };
+
+        Ok(Self {
+            package: format!("0x{}", package.to_canonical_string()),
+            module,
+            name,
+            exact_type,
+        })
+    }
+}
+
+impl ObjectTypeQuery {
+    pub fn to_objects_sql(
+        &self,
+        cursor: Option<ObjectID>,
+        limit: usize,
+        columns: Vec<&str>,
+    ) -> String {
+        let columns = columns
+            .iter()
+            .map(|c| format!("o.{c}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let mut clauses = vec![format!("o.object_type_package = '{}'", self.package)];
+        if let Some(module) = &self.module {
+            clauses.push(format!("o.object_type_module = '{module}'"));
+        }
+        if let Some(name) = &self.name {
+            clauses.push(format!("o.object_type_name = '{name}'"));
+        }
+        if let Some(exact_type) = &self.exact_type {
+            clauses.push(format!("o.object_type = '{exact_type}'"));
+        }
+        if let Some(cursor) = cursor {
+            clauses.push(format!("o.object_id > '{cursor}'"));
+        }
+        let clauses = clauses.join(" AND ");
+
+        format!(
+            "SELECT {columns}
+FROM objects o WHERE o.object_status NOT IN ('deleted', 'wrapped', 'unwrapped_then_deleted') AND {clauses}
+ORDER BY o.object_id
+LIMIT {limit};"
+        )
+    }
+}
+
 #[cfg(test)]
 mod test {
     use std::str::FromStr;
@@ -220,7 +344,7 @@ mod test {
     use sui_types::base_types::{ObjectID, SuiAddress};
     use sui_types::parse_sui_struct_tag;
 
-    use crate::store::query::DBFilter;
+    use crate::store::query::{DBFilter, ObjectTypeQuery};
 
     #[test]
     fn test_address_filter() {
@@ -326,4 +450,39 @@ LIMIT 100;";
             filter.to_objects_history_sql(None, 100, vec!["*"])
         );
     }
+
+    #[test]
+    fn test_object_type_prefix_query() {
+        let query = ObjectTypeQuery::from_str("0x2::coin::Coin<*>").unwrap();
+        let expected_sql = "SELECT o.*
+FROM objects o WHERE o.object_status NOT IN ('deleted', 'wrapped', 'unwrapped_then_deleted') AND o.object_type_package = '0x0000000000000000000000000000000000000000000000000000000000000002' AND o.object_type_module = 'coin' AND o.object_type_name = 'Coin'
+ORDER BY o.object_id
+LIMIT 100;";
+        assert_eq!(expected_sql, query.to_objects_sql(None, 100, vec!["*"]));
+        assert_eq!(query, ObjectTypeQuery::from_str("0x2::coin::Coin").unwrap());
+    }
+
+    #[test]
+    fn test_object_type_exact_query() {
+        let cursor = ObjectID::from_str(
+            "0xef9fb75a7b3d4cb5551ef0b08c83528b94d5f5cd8be28b1d08a87dbbf3731738",
+        )
+        .unwrap();
+        let query = ObjectTypeQuery::from_str("0x2::coin::Coin<0x2::sui::SUI>").unwrap();
+        let expected_sql = "SELECT o.*
+FROM objects o WHERE o.object_status NOT IN ('deleted', 'wrapped', 'unwrapped_then_deleted') AND o.object_type_package = '0x0000000000000000000000000000000000000000000000000000000000000002' AND o.object_type_module = 'coin' AND o.object_type_name = 'Coin' AND o.object_type = '0x2::coin::Coin<0x2::sui::SUI>' AND o.object_id > '0xef9fb75a7b3d4cb5551ef0b08c83528b94d5f5cd8be28b1d08a87dbbf3731738'
+ORDER BY o.object_id
+LIMIT 100;";
+        assert_eq!(
+            expected_sql,
+            query.to_objects_sql(Some(cursor), 100, vec!["*"])
+        );
+    }
+
+    #[test]
+    fn test_invalid_object_type_query() {
+        assert!(ObjectTypeQuery::from_str("0x2::coin::Coin::Extra").is_err());
+        assert!(ObjectTypeQuery::from_str("0x2::coin'; DROP TABLE objects; --").is_err());
+        assert!(ObjectTypeQuery::from_str("0x2::coin<0x2::sui::SUI>").is_err());
+    }
 }
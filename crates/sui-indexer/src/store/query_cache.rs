@@ -0,0 +1,106 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::models::epoch::DBEpochInfo;
+use crate::models::system_state::DBValidatorSummary;
+
+/// In-process cache of reads hit on most RPC requests. Entries expire after their TTL,
+/// which bounds staleness when another process commits, and are invalidated when this
+/// process commits the data they are read from.
+#[derive(Clone)]
+pub(crate) struct QueryCache {
+    pub latest_checkpoint: CachedValue<i64>,
+    pub current_epoch: CachedValue<(DBEpochInfo, Vec<DBValidatorSummary>)>,
+    pub reference_gas_price: CachedValue<i64>,
+}
+
+const LATEST_CHECKPOINT_TTL: Duration = Duration::from_secs(1);
+const CURRENT_EPOCH_TTL: Duration = Duration::from_secs(30);
+
+impl Default for QueryCache {
+    fn default() -> Self {
+        Self {
+            latest_checkpoint: CachedValue::new(LATEST_CHECKPOINT_TTL),
+            current_epoch: CachedValue::new(CURRENT_EPOCH_TTL),
+            reference_gas_price: CachedValue::new(CURRENT_EPOCH_TTL),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct CachedValue<T> {
+    ttl: Duration,
+    entry: Arc<Mutex<Entry<T>>>,
+}
+
+struct Entry<T> {
+    value: Option<(Instant, T)>,
+    // bumped on invalidation, so that a value read before it is not cached after it
+    generation: u64,
+}
+
+impl<T: Clone> CachedValue<T> {
+    fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entry: Arc::new(Mutex::new(Entry {
+                value: None,
+                generation: 0,
+            })),
+        }
+    }
+
+    pub fn get_or_try_insert_with<E>(&self, read: impl FnOnce() -> Result<T, E>) -> Result<T, E> {
+        let generation = {
+            let entry = self.entry.lock().unwrap();
+            if let Some((inserted_at, value)) = &entry.value {
+                if inserted_at.elapsed() < self.ttl {
+                    return Ok(value.clone());
+                }
+            }
+            entry.generation
+        };
+        let value = read()?;
+        let mut entry = self.entry.lock().unwrap();
+        if entry.generation == generation {
+            entry.value = Some((Instant::now(), value.clone()));
+        }
+        Ok(value)
+    }
+
+    pub fn invalidate(&self) {
+        let mut entry = self.entry.lock().unwrap();
+        entry.value = None;
+        entry.generation += 1;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::CachedValue;
+
+    #[test]
+    fn test_cached_value() {
+        let cached = CachedValue::new(Duration::from_secs(60));
+        let read = |v: i64| move || Ok::<_, ()>(v);
+        assert_eq!(cached.get_or_try_insert_with(read(1)), Ok(1));
+        assert_eq!(cached.get_or_try_insert_with(read(2)), Ok(1));
+
+        cached.invalidate();
+        assert_eq!(cached.get_or_try_insert_with(read(2)), Ok(2));
+        // failed reads are not cached
+        assert_eq!(cached.get_or_try_insert_with(|| Err(())), Ok(2));
+        cached.invalidate();
+        assert_eq!(cached.get_or_try_insert_with(|| Err::<i64, _>(())), Err(()));
+        assert_eq!(cached.get_or_try_insert_with(read(3)), Ok(3));
+
+        let expiring = CachedValue::new(Duration::ZERO);
+        assert_eq!(expiring.get_or_try_insert_with(read(1)), Ok(1));
+        assert_eq!(expiring.get_or_try_insert_with(read(2)), Ok(2));
+    }
+}
@@ -0,0 +1,455 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use diesel::pg::Pg;
+use diesel::query_builder::{AstPass, Query, QueryFragment, QueryId};
+use diesel::sql_types::BigInt;
+use diesel::{debug_query, PgConnection, QueryDsl, QueryResult, RunQueryDsl};
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::StructTag;
+use serde_json::Value;
+
+use sui_json_rpc_types::SuiObjectDataFilter;
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::digests::TransactionDigest;
+
+use crate::query;
+use crate::schema::transactions;
+use crate::store::page_queries::{
+    address_transactions_page_sql, events_page, gas_owner_filter, move_call_transactions_page_sql,
+    object_transactions_page_sql, recipient_transactions_page_sql, sponsored_transactions_filter,
+    transaction_by_digest_query, transaction_kinds_filter, transactions_by_abort_query,
+    transactions_by_gas_object_query, transactions_page, transactions_query, OBJECT_COLUMNS,
+};
+use crate::store::query::{DBFilter, ObjectTypeQuery};
+
+// placeholder values of the query shapes, the plans depend on the filtered columns rather
+// than on the values
+const ADDRESS: &str = "0x0000000000000000000000000000000000000000000000000000000000000abc";
+const PAGE_SIZE: usize = 50;
+const START_MS: u64 = 1_680_000_000_000;
+const END_MS: u64 = 1_680_003_600_000;
+
+/// A query the store issues for a combination of filters of the RPC APIs, with placeholder
+/// values, to be explained against the live schema.
+pub struct QueryShape {
+    pub name: &'static str,
+    pub query: Box<dyn QueryFragment<Pg> + Send>,
+}
+
+impl QueryShape {
+    fn new(name: &'static str, query: impl QueryFragment<Pg> + Send + 'static) -> Self {
+        Self {
+            name,
+            query: Box::new(query),
+        }
+    }
+
+    /// SQL of the query, followed by the values of its binds.
+    pub fn sql(&self) -> String {
+        debug_query::<Pg, _>(&self.query).to_string()
+    }
+}
+
+impl fmt::Debug for QueryShape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("QueryShape")
+            .field("name", &self.name)
+            .field("sql", &self.sql())
+            .finish()
+    }
+}
+
+/// The canonical query shapes of the paginated reads of the store, in the order of their
+/// pages, e.g. by id for transactions. The queries are built by the store's own query
+/// builders, so they are the ones the store runs.
+pub fn query_shapes() -> Vec<QueryShape> {
+    let address = SuiAddress::from_str(ADDRESS).unwrap();
+    let object_id = ObjectID::from(address);
+    let module = Identifier::new("pool").unwrap();
+    let function = Identifier::new("swap").unwrap();
+    let transactions = |filter: query::TransactionFilter| {
+        transactions_page(transactions_query(&filter), None, PAGE_SIZE, true)
+    };
+    // the store fetches one more event to tell if there is a next page
+    let events = |filter: query::EventFilter| events_page(&filter, None, PAGE_SIZE + 1, true);
+    vec![
+        QueryShape::new(
+            "transactions",
+            transactions_page(transactions::table.into_boxed(), None, PAGE_SIZE, true),
+        ),
+        QueryShape::new(
+            "transactions_by_checkpoint",
+            transactions(query::TransactionFilter::Checkpoint(1000)),
+        ),
+        QueryShape::new(
+            "transactions_by_kind",
+            transactions(transaction_kinds_filter(vec![
+                "ProgrammableTransaction".to_string()
+            ])),
+        ),
+        QueryShape::new(
+            "transactions_by_time_range",
+            transactions(query::TransactionFilter::TimeRange {
+                start_ms: START_MS,
+                end_ms: END_MS,
+            }),
+        ),
+        QueryShape::new(
+            "transactions_by_sender",
+            transactions(query::TransactionFilter::Sender(address)),
+        ),
+        QueryShape::new(
+            "transactions_by_digest",
+            transaction_by_digest_query(&TransactionDigest::ZERO.to_string()).limit(1),
+        ),
+        QueryShape::new(
+            "transactions_by_gas_object",
+            transactions_page(
+                transactions_by_gas_object_query(object_id),
+                None,
+                PAGE_SIZE,
+                true,
+            ),
+        ),
+        QueryShape::new(
+            "transactions_by_gas_owner",
+            transactions(gas_owner_filter(address, false)),
+        ),
+        QueryShape::new(
+            "sponsored_transactions",
+            transactions(sponsored_transactions_filter(address, START_MS, END_MS)),
+        ),
+        QueryShape::new(
+            "failed_transactions_by_abort",
+            transactions_page(
+                transactions_by_abort_query(object_id, module.as_str(), Some(1)),
+                None,
+                PAGE_SIZE,
+                true,
+            ),
+        ),
+        QueryShape::new(
+            "transactions_by_input_object",
+            diesel::sql_query(object_transactions_page_sql(
+                "input_objects",
+                object_id,
+                None,
+                None,
+                PAGE_SIZE,
+                true,
+            )),
+        ),
+        QueryShape::new(
+            "transactions_by_changed_object",
+            diesel::sql_query(object_transactions_page_sql(
+                "changed_objects",
+                object_id,
+                None,
+                None,
+                PAGE_SIZE,
+                true,
+            )),
+        ),
+        QueryShape::new(
+            "transactions_by_move_package",
+            diesel::sql_query(move_call_transactions_page_sql(
+                object_id, None, None, None, PAGE_SIZE, true,
+            )),
+        ),
+        QueryShape::new(
+            "transactions_by_move_function",
+            diesel::sql_query(move_call_transactions_page_sql(
+                object_id,
+                Some(&module),
+                Some(&function),
+                None,
+                PAGE_SIZE,
+                true,
+            )),
+        ),
+        QueryShape::new(
+            "transactions_by_recipient",
+            diesel::sql_query(recipient_transactions_page_sql(
+                None, address, None, PAGE_SIZE, true,
+            )),
+        ),
+        QueryShape::new(
+            "transactions_by_address",
+            diesel::sql_query(address_transactions_page_sql(
+                address, None, PAGE_SIZE, true,
+            )),
+        ),
+        QueryShape::new("events", events(query::EventFilter::And(vec![]))),
+        QueryShape::new(
+            "events_by_transaction",
+            events(query::EventFilter::Transaction(TransactionDigest::ZERO)),
+        ),
+        QueryShape::new(
+            "events_by_module",
+            events(query::EventFilter::Module {
+                package: object_id,
+                module: module.clone(),
+            }),
+        ),
+        QueryShape::new(
+            "events_by_type",
+            events(query::EventFilter::MoveEventType(
+                StructTag::from_str(&format!("{ADDRESS}::pool::SwapEvent")).unwrap(),
+            )),
+        ),
+        QueryShape::new(
+            "events_by_sender",
+            events(query::EventFilter::Sender(address)),
+        ),
+        QueryShape::new(
+            "events_by_time_range",
+            events(query::EventFilter::TimeRange {
+                start_ms: START_MS,
+                end_ms: END_MS,
+            }),
+        ),
+        QueryShape::new(
+            "objects_by_owner",
+            diesel::sql_query(
+                SuiObjectDataFilter::AddressOwner(address).to_latest_objects_sql(
+                    None,
+                    PAGE_SIZE,
+                    OBJECT_COLUMNS.to_vec(),
+                ),
+            ),
+        ),
+        QueryShape::new(
+            "objects_by_owner_and_type",
+            diesel::sql_query(
+                SuiObjectDataFilter::MatchAll(vec![
+                    SuiObjectDataFilter::AddressOwner(address),
+                    SuiObjectDataFilter::Package(object_id),
+                ])
+                .to_objects_history_sql(None, PAGE_SIZE, OBJECT_COLUMNS.to_vec()),
+            )
+            .bind::<BigInt, _>(1000i64),
+        ),
+        QueryShape::new(
+            "objects_by_type",
+            diesel::sql_query(
+                ObjectTypeQuery::from_str(&format!("{ADDRESS}::pool::Pool"))
+                    .unwrap()
+                    .to_objects_sql(None, PAGE_SIZE, OBJECT_COLUMNS.to_vec()),
+            ),
+        ),
+    ]
+}
+
+/// EXPLAIN of a query, with the binds of the query, returning its plan as JSON.
+pub(crate) struct Explain(pub(crate) Box<dyn QueryFragment<Pg> + Send>);
+
+impl QueryId for Explain {
+    type QueryId = ();
+    const HAS_STATIC_QUERY_ID: bool = false;
+}
+
+impl Query for Explain {
+    type SqlType = diesel::sql_types::Json;
+}
+
+impl QueryFragment<Pg> for Explain {
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, Pg>) -> QueryResult<()> {
+        out.push_sql("EXPLAIN (FORMAT JSON) ");
+        self.0.walk_ast(out.reborrow())
+    }
+}
+
+impl RunQueryDsl<PgConnection> for Explain {}
+
+/// A step of the plan of a query shape that reads much more of a table than its page.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlanIssue {
+    /// The whole table is read, an index on the columns of the filter may be missing.
+    SeqScan {
+        relation: String,
+        table_rows: i64,
+        filter: Option<String>,
+    },
+    /// Rows are sorted rather than read in the order of an index.
+    Sort { rows: f64, sort_key: Vec<String> },
+}
+
+impl fmt::Display for PlanIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlanIssue::SeqScan {
+                relation,
+                table_rows,
+                filter,
+            } => {
+                write!(f, "sequential scan of {relation} (~{table_rows} rows)")?;
+                if let Some(filter) = filter {
+                    write!(f, " with filter {filter}, consider an index on its columns")?;
+                }
+                Ok(())
+            }
+            PlanIssue::Sort { rows, sort_key } => write!(
+                f,
+                "sort of ~{} rows by {}, consider an index in this order",
+                *rows as i64,
+                sort_key.join(", ")
+            ),
+        }
+    }
+}
+
+/// Steps of a plan in the JSON format of EXPLAIN reading `min_rows` or more rows, with
+/// the row counts of the tables by name.
+pub fn find_plan_issues(
+    plan: &Value,
+    table_rows: &HashMap<String, i64>,
+    min_rows: i64,
+) -> Vec<PlanIssue> {
+    let mut issues = vec![];
+    // EXPLAIN (FORMAT JSON) returns a list with one object of the root plan
+    let root = plan
+        .as_array()
+        .and_then(|plans| plans.first())
+        .and_then(|plan| plan.get("Plan"))
+        .unwrap_or(plan);
+    collect_plan_issues(root, table_rows, min_rows, &mut issues);
+    issues
+}
+
+fn collect_plan_issues(
+    node: &Value,
+    table_rows: &HashMap<String, i64>,
+    min_rows: i64,
+    issues: &mut Vec<PlanIssue>,
+) {
+    let string = |key| node.get(key).and_then(Value::as_str).map(str::to_string);
+    match node.get("Node Type").and_then(Value::as_str) {
+        Some("Seq Scan") => {
+            let relation = string("Relation Name").unwrap_or_default();
+            let rows = table_rows.get(&relation).copied().unwrap_or(0);
+            if rows >= min_rows {
+                issues.push(PlanIssue::SeqScan {
+                    relation,
+                    table_rows: rows,
+                    filter: string("Filter"),
+                });
+            }
+        }
+        Some("Sort") => {
+            // rows sorted are the rows of the input of the sort
+            let rows = node
+                .get("Plans")
+                .and_then(Value::as_array)
+                .and_then(|plans| plans.first())
+                .and_then(|plan| plan.get("Plan Rows"))
+                .and_then(Value::as_f64)
+                .unwrap_or(0.0);
+            if rows >= min_rows as f64 {
+                let sort_key = node
+                    .get("Sort Key")
+                    .and_then(Value::as_array)
+                    .map(|keys| {
+                        keys.iter()
+                            .filter_map(|key| key.as_str().map(str::to_string))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                issues.push(PlanIssue::Sort { rows, sort_key });
+            }
+        }
+        _ => {}
+    }
+    if let Some(plans) = node.get("Plans").and_then(Value::as_array) {
+        for plan in plans {
+            collect_plan_issues(plan, table_rows, min_rows, issues);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use serde_json::json;
+
+    use super::{find_plan_issues, query_shapes, PlanIssue};
+
+    #[test]
+    fn test_query_shapes() {
+        let shapes = query_shapes();
+        let sender = shapes
+            .iter()
+            .find(|shape| shape.name == "transactions_by_sender")
+            .unwrap();
+        assert!(sender.sql().contains(r#""transactions"."sender" = $1"#));
+        let objects = shapes
+            .iter()
+            .find(|shape| shape.name == "objects_by_owner_and_type")
+            .unwrap();
+        assert!(objects.sql().contains("binds: [1000]"));
+        let mut names = shapes.iter().map(|shape| shape.name).collect::<Vec<_>>();
+        names.sort();
+        names.dedup();
+        assert_eq!(names.len(), shapes.len());
+    }
+
+    #[test]
+    fn test_find_plan_issues() {
+        let plan = json!([{
+            "Plan": {
+                "Node Type": "Limit",
+                "Plan Rows": 50,
+                "Plans": [{
+                    "Node Type": "Sort",
+                    "Sort Key": ["id DESC"],
+                    "Plan Rows": 20000,
+                    "Plans": [{
+                        "Node Type": "Seq Scan",
+                        "Relation Name": "transactions",
+                        "Filter": "((sender)::text = '0xabc'::text)",
+                        "Plan Rows": 20000
+                    }]
+                }]
+            }
+        }]);
+        let table_rows = HashMap::from([("transactions".to_string(), 1_000_000)]);
+        assert_eq!(
+            find_plan_issues(&plan, &table_rows, 10_000),
+            vec![
+                PlanIssue::Sort {
+                    rows: 20000.0,
+                    sort_key: vec!["id DESC".to_string()]
+                },
+                PlanIssue::SeqScan {
+                    relation: "transactions".to_string(),
+                    table_rows: 1_000_000,
+                    filter: Some("((sender)::text = '0xabc'::text)".to_string()),
+                },
+            ]
+        );
+        // small tables are fine to scan
+        let table_rows = HashMap::from([("transactions".to_string(), 100)]);
+        assert_eq!(
+            find_plan_issues(&plan, &table_rows, 10_000),
+            vec![PlanIssue::Sort {
+                rows: 20000.0,
+                sort_key: vec!["id DESC".to_string()]
+            }]
+        );
+
+        let plan = json!([{
+            "Plan": {
+                "Node Type": "Index Scan",
+                "Relation Name": "transactions",
+                "Index Name": "transactions_sender",
+                "Plan Rows": 50
+            }
+        }]);
+        assert!(find_plan_issues(&plan, &table_rows, 10_000).is_empty());
+    }
+}
@@ -0,0 +1,500 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::errors::IndexerError;
+
+// functions reading data other than their arguments, e.g. query_to_xml and ts_stat run the
+// query they are given, pg_read_file reads files of the server
+const DENIED_FUNCTION_PREFIXES: &[&str] = &["pg_", "lo_", "dblink"];
+const DENIED_FUNCTIONS: &[&str] = &["ts_stat", "set_config", "current_setting"];
+const DENIED_FUNCTION_INFIX: &str = "_to_xml";
+
+// keywords ending the FROM clause of a SELECT
+const FROM_CLAUSE_TERMINATORS: &[&str] = &[
+    "where",
+    "group",
+    "having",
+    "window",
+    "order",
+    "limit",
+    "offset",
+    "fetch",
+    "for",
+    "union",
+    "intersect",
+    "except",
+    "select",
+];
+
+/// Ad hoc SELECTs of clients over an allowlist of views, run in read only transactions with
+/// their own statement timeout and returning at most `max_rows` rows.
+///
+/// Queries run as `role`, set with `SET LOCAL ROLE` in their transaction, which is what
+/// enforces the allowlist: the role must be able to SELECT from the allowlisted views and
+/// nothing else, and must not be able to EXECUTE functions outside of the system catalogs.
+/// The store checks this when it is configured. For the indexer user `indexer` and the
+/// schema `public`, the role is provisioned with
+///
+/// ```sql
+/// CREATE ROLE readonly_sql NOLOGIN;
+/// GRANT readonly_sql TO indexer;
+/// GRANT USAGE ON SCHEMA public TO readonly_sql;
+/// GRANT SELECT ON network_metrics, daily_transaction_counts TO readonly_sql;
+/// REVOKE EXECUTE ON ALL FUNCTIONS IN SCHEMA public FROM PUBLIC;
+/// ```
+///
+/// Queries are also checked before they run, to reject what the role would fail on with a
+/// clearer error: they must be a single SELECT, optionally with a WITH clause, reading only
+/// from the allowlisted views and its own CTEs, without schema qualified relations and
+/// without the functions that read other relations or the server, see `DENIED_FUNCTIONS`.
+/// Queries are run as a subquery of a prepared statement, which Postgres rejects if it holds
+/// more than one statement.
+///
+/// At most `max_concurrent_queries` queries run at once, others are rejected.
+#[derive(Clone, Debug)]
+pub struct ReadonlySql {
+    views: BTreeSet<String>,
+    pub role: String,
+    pub max_rows: usize,
+    pub timeout: Duration,
+    pub max_concurrent_queries: usize,
+    permits: Arc<Semaphore>,
+}
+
+/// Rows of a query as JSON objects keyed by column name, `truncated` if it returned more
+/// than the row cap.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReadonlySqlResult {
+    pub rows: Vec<serde_json::Value>,
+    pub truncated: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Token {
+    // unquoted words are lowercased, as Postgres folds them
+    Word { name: String, quoted: bool },
+    Literal,
+    Punct(char),
+}
+
+impl Token {
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self, Token::Word { name, quoted: false } if name == keyword)
+    }
+
+    fn word(&self) -> Option<&str> {
+        match self {
+            Token::Word { name, .. } => Some(name),
+            _ => None,
+        }
+    }
+}
+
+impl ReadonlySql {
+    pub fn new(
+        views: impl IntoIterator<Item = String>,
+        role: String,
+        max_rows: usize,
+        timeout: Duration,
+        max_concurrent_queries: usize,
+    ) -> Self {
+        Self {
+            views: views.into_iter().map(|v| v.to_lowercase()).collect(),
+            role,
+            max_rows,
+            timeout,
+            max_concurrent_queries,
+            permits: Arc::new(Semaphore::new(max_concurrent_queries)),
+        }
+    }
+
+    pub fn views(&self) -> &BTreeSet<String> {
+        &self.views
+    }
+
+    /// Permit to run a query, held until it is done. Queries beyond the cap are rejected
+    /// rather than queued, so that slow queries do not pile up behind each other.
+    pub fn try_acquire(&self) -> Result<OwnedSemaphorePermit, IndexerError> {
+        self.permits.clone().try_acquire_owned().map_err(|_| {
+            IndexerError::TooManyRequests(format!(
+                "{} read only SQL queries are already running",
+                self.max_concurrent_queries
+            ))
+        })
+    }
+
+    /// Checks `sql` against the restrictions on queries, and returns it without its
+    /// trailing semicolon to be run as a subquery.
+    pub fn check<'a>(&self, sql: &'a str) -> Result<&'a str, IndexerError> {
+        let (tokens, end) = tokenize(sql)?;
+        match tokens.first() {
+            Some(token) if token.is_keyword("select") || token.is_keyword("with") => {}
+            _ => return Err(invalid("Only SELECT queries are allowed")),
+        }
+        let ctes = cte_names(&tokens);
+        // (is the FROM clause of the SELECT at this depth open, is this depth a function call)
+        let mut depths = vec![(false, false)];
+        let mut expect_relation = false;
+        for (i, token) in tokens.iter().enumerate() {
+            let next = tokens.get(i + 1);
+            if expect_relation {
+                if token.is_keyword("lateral") {
+                    continue;
+                }
+                expect_relation = false;
+                if *token == Token::Punct('(') {
+                    // subquery, checked as the rest of the query
+                } else if let Some(name) = token.word() {
+                    let is_cte = ctes.get(name).map_or(false, |from| i >= *from);
+                    if !self.views.contains(name) && !is_cte {
+                        return Err(invalid(&format!(
+                            "Relation {name} is not among the allowed views {:?}",
+                            self.views
+                        )));
+                    }
+                    if matches!(next, Some(Token::Punct('.')) | Some(Token::Punct('('))) {
+                        return Err(invalid(&format!(
+                            "Relation {name} must be an unqualified view name"
+                        )));
+                    }
+                    continue;
+                } else {
+                    return Err(invalid("Expected a view name after FROM or JOIN"));
+                }
+            }
+            match token {
+                Token::Punct('(') => {
+                    let is_call = i > 0
+                        && matches!(tokens[i - 1], Token::Word { .. })
+                        && !next.map_or(false, |t| {
+                            t.is_keyword("select") || t.is_keyword("with") || t.is_keyword("values")
+                        });
+                    depths.push((false, is_call));
+                }
+                Token::Punct(')') => {
+                    if depths.len() > 1 {
+                        depths.pop();
+                    }
+                }
+                Token::Punct(',') => {
+                    expect_relation = depths.last().unwrap().0;
+                }
+                Token::Word { name, quoted } => {
+                    if next == Some(&Token::Punct('(')) && is_denied_function(name) {
+                        return Err(invalid(&format!("Function {name} is not allowed")));
+                    }
+                    if *quoted {
+                        continue;
+                    }
+                    let (from_open, is_call) = depths.last_mut().unwrap();
+                    match name.as_str() {
+                        // e.g. extract(epoch FROM ..) and a IS DISTINCT FROM b
+                        "from" if *is_call || (i > 0 && tokens[i - 1].is_keyword("distinct")) => {}
+                        "from" | "join" => {
+                            *from_open = true;
+                            expect_relation = true;
+                        }
+                        "table" => expect_relation = true,
+                        name if FROM_CLAUSE_TERMINATORS.contains(&name) => *from_open = false,
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+        }
+        if expect_relation {
+            return Err(invalid("Expected a view name after FROM or JOIN"));
+        }
+        Ok(sql[..end].trim_end())
+    }
+}
+
+fn invalid(msg: &str) -> IndexerError {
+    IndexerError::InvalidArgumentError(format!("Invalid read only SQL query: {msg}"))
+}
+
+fn is_denied_function(name: &str) -> bool {
+    DENIED_FUNCTION_PREFIXES
+        .iter()
+        .any(|prefix| name.starts_with(prefix))
+        || DENIED_FUNCTIONS.contains(&name)
+        || name.contains(DENIED_FUNCTION_INFIX)
+}
+
+// CTEs of the WITH clause of the query, by the index of the first token they are visible
+// from. CTEs of a RECURSIVE clause are visible in the whole clause, other CTEs only after
+// their definition, e.g. `transactions` is the table in the definition of
+// `WITH transactions AS (SELECT * FROM transactions)`. CTEs of nested WITH clauses are not
+// returned, so that relations of their scopes are checked against the allowlist.
+fn cte_names(tokens: &[Token]) -> BTreeMap<String, usize> {
+    let mut names = BTreeMap::new();
+    if !tokens.first().map_or(false, |t| t.is_keyword("with")) {
+        return names;
+    }
+    let recursive = tokens.get(1).map_or(false, |t| t.is_keyword("recursive"));
+    let mut i = if recursive { 2 } else { 1 };
+    while let Some(name) = tokens.get(i).and_then(Token::word) {
+        i += 1;
+        if tokens.get(i) == Some(&Token::Punct('(')) {
+            i = skip_parens(tokens, i);
+        }
+        if !tokens.get(i).map_or(false, |t| t.is_keyword("as")) {
+            break;
+        }
+        i += 1;
+        while tokens.get(i).map_or(false, |t| {
+            t.is_keyword("not") || t.is_keyword("materialized")
+        }) {
+            i += 1;
+        }
+        if tokens.get(i) != Some(&Token::Punct('(')) {
+            break;
+        }
+        let definition_end = skip_parens(tokens, i);
+        names
+            .entry(name.to_string())
+            .or_insert(if recursive { 0 } else { definition_end });
+        i = definition_end;
+        if tokens.get(i) != Some(&Token::Punct(',')) {
+            break;
+        }
+        i += 1;
+    }
+    names
+}
+
+// index after the parenthesis closing the one at `start`
+fn skip_parens(tokens: &[Token], start: usize) -> usize {
+    let mut depth = 0;
+    for (i, token) in tokens.iter().enumerate().skip(start) {
+        match token {
+            Token::Punct('(') => depth += 1,
+            Token::Punct(')') => {
+                depth -= 1;
+                if depth == 0 {
+                    return i + 1;
+                }
+            }
+            _ => {}
+        }
+    }
+    tokens.len()
+}
+
+// Splits `sql` into words, literals and punctuation, skipping comments. Returns the tokens
+// and the end of the statement, i.e. the position of its semicolon if any.
+fn tokenize(sql: &str) -> Result<(Vec<Token>, usize), IndexerError> {
+    let chars = sql.char_indices().collect::<Vec<_>>();
+    let at = |i: usize| chars.get(i).map(|(_, c)| *c);
+    let mut tokens = vec![];
+    let mut end = None;
+    let mut i = 0;
+    while let Some(c) = at(i) {
+        let starts_comment = matches!((c, at(i + 1)), ('-', Some('-')) | ('/', Some('*')));
+        if end.is_some() && !c.is_whitespace() && !starts_comment {
+            return Err(invalid("Only a single statement is allowed"));
+        }
+        match c {
+            c if c.is_whitespace() => i += 1,
+            '-' if at(i + 1) == Some('-') => {
+                while at(i).map_or(false, |c| c != '\n') {
+                    i += 1;
+                }
+            }
+            // block comments nest in Postgres
+            '/' if at(i + 1) == Some('*') => {
+                let mut depth = 0;
+                loop {
+                    match (at(i), at(i + 1)) {
+                        (Some('/'), Some('*')) => {
+                            depth += 1;
+                            i += 2;
+                        }
+                        (Some('*'), Some('/')) => {
+                            depth -= 1;
+                            i += 2;
+                            if depth == 0 {
+                                break;
+                            }
+                        }
+                        (Some(_), _) => i += 1,
+                        (None, _) => return Err(invalid("Unterminated comment")),
+                    }
+                }
+            }
+            '\'' => {
+                i = skip_quoted(&chars, i, '\'', false)?;
+                tokens.push(Token::Literal);
+            }
+            '"' => {
+                let start = i + 1;
+                i = skip_quoted(&chars, i, '"', false)?;
+                let name = chars[start..i - 1]
+                    .iter()
+                    .map(|(_, c)| *c)
+                    .collect::<String>()
+                    .replace("\"\"", "\"");
+                tokens.push(Token::Word { name, quoted: true });
+            }
+            '$' => return Err(invalid("Dollar quoting and parameters are not allowed")),
+            ';' => {
+                end = Some(chars[i].0);
+                i += 1;
+            }
+            c if c.is_ascii_digit() => {
+                while at(i).map_or(false, |c| c.is_alphanumeric() || c == '_' || c == '.') {
+                    i += 1;
+                }
+                tokens.push(Token::Literal);
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let start = i;
+                while at(i).map_or(false, |c| c.is_alphanumeric() || c == '_' || c == '$') {
+                    i += 1;
+                }
+                let name = chars[start..i]
+                    .iter()
+                    .map(|(_, c)| *c)
+                    .collect::<String>()
+                    .to_lowercase();
+                match (name.as_str(), at(i)) {
+                    ("u", Some('&')) => {
+                        return Err(invalid("Unicode escapes are not allowed"));
+                    }
+                    // escape string, backslashes escape quotes
+                    ("e", Some('\'')) => {
+                        i = skip_quoted(&chars, i, '\'', true)?;
+                        tokens.push(Token::Literal);
+                    }
+                    ("b" | "x" | "n", Some('\'')) => {
+                        i = skip_quoted(&chars, i, '\'', false)?;
+                        tokens.push(Token::Literal);
+                    }
+                    _ => tokens.push(Token::Word {
+                        name,
+                        quoted: false,
+                    }),
+                }
+            }
+            c => {
+                tokens.push(Token::Punct(c));
+                i += 1;
+            }
+        }
+    }
+    Ok((tokens, end.unwrap_or(sql.len())))
+}
+
+// index after the closing quote of the quoted string or identifier starting at `start`,
+// doubled quotes standing for a quote
+fn skip_quoted(
+    chars: &[(usize, char)],
+    start: usize,
+    quote: char,
+    backslash_escapes: bool,
+) -> Result<usize, IndexerError> {
+    let mut i = start + 1;
+    loop {
+        match chars.get(i).map(|(_, c)| *c) {
+            Some('\\') if backslash_escapes => i += 2,
+            Some(c) if c == quote => {
+                if chars.get(i + 1).map(|(_, c)| *c) == Some(quote) {
+                    i += 2;
+                } else {
+                    return Ok(i + 1);
+                }
+            }
+            Some(_) => i += 1,
+            None => return Err(invalid("Unterminated quoted string or identifier")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::ReadonlySql;
+
+    fn readonly_sql() -> ReadonlySql {
+        ReadonlySql::new(
+            [
+                "network_metrics".to_string(),
+                "Daily_Transaction_Counts".to_string(),
+            ],
+            "readonly_sql".to_string(),
+            100,
+            Duration::from_secs(1),
+            1,
+        )
+    }
+
+    #[test]
+    fn test_allowed_queries() {
+        let sql = readonly_sql();
+        for query in [
+            "SELECT * FROM network_metrics",
+            "select day, count from DAILY_TRANSACTION_COUNTS d where d.count > 10 order by day;",
+            "SELECT * FROM network_metrics n, daily_transaction_counts d  ; -- done",
+            "SELECT * FROM network_metrics JOIN daily_transaction_counts ON true",
+            "WITH recent AS (SELECT * FROM daily_transaction_counts ORDER BY day DESC LIMIT 7) \
+             SELECT avg(count) FROM recent",
+            "WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n WHERE i < 10) \
+             SELECT * FROM n",
+            "SELECT extract(epoch FROM day), 'FROM transactions' FROM daily_transaction_counts",
+            "SELECT day FROM daily_transaction_counts WHERE day IS DISTINCT FROM NULL",
+            "SELECT * FROM (SELECT * FROM network_metrics) AS m /* FROM objects */",
+            "SELECT E'\\' FROM objects', \"count\" FROM daily_transaction_counts",
+        ] {
+            assert!(sql.check(query).is_ok(), "{query}");
+        }
+        assert_eq!(
+            "SELECT * FROM network_metrics",
+            sql.check("SELECT * FROM network_metrics ; ").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_concurrent_queries_are_capped() {
+        let sql = readonly_sql();
+        let permit = sql.try_acquire().unwrap();
+        assert!(sql.clone().try_acquire().is_err());
+        drop(permit);
+        assert!(sql.try_acquire().is_ok());
+    }
+
+    #[test]
+    fn test_denied_queries() {
+        let sql = readonly_sql();
+        for query in [
+            "SELECT * FROM transactions",
+            "DELETE FROM network_metrics",
+            "TABLE network_metrics",
+            "SELECT * FROM network_metrics; DROP TABLE transactions",
+            "SELECT * FROM network_metrics, transactions",
+            "SELECT * FROM network_metrics JOIN objects ON true",
+            "SELECT * FROM network_metrics WHERE EXISTS (SELECT 1 FROM transactions)",
+            "WITH transactions AS (SELECT * FROM transactions) SELECT * FROM transactions",
+            "SELECT * FROM (WITH t AS (SELECT 1) SELECT * FROM t) a, transactions",
+            "SELECT * FROM public.network_metrics",
+            "SELECT * FROM generate_series(1, 10)",
+            "SELECT pg_read_file('/etc/passwd') FROM network_metrics",
+            "SELECT query_to_xml('SELECT * FROM transactions', true, true, '') \
+             FROM network_metrics",
+            "SELECT $$x$$ FROM network_metrics",
+            "SELECT * FROM \"transactions\"",
+            "SELECT * FROM network_metrics /* unterminated",
+            "SELECT 'unterminated FROM network_metrics",
+            "SELECT * FROM",
+        ] {
+            assert!(sql.check(query).is_err(), "{query}");
+        }
+    }
+}
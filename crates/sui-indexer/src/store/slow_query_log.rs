@@ -0,0 +1,78 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use prometheus::IntCounterVec;
+use tracing::warn;
+
+// parameters are cut to this many chars in the log, e.g. long lists of digests
+const MAX_LOGGED_PARAMS_LENGTH: usize = 256;
+
+/// Logs reads of the RPC APIs running longer than a threshold along with their
+/// parameters, and counts them by store method, to find the access patterns lacking an
+/// index.
+#[derive(Clone)]
+pub(crate) struct SlowQueryLog {
+    threshold: Duration,
+    slow_reads: IntCounterVec,
+}
+
+impl SlowQueryLog {
+    pub fn new(threshold: Duration, slow_reads: IntCounterVec) -> Self {
+        Self {
+            threshold,
+            slow_reads,
+        }
+    }
+
+    pub fn record(&self, method: &str, params: &str, elapsed: Duration) {
+        if elapsed < self.threshold {
+            return;
+        }
+        self.slow_reads.with_label_values(&[method]).inc();
+        warn!(
+            method,
+            elapsed_ms = elapsed.as_millis() as u64,
+            params = sanitize_params(params),
+            "Slow store read"
+        );
+    }
+}
+
+// Parameters come from RPC clients, so control characters are escaped to keep them from
+// forging log lines, and the length is bounded.
+fn sanitize_params(params: &str) -> String {
+    let mut sanitized = String::new();
+    for (i, c) in params.chars().enumerate() {
+        if i == MAX_LOGGED_PARAMS_LENGTH {
+            sanitized.push_str("...");
+            break;
+        }
+        if c.is_control() {
+            sanitized.extend(c.escape_default());
+        } else {
+            sanitized.push(c);
+        }
+    }
+    sanitized
+}
+
+#[cfg(test)]
+mod test {
+    use super::{sanitize_params, MAX_LOGGED_PARAMS_LENGTH};
+
+    #[test]
+    fn test_sanitize_params() {
+        assert_eq!(
+            sanitize_params("Some(\"0x2\"), 10"),
+            "Some(\"0x2\"), 10".to_string()
+        );
+        assert_eq!(sanitize_params("a\nb\u{1b}"), "a\\nb\\u{1b}".to_string());
+
+        let long = "é".repeat(MAX_LOGGED_PARAMS_LENGTH + 1);
+        let sanitized = sanitize_params(&long);
+        assert!(sanitized.ends_with("..."));
+        assert_eq!(sanitized.chars().count(), MAX_LOGGED_PARAMS_LENGTH + 3);
+    }
+}
@@ -32,7 +32,10 @@ pub async fn start_test_indexer(
     let registry = Registry::default();
     let indexer_metrics = IndexerMetrics::new(&registry);
 
-    let store = PgIndexerStore::new(blocking_pool, indexer_metrics.clone());
+    let mut store = PgIndexerStore::new(blocking_pool, indexer_metrics.clone());
+    if config.allow_db_reader_writes {
+        store = store.with_read_path_writes();
+    }
     let store_clone = store.clone();
     let handle = tokio::spawn(async move {
         Indexer::start(&config, &registry, store_clone, indexer_metrics, None).await
@@ -31,11 +31,27 @@ pub fn reset_database(conn: &mut PgPoolConnection, drop_all: bool) -> Result<(),
     Ok(())
 }
 
+/// Creates `schema` if it does not exist, returns whether it was created.
+pub fn create_schema_if_not_exists(
+    conn: &mut PgConnection,
+    schema: &str,
+) -> Result<bool, diesel::result::Error> {
+    let exists = diesel::select(diesel::dsl::sql::<diesel::sql_types::Bool>(&format!(
+        "EXISTS (SELECT 1 FROM pg_namespace WHERE nspname = '{schema}')"
+    )))
+    .get_result::<bool>(conn)?;
+    if !exists {
+        info!("Creating schema {schema}");
+        diesel::sql_query(format!("CREATE SCHEMA \"{schema}\"")).execute(conn)?;
+    }
+    Ok(!exists)
+}
+
 pub fn drop_all_tables(conn: &mut PgConnection) -> Result<(), diesel::result::Error> {
     info!("Dropping all tables in the database");
     let table_names: Vec<String> = diesel::dsl::sql::<diesel::sql_types::Text>(
         "
-        SELECT tablename FROM pg_tables WHERE schemaname = 'public'
+        SELECT tablename FROM pg_tables WHERE schemaname = current_schema()
     ",
     )
     .load(conn)?;
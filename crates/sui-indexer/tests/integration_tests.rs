@@ -4,47 +4,100 @@
 // integration test with standalone postgresql database
 #[cfg(feature = "pg_integration")]
 pub mod pg_integration_test {
-    use diesel::RunQueryDsl;
+    use diesel::connection::SimpleConnection;
+    use diesel::pg::PgConnection;
+    use diesel::sql_types::Text;
+    use diesel::{ExpressionMethods, QueryDsl, QueryableByName, RunQueryDsl};
+    use fastcrypto::traits::ToFromBytes;
     use futures::future::join_all;
     use jsonrpsee::http_client::{HttpClient, HttpClientBuilder};
     use move_core_types::ident_str;
     use move_core_types::identifier::Identifier;
     use move_core_types::language_storage::StructTag;
     use move_core_types::parser::parse_struct_tag;
+    use move_core_types::value::{MoveStruct, MoveStructLayout, MoveValue};
     use ntest::timeout;
+    use object_store::memory::InMemory;
+    use std::collections::BTreeMap;
     use std::env;
     use std::str::FromStr;
+    use std::sync::Arc;
+    use std::time::Duration;
     use sui_test_transaction_builder::{
         create_devnet_nft, delete_devnet_nft, publish_nfts_package,
     };
     use tokio::task::JoinHandle;
 
+    use prometheus::Registry;
+    use sui_indexer::client::IndexerClient;
     use sui_indexer::errors::IndexerError;
+    use sui_indexer::framework::endpoints::FullNodeEndpoints;
+    use sui_indexer::handlers::epoch_repair::repair_missing_epochs;
+    use sui_indexer::metrics::IndexerMetrics;
+    use sui_indexer::models::addresses::CheckpointAddressActivity;
+    use sui_indexer::models::api_keys::{hash_api_key, ApiKey};
+    use sui_indexer::models::checkpoint_proofs::CheckpointProof;
+    use sui_indexer::models::checkpoint_queue::{QueueKind, QueuedCheckpoint};
+    use sui_indexer::models::checkpoints::{Checkpoint, CheckpointTxDigest};
+    use sui_indexer::models::epoch::DBEpochInfo;
+    use sui_indexer::models::events::Event;
+    use sui_indexer::models::hot_entities::CheckpointHotEntities;
     use sui_indexer::models::objects::{
         compose_object_bulk_insert_query, compose_object_bulk_insert_update_query,
-        filter_latest_objects, NamedBcsBytes, Object, ObjectStatus,
+        filter_latest_objects, DeletedObject, NamedBcsBytes, Object, ObjectStatus,
     };
     use sui_indexer::models::owners::OwnerType;
-    use sui_indexer::schema::objects;
-    use sui_indexer::store::{IndexerStore, PgIndexerStore};
+    use sui_indexer::models::ownership_history::OwnershipTransition;
+    use sui_indexer::models::protocol_configs::DBProtocolConfig;
+    use sui_indexer::models::reexecution::ReexecutionMismatch;
+    use sui_indexer::models::retention::PrunedTable;
+    use sui_indexer::models::system_state::DBSystemStateSummary;
+    use sui_indexer::models::transaction_index::{
+        ChangedObject, InputObject, MoveCall, Recipient, TxCommand, TxDependency,
+    };
+    use sui_indexer::models::transactions::{Transaction, TransactionJson};
+    use sui_indexer::models::transfers::Transfer;
+    use sui_indexer::models::tx_calls::TxCall;
+    use sui_indexer::models::validator_participation::CheckpointSigners;
+    use sui_indexer::query::{ObjectFilter, TransactionFilter as StoreTransactionFilter};
+    use sui_indexer::schema::{
+        api_keys, changed_objects, checkpoint_proofs, checkpoint_tx_digests, checkpoints, epochs,
+        events, input_objects, move_calls, object_ownership_history, object_type_counts, objects,
+        protocol_configs, recipients, transactions, transactions_json, transfers, tx_calls,
+        tx_commands, tx_dependencies,
+    };
+    use sui_indexer::store::{
+        BlobStore, IndexerStore, PgIndexerStore, ReadonlySql, TemporaryEpochStore,
+        TransactionObjectChanges,
+    };
     use sui_indexer::test_utils::{start_test_indexer, SuiTransactionBlockResponseBuilder};
-    use sui_indexer::{get_pg_pool_connection, new_pg_connection_pool, IndexerConfig};
+    use sui_indexer::utils::{create_schema_if_not_exists, reset_database};
+    use sui_indexer::{
+        get_pg_pool_connection, new_pg_connection_in_schema, new_pg_connection_pool,
+        new_pg_connection_pool_in_schema, IndexerConfig,
+    };
     use sui_json_rpc::api::ExtendedApiClient;
+    use sui_json_rpc::api::GovernanceReadApiClient;
     use sui_json_rpc::api::IndexerApiClient;
     use sui_json_rpc::api::{ReadApiClient, TransactionBuilderClient, WriteApiClient};
     use sui_json_rpc_types::{
         CheckpointId, EventFilter, SuiMoveObject, SuiObjectData, SuiObjectDataFilter,
         SuiObjectDataOptions, SuiObjectResponse, SuiObjectResponseQuery, SuiParsedMoveObject,
-        SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
-        SuiTransactionBlockResponseQuery, TransactionBlockBytes, TransactionFilter,
+        SuiRawData, SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
+        SuiTransactionBlockResponseQuery, TransactionBlockBytes, TransactionFilter, WatchedEntity,
+        WatchlistEntry,
     };
-    use sui_types::base_types::{ObjectID, SuiAddress};
+    use sui_types::base_types::{AuthorityName, ObjectID, SequenceNumber, SuiAddress};
     use sui_types::digests::{ObjectDigest, TransactionDigest};
+    use sui_types::effects::TransactionEffectsAPI;
     use sui_types::error::SuiObjectResponseError;
     use sui_types::gas_coin::GasCoin;
     use sui_types::object::ObjectFormatOptions;
     use sui_types::quorum_driver_types::ExecuteTransactionRequestType;
+    use sui_types::storage::WriteKind;
+    use sui_types::sui_system_state::sui_system_state_summary::SuiSystemStateSummary;
     use sui_types::transaction::TEST_ONLY_GAS_UNIT_FOR_TRANSFER;
+    use sui_types::SUI_CLOCK_OBJECT_ID;
     use test_cluster::{TestCluster, TestClusterBuilder};
 
     const WAIT_UNTIL_TIME_LIMIT: u64 = 60;
@@ -528,9 +581,11 @@ pub mod pg_integration_test {
         let (package_id, _, publish_digest) = publish_nfts_package(context).await;
         wait_until_transaction_synced(&store, publish_digest.base58_encode().as_str()).await;
 
+        let mut digests = vec![];
         for _ in 0..5 {
             let (sender, object_id, digest) = create_devnet_nft(context, package_id).await;
             wait_until_transaction_synced(&store, digest.base58_encode().as_str()).await;
+            digests.push(digest);
             let obj_resp = indexer_rpc_client
                 .get_object(object_id, None)
                 .await
@@ -551,10 +606,46 @@ pub mod pg_integration_test {
             module: Identifier::new("devnet_nft").unwrap(),
         };
         let query_response = indexer_rpc_client
-            .query_events(filter_on_module, None, None, None)
+            .query_events(filter_on_module.clone(), None, None, None)
             .await?;
         assert_eq!(query_response.data.len(), 5);
 
+        // events are in the order the transactions emitting them were executed in, which
+        // is the order they were sent in here, both ways
+        let event_ids: Vec<_> = query_response.data.iter().map(|e| e.id.clone()).collect();
+        let descending_response = indexer_rpc_client
+            .query_events(filter_on_module.clone(), None, None, Some(true))
+            .await?;
+        let mut descending_ids: Vec<_> = descending_response
+            .data
+            .iter()
+            .map(|e| e.id.clone())
+            .collect();
+        descending_ids.reverse();
+        assert_eq!(event_ids, descending_ids);
+        assert_eq!(
+            event_ids.iter().map(|id| id.tx_digest).collect::<Vec<_>>(),
+            digests,
+        );
+
+        // the same order by pages of the sequence cursor, which increases along them
+        let mut sequenced_ids = vec![];
+        let mut sequences = vec![];
+        let mut cursor = None;
+        loop {
+            let page = indexer_rpc_client
+                .query_events_by_sequence(filter_on_module.clone(), cursor, Some(2), None)
+                .await?;
+            sequenced_ids.extend(page.data.iter().map(|e| e.id.clone()));
+            sequences.extend(page.next_cursor);
+            cursor = page.next_cursor;
+            if !page.has_next_page {
+                break;
+            }
+        }
+        assert_eq!(event_ids, sequenced_ids);
+        assert!(sequences.windows(2).all(|pair| pair[0] < pair[1]));
+
         let mint_nft_event = &format!("{package_id}::devnet_nft::MintNFTEvent");
         let filter = get_filter_on_event_type(mint_nft_event);
         let query_response = indexer_rpc_client
@@ -863,34 +954,58 @@ pub mod pg_integration_test {
 
     #[tokio::test]
     #[timeout(60000)]
-    async fn test_get_last_checkpoint_of_epoch() {
-        let (test_cluster, _, store, handle) = start_test_cluster(Some(20000)).await;
-        // Allow indexer to sync geneis epoch
+    async fn test_get_validator_participation() {
+        let (test_cluster, indexer_rpc_client, store, handle) = start_test_cluster(None).await;
+        wait_until_next_checkpoint(&store).await;
         wait_until_next_checkpoint(&store).await;
-        wait_until_next_epoch(&store).await;
-        let current_epoch = store.get_current_epoch().await.unwrap();
-        let prev_epoch_last_checkpoint_id = current_epoch.first_checkpoint_id - 1;
-        wait_for_checkpoint(&store, current_epoch.first_checkpoint_id as i64).await;
-
-        let checkpoint = store
-            .get_checkpoint(CheckpointId::SequenceNumber(prev_epoch_last_checkpoint_id))
-            .await
-            .unwrap();
-        assert_eq!(checkpoint.epoch, current_epoch.epoch - 1);
-        assert_eq!(checkpoint.sequence_number, prev_epoch_last_checkpoint_id);
-        assert!(checkpoint.end_of_epoch_data.is_some());
-
-        assert_eq!(checkpoint.epoch, current_epoch.epoch - 1);
-        assert_eq!(checkpoint.sequence_number, prev_epoch_last_checkpoint_id);
 
-        // cross check with FN
-        let fn_cp = test_cluster
+        let system_state = test_cluster
             .rpc_client()
-            .get_checkpoint(CheckpointId::SequenceNumber(prev_epoch_last_checkpoint_id))
+            .get_latest_sui_system_state()
             .await
             .unwrap();
-
-        assert_eq!(fn_cp, checkpoint);
+        let mut participation = vec![];
+        let mut cursor = None;
+        loop {
+            let page = indexer_rpc_client
+                .get_validator_participation(0.into(), cursor, Some(2))
+                .await
+                .unwrap();
+            assert!(page.data.len() <= 2);
+            participation.extend(page.data);
+            if !page.has_next_page {
+                break;
+            }
+            cursor = page.next_cursor;
+        }
+        assert!(indexer_rpc_client
+            .get_validator_participation(0.into(), Some("not a cursor".to_string()), None)
+            .await
+            .is_err());
+        assert!(participation
+            .iter()
+            .enumerate()
+            .all(|(index, p)| p.committee_index == index as u64));
+        let mut validators = participation
+            .iter()
+            .map(|p| p.sui_address)
+            .collect::<Vec<_>>();
+        validators.sort();
+        let mut active_validators = system_state
+            .active_validators
+            .iter()
+            .map(|v| v.sui_address)
+            .collect::<Vec<_>>();
+        active_validators.sort();
+        assert_eq!(active_validators, validators);
+        // every committed checkpoint is signed by a quorum of the committee
+        let epoch_checkpoints = participation[0].epoch_checkpoints;
+        assert!(epoch_checkpoints > 0);
+        let signatures: u64 = participation.iter().map(|p| p.checkpoints_signed).sum();
+        assert!(signatures >= epoch_checkpoints * (2 * validators.len() as u64 / 3 + 1));
+        assert!(participation
+            .iter()
+            .all(|p| p.checkpoints_signed <= epoch_checkpoints));
 
         drop(handle);
         drop(test_cluster);
@@ -898,165 +1013,1581 @@ pub mod pg_integration_test {
 
     #[tokio::test]
     #[timeout(60000)]
-    async fn test_query_objects_cross_check() -> Result<(), anyhow::Error> {
-        let (test_cluster, indexer_rpc_client, store, _handle) = start_test_cluster(None).await;
-        // Allow indexer to sync genesis
+    async fn test_get_transactions_by_gas_payment() -> Result<(), anyhow::Error> {
+        let (mut test_cluster, indexer_rpc_client, store, handle) = start_test_cluster(None).await;
         wait_until_next_checkpoint(&store).await;
-        let address = test_cluster.get_address_0();
-        let fullnode_client = test_cluster.rpc_client();
 
-        let object_from_fullnode = fullnode_client
-            .get_owned_objects(address, None, None, None)
-            .await
-            .unwrap();
+        // both transfers pay with the same gas object, the last one of the sender
+        let (first_response, sender, recipient, gas_objects) =
+            execute_simple_transfer(&mut test_cluster, &indexer_rpc_client).await?;
+        let gas = *gas_objects.last().unwrap();
+        let second_response = sign_and_transfer_object(
+            &test_cluster,
+            &indexer_rpc_client,
+            sender,
+            recipient,
+            gas_objects[1],
+            Some(gas),
+        )
+        .await?;
+        wait_until_transaction_synced(&store, second_response.digest.base58_encode().as_str())
+            .await;
 
-        let object_from_indexer = indexer_rpc_client
-            .query_objects(
-                SuiObjectResponseQuery::new_with_filter(SuiObjectDataFilter::AddressOwner(address)),
+        let mut digests = vec![];
+        let mut cursor = None;
+        loop {
+            let page = indexer_rpc_client
+                .get_transaction_blocks_by_gas_object(gas, None, cursor, Some(1), None)
+                .await?;
+            assert!(page.data.len() <= 1);
+            digests.extend(page.data.iter().map(|tx| tx.digest));
+            if !page.has_next_page {
+                break;
+            }
+            cursor = page.next_cursor;
+        }
+        assert_eq!(vec![first_response.digest, second_response.digest], digests);
+
+        let page = indexer_rpc_client
+            .get_transaction_blocks_by_gas_object(
+                gas,
+                Some(SuiTransactionBlockResponseOptions::new().with_effects()),
                 None,
                 None,
+                Some(true),
             )
-            .await
-            .unwrap();
-        assert_eq!(object_from_fullnode.data, object_from_indexer.data);
+            .await?;
+        assert_eq!(second_response.digest, page.data[0].digest);
+        assert!(page.data[0].effects.is_some());
+
+        // the sender paid for its own transactions, none of which is sponsored
+        let paid = indexer_rpc_client
+            .get_transaction_blocks_by_gas_owner(sender, None, None, None, None, None)
+            .await?
+            .data
+            .into_iter()
+            .map(|tx| tx.digest)
+            .collect::<Vec<_>>();
+        assert!(paid.contains(&first_response.digest) && paid.contains(&second_response.digest));
+        let sponsored = indexer_rpc_client
+            .get_transaction_blocks_by_gas_owner(sender, Some(true), None, None, None, None)
+            .await?;
+        assert!(sponsored.data.is_empty());
+
+        drop(handle);
+        drop(test_cluster);
         Ok(())
     }
 
     #[tokio::test]
     #[timeout(60000)]
-    async fn test_query_objects() -> Result<(), anyhow::Error> {
-        let (_test_cluster, indexer_rpc_client, store, _handle) = start_test_cluster(None).await;
-        // Allow indexer to sync genesis
+    async fn test_get_hot_objects() -> Result<(), anyhow::Error> {
+        let (test_cluster, indexer_rpc_client, store, handle) = start_test_cluster(None).await;
+        wait_until_next_checkpoint(&store).await;
         wait_until_next_checkpoint(&store).await;
 
-        let all_coins = indexer_rpc_client
-            .query_objects(
-                SuiObjectResponseQuery::new_with_filter(SuiObjectDataFilter::StructType(
-                    parse_struct_tag("0x2::coin::Coin<0x2::sui::SUI>").unwrap(),
-                )),
-                None,
-                None,
-            )
+        // the clock is touched by the consensus commit prologue of every checkpoint
+        let hot_objects = indexer_rpc_client
+            .get_hot_objects("1h".to_string(), Some(3))
+            .await?;
+        assert!(!hot_objects.is_empty() && hot_objects.len() <= 3);
+        assert!(hot_objects.iter().any(|o| o.id == SUI_CLOCK_OBJECT_ID));
+        assert!(hot_objects.windows(2).all(|w| w[0].count >= w[1].count));
+        let hot_objects_24h = indexer_rpc_client
+            .get_hot_objects("24h".to_string(), Some(3))
+            .await?;
+        assert!(hot_objects_24h.iter().any(|o| o.id == SUI_CLOCK_OBJECT_ID));
+
+        assert!(indexer_rpc_client
+            .get_hot_objects("7d".to_string(), None)
             .await
-            .unwrap();
-        assert_eq!(25, all_coins.data.len());
+            .is_err());
+
+        drop(handle);
+        drop(test_cluster);
         Ok(())
     }
 
     #[tokio::test]
     #[timeout(60000)]
-    async fn pg_parameter_limit_test() {
-        // Helps clear/build the database
-        start_test_cluster(None).await;
+    async fn test_watchlist_api() -> Result<(), anyhow::Error> {
+        let (test_cluster, indexer_rpc_client, store, handle) = start_test_cluster(None).await;
+        wait_until_next_checkpoint(&store).await;
 
-        let pg_host = env::var("POSTGRES_HOST").unwrap_or_else(|_| "localhost".into());
-        let pg_port = env::var("POSTGRES_PORT").unwrap_or_else(|_| "32770".into());
-        let pw = env::var("POSTGRES_PASSWORD").unwrap_or_else(|_| "postgrespw".into());
-        let db_url = format!("postgres://postgres:{pw}@{pg_host}:{pg_port}");
-        let pg_connection_pool = new_pg_connection_pool(&db_url).unwrap();
-        let mut pg_pool_conn = get_pg_pool_connection(&pg_connection_pool).unwrap();
+        let address = SuiAddress::random_for_testing_only();
+        let object_id = ObjectID::random();
+        let entries = vec![
+            WatchlistEntry {
+                entity: WatchedEntity::Address(address),
+                rule: "deposits".to_string(),
+            },
+            WatchlistEntry {
+                entity: WatchedEntity::Address(address),
+                rule: "withdrawals".to_string(),
+            },
+            WatchlistEntry {
+                entity: WatchedEntity::Object(object_id),
+                rule: "pool".to_string(),
+            },
+        ];
+        // managed through the admin server of the writer
+        for entry in &entries {
+            store.persist_watchlist_entry(entry.clone()).await?;
+        }
+        // adding an entry again is a no-op
+        store.persist_watchlist_entry(entries[0].clone()).await?;
 
-        let lot_of_data = (1..10000)
-            .map(|_| Object {
-                epoch: 0,
-                checkpoint: 0,
-                object_id: ObjectID::random().to_string(),
-                version: 0,
-                object_digest: "".to_string(),
-                owner_type: OwnerType::AddressOwner,
-                owner_address: None,
-                initial_shared_version: None,
-                previous_transaction: "".to_string(),
-                object_type: "".to_string(),
-                object_status: ObjectStatus::Created,
-                has_public_transfer: false,
-                storage_rebate: 0,
-                bcs: vec![],
-            })
-            .collect::<Vec<_>>();
+        let mut watched = vec![];
+        let mut cursor = None;
+        loop {
+            let page = indexer_rpc_client.get_watchlist(cursor, Some(2)).await?;
+            assert!(page.data.len() <= 2);
+            watched.extend(page.data);
+            if !page.has_next_page {
+                break;
+            }
+            cursor = page.next_cursor;
+        }
+        assert_eq!(entries, watched);
 
-        // this should fail because of the parameter limit
-        let result = pg_pool_conn
-            .build_transaction()
-            .serializable()
-            .read_write()
-            .run(|conn| {
-                diesel::insert_into(objects::table)
-                    .values(&lot_of_data)
-                    .on_conflict_do_nothing()
-                    .execute(conn)
-            });
+        store.delete_watchlist_entry(entries[1].clone()).await?;
+        let page = indexer_rpc_client.get_watchlist(None, None).await?;
+        assert_eq!(vec![entries[0].clone(), entries[2].clone()], page.data);
 
-        assert!(result.is_err());
+        drop(handle);
+        drop(test_cluster);
+        Ok(())
+    }
 
-        // this should pass, we can chunk up the data but they can still be transactional.
-        let result: Result<(), IndexerError> = pg_pool_conn
-            .build_transaction()
-            .serializable()
-            .read_write()
-            .run(|conn| {
-                for chunk in lot_of_data.chunks(1000) {
-                    diesel::insert_into(objects::table)
-                        .values(chunk)
-                        .on_conflict_do_nothing()
-                        .execute(conn)?;
-                }
-                Ok(())
-            });
-        assert!(result.is_ok());
+    #[tokio::test]
+    #[timeout(60000)]
+    async fn test_get_system_state_at_epoch() {
+        let (test_cluster, indexer_rpc_client, store, handle) =
+            start_test_cluster(Some(10000)).await;
+        wait_until_next_checkpoint(&store).await;
+
+        let fullnode_state = test_cluster
+            .rpc_client()
+            .get_latest_sui_system_state()
+            .await
+            .unwrap();
+        let genesis_state = indexer_rpc_client
+            .get_system_state_at_epoch(0.into())
+            .await
+            .unwrap();
+        assert_eq!(fullnode_state.epoch, genesis_state.epoch);
+        assert_eq!(
+            fullnode_state.protocol_version,
+            genesis_state.protocol_version
+        );
+        assert_eq!(
+            fullnode_state.reference_gas_price,
+            genesis_state.reference_gas_price
+        );
+        let validators = |s: &SuiSystemStateSummary| {
+            s.active_validators
+                .iter()
+                .map(|v| (v.sui_address, v.staking_pool_sui_balance))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(validators(&fullnode_state), validators(&genesis_state));
+
+        // past epochs are still served once the epoch has changed
+        wait_until_next_epoch(&store).await;
+        let state = store.get_system_state_at_epoch(1).await.unwrap();
+        assert_eq!(1, state.epoch);
+        let state = store.get_system_state_at_epoch(0).await.unwrap();
+        assert_eq!(validators(&genesis_state), validators(&state));
+        assert!(store.get_system_state_at_epoch(100).await.is_err());
+
+        drop(handle);
+        drop(test_cluster);
     }
 
     #[tokio::test]
     #[timeout(60000)]
-    async fn pg_unnest_bulk_insert_update_test() {
-        start_test_cluster(None).await;
-        let pg_host = env::var("POSTGRES_HOST").unwrap_or_else(|_| "localhost".into());
-        let pg_port = env::var("POSTGRES_PORT").unwrap_or_else(|_| "32770".into());
-        let pw = env::var("POSTGRES_PASSWORD").unwrap_or_else(|_| "postgrespw".into());
-        let db_url = format!("postgres://postgres:{pw}@{pg_host}:{pg_port}");
-        let pg_connection_pool = new_pg_connection_pool(&db_url).unwrap();
-        let mut pg_pool_conn = get_pg_pool_connection(&pg_connection_pool).unwrap();
+    async fn test_wait_for_checkpoint() {
+        let (test_cluster, indexer_rpc_client, store, handle) =
+            start_test_cluster(Some(10000)).await;
+        wait_until_next_checkpoint(&store).await;
+        let latest = store
+            .get_latest_tx_checkpoint_sequence_number()
+            .await
+            .unwrap() as u64;
 
-        let bulk_data = (1..=10000)
-            .map(|_| Object {
-                epoch: 0,
-                checkpoint: 0,
-                object_id: ObjectID::random().to_string(),
-                version: 0,
-                object_digest: ObjectDigest::random().to_string(),
-                owner_type: OwnerType::AddressOwner,
-                owner_address: Some(SuiAddress::random_for_testing_only().to_string()),
-                initial_shared_version: None,
-                previous_transaction: TransactionDigest::random().to_string(),
-                object_type: "0x2::coin::Coin<0x2::sui::SUI>".to_string(),
-                object_status: ObjectStatus::Created,
-                has_public_transfer: false,
-                storage_rebate: 0,
-                bcs: vec![NamedBcsBytes("object".to_string(), vec![1u8, 2u8, 3u8])],
-            })
-            .collect::<Vec<_>>();
+        // the next checkpoint is waited for until its events, indices and objects are committed
+        indexer_rpc_client
+            .wait_for_checkpoint((latest + 1).into(), Some(20_000.into()))
+            .await
+            .unwrap();
+        let object_checkpoint = store
+            .get_latest_object_checkpoint_sequence_number()
+            .await
+            .unwrap();
+        assert!(object_checkpoint >= latest as i64 + 1);
 
-        let insert_query = compose_object_bulk_insert_query(&bulk_data);
-        let result: Result<usize, IndexerError> = pg_pool_conn
-            .build_transaction()
-            .serializable()
-            .read_write()
-            .run(|conn| diesel::sql_query(insert_query).execute(conn))
-            .map_err(IndexerError::PostgresError);
-        assert!(result.is_ok());
-        assert_eq!(result.unwrap(), 10000);
+        // checkpoints far ahead time out
+        assert!(indexer_rpc_client
+            .wait_for_checkpoint((latest + 1_000_000).into(), Some(100.into()))
+            .await
+            .is_err());
 
-        let mutated_bulk_data = bulk_data
-            .clone()
-            .into_iter()
-            .map(|mut object| {
-                object.object_status = ObjectStatus::Mutated;
-                object.version = 1;
-                object
-            })
-            .collect::<Vec<_>>();
-        let mutated_bulk_data_same_checkpoint = bulk_data
-            .into_iter()
+        drop(handle);
+        drop(test_cluster);
+    }
+
+    #[tokio::test]
+    #[timeout(60000)]
+    async fn test_get_event_schema() {
+        let (test_cluster, indexer_rpc_client, store, handle) =
+            start_test_cluster(Some(10000)).await;
+        wait_until_next_checkpoint(&store).await;
+
+        // system packages are indexed from the genesis checkpoint
+        let schema = indexer_rpc_client
+            .get_event_schema("0x3::validator::StakingRequestEvent".to_string())
+            .await
+            .unwrap();
+        let fields = schema
+            .fields
+            .iter()
+            .map(|f| f.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            fields,
+            vec![
+                "pool_id",
+                "validator_address",
+                "staker_address",
+                "epoch",
+                "amount"
+            ]
+        );
+        assert!(schema.type_parameters.is_empty());
+
+        // type arguments of generic events are ignored
+        let schema = indexer_rpc_client
+            .get_event_schema("0x2::display::DisplayCreated<0x2::sui::SUI>".to_string())
+            .await
+            .unwrap();
+        assert_eq!(1, schema.type_parameters.len());
+
+        // structs without copy and drop cannot be emitted as events
+        assert!(indexer_rpc_client
+            .get_event_schema("0x2::coin::Coin".to_string())
+            .await
+            .is_err());
+        assert!(indexer_rpc_client
+            .get_event_schema("0x2::coin::NotAStruct".to_string())
+            .await
+            .is_err());
+
+        drop(handle);
+        drop(test_cluster);
+    }
+
+    #[tokio::test]
+    #[timeout(60000)]
+    async fn test_get_type_layout() -> Result<(), anyhow::Error> {
+        let (test_cluster, indexer_rpc_client, store, handle) =
+            start_test_cluster(Some(10000)).await;
+        wait_until_next_checkpoint(&store).await;
+
+        let gas_objects =
+            get_owned_objects_for_address(&indexer_rpc_client, &test_cluster.get_address_0())
+                .await?;
+        let object = indexer_rpc_client
+            .get_object(gas_objects[0], Some(SuiObjectDataOptions::new().with_bcs()))
+            .await?
+            .into_object()?;
+        let Some(SuiRawData::MoveObject(raw_object)) = object.bcs else {
+            panic!("Expected the BCS of a Move object");
+        };
+        let layout = indexer_rpc_client
+            .get_type_layout(raw_object.type_.to_string())
+            .await?;
+        assert_eq!(serde_json::to_value(GasCoin::layout())?, layout);
+
+        // the raw BCS of the object decodes with the layout
+        let layout: MoveStructLayout = serde_json::from_value(layout)?;
+        let decoded = MoveStruct::simple_deserialize(&raw_object.bcs_bytes, &layout)?;
+        assert_eq!(
+            Some(raw_object.bcs_bytes),
+            MoveValue::Struct(decoded).simple_serialize()
+        );
+        // served from the cache the second time
+        assert_eq!(
+            serde_json::to_value(&layout)?,
+            indexer_rpc_client
+                .get_type_layout("0x2::coin::Coin<0x2::sui::SUI>".to_string())
+                .await?
+        );
+
+        // generic types need their type arguments
+        assert!(indexer_rpc_client
+            .get_type_layout("0x2::coin::Coin".to_string())
+            .await
+            .is_err());
+        assert!(indexer_rpc_client
+            .get_type_layout("0x2::coin::NotAStruct".to_string())
+            .await
+            .is_err());
+
+        drop(handle);
+        drop(test_cluster);
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[timeout(60000)]
+    async fn test_checkpoint_queue() {
+        let (test_cluster, _, store, handle) = start_test_cluster(None).await;
+        wait_until_next_checkpoint(&store).await;
+
+        let queued = |sequence_number: i64| {
+            QueuedCheckpoint::new(QueueKind::Objects, sequence_number, &sequence_number).unwrap()
+        };
+        let enqueued = store
+            .enqueue_checkpoints(vec![queued(100_000), queued(100_001), queued(100_003)])
+            .await
+            .unwrap();
+        assert_eq!(3, enqueued);
+        // checkpoints queued again, e.g. by a restarted indexer, are kept as they are
+        let enqueued = store
+            .enqueue_checkpoints(vec![queued(100_001), queued(100_002)])
+            .await
+            .unwrap();
+        assert_eq!(1, enqueued);
+        let checkpoints = store
+            .get_queued_checkpoints(QueueKind::Objects, 100_001, 2)
+            .await
+            .unwrap();
+        assert_eq!(
+            vec![100_001, 100_002],
+            checkpoints
+                .iter()
+                .map(|checkpoint| checkpoint.data::<i64>().unwrap())
+                .collect::<Vec<_>>()
+        );
+        assert!(store
+            .get_queued_checkpoints(QueueKind::Transactions, 0, 10)
+            .await
+            .unwrap()
+            .is_empty());
+
+        let deleted = store
+            .delete_queued_checkpoints(QueueKind::Objects, 100_001)
+            .await
+            .unwrap();
+        assert_eq!(2, deleted);
+        let checkpoints = store
+            .get_queued_checkpoints(QueueKind::Objects, 0, 10)
+            .await
+            .unwrap();
+        assert_eq!(2, checkpoints.len());
+        assert_eq!(100_002, checkpoints[0].sequence_number);
+
+        let committed = store
+            .get_committed_queue_sequence_number(QueueKind::Epochs)
+            .await
+            .unwrap();
+        assert_eq!(0, committed);
+
+        drop(handle);
+        drop(test_cluster);
+    }
+
+    #[derive(QueryableByName)]
+    struct TableName {
+        #[diesel(sql_type = Text)]
+        table_name: String,
+    }
+
+    #[derive(QueryableByName)]
+    struct TableChecksum {
+        #[diesel(sql_type = Text)]
+        checksum: String,
+    }
+
+    // checksums of the rows of every table of `schema`, by table
+    fn table_checksums(conn: &mut PgConnection, schema: &str) -> BTreeMap<String, String> {
+        diesel::sql_query(
+            "SELECT table_name::TEXT AS table_name FROM information_schema.tables \
+            WHERE table_schema = $1 AND table_type = 'BASE TABLE'",
+        )
+        .bind::<Text, _>(schema)
+        .load::<TableName>(conn)
+        .unwrap()
+        .into_iter()
+        .map(|table| {
+            let checksum = diesel::sql_query(format!(
+                "SELECT md5(coalesce(string_agg(t::TEXT, '|' ORDER BY t::TEXT), '')) AS checksum \
+                FROM \"{schema}\".\"{}\" t",
+                table.table_name
+            ))
+            .get_result::<TableChecksum>(conn)
+            .unwrap()
+            .checksum;
+            (table.table_name, checksum)
+        })
+        .collect()
+    }
+
+    #[tokio::test]
+    #[timeout(60000)]
+    async fn test_checkpoint_replay_is_noop() {
+        let (test_cluster, _, store, handle) = start_test_cluster(None).await;
+        wait_until_next_checkpoint(&store).await;
+
+        // data committed by the indexer is committed again into a schema of its own
+        let pg_host = env::var("POSTGRES_HOST").unwrap_or_else(|_| "localhost".into());
+        let pg_port = env::var("POSTGRES_PORT").unwrap_or_else(|_| "32770".into());
+        let pw = env::var("POSTGRES_PASSWORD").unwrap_or_else(|_| "postgrespw".into());
+        let db_url = format!("postgres://postgres:{pw}@{pg_host}:{pg_port}");
+        let mut conn = get_pg_pool_connection(&new_pg_connection_pool(&db_url).unwrap()).unwrap();
+        let last = store
+            .get_latest_tx_checkpoint_sequence_number()
+            .await
+            .unwrap();
+        let checkpoints = checkpoints::table
+            .filter(checkpoints::sequence_number.le(last))
+            .load::<Checkpoint>(&mut conn)
+            .unwrap();
+        let transactions = transactions::table
+            .filter(transactions::checkpoint_sequence_number.le(last))
+            .load::<Transaction>(&mut conn)
+            .unwrap();
+        let digests = transactions
+            .iter()
+            .map(|tx| tx.transaction_digest.clone())
+            .collect::<Vec<_>>();
+        let events = events::table
+            .filter(events::transaction_digest.eq_any(&digests))
+            .load::<Event>(&mut conn)
+            .unwrap();
+        let changed_objects = objects::table
+            .filter(objects::checkpoint.le(last))
+            .load::<Object>(&mut conn)
+            .unwrap();
+        let transactions_json = transactions_json::table
+            .filter(transactions_json::checkpoint_sequence_number.le(last))
+            .load::<TransactionJson>(&mut conn)
+            .unwrap();
+        let checkpoint_tx_digests = checkpoint_tx_digests::table
+            .filter(checkpoint_tx_digests::checkpoint_sequence_number.le(last))
+            .load::<CheckpointTxDigest>(&mut conn)
+            .unwrap();
+        let proofs = checkpoint_proofs::table
+            .filter(checkpoint_proofs::sequence_number.le(last))
+            .load::<CheckpointProof>(&mut conn)
+            .unwrap();
+        let input_objects = input_objects::table
+            .filter(input_objects::checkpoint_sequence_number.le(last))
+            .load::<InputObject>(&mut conn)
+            .unwrap();
+        let changed_object_indices = changed_objects::table
+            .filter(changed_objects::checkpoint_sequence_number.le(last))
+            .load::<ChangedObject>(&mut conn)
+            .unwrap();
+        let move_calls = move_calls::table
+            .filter(move_calls::checkpoint_sequence_number.le(last))
+            .load::<MoveCall>(&mut conn)
+            .unwrap();
+        let tx_commands = tx_commands::table
+            .filter(tx_commands::checkpoint_sequence_number.le(last))
+            .load::<TxCommand>(&mut conn)
+            .unwrap();
+        let tx_calls = tx_calls::table
+            .filter(tx_calls::checkpoint_sequence_number.le(last))
+            .load::<TxCall>(&mut conn)
+            .unwrap();
+        let recipients = recipients::table
+            .filter(recipients::checkpoint_sequence_number.le(last))
+            .load::<Recipient>(&mut conn)
+            .unwrap();
+        let tx_dependencies = tx_dependencies::table
+            .filter(tx_dependencies::checkpoint_sequence_number.le(last))
+            .load::<TxDependency>(&mut conn)
+            .unwrap();
+        let transfers = transfers::table
+            .filter(transfers::checkpoint_sequence_number.le(last))
+            .load::<Transfer>(&mut conn)
+            .unwrap();
+        let ownership_transitions = object_ownership_history::table
+            .filter(object_ownership_history::checkpoint_sequence_number.le(last))
+            .load::<OwnershipTransition>(&mut conn)
+            .unwrap();
+        let genesis_epoch = epochs::table
+            .filter(epochs::epoch.eq(0))
+            .first::<DBEpochInfo>(&mut conn)
+            .unwrap();
+        let protocol_config = protocol_configs::table
+            .filter(protocol_configs::epoch.eq(0))
+            .first::<DBProtocolConfig>(&mut conn)
+            .unwrap();
+        assert!(!transactions.is_empty() && !changed_objects.is_empty());
+        assert!(!input_objects.is_empty() && !recipients.is_empty());
+
+        let schema = "checkpoint_replay";
+        let pool =
+            new_pg_connection_pool_in_schema(&db_url, Some(schema), &Default::default()).unwrap();
+        let mut conn = get_pg_pool_connection(&pool).unwrap();
+        create_schema_if_not_exists(&mut conn, schema).unwrap();
+        reset_database(&mut conn, true).unwrap();
+        let metrics = IndexerMetrics::new(&Registry::default());
+        let replay_store = PgIndexerStore::new(pool, metrics.clone());
+
+        let ended_epoch = DBEpochInfo {
+            last_checkpoint_id: Some(last),
+            epoch_end_timestamp: Some(genesis_epoch.epoch_start_timestamp + 1),
+            epoch_total_transactions: transactions.len() as i64,
+            ..genesis_epoch.clone()
+        };
+        let epochs = vec![
+            TemporaryEpochStore {
+                last_epoch: None,
+                new_epoch: genesis_epoch.clone(),
+                system_state: DBSystemStateSummary::default(),
+                validators: vec![],
+                validator_epoch_events: vec![],
+                protocol_config: protocol_config.clone(),
+            },
+            TemporaryEpochStore {
+                last_epoch: Some(ended_epoch),
+                new_epoch: DBEpochInfo {
+                    epoch: 1,
+                    first_checkpoint_id: last + 1,
+                    ..genesis_epoch
+                },
+                system_state: DBSystemStateSummary {
+                    epoch: 1,
+                    ..Default::default()
+                },
+                validators: vec![],
+                validator_epoch_events: vec![],
+                protocol_config: DBProtocolConfig {
+                    epoch: 1,
+                    ..protocol_config
+                },
+            },
+        ];
+        let object_changes = vec![TransactionObjectChanges {
+            changed_objects: changed_objects.clone(),
+            deleted_objects: vec![],
+            name_records: vec![],
+        }];
+        let signers = checkpoints
+            .iter()
+            .map(|checkpoint| CheckpointSigners {
+                checkpoint_sequence_number: checkpoint.sequence_number,
+                epoch: checkpoint.epoch,
+                committee_indices: vec![0],
+            })
+            .collect::<Vec<_>>();
+        // the leaderboard and address counters are derived from the transaction index rows
+        // of each checkpoint, as by the checkpoint handler
+        let hot_entities = checkpoints
+            .iter()
+            .map(|checkpoint| {
+                let sequence_number = checkpoint.sequence_number;
+                CheckpointHotEntities::new(
+                    sequence_number,
+                    checkpoint.timestamp_ms,
+                    &input_objects
+                        .iter()
+                        .filter(|o| o.checkpoint_sequence_number == sequence_number)
+                        .cloned()
+                        .collect::<Vec<_>>(),
+                    &changed_object_indices
+                        .iter()
+                        .filter(|o| o.checkpoint_sequence_number == sequence_number)
+                        .cloned()
+                        .collect::<Vec<_>>(),
+                    &move_calls
+                        .iter()
+                        .filter(|c| c.checkpoint_sequence_number == sequence_number)
+                        .cloned()
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .collect::<Vec<_>>();
+        let address_activity = checkpoints
+            .iter()
+            .map(|checkpoint| {
+                let sequence_number = checkpoint.sequence_number;
+                CheckpointAddressActivity::new(
+                    sequence_number,
+                    checkpoint.timestamp_ms,
+                    &transactions
+                        .iter()
+                        .filter(|tx| tx.checkpoint_sequence_number == Some(sequence_number))
+                        .cloned()
+                        .collect::<Vec<_>>(),
+                    &recipients
+                        .iter()
+                        .filter(|r| r.checkpoint_sequence_number == sequence_number)
+                        .cloned()
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .collect::<Vec<_>>();
+        // the data is committed, then committed again
+        let mut checksums = vec![];
+        for _ in 0..2 {
+            for epoch in &epochs {
+                replay_store.persist_epoch(epoch).await.unwrap();
+            }
+            replay_store
+                .persist_checkpoint_transactions(
+                    &checkpoints,
+                    &transactions,
+                    &transactions_json,
+                    &checkpoint_tx_digests,
+                    &hot_entities,
+                    &address_activity,
+                    &signers,
+                    &proofs,
+                    metrics.total_transaction_chunk_committed.clone(),
+                )
+                .await
+                .unwrap();
+            replay_store.persist_events(&events).await.unwrap();
+            replay_store
+                .persist_transaction_index_tables(
+                    &input_objects,
+                    &changed_object_indices,
+                    &move_calls,
+                    &tx_commands,
+                    &tx_calls,
+                    &recipients,
+                    &tx_dependencies,
+                    &transfers,
+                    &ownership_transitions,
+                )
+                .await
+                .unwrap();
+            replay_store
+                .persist_object_changes(
+                    &object_changes,
+                    metrics.object_mutation_db_commit_latency.clone(),
+                    metrics.object_deletion_db_commit_latency.clone(),
+                    metrics.total_object_change_chunk_committed.clone(),
+                )
+                .await
+                .unwrap();
+            checksums.push(table_checksums(&mut conn, schema));
+        }
+        let committed = checksums.remove(0);
+        assert_eq!(committed, checksums[0]);
+
+        // object changes of earlier versions, e.g. of a checkpoint replayed after later ones,
+        // leave the objects as they are
+        let earlier_changes = vec![TransactionObjectChanges {
+            changed_objects: changed_objects
+                .iter()
+                .map(|object| Object {
+                    version: object.version - 1,
+                    previous_transaction: "replayed".to_string(),
+                    ..object.clone()
+                })
+                .collect(),
+            deleted_objects: changed_objects
+                .iter()
+                .take(1)
+                .map(|object| DeletedObject {
+                    epoch: object.epoch,
+                    checkpoint: Some(object.checkpoint),
+                    object_id: object.object_id.clone(),
+                    version: object.version - 1,
+                    object_digest: object.object_digest.clone(),
+                    owner_type: object.owner_type.clone(),
+                    previous_transaction: "replayed".to_string(),
+                    object_type: object.object_type.clone(),
+                    object_status: ObjectStatus::Deleted,
+                    has_public_transfer: object.has_public_transfer,
+                })
+                .collect(),
+            name_records: vec![],
+        }];
+        replay_store
+            .persist_object_changes(
+                &earlier_changes,
+                metrics.object_mutation_db_commit_latency.clone(),
+                metrics.object_deletion_db_commit_latency.clone(),
+                metrics.total_object_change_chunk_committed.clone(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(committed, table_checksums(&mut conn, schema));
+
+        drop(handle);
+        drop(test_cluster);
+    }
+
+    #[tokio::test]
+    #[timeout(60000)]
+    async fn test_prune_events_by_checkpoint() {
+        let pg_host = env::var("POSTGRES_HOST").unwrap_or_else(|_| "localhost".into());
+        let pg_port = env::var("POSTGRES_PORT").unwrap_or_else(|_| "32770".into());
+        let pw = env::var("POSTGRES_PASSWORD").unwrap_or_else(|_| "postgrespw".into());
+        let db_url = format!("postgres://postgres:{pw}@{pg_host}:{pg_port}");
+        let schema = "event_pruning";
+        let pool =
+            new_pg_connection_pool_in_schema(&db_url, Some(schema), &Default::default()).unwrap();
+        let mut conn = get_pg_pool_connection(&pool).unwrap();
+        create_schema_if_not_exists(&mut conn, schema).unwrap();
+        reset_database(&mut conn, true).unwrap();
+        let store = PgIndexerStore::new(pool, IndexerMetrics::new(&Registry::default()));
+
+        // 3 checkpoints of 2 transactions each, numbered 0 to 5 in the network, with ids
+        // assigned in reverse so that they never match the sequence numbers
+        let checkpoints = (0..3)
+            .map(|sequence_number| Checkpoint {
+                sequence_number,
+                checkpoint_digest: format!("checkpoint{sequence_number}"),
+                total_transaction_blocks: 2,
+                network_total_transactions: 2 * (sequence_number + 1),
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+        let transactions = (0..6)
+            .map(|tx_sequence_number| Transaction {
+                id: Some(100 - tx_sequence_number),
+                transaction_digest: format!("transaction{tx_sequence_number}"),
+                checkpoint_sequence_number: Some(tx_sequence_number / 2),
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+        let events = (0..6)
+            .map(|tx_sequence_number| Event {
+                id: None,
+                transaction_digest: format!("transaction{tx_sequence_number}"),
+                event_sequence: 0,
+                sender: SuiAddress::ZERO.to_string(),
+                package: ObjectID::ZERO.to_string(),
+                module: "test".to_string(),
+                event_type: "0x2::test::Test".to_string(),
+                event_time_ms: None,
+                event_bcs: vec![],
+                event_type_package: ObjectID::ZERO.to_string(),
+                event_type_module: "test".to_string(),
+                event_type_name: "Test".to_string(),
+                event_type_params: String::new(),
+                tx_sequence_number,
+            })
+            .collect::<Vec<_>>();
+        diesel::insert_into(checkpoints::table)
+            .values(&checkpoints)
+            .execute(&mut conn)
+            .unwrap();
+        diesel::insert_into(transactions::table)
+            .values(&transactions)
+            .execute(&mut conn)
+            .unwrap();
+        store.persist_events(&events).await.unwrap();
+        let remaining_events = |conn: &mut PgConnection| {
+            events::table
+                .select(events::tx_sequence_number)
+                .order_by(events::tx_sequence_number)
+                .load::<i64>(conn)
+                .unwrap()
+        };
+
+        // only the events of the transactions of checkpoint 1 are pruned
+        store
+            .prune_checkpoints(PrunedTable::Events, 1, 2)
+            .await
+            .unwrap();
+        assert_eq!(remaining_events(&mut conn), vec![0, 1, 4, 5]);
+
+        // events are pruned even after the transactions they belong to
+        store
+            .prune_checkpoints(PrunedTable::Transactions, 0, 3)
+            .await
+            .unwrap();
+        store
+            .prune_checkpoints(PrunedTable::Events, 0, 3)
+            .await
+            .unwrap();
+        assert!(remaining_events(&mut conn).is_empty());
+    }
+
+    #[tokio::test]
+    #[timeout(60000)]
+    async fn test_object_type_counts() {
+        let (mut test_cluster, indexer_rpc_client, store, handle) = start_test_cluster(None).await;
+        wait_until_next_checkpoint(&store).await;
+        let context = &mut test_cluster.wallet;
+        let (package_id, _, publish_digest) = publish_nfts_package(context).await;
+        wait_until_transaction_synced(&store, publish_digest.base58_encode().as_str()).await;
+        let (_, _, digest) = create_devnet_nft(context, package_id).await;
+        wait_until_transaction_synced(&store, digest.base58_encode().as_str()).await;
+        let (sender, object_id, digest) = create_devnet_nft(context, package_id).await;
+        wait_until_transaction_synced(&store, digest.base58_encode().as_str()).await;
+        let object = indexer_rpc_client
+            .get_object(object_id, None)
+            .await
+            .unwrap();
+        let data = object.object().unwrap();
+        let result = delete_devnet_nft(
+            context,
+            sender,
+            package_id,
+            (data.object_id, data.version, data.digest),
+        )
+        .await;
+        wait_until_transaction_synced(&store, result.digest.base58_encode().as_str()).await;
+        let last = store
+            .get_latest_tx_checkpoint_sequence_number()
+            .await
+            .unwrap();
+        while store
+            .get_latest_object_checkpoint_sequence_number()
+            .await
+            .unwrap()
+            < last
+        {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+
+        // the counts maintained as objects are committed match a count of the objects
+        let pg_host = env::var("POSTGRES_HOST").unwrap_or_else(|_| "localhost".into());
+        let pg_port = env::var("POSTGRES_PORT").unwrap_or_else(|_| "32770".into());
+        let pw = env::var("POSTGRES_PASSWORD").unwrap_or_else(|_| "postgrespw".into());
+        let db_url = format!("postgres://postgres:{pw}@{pg_host}:{pg_port}");
+        let mut conn = get_pg_pool_connection(&new_pg_connection_pool(&db_url).unwrap()).unwrap();
+        let counts = object_type_counts::table
+            .filter(object_type_counts::object_count.gt(0))
+            .select((
+                object_type_counts::package,
+                object_type_counts::module,
+                object_type_counts::name,
+                object_type_counts::object_count,
+            ))
+            .load::<(String, String, String, i64)>(&mut conn)
+            .unwrap()
+            .into_iter()
+            .map(|(package, module, name, count)| ((package, module, name), count))
+            .collect::<BTreeMap<_, _>>();
+        let mut expected = BTreeMap::new();
+        for (package, module, name) in objects::table
+            .filter(objects::object_status.ne(ObjectStatus::Deleted))
+            .filter(objects::object_status.ne(ObjectStatus::Wrapped))
+            .filter(objects::object_status.ne(ObjectStatus::UnwrappedThenDeleted))
+            .select((
+                objects::object_type_package,
+                objects::object_type_module,
+                objects::object_type_name,
+            ))
+            .load::<(Option<String>, Option<String>, Option<String>)>(&mut conn)
+            .unwrap()
+        {
+            if let (Some(package), Some(module), Some(name)) = (package, module, name) {
+                *expected.entry((package, module, name)).or_insert(0) += 1;
+            }
+        }
+        assert_eq!(expected, counts);
+        assert_eq!(
+            Some(&1),
+            counts.get(&(
+                package_id.to_string(),
+                "devnet_nft".to_string(),
+                "DevNetNFT".to_string()
+            ))
+        );
+
+        drop(handle);
+        drop(test_cluster);
+    }
+
+    #[tokio::test]
+    #[timeout(60000)]
+    async fn test_unchanged_object_mutations() {
+        let pg_host = env::var("POSTGRES_HOST").unwrap_or_else(|_| "localhost".into());
+        let pg_port = env::var("POSTGRES_PORT").unwrap_or_else(|_| "32770".into());
+        let pw = env::var("POSTGRES_PASSWORD").unwrap_or_else(|_| "postgrespw".into());
+        let db_url = format!("postgres://postgres:{pw}@{pg_host}:{pg_port}");
+        let schema = "unchanged_objects";
+        let pool =
+            new_pg_connection_pool_in_schema(&db_url, Some(schema), &Default::default()).unwrap();
+        let mut conn = get_pg_pool_connection(&pool).unwrap();
+        create_schema_if_not_exists(&mut conn, schema).unwrap();
+        reset_database(&mut conn, true).unwrap();
+        let metrics = IndexerMetrics::new(&Registry::default());
+        let store = PgIndexerStore::new(pool, metrics.clone());
+
+        let id = ObjectID::random();
+        let owner = SuiAddress::random_for_testing_only();
+        let persist = |version: u64| {
+            let object = sui_types::object::Object::with_id_owner_version_for_testing(
+                id,
+                SequenceNumber::from_u64(version),
+                owner,
+            );
+            let changes = TransactionObjectChanges {
+                changed_objects: vec![Object::new(0, version, WriteKind::Mutate, &object)],
+                deleted_objects: vec![],
+                name_records: vec![],
+            };
+            let store = store.clone();
+            let metrics = metrics.clone();
+            async move {
+                store
+                    .persist_object_changes(
+                        &[changes],
+                        metrics.object_mutation_db_commit_latency.clone(),
+                        metrics.object_deletion_db_commit_latency.clone(),
+                        metrics.total_object_change_chunk_committed.clone(),
+                    )
+                    .await
+                    .unwrap();
+                object
+            }
+        };
+
+        persist(1).await;
+        // only the version changed, the row is updated in place
+        let expected = persist(2).await;
+        assert_eq!(1, metrics.total_unchanged_object_mutations.get());
+        let read = store.get_object(id, None).await.unwrap();
+        assert_eq!(Some(expected), read.into_object().ok());
+
+        // the row is gone, e.g. pruned or never committed, so the object is written in full
+        diesel::delete(objects::table).execute(&mut conn).unwrap();
+        let expected = persist(3).await;
+        assert_eq!(1, metrics.total_unchanged_object_mutations.get());
+        let read = store.get_object(id, None).await.unwrap();
+        assert_eq!(Some(expected), read.into_object().ok());
+    }
+
+    #[tokio::test]
+    #[timeout(60000)]
+    async fn test_object_blob_offloading() {
+        let (test_cluster, _, store, handle) = start_test_cluster(None).await;
+        wait_until_next_checkpoint(&store).await;
+
+        let pg_host = env::var("POSTGRES_HOST").unwrap_or_else(|_| "localhost".into());
+        let pg_port = env::var("POSTGRES_PORT").unwrap_or_else(|_| "32770".into());
+        let pw = env::var("POSTGRES_PASSWORD").unwrap_or_else(|_| "postgrespw".into());
+        let db_url = format!("postgres://postgres:{pw}@{pg_host}:{pg_port}");
+        let mut conn = get_pg_pool_connection(&new_pg_connection_pool(&db_url).unwrap()).unwrap();
+        let changed_objects = objects::table.load::<Object>(&mut conn).unwrap();
+        assert!(!changed_objects.is_empty());
+
+        let schema = "blob_offloading";
+        let pool =
+            new_pg_connection_pool_in_schema(&db_url, Some(schema), &Default::default()).unwrap();
+        let mut conn = get_pg_pool_connection(&pool).unwrap();
+        create_schema_if_not_exists(&mut conn, schema).unwrap();
+        reset_database(&mut conn, true).unwrap();
+        let metrics = IndexerMetrics::new(&Registry::default());
+        // every object is larger than the threshold
+        let blob_store = PgIndexerStore::new(pool, metrics.clone())
+            .with_blob_store(BlobStore::new(Arc::new(InMemory::new()), 1));
+        blob_store
+            .persist_object_changes(
+                &[TransactionObjectChanges {
+                    changed_objects: changed_objects.clone(),
+                    deleted_objects: vec![],
+                    name_records: vec![],
+                }],
+                metrics.object_mutation_db_commit_latency.clone(),
+                metrics.object_deletion_db_commit_latency.clone(),
+                metrics.total_object_change_chunk_committed.clone(),
+            )
+            .await
+            .unwrap();
+
+        // only pointers are stored in Postgres
+        let offloaded = objects::table.load::<Object>(&mut conn).unwrap();
+        assert_eq!(changed_objects.len(), offloaded.len());
+        for object in &offloaded {
+            let original = changed_objects
+                .iter()
+                .find(|o| o.object_id == object.object_id)
+                .unwrap();
+            assert_eq!(original.object_size, object.object_size);
+            for (original, offloaded) in original.bcs.iter().zip(&object.bcs) {
+                assert_ne!(original.1, offloaded.1);
+            }
+        }
+        for object in &changed_objects {
+            let object_id = ObjectID::from_str(&object.object_id).unwrap();
+            let expected = store.get_object(object_id, None).await.unwrap();
+            let resolved = blob_store.get_object(object_id, None).await.unwrap();
+            assert_eq!(expected.into_object().ok(), resolved.into_object().ok());
+        }
+
+        drop(handle);
+        drop(test_cluster);
+    }
+
+    #[tokio::test]
+    #[timeout(60000)]
+    async fn test_leader_lock() {
+        let (test_cluster, _, _, handle) = start_test_cluster(None).await;
+
+        let pg_host = env::var("POSTGRES_HOST").unwrap_or_else(|_| "localhost".into());
+        let pg_port = env::var("POSTGRES_PORT").unwrap_or_else(|_| "32770".into());
+        let pw = env::var("POSTGRES_PASSWORD").unwrap_or_else(|_| "postgrespw".into());
+        let db_url = format!("postgres://postgres:{pw}@{pg_host}:{pg_port}");
+        let new_store = || {
+            let db_url = db_url.clone();
+            PgIndexerStore::new(
+                new_pg_connection_pool(&db_url).unwrap(),
+                IndexerMetrics::new(&Registry::default()),
+            )
+            .with_leader_lock_connector(move || {
+                new_pg_connection_in_schema(&db_url, None, &Default::default())
+            })
+        };
+        let mismatch = || ReexecutionMismatch {
+            transaction_digest: TransactionDigest::random().base58_encode(),
+            checkpoint_sequence_number: 0,
+            expected_effects_digest: String::new(),
+            local_effects_digest: String::new(),
+            local_error: None,
+            reexecuted_at_ms: 0,
+        };
+        let leader = new_store();
+        let standby = new_store();
+        let lock_name = "sui-indexer:committer".to_string();
+
+        assert!(!standby.holds_leader_lock().await.unwrap());
+        assert!(leader
+            .try_acquire_leader_lock(lock_name.clone())
+            .await
+            .unwrap());
+        assert!(leader.holds_leader_lock().await.unwrap());
+        assert!(!standby
+            .try_acquire_leader_lock(lock_name.clone())
+            .await
+            .unwrap());
+        // only the leader commits
+        leader
+            .persist_reexecution_mismatch(mismatch())
+            .await
+            .unwrap();
+        assert!(standby
+            .persist_reexecution_mismatch(mismatch())
+            .await
+            .is_err());
+        // locks of other roles are taken independently
+        let other_role = new_store();
+        assert!(other_role
+            .try_acquire_leader_lock("sui-indexer:indexer:0/2".to_string())
+            .await
+            .unwrap());
+        other_role
+            .persist_reexecution_mismatch(mismatch())
+            .await
+            .unwrap();
+
+        // the leader stops committing as soon as its lock connection is lost, before the
+        // loss is noticed by the leader election
+        let mut conn = get_pg_pool_connection(&new_pg_connection_pool(&db_url).unwrap()).unwrap();
+        conn.batch_execute(
+            "SELECT pg_terminate_backend(pid) FROM pg_locks \
+            WHERE locktype = 'advisory' AND pid <> pg_backend_pid()",
+        )
+        .unwrap();
+        assert!(leader
+            .persist_reexecution_mismatch(mismatch())
+            .await
+            .is_err());
+        assert!(!leader.holds_leader_lock().await.unwrap_or(false));
+
+        // the lock is released with the connection of the leader, another replica takes over
+        assert!(standby.try_acquire_leader_lock(lock_name).await.unwrap());
+        standby
+            .persist_reexecution_mismatch(mismatch())
+            .await
+            .unwrap();
+
+        drop(handle);
+        drop(test_cluster);
+    }
+
+    #[tokio::test]
+    #[timeout(60000)]
+    async fn test_epoch_boundary_system_state() {
+        let (test_cluster, _, store, handle) = start_test_cluster(Some(5000)).await;
+        wait_until_next_checkpoint(&store).await;
+        wait_until_next_epoch(&store).await;
+        wait_until_next_epoch(&store).await;
+
+        // validator set of every indexed epoch should match the historical committee on FN
+        let epochs = store.get_epochs(None, 100, None).await.unwrap();
+        assert!(epochs.len() >= 3);
+        for epoch in epochs {
+            let committee = test_cluster
+                .rpc_client()
+                .get_committee_info(Some(epoch.epoch.into()))
+                .await
+                .unwrap();
+            let mut expected: Vec<_> = committee.validators.into_iter().map(|(n, _)| n).collect();
+            let mut indexed: Vec<_> = epoch
+                .validators
+                .iter()
+                .map(|v| AuthorityName::from_bytes(&v.protocol_pubkey_bytes).unwrap())
+                .collect();
+            expected.sort();
+            indexed.sort();
+            assert_eq!(
+                expected, indexed,
+                "validator set mismatch at epoch {}",
+                epoch.epoch
+            );
+        }
+
+        drop(handle);
+        drop(test_cluster);
+    }
+
+    #[tokio::test]
+    #[timeout(60000)]
+    async fn test_repair_missing_epochs() {
+        let (test_cluster, _, store, handle) = start_test_cluster(Some(10000)).await;
+        wait_until_next_checkpoint(&store).await;
+        wait_until_next_epoch(&store).await;
+
+        let pg_host = env::var("POSTGRES_HOST").unwrap_or_else(|_| "localhost".into());
+        let pg_port = env::var("POSTGRES_PORT").unwrap_or_else(|_| "32770".into());
+        let pw = env::var("POSTGRES_PASSWORD").unwrap_or_else(|_| "postgrespw".into());
+        let db_url = format!("postgres://postgres:{pw}@{pg_host}:{pg_port}");
+        let mut conn = get_pg_pool_connection(&new_pg_connection_pool(&db_url).unwrap()).unwrap();
+        let epoch = epochs::table
+            .filter(epochs::epoch.eq(1))
+            .first::<DBEpochInfo>(&mut conn)
+            .unwrap();
+        // as if the indexer stopped between committing the last checkpoint of epoch 0 and
+        // committing epoch 1
+        diesel::delete(epochs::table.filter(epochs::epoch.eq(1)))
+            .execute(&mut conn)
+            .unwrap();
+        assert_eq!(0, store.get_latest_epoch_id().await.unwrap());
+        assert_eq!(
+            Some(epoch.first_checkpoint_id - 1),
+            store.get_epoch_last_checkpoint(0).await.unwrap()
+        );
+
+        let url = test_cluster.rpc_url().to_string();
+        let metrics = IndexerMetrics::new(&Registry::default());
+        let endpoints = FullNodeEndpoints::new(
+            vec![sui_rest_api::Client::new(format!("{url}/rest"))],
+            metrics.clone(),
+        );
+        repair_missing_epochs(&store, &endpoints, &metrics)
+            .await
+            .unwrap();
+        assert_eq!(1, metrics.total_epochs_repaired.get());
+        let repaired = epochs::table
+            .filter(epochs::epoch.eq(1))
+            .first::<DBEpochInfo>(&mut conn)
+            .unwrap();
+        assert_eq!(epoch.first_checkpoint_id, repaired.first_checkpoint_id);
+        assert_eq!(epoch.epoch_start_timestamp, repaired.epoch_start_timestamp);
+
+        // nothing is missing anymore
+        repair_missing_epochs(&store, &endpoints, &metrics)
+            .await
+            .unwrap();
+        assert_eq!(1, metrics.total_epochs_repaired.get());
+
+        drop(handle);
+        drop(test_cluster);
+    }
+
+    #[tokio::test]
+    #[timeout(60000)]
+    async fn test_get_last_checkpoint_of_epoch() {
+        let (test_cluster, _, store, handle) = start_test_cluster(Some(20000)).await;
+        // Allow indexer to sync geneis epoch
+        wait_until_next_checkpoint(&store).await;
+        wait_until_next_epoch(&store).await;
+        let current_epoch = store.get_current_epoch().await.unwrap();
+        let prev_epoch_last_checkpoint_id = current_epoch.first_checkpoint_id - 1;
+        wait_for_checkpoint(&store, current_epoch.first_checkpoint_id as i64).await;
+
+        let checkpoint = store
+            .get_checkpoint(CheckpointId::SequenceNumber(prev_epoch_last_checkpoint_id))
+            .await
+            .unwrap();
+        assert_eq!(checkpoint.epoch, current_epoch.epoch - 1);
+        assert_eq!(checkpoint.sequence_number, prev_epoch_last_checkpoint_id);
+        assert!(checkpoint.end_of_epoch_data.is_some());
+
+        assert_eq!(checkpoint.epoch, current_epoch.epoch - 1);
+        assert_eq!(checkpoint.sequence_number, prev_epoch_last_checkpoint_id);
+
+        // cross check with FN
+        let fn_cp = test_cluster
+            .rpc_client()
+            .get_checkpoint(CheckpointId::SequenceNumber(prev_epoch_last_checkpoint_id))
+            .await
+            .unwrap();
+
+        assert_eq!(fn_cp, checkpoint);
+
+        drop(handle);
+        drop(test_cluster);
+    }
+
+    #[tokio::test]
+    #[timeout(60000)]
+    async fn test_query_objects_cross_check() -> Result<(), anyhow::Error> {
+        let (test_cluster, indexer_rpc_client, store, _handle) = start_test_cluster(None).await;
+        // Allow indexer to sync genesis
+        wait_until_next_checkpoint(&store).await;
+        let address = test_cluster.get_address_0();
+        let fullnode_client = test_cluster.rpc_client();
+
+        let object_from_fullnode = fullnode_client
+            .get_owned_objects(address, None, None, None)
+            .await
+            .unwrap();
+
+        let object_from_indexer = indexer_rpc_client
+            .query_objects(
+                SuiObjectResponseQuery::new_with_filter(SuiObjectDataFilter::AddressOwner(address)),
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(object_from_fullnode.data, object_from_indexer.data);
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[timeout(60000)]
+    async fn test_query_objects() -> Result<(), anyhow::Error> {
+        let (_test_cluster, indexer_rpc_client, store, _handle) = start_test_cluster(None).await;
+        // Allow indexer to sync genesis
+        wait_until_next_checkpoint(&store).await;
+
+        let all_coins = indexer_rpc_client
+            .query_objects(
+                SuiObjectResponseQuery::new_with_filter(SuiObjectDataFilter::StructType(
+                    parse_struct_tag("0x2::coin::Coin<0x2::sui::SUI>").unwrap(),
+                )),
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(25, all_coins.data.len());
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[timeout(60000)]
+    async fn test_execute_readonly_sql() {
+        let (test_cluster, _, store, handle) = start_test_cluster(None).await;
+        wait_until_next_checkpoint(&store).await;
+
+        assert!(matches!(
+            store
+                .execute_readonly_sql("SELECT * FROM network_metrics".to_string())
+                .await,
+            Err(IndexerError::NotSupportedError(_))
+        ));
+        let readonly_sql = |views: Vec<&str>| {
+            ReadonlySql::new(
+                views.into_iter().map(String::from),
+                "readonly_sql".to_string(),
+                1,
+                Duration::from_secs(5),
+                1,
+            )
+        };
+        let pg_host = env::var("POSTGRES_HOST").unwrap_or_else(|_| "localhost".into());
+        let pg_port = env::var("POSTGRES_PORT").unwrap_or_else(|_| "32770".into());
+        let pw = env::var("POSTGRES_PASSWORD").unwrap_or_else(|_| "postgrespw".into());
+        let db_url = format!("postgres://postgres:{pw}@{pg_host}:{pg_port}");
+        let mut conn = get_pg_pool_connection(&new_pg_connection_pool(&db_url).unwrap()).unwrap();
+        conn.batch_execute(
+            "DO $$ BEGIN \
+                IF NOT EXISTS (SELECT FROM pg_roles WHERE rolname = 'readonly_sql') THEN \
+                    CREATE ROLE readonly_sql NOLOGIN; \
+                END IF; \
+            END $$; \
+            GRANT readonly_sql TO CURRENT_USER; \
+            GRANT USAGE ON SCHEMA public TO readonly_sql; \
+            REVOKE EXECUTE ON ALL FUNCTIONS IN SCHEMA public FROM PUBLIC;",
+        )
+        .unwrap();
+        // the role cannot read the view yet
+        assert!(store
+            .clone()
+            .with_readonly_sql(readonly_sql(vec!["network_metrics"]))
+            .is_err());
+        conn.batch_execute("GRANT SELECT ON network_metrics TO readonly_sql")
+            .unwrap();
+        // the role can read a view that is not allowlisted
+        assert!(store
+            .clone()
+            .with_readonly_sql(readonly_sql(vec![]))
+            .is_err());
+        let store = store
+            .with_readonly_sql(readonly_sql(vec!["network_metrics"]))
+            .unwrap();
+        let result = store
+            .execute_readonly_sql(
+                "WITH m AS (SELECT * FROM network_metrics) \
+                 SELECT 1 AS n FROM m UNION ALL SELECT 2 FROM m -- both rows"
+                    .to_string(),
+            )
+            .await
+            .unwrap();
+        assert!(result.truncated);
+        assert_eq!(vec![serde_json::json!({ "n": 1 })], result.rows);
+        assert!(matches!(
+            store
+                .execute_readonly_sql("SELECT * FROM transactions".to_string())
+                .await,
+            Err(IndexerError::InvalidArgumentError(_))
+        ));
+        assert!(matches!(
+            store
+                .execute_readonly_sql("SELECT unknown_column FROM network_metrics".to_string())
+                .await,
+            Err(IndexerError::PostgresReadError(_))
+        ));
+        // queries passing the checks still only read what the role can read
+        assert!(matches!(
+            store
+                .execute_readonly_sql(
+                    "SELECT ts_rewrite('a'::tsquery, \
+                     'SELECT ''a''::tsquery, transaction_digest::tsquery FROM transactions')"
+                        .to_string()
+                )
+                .await,
+            Err(IndexerError::PostgresReadError(_))
+        ));
+
+        drop(handle);
+        drop(test_cluster);
+    }
+
+    #[tokio::test]
+    #[timeout(60000)]
+    async fn test_get_transaction_proof() -> Result<(), anyhow::Error> {
+        let (test_cluster, indexer_rpc_client, store, handle) = start_test_cluster(None).await;
+        wait_until_next_checkpoint(&store).await;
+
+        let address = test_cluster.get_address_0();
+        let gas_objects = get_owned_objects_for_address(&indexer_rpc_client, &address).await?;
+        let tx_response = sign_and_transfer_object(
+            &test_cluster,
+            &indexer_rpc_client,
+            address,
+            test_cluster.get_address_1(),
+            gas_objects[0],
+            None,
+        )
+        .await?;
+        wait_until_transaction_synced_in_checkpoint(
+            &store,
+            tx_response.digest.base58_encode().as_str(),
+        )
+        .await;
+
+        let proof = store
+            .get_transaction_proof(tx_response.digest)
+            .await?
+            .unwrap();
+        let effects = proof.verify(&test_cluster.committee())?;
+        assert_eq!(tx_response.digest, *effects.transaction_digest());
+        let inclusion = store
+            .get_transaction_inclusion(tx_response.digest)
+            .await?
+            .unwrap();
+        assert_eq!(inclusion.checkpoint, proof.checkpoint);
+        assert_eq!(inclusion.position, proof.position);
+
+        // the proof does not hold for another transaction
+        let mut tampered = proof.clone();
+        tampered.transaction_digest = TransactionDigest::random();
+        assert!(tampered.verify(&test_cluster.committee()).is_err());
+        assert!(store
+            .get_transaction_proof(TransactionDigest::random())
+            .await?
+            .is_none());
+
+        drop(handle);
+        drop(test_cluster);
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[timeout(60000)]
+    async fn test_query_filters() {
+        let (test_cluster, _, store, handle) = start_test_cluster(None).await;
+        // Allow indexer to sync genesis
+        wait_until_next_checkpoint(&store).await;
+
+        let sui_coin_type = parse_struct_tag("0x2::coin::Coin<0x2::sui::SUI>")
+            .unwrap()
+            .to_string();
+        let coin_module = ObjectFilter::Module {
+            package: ObjectID::from_single_byte(2),
+            module: Identifier::new("coin").unwrap(),
+        };
+        let sui_coins = store
+            .query_objects(
+                coin_module.and(ObjectFilter::Type(sui_coin_type)),
+                None,
+                100,
+            )
+            .await
+            .unwrap();
+        assert_eq!(25, sui_coins.len());
+
+        let genesis_transactions = store
+            .query_transactions(StoreTransactionFilter::Checkpoint(0), None, 10, false)
+            .await
+            .unwrap();
+        assert!(!genesis_transactions.is_empty());
+        assert!(genesis_transactions
+            .iter()
+            .all(|tx| tx.checkpoint_sequence_number == Some(0)));
+        let failed_genesis_transactions = store
+            .query_transactions(
+                StoreTransactionFilter::Checkpoint(0).and(StoreTransactionFilter::Success(false)),
+                None,
+                10,
+                false,
+            )
+            .await
+            .unwrap();
+        assert!(failed_genesis_transactions.is_empty());
+        let none = store
+            .query_transactions(StoreTransactionFilter::Or(vec![]), None, 10, false)
+            .await
+            .unwrap();
+        assert!(none.is_empty());
+
+        drop(handle);
+        drop(test_cluster);
+    }
+
+    #[tokio::test]
+    #[timeout(60000)]
+    async fn pg_parameter_limit_test() {
+        // Helps clear/build the database
+        start_test_cluster(None).await;
+
+        let pg_host = env::var("POSTGRES_HOST").unwrap_or_else(|_| "localhost".into());
+        let pg_port = env::var("POSTGRES_PORT").unwrap_or_else(|_| "32770".into());
+        let pw = env::var("POSTGRES_PASSWORD").unwrap_or_else(|_| "postgrespw".into());
+        let db_url = format!("postgres://postgres:{pw}@{pg_host}:{pg_port}");
+        let pg_connection_pool = new_pg_connection_pool(&db_url).unwrap();
+        let mut pg_pool_conn = get_pg_pool_connection(&pg_connection_pool).unwrap();
+
+        let lot_of_data = (1..10000)
+            .map(|_| Object {
+                epoch: 0,
+                checkpoint: 0,
+                object_id: ObjectID::random().to_string(),
+                version: 0,
+                object_digest: "".to_string(),
+                owner_type: OwnerType::AddressOwner,
+                owner_address: None,
+                initial_shared_version: None,
+                previous_transaction: "".to_string(),
+                object_type: "".to_string(),
+                object_status: ObjectStatus::Created,
+                has_public_transfer: false,
+                storage_rebate: 0,
+                bcs: vec![],
+                object_size: 0,
+            })
+            .collect::<Vec<_>>();
+
+        // this should fail because of the parameter limit
+        let result = pg_pool_conn
+            .build_transaction()
+            .serializable()
+            .read_write()
+            .run(|conn| {
+                diesel::insert_into(objects::table)
+                    .values(&lot_of_data)
+                    .on_conflict_do_nothing()
+                    .execute(conn)
+            });
+
+        assert!(result.is_err());
+
+        // this should pass, we can chunk up the data but they can still be transactional.
+        let result: Result<(), IndexerError> = pg_pool_conn
+            .build_transaction()
+            .serializable()
+            .read_write()
+            .run(|conn| {
+                for chunk in lot_of_data.chunks(1000) {
+                    diesel::insert_into(objects::table)
+                        .values(chunk)
+                        .on_conflict_do_nothing()
+                        .execute(conn)?;
+                }
+                Ok(())
+            });
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    #[timeout(60000)]
+    async fn pg_unnest_bulk_insert_update_test() {
+        start_test_cluster(None).await;
+        let pg_host = env::var("POSTGRES_HOST").unwrap_or_else(|_| "localhost".into());
+        let pg_port = env::var("POSTGRES_PORT").unwrap_or_else(|_| "32770".into());
+        let pw = env::var("POSTGRES_PASSWORD").unwrap_or_else(|_| "postgrespw".into());
+        let db_url = format!("postgres://postgres:{pw}@{pg_host}:{pg_port}");
+        let pg_connection_pool = new_pg_connection_pool(&db_url).unwrap();
+        let mut pg_pool_conn = get_pg_pool_connection(&pg_connection_pool).unwrap();
+
+        let bulk_data = (1..=10000)
+            .map(|_| Object {
+                epoch: 0,
+                checkpoint: 0,
+                object_id: ObjectID::random().to_string(),
+                version: 0,
+                object_digest: ObjectDigest::random().to_string(),
+                owner_type: OwnerType::AddressOwner,
+                owner_address: Some(SuiAddress::random_for_testing_only().to_string()),
+                initial_shared_version: None,
+                previous_transaction: TransactionDigest::random().to_string(),
+                object_type: "0x2::coin::Coin<0x2::sui::SUI>".to_string(),
+                object_status: ObjectStatus::Created,
+                has_public_transfer: false,
+                storage_rebate: 0,
+                bcs: vec![NamedBcsBytes("object".to_string(), vec![1u8, 2u8, 3u8])],
+                object_size: 3,
+            })
+            .collect::<Vec<_>>();
+
+        let insert_query = compose_object_bulk_insert_query(&bulk_data);
+        let result: Result<usize, IndexerError> = pg_pool_conn
+            .build_transaction()
+            .serializable()
+            .read_write()
+            .run(|conn| diesel::sql_query(insert_query).execute(conn))
+            .map_err(IndexerError::PostgresError);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), 10000);
+
+        let mutated_bulk_data = bulk_data
+            .clone()
+            .into_iter()
+            .map(|mut object| {
+                object.object_status = ObjectStatus::Mutated;
+                object.version = 1;
+                object
+            })
+            .collect::<Vec<_>>();
+        let mutated_bulk_data_same_checkpoint = bulk_data
+            .into_iter()
             .map(|mut object| {
                 object.object_status = ObjectStatus::Mutated;
                 object.version = 2;
@@ -1236,6 +2767,75 @@ pub mod pg_integration_test {
         Ok(())
     }
 
+    #[tokio::test]
+    #[timeout(60000)]
+    async fn test_indexer_client_with_api_key() -> Result<(), anyhow::Error> {
+        let pg_host = env::var("POSTGRES_HOST").unwrap_or_else(|_| "localhost".into());
+        let pg_port = env::var("POSTGRES_PORT").unwrap_or_else(|_| "32770".into());
+        let pw = env::var("POSTGRES_PASSWORD").unwrap_or_else(|_| "postgrespw".into());
+        let db_url = format!("postgres://postgres:{pw}@{pg_host}:{pg_port}");
+        let test_cluster = TestClusterBuilder::new().build().await;
+
+        // the keys are loaded when the RPC server starts, so add one before starting it
+        let pool = new_pg_connection_pool(&db_url).unwrap();
+        let mut conn = get_pg_pool_connection(&pool).unwrap();
+        reset_database(&mut conn, true).unwrap();
+        diesel::insert_into(api_keys::table)
+            .values(ApiKey {
+                key_hash: hash_api_key("client-test-key"),
+                name: "client-test".to_string(),
+                requests_per_second: None,
+                revoked: false,
+            })
+            .execute(&mut conn)
+            .unwrap();
+        let config = IndexerConfig {
+            db_url: Some(db_url),
+            rpc_client_url: test_cluster.rpc_url().to_string(),
+            migrated_methods: IndexerConfig::all_implemented_methods(),
+            rpc_require_api_key: true,
+            ..Default::default()
+        };
+        let rpc_url = format!(
+            "http://{}:{}",
+            config.rpc_server_url, config.rpc_server_port
+        );
+        let (store, handle) = start_test_indexer(config).await.unwrap();
+        wait_until_next_checkpoint(&store).await;
+
+        let client = IndexerClient::with_api_key(&rpc_url, "client-test-key")?;
+        let address = test_cluster.get_address_0();
+        let transactions = client
+            .query_transactions(
+                SuiTransactionBlockResponseQuery::new_with_filter(TransactionFilter::Checkpoint(0)),
+                None,
+                Some(10),
+                false,
+            )
+            .await?;
+        assert!(!transactions.data.is_empty());
+        let objects = client.get_owned_objects(address, None, None, None).await?;
+        assert!(!objects.data.is_empty());
+        client
+            .get_events(EventFilter::Sender(address), None, None, false)
+            .await?;
+
+        let anonymous = IndexerClient::new(&rpc_url)?;
+        assert!(anonymous
+            .get_owned_objects(address, None, None, None)
+            .await
+            .is_err());
+        let wrong_key = IndexerClient::with_api_key(&rpc_url, "wrong-key")?;
+        assert!(wrong_key
+            .get_owned_objects(address, None, None, None)
+            .await
+            .is_err());
+
+        drop(handle);
+        drop(test_cluster);
+        Ok(())
+    }
+
     async fn start_test_cluster(
         epoch_duration_ms: Option<u64>,
     ) -> (
@@ -1263,6 +2863,8 @@ pub mod pg_integration_test {
             rpc_client_url: test_cluster.rpc_url().to_string(),
             migrated_methods: IndexerConfig::all_implemented_methods(),
             reset_db: true,
+            // connects as the postgres superuser
+            allow_db_reader_writes: true,
             ..Default::default()
         };
 
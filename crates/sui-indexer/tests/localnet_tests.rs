@@ -0,0 +1,242 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// end-to-end test of the indexer against a local network, running a scripted set of
+// transactions and checking the indexed store, with a standalone postgresql database
+#[cfg(feature = "localnet_integration")]
+pub mod localnet_integration_test {
+    use std::env;
+    use std::time::{Duration, Instant};
+
+    use ntest::timeout;
+    use sui_indexer::store::{IndexerStore, PgIndexerStore};
+    use sui_indexer::test_utils::start_test_indexer;
+    use sui_indexer::IndexerConfig;
+    use sui_json_rpc::api::ReadApiClient;
+    use sui_json_rpc_types::{
+        ObjectChange, SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponse,
+    };
+    use sui_test_transaction_builder::publish_nfts_package;
+    use sui_types::base_types::{ObjectID, ObjectRef, SuiAddress};
+    use sui_types::object::{ObjectRead, Owner};
+    use sui_types::transaction::{CallArg, ObjectArg};
+    use test_cluster::{TestCluster, TestClusterBuilder};
+
+    const CATCH_UP_TIME_LIMIT: Duration = Duration::from_secs(120);
+
+    #[tokio::test]
+    #[timeout(300000)]
+    async fn test_localnet_scripted_transactions() -> Result<(), anyhow::Error> {
+        let (test_cluster, store) = start_localnet().await?;
+        let sender = test_cluster.get_address_0();
+        let recipient = test_cluster.get_address_1();
+
+        // publish
+        let (package_id, _, publish_digest) = publish_nfts_package(&test_cluster.wallet).await;
+
+        // dynamic fields, the farm of a player is a dynamic object field of the player
+        let response = move_call(
+            &test_cluster,
+            package_id,
+            "create_player",
+            vec![CallArg::Pure(bcs::to_bytes(&b"player".to_vec())?)],
+        )
+        .await;
+        let player = created_object(&response, "::geniteam::Player");
+        let response = move_call(
+            &test_cluster,
+            package_id,
+            "create_farm",
+            vec![
+                CallArg::Object(ObjectArg::ImmOrOwnedObject(player)),
+                CallArg::Pure(bcs::to_bytes(&0u64)?),
+                CallArg::Pure(bcs::to_bytes(&b"farm".to_vec())?),
+                CallArg::Pure(bcs::to_bytes(&10u64)?),
+            ],
+        )
+        .await;
+        let field = created_object(&response, "::dynamic_field::Field<");
+        let player = mutated_object(&response, player.0);
+
+        // transfer
+        let transfer_data = test_cluster
+            .test_transaction_builder()
+            .await
+            .transfer(player, recipient)
+            .build();
+        let transfer = test_cluster
+            .sign_and_execute_transaction(&transfer_data)
+            .await;
+
+        // epoch change
+        test_cluster.trigger_reconfiguration().await;
+
+        let latest_checkpoint = *test_cluster
+            .rpc_client()
+            .get_latest_checkpoint_sequence_number()
+            .await?;
+        wait_until_indexed(&store, latest_checkpoint as i64).await;
+
+        // checkpoints are indexed without gaps, with the full node's transaction counts
+        let checkpoints = store
+            .get_indexer_checkpoints(-1, latest_checkpoint as usize + 1)
+            .await?;
+        assert_eq!(checkpoints.len() as u64, latest_checkpoint + 1);
+        for (sequence_number, checkpoint) in checkpoints.iter().enumerate() {
+            assert_eq!(checkpoint.sequence_number, sequence_number as i64);
+            let fullnode_checkpoint = test_cluster
+                .rpc_client()
+                .get_checkpoint((sequence_number as u64).into())
+                .await?;
+            assert_eq!(
+                checkpoint.network_total_transactions as u64,
+                fullnode_checkpoint.network_total_transactions
+            );
+        }
+
+        for digest in [publish_digest, response.digest, transfer.digest] {
+            let transaction = store
+                .get_transaction_by_digest(&digest.base58_encode())
+                .await?;
+            assert!(transaction.checkpoint_sequence_number.is_some());
+        }
+        let sender_transactions = store
+            .get_transaction_page_by_sender_address(sender.to_string(), None, 100, false)
+            .await?;
+        assert!(sender_transactions
+            .iter()
+            .any(|transaction| transaction.transaction_digest == transfer.digest.base58_encode()));
+
+        match store.get_object(package_id, None).await? {
+            ObjectRead::Exists(_, object, _) => assert!(object.is_package()),
+            read => panic!("Published package {package_id} is not indexed: {read:?}"),
+        }
+        assert_eq!(
+            object_owner(&store, field.0).await?,
+            Owner::ObjectOwner(SuiAddress::from(player.0))
+        );
+        assert_eq!(
+            object_owner(&store, player.0).await?,
+            Owner::AddressOwner(recipient)
+        );
+
+        let current_epoch = store.get_current_epoch().await?;
+        assert_eq!(current_epoch.epoch, 1);
+        let epochs = store.get_epochs(None, 10, None).await?;
+        let first_epoch = epochs.iter().find(|epoch| epoch.epoch == 0).unwrap();
+        assert!(first_epoch.end_of_epoch_info.is_some());
+        // the player is live at the end of the first epoch
+        let object_type_stats = store.get_object_type_stats(0).await?;
+        assert!(object_type_stats
+            .iter()
+            .any(|stat| stat.package == package_id.to_string()
+                && stat.module == "geniteam"
+                && stat.name == "Player"
+                && stat.object_count == 1));
+        Ok(())
+    }
+
+    async fn start_localnet() -> Result<(TestCluster, PgIndexerStore), anyhow::Error> {
+        let pg_host = env::var("POSTGRES_HOST").unwrap_or_else(|_| "localhost".into());
+        let pg_port = env::var("POSTGRES_PORT").unwrap_or_else(|_| "32770".into());
+        let pw = env::var("POSTGRES_PASSWORD").unwrap_or_else(|_| "postgrespw".into());
+        let db_url = format!("postgres://postgres:{pw}@{pg_host}:{pg_port}");
+
+        let test_cluster = TestClusterBuilder::new().build().await;
+        let config = IndexerConfig {
+            db_url: Some(db_url),
+            rpc_client_url: test_cluster.rpc_url().to_string(),
+            migrated_methods: IndexerConfig::all_implemented_methods(),
+            reset_db: true,
+            // connects as the postgres superuser
+            allow_db_reader_writes: true,
+            ..Default::default()
+        };
+        let (store, _handle) = start_test_indexer(config).await?;
+        Ok((test_cluster, store))
+    }
+
+    async fn move_call(
+        test_cluster: &TestCluster,
+        package_id: ObjectID,
+        function: &'static str,
+        args: Vec<CallArg>,
+    ) -> SuiTransactionBlockResponse {
+        let data = test_cluster
+            .test_transaction_builder()
+            .await
+            .move_call(package_id, "geniteam", function, args)
+            .build();
+        test_cluster.sign_and_execute_transaction(&data).await
+    }
+
+    fn created_object(response: &SuiTransactionBlockResponse, type_pattern: &str) -> ObjectRef {
+        response
+            .object_changes
+            .as_ref()
+            .unwrap()
+            .iter()
+            .find_map(|change| match change {
+                ObjectChange::Created {
+                    object_type,
+                    object_id,
+                    version,
+                    digest,
+                    ..
+                } if object_type.to_string().contains(type_pattern) => {
+                    Some((*object_id, *version, *digest))
+                }
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("No object of type {type_pattern} created"))
+    }
+
+    fn mutated_object(response: &SuiTransactionBlockResponse, object_id: ObjectID) -> ObjectRef {
+        response
+            .effects
+            .as_ref()
+            .unwrap()
+            .mutated()
+            .iter()
+            .map(|mutated| mutated.reference.to_object_ref())
+            .find(|object_ref| object_ref.0 == object_id)
+            .unwrap_or_else(|| panic!("Object {object_id} not mutated"))
+    }
+
+    async fn object_owner(
+        store: &PgIndexerStore,
+        object_id: ObjectID,
+    ) -> Result<Owner, anyhow::Error> {
+        match store.get_object(object_id, None).await? {
+            ObjectRead::Exists(_, object, _) => Ok(object.owner),
+            read => Err(anyhow::anyhow!(
+                "Object {object_id} is not indexed: {read:?}"
+            )),
+        }
+    }
+
+    // Both the transaction and the object pipelines reach `checkpoint`.
+    async fn wait_until_indexed(store: &PgIndexerStore, checkpoint: i64) {
+        let since = Instant::now();
+        loop {
+            let tx_checkpoint = store
+                .get_latest_tx_checkpoint_sequence_number()
+                .await
+                .unwrap_or(-1);
+            let object_checkpoint = store
+                .get_latest_object_checkpoint_sequence_number()
+                .await
+                .unwrap_or(-1);
+            if tx_checkpoint >= checkpoint && object_checkpoint >= checkpoint {
+                return;
+            }
+            if since.elapsed() > CATCH_UP_TIME_LIMIT {
+                panic!(
+                    "Indexer did not reach checkpoint {checkpoint}, transactions at \
+                    {tx_checkpoint}, objects at {object_checkpoint}"
+                );
+            }
+            tokio::time::sleep(Duration::from_millis(200)).await;
+        }
+    }
+}
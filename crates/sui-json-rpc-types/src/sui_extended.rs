@@ -8,14 +8,21 @@ use serde::Serialize;
 use serde_with::serde_as;
 use serde_with::DisplayFromStr;
 
-use sui_types::base_types::{EpochId, ObjectID};
+use sui_types::base_types::{EpochId, ObjectID, SuiAddress};
+use sui_types::digests::TransactionDigest;
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::sui_serde::BigInt;
 use sui_types::sui_system_state::sui_system_state_summary::SuiValidatorSummary;
 
-use crate::Page;
+use crate::{Page, SuiEvent};
 
 pub type EpochPage = Page<EpochInfo, BigInt<u64>>;
+pub type AbortedTransactionBlockPage = Page<AbortedTransactionBlock, String>;
+pub type SequencedEventPage = Page<SuiEvent, EventSequence>;
+pub type WatchlistPage = Page<WatchlistEntry, String>;
+pub type EpochGasSpendPage = Page<EpochGasSpend, String>;
+pub type ValidatorParticipationPage = Page<EpochValidatorParticipation, String>;
+pub type OwnershipHistoryPage = Page<ObjectOwnershipTransition, String>;
 
 #[serde_as]
 #[derive(Serialize, Deserialize, Debug, JsonSchema)]
@@ -157,3 +164,136 @@ pub struct AddressMetrics {
     pub cumulative_active_addresses: u64,
     pub daily_active_addresses: u64,
 }
+
+#[serde_as]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AbortedTransactionBlock {
+    pub digest: TransactionDigest,
+    pub sender: SuiAddress,
+    #[schemars(with = "Option<BigInt<u64>>")]
+    #[serde_as(as = "Option<BigInt<u64>>")]
+    pub checkpoint: Option<CheckpointSequenceNumber>,
+    #[schemars(with = "Option<BigInt<u64>>")]
+    #[serde_as(as = "Option<BigInt<u64>>")]
+    pub timestamp_ms: Option<u64>,
+    /// index of the command of the transaction that aborted
+    #[schemars(with = "Option<BigInt<u64>>")]
+    #[serde_as(as = "Option<BigInt<u64>>")]
+    pub command: Option<u64>,
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub abort_code: u64,
+    /// name of the error constant of the abort code, if the package has a verified source
+    /// declaring it
+    pub abort_constant: Option<String>,
+}
+
+/// Position of an event in the order of execution of the network: the sequence number of its
+/// transaction, then its index in the events of the transaction.
+#[serde_as]
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, JsonSchema,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct EventSequence {
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub tx_sequence_number: u64,
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub event_sequence: u64,
+}
+
+/// An address, matched by transactions it sends or receives objects from, or an object,
+/// matched by transactions taking it as input or changing it.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, JsonSchema)]
+#[serde(tag = "kind", content = "id", rename_all = "camelCase")]
+pub enum WatchedEntity {
+    Address(SuiAddress),
+    Object(ObjectID),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchlistEntry {
+    pub entity: WatchedEntity,
+    /// name of the rule the entity is watched by, reported with the matches
+    pub rule: String,
+}
+
+/// A non-coin object passed to an address by a transaction.
+#[serde_as]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ObjectOwnershipTransition {
+    /// version of the object written by the transaction
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub object_version: u64,
+    /// None if the object was not owned by an address before the transaction
+    pub from_owner: Option<SuiAddress>,
+    pub to_owner: SuiAddress,
+    pub transaction_digest: TransactionDigest,
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub checkpoint: CheckpointSequenceNumber,
+}
+
+/// An object or package of a leaderboard, with the transactions touching the object or the
+/// Move calls to the package within the window of the leaderboard.
+#[serde_as]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct HotEntity {
+    pub id: ObjectID,
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub count: u64,
+}
+
+/// Gas spent by a sponsor on the transactions of other senders within an epoch.
+#[serde_as]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EpochGasSpend {
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub epoch: EpochId,
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub transaction_count: u64,
+    /// computation and storage costs net of storage rebates
+    #[schemars(with = "BigInt<i64>")]
+    #[serde_as(as = "BigInt<i64>")]
+    pub total_gas_cost: i64,
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub computation_cost: u64,
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub storage_cost: u64,
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub storage_rebate: u64,
+}
+
+/// Checkpoint signature participation of a validator of the committee of an epoch, out of
+/// the checkpoints of the epoch committed so far.
+#[serde_as]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EpochValidatorParticipation {
+    /// index of the validator in the committee of the epoch
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub committee_index: u64,
+    pub sui_address: SuiAddress,
+    pub name: String,
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub checkpoints_signed: u64,
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub epoch_checkpoints: u64,
+}
@@ -5,11 +5,17 @@ use jsonrpsee::core::RpcResult;
 use jsonrpsee::proc_macros::rpc;
 
 use sui_json_rpc_types::{
-    AddressMetrics, CheckpointedObjectID, EpochInfo, EpochPage, MoveCallMetrics, NetworkMetrics,
-    QueryObjectsPage, SuiObjectResponseQuery,
+    AbortedTransactionBlockPage, AddressMetrics, CheckpointedObjectID, EpochGasSpendPage,
+    EpochInfo, EpochPage, EventFilter, EventSequence, HotEntity, MoveCallMetrics, NetworkMetrics,
+    OwnershipHistoryPage, QueryObjectsPage, SequencedEventPage, SuiMoveNormalizedStruct,
+    SuiObjectResponseQuery, SuiTransactionBlockResponseOptions, TransactionBlocksPage,
+    ValidatorParticipationPage, WatchlistPage,
 };
 use sui_open_rpc_macros::open_rpc;
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::digests::TransactionDigest;
 use sui_types::sui_serde::BigInt;
+use sui_types::sui_system_state::sui_system_state_summary::SuiSystemStateSummary;
 
 #[open_rpc(namespace = "suix", tag = "Extended API")]
 #[rpc(server, client, namespace = "suix")]
@@ -30,6 +36,14 @@ pub trait ExtendedApi {
     #[method(name = "getCurrentEpoch")]
     async fn get_current_epoch(&self) -> RpcResult<EpochInfo>;
 
+    /// Return the system state summary at the start of an epoch, including its validators
+    #[method(name = "getSystemStateAtEpoch")]
+    async fn get_system_state_at_epoch(
+        &self,
+        /// the epoch to query
+        epoch: BigInt<u64>,
+    ) -> RpcResult<SuiSystemStateSummary>;
+
     /// Return the list of queried objects. Note that this is an enhanced full node only api.
     #[method(name = "queryObjects")]
     async fn query_objects(
@@ -42,6 +56,178 @@ pub trait ExtendedApi {
         limit: Option<usize>,
     ) -> RpcResult<QueryObjectsPage>;
 
+    /// Return list of events for a specified query criteria, paginated by their position in the order of execution of the network rather than by event ID
+    #[method(name = "queryEventsBySequence")]
+    async fn query_events_by_sequence(
+        &self,
+        /// the event query criteria.
+        query: EventFilter,
+        /// optional paging cursor, exclusive
+        cursor: Option<EventSequence>,
+        /// maximum number of items per page
+        limit: Option<usize>,
+        /// flag to return results in descending order
+        descending_order: Option<bool>,
+    ) -> RpcResult<SequencedEventPage>;
+
+    /// Return the layout of the fields of events of the given type, i.e. of their `parsedJson`
+    #[method(name = "getEventSchema")]
+    async fn get_event_schema(
+        &self,
+        /// the event type, e.g. `0x3::validator::StakingRequestEvent`; type arguments are ignored
+        event_type: String,
+    ) -> RpcResult<SuiMoveNormalizedStruct>;
+
+    /// Return the Move layout of a struct type, with its field names and types, to decode the BCS of its objects and events
+    #[method(name = "getTypeLayout")]
+    async fn get_type_layout(
+        &self,
+        /// the struct type with all its type arguments, e.g. `0x2::coin::Coin<0x2::sui::SUI>`
+        struct_type: String,
+    ) -> RpcResult<serde_json::Value>;
+
+    /// Return transactions that aborted in the given Move module, with the names of the error constants of their abort codes if the package has a verified source
+    #[method(name = "getAbortedTransactions")]
+    async fn get_aborted_transactions(
+        &self,
+        /// the package of the module
+        package: ObjectID,
+        /// the module name
+        module: String,
+        /// only return transactions that aborted with this code
+        abort_code: Option<BigInt<u64>>,
+        /// optional opaque paging cursor, the nextCursor of the previous page
+        cursor: Option<String>,
+        /// maximum number of items per page
+        limit: Option<usize>,
+        /// flag to return results in descending order
+        descending_order: Option<bool>,
+    ) -> RpcResult<AbortedTransactionBlockPage>;
+
+    /// Return the addresses and objects watched for transactions touching them, ordered by kind, id and rule
+    #[method(name = "getWatchlist")]
+    async fn get_watchlist(
+        &self,
+        /// optional opaque paging cursor, the nextCursor of the previous page
+        cursor: Option<String>,
+        /// maximum number of items per page
+        limit: Option<usize>,
+    ) -> RpcResult<WatchlistPage>;
+
+    /// Return the addresses a non-coin object, e.g. an NFT, was passed to, ordered by the version of the object
+    #[method(name = "getOwnershipHistory")]
+    async fn get_ownership_history(
+        &self,
+        /// the ID of the object
+        object_id: ObjectID,
+        /// optional opaque paging cursor, the nextCursor of the previous page
+        cursor: Option<String>,
+        /// maximum number of items per page
+        limit: Option<usize>,
+        /// flag to return results in descending order
+        descending_order: Option<bool>,
+    ) -> RpcResult<OwnershipHistoryPage>;
+
+    /// Return the objects touched by the most transactions within a rolling window ending at the latest checkpoint, most touched first
+    #[method(name = "getHotObjects")]
+    async fn get_hot_objects(
+        &self,
+        /// the window, either `1h` or `24h`
+        window: String,
+        /// maximum number of objects returned
+        limit: Option<usize>,
+    ) -> RpcResult<Vec<HotEntity>>;
+
+    /// Return the packages with the most Move calls within a rolling window ending at the latest checkpoint, most called first
+    #[method(name = "getHotPackages")]
+    async fn get_hot_packages(
+        &self,
+        /// the window, either `1h` or `24h`
+        window: String,
+        /// maximum number of packages returned
+        limit: Option<usize>,
+    ) -> RpcResult<Vec<HotEntity>>;
+
+    /// Return transactions with the given object among their gas payment objects, e.g. the coins of a gas station
+    #[method(name = "getTransactionBlocksByGasObject")]
+    async fn get_transaction_blocks_by_gas_object(
+        &self,
+        /// the ID of the gas object
+        object_id: ObjectID,
+        /// options for specifying the content to be returned
+        options: Option<SuiTransactionBlockResponseOptions>,
+        /// optional paging cursor, exclusive
+        cursor: Option<TransactionDigest>,
+        /// maximum number of items per page
+        limit: Option<usize>,
+        /// flag to return results in descending order
+        descending_order: Option<bool>,
+    ) -> RpcResult<TransactionBlocksPage>;
+
+    /// Return transactions whose gas was paid by the given address, whether it sent them or sponsored them
+    #[method(name = "getTransactionBlocksByGasOwner")]
+    async fn get_transaction_blocks_by_gas_owner(
+        &self,
+        /// the owner of the gas payment objects
+        gas_owner: SuiAddress,
+        /// only return transactions of other senders, default to false
+        sponsored_only: Option<bool>,
+        /// options for specifying the content to be returned
+        options: Option<SuiTransactionBlockResponseOptions>,
+        /// optional paging cursor, exclusive
+        cursor: Option<TransactionDigest>,
+        /// maximum number of items per page
+        limit: Option<usize>,
+        /// flag to return results in descending order
+        descending_order: Option<bool>,
+    ) -> RpcResult<TransactionBlocksPage>;
+
+    /// Return transactions of other senders whose gas was paid by the given sponsor, within a time range
+    #[method(name = "getSponsoredTransactionBlocks")]
+    async fn get_sponsored_transaction_blocks(
+        &self,
+        /// the sponsor paying the gas
+        sponsor: SuiAddress,
+        /// start of the time range in milliseconds, inclusive
+        start_ms: BigInt<u64>,
+        /// end of the time range in milliseconds, exclusive
+        end_ms: BigInt<u64>,
+        /// options for specifying the content to be returned
+        options: Option<SuiTransactionBlockResponseOptions>,
+        /// optional paging cursor, exclusive
+        cursor: Option<TransactionDigest>,
+        /// maximum number of items per page
+        limit: Option<usize>,
+        /// flag to return results in descending order
+        descending_order: Option<bool>,
+    ) -> RpcResult<TransactionBlocksPage>;
+
+    /// Return the gas spent by a sponsor on the transactions of other senders per epoch
+    #[method(name = "getSponsorGasSpend")]
+    async fn get_sponsor_gas_spend(
+        &self,
+        /// the sponsor paying the gas
+        sponsor: SuiAddress,
+        /// optional opaque paging cursor, the nextCursor of the previous page
+        cursor: Option<String>,
+        /// maximum number of items per page
+        limit: Option<usize>,
+        /// flag to return results in descending order
+        descending_order: Option<bool>,
+    ) -> RpcResult<EpochGasSpendPage>;
+
+    /// Return the number of checkpoints of an epoch signed by each validator of its committee, in committee order
+    #[method(name = "getValidatorParticipation")]
+    async fn get_validator_participation(
+        &self,
+        /// the epoch to query
+        epoch: BigInt<u64>,
+        /// optional opaque paging cursor, the nextCursor of the previous page
+        cursor: Option<String>,
+        /// maximum number of items per page
+        limit: Option<usize>,
+    ) -> RpcResult<ValidatorParticipationPage>;
+
     /// Return Network metrics
     #[method(name = "getNetworkMetrics")]
     async fn get_network_metrics(&self) -> RpcResult<NetworkMetrics>;
@@ -63,4 +249,14 @@ pub trait ExtendedApi {
 
     #[method(name = "getTotalTransactions")]
     async fn get_total_transactions(&self) -> RpcResult<BigInt<u64>>;
+
+    /// Wait until the given checkpoint is indexed with all its data, i.e. its transactions, events, transaction indices and object changes, so that the reads that follow observe it. Fails if it is not indexed before the timeout
+    #[method(name = "waitForCheckpoint")]
+    async fn wait_for_checkpoint(
+        &self,
+        /// the checkpoint sequence number
+        sequence_number: BigInt<u64>,
+        /// how long to wait in milliseconds, default to 10 seconds and at most 30 seconds
+        timeout_ms: Option<BigInt<u64>>,
+    ) -> RpcResult<()>;
 }
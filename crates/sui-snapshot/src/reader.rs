@@ -17,6 +17,7 @@ use object_store::DynObjectStore;
 use std::collections::{BTreeMap, HashSet};
 use std::fs;
 use std::fs::File;
+use std::future::Future;
 use std::io::{BufReader, Read, Seek, SeekFrom};
 use std::num::NonZeroUsize;
 use std::path::PathBuf;
@@ -24,6 +25,7 @@ use std::sync::Arc;
 use sui_core::authority::authority_store_tables::{AuthorityPerpetualTables, LiveObject};
 use sui_core::authority::AuthorityStore;
 use sui_storage::blob::{Blob, BlobEncoding};
+use sui_storage::compute_sha3_checksum_for_bytes;
 use sui_storage::object_store::util::{copy_file, copy_files, path_to_filesystem};
 use sui_storage::object_store::ObjectStoreConfig;
 use sui_types::base_types::{ObjectDigest, ObjectID, ObjectRef, SequenceNumber};
@@ -241,6 +243,46 @@ impl StateSnapshotReaderV1 {
         .await?
     }
 
+    /// Downloads the object files of the snapshot and calls `consumer` with the live objects
+    /// of each file in turn, for consumers building their own state from the snapshot rather
+    /// than a perpetual store. Files are checked against the digests of the MANIFEST.
+    pub async fn read_live_objects<F, Fut>(&self, mut consumer: F) -> Result<()>
+    where
+        F: FnMut(Vec<LiveObject>) -> Fut,
+        Fut: Future<Output = Result<()>>,
+    {
+        let epoch_dir = self.epoch_dir();
+        let remote_object_store = self.remote_object_store.clone();
+        let files: Vec<FileMetadata> = self
+            .object_files
+            .values()
+            .flat_map(|parts| parts.values().cloned())
+            .collect();
+        let mut downloads = futures::stream::iter(files)
+            .map(|file_metadata| {
+                let file_path = file_metadata.file_path(&epoch_dir);
+                let remote_object_store = remote_object_store.clone();
+                async move {
+                    let bytes = remote_object_store
+                        .get(&file_path)
+                        .await
+                        .map_err(|e| anyhow!("Failed to download file: {e}"))?
+                        .bytes()
+                        .await?;
+                    if compute_sha3_checksum_for_bytes(bytes.clone())? != file_metadata.sha3_digest
+                    {
+                        return Err(anyhow!("Checksum of file {file_path} doesn't match"));
+                    }
+                    Ok::<(Bytes, FileMetadata), anyhow::Error>((bytes, file_metadata))
+                }
+            })
+            .buffered(self.concurrency);
+        while let Some((bytes, file_metadata)) = downloads.try_next().await? {
+            consumer(LiveObjectIter::new(&file_metadata, bytes)?.collect()).await?;
+        }
+        Ok(())
+    }
+
     pub fn ref_iter(&mut self, bucket_num: u32) -> Result<ObjectRefIter> {
         let file_metadata = self
             .ref_files